@@ -10,6 +10,10 @@ pub enum PlatformMode {
     Discord,
     /// 同时运行 Telegram + Discord
     Both,
+    /// 仅 Slack（Events API）
+    Slack,
+    /// 同时运行 Telegram + Discord + Slack
+    All,
 }
 
 impl PlatformMode {
@@ -17,16 +21,22 @@ impl PlatformMode {
         match s.to_lowercase().as_str() {
             "discord" => PlatformMode::Discord,
             "both" => PlatformMode::Both,
+            "slack" => PlatformMode::Slack,
+            "all" => PlatformMode::All,
             _ => PlatformMode::Telegram,
         }
     }
 
     pub fn needs_telegram(&self) -> bool {
-        matches!(self, PlatformMode::Telegram | PlatformMode::Both)
+        matches!(self, PlatformMode::Telegram | PlatformMode::Both | PlatformMode::All)
     }
 
     pub fn needs_discord(&self) -> bool {
-        matches!(self, PlatformMode::Discord | PlatformMode::Both)
+        matches!(self, PlatformMode::Discord | PlatformMode::Both | PlatformMode::All)
+    }
+
+    pub fn needs_slack(&self) -> bool {
+        matches!(self, PlatformMode::Slack | PlatformMode::All)
     }
 }
 
@@ -49,6 +59,23 @@ impl DiscordConfig {
     }
 }
 
+/// Slack 专用配置
+#[derive(Debug, Clone, Default)]
+pub struct SlackConfig {
+    /// Slack Bot User OAuth Token (xoxb-...)
+    pub bot_token: String,
+    /// Slack App Signing Secret（验证 Events API 请求）
+    pub signing_secret: String,
+    /// bot_id_hash = SHA256(slack_bot_token)
+    pub bot_id_hash: [u8; 32],
+}
+
+impl SlackConfig {
+    pub fn bot_id_hash_hex(&self) -> String {
+        hex::encode(self.bot_id_hash)
+    }
+}
+
 /// Agent 全局配置（从环境变量加载）
 #[derive(Clone, Deserialize)]
 pub struct AgentConfig {
@@ -83,6 +110,10 @@ pub struct AgentConfig {
     #[serde(default = "default_multicast_timeout_ms")]
     pub multicast_timeout_ms: u64,
 
+    /// 警告记录过期天数（最后一次变更起算，0 = 永不过期）
+    #[serde(default = "default_warn_ttl_days")]
+    pub warn_ttl_days: u64,
+
     /// /v1/execute 端点的 Bearer Token 认证（可选，设置后强制验证）
     #[serde(default)]
     pub execute_token: Option<String>,
@@ -94,6 +125,10 @@ pub struct AgentConfig {
     /// Discord 专用配置（PLATFORM=discord 或 both 时加载）
     #[serde(skip)]
     pub discord: Option<DiscordConfig>,
+
+    /// Slack 专用配置（PLATFORM=slack 或 all 时加载）
+    #[serde(skip)]
+    pub slack: Option<SlackConfig>,
 }
 
 fn default_webhook_port() -> u16 {
@@ -116,6 +151,10 @@ fn default_multicast_timeout_ms() -> u64 {
     3000
 }
 
+fn default_warn_ttl_days() -> u64 {
+    30
+}
+
 impl std::fmt::Debug for AgentConfig {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("AgentConfig")
@@ -127,9 +166,11 @@ impl std::fmt::Debug for AgentConfig {
             .field("chain_rpc", &self.chain_rpc)
             .field("data_dir", &self.data_dir)
             .field("multicast_timeout_ms", &self.multicast_timeout_ms)
+            .field("warn_ttl_days", &self.warn_ttl_days)
             .field("execute_token", &self.execute_token.as_ref().map(|_| "[REDACTED]"))
             .field("bot_id_hash", &hex::encode(self.bot_id_hash))
             .field("discord", &self.discord.as_ref().map(|_| "[configured]"))
+            .field("slack", &self.slack.as_ref().map(|_| "[configured]"))
             .finish()
     }
 }
@@ -177,6 +218,11 @@ impl AgentConfig {
             .parse::<u64>()
             .unwrap_or(3000);
 
+        let warn_ttl_days = std::env::var("WARN_TTL_DAYS")
+            .ok()
+            .and_then(|s| s.parse::<u64>().ok())
+            .unwrap_or_else(default_warn_ttl_days);
+
         let execute_token = std::env::var("EXECUTE_TOKEN").ok()
             .filter(|s| !s.is_empty());
 
@@ -224,6 +270,28 @@ impl AgentConfig {
             None
         };
 
+        // ── Slack 配置（PLATFORM=slack 或 all 时加载）──
+        let slack = if platform.needs_slack() {
+            let sl_token = std::env::var("SLACK_BOT_TOKEN")
+                .map_err(|_| anyhow::anyhow!("SLACK_BOT_TOKEN 环境变量未设置（PLATFORM={:?} 时必填）", platform))?;
+            let signing_secret = std::env::var("SLACK_SIGNING_SECRET")
+                .map_err(|_| anyhow::anyhow!("SLACK_SIGNING_SECRET 环境变量未设置（PLATFORM={:?} 时必填）", platform))?;
+
+            let mut hasher = Sha256::new();
+            hasher.update(sl_token.as_bytes());
+            let hash = hasher.finalize();
+            let mut sl_hash = [0u8; 32];
+            sl_hash.copy_from_slice(&hash);
+
+            Some(SlackConfig {
+                bot_token: sl_token,
+                signing_secret,
+                bot_id_hash: sl_hash,
+            })
+        } else {
+            None
+        };
+
         Ok(Self {
             platform,
             bot_token,
@@ -233,9 +301,11 @@ impl AgentConfig {
             chain_rpc,
             data_dir,
             multicast_timeout_ms,
+            warn_ttl_days,
             execute_token,
             bot_id_hash,
             discord,
+            slack,
         })
     }
 
@@ -392,6 +462,8 @@ mod tests {
         assert_eq!(PlatformMode::from_str("Discord"), PlatformMode::Discord);
         assert_eq!(PlatformMode::from_str("both"), PlatformMode::Both);
         assert_eq!(PlatformMode::from_str("BOTH"), PlatformMode::Both);
+        assert_eq!(PlatformMode::from_str("slack"), PlatformMode::Slack);
+        assert_eq!(PlatformMode::from_str("ALL"), PlatformMode::All);
         assert_eq!(PlatformMode::from_str("unknown"), PlatformMode::Telegram);
     }
 
@@ -403,6 +475,13 @@ mod tests {
         assert!(PlatformMode::Discord.needs_discord());
         assert!(PlatformMode::Both.needs_telegram());
         assert!(PlatformMode::Both.needs_discord());
+        assert!(!PlatformMode::Both.needs_slack());
+        assert!(PlatformMode::Slack.needs_slack());
+        assert!(!PlatformMode::Slack.needs_telegram());
+        assert!(!PlatformMode::Slack.needs_discord());
+        assert!(PlatformMode::All.needs_telegram());
+        assert!(PlatformMode::All.needs_discord());
+        assert!(PlatformMode::All.needs_slack());
     }
 
     #[test]
@@ -473,4 +552,42 @@ mod tests {
             },
         );
     }
+
+    #[test]
+    fn test_slack_mode_requires_signing_secret() {
+        with_env(
+            &[("PLATFORM", "slack"), ("SLACK_BOT_TOKEN", "xoxb-test")],
+            &["SLACK_SIGNING_SECRET", "BOT_TOKEN", "WEBHOOK_URL"],
+            || {
+                let result = AgentConfig::from_env();
+                assert!(result.is_err());
+                assert!(result.unwrap_err().to_string().contains("SLACK_SIGNING_SECRET"));
+            },
+        );
+    }
+
+    #[test]
+    fn test_slack_mode_loads_config() {
+        with_env(
+            &[
+                ("PLATFORM", "slack"),
+                ("SLACK_BOT_TOKEN", "xoxb-secret"),
+                ("SLACK_SIGNING_SECRET", "sign_secret"),
+            ],
+            &["BOT_TOKEN", "WEBHOOK_URL", "DISCORD_BOT_TOKEN"],
+            || {
+                let config = AgentConfig::from_env().unwrap();
+                assert_eq!(config.platform, PlatformMode::Slack);
+                assert!(config.discord.is_none());
+                let sl = config.slack.as_ref().unwrap();
+                assert_eq!(sl.bot_token, "xoxb-secret");
+                assert_eq!(sl.signing_secret, "sign_secret");
+                assert_eq!(sl.bot_id_hash_hex().len(), 64);
+
+                let debug_str = format!("{:?}", config);
+                assert!(!debug_str.contains("xoxb-secret"));
+                assert!(!debug_str.contains("sign_secret"));
+            },
+        );
+    }
 }
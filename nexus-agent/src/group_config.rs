@@ -157,11 +157,148 @@ pub struct GroupConfig {
     pub spam_max_emoji: u8,
     #[serde(default)]
     pub spam_first_messages_only: u8,
+    /// 自定义规则（空时不参与序列化，与 Node 签名验证保持一致）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub custom_rules: Vec<CustomRule>,
 }
 
 fn default_antiflood_window() -> u16 { 10 }
 fn default_warn_limit() -> u8 { 3 }
 
+// ═══════════════════════════════════════════════════════════════
+// 自定义规则 DSL（与 nexus-node/src/types.rs 保持一致，Node 端编译执行）
+// ═══════════════════════════════════════════════════════════════
+
+/// 自定义规则
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomRule {
+    pub id: String,
+    #[serde(default = "default_custom_rule_priority")]
+    pub priority: i32,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    pub when: RuleCondition,
+    pub then: CustomRuleAction,
+}
+
+fn default_custom_rule_priority() -> i32 { 100 }
+fn default_true() -> bool { true }
+
+/// 规则条件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum RuleCondition {
+    All(Vec<RuleCondition>),
+    Any(Vec<RuleCondition>),
+    Not(Box<RuleCondition>),
+    TextRegex(String),
+    SenderRole(Vec<SenderRole>),
+    MessageType(Vec<MessageKind>),
+    TimeWindow { start_hour: u8, end_hour: u8 },
+    Counter { counter: CounterKind, op: CompareOp, value: u32 },
+}
+
+/// 发送者角色
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SenderRole {
+    Admin,
+    Whitelisted,
+    Member,
+}
+
+/// 消息类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MessageKind {
+    Text,
+    Command,
+    Callback,
+    JoinRequest,
+    Audio,
+    Video,
+    Photo,
+    Document,
+    Sticker,
+    Gif,
+    Forward,
+    Voice,
+    Contact,
+    Location,
+    Poll,
+    Game,
+    Inline,
+}
+
+/// 计数项
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CounterKind {
+    TextLength,
+    LineCount,
+    LinkCount,
+    MentionCount,
+    EmojiCount,
+}
+
+/// 比较运算符
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CompareOp {
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+    Eq,
+}
+
+/// 自定义规则动作
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomRuleAction {
+    pub action_type: crate::executor::ActionType,
+    #[serde(default)]
+    pub params: serde_json::Value,
+}
+
+/// 预检自定义规则（Node 端做完整校验，此处提前拒绝明显错误，避免签名后被全网拒绝）
+fn validate_custom_rules(rules: &[CustomRule]) -> Result<(), String> {
+    if rules.len() > 32 {
+        return Err(format!("Too many custom rules: {} > 32", rules.len()));
+    }
+    let mut ids = std::collections::HashSet::new();
+    for rule in rules {
+        if rule.id.is_empty() || rule.id.len() > 64 {
+            return Err("Custom rule id must be 1-64 bytes".into());
+        }
+        if !ids.insert(rule.id.as_str()) {
+            return Err(format!("Duplicate custom rule id: {}", rule.id));
+        }
+        if matches!(rule.then.action_type, crate::executor::ActionType::ConfigUpdate(_)) {
+            return Err(format!("Rule {}: ConfigUpdate actions are not allowed", rule.id));
+        }
+        validate_condition(&rule.when)
+            .map_err(|e| format!("Rule {}: {}", rule.id, e))?;
+    }
+    Ok(())
+}
+
+fn validate_condition(cond: &RuleCondition) -> Result<(), String> {
+    match cond {
+        RuleCondition::All(list) | RuleCondition::Any(list) => {
+            if list.is_empty() {
+                return Err("All/Any requires at least one condition".into());
+            }
+            list.iter().try_for_each(validate_condition)
+        }
+        RuleCondition::Not(inner) => validate_condition(inner),
+        RuleCondition::TextRegex(pattern) => regex::Regex::new(pattern)
+            .map(|_| ())
+            .map_err(|e| format!("Invalid regex: {}", e)),
+        RuleCondition::TimeWindow { start_hour, end_hour } => {
+            if *start_hour > 23 || *end_hour > 23 || start_hour == end_hour {
+                return Err("TimeWindow hours must be 0-23 and differ".into());
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
 /// 签名的群配置
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SignedGroupConfig {
@@ -201,6 +338,8 @@ pub struct UpdateGroupConfigRequest {
     pub spam_detection_enabled: Option<bool>,
     pub spam_max_emoji: Option<u8>,
     pub spam_first_messages_only: Option<u8>,
+    /// 自定义规则（整体替换）
+    pub custom_rules: Option<Vec<CustomRule>>,
     /// 钱包签名认证 (hex): sign(bot_id_hash + timestamp)
     pub auth_signature: String,
     /// 签名时间戳
@@ -387,6 +526,7 @@ pub async fn handle_update_config(
             spam_detection_enabled: false,
             spam_max_emoji: 0,
             spam_first_messages_only: 0,
+            custom_rules: vec![],
         });
 
     let new_config = GroupConfig {
@@ -425,8 +565,17 @@ pub async fn handle_update_config(
         spam_detection_enabled: req.spam_detection_enabled.unwrap_or(base.spam_detection_enabled),
         spam_max_emoji: req.spam_max_emoji.unwrap_or(base.spam_max_emoji),
         spam_first_messages_only: req.spam_first_messages_only.unwrap_or(base.spam_first_messages_only),
+        custom_rules: req.custom_rules.unwrap_or(base.custom_rules),
     };
 
+    if let Err(e) = validate_custom_rules(&new_config.custom_rules) {
+        return (StatusCode::BAD_REQUEST, Json(UpdateGroupConfigResponse {
+            success: false,
+            version: 0,
+            error: Some(e),
+        }));
+    }
+
     // 3. Agent 签名
     let config_json = match serde_json::to_string(&new_config) {
         Ok(j) => j,
@@ -616,6 +765,7 @@ mod tests {
                 spam_detection_enabled: false,
                 spam_max_emoji: 0,
                 spam_first_messages_only: 0,
+                custom_rules: vec![],
             },
             signature: "sig".into(),
             signer_public_key: "pk".into(),
@@ -787,4 +937,37 @@ mod tests {
         };
         assert_eq!(result, Some(8), "新值应覆盖");
     }
+
+    // ═══════════════════════════════════════════
+    // 自定义规则
+    // ═══════════════════════════════════════════
+
+    #[test]
+    fn test_empty_custom_rules_not_serialized() {
+        let config = make_signed_config(1).config;
+        let json = serde_json::to_string(&config).unwrap();
+        assert!(!json.contains("custom_rules"), "空规则不应改变旧配置的签名内容");
+    }
+
+    #[test]
+    fn test_custom_rules_roundtrip_and_validate() {
+        let rules: Vec<CustomRule> = serde_json::from_value(serde_json::json!([{
+            "id": "no_links",
+            "priority": 650,
+            "when": { "All": [
+                { "SenderRole": ["Member"] },
+                { "Counter": { "counter": "LinkCount", "op": "Gte", "value": 1 } }
+            ]},
+            "then": { "action_type": { "Message": "Delete" } }
+        }])).unwrap();
+        assert!(rules[0].enabled);
+        assert!(validate_custom_rules(&rules).is_ok());
+
+        let mut bad = rules.clone();
+        bad[0].when = RuleCondition::TextRegex("(".into());
+        assert!(validate_custom_rules(&bad).is_err());
+
+        let dup = vec![rules[0].clone(), rules[0].clone()];
+        assert!(validate_custom_rules(&dup).is_err());
+    }
 }
@@ -556,6 +556,7 @@ mod tests {
             spam_detection_enabled: false,
             spam_max_emoji: 0,
             spam_first_messages_only: 0,
+            custom_rules: vec![],
         }
     }
 
@@ -779,6 +780,7 @@ mod tests {
             spam_detection_enabled: true,
             spam_max_emoji: 3,
             spam_first_messages_only: 0,
+            custom_rules: vec![],
         }
     }
 
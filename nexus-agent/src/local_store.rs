@@ -1,13 +1,41 @@
 use std::collections::HashMap;
-use std::sync::RwLock;
-use std::time::Instant;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
 use std::hash::{Hash, Hasher, BuildHasher, RandomState};
 
+use axum::{extract::State, http::StatusCode, Json};
+use redb::{Database, ReadableTable, TableDefinition};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+use crate::AppState;
+
+/// 警告记录表: "chat_id:user_id" → WarnRecord (JSON)
+const WARNS: TableDefinition<&str, &[u8]> = TableDefinition::new("warns");
+/// 防刷屏计数器快照表: "chat_id:user_id" → PersistedFlood (JSON)
+const FLOODS: TableDefinition<&str, &[u8]> = TableDefinition::new("flood_counters");
+/// 管理员缓存快照表: "chat_id" → PersistedAdminCache (JSON)
+const ADMINS: TableDefinition<&str, &[u8]> = TableDefinition::new("admin_cache");
+
+/// 每个用户保留的警告历史条数
+const MAX_WARN_HISTORY: usize = 20;
+/// 防刷屏计数器保留时间（秒）
+const FLOOD_RETENTION_SECS: u64 = 60;
+/// 平台事件去重窗口（秒），覆盖 Slack 的全部重试（最晚约 5 分钟后）
+const EVENT_DEDUP_SECS: u64 = 60 * 60;
+
 /// Agent 本地状态存储
 ///
 /// 用于高频、低延迟的检测操作（防刷屏、警告计数等）。
 /// 不需要共识，但会异步提交审计记录到 Node 网络。
 ///
+/// 持久化（`open` 创建时启用，redb 嵌入式 KV）:
+///   - 警告记录: 每次变更立即落盘，重启后 WarnAction 阈值继续生效
+///   - 防刷屏计数器 / 管理员缓存: 由 `cleanup_expired` 定时快照（最多丢失一个周期）
+///   - 消息指纹: 仅内存（哈希种子为进程级随机数，5 分钟窗口，重启丢失无影响）
+///   - 已处理的平台事件 ID: 仅内存（1 小时窗口，用于丢弃平台重试投递的重复事件）
+///
 /// 参考:
 ///   - FallenRobot: CHAT_FLOOD 内存字典 + user_data 内存字典
 ///   - tg-spam: duplicateDetector 内存窗口
@@ -15,8 +43,8 @@ pub struct LocalStore {
     /// 防刷屏计数器: (chat_id, user_id) → FloodCounter
     flood_counters: RwLock<HashMap<(i64, i64), FloodCounter>>,
 
-    /// 警告计数: (chat_id, user_id) → warn_count
-    warn_counts: RwLock<HashMap<(i64, i64), u8>>,
+    /// 警告记录: (chat_id, user_id) → WarnRecord
+    warns: RwLock<HashMap<(i64, i64), WarnRecord>>,
 
     /// 管理员缓存: chat_id → AdminCacheEntry
     admin_cache: RwLock<HashMap<i64, AdminCacheEntry>>,
@@ -25,8 +53,66 @@ pub struct LocalStore {
     /// 用于重复消息检测
     recent_messages: RwLock<HashMap<i64, Vec<MessageFingerprint>>>,
 
+    /// 已处理的平台事件: event_id → 首次处理时间
+    seen_events: RwLock<HashMap<String, Instant>>,
+
     /// 随机哈希种子（进程生命周期内固定，防外部碰撞攻击）
     hash_state: RandomState,
+
+    /// 持久化后端（None = 纯内存）
+    db: Option<Mutex<Database>>,
+
+    /// 警告过期时间（秒，最后一次变更起算，0 = 永不过期）
+    warn_ttl_secs: u64,
+}
+
+/// 警告记录（可导出，供替换 Agent 或 Node 集群接管）
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WarnRecord {
+    pub chat_id: i64,
+    pub user_id: i64,
+    /// 当前警告数
+    pub count: u8,
+    /// 最后变更时间 (Unix 秒)
+    pub updated_at: u64,
+    /// 最近的警告变更（最多 MAX_WARN_HISTORY 条，旧的在前）
+    #[serde(default)]
+    pub history: Vec<WarnEvent>,
+}
+
+/// 警告变更事件
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WarnEvent {
+    pub kind: WarnEventKind,
+    /// 变更后的警告数
+    pub count: u8,
+    /// 变更时间 (Unix 秒)
+    pub at: u64,
+}
+
+/// 警告变更类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WarnEventKind {
+    Add,
+    Remove,
+}
+
+/// 防刷屏计数器持久化格式（Instant 转换为 Unix 秒）
+#[derive(Serialize, Deserialize)]
+struct PersistedFlood {
+    chat_id: i64,
+    user_id: i64,
+    count: u16,
+    window_start: u64,
+}
+
+/// 管理员缓存持久化格式
+#[derive(Serialize, Deserialize)]
+struct PersistedAdminCache {
+    chat_id: i64,
+    admin_ids: Vec<i64>,
+    cached_at: u64,
+    ttl_seconds: u64,
 }
 
 /// 防刷屏计数器
@@ -50,16 +136,107 @@ struct MessageFingerprint {
 }
 
 impl LocalStore {
+    /// 创建纯内存存储（重启后状态丢失）
     pub fn new() -> Self {
         Self {
             flood_counters: RwLock::new(HashMap::new()),
-            warn_counts: RwLock::new(HashMap::new()),
+            warns: RwLock::new(HashMap::new()),
             admin_cache: RwLock::new(HashMap::new()),
             recent_messages: RwLock::new(HashMap::new()),
+            seen_events: RwLock::new(HashMap::new()),
             hash_state: RandomState::new(),
+            db: None,
+            warn_ttl_secs: 0,
         }
     }
 
+    /// 打开持久化存储 (`data_dir/local_store.redb`) 并恢复状态
+    ///
+    /// 恢复时丢弃已过期的警告、防刷屏计数器和管理员缓存。
+    pub fn open(data_dir: &str, warn_ttl_secs: u64) -> Result<Self, String> {
+        let dir = PathBuf::from(data_dir);
+        std::fs::create_dir_all(&dir)
+            .map_err(|e| format!("Create dir failed: {}", e))?;
+        let db = Database::create(dir.join("local_store.redb"))
+            .map_err(|e| format!("Open local store failed: {}", e))?;
+
+        // 确保表存在（读事务打开不存在的表会报错）
+        let txn = db.begin_write().map_err(db_err)?;
+        {
+            txn.open_table(WARNS).map_err(db_err)?;
+            txn.open_table(FLOODS).map_err(db_err)?;
+            txn.open_table(ADMINS).map_err(db_err)?;
+        }
+        txn.commit().map_err(db_err)?;
+
+        let mut store = Self::new();
+        store.warn_ttl_secs = warn_ttl_secs;
+        store.load_from_db(&db)?;
+        store.db = Some(Mutex::new(db));
+        Ok(store)
+    }
+
+    /// 从数据库恢复内存状态
+    fn load_from_db(&mut self, db: &Database) -> Result<(), String> {
+        let now = Instant::now();
+        let now_unix = now_unix();
+        let txn = db.begin_read().map_err(db_err)?;
+
+        let warns = self.warns.get_mut().unwrap_or_else(|e| e.into_inner());
+        for item in txn.open_table(WARNS).map_err(db_err)?.iter().map_err(db_err)? {
+            let (_, value) = item.map_err(db_err)?;
+            let Ok(record) = serde_json::from_slice::<WarnRecord>(value.value()) else {
+                continue;
+            };
+            if !warn_expired(&record, self.warn_ttl_secs, now_unix) {
+                warns.insert((record.chat_id, record.user_id), record);
+            }
+        }
+
+        let counters = self.flood_counters.get_mut().unwrap_or_else(|e| e.into_inner());
+        for item in txn.open_table(FLOODS).map_err(db_err)?.iter().map_err(db_err)? {
+            let (_, value) = item.map_err(db_err)?;
+            let Ok(flood) = serde_json::from_slice::<PersistedFlood>(value.value()) else {
+                continue;
+            };
+            if now_unix.saturating_sub(flood.window_start) >= FLOOD_RETENTION_SECS {
+                continue;
+            }
+            if let Some(window_start) = unix_to_instant(flood.window_start, now, now_unix) {
+                counters.insert((flood.chat_id, flood.user_id), FloodCounter {
+                    count: flood.count,
+                    window_start,
+                });
+            }
+        }
+
+        let cache = self.admin_cache.get_mut().unwrap_or_else(|e| e.into_inner());
+        for item in txn.open_table(ADMINS).map_err(db_err)?.iter().map_err(db_err)? {
+            let (_, value) = item.map_err(db_err)?;
+            let Ok(entry) = serde_json::from_slice::<PersistedAdminCache>(value.value()) else {
+                continue;
+            };
+            if now_unix.saturating_sub(entry.cached_at) >= entry.ttl_seconds {
+                continue;
+            }
+            if let Some(cached_at) = unix_to_instant(entry.cached_at, now, now_unix) {
+                cache.insert(entry.chat_id, AdminCacheEntry {
+                    admin_ids: entry.admin_ids,
+                    cached_at,
+                    ttl_seconds: entry.ttl_seconds,
+                });
+            }
+        }
+
+        info!(
+            warns = warns.len(),
+            flood_counters = counters.len(),
+            admin_cache = cache.len(),
+            "LocalStore 已从本地恢复"
+        );
+        Ok(())
+    }
+
     // ═══════════════════════════════════════════════════════════
     // 防刷屏
     // ═══════════════════════════════════════════════════════════
@@ -111,30 +288,79 @@ impl LocalStore {
     /// 参考: FallenRobot/modules/warns.py
     ///   - user_data[chat_id][user_id]["warnings"] += 1
     pub fn add_warn(&self, chat_id: i64, user_id: i64) -> u8 {
-        let mut warns = self.warn_counts.write().unwrap_or_else(|e| e.into_inner());
-        let count = warns.entry((chat_id, user_id)).or_insert(0);
-        *count = count.saturating_add(1);
-        *count
+        let mut warns = self.warns.write().unwrap_or_else(|e| e.into_inner());
+        let record = warns.entry((chat_id, user_id)).or_insert_with(|| WarnRecord {
+            chat_id,
+            user_id,
+            count: 0,
+            updated_at: 0,
+            history: Vec::new(),
+        });
+        record.count = record.count.saturating_add(1);
+        push_warn_event(record, WarnEventKind::Add);
+        self.persist_warn(record);
+        record.count
     }
 
     /// 减少警告计数，返回新的计数值
     pub fn remove_warn(&self, chat_id: i64, user_id: i64) -> u8 {
-        let mut warns = self.warn_counts.write().unwrap_or_else(|e| e.into_inner());
-        let count = warns.entry((chat_id, user_id)).or_insert(0);
-        *count = count.saturating_sub(1);
-        *count
+        let mut warns = self.warns.write().unwrap_or_else(|e| e.into_inner());
+        let Some(record) = warns.get_mut(&(chat_id, user_id)) else {
+            return 0;
+        };
+        record.count = record.count.saturating_sub(1);
+        push_warn_event(record, WarnEventKind::Remove);
+        self.persist_warn(record);
+        record.count
     }
 
     /// 重置警告
     pub fn reset_warns(&self, chat_id: i64, user_id: i64) {
-        let mut warns = self.warn_counts.write().unwrap_or_else(|e| e.into_inner());
-        warns.remove(&(chat_id, user_id));
+        let mut warns = self.warns.write().unwrap_or_else(|e| e.into_inner());
+        if warns.remove(&(chat_id, user_id)).is_some() {
+            self.delete_warns(&[(chat_id, user_id)]);
+        }
     }
 
     /// 获取警告数
     pub fn get_warns(&self, chat_id: i64, user_id: i64) -> u8 {
-        let warns = self.warn_counts.read().unwrap_or_else(|e| e.into_inner());
-        warns.get(&(chat_id, user_id)).copied().unwrap_or(0)
+        let warns = self.warns.read().unwrap_or_else(|e| e.into_inner());
+        warns.get(&(chat_id, user_id)).map(|r| r.count).unwrap_or(0)
+    }
+
+    /// 导出全部警告记录（按 chat_id, user_id 排序，输出确定）
+    pub fn export_warns(&self) -> Vec<WarnRecord> {
+        let warns = self.warns.read().unwrap_or_else(|e| e.into_inner());
+        let mut records: Vec<WarnRecord> = warns.values().cloned().collect();
+        records.sort_by_key(|r| (r.chat_id, r.user_id));
+        records
+    }
+
+    /// 导入警告记录，返回实际采用的条数
+    ///
+    /// 同一用户以 updated_at 较新的一方为准，重放旧导出不会回滚计数。
+    pub fn import_warns(&self, records: Vec<WarnRecord>) -> usize {
+        let now_unix = now_unix();
+        let mut warns = self.warns.write().unwrap_or_else(|e| e.into_inner());
+        let mut imported = 0;
+
+        for mut record in records {
+            if warn_expired(&record, self.warn_ttl_secs, now_unix) {
+                continue;
+            }
+            let key = (record.chat_id, record.user_id);
+            if warns.get(&key).is_some_and(|r| r.updated_at >= record.updated_at) {
+                continue;
+            }
+            if record.history.len() > MAX_WARN_HISTORY {
+                record.history.drain(..record.history.len() - MAX_WARN_HISTORY);
+            }
+            self.persist_warn(&record);
+            warns.insert(key, record);
+            imported += 1;
+        }
+
+        imported
     }
 
     // ═══════════════════════════════════════════════════════════
@@ -203,6 +429,28 @@ impl LocalStore {
         dup_count + 1 // 包含当前这条
     }
 
+    /// 登记平台事件 ID，返回 true = 首次出现（窗口内重复投递返回 false）
+    ///
+    /// 平台在未及时收到 2xx 时会重试投递同一事件（Slack: `event_id` 不变），
+    /// 处理失败时调用 `forget_event` 撤销登记，让后续重试仍能被处理。
+    pub fn mark_event_seen(&self, event_id: &str) -> bool {
+        let now = Instant::now();
+        let mut seen = self.seen_events.write().unwrap_or_else(|e| e.into_inner());
+        match seen.get(event_id) {
+            Some(at) if now.duration_since(*at).as_secs() < EVENT_DEDUP_SECS => false,
+            _ => {
+                seen.insert(event_id.to_string(), now);
+                true
+            }
+        }
+    }
+
+    /// 撤销事件登记（处理失败，允许平台重试）
+    pub fn forget_event(&self, event_id: &str) {
+        let mut seen = self.seen_events.write().unwrap_or_else(|e| e.into_inner());
+        seen.remove(event_id);
+    }
+
     /// 计算文本哈希（使用进程级随机种子，防碰撞攻击）
     fn hash_text(&self, text: &str) -> u64 {
         let mut hasher = self.hash_state.build_hasher();
@@ -215,13 +463,15 @@ impl LocalStore {
     // ═══════════════════════════════════════════════════════════
 
     /// 清理过期数据（定时任务调用）
+    ///
+    /// 启用持久化时同时完成磁盘压缩: 删除过期警告，重写防刷屏 / 管理员缓存快照。
     pub fn cleanup_expired(&self) {
         let now = Instant::now();
 
         // 清理防刷屏（超过 60 秒未更新的计数器）
         {
             let mut counters = self.flood_counters.write().unwrap_or_else(|e| e.into_inner());
-            counters.retain(|_, v| now.duration_since(v.window_start).as_secs() < 60);
+            counters.retain(|_, v| now.duration_since(v.window_start).as_secs() < FLOOD_RETENTION_SECS);
         }
 
         // 清理管理员缓存（过期条目）
@@ -238,7 +488,297 @@ impl LocalStore {
             }
             messages.retain(|_, v| !v.is_empty());
         }
+
+        // 清理过期的事件 ID
+        {
+            let mut seen = self.seen_events.write().unwrap_or_else(|e| e.into_inner());
+            seen.retain(|_, at| now.duration_since(*at).as_secs() < EVENT_DEDUP_SECS);
+        }
+
+        // 清理过期警告（warn_ttl_secs > 0 时）
+        let expired: Vec<(i64, i64)> = {
+            let now_unix = now_unix();
+            let mut warns = self.warns.write().unwrap_or_else(|e| e.into_inner());
+            let expired = warns.iter()
+                .filter(|(_, r)| warn_expired(r, self.warn_ttl_secs, now_unix))
+                .map(|(k, _)| *k)
+                .collect::<Vec<_>>();
+            for key in &expired {
+                warns.remove(key);
+            }
+            expired
+        };
+
+        if self.db.is_some() {
+            self.delete_warns(&expired);
+            if let Err(e) = self.snapshot_to_db() {
+                warn!(error = %e, "LocalStore 快照写入失败");
+            }
+            if !expired.is_empty() {
+                self.compact();
+            }
+        }
+    }
+
+    // ═══════════════════════════════════════════════════════════
+    // 持久化
+    // ═══════════════════════════════════════════════════════════
+
+    /// 写入单条警告记录（立即落盘）
+    fn persist_warn(&self, record: &WarnRecord) {
+        let Some(db) = &self.db else { return };
+        let result = (|| -> Result<(), String> {
+            let value = serde_json::to_vec(record).map_err(db_err)?;
+            let key = warn_key(record.chat_id, record.user_id);
+            let db = db.lock().unwrap_or_else(|e| e.into_inner());
+            let txn = db.begin_write().map_err(db_err)?;
+            txn.open_table(WARNS).map_err(db_err)?
+                .insert(key.as_str(), value.as_slice()).map_err(db_err)?;
+            txn.commit().map_err(db_err)
+        })();
+        if let Err(e) = result {
+            warn!(chat_id = record.chat_id, user_id = record.user_id, error = %e, "警告记录持久化失败");
+        }
     }
+
+    /// 删除警告记录
+    fn delete_warns(&self, keys: &[(i64, i64)]) {
+        let Some(db) = &self.db else { return };
+        if keys.is_empty() {
+            return;
+        }
+        let result = (|| -> Result<(), String> {
+            let db = db.lock().unwrap_or_else(|e| e.into_inner());
+            let txn = db.begin_write().map_err(db_err)?;
+            {
+                let mut table = txn.open_table(WARNS).map_err(db_err)?;
+                for (chat_id, user_id) in keys {
+                    table.remove(warn_key(*chat_id, *user_id).as_str()).map_err(db_err)?;
+                }
+            }
+            txn.commit().map_err(db_err)
+        })();
+        if let Err(e) = result {
+            warn!(count = keys.len(), error = %e, "警告记录删除失败");
+        }
+    }
+
+    /// 重写防刷屏计数器与管理员缓存快照
+    fn snapshot_to_db(&self) -> Result<(), String> {
+        let Some(db) = &self.db else { return Ok(()) };
+        let now = Instant::now();
+        let now_unix = now_unix();
+
+        let floods: Vec<(String, Vec<u8>)> = {
+            let counters = self.flood_counters.read().unwrap_or_else(|e| e.into_inner());
+            counters.iter()
+                .map(|(&(chat_id, user_id), c)| {
+                    let persisted = PersistedFlood {
+                        chat_id,
+                        user_id,
+                        count: c.count,
+                        window_start: instant_to_unix(c.window_start, now, now_unix),
+                    };
+                    serde_json::to_vec(&persisted).map(|v| (warn_key(chat_id, user_id), v))
+                })
+                .collect::<Result<_, _>>()
+                .map_err(db_err)?
+        };
+        let admins: Vec<(String, Vec<u8>)> = {
+            let cache = self.admin_cache.read().unwrap_or_else(|e| e.into_inner());
+            cache.iter()
+                .map(|(&chat_id, entry)| {
+                    let persisted = PersistedAdminCache {
+                        chat_id,
+                        admin_ids: entry.admin_ids.clone(),
+                        cached_at: instant_to_unix(entry.cached_at, now, now_unix),
+                        ttl_seconds: entry.ttl_seconds,
+                    };
+                    serde_json::to_vec(&persisted).map(|v| (chat_id.to_string(), v))
+                })
+                .collect::<Result<_, _>>()
+                .map_err(db_err)?
+        };
+
+        let db = db.lock().unwrap_or_else(|e| e.into_inner());
+        let txn = db.begin_write().map_err(db_err)?;
+        txn.delete_table(FLOODS).map_err(db_err)?;
+        txn.delete_table(ADMINS).map_err(db_err)?;
+        {
+            let mut table = txn.open_table(FLOODS).map_err(db_err)?;
+            for (key, value) in &floods {
+                table.insert(key.as_str(), value.as_slice()).map_err(db_err)?;
+            }
+            let mut table = txn.open_table(ADMINS).map_err(db_err)?;
+            for (key, value) in &admins {
+                table.insert(key.as_str(), value.as_slice()).map_err(db_err)?;
+            }
+        }
+        txn.commit().map_err(db_err)
+    }
+
+    /// 压缩数据库文件（回收已删除记录占用的空间）
+    fn compact(&self) {
+        let Some(db) = &self.db else { return };
+        let mut db = db.lock().unwrap_or_else(|e| e.into_inner());
+        match db.compact() {
+            Ok(compacted) => debug!(compacted, "LocalStore 已压缩"),
+            Err(e) => debug!(error = %e, "LocalStore 压缩跳过"),
+        }
+    }
+}
+
+/// 警告记录 / 防刷屏计数器的存储键
+fn warn_key(chat_id: i64, user_id: i64) -> String {
+    format!("{}:{}", chat_id, user_id)
+}
+
+fn db_err(e: impl std::fmt::Display) -> String {
+    e.to_string()
+}
+
+fn now_unix() -> u64 {
+    chrono::Utc::now().timestamp() as u64
+}
+
+/// 记录警告变更事件（保留最近 MAX_WARN_HISTORY 条）
+fn push_warn_event(record: &mut WarnRecord, kind: WarnEventKind) {
+    let at = now_unix();
+    record.updated_at = at;
+    record.history.push(WarnEvent { kind, count: record.count, at });
+    if record.history.len() > MAX_WARN_HISTORY {
+        record.history.remove(0);
+    }
+}
+
+fn warn_expired(record: &WarnRecord, ttl_secs: u64, now_unix: u64) -> bool {
+    ttl_secs > 0 && now_unix.saturating_sub(record.updated_at) >= ttl_secs
+}
+
+/// Instant → Unix 秒（以当前时刻为锚点换算）
+fn instant_to_unix(t: Instant, now: Instant, now_unix: u64) -> u64 {
+    now_unix.saturating_sub(now.duration_since(t).as_secs())
+}
+
+/// Unix 秒 → Instant（早于系统启动时间则无法表示，返回 None）
+fn unix_to_instant(ts: u64, now: Instant, now_unix: u64) -> Option<Instant> {
+    now.checked_sub(Duration::from_secs(now_unix.saturating_sub(ts)))
+}
+
+// ═══════════════════════════════════════════════════════════════
+// 警告记录导出 / 导入
+// ═══════════════════════════════════════════════════════════════
+
+/// 警告记录导出（Agent 签名，Node 集群可用 Bot owner 公钥验证）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WarnExport {
+    pub bot_id_hash: String,
+    /// 导出时间 (Unix 秒)
+    pub exported_at: u64,
+    pub records: Vec<WarnRecord>,
+}
+
+/// 签名的警告记录导出
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedWarnExport {
+    pub export: WarnExport,
+    /// Ed25519 签名 (hex)，签名内容为 export 的 JSON
+    pub signature: String,
+    /// 签名者公钥 (hex)
+    pub signer_public_key: String,
+}
+
+/// POST /v1/warns/import 响应
+#[derive(Debug, Serialize)]
+pub struct ImportWarnsResponse {
+    pub success: bool,
+    pub imported: usize,
+    pub error: Option<String>,
+}
+
+/// 验证签名导出: 签名者必须是 Bot owner（本 Agent 公钥），且 bot_id_hash 一致
+fn verify_warn_export(
+    signed: &SignedWarnExport,
+    expected_public_key: &str,
+    expected_bot_id_hash: &str,
+) -> Result<(), String> {
+    use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+
+    if signed.signer_public_key != expected_public_key {
+        return Err("Signer public key does not match Bot owner".into());
+    }
+    if signed.export.bot_id_hash != expected_bot_id_hash {
+        return Err("bot_id_hash mismatch".into());
+    }
+
+    let pk: [u8; 32] = hex::decode(&signed.signer_public_key)
+        .map_err(|e| format!("Invalid public key hex: {}", e))?
+        .try_into()
+        .map_err(|_| "Public key must be 32 bytes")?;
+    let verifying_key = VerifyingKey::from_bytes(&pk)
+        .map_err(|e| format!("Invalid Ed25519 public key: {}", e))?;
+    let sig: [u8; 64] = hex::decode(&signed.signature)
+        .map_err(|e| format!("Invalid signature hex: {}", e))?
+        .try_into()
+        .map_err(|_| "Signature must be 64 bytes")?;
+
+    let json = serde_json::to_string(&signed.export)
+        .map_err(|e| format!("Export serialization error: {}", e))?;
+    verifying_key.verify(json.as_bytes(), &Signature::from_bytes(&sig))
+        .map_err(|e| format!("Signature verification failed: {}", e))
+}
+
+/// GET /v1/warns/export — 导出签名的警告记录
+pub async fn handle_export_warns(
+    State(state): State<Arc<AppState>>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    let export = WarnExport {
+        bot_id_hash: state.config.bot_id_hash_hex(),
+        exported_at: now_unix(),
+        records: state.local_store.export_warns(),
+    };
+    let json = match serde_json::to_string(&export) {
+        Ok(j) => j,
+        Err(e) => {
+            return (StatusCode::INTERNAL_SERVER_ERROR, Json(serde_json::json!({
+                "error": format!("Export serialization failed: {}", e),
+            })));
+        }
+    };
+
+    let signed = SignedWarnExport {
+        signature: hex::encode(state.key_manager.sign_raw(json.as_bytes())),
+        signer_public_key: state.key_manager.public_key_hex(),
+        export,
+    };
+    (StatusCode::OK, Json(serde_json::json!(signed)))
+}
+
+/// POST /v1/warns/import — 导入签名的警告记录（替换 Agent 接管）
+pub async fn handle_import_warns(
+    State(state): State<Arc<AppState>>,
+    Json(signed): Json<SignedWarnExport>,
+) -> (StatusCode, Json<ImportWarnsResponse>) {
+    if let Err(e) = verify_warn_export(
+        &signed,
+        &state.key_manager.public_key_hex(),
+        &state.config.bot_id_hash_hex(),
+    ) {
+        warn!(error = %e, "警告记录导入验证失败");
+        return (StatusCode::FORBIDDEN, Json(ImportWarnsResponse {
+            success: false,
+            imported: 0,
+            error: Some(e),
+        }));
+    }
+
+    let imported = state.local_store.import_warns(signed.export.records);
+    info!(imported, "警告记录已导入");
+    (StatusCode::OK, Json(ImportWarnsResponse {
+        success: true,
+        imported,
+        error: None,
+    }))
 }
 
 #[cfg(test)]
@@ -345,6 +885,19 @@ mod tests {
         assert_eq!(store.record_message(-100, 99, "spam msg", 60), 1);
     }
 
+    #[test]
+    fn test_event_dedup() {
+        let store = LocalStore::new();
+        assert!(store.mark_event_seen("Ev1"));
+        assert!(!store.mark_event_seen("Ev1"));
+        assert!(store.mark_event_seen("Ev2"));
+        // 处理失败撤销后，重试可再次处理
+        store.forget_event("Ev1");
+        assert!(store.mark_event_seen("Ev1"));
+        store.cleanup_expired();
+        assert!(!store.mark_event_seen("Ev2"));
+    }
+
     #[test]
     fn test_cleanup() {
         let store = LocalStore::new();
@@ -354,4 +907,131 @@ mod tests {
         // 不应 panic
         store.cleanup_expired();
     }
+
+    // ═══════════════════════════════════════════
+    // 持久化（重启恢复）
+    // ═══════════════════════════════════════════
+
+    fn open_store(dir: &tempfile::TempDir, warn_ttl_secs: u64) -> LocalStore {
+        LocalStore::open(dir.path().to_str().unwrap(), warn_ttl_secs).unwrap()
+    }
+
+    #[test]
+    fn test_warns_survive_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let store = open_store(&dir, 0);
+            store.add_warn(-100, 42);
+            store.add_warn(-100, 42);
+            store.add_warn(-100, 7);
+            store.reset_warns(-100, 7);
+        }
+
+        let store = open_store(&dir, 0);
+        assert_eq!(store.get_warns(-100, 42), 2);
+        assert_eq!(store.get_warns(-100, 7), 0);
+        // 重启后继续累积，阈值可以正常触发
+        assert_eq!(store.add_warn(-100, 42), 3);
+
+        let records = store.export_warns();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].history.len(), 3);
+        assert_eq!(records[0].history[2].kind, WarnEventKind::Add);
+    }
+
+    #[test]
+    fn test_flood_and_admin_cache_survive_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let store = open_store(&dir, 0);
+            for _ in 0..5 {
+                assert!(!store.check_flood(-100, 42, 5, 10));
+            }
+            store.set_admin_cache(-100, vec![1, 2]);
+            // 定时清理同时写入快照
+            store.cleanup_expired();
+        }
+
+        let store = open_store(&dir, 0);
+        assert!(store.check_flood(-100, 42, 5, 10), "第 6 条应触发（计数已恢复）");
+        assert_eq!(store.is_admin_cached(-100, 2), Some(true));
+    }
+
+    #[test]
+    fn test_expired_warns_compacted() {
+        let dir = tempfile::tempdir().unwrap();
+        {
+            let store = open_store(&dir, 3600);
+            store.add_warn(-100, 1);
+            let stale = WarnRecord {
+                chat_id: -100,
+                user_id: 2,
+                count: 2,
+                updated_at: now_unix() - 1800,
+                history: vec![],
+            };
+            assert_eq!(store.import_warns(vec![stale.clone()]), 1);
+
+            // 模拟时间流逝: 直接改写内存中的 updated_at
+            store.warns.write().unwrap()
+                .get_mut(&(-100, 2)).unwrap()
+                .updated_at = now_unix() - 7200;
+            store.cleanup_expired();
+            assert_eq!(store.get_warns(-100, 2), 0);
+        }
+
+        let store = open_store(&dir, 3600);
+        assert_eq!(store.get_warns(-100, 1), 1);
+        // 数据库中的过期记录已删除（导入时仍未过期，清理后不应复活）
+        assert_eq!(store.get_warns(-100, 2), 0);
+    }
+
+    #[test]
+    fn test_import_keeps_newer_record() {
+        let source = LocalStore::new();
+        source.add_warn(-100, 42);
+        source.add_warn(-100, 42);
+        let exported = source.export_warns();
+
+        let target = LocalStore::new();
+        assert_eq!(target.import_warns(exported.clone()), 1);
+        assert_eq!(target.get_warns(-100, 42), 2);
+
+        // 重放同一份导出不生效
+        assert_eq!(target.import_warns(exported.clone()), 0);
+
+        // 更旧的记录不覆盖
+        let mut older = exported[0].clone();
+        older.count = 9;
+        older.updated_at -= 10;
+        assert_eq!(target.import_warns(vec![older]), 0);
+        assert_eq!(target.get_warns(-100, 42), 2);
+    }
+
+    #[test]
+    fn test_verify_warn_export() {
+        use ed25519_dalek::{Signer, SigningKey};
+
+        let key = SigningKey::from_bytes(&[7u8; 32]);
+        let pk_hex = hex::encode(key.verifying_key().as_bytes());
+        let export = WarnExport {
+            bot_id_hash: "bot".into(),
+            exported_at: 1,
+            records: vec![],
+        };
+        let sig = key.sign(serde_json::to_string(&export).unwrap().as_bytes());
+        let signed = SignedWarnExport {
+            export,
+            signature: hex::encode(sig.to_bytes()),
+            signer_public_key: pk_hex.clone(),
+        };
+
+        assert!(verify_warn_export(&signed, &pk_hex, "bot").is_ok());
+        assert!(verify_warn_export(&signed, &pk_hex, "other").is_err());
+        assert!(verify_warn_export(&signed, &hex::encode([1u8; 32]), "bot").is_err());
+
+        let mut tampered = signed.clone();
+        tampered.export.exported_at = 2;
+        assert!(verify_warn_export(&tampered, &pk_hex, "bot").is_err());
+    }
 }
@@ -8,6 +8,8 @@ mod multicaster;
 pub mod platform;
 mod rate_limiter;
 mod signer;
+mod slack_events;
+mod slack_executor;
 mod types;
 pub mod local_store;
 pub mod local_processor;
@@ -18,7 +20,7 @@ use std::time::Instant;
 
 use axum::{routing::{get, post}, Router};
 use tokio::sync::RwLock;
-use tracing::{info, error};
+use tracing::{info, warn, error};
 
 use config::AgentConfig;
 use signer::{KeyManager, SequenceManager};
@@ -31,6 +33,7 @@ pub struct AppState {
     pub sequence_manager: SequenceManager,
     pub executor: executor::TelegramExecutor,
     pub discord_executor: Option<discord_executor::DiscordExecutor>,
+    pub slack_executor: Option<slack_executor::SlackExecutor>,
     pub config_store: group_config::ConfigStore,
     pub local_store: local_store::LocalStore,
     pub http_client: reqwest::Client,
@@ -104,12 +107,34 @@ async fn main() -> anyhow::Result<()> {
         None
     };
 
+    // Slack 执行器（仅在 slack/all 模式下创建）
+    let slack_exec = if config.platform.needs_slack() {
+        let sl = config.slack.as_ref().expect("Slack 配置缺失");
+        Some(slack_executor::SlackExecutor::new(
+            sl.bot_token.clone(),
+            http_client.clone(),
+        ))
+    } else {
+        None
+    };
+
     // 群配置存储
     let config_store = group_config::ConfigStore::new(&config.data_dir);
 
-    // 本地状态存储（防刷屏、警告计数等）
-    let local_store = local_store::LocalStore::new();
-    info!("LocalStore 已初始化");
+    // 本地状态存储（防刷屏、警告计数等，redb 持久化）
+    let local_store = match local_store::LocalStore::open(
+        &config.data_dir,
+        config.warn_ttl_days * 86_400,
+    ) {
+        Ok(store) => {
+            info!("LocalStore 已初始化（持久化）");
+            store
+        }
+        Err(e) => {
+            warn!(error = %e, "LocalStore 持久化不可用，回退到内存模式");
+            local_store::LocalStore::new()
+        }
+    };
 
     // 限流器: webhook 60s/1000次, execute 60s/200次
     let webhook_limiter = rate_limiter::RateLimiter::new(60, 1000);
@@ -121,6 +146,7 @@ async fn main() -> anyhow::Result<()> {
         sequence_manager,
         executor: tg_executor,
         discord_executor: discord_exec,
+        slack_executor: slack_exec,
         config_store,
         local_store,
         http_client,
@@ -130,7 +156,7 @@ async fn main() -> anyhow::Result<()> {
         execute_limiter,
     });
 
-    // 定时清理 LocalStore 过期数据 (每 60 秒)
+    // 定时清理 LocalStore 过期数据 + 落盘快照 (每 60 秒)
     {
         let state_ref = state.clone();
         tokio::spawn(async move {
@@ -226,12 +252,20 @@ async fn main() -> anyhow::Result<()> {
         info!("Discord 事件处理循环已启动");
     }
 
+    // Slack: Events API 推送到 POST /slack/events（需在 Slack App 中配置 Request URL）
+    if config.platform.needs_slack() {
+        info!("Slack Events API 已启用: POST /slack/events");
+    }
+
     // Axum HTTP 服务器（限制请求体 1MB 防止 OOM 攻击）
     let app = Router::new()
         .route("/webhook", post(webhook::handle_webhook))
+        .route("/slack/events", post(slack_events::handle_slack_events))
         .route("/v1/execute", post(webhook::handle_execute))
         .route("/v1/group-config", get(group_config::handle_get_config))
         .route("/v1/group-config", post(group_config::handle_update_config))
+        .route("/v1/warns/export", get(local_store::handle_export_warns))
+        .route("/v1/warns/import", post(local_store::handle_import_warns))
         .route("/health", get(webhook::handle_health))
         .layer(axum::extract::DefaultBodyLimit::max(1024 * 1024))
         .with_state(state);
@@ -1,4 +1,5 @@
 pub mod discord;
+pub mod slack;
 pub mod telegram;
 
 use serde::{Deserialize, Serialize};
@@ -9,11 +10,11 @@ use serde::{Deserialize, Serialize};
 /// 全部基于 PlatformEvent 操作，不再直接访问平台特定 JSON。
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PlatformEvent {
-    /// 平台类型: "telegram" | "discord"
+    /// 平台类型: "telegram" | "discord" | "slack"
     pub platform: String,
-    /// 群组/服务器 ID（TG: chat_id, Discord: guild_id）
+    /// 群组/服务器 ID（TG: chat_id, Discord: guild_id, Slack: team_id）
     pub group_id: String,
-    /// 频道 ID（Discord 专用，TG 为空）
+    /// 频道 ID（Discord / Slack，TG 为空）
     #[serde(default)]
     pub channel_id: String,
     /// 发送者 ID
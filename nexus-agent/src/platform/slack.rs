@@ -0,0 +1,398 @@
+use super::{PlatformAdapter, PlatformEvent};
+
+/// Slack 平台适配器 (Agent 侧)
+///
+/// 将 Slack Events API 的 `event_callback` 负载解析为统一的 PlatformEvent。
+/// 与 nexus-node `rule_engine::SlackAdapter` 使用相同的原始 JSON 格式，
+/// 节点侧可直接从 `raw_event` 重新提取规则上下文。
+#[derive(Default)]
+pub struct SlackAdapter;
+
+impl SlackAdapter {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl PlatformAdapter for SlackAdapter {
+    fn platform_name(&self) -> &str {
+        "slack"
+    }
+
+    fn parse_event(&self, raw: &serde_json::Value) -> Option<PlatformEvent> {
+        parse_slack_raw(raw)
+    }
+}
+
+/// 从 Slack Events API 负载解析为 PlatformEvent
+///
+/// 支持的事件类型（`event.type`）:
+/// - message（普通消息 / file_share / thread_broadcast / bot_message）
+/// - member_joined_channel / member_left_channel
+/// - team_join
+///
+/// `url_verification` 等非 `event_callback` 负载返回 None。
+pub fn parse_slack_raw(raw: &serde_json::Value) -> Option<PlatformEvent> {
+    if raw.get("type").and_then(|v| v.as_str()) != Some("event_callback") {
+        return None;
+    }
+    let event = raw.get("event")?;
+
+    // group_id = workspace (team) ID，部分事件仅在 event 内携带 team
+    let team_id = raw.get("team_id")
+        .or_else(|| event.get("team"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .to_string();
+
+    match event.get("type")?.as_str()? {
+        "message" => parse_message(event, team_id, raw),
+        "member_joined_channel" => parse_member_joined(event, team_id, raw),
+        "member_left_channel" => parse_member_left(event, team_id, raw),
+        "team_join" => parse_team_join(event, team_id, raw),
+        _ => None,
+    }
+}
+
+fn parse_message(
+    event: &serde_json::Value,
+    team_id: String,
+    raw: &serde_json::Value,
+) -> Option<PlatformEvent> {
+    let subtype = event.get("subtype").and_then(|v| v.as_str());
+    // message_changed / message_deleted / channel_join 等系统子类型不进入共识
+    if !matches!(subtype, None | Some("file_share") | Some("thread_broadcast") | Some("bot_message")) {
+        return None;
+    }
+
+    let channel_id = event.get("channel")?.as_str()?.to_string();
+    let sender_is_bot = event.get("bot_id").is_some() || subtype == Some("bot_message");
+    let sender_id = event.get("user")
+        .or_else(|| event.get("bot_id"))
+        .and_then(|v| v.as_str())
+        .unwrap_or("")
+        .to_string();
+    let text = event.get("text").and_then(|v| v.as_str()).unwrap_or("").to_string();
+    // Slack 以 ts 作为消息 ID
+    let message_id = event.get("ts").and_then(|v| v.as_str()).unwrap_or("").to_string();
+
+    let is_command = text.starts_with('/') || text.starts_with('!');
+    let (command, command_args) = if is_command {
+        let parts: Vec<&str> = text[1..].splitn(2, ' ').collect();
+        (Some(parts[0].to_string()), parts.get(1).map(|s| s.to_string()))
+    } else {
+        (None, None)
+    };
+
+    // 线程回复 → 父消息；否则取文本中第一个 <@U...> mention 作为目标用户
+    let reply_to_message_id = event.get("thread_ts")
+        .and_then(|v| v.as_str())
+        .filter(|ts| *ts != message_id)
+        .map(|s| s.to_string());
+    let reply_to_user_id = event.get("parent_user_id")
+        .and_then(|v| v.as_str())
+        .or_else(|| extract_mention(&text))
+        .map(|s| s.to_string());
+
+    Some(PlatformEvent {
+        platform: "slack".to_string(),
+        group_id: team_id,
+        channel_id,
+        sender_id,
+        sender_is_bot,
+        text,
+        message_id,
+        is_command,
+        command,
+        command_args,
+        reply_to_user_id,
+        reply_to_message_id,
+        is_join_event: false,
+        join_user_id: None,
+        is_leave_event: false,
+        is_interaction: false,
+        interaction_id: None,
+        interaction_token: None,
+        interaction_data: None,
+        is_member_update: false,
+        raw_event: raw.clone(),
+    })
+}
+
+fn parse_member_joined(
+    event: &serde_json::Value,
+    team_id: String,
+    raw: &serde_json::Value,
+) -> Option<PlatformEvent> {
+    let channel_id = event.get("channel").and_then(|v| v.as_str()).unwrap_or("").to_string();
+    let user_id = event.get("user").and_then(|v| v.as_str()).map(|s| s.to_string());
+
+    Some(PlatformEvent {
+        platform: "slack".to_string(),
+        group_id: team_id,
+        channel_id,
+        sender_id: user_id.clone().unwrap_or_default(),
+        sender_is_bot: false,
+        text: String::new(),
+        message_id: String::new(),
+        is_command: false,
+        command: None,
+        command_args: None,
+        reply_to_user_id: None,
+        reply_to_message_id: None,
+        is_join_event: true,
+        join_user_id: user_id,
+        is_leave_event: false,
+        is_interaction: false,
+        interaction_id: None,
+        interaction_token: None,
+        interaction_data: None,
+        is_member_update: false,
+        raw_event: raw.clone(),
+    })
+}
+
+fn parse_member_left(
+    event: &serde_json::Value,
+    team_id: String,
+    raw: &serde_json::Value,
+) -> Option<PlatformEvent> {
+    let channel_id = event.get("channel").and_then(|v| v.as_str()).unwrap_or("").to_string();
+    let user_id = event.get("user").and_then(|v| v.as_str()).unwrap_or("").to_string();
+
+    Some(PlatformEvent {
+        platform: "slack".to_string(),
+        group_id: team_id,
+        channel_id,
+        sender_id: user_id,
+        sender_is_bot: false,
+        text: String::new(),
+        message_id: String::new(),
+        is_command: false,
+        command: None,
+        command_args: None,
+        reply_to_user_id: None,
+        reply_to_message_id: None,
+        is_join_event: false,
+        join_user_id: None,
+        is_leave_event: true,
+        is_interaction: false,
+        interaction_id: None,
+        interaction_token: None,
+        interaction_data: None,
+        is_member_update: false,
+        raw_event: raw.clone(),
+    })
+}
+
+fn parse_team_join(
+    event: &serde_json::Value,
+    team_id: String,
+    raw: &serde_json::Value,
+) -> Option<PlatformEvent> {
+    let user_id = event.pointer("/user/id").and_then(|v| v.as_str()).map(|s| s.to_string());
+    let is_bot = event.pointer("/user/is_bot").and_then(|v| v.as_bool()).unwrap_or(false);
+
+    Some(PlatformEvent {
+        platform: "slack".to_string(),
+        group_id: team_id,
+        channel_id: String::new(),
+        sender_id: user_id.clone().unwrap_or_default(),
+        sender_is_bot: is_bot,
+        text: String::new(),
+        message_id: String::new(),
+        is_command: false,
+        command: None,
+        command_args: None,
+        reply_to_user_id: None,
+        reply_to_message_id: None,
+        is_join_event: true,
+        join_user_id: user_id,
+        is_leave_event: false,
+        is_interaction: false,
+        interaction_id: None,
+        interaction_token: None,
+        interaction_data: None,
+        is_member_update: false,
+        raw_event: raw.clone(),
+    })
+}
+
+/// 从 Slack 文本中提取 `<@U12345>` / `<@U12345|name>` 格式的 mention
+fn extract_mention(text: &str) -> Option<&str> {
+    let start = text.find("<@")?;
+    let rest = &text[start + 2..];
+    let end = rest.find('>')?;
+    rest[..end].split('|').next()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_message() {
+        let raw = serde_json::json!({
+            "token": "XXYYZZ",
+            "team_id": "T0001",
+            "api_app_id": "A0001",
+            "type": "event_callback",
+            "event_id": "Ev0001",
+            "event_time": 1700000000,
+            "event": {
+                "type": "message",
+                "channel": "C2147483705",
+                "user": "U2147483697",
+                "text": "Hello world",
+                "ts": "1355517523.000005",
+                "channel_type": "channel"
+            }
+        });
+        let event = parse_slack_raw(&raw).unwrap();
+        assert_eq!(event.platform, "slack");
+        assert_eq!(event.group_id, "T0001");
+        assert_eq!(event.channel_id, "C2147483705");
+        assert_eq!(event.sender_id, "U2147483697");
+        assert_eq!(event.message_id, "1355517523.000005");
+        assert_eq!(event.text, "Hello world");
+        assert!(!event.sender_is_bot);
+        assert!(!event.is_command);
+        assert_eq!(event.raw_event, raw);
+    }
+
+    #[test]
+    fn test_parse_command_with_mention() {
+        let raw = serde_json::json!({
+            "team_id": "T0001",
+            "type": "event_callback",
+            "event": {
+                "type": "message",
+                "channel": "C1",
+                "user": "U1",
+                "text": "!ban <@U99|mallory> spam",
+                "ts": "1700000000.000100"
+            }
+        });
+        let event = parse_slack_raw(&raw).unwrap();
+        assert!(event.is_command);
+        assert_eq!(event.command.as_deref(), Some("ban"));
+        assert_eq!(event.command_args.as_deref(), Some("<@U99|mallory> spam"));
+        assert_eq!(event.reply_to_user_id.as_deref(), Some("U99"));
+    }
+
+    #[test]
+    fn test_parse_thread_reply() {
+        let raw = serde_json::json!({
+            "team_id": "T0001",
+            "type": "event_callback",
+            "event": {
+                "type": "message",
+                "channel": "C1",
+                "user": "U2",
+                "text": "reply",
+                "ts": "1700000001.000200",
+                "thread_ts": "1700000000.000100",
+                "parent_user_id": "U1"
+            }
+        });
+        let event = parse_slack_raw(&raw).unwrap();
+        assert_eq!(event.reply_to_message_id.as_deref(), Some("1700000000.000100"));
+        assert_eq!(event.reply_to_user_id.as_deref(), Some("U1"));
+    }
+
+    #[test]
+    fn test_bot_message_flagged() {
+        let raw = serde_json::json!({
+            "team_id": "T0001",
+            "type": "event_callback",
+            "event": {
+                "type": "message",
+                "subtype": "bot_message",
+                "channel": "C1",
+                "bot_id": "B123",
+                "text": "automated",
+                "ts": "1700000000.000300"
+            }
+        });
+        let event = parse_slack_raw(&raw).unwrap();
+        assert!(event.sender_is_bot);
+        assert_eq!(event.sender_id, "B123");
+    }
+
+    #[test]
+    fn test_system_subtypes_ignored() {
+        let raw = serde_json::json!({
+            "team_id": "T0001",
+            "type": "event_callback",
+            "event": {
+                "type": "message",
+                "subtype": "message_deleted",
+                "channel": "C1",
+                "ts": "1700000000.000400",
+                "deleted_ts": "1700000000.000100"
+            }
+        });
+        assert!(parse_slack_raw(&raw).is_none());
+    }
+
+    #[test]
+    fn test_parse_member_joined_and_left() {
+        let joined = serde_json::json!({
+            "team_id": "T0001",
+            "type": "event_callback",
+            "event": {
+                "type": "member_joined_channel",
+                "user": "W06GH7XHN",
+                "channel": "C0698JE0H",
+                "channel_type": "C",
+                "team": "T0001"
+            }
+        });
+        let event = parse_slack_raw(&joined).unwrap();
+        assert!(event.is_join_event);
+        assert_eq!(event.join_user_id.as_deref(), Some("W06GH7XHN"));
+        assert_eq!(event.channel_id, "C0698JE0H");
+
+        let left = serde_json::json!({
+            "team_id": "T0001",
+            "type": "event_callback",
+            "event": {
+                "type": "member_left_channel",
+                "user": "W06GH7XHN",
+                "channel": "C0698JE0H",
+                "channel_type": "C",
+                "team": "T0001"
+            }
+        });
+        let event = parse_slack_raw(&left).unwrap();
+        assert!(event.is_leave_event);
+        assert_eq!(event.sender_id, "W06GH7XHN");
+    }
+
+    #[test]
+    fn test_parse_team_join() {
+        let raw = serde_json::json!({
+            "team_id": "T0001",
+            "type": "event_callback",
+            "event": {
+                "type": "team_join",
+                "user": {"id": "U55555", "is_bot": false}
+            }
+        });
+        let event = parse_slack_raw(&raw).unwrap();
+        assert!(event.is_join_event);
+        assert_eq!(event.group_id, "T0001");
+        assert_eq!(event.join_user_id.as_deref(), Some("U55555"));
+    }
+
+    #[test]
+    fn test_url_verification_not_an_event() {
+        let raw = serde_json::json!({
+            "token": "Jhj5dZrVaK7ZwHHjRyZWjbDl",
+            "challenge": "3eZbrw1aBm2rZgRNFdxV2595E9CY3gmdALWMmHkvFXO7tYXAYM8P",
+            "type": "url_verification"
+        });
+        assert!(parse_slack_raw(&raw).is_none());
+        assert_eq!(SlackAdapter::new().platform_name(), "slack");
+    }
+}
@@ -0,0 +1,392 @@
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::sync::Arc;
+use tracing::{debug, error, info, warn};
+
+use crate::platform::slack::parse_slack_raw;
+use crate::types::SignedMessage;
+use crate::AppState;
+
+/// Slack 请求时间戳允许的最大偏差（秒），超出视为重放
+const MAX_TIMESTAMP_SKEW_SECS: i64 = 5 * 60;
+
+/// POST /slack/events — 接收 Slack Events API 推送
+///
+/// 流程:
+/// 1. 验证 X-Slack-Signature（v0 HMAC-SHA256 + 5 分钟时间窗）
+/// 2. url_verification → 原样返回 challenge
+/// 3. event_callback → SlackAdapter 解析为 PlatformEvent（跳过 Bot 消息）
+/// 4. 签名 + 构造 SignedMessage（platform = "slack"）
+/// 5. 异步多播到 K 个节点，立即返回 200（Slack 要求 3 秒内响应）
+pub async fn handle_slack_events(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    body: axum::body::Bytes,
+) -> Response {
+    let Some(ref slack) = state.config.slack else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+
+    // 0. 限流检查（与 Telegram Webhook 共享）
+    if !state.webhook_limiter.check().await {
+        warn!("Slack Events 限流触发");
+        return StatusCode::TOO_MANY_REQUESTS.into_response();
+    }
+
+    // 1. 验证签名
+    let timestamp = header_str(&headers, "x-slack-request-timestamp");
+    let signature = header_str(&headers, "x-slack-signature");
+    if let Err(e) = verify_slack_signature(
+        &slack.signing_secret,
+        timestamp,
+        &body,
+        signature,
+        chrono::Utc::now().timestamp(),
+    ) {
+        warn!(error = e, "Slack 请求签名验证失败");
+        return StatusCode::UNAUTHORIZED.into_response();
+    }
+
+    let payload: serde_json::Value = match serde_json::from_slice(&body) {
+        Ok(v) => v,
+        Err(e) => {
+            warn!(error = %e, "无法解析 Slack Events JSON");
+            return StatusCode::BAD_REQUEST.into_response();
+        }
+    };
+
+    // 2. URL 验证握手（配置 Request URL 时 Slack 发送一次）
+    if payload.get("type").and_then(|v| v.as_str()) == Some("url_verification") {
+        let challenge = payload.get("challenge").and_then(|v| v.as_str()).unwrap_or("");
+        info!("Slack url_verification 完成");
+        return Json(serde_json::json!({ "challenge": challenge })).into_response();
+    }
+
+    // 3. 解析事件
+    let Some(event) = parse_slack_raw(&payload) else {
+        debug!(event_type = ?payload.pointer("/event/type"), "忽略不支持的 Slack 事件");
+        return StatusCode::OK.into_response();
+    };
+    if event.sender_is_bot {
+        return StatusCode::OK.into_response();
+    }
+
+    // 重试去重: 按 event_id 丢弃已处理的事件；首次投递未处理成功时，重试照常处理
+    let dedup_key = payload.get("event_id").and_then(|v| v.as_str()).map(str::to_string);
+    if let Some(ref event_id) = dedup_key {
+        if !state.local_store.mark_event_seen(event_id) {
+            debug!(
+                event_id,
+                retry = header_str(&headers, "x-slack-retry-num"),
+                reason = header_str(&headers, "x-slack-retry-reason"),
+                "跳过重复的 Slack 事件投递"
+            );
+            return StatusCode::OK.into_response();
+        }
+    }
+
+    // 4. 签名 + 构造 SignedMessage
+    let raw_json = body.to_vec();
+    let timestamp = chrono::Utc::now().timestamp() as u64;
+
+    let sequence = match state.sequence_manager.next() {
+        Ok(s) => s,
+        Err(e) => {
+            error!(error = %e, "Slack 序列号递增失败");
+            if let Some(ref event_id) = dedup_key {
+                state.local_store.forget_event(event_id);
+            }
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let (signature, message_hash) = state.key_manager.sign_message(
+        &slack.bot_id_hash,
+        sequence,
+        timestamp,
+        &raw_json,
+    );
+
+    let signed_message = SignedMessage {
+        owner_public_key: state.key_manager.public_key_hex(),
+        bot_id_hash: slack.bot_id_hash_hex(),
+        sequence,
+        timestamp,
+        message_hash: hex::encode(message_hash),
+        platform_event: payload,
+        owner_signature: hex::encode(signature),
+        platform: "slack".to_string(),
+    };
+
+    // 5. 异步多播到节点
+    let event_id = event.message_id;
+    let state_clone = state.clone();
+    tokio::spawn(async move {
+        match crate::multicaster::multicast_to_nodes(&state_clone, &signed_message).await {
+            Ok(r) => {
+                info!(
+                    event_id,
+                    sequence,
+                    success = r.success_count,
+                    failure = r.failure_count,
+                    "Slack 事件多播完成"
+                );
+            }
+            Err(e) => {
+                error!(event_id, sequence, error = %e, "Slack 事件多播失败");
+            }
+        }
+    });
+
+    StatusCode::OK.into_response()
+}
+
+fn header_str<'a>(headers: &'a HeaderMap, name: &str) -> &'a str {
+    headers.get(name).and_then(|v| v.to_str().ok()).unwrap_or("")
+}
+
+/// 验证 Slack 请求签名
+///
+/// ```text
+/// basestring = "v0:" + X-Slack-Request-Timestamp + ":" + body
+/// X-Slack-Signature = "v0=" + hex(HMAC-SHA256(signing_secret, basestring))
+/// ```
+///
+/// `now` 为当前 Unix 秒，时间戳偏差超过 5 分钟的请求拒绝（防重放）。
+pub fn verify_slack_signature(
+    signing_secret: &str,
+    timestamp: &str,
+    body: &[u8],
+    signature: &str,
+    now: i64,
+) -> Result<(), &'static str> {
+    let ts = timestamp.parse::<i64>().map_err(|_| "缺少或无效的 X-Slack-Request-Timestamp")?;
+    if (now - ts).abs() > MAX_TIMESTAMP_SKEW_SECS {
+        return Err("请求时间戳超出允许窗口");
+    }
+
+    let provided = signature.strip_prefix("v0=")
+        .and_then(|h| hex::decode(h).ok())
+        .ok_or("缺少或无效的 X-Slack-Signature")?;
+
+    let mut basestring = Vec::with_capacity(body.len() + timestamp.len() + 4);
+    basestring.extend_from_slice(b"v0:");
+    basestring.extend_from_slice(timestamp.as_bytes());
+    basestring.push(b':');
+    basestring.extend_from_slice(body);
+
+    // verify_slice 为常数时间比较，避免签名校验的时序侧信道
+    let mut mac = HmacSha256::new_from_slice(signing_secret.as_bytes())
+        .map_err(|_| "无效的 Slack signing secret")?;
+    mac.update(&basestring);
+    mac.verify_slice(&provided).map_err(|_| "签名不匹配")
+}
+
+type HmacSha256 = Hmac<Sha256>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use std::time::Instant;
+    use tokio::sync::RwLock;
+
+    fn sign(secret: &str, ts: &str, body: &[u8]) -> String {
+        let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(format!("v0:{}:", ts).as_bytes());
+        mac.update(body);
+        format!("v0={}", hex::encode(mac.finalize().into_bytes()))
+    }
+
+    #[test]
+    fn test_slack_documented_signature() {
+        // Slack 文档示例（api.slack.com/authentication/verifying-requests-from-slack）
+        let secret = "8f742231b10e8888abcd99yyyzzz85a5";
+        let ts = "1531420618";
+        let body = b"token=xyzz0WbapA4vBCDEFasx0q6G&team_id=T1DC2JH3J&team_domain=testteamnow&channel_id=G8PSS9T3V&channel_name=foobar&user_id=U2CERLKJA&user_name=roadrunner&command=%2Fwebhook-collect&text=&response_url=https%3A%2F%2Fhooks.slack.com%2Fcommands%2FT1DC2JH3J%2F397700885554%2F96rGlfmibIGlgcZRskXaIFfN&trigger_id=398738663015.47445629121.803a0bc887a14d10d2c447fce8b6703c";
+        let sig = "v0=a2114d57b48eac39b9ad189dd8316235a7b4a8d21a10bd27519666489c69b503";
+        assert!(verify_slack_signature(secret, ts, body, sig, 1531420618).is_ok());
+        assert_eq!(sign(secret, ts, body), sig);
+    }
+
+    #[test]
+    fn test_verify_slack_signature() {
+        let secret = "8f742231b10e8888abcd99yyyzzz85a5";
+        let body = br#"{"type":"event_callback","team_id":"T1"}"#;
+        let ts = "1531420618";
+        let now = 1531420618 + 10;
+        let sig = sign(secret, ts, body);
+
+        assert!(verify_slack_signature(secret, ts, body, &sig, now).is_ok());
+        // 错误密钥 / 篡改 body
+        assert!(verify_slack_signature("other_secret", ts, body, &sig, now).is_err());
+        assert!(verify_slack_signature(secret, ts, b"{}", &sig, now).is_err());
+        // 缺少 v0= 前缀 / 非 hex
+        assert!(verify_slack_signature(secret, ts, body, &sig[3..], now).is_err());
+        assert!(verify_slack_signature(secret, ts, body, "v0=zz", now).is_err());
+        // 缺少时间戳 / 超出 5 分钟窗口
+        assert!(verify_slack_signature(secret, "", body, &sig, now).is_err());
+        assert_eq!(
+            verify_slack_signature(secret, ts, body, &sig, 1531420618 + 301),
+            Err("请求时间戳超出允许窗口"),
+        );
+    }
+
+    /// 构造 Slack 模式的 AppState，节点列表指向 `node_endpoint`
+    fn slack_state(dir: &std::path::Path, node_endpoint: Option<String>) -> Arc<AppState> {
+        let data_dir = dir.to_str().unwrap();
+        let config = crate::config::AgentConfig {
+            platform: crate::config::PlatformMode::Slack,
+            bot_token: String::new(),
+            webhook_port: 0,
+            webhook_url: String::new(),
+            webhook_secret: "unused".into(),
+            chain_rpc: String::new(),
+            data_dir: data_dir.to_string(),
+            multicast_timeout_ms: 1000,
+            warn_ttl_days: 30,
+            execute_token: None,
+            bot_id_hash: [0u8; 32],
+            discord: None,
+            slack: Some(crate::config::SlackConfig {
+                bot_token: "xoxb-test".into(),
+                signing_secret: "test_signing_secret".into(),
+                bot_id_hash: [7u8; 32],
+            }),
+        };
+        let http_client = reqwest::Client::new();
+        let nodes = node_endpoint.map(|endpoint| vec![crate::types::NodeInfo {
+            node_id: "node1".into(),
+            endpoint,
+            node_public_key: String::new(),
+            status: "Active".into(),
+        }]).unwrap_or_default();
+
+        Arc::new(AppState {
+            config,
+            key_manager: crate::signer::KeyManager::load_or_generate(data_dir).unwrap(),
+            sequence_manager: crate::signer::SequenceManager::load_or_init(data_dir).unwrap(),
+            executor: crate::executor::TelegramExecutor::new(String::new(), http_client.clone()),
+            discord_executor: None,
+            slack_executor: None,
+            config_store: crate::group_config::ConfigStore::new(data_dir),
+            local_store: crate::local_store::LocalStore::new(),
+            http_client,
+            nodes: RwLock::new(nodes),
+            start_time: Instant::now(),
+            webhook_limiter: crate::rate_limiter::RateLimiter::new(60, 1000),
+            execute_limiter: crate::rate_limiter::RateLimiter::new(60, 200),
+        })
+    }
+
+    fn signed_headers(body: &[u8]) -> HeaderMap {
+        let ts = chrono::Utc::now().timestamp().to_string();
+        let mut headers = HeaderMap::new();
+        headers.insert("x-slack-request-timestamp", ts.parse().unwrap());
+        headers.insert("x-slack-signature", sign("test_signing_secret", &ts, body).parse().unwrap());
+        headers
+    }
+
+    #[tokio::test]
+    async fn test_url_verification_and_bad_signature() {
+        let dir = tempfile::tempdir().unwrap();
+        let state = slack_state(dir.path(), None);
+
+        let body = br#"{"token":"Jhj5dZrVaK7ZwHHjRyZWjbDl","challenge":"3eZbrw1aBm2rZgRNFdxV2595E9CY3gmdALWMmHkvFXO7tYXAYM8P","type":"url_verification"}"#;
+        let resp = handle_slack_events(
+            State(state.clone()), signed_headers(body), axum::body::Bytes::from_static(body),
+        ).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        let bytes = axum::body::to_bytes(resp.into_body(), 1024).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(json["challenge"], "3eZbrw1aBm2rZgRNFdxV2595E9CY3gmdALWMmHkvFXO7tYXAYM8P");
+
+        // 签名错误 → 401，且不消耗序列号
+        let mut headers = signed_headers(body);
+        headers.insert("x-slack-signature", format!("v0={}", "00".repeat(32)).parse().unwrap());
+        let resp = handle_slack_events(
+            State(state.clone()), headers, axum::body::Bytes::from_static(body),
+        ).await;
+        assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(state.sequence_manager.current(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_event_callback_multicast_to_node() {
+        // mock 节点记录收到的 SignedMessage
+        let received: Arc<Mutex<Vec<serde_json::Value>>> = Arc::new(Mutex::new(Vec::new()));
+        let rec = received.clone();
+        let app = axum::Router::new().route(
+            "/v1/message",
+            axum::routing::post(move |Json(msg): Json<serde_json::Value>| {
+                let rec = rec.clone();
+                async move {
+                    rec.lock().unwrap().push(msg);
+                    Json(serde_json::json!({"accepted": true}))
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let dir = tempfile::tempdir().unwrap();
+        let state = slack_state(dir.path(), Some(format!("http://{}", addr)));
+
+        // Bot 消息: 200 但不多播
+        let bot_body = br#"{"type":"event_callback","team_id":"T1","event":{"type":"message","subtype":"bot_message","bot_id":"B1","channel":"C1","text":"hi","ts":"1.0"}}"#;
+        let resp = handle_slack_events(
+            State(state.clone()), signed_headers(bot_body), axum::body::Bytes::from_static(bot_body),
+        ).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+
+        let body = br#"{"type":"event_callback","team_id":"T1","event_id":"Ev1","event":{"type":"message","channel":"C1","user":"U1","text":"!ban <@U2>","ts":"1700000000.000100"}}"#;
+        let resp = handle_slack_events(
+            State(state.clone()), signed_headers(body), axum::body::Bytes::from_static(body),
+        ).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(state.sequence_manager.current(), 1);
+
+        // 同一 event_id 的重试投递: 200 且不重复签名多播
+        let mut retry_headers = signed_headers(body);
+        retry_headers.insert("x-slack-retry-num", "1".parse().unwrap());
+        retry_headers.insert("x-slack-retry-reason", "http_timeout".parse().unwrap());
+        let resp = handle_slack_events(
+            State(state.clone()), retry_headers, axum::body::Bytes::from_static(body),
+        ).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(state.sequence_manager.current(), 1);
+
+        // 首次投递未被处理的事件: 超时重试照常处理
+        let unseen = br#"{"type":"event_callback","team_id":"T1","event_id":"Ev2","event":{"type":"message","channel":"C1","user":"U1","text":"hello","ts":"1700000001.000100"}}"#;
+        let mut retry_headers = signed_headers(unseen);
+        retry_headers.insert("x-slack-retry-num", "1".parse().unwrap());
+        retry_headers.insert("x-slack-retry-reason", "http_timeout".parse().unwrap());
+        let resp = handle_slack_events(
+            State(state.clone()), retry_headers, axum::body::Bytes::from_static(unseen),
+        ).await;
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(state.sequence_manager.current(), 2);
+
+        // 等待异步多播
+        for _ in 0..50 {
+            if received.lock().unwrap().len() >= 2 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        let mut msgs = received.lock().unwrap().clone();
+        msgs.sort_by_key(|m| m["sequence"].as_u64());
+        assert_eq!(msgs.len(), 2);
+        assert_eq!(msgs[0]["platform"], "slack");
+        assert_eq!(msgs[0]["bot_id_hash"], hex::encode([7u8; 32]));
+        assert_eq!(msgs[0]["sequence"], 1);
+        assert_eq!(msgs[0]["platform_event"]["event"]["text"], "!ban <@U2>");
+    }
+}
@@ -0,0 +1,498 @@
+use std::time::Duration;
+
+use reqwest::{Client, RequestBuilder, StatusCode};
+use serde_json::json;
+use tracing::{info, warn};
+
+use crate::executor::{
+    ActionType, AdminAction, ExecuteAction, ExecuteResult, MessageAction, PlatformExecutor,
+    QueryAction,
+};
+
+/// Slack Web API 基地址
+const SLACK_API_BASE: &str = "https://slack.com/api";
+
+/// 429 重试等待上限（秒）
+const MAX_RETRY_AFTER_SECS: u64 = 30;
+
+/// Slack Web API 执行器
+///
+/// 实现 PlatformExecutor trait，将 ExecuteAction 映射到 Slack Web API 调用。
+/// 参数命名与 nexus-node `SlackAdapter::action_to_api_call` 一致
+/// （`channel` / `ts` / `user_id` / `text`），同时兼容 `channel_id` / `message_id`。
+pub struct SlackExecutor {
+    bot_token: String,
+    client: Client,
+    api_base: String,
+}
+
+impl SlackExecutor {
+    pub fn new(bot_token: String, client: Client) -> Self {
+        Self::with_api_base(bot_token, client, SLACK_API_BASE.to_string())
+    }
+
+    /// 指定 API 基地址（测试用 mock server）
+    pub fn with_api_base(bot_token: String, client: Client, api_base: String) -> Self {
+        Self {
+            bot_token,
+            client,
+            api_base: api_base.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// POST JSON 调用 Slack 写方法（chat.* / pins.* / conversations.kick）
+    async fn call_slack_api(
+        &self,
+        api_method: &str,
+        body: serde_json::Value,
+    ) -> Result<(String, serde_json::Value), String> {
+        let url = format!("{}/{}", self.api_base, api_method);
+        self.send_with_retry(api_method, || {
+            self.client
+                .post(&url)
+                .bearer_auth(&self.bot_token)
+                .json(&body)
+        }).await
+    }
+
+    /// GET 查询参数调用 Slack 读方法（users.info / conversations.info 不接受 JSON body）
+    async fn call_slack_query(
+        &self,
+        api_method: &str,
+        query: &[(&str, &str)],
+    ) -> Result<(String, serde_json::Value), String> {
+        let url = format!("{}/{}", self.api_base, api_method);
+        self.send_with_retry(api_method, || {
+            self.client
+                .get(&url)
+                .bearer_auth(&self.bot_token)
+                .query(query)
+        }).await
+    }
+
+    /// 发送请求（429 → 等待 Retry-After 后重试一次）并检查 `ok` 字段
+    async fn send_with_retry<F>(
+        &self,
+        api_method: &str,
+        build: F,
+    ) -> Result<(String, serde_json::Value), String>
+    where
+        F: Fn() -> RequestBuilder,
+    {
+        let mut response = build().send().await
+            .map_err(|e| format!("Slack API 请求失败: {}", e))?;
+
+        if response.status() == StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = response.headers()
+                .get("retry-after")
+                .and_then(|v| v.to_str().ok())
+                .and_then(|s| s.parse::<u64>().ok())
+                .unwrap_or(1)
+                .min(MAX_RETRY_AFTER_SECS);
+
+            warn!(retry_after_s = retry_after, method = api_method, "Slack 429 rate limit");
+            tokio::time::sleep(Duration::from_secs(retry_after)).await;
+
+            response = build().send().await
+                .map_err(|e| format!("Slack API 重试失败: {}", e))?;
+        }
+
+        let status = response.status();
+        if !status.is_success() {
+            let body_text = response.text().await.unwrap_or_default();
+            return Err(format!("Slack API HTTP 错误 {}: {}", status, body_text));
+        }
+
+        let body: serde_json::Value = response.json().await
+            .map_err(|e| format!("Slack API 响应解析失败: {}", e))?;
+
+        // Slack Web API 总是返回 200，业务错误通过 ok=false + error 表达
+        if body.get("ok").and_then(|v| v.as_bool()) != Some(true) {
+            let error = body.get("error").and_then(|v| v.as_str()).unwrap_or("unknown_error");
+            return Err(format!("Slack API 错误 {}: {}", api_method, error));
+        }
+
+        Ok((api_method.to_string(), body))
+    }
+
+    /// 签名回执（复用 TelegramExecutor 相同逻辑）
+    fn sign_receipt(
+        key_manager: &crate::signer::KeyManager,
+        action_id: &str,
+        method: &str,
+        response: &serde_json::Value,
+    ) -> String {
+        use sha2::{Sha256, Digest};
+
+        let response_bytes = serde_json::to_vec(response).unwrap_or_default();
+        let mut hasher = Sha256::new();
+        hasher.update(&response_bytes);
+        let response_hash = hasher.finalize();
+
+        let mut receipt_data = Vec::new();
+        receipt_data.extend_from_slice(action_id.as_bytes());
+        receipt_data.extend_from_slice(method.as_bytes());
+        receipt_data.extend_from_slice(&response_hash);
+
+        let sig = key_manager.sign(&receipt_data);
+        format!("{}:{}", key_manager.public_key_hex(), hex::encode(sig))
+    }
+
+    // ═══════════════════════════════════════════════════════════════
+    // Slack Web API 方法映射
+    // ═══════════════════════════════════════════════════════════════
+
+    /// chat.postMessage
+    async fn send_message(&self, action: &ExecuteAction) -> Result<(String, serde_json::Value), String> {
+        let channel = channel_param(action)?;
+        let text = action.params.get("text")
+            .and_then(|v| v.as_str())
+            .unwrap_or("");
+
+        let mut body = json!({"channel": channel, "text": text});
+        // 线程内回复
+        if let Some(thread_ts) = action.params.get("thread_ts").and_then(|v| v.as_str()) {
+            body["thread_ts"] = json!(thread_ts);
+        }
+        if let Some(blocks) = action.params.get("blocks") {
+            body["blocks"] = blocks.clone();
+        }
+
+        self.call_slack_api("chat.postMessage", body).await
+    }
+
+    /// chat.delete
+    async fn delete_message(&self, action: &ExecuteAction) -> Result<(String, serde_json::Value), String> {
+        let channel = channel_param(action)?;
+        let ts = ts_param(action)?;
+
+        self.call_slack_api("chat.delete", json!({"channel": channel, "ts": ts})).await
+    }
+
+    /// chat.update
+    async fn edit_message(&self, action: &ExecuteAction) -> Result<(String, serde_json::Value), String> {
+        let channel = channel_param(action)?;
+        let ts = ts_param(action)?;
+        let text = action.params.get("text")
+            .and_then(|v| v.as_str())
+            .ok_or("缺少 text")?;
+
+        self.call_slack_api(
+            "chat.update",
+            json!({"channel": channel, "ts": ts, "text": text}),
+        ).await
+    }
+
+    /// pins.add
+    async fn pin_message(&self, action: &ExecuteAction) -> Result<(String, serde_json::Value), String> {
+        let channel = channel_param(action)?;
+        let ts = ts_param(action)?;
+
+        self.call_slack_api("pins.add", json!({"channel": channel, "timestamp": ts})).await
+    }
+
+    /// pins.remove
+    async fn unpin_message(&self, action: &ExecuteAction) -> Result<(String, serde_json::Value), String> {
+        let channel = channel_param(action)?;
+        let ts = ts_param(action)?;
+
+        self.call_slack_api("pins.remove", json!({"channel": channel, "timestamp": ts})).await
+    }
+
+    /// conversations.kick — Slack 无永久封禁 API，Ban / Kick 均映射为移出频道
+    async fn kick_user(&self, action: &ExecuteAction) -> Result<(String, serde_json::Value), String> {
+        let channel = channel_param(action)?;
+        let user = user_param(action)?;
+
+        self.call_slack_api("conversations.kick", json!({"channel": channel, "user": user})).await
+    }
+
+    /// users.info
+    async fn get_member(&self, action: &ExecuteAction) -> Result<(String, serde_json::Value), String> {
+        let user = user_param(action)?;
+
+        self.call_slack_query("users.info", &[("user", user)]).await
+    }
+
+    /// conversations.info
+    async fn get_channel(&self, action: &ExecuteAction) -> Result<(String, serde_json::Value), String> {
+        let channel = channel_param(action)?;
+
+        self.call_slack_query("conversations.info", &[("channel", channel)]).await
+    }
+
+    /// auth.test
+    async fn get_me(&self) -> Result<(String, serde_json::Value), String> {
+        self.call_slack_api("auth.test", json!({})).await
+    }
+}
+
+/// 辅助: 按顺序读取第一个存在的字符串参数
+fn str_param<'a>(action: &'a ExecuteAction, keys: &[&str]) -> Option<&'a str> {
+    keys.iter().find_map(|k| action.params.get(*k).and_then(|v| v.as_str()))
+}
+
+fn channel_param(action: &ExecuteAction) -> Result<&str, String> {
+    str_param(action, &["channel", "channel_id"]).ok_or_else(|| "缺少 channel".to_string())
+}
+
+fn ts_param(action: &ExecuteAction) -> Result<&str, String> {
+    str_param(action, &["ts", "message_id"]).ok_or_else(|| "缺少 ts".to_string())
+}
+
+fn user_param(action: &ExecuteAction) -> Result<&str, String> {
+    str_param(action, &["user_id", "user"]).ok_or_else(|| "缺少 user_id".to_string())
+}
+
+impl PlatformExecutor for SlackExecutor {
+    async fn execute(&self, action: &ExecuteAction, key_manager: &crate::signer::KeyManager) -> ExecuteResult {
+        let result = match &action.action_type {
+            ActionType::Message(MessageAction::Send) => self.send_message(action).await,
+            ActionType::Message(MessageAction::Delete) => self.delete_message(action).await,
+            ActionType::Message(MessageAction::Pin) => self.pin_message(action).await,
+            ActionType::Message(MessageAction::Unpin) => self.unpin_message(action).await,
+            ActionType::Message(MessageAction::EditText) => self.edit_message(action).await,
+
+            ActionType::Admin(AdminAction::Ban) | ActionType::Admin(AdminAction::Kick) => {
+                self.kick_user(action).await
+            }
+
+            ActionType::Query(QueryAction::GetChatMember) => self.get_member(action).await,
+            ActionType::Query(QueryAction::GetChat) => self.get_channel(action).await,
+            ActionType::Query(QueryAction::GetMe) => self.get_me().await,
+
+            ActionType::NoAction => {
+                return ExecuteResult {
+                    action_id: action.action_id.clone(),
+                    success: true,
+                    error: None,
+                    api_method: Some("NoAction".into()),
+                    api_response: Some(json!({"ok": true})),
+                    agent_signature: None,
+                };
+            }
+
+            other => {
+                return ExecuteResult {
+                    action_id: action.action_id.clone(),
+                    success: false,
+                    error: Some(format!("Slack 不支持的动作类型: {:?}", other)),
+                    api_method: None,
+                    api_response: None,
+                    agent_signature: None,
+                };
+            }
+        };
+
+        match result {
+            Ok((method, response)) => {
+                info!(action_id = action.action_id, method = %method, "Slack API 执行成功");
+                let sig = Self::sign_receipt(key_manager, &action.action_id, &method, &response);
+                ExecuteResult {
+                    action_id: action.action_id.clone(),
+                    success: true,
+                    error: None,
+                    api_method: Some(method),
+                    api_response: Some(response),
+                    agent_signature: Some(sig),
+                }
+            }
+            Err(e) => {
+                warn!(action_id = action.action_id, error = %e, "Slack API 执行失败");
+                ExecuteResult {
+                    action_id: action.action_id.clone(),
+                    success: false,
+                    error: Some(e),
+                    api_method: None,
+                    api_response: None,
+                    agent_signature: None,
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    use axum::{
+        extract::{Path, RawQuery, State},
+        http::HeaderMap,
+        routing::any,
+        Router,
+    };
+
+    /// mock server 记录的请求: (api_method, authorization, query, body)
+    type Recorded = Arc<Mutex<Vec<(String, String, String, serde_json::Value)>>>;
+
+    /// 启动 mock Slack Web API，返回 (api_base, 请求记录)
+    ///
+    /// `not_in_channel` 频道模拟业务错误（HTTP 200 + ok=false）
+    async fn spawn_mock_slack() -> (String, Recorded) {
+        let recorded: Recorded = Arc::new(Mutex::new(Vec::new()));
+
+        async fn handler(
+            State(rec): State<Recorded>,
+            Path(method): Path<String>,
+            RawQuery(query): RawQuery,
+            headers: HeaderMap,
+            body: axum::body::Bytes,
+        ) -> axum::Json<serde_json::Value> {
+            let auth = headers.get("authorization")
+                .and_then(|v| v.to_str().ok())
+                .unwrap_or("")
+                .to_string();
+            let body: serde_json::Value = serde_json::from_slice(&body).unwrap_or(json!(null));
+            let failing = body.get("channel").and_then(|v| v.as_str()) == Some("not_in_channel");
+            rec.lock().unwrap().push((method.clone(), auth, query.unwrap_or_default(), body));
+
+            if failing {
+                return axum::Json(json!({"ok": false, "error": "not_in_channel"}));
+            }
+            axum::Json(match method.as_str() {
+                "chat.postMessage" => json!({"ok": true, "channel": "C1", "ts": "1700000000.000100"}),
+                "users.info" => json!({"ok": true, "user": {"id": "U1", "is_admin": false}}),
+                _ => json!({"ok": true}),
+            })
+        }
+
+        let app = Router::new()
+            .route("/api/:method", any(handler))
+            .with_state(recorded.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        (format!("http://{}/api", addr), recorded)
+    }
+
+    fn slack_action(action_type: ActionType, params: serde_json::Value) -> ExecuteAction {
+        ExecuteAction {
+            action_id: "act_sl_001".into(),
+            action_type,
+            bot_id_hash: String::new(),
+            chat_id: 0,
+            params,
+            leader_signature: String::new(),
+            leader_node_id: String::new(),
+            consensus_nodes: vec![],
+            platform: "slack".into(),
+        }
+    }
+
+    fn test_key_manager() -> (tempfile::TempDir, crate::signer::KeyManager) {
+        let dir = tempfile::tempdir().unwrap();
+        let km = crate::signer::KeyManager::load_or_generate(dir.path().to_str().unwrap()).unwrap();
+        (dir, km)
+    }
+
+    #[tokio::test]
+    async fn test_send_message_against_mock() {
+        let (base, rec) = spawn_mock_slack().await;
+        let exec = SlackExecutor::with_api_base("xoxb-test".into(), Client::new(), base);
+        let (_dir, km) = test_key_manager();
+
+        let action = slack_action(
+            ActionType::Message(MessageAction::Send),
+            json!({"channel": "C1", "text": "hello", "thread_ts": "1699999999.000001"}),
+        );
+        let result = exec.execute(&action, &km).await;
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(result.api_method.as_deref(), Some("chat.postMessage"));
+        assert_eq!(result.api_response.unwrap()["ts"], "1700000000.000100");
+        assert!(result.agent_signature.is_some());
+
+        let calls = rec.lock().unwrap();
+        assert_eq!(calls.len(), 1);
+        let (method, auth, _, body) = &calls[0];
+        assert_eq!(method, "chat.postMessage");
+        assert_eq!(auth, "Bearer xoxb-test");
+        assert_eq!(body["channel"], "C1");
+        assert_eq!(body["text"], "hello");
+        assert_eq!(body["thread_ts"], "1699999999.000001");
+    }
+
+    #[tokio::test]
+    async fn test_action_mapping_against_mock() {
+        let (base, rec) = spawn_mock_slack().await;
+        let exec = SlackExecutor::with_api_base("xoxb-test".into(), Client::new(), base);
+        let (_dir, km) = test_key_manager();
+
+        // 节点侧参数名 (channel / ts / user_id) 与 Discord 风格别名 (channel_id / message_id)
+        let cases = vec![
+            (ActionType::Message(MessageAction::Delete), json!({"channel": "C1", "ts": "1.1"}), "chat.delete"),
+            (ActionType::Message(MessageAction::Pin), json!({"channel_id": "C1", "message_id": "1.2"}), "pins.add"),
+            (ActionType::Message(MessageAction::Unpin), json!({"channel": "C1", "ts": "1.2"}), "pins.remove"),
+            (ActionType::Message(MessageAction::EditText), json!({"channel": "C1", "ts": "1.3", "text": "edited"}), "chat.update"),
+            (ActionType::Admin(AdminAction::Ban), json!({"channel": "C1", "user_id": "U9"}), "conversations.kick"),
+            (ActionType::Admin(AdminAction::Kick), json!({"channel": "C1", "user_id": "U9"}), "conversations.kick"),
+            (ActionType::Query(QueryAction::GetChatMember), json!({"user_id": "U1"}), "users.info"),
+            (ActionType::Query(QueryAction::GetChat), json!({"channel": "C1"}), "conversations.info"),
+            (ActionType::Query(QueryAction::GetMe), json!({}), "auth.test"),
+        ];
+        for (action_type, params, expected) in cases {
+            let result = exec.execute(&slack_action(action_type, params), &km).await;
+            assert!(result.success, "{}: {:?}", expected, result.error);
+            assert_eq!(result.api_method.as_deref(), Some(expected));
+        }
+
+        let calls = rec.lock().unwrap();
+        let methods: Vec<&str> = calls.iter().map(|c| c.0.as_str()).collect();
+        assert_eq!(methods, vec![
+            "chat.delete", "pins.add", "pins.remove", "chat.update",
+            "conversations.kick", "conversations.kick",
+            "users.info", "conversations.info", "auth.test",
+        ]);
+        assert_eq!(calls[1].3, json!({"channel": "C1", "timestamp": "1.2"}));
+        assert_eq!(calls[4].3, json!({"channel": "C1", "user": "U9"}));
+        // 读方法走查询参数
+        assert_eq!(calls[6].2, "user=U1");
+        assert_eq!(calls[7].2, "channel=C1");
+    }
+
+    #[tokio::test]
+    async fn test_ok_false_is_failure() {
+        let (base, _rec) = spawn_mock_slack().await;
+        let exec = SlackExecutor::with_api_base("xoxb-test".into(), Client::new(), base);
+        let (_dir, km) = test_key_manager();
+
+        let action = slack_action(
+            ActionType::Admin(AdminAction::Kick),
+            json!({"channel": "not_in_channel", "user_id": "U9"}),
+        );
+        let result = exec.execute(&action, &km).await;
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("not_in_channel"));
+        assert!(result.agent_signature.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_missing_params_and_unsupported_actions() {
+        // 不会发出请求，API 地址无需可达
+        let exec = SlackExecutor::with_api_base(
+            "xoxb-test".into(), Client::new(), "http://127.0.0.1:9/api".into(),
+        );
+        let (_dir, km) = test_key_manager();
+
+        let result = exec.execute(
+            &slack_action(ActionType::Message(MessageAction::Delete), json!({"channel": "C1"})),
+            &km,
+        ).await;
+        assert!(!result.success);
+        assert_eq!(result.error.as_deref(), Some("缺少 ts"));
+
+        let result = exec.execute(
+            &slack_action(ActionType::Admin(AdminAction::Mute), json!({"channel": "C1", "user_id": "U1"})),
+            &km,
+        ).await;
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("Slack 不支持"));
+
+        let result = exec.execute(&slack_action(ActionType::NoAction, json!({})), &km).await;
+        assert!(result.success);
+    }
+}
@@ -255,7 +255,20 @@ pub async fn handle_execute(
             crate::executor::ExecuteResult {
                 action_id: action.action_id.clone(),
                 success: false,
-                error: Some("Discord executor 未配置（PLATFORM 未设置为 discord 或 both）".into()),
+                error: Some("Discord executor 未配置（PLATFORM 未设置为 discord、both 或 all）".into()),
+                api_method: None,
+                api_response: None,
+                agent_signature: None,
+            }
+        }
+    } else if action.platform == "slack" {
+        if let Some(ref sl_exec) = state.slack_executor {
+            sl_exec.execute(&action, &state.key_manager).await
+        } else {
+            crate::executor::ExecuteResult {
+                action_id: action.action_id.clone(),
+                success: false,
+                error: Some("Slack executor 未配置（PLATFORM 未设置为 slack 或 all）".into()),
                 api_method: None,
                 api_response: None,
                 agent_signature: None,
@@ -3,10 +3,11 @@ use axum::{
     http::StatusCode,
     Json,
 };
+use serde::Deserialize;
 use std::sync::Arc;
 use tracing::{warn, debug};
 
-use crate::types::SignedMessage;
+use crate::types::{GroupConfig, MessageStatus, SignedMessage};
 use crate::AppState;
 
 /// POST /v1/message — 接收 Agent 多播的签名消息
@@ -56,13 +57,16 @@ pub async fn handle_health(
     let active_msgs = state.gossip_engine.state.active_message_count();
     let processed = state.gossip_engine.state.messages_processed
         .load(std::sync::atomic::Ordering::Relaxed);
+    let submitter = state.chain_submitter.health().await;
+    let status = if submitter.healthy { "ok" } else { "degraded" };
 
     Json(serde_json::json!({
-        "status": "ok",
+        "status": status,
         "node_id": state.config.node_id,
         "uptime_seconds": uptime,
         "active_messages": active_msgs,
         "messages_processed": processed,
+        "chain_submitter": submitter,
     }))
 }
 
@@ -78,3 +82,92 @@ pub async fn handle_message_status(
         "status": format!("{:?}", status),
     }))
 }
+
+/// POST /v1/rules/dry-run 请求体
+#[derive(Debug, Deserialize)]
+pub struct DryRunRequest {
+    /// 待评估的消息（不校验签名，不进入共识）
+    pub message: SignedMessage,
+    /// 可选: 未发布的群配置（测试新规则），缺省使用缓存中的已签名配置
+    #[serde(default)]
+    pub config: Option<GroupConfig>,
+}
+
+/// POST /v1/rules/dry-run — 规则试运行（调试用）
+///
+/// 返回命中的规则、动作与逐条评估轨迹，不执行任何动作。
+pub async fn handle_rules_dry_run(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<DryRunRequest>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    match state.rule_engine.dry_run(&req.message, req.config, Some(&state.chain_cache)) {
+        Ok(report) => (StatusCode::OK, Json(serde_json::json!(report))),
+        Err(e) => (StatusCode::BAD_REQUEST, Json(serde_json::json!({ "error": e }))),
+    }
+}
+
+/// GET /metrics — Prometheus 指标
+///
+/// 抓取时刷新瞬时值（活跃消息数 / 对等连接数 / 待提交队列长度）
+pub async fn handle_metrics(
+    State(state): State<Arc<AppState>>,
+) -> impl axum::response::IntoResponse {
+    let m = crate::metrics::metrics();
+    m.active_messages.set(state.gossip_engine.state.active_message_count() as i64);
+    m.gossip_peers.set(state.gossip_peers.len().await as i64);
+    let (c, a, e) = state.chain_submitter.queue_sizes().await;
+    m.chain_queue_size.with_label_values(&["confirmation"]).set(c as i64);
+    m.chain_queue_size.with_label_values(&["action_log"]).set(a as i64);
+    m.chain_queue_size.with_label_values(&["equivocation"]).set(e as i64);
+
+    (
+        [(axum::http::header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        m.render(),
+    )
+}
+
+/// GET /v1/peers — Gossip 对等连接（调试用）
+///
+/// `peers` 为已认证连接，`disconnected` 为 ChainCache 中已知但未连接的节点
+pub async fn handle_peers(
+    State(state): State<Arc<AppState>>,
+) -> Json<serde_json::Value> {
+    let peers = state.gossip_peers.snapshot().await;
+    let mut disconnected: Vec<String> = state.chain_cache.get_active_node_ids()
+        .into_iter()
+        .filter(|id| *id != state.config.node_id && !peers.iter().any(|p| p.node_id == *id))
+        .collect();
+    disconnected.sort();
+
+    Json(serde_json::json!({
+        "node_id": state.config.node_id,
+        "peers": peers,
+        "disconnected": disconnected,
+    }))
+}
+
+/// GET /v1/messages 查询参数
+#[derive(Debug, Deserialize)]
+pub struct MessagesQuery {
+    /// 按状态过滤（如 `HeardViaSeen` / `Confirmed`）
+    pub status: Option<MessageStatus>,
+    /// 仅返回存活时间 ≥ 该值的消息（毫秒），用于定位卡住的消息
+    #[serde(default)]
+    pub min_age_ms: u64,
+    /// 最多返回条数（默认 100，上限 1000）
+    pub limit: Option<usize>,
+}
+
+/// GET /v1/messages — 消息状态列表（调试用，最老的在前）
+pub async fn handle_messages(
+    State(state): State<Arc<AppState>>,
+    axum::extract::Query(query): axum::extract::Query<MessagesQuery>,
+) -> Json<serde_json::Value> {
+    let limit = query.limit.unwrap_or(100).min(1000);
+    let messages = state.gossip_engine.state.snapshot(query.status, query.min_age_ms, limit);
+
+    Json(serde_json::json!({
+        "total": state.gossip_engine.state.active_message_count(),
+        "messages": messages,
+    }))
+}
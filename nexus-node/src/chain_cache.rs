@@ -7,8 +7,8 @@ use crate::types::{BotInfoCache, NodeInfoCache, SignedGroupConfig};
 
 /// 链上数据缓存（从链上读取，本地缓存）
 ///
-/// TODO: Sprint 3 Week 3 — 使用 subxt 订阅链上事件自动刷新
-/// 当前: 从环境变量加载静态数据（开发用）
+/// 启动时从环境变量加载静态数据（开发用），连接链后由 `ChainClient`
+/// 全量刷新，并随 finalized 区块中的 BotRegistry / BotConsensus 事件增量更新
 pub struct ChainCache {
     /// Bot 注册信息: bot_id_hash → BotInfoCache
     bots: RwLock<HashMap<String, BotInfoCache>>,
@@ -49,7 +49,7 @@ impl ChainCache {
     /// 从环境变量初始化（开发用）
     ///
     /// BOT_REGISTRATIONS=bot_id_hash:owner_pubkey:platform,...
-    /// NODE_LIST=node_id:endpoint:pubkey,...
+    /// NODE_LIST=node_id@endpoint@pubkey_hex,...（公钥缺省时节点无法通过 Gossip 认证）
     pub fn load_from_env(&self) {
         // 加载 Bot 注册
         if let Ok(bots_str) = std::env::var("BOT_REGISTRATIONS") {
@@ -88,10 +88,17 @@ impl ChainCache {
                     let node_id = parts[0].to_string();
                     let endpoint = parts[1].to_string();
 
+                    let mut pk = [0u8; 32];
+                    if let Some(Ok(bytes)) = parts.get(2).map(hex::decode) {
+                        if bytes.len() == 32 {
+                            pk.copy_from_slice(&bytes);
+                        }
+                    }
+
                     nodes.insert(node_id.clone(), NodeInfoCache {
                         node_id: node_id.clone(),
                         endpoint,
-                        node_public_key: [0u8; 32],
+                        node_public_key: pk,
                         status: "Active".to_string(),
                         reputation: 5000,
                     });
@@ -129,6 +136,11 @@ impl ChainCache {
         self.bots.write().unwrap_or_else(|e| e.into_inner()).insert(info.bot_id_hash.clone(), info);
     }
 
+    /// 移除 Bot（链上记录已删除）
+    pub fn remove_bot(&self, bot_id_hash: &str) {
+        self.bots.write().unwrap_or_else(|e| e.into_inner()).remove(bot_id_hash);
+    }
+
     /// 添加节点
     pub fn add_node(&self, info: NodeInfoCache) {
         self.nodes.write().unwrap_or_else(|e| e.into_inner()).insert(info.node_id.clone(), info);
@@ -139,6 +151,25 @@ impl ChainCache {
         self.nodes.write().unwrap_or_else(|e| e.into_inner()).remove(node_id);
     }
 
+    /// 同步链上节点信息
+    ///
+    /// 链上只有 endpoint 哈希，链上记录的 endpoint 为空时保留本地已知的 endpoint。
+    pub fn sync_node(&self, mut info: NodeInfoCache) {
+        let mut nodes = self.nodes.write().unwrap_or_else(|e| e.into_inner());
+        if info.endpoint.is_empty() {
+            if let Some(existing) = nodes.get(&info.node_id) {
+                info.endpoint = existing.endpoint.clone();
+            }
+        }
+        nodes.insert(info.node_id.clone(), info);
+    }
+
+    /// 只保留指定节点（链上活跃列表全量同步后调用）
+    pub fn retain_nodes(&self, node_ids: &[String]) {
+        self.nodes.write().unwrap_or_else(|e| e.into_inner())
+            .retain(|id, _| node_ids.contains(id));
+    }
+
     // ═══════════════════════════════════════════════════════════════
     // 群配置管理 (Sprint 8)
     // ═══════════════════════════════════════════════════════════════
@@ -149,6 +180,7 @@ impl ChainCache {
     /// 1. 根据 bot_id_hash 查找 Bot 注册信息
     /// 2. 验证签名者公钥 == Bot owner_public_key
     /// 3. 验证 Ed25519 签名 (config JSON → signature)
+    /// 3b. 校验自定义规则可编译（rule_dsl）
     /// 4. 版本号单调递增检查
     /// 5. 存入缓存 + 持久化到本地 JSON
     pub fn apply_signed_config(&self, bot_id_hash: &str, signed_config: SignedGroupConfig) -> Result<(), String> {
@@ -169,6 +201,10 @@ impl ChainCache {
         // 3. 验证 Ed25519 签名
         self.verify_config_signature(&signed_config)?;
 
+        // 3b. 自定义规则必须可编译（所有节点同样拒绝，保证决策一致）
+        crate::rule_dsl::compile(&signed_config.config.custom_rules)
+            .map_err(|e| format!("Invalid custom rules: {}", e))?;
+
         // 4. 版本号单调递增
         {
             let configs = self.group_configs.read().unwrap_or_else(|e| e.into_inner());
@@ -309,7 +345,7 @@ impl ChainCache {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::{GroupConfig, SignedGroupConfig, JoinApprovalPolicy, BotInfoCache};
+    use crate::types::{GroupConfig, SignedGroupConfig, JoinApprovalPolicy, BotInfoCache, NodeInfoCache};
     use ed25519_dalek::{SigningKey, Signer};
     use tempfile::TempDir;
 
@@ -346,6 +382,7 @@ mod tests {
             spam_detection_enabled: false,
             spam_max_emoji: 0,
             spam_first_messages_only: 0,
+            custom_rules: vec![],
         }
     }
 
@@ -442,6 +479,29 @@ mod tests {
         assert!(err.contains("Signature verification failed"));
     }
 
+    #[test]
+    fn test_invalid_custom_rules_rejected() {
+        let key = make_signing_key();
+        let (cache, _tmp) = setup_cache_with_bot(&key);
+
+        let mut config = make_group_config("bot_abc", 1);
+        config.custom_rules = vec![crate::types::CustomRule {
+            id: "bad".into(),
+            priority: 100,
+            enabled: true,
+            when: crate::types::RuleCondition::TextRegex("(".into()),
+            then: crate::types::CustomRuleAction {
+                action_type: crate::types::ActionType::NoAction,
+                params: serde_json::Value::Null,
+            },
+        }];
+        let signed = sign_config(&key, &config);
+
+        let err = cache.apply_signed_config("bot_abc", signed).unwrap_err();
+        assert!(err.contains("Invalid custom rules"));
+        assert_eq!(cache.get_config_version("bot_abc"), 0);
+    }
+
     #[test]
     fn test_unknown_bot_rejected() {
         let key = make_signing_key();
@@ -478,6 +538,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_sync_node_keeps_known_endpoint() {
+        let tmp = TempDir::new().unwrap();
+        let cache = ChainCache::with_data_dir(tmp.path().to_path_buf());
+        let node = |id: &str, endpoint: &str, reputation: u16| NodeInfoCache {
+            node_id: id.into(),
+            endpoint: endpoint.into(),
+            node_public_key: [0u8; 32],
+            status: "Active".into(),
+            reputation,
+        };
+        cache.add_node(node("node-1", "ws://10.0.0.1:9000", 5000));
+        cache.add_node(node("node-2", "ws://10.0.0.2:9000", 5000));
+
+        // 链上记录无 endpoint 明文 → 保留本地值，其余字段以链上为准
+        cache.sync_node(node("node-1", "", 8000));
+        let synced = cache.get_node("node-1").unwrap();
+        assert_eq!(synced.endpoint, "ws://10.0.0.1:9000");
+        assert_eq!(synced.reputation, 8000);
+
+        // 新节点直接加入
+        cache.sync_node(node("node-3", "", 5000));
+        assert!(cache.get_node("node-3").unwrap().endpoint.is_empty());
+
+        // 不在链上活跃列表中的节点被移除
+        cache.retain_nodes(&["node-1".into(), "node-3".into()]);
+        assert_eq!(cache.get_active_node_ids(), vec!["node-1", "node-3"]);
+    }
+
     #[test]
     fn test_get_config_bot_ids() {
         let key = make_signing_key();
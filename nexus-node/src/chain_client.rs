@@ -1,15 +1,26 @@
+use codec::{Decode, DecodeAll, Encode};
+use subxt::blocks::ExtrinsicEvents;
+use subxt::config::DefaultExtrinsicParamsBuilder;
+use subxt::tx::{DynamicPayload, Signer};
 use subxt::{OnlineClient, SubstrateConfig, dynamic::Value};
 use subxt_signer::sr25519::Keypair;
+use tokio::sync::Mutex;
 use tracing::{info, warn, debug};
 
+use crate::chain_cache::ChainCache;
 use crate::types::{BotInfoCache, NodeInfoCache};
 
+/// finalized 区块类型
+pub type FinalizedBlock = subxt::blocks::Block<SubstrateConfig, OnlineClient<SubstrateConfig>>;
+
 /// Substrate 链客户端
 ///
 /// 使用 subxt dynamic API — 不需要编译时 metadata 生成
 pub struct ChainClient {
     api: OnlineClient<SubstrateConfig>,
     signer: Keypair,
+    /// 下一笔交易的 nonce（None = 需从链上读取）
+    nonce: Mutex<Option<u64>>,
 }
 
 impl ChainClient {
@@ -26,7 +37,7 @@ impl ChainClient {
             "链连接成功"
         );
 
-        Ok(Self { api, signer })
+        Ok(Self { api, signer, nonce: Mutex::new(None) })
     }
 
     // ═══════════════════════════════════════════════════════════════
@@ -42,47 +53,44 @@ impl ChainClient {
         let result = self.api.storage().at_latest().await?.fetch(&query).await?;
 
         match result {
-            Some(val) => {
-                // 解码为 JSON 字符串，再提取字段
-                let decoded = val.to_value()?;
-                let json_str = format!("{:?}", decoded);
-                debug!(raw = json_str, "Bot 原始数据");
-
-                warn!("fetch_bot 返回占位值 — SCALE 解码未实现，公钥为全零");
-                Ok(Some(BotInfoCache {
-                    bot_id_hash: hex::encode(bot_id_hash),
-                    owner_public_key: [0u8; 32], // TODO: 从 decoded 提取
-                    platform: "telegram".into(),
-                    is_active: true,
-                }))
-            }
+            Some(val) => Ok(Some(decode_bot(val.encoded())?)),
             None => Ok(None),
         }
     }
 
+    /// 从链上读取全部 Bot 注册信息
+    pub async fn fetch_all_bots(&self) -> anyhow::Result<Vec<BotInfoCache>> {
+        use futures_util::StreamExt;
+
+        let query = subxt::dynamic::storage("BotRegistry", "Bots", Vec::<Value>::new());
+        let mut iter = self.api.storage().at_latest().await?.iter(query).await?;
+
+        let mut bots = Vec::new();
+        while let Some(entry) = iter.next().await {
+            let entry = entry?;
+            match decode_bot(entry.value.encoded()) {
+                Ok(bot) => bots.push(bot),
+                Err(e) => warn!(error = %e, "Bot 记录解码失败，已跳过"),
+            }
+        }
+        Ok(bots)
+    }
+
     /// 从链上读取活跃节点列表
-    pub async fn fetch_active_node_list(&self) -> anyhow::Result<Vec<[u8; 32]>> {
+    pub async fn fetch_active_node_list(&self) -> anyhow::Result<Vec<Vec<u8>>> {
         let query = subxt::dynamic::storage(
             "BotConsensus", "ActiveNodeList", vec![],
         );
         let result = self.api.storage().at_latest().await?.fetch(&query).await?;
 
         match result {
-            Some(val) => {
-                // 原始 SCALE 编码的 bytes → 手动解码 Vec<[u8;32]>
-                let raw = val.encoded();
-                let node_ids = Vec::new();
-                // SCALE: Vec prefix = compact length, then N * 32 bytes
-                // 简化: 仅记录日志，实际解码需要 codec
-                warn!(raw_len = raw.len(), "fetch_active_node_list 返回空列表 — SCALE 解码未实现");
-                Ok(node_ids)
-            }
+            Some(val) => Ok(decode_node_list(val.encoded())?),
             None => Ok(vec![]),
         }
     }
 
     /// 从链上读取单个节点信息
-    pub async fn fetch_node(&self, node_id: &[u8; 32]) -> anyhow::Result<Option<NodeInfoCache>> {
+    pub async fn fetch_node(&self, node_id: &[u8]) -> anyhow::Result<Option<NodeInfoCache>> {
         let query = subxt::dynamic::storage(
             "BotConsensus", "Nodes",
             vec![Value::from_bytes(node_id)],
@@ -90,121 +98,200 @@ impl ChainClient {
         let result = self.api.storage().at_latest().await?.fetch(&query).await?;
 
         match result {
-            Some(val) => {
-                let decoded = val.to_value()?;
-                let json_str = format!("{:?}", decoded);
-                debug!(raw = json_str, "Node 原始数据");
-
-                warn!("fetch_node 返回占位值 — SCALE 解码未实现");
-                Ok(Some(NodeInfoCache {
-                    node_id: hex::encode(node_id),
-                    endpoint: String::new(), // TODO: 从 decoded 提取
-                    node_public_key: [0u8; 32],
-                    status: "Active".into(),
-                    reputation: 5000,
-                }))
-            }
+            Some(val) => Ok(Some(decode_node(node_id, val.encoded())?)),
             None => Ok(None),
         }
     }
 
     // ═══════════════════════════════════════════════════════════════
-    // 交易提交
+    // 缓存同步
     // ═══════════════════════════════════════════════════════════════
 
-    /// 提交消息确认到链上
-    pub async fn submit_confirmations(
-        &self,
-        confirmations: Vec<ConfirmationPayload>,
-    ) -> anyhow::Result<()> {
-        let encoded: Vec<Value> = confirmations.iter().map(|c| {
-            Value::unnamed_composite(vec![
-                Value::from_bytes(&c.bot_id_hash),
-                Value::from_bytes(&c.msg_hash),
-                Value::u128(c.sequence as u128),
-                Value::u128(c.timestamp as u128),
-            ])
-        }).collect();
-
-        let tx = subxt::dynamic::tx(
-            "BotConsensus", "submit_confirmations",
-            vec![Value::unnamed_composite(encoded)],
-        );
+    /// 全量刷新缓存（启动时 + 订阅重连后 + 周期兜底）
+    pub async fn refresh_cache(&self, cache: &ChainCache) {
+        match self.fetch_all_bots().await {
+            Ok(bots) => {
+                let count = bots.len();
+                for bot in bots {
+                    cache.register_bot(bot);
+                }
+                info!(count, "从链上刷新 Bot 注册完成");
+            }
+            Err(e) => warn!(error = %e, "获取链上 Bot 注册失败"),
+        }
 
-        let _events = self.api.tx()
-            .sign_and_submit_then_watch_default(&tx, &self.signer)
-            .await?
-            .wait_for_finalized_success()
-            .await?;
+        match self.fetch_active_node_list().await {
+            Ok(node_ids) if node_ids.is_empty() => {
+                // 开发环境可能只有 NODE_LIST 静态节点，链上为空时保留
+                warn!("链上活跃节点列表为空，保留本地节点缓存");
+            }
+            Ok(node_ids) => {
+                let mut synced = Vec::with_capacity(node_ids.len());
+                for nid in &node_ids {
+                    match self.fetch_node(nid).await {
+                        Ok(Some(node_info)) => {
+                            synced.push(node_info.node_id.clone());
+                            cache.sync_node(node_info);
+                        }
+                        Ok(None) => warn!(node = %node_id_to_string(nid), "活跃列表中的节点无记录"),
+                        Err(e) => warn!(node = %node_id_to_string(nid), error = %e, "读取节点信息失败"),
+                    }
+                }
+                cache.retain_nodes(&synced);
+                info!(count = synced.len(), "从链上刷新节点列表完成");
+            }
+            Err(e) => warn!(error = %e, "获取链上节点列表失败"),
+        }
+    }
 
-        info!(count = confirmations.len(), "确认已上链");
+    /// 提取区块中影响缓存的事件
+    pub async fn block_updates(&self, block: &FinalizedBlock) -> anyhow::Result<Vec<ChainUpdate>> {
+        let events = block.events().await?;
+        let mut updates = Vec::new();
+        for event in events.iter() {
+            let event = event?;
+            if let Some(update) = classify_event(event.pallet_name(), event.variant_name(), event.field_bytes()) {
+                if !updates.contains(&update) {
+                    updates.push(update);
+                }
+            }
+        }
+        Ok(updates)
+    }
+
+    /// 按事件增量刷新单条缓存
+    pub async fn apply_update(&self, cache: &ChainCache, update: &ChainUpdate) -> anyhow::Result<()> {
+        match update {
+            ChainUpdate::Bot(bot_id_hash) => match self.fetch_bot(bot_id_hash).await? {
+                Some(bot) => {
+                    debug!(bot = %bot.bot_id_hash, active = bot.is_active, "Bot 缓存已更新");
+                    cache.register_bot(bot);
+                }
+                None => cache.remove_bot(&hex::encode(bot_id_hash)),
+            },
+            ChainUpdate::Node(node_id) => {
+                let key = node_id_to_string(node_id);
+                let active = self.fetch_active_node_list().await?;
+                if !active.contains(node_id) {
+                    debug!(node = %key, "节点已不在活跃列表，移出缓存");
+                    cache.remove_node(&key);
+                    return Ok(());
+                }
+                match self.fetch_node(node_id).await? {
+                    Some(node) => {
+                        debug!(node = %key, status = %node.status, "节点缓存已更新");
+                        cache.sync_node(node);
+                    }
+                    None => cache.remove_node(&key),
+                }
+            }
+        }
         Ok(())
     }
 
-    /// 提交动作日志到链上
-    pub async fn submit_action_log(
-        &self,
-        community_id_hash: [u8; 32],
-        action_type_idx: u8,
-        target_user_hash: [u8; 32],
-        executor_node_hash: [u8; 32],
-        consensus_count: u8,
-        sequence: u64,
-        msg_hash: [u8; 32],
-    ) -> anyhow::Result<()> {
-        let action_type_value = Value::unnamed_variant(
-            action_type_name(action_type_idx), vec![],
-        );
+    // ═══════════════════════════════════════════════════════════════
+    // 交易提交
+    // ═══════════════════════════════════════════════════════════════
 
-        let tx = subxt::dynamic::tx(
-            "BotGroupMgmt", "log_action",
-            vec![
-                Value::from_bytes(&community_id_hash),
-                action_type_value,
-                Value::from_bytes(&target_user_hash),
-                Value::from_bytes(&executor_node_hash),
-                Value::u128(consensus_count as u128),
-                Value::u128(sequence as u128),
-                Value::from_bytes(&msg_hash),
-            ],
-        );
+    /// runtime 是否支持 `Utility::batch`
+    pub fn supports_batch(&self) -> bool {
+        self.api.metadata()
+            .pallet_by_name("Utility")
+            .and_then(|p| p.call_variant_by_name("batch"))
+            .is_some()
+    }
 
-        let _events = self.api.tx()
-            .sign_and_submit_then_watch_default(&tx, &self.signer)
-            .await?
-            .wait_for_finalized_success()
-            .await?;
+    /// 按顺序提交一组调用
+    ///
+    /// - 多个调用且 runtime 支持 `Utility::batch` → 打包为一笔交易
+    /// - 否则逐笔提交
+    ///
+    /// 遇到 runtime 拒绝（DispatchError）或可重试错误即停止，后续调用不提交。
+    pub async fn submit_calls(&self, calls: Vec<DynamicPayload>) -> BatchOutcome {
+        let mut outcome = BatchOutcome::default();
+        if calls.is_empty() {
+            return outcome;
+        }
 
-        info!(sequence, "动作日志已上链");
-        Ok(())
+        if calls.len() > 1 && calls.len() <= MAX_BATCH_CALLS && self.supports_batch() {
+            let count = calls.len();
+            let batch = subxt::dynamic::tx(
+                "Utility", "batch",
+                vec![Value::unnamed_composite(calls.into_iter().map(|c| c.into_value()))],
+            );
+            match self.sign_and_watch(&batch).await {
+                Ok(events) => match batch_interrupted(&events) {
+                    Some((index, error)) => {
+                        outcome.included = index;
+                        outcome.rejected = Some(error);
+                    }
+                    None => outcome.included = count,
+                },
+                // batch 外层失败不对应任何单个调用，整体重试
+                Err(e) => outcome.retryable = Some(e.to_string()),
+            }
+            debug!(count, included = outcome.included, "Utility::batch 提交完成");
+            return outcome;
+        }
+
+        for call in &calls {
+            match self.sign_and_watch(call).await {
+                Ok(_) => outcome.included += 1,
+                Err(subxt::Error::Runtime(e)) => {
+                    outcome.rejected = Some(e.to_string());
+                    break;
+                }
+                Err(e) => {
+                    outcome.retryable = Some(e.to_string());
+                    break;
+                }
+            }
+        }
+        outcome
     }
 
-    /// 提交 Equivocation 举报到链上
-    pub async fn report_equivocation(
+    /// 签名、提交并等待 finalized
+    ///
+    /// nonce 在本地递增，避免同一区块内多笔交易 nonce 冲突；
+    /// 提交失败（含交易池拒绝）后清空，下次从链上重新读取。
+    async fn sign_and_watch(
         &self,
-        node_id_hash: [u8; 32],
-        msg_hash_a: [u8; 32],
-        msg_hash_b: [u8; 32],
-        sequence: u64,
-    ) -> anyhow::Result<()> {
-        let tx = subxt::dynamic::tx(
-            "BotConsensus", "report_equivocation",
-            vec![
-                Value::from_bytes(&node_id_hash),
-                Value::from_bytes(&msg_hash_a),
-                Value::from_bytes(&msg_hash_b),
-                Value::u128(sequence as u128),
-            ],
-        );
-
-        let _events = self.api.tx()
-            .sign_and_submit_then_watch_default(&tx, &self.signer)
-            .await?
-            .wait_for_finalized_success()
-            .await?;
+        call: &DynamicPayload,
+    ) -> Result<ExtrinsicEvents<SubstrateConfig>, subxt::Error> {
+        let progress = {
+            let mut nonce = self.nonce.lock().await;
+            let next = match *nonce {
+                Some(n) => n,
+                None => {
+                    let account = Signer::<SubstrateConfig>::account_id(&self.signer);
+                    self.api.tx().account_nonce(&account).await?
+                }
+            };
+            let params = DefaultExtrinsicParamsBuilder::<SubstrateConfig>::new()
+                .nonce(next)
+                .build();
+            let signed = self.api.tx().create_signed(call, &self.signer, params).await?;
+            match signed.submit_and_watch().await {
+                Ok(progress) => {
+                    *nonce = Some(next + 1);
+                    progress
+                }
+                Err(e) => {
+                    *nonce = None;
+                    return Err(e);
+                }
+            }
+        };
 
-        warn!(sequence, "Equivocation 举报已上链");
-        Ok(())
+        match progress.wait_for_finalized_success().await {
+            Ok(events) => Ok(events),
+            Err(subxt::Error::Runtime(e)) => Err(subxt::Error::Runtime(e)),
+            Err(e) => {
+                // 交易被丢弃/失效时本地 nonce 不再可信
+                *self.nonce.lock().await = None;
+                Err(e)
+            }
+        }
     }
 
     // ═══════════════════════════════════════════════════════════════
@@ -213,26 +300,299 @@ impl ChainClient {
 
     /// 订阅 finalized 区块
     pub async fn subscribe_finalized_blocks(&self) -> anyhow::Result<
-        impl futures_util::Stream<Item = Result<subxt::blocks::Block<SubstrateConfig, OnlineClient<SubstrateConfig>>, subxt::Error>>
+        impl futures_util::Stream<Item = Result<FinalizedBlock, subxt::Error>>
     > {
         let stream = self.api.blocks().subscribe_finalized().await?;
         Ok(stream)
     }
 }
 
-/// 确认载荷
-pub struct ConfirmationPayload {
+/// `Utility::batch` 单笔最多打包的调用数
+pub const MAX_BATCH_CALLS: usize = 50;
+
+/// 批量提交结果（按调用顺序）
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BatchOutcome {
+    /// 从头开始已上链的调用数
+    pub included: usize,
+    /// 第 `included` 个调用被 runtime 拒绝（DispatchError，重试无意义）
+    pub rejected: Option<String>,
+    /// 网络 / 交易池等可重试错误，`included` 之后的调用均未上链
+    pub retryable: Option<String>,
+}
+
+/// 查找 `Utility::BatchInterrupted { index, error }`
+fn batch_interrupted(events: &ExtrinsicEvents<SubstrateConfig>) -> Option<(usize, String)> {
+    for event in events.iter().flatten() {
+        if event.pallet_name() == "Utility" && event.variant_name() == "BatchInterrupted" {
+            let index = u32::decode(&mut event.field_bytes()).unwrap_or(0) as usize;
+            let error = event.field_values()
+                .map(|v| v.to_string())
+                .unwrap_or_else(|_| "BatchInterrupted".into());
+            return Some((index, error));
+        }
+    }
+    None
+}
+
+// ═══════════════════════════════════════════════════════════════
+// 链上类型镜像（SCALE 解码）
+// ═══════════════════════════════════════════════════════════════
+//
+// 字段顺序必须与 pallet 定义完全一致：
+// - pallet-bot-registry: Platform / BotStatus / BotRegistration
+// - pallet-bot-consensus: NodeStatus / ProjectNode
+// 运行时: AccountId = AccountId32, Balance = u128, BlockNumber = u32
+
+/// 平台类型（镜像 `pallet_bot_registry::Platform`）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+pub enum Platform {
+    Telegram,
+    Discord,
+    Slack,
+    Matrix,
+    Farcaster,
+}
+
+impl Platform {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Telegram => "telegram",
+            Self::Discord => "discord",
+            Self::Slack => "slack",
+            Self::Matrix => "matrix",
+            Self::Farcaster => "farcaster",
+        }
+    }
+}
+
+/// Bot 状态（镜像 `pallet_bot_registry::BotStatus`）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+pub enum BotStatus {
+    Active,
+    Suspended,
+    Deactivated,
+}
+
+/// Bot 注册记录（镜像 `pallet_bot_registry::BotRegistration`）
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct BotRegistration {
+    pub owner: [u8; 32],
+    pub platform: Platform,
     pub bot_id_hash: [u8; 32],
-    pub msg_hash: [u8; 32],
-    pub sequence: u64,
-    pub timestamp: u64,
+    pub community_id_hash: [u8; 32],
+    pub hash_salt: [u8; 16],
+    pub owner_public_key: [u8; 32],
+    pub status: BotStatus,
+    pub registered_at: u32,
+    pub updated_at: u32,
 }
 
-fn action_type_name(idx: u8) -> &'static str {
-    match idx {
-        0 => "Ban", 1 => "Mute", 2 => "Unmute",
-        3 => "DeleteMessage", 4 => "PinMessage",
-        5 => "ApproveJoin", 6 => "DeclineJoin",
-        7 => "SendMessage", _ => "Unknown",
+/// 节点状态（镜像 `pallet_bot_consensus::NodeStatus`）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+pub enum NodeStatus {
+    Active,
+    Probation,
+    Suspended,
+    Exiting,
+}
+
+impl NodeStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Active => "Active",
+            Self::Probation => "Probation",
+            Self::Suspended => "Suspended",
+            Self::Exiting => "Exiting",
+        }
+    }
+}
+
+/// 项目节点记录（镜像 `pallet_bot_consensus::ProjectNode`）
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct ProjectNode {
+    pub operator: [u8; 32],
+    pub node_public_key: [u8; 32],
+    pub endpoint_hash: [u8; 32],
+    pub stake: u128,
+    pub status: NodeStatus,
+    pub reputation: u16,
+    pub messages_confirmed: u64,
+    pub messages_missed: u64,
+    pub equivocations_reported: u32,
+    pub registered_at: u32,
+    pub last_active: u32,
+}
+
+/// 解码 `BotRegistry::Bots` 的值
+pub fn decode_bot(raw: &[u8]) -> Result<BotInfoCache, codec::Error> {
+    let reg = BotRegistration::decode_all(&mut &raw[..])?;
+    Ok(BotInfoCache {
+        bot_id_hash: hex::encode(reg.bot_id_hash),
+        owner_public_key: reg.owner_public_key,
+        platform: reg.platform.as_str().into(),
+        is_active: reg.status == BotStatus::Active,
+    })
+}
+
+/// 解码 `BotConsensus::ActiveNodeList` 的值（`BoundedVec<NodeId, 100>`）
+pub fn decode_node_list(raw: &[u8]) -> Result<Vec<Vec<u8>>, codec::Error> {
+    Vec::<Vec<u8>>::decode_all(&mut &raw[..])
+}
+
+/// 解码 `BotConsensus::Nodes` 的值
+///
+/// 链上只保存 endpoint 哈希，endpoint 明文由 `ChainCache::sync_node` 保留本地已知值。
+pub fn decode_node(node_id: &[u8], raw: &[u8]) -> Result<NodeInfoCache, codec::Error> {
+    let node = ProjectNode::decode_all(&mut &raw[..])?;
+    Ok(NodeInfoCache {
+        node_id: node_id_to_string(node_id),
+        endpoint: String::new(),
+        node_public_key: node.node_public_key,
+        status: node.status.as_str().into(),
+        reputation: node.reputation,
+    })
+}
+
+/// 链上 NodeId → 缓存键
+///
+/// 节点以 `NODE_ID` 的 UTF-8 字节注册，可打印时直接使用，否则回退为 hex。
+pub fn node_id_to_string(node_id: &[u8]) -> String {
+    match std::str::from_utf8(node_id) {
+        Ok(s) if !s.is_empty() && s.chars().all(|c| c.is_ascii_graphic()) => s.to_string(),
+        _ => hex::encode(node_id),
+    }
+}
+
+/// 需要刷新的缓存条目
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChainUpdate {
+    /// Bot 注册信息变化（bot_id_hash）
+    Bot([u8; 32]),
+    /// 节点信息变化（NodeId）
+    Node(Vec<u8>),
+}
+
+/// 根据事件判断需要刷新的缓存条目
+///
+/// 只解码事件的前导字段，其余字段忽略。
+pub fn classify_event(pallet: &str, variant: &str, mut fields: &[u8]) -> Option<ChainUpdate> {
+    match (pallet, variant) {
+        // BotRegistered { owner, bot_id_hash, platform }
+        ("BotRegistry", "BotRegistered") => {
+            <[u8; 32]>::decode(&mut fields).ok()?;
+            <[u8; 32]>::decode(&mut fields).ok().map(ChainUpdate::Bot)
+        }
+        ("BotRegistry", "BotPublicKeyUpdated" | "BotDeactivated" | "BotSuspended" | "BotReactivated") => {
+            <[u8; 32]>::decode(&mut fields).ok().map(ChainUpdate::Bot)
+        }
+        (
+            "BotConsensus",
+            "NodeRegistered" | "NodeExitRequested" | "NodeExited" | "NodeSuspended"
+            | "NodeActivated" | "ReputationUpdated",
+        ) => Vec::<u8>::decode(&mut fields).ok().map(ChainUpdate::Node),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 按 pallet 字段顺序手工拼接的 BotRegistration 存储值
+    fn recorded_bot_blob(status: u8) -> Vec<u8> {
+        let mut raw = Vec::new();
+        raw.extend_from_slice(&[0x01; 32]); // owner
+        raw.push(1); // platform = Discord
+        raw.extend_from_slice(&[0x02; 32]); // bot_id_hash
+        raw.extend_from_slice(&[0x03; 32]); // community_id_hash
+        raw.extend_from_slice(&[0x04; 16]); // hash_salt
+        raw.extend_from_slice(&[0x05; 32]); // owner_public_key
+        raw.push(status);
+        raw.extend_from_slice(&10u32.to_le_bytes()); // registered_at
+        raw.extend_from_slice(&20u32.to_le_bytes()); // updated_at
+        raw
+    }
+
+    /// 按 pallet 字段顺序手工拼接的 ProjectNode 存储值
+    fn recorded_node_blob() -> Vec<u8> {
+        let mut raw = Vec::new();
+        raw.extend_from_slice(&[0x11; 32]); // operator
+        raw.extend_from_slice(&[0x12; 32]); // node_public_key
+        raw.extend_from_slice(&[0x13; 32]); // endpoint_hash
+        raw.extend_from_slice(&1_000u128.to_le_bytes()); // stake
+        raw.push(1); // status = Probation
+        raw.extend_from_slice(&7_500u16.to_le_bytes()); // reputation
+        raw.extend_from_slice(&42u64.to_le_bytes()); // messages_confirmed
+        raw.extend_from_slice(&3u64.to_le_bytes()); // messages_missed
+        raw.extend_from_slice(&0u32.to_le_bytes()); // equivocations_reported
+        raw.extend_from_slice(&5u32.to_le_bytes()); // registered_at
+        raw.extend_from_slice(&9u32.to_le_bytes()); // last_active
+        raw
+    }
+
+    #[test]
+    fn test_decode_bot() {
+        let bot = decode_bot(&recorded_bot_blob(0)).unwrap();
+        assert_eq!(bot.bot_id_hash, hex::encode([0x02; 32]));
+        assert_eq!(bot.owner_public_key, [0x05; 32]);
+        assert_eq!(bot.platform, "discord");
+        assert!(bot.is_active);
+
+        let suspended = decode_bot(&recorded_bot_blob(1)).unwrap();
+        assert!(!suspended.is_active);
+
+        // 截断或多余字节都视为布局不匹配
+        let blob = recorded_bot_blob(0);
+        assert!(decode_bot(&blob[..blob.len() - 1]).is_err());
+        let mut extra = blob.clone();
+        extra.push(0);
+        assert!(decode_bot(&extra).is_err());
+    }
+
+    #[test]
+    fn test_decode_node() {
+        let node = decode_node(b"node-1", &recorded_node_blob()).unwrap();
+        assert_eq!(node.node_id, "node-1");
+        assert_eq!(node.node_public_key, [0x12; 32]);
+        assert_eq!(node.status, "Probation");
+        assert_eq!(node.reputation, 7_500);
+        assert!(node.endpoint.is_empty());
+    }
+
+    #[test]
+    fn test_decode_node_list() {
+        let ids: Vec<Vec<u8>> = vec![b"node-1".to_vec(), vec![0xff; 32]];
+        let decoded = decode_node_list(&ids.encode()).unwrap();
+        assert_eq!(decoded, ids);
+        assert_eq!(node_id_to_string(&decoded[0]), "node-1");
+        assert_eq!(node_id_to_string(&decoded[1]), hex::encode([0xff; 32]));
+
+        assert!(decode_node_list(&[]).is_err());
+        assert!(decode_node_list(&[0]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_classify_event() {
+        let bot_registered = ([0x01u8; 32], [0x02u8; 32], Platform::Telegram).encode();
+        assert_eq!(
+            classify_event("BotRegistry", "BotRegistered", &bot_registered),
+            Some(ChainUpdate::Bot([0x02; 32])),
+        );
+        assert_eq!(
+            classify_event("BotRegistry", "BotSuspended", &[0x07; 32]),
+            Some(ChainUpdate::Bot([0x07; 32])),
+        );
+
+        let node_exited = (b"node-1".to_vec(), [0u8; 32], 100u128).encode();
+        assert_eq!(
+            classify_event("BotConsensus", "NodeExited", &node_exited),
+            Some(ChainUpdate::Node(b"node-1".to_vec())),
+        );
+
+        assert_eq!(classify_event("BotConsensus", "ConfirmationsSubmitted", &[]), None);
+        assert_eq!(classify_event("Balances", "Transfer", &[]), None);
+        // 字段不完整
+        assert_eq!(classify_event("BotRegistry", "BotSuspended", &[0x07; 8]), None);
     }
 }
@@ -1,9 +1,16 @@
-use std::sync::Arc;
 use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+use codec::{Decode, Encode};
+use serde::Serialize;
+use subxt::dynamic::Value;
+use subxt::tx::DynamicPayload;
 use tokio::sync::Mutex;
 use tracing::{info, warn, debug};
 
-// SignedMessage used by ConfirmationEntry and ActionLogEntry fields
+use crate::chain_client::{BatchOutcome, ChainClient};
+use crate::types::{ActionType, AdminAction, AlertPayload, MessageAction, SignedMessage};
 
 /// 批量上链提交器
 ///
@@ -13,7 +20,9 @@ use tracing::{info, warn, debug};
 /// 3. 收集 Leader 执行结果 → 提交到链上 (pallet-bot-group-mgmt::log_action)
 /// 4. 检测 Equivocation → 自动提交举报
 ///
-/// 当前实现: 内存队列 + 定时批量提交（需 subxt 集成）
+/// 提交失败的条目重新入队并指数退避重试；队列快照持久化到
+/// `DATA_DIR/submitter/pending.scale`，重启后由 `load_pending_from_disk` 恢复。
+/// 未连接链（静态缓存模式）时仅记录日志并视为已提交。
 pub struct ChainSubmitter {
     /// 待提交的消息确认
     confirmation_queue: Mutex<VecDeque<ConfirmationEntry>>,
@@ -27,20 +36,31 @@ pub struct ChainSubmitter {
     batch_size: usize,
     /// 提交间隔（秒）
     submit_interval_secs: u64,
+    /// 链客户端（连接成功后注入）
+    client: OnceLock<Arc<ChainClient>>,
+    /// 退避与健康统计
+    health: Mutex<HealthState>,
+    /// 本地持久化目录
+    data_dir: PathBuf,
 }
 
 /// 消息确认条目
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
 pub struct ConfirmationEntry {
+    pub msg_id: [u8; 32],
+    /// Bot 所有者账户（AccountId32）
+    pub owner: [u8; 32],
     pub bot_id_hash: [u8; 32],
     pub msg_hash: [u8; 32],
     pub sequence: u64,
     pub timestamp: u64,
     pub confirmed_at_ms: u64,
+    /// 参与确认的节点 ID
+    pub confirmed_by: Vec<Vec<u8>>,
 }
 
 /// 动作日志条目
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
 pub struct ActionLogEntry {
     pub community_id_hash: [u8; 32],
     pub action_type: u8,
@@ -52,27 +72,164 @@ pub struct ActionLogEntry {
 }
 
 /// Equivocation 举报条目
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
 pub struct EquivocationEntry {
+    pub bot_id_hash: [u8; 32],
     pub owner_public_key: [u8; 32],
     pub sequence: u64,
+    pub timestamp_a: u64,
     pub msg_hash_a: [u8; 32],
     pub signature_a: [u8; 64],
+    pub timestamp_b: u64,
     pub msg_hash_b: [u8; 32],
     pub signature_b: [u8; 64],
 }
 
+impl ConfirmationEntry {
+    /// 从已达成 M/K 共识的签名消息构造
+    ///
+    /// msg_id = SHA256(gossip msg_id)；owner 取消息中的 owner 公钥（AccountId32 即公钥）。
+    /// hex 字段解码失败时返回 None。
+    pub fn from_message(msg_id: &str, message: &SignedMessage, confirmed_by: &[String]) -> Option<Self> {
+        Some(Self {
+            msg_id: sha256(msg_id.as_bytes()),
+            owner: decode_hex_array(&message.owner_public_key)?,
+            bot_id_hash: decode_hex_array(&message.bot_id_hash)?,
+            msg_hash: decode_hex_array(&message.message_hash)?,
+            sequence: message.sequence,
+            timestamp: message.timestamp,
+            confirmed_at_ms: chrono::Utc::now().timestamp_millis() as u64,
+            confirmed_by: confirmed_by.iter().map(|id| id.as_bytes().to_vec()).collect(),
+        })
+    }
+}
+
+impl ActionLogEntry {
+    /// 从 Leader 执行成功的动作构造
+    ///
+    /// community / target 哈希与 Agent `crypto.rs` 的加盐算法一致（盐由 owner 公钥派生）；
+    /// 无链上对应变体的动作返回 None。
+    pub fn from_execution(
+        message: &SignedMessage,
+        action: &ActionType,
+        chat_id: i64,
+        params: &serde_json::Value,
+        executor_node_id: &str,
+        consensus_count: usize,
+    ) -> Option<Self> {
+        let action_type = pallet_action_index(action)?;
+        let owner: [u8; 32] = decode_hex_array(&message.owner_public_key)?;
+        let target_user_hash = match params.get("user_id") {
+            Some(serde_json::Value::Number(n)) => user_id_hash(&message.platform, &n.to_string(), &owner),
+            Some(serde_json::Value::String(s)) => user_id_hash(&message.platform, s, &owner),
+            _ => [0u8; 32],
+        };
+        Some(Self {
+            community_id_hash: community_id_hash(&message.platform, chat_id, &owner),
+            action_type,
+            target_user_hash,
+            executor_node_hash: sha256(executor_node_id.as_bytes()),
+            consensus_count: consensus_count.min(u8::MAX as usize) as u8,
+            sequence: message.sequence,
+            msg_hash: decode_hex_array(&message.message_hash)?,
+        })
+    }
+}
+
+impl EquivocationEntry {
+    /// 从 Equivocation 警报构造（hex 字段解码失败时返回 None）
+    pub fn from_alert(alert: &AlertPayload) -> Option<Self> {
+        Some(Self {
+            bot_id_hash: decode_hex_array(&alert.bot_id_hash)?,
+            owner_public_key: decode_hex_array(&alert.owner_public_key)?,
+            sequence: alert.sequence,
+            timestamp_a: alert.timestamp_a,
+            msg_hash_a: decode_hex_array(&alert.msg_hash_a)?,
+            signature_a: decode_hex_array(&alert.signature_a)?,
+            timestamp_b: alert.timestamp_b,
+            msg_hash_b: decode_hex_array(&alert.msg_hash_b)?,
+            signature_b: decode_hex_array(&alert.signature_b)?,
+        })
+    }
+
+    /// 本地预验证举报证据（与 pallet `verify_owner_signature` 一致）
+    ///
+    /// 证据无效的举报上链会罚没举报押金，入队前必须通过
+    pub fn verify(&self) -> bool {
+        self.msg_hash_a != self.msg_hash_b
+            && crate::verifier::verify_owner_proof(
+                &self.owner_public_key, &self.bot_id_hash, self.sequence,
+                self.timestamp_a, &self.msg_hash_a, &self.signature_a,
+            )
+            && crate::verifier::verify_owner_proof(
+                &self.owner_public_key, &self.bot_id_hash, self.sequence,
+                self.timestamp_b, &self.msg_hash_b, &self.signature_b,
+            )
+    }
+}
+
 /// 批量提交结果
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct SubmitResult {
     pub confirmations_submitted: usize,
     pub action_logs_submitted: usize,
     pub equivocations_submitted: usize,
+    /// 被 runtime 拒绝而丢弃的条目数
+    pub dropped: usize,
+    /// 处于退避期，本轮未提交
+    pub deferred: bool,
     pub errors: Vec<String>,
 }
 
+/// 持久化的待提交队列快照
+#[derive(Debug, Default, Encode, Decode)]
+struct PendingSnapshot {
+    confirmations: Vec<ConfirmationEntry>,
+    action_logs: Vec<ActionLogEntry>,
+    equivocations: Vec<EquivocationEntry>,
+}
+
+/// 退避与统计状态
+#[derive(Debug, Default)]
+struct HealthState {
+    consecutive_failures: u32,
+    next_attempt: Option<Instant>,
+    total_confirmations: u64,
+    total_action_logs: u64,
+    total_equivocations: u64,
+    total_dropped: u64,
+    last_error: Option<String>,
+    last_success_ms: Option<u64>,
+}
+
+/// 提交器健康状态（/health 输出）
+#[derive(Debug, Clone, Serialize)]
+pub struct SubmitterHealth {
+    pub connected: bool,
+    pub healthy: bool,
+    pub pending_confirmations: usize,
+    pub pending_action_logs: usize,
+    pub pending_equivocations: usize,
+    pub consecutive_failures: u32,
+    pub retry_in_secs: u64,
+    pub total_confirmations: u64,
+    pub total_action_logs: u64,
+    pub total_equivocations: u64,
+    pub total_dropped: u64,
+    pub last_error: Option<String>,
+    pub last_success_ms: Option<u64>,
+}
+
 impl ChainSubmitter {
     pub fn new(node_id: String) -> Self {
+        let data_dir = std::env::var("DATA_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| PathBuf::from("/tmp/nexus-node"));
+        Self::with_data_dir(node_id, data_dir)
+    }
+
+    /// 使用指定 data_dir 创建（测试用）
+    pub fn with_data_dir(node_id: String, data_dir: PathBuf) -> Self {
         Self {
             confirmation_queue: Mutex::new(VecDeque::new()),
             action_log_queue: Mutex::new(VecDeque::new()),
@@ -80,129 +237,172 @@ impl ChainSubmitter {
             node_id,
             batch_size: 50,
             submit_interval_secs: 6, // 每个区块提交一次
+            client: OnceLock::new(),
+            health: Mutex::new(HealthState::default()),
+            data_dir,
+        }
+    }
+
+    /// 注入链客户端
+    pub fn attach_client(&self, client: Arc<ChainClient>) {
+        if self.client.set(client).is_err() {
+            warn!("链客户端已注入，忽略重复设置");
         }
     }
 
     /// 队列容量上限（防止 OOM）
     const MAX_QUEUE_SIZE: usize = 10_000;
 
-    /// 添加消息确认到队列
+    /// 连续失败达到该次数时 /health 报告 degraded
+    const UNHEALTHY_FAILURES: u32 = 3;
+
+    /// 添加消息确认到队列（入队即持久化）
     pub async fn queue_confirmation(&self, entry: ConfirmationEntry) {
-        let mut queue = self.confirmation_queue.lock().await;
-        if queue.len() >= Self::MAX_QUEUE_SIZE {
-            warn!(queue_size = queue.len(), "确认队列已满，丢弃最旧条目");
-            queue.pop_front();
+        {
+            let mut queue = self.confirmation_queue.lock().await;
+            if queue.len() >= Self::MAX_QUEUE_SIZE {
+                warn!(queue_size = queue.len(), "确认队列已满，丢弃最旧条目");
+                queue.pop_front();
+            }
+            queue.push_back(entry);
+            debug!(queue_size = queue.len(), "确认已入队");
         }
-        queue.push_back(entry);
-        debug!(queue_size = queue.len(), "确认已入队");
+        self.persist_pending().await;
     }
 
-    /// 添加动作日志到队列
+    /// 添加动作日志到队列（入队即持久化）
+    ///
+    /// `action_type` 不是 pallet `ActionType` 索引的条目直接丢弃
     pub async fn queue_action_log(&self, entry: ActionLogEntry) {
-        let mut queue = self.action_log_queue.lock().await;
-        if queue.len() >= Self::MAX_QUEUE_SIZE {
-            warn!(queue_size = queue.len(), "动作日志队列已满，丢弃最旧条目");
-            queue.pop_front();
+        if action_type_name(entry.action_type).is_none() {
+            warn!(action_type = entry.action_type, "未知的动作类型索引，丢弃动作日志");
+            return;
         }
-        queue.push_back(entry);
-        debug!(queue_size = queue.len(), "动作日志已入队");
+        {
+            let mut queue = self.action_log_queue.lock().await;
+            if queue.len() >= Self::MAX_QUEUE_SIZE {
+                warn!(queue_size = queue.len(), "动作日志队列已满，丢弃最旧条目");
+                queue.pop_front();
+            }
+            queue.push_back(entry);
+            debug!(queue_size = queue.len(), "动作日志已入队");
+        }
+        self.persist_pending().await;
     }
 
-    /// 添加 Equivocation 举报到队列（优先级最高）
+    /// 添加 Equivocation 举报到队列（优先级最高，入队即持久化）
     pub async fn queue_equivocation(&self, entry: EquivocationEntry) {
-        let mut queue = self.equivocation_queue.lock().await;
-        if queue.len() >= Self::MAX_QUEUE_SIZE {
-            warn!(queue_size = queue.len(), "举报队列已满，丢弃最旧条目");
-            queue.pop_front();
+        {
+            let mut queue = self.equivocation_queue.lock().await;
+            if queue.iter().any(|e| e.bot_id_hash == entry.bot_id_hash && e.sequence == entry.sequence) {
+                debug!(sequence = entry.sequence, "同一 (bot, sequence) 的举报已在队列中");
+                return;
+            }
+            if queue.len() >= Self::MAX_QUEUE_SIZE {
+                warn!(queue_size = queue.len(), "举报队列已满，丢弃最旧条目");
+                queue.pop_front();
+            }
+            queue.push_back(entry);
+            warn!(queue_size = queue.len(), "Equivocation 举报已入队");
         }
-        queue.push_back(entry);
-        warn!(queue_size = queue.len(), "Equivocation 举报已入队");
+        self.persist_pending().await;
     }
 
     /// 批量提交（定时调用）
     ///
     /// 优先级: Equivocation > ActionLog > Confirmation
+    ///
+    /// 可重试错误会中止本轮并进入退避；被 runtime 拒绝的条目直接丢弃。
     pub async fn flush(&self) -> SubmitResult {
-        let mut result = SubmitResult {
-            confirmations_submitted: 0,
-            action_logs_submitted: 0,
-            equivocations_submitted: 0,
-            errors: vec![],
-        };
+        let mut result = SubmitResult::default();
+
+        if let Some(next) = self.health.lock().await.next_attempt {
+            if Instant::now() < next {
+                result.deferred = true;
+                return result;
+            }
+        }
+
+        let mut retryable = None;
 
-        // 1. 提交 Equivocation（立即，不等批量）
+        // 1. 提交 Equivocation
         {
             let mut queue = self.equivocation_queue.lock().await;
-            while let Some(entry) = queue.pop_front() {
-                match self.submit_equivocation(&entry).await {
-                    Ok(_) => {
-                        result.equivocations_submitted += 1;
-                        info!(
-                            sequence = entry.sequence,
-                            "Equivocation 举报已提交"
-                        );
-                    }
-                    Err(e) => {
-                        result.errors.push(format!("equivocation: {}", e));
-                        // 重新入队
-                        queue.push_front(entry);
-                        break;
-                    }
+            let drain_len = self.batch_size.min(queue.len());
+            let batch: Vec<_> = queue.drain(..drain_len).collect();
+            if !batch.is_empty() {
+                let calls = batch.iter().map(equivocation_call).collect();
+                let outcome = self.submit(calls).await;
+                let (submitted, dropped) = settle(&mut queue, batch, &outcome);
+                result.equivocations_submitted = submitted;
+                result.dropped += dropped;
+                if let Some(e) = &outcome.rejected {
+                    result.errors.push(format!("equivocation rejected: {}", e));
                 }
+                if submitted > 0 {
+                    info!(count = submitted, "Equivocation 举报已提交");
+                }
+                retryable = outcome.retryable.map(|e| format!("equivocation: {}", e));
             }
         }
 
         // 2. 提交动作日志
-        {
+        if retryable.is_none() {
             let mut queue = self.action_log_queue.lock().await;
             let drain_len = self.batch_size.min(queue.len());
             let batch: Vec<_> = queue.drain(..drain_len).collect();
             if !batch.is_empty() {
-                match self.submit_action_logs(&batch).await {
-                    Ok(_) => {
-                        result.action_logs_submitted = batch.len();
-                        debug!(count = batch.len(), "动作日志批量提交成功");
-                    }
-                    Err(e) => {
-                        result.errors.push(format!("action_logs: {}", e));
-                        // 重新入队
-                        for entry in batch.into_iter().rev() {
-                            queue.push_front(entry);
-                        }
-                    }
+                let calls = batch.iter().map(action_log_call).collect();
+                let outcome = self.submit(calls).await;
+                let (submitted, dropped) = settle(&mut queue, batch, &outcome);
+                result.action_logs_submitted = submitted;
+                result.dropped += dropped;
+                if let Some(e) = &outcome.rejected {
+                    result.errors.push(format!("action_log rejected: {}", e));
                 }
+                debug!(count = submitted, "动作日志批量提交完成");
+                retryable = outcome.retryable.map(|e| format!("action_logs: {}", e));
             }
         }
 
-        // 3. 提交消息确认
-        {
+        // 3. 提交消息确认（整批一笔 submit_confirmations）
+        if retryable.is_none() {
             let mut queue = self.confirmation_queue.lock().await;
             let drain_len = self.batch_size.min(queue.len());
             let batch: Vec<_> = queue.drain(..drain_len).collect();
             if !batch.is_empty() {
-                match self.submit_confirmations(&batch).await {
-                    Ok(_) => {
-                        result.confirmations_submitted = batch.len();
-                        debug!(count = batch.len(), "确认批量提交成功");
-                    }
-                    Err(e) => {
-                        result.errors.push(format!("confirmations: {}", e));
-                        for entry in batch.into_iter().rev() {
-                            queue.push_front(entry);
-                        }
+                let outcome = self.submit(vec![confirmations_call(&batch)]).await;
+                if outcome.included == 1 {
+                    result.confirmations_submitted = batch.len();
+                    debug!(count = batch.len(), "确认批量提交成功");
+                } else if let Some(e) = &outcome.rejected {
+                    result.dropped += batch.len();
+                    result.errors.push(format!("confirmations rejected: {}", e));
+                } else {
+                    for entry in batch.into_iter().rev() {
+                        queue.push_front(entry);
                     }
                 }
+                retryable = outcome.retryable.map(|e| format!("confirmations: {}", e));
             }
         }
 
+        if let Some(e) = &retryable {
+            result.errors.push(e.clone());
+        }
+        self.record_outcome(&result, retryable).await;
+        self.persist_pending().await;
+
         if result.confirmations_submitted > 0
             || result.action_logs_submitted > 0
             || result.equivocations_submitted > 0
+            || !result.errors.is_empty()
         {
             info!(
                 confirmations = result.confirmations_submitted,
                 action_logs = result.action_logs_submitted,
                 equivocations = result.equivocations_submitted,
+                dropped = result.dropped,
                 errors = result.errors.len(),
                 "批量提交完成"
             );
@@ -211,34 +411,87 @@ impl ChainSubmitter {
         result
     }
 
-    /// 提交消息确认到链上
-    ///
-    /// 调用: pallet-bot-consensus::submit_confirmations(confirmations)
-    async fn submit_confirmations(&self, _batch: &[ConfirmationEntry]) -> anyhow::Result<()> {
-        // TODO: 使用 subxt 构造并提交交易
-        // let api = subxt::OnlineClient::new().await?;
-        // let tx = api.tx().pallet_bot_consensus().submit_confirmations(batch);
-        // api.sign_and_submit(&tx, &signer).await?;
-        debug!(count = _batch.len(), "TODO: subxt submit_confirmations");
-        Ok(())
+    /// 提交一组调用到链上
+    async fn submit(&self, calls: Vec<DynamicPayload>) -> BatchOutcome {
+        match self.client.get() {
+            Some(client) => client.submit_calls(calls).await,
+            None => {
+                debug!(node = self.node_id, count = calls.len(), "链客户端未连接（静态缓存模式），跳过上链");
+                BatchOutcome { included: calls.len(), ..Default::default() }
+            }
+        }
     }
 
-    /// 提交动作日志到链上
-    ///
-    /// 调用: pallet-bot-group-mgmt::log_action(...)
-    async fn submit_action_logs(&self, _batch: &[ActionLogEntry]) -> anyhow::Result<()> {
-        // TODO: 使用 subxt 构造并提交交易
-        debug!(count = _batch.len(), "TODO: subxt log_action");
-        Ok(())
+    /// 更新退避与统计
+    async fn record_outcome(&self, result: &SubmitResult, retryable: Option<String>) {
+        let mut health = self.health.lock().await;
+        health.total_confirmations += result.confirmations_submitted as u64;
+        health.total_action_logs += result.action_logs_submitted as u64;
+        health.total_equivocations += result.equivocations_submitted as u64;
+        health.total_dropped += result.dropped as u64;
+
+        let m = crate::metrics::metrics();
+        m.chain_submitted.with_label_values(&["confirmation"]).inc_by(result.confirmations_submitted as u64);
+        m.chain_submitted.with_label_values(&["action_log"]).inc_by(result.action_logs_submitted as u64);
+        m.chain_submitted.with_label_values(&["equivocation"]).inc_by(result.equivocations_submitted as u64);
+        m.chain_dropped.inc_by(result.dropped as u64);
+        if retryable.is_some() {
+            m.chain_submit_failures.inc();
+        }
+
+        let submitted = result.confirmations_submitted
+            + result.action_logs_submitted
+            + result.equivocations_submitted;
+        if submitted > 0 {
+            health.last_success_ms = Some(chrono::Utc::now().timestamp_millis() as u64);
+        }
+
+        // 可重试错误进入退避（被拒绝的条目已丢弃，不影响退避）
+        match retryable {
+            Some(e) => {
+                health.consecutive_failures = health.consecutive_failures.saturating_add(1);
+                let delay = backoff_delay(self.submit_interval_secs, health.consecutive_failures);
+                health.next_attempt = Some(Instant::now() + delay);
+                warn!(
+                    failures = health.consecutive_failures,
+                    retry_in_secs = delay.as_secs(),
+                    error = e.as_str(),
+                    "链上提交失败，退避重试"
+                );
+                health.last_error = Some(e);
+            }
+            None => {
+                health.consecutive_failures = 0;
+                health.next_attempt = None;
+                if let Some(e) = result.errors.last() {
+                    health.last_error = Some(e.clone());
+                }
+            }
+        }
     }
 
-    /// 提交 Equivocation 举报到链上
-    ///
-    /// 调用: pallet-bot-consensus::report_equivocation(...)
-    async fn submit_equivocation(&self, _entry: &EquivocationEntry) -> anyhow::Result<()> {
-        // TODO: 使用 subxt 构造并提交交易
-        debug!(sequence = _entry.sequence, "TODO: subxt report_equivocation");
-        Ok(())
+    /// 提交器健康状态
+    pub async fn health(&self) -> SubmitterHealth {
+        let (c, a, e) = self.queue_sizes().await;
+        let health = self.health.lock().await;
+        let retry_in_secs = health.next_attempt
+            .map(|t| t.saturating_duration_since(Instant::now()).as_secs())
+            .unwrap_or(0);
+        SubmitterHealth {
+            connected: self.client.get().is_some(),
+            healthy: health.consecutive_failures < Self::UNHEALTHY_FAILURES,
+            pending_confirmations: c,
+            pending_action_logs: a,
+            pending_equivocations: e,
+            consecutive_failures: health.consecutive_failures,
+            retry_in_secs,
+            total_confirmations: health.total_confirmations,
+            total_action_logs: health.total_action_logs,
+            total_equivocations: health.total_equivocations,
+            total_dropped: health.total_dropped,
+            last_error: health.last_error.clone(),
+            last_success_ms: health.last_success_ms,
+        }
     }
 
     /// 获取队列大小
@@ -249,6 +502,72 @@ impl ChainSubmitter {
         (c, a, e)
     }
 
+    // ═══════════════════════════════════════════════════════════════
+    // 持久化
+    // ═══════════════════════════════════════════════════════════════
+
+    fn pending_path(&self) -> PathBuf {
+        self.data_dir.join("submitter").join("pending.scale")
+    }
+
+    /// 持久化未提交队列（队列为空时删除快照）
+    async fn persist_pending(&self) {
+        let snapshot = PendingSnapshot {
+            confirmations: self.confirmation_queue.lock().await.iter().cloned().collect(),
+            action_logs: self.action_log_queue.lock().await.iter().cloned().collect(),
+            equivocations: self.equivocation_queue.lock().await.iter().cloned().collect(),
+        };
+        let path = self.pending_path();
+
+        if snapshot.confirmations.is_empty()
+            && snapshot.action_logs.is_empty()
+            && snapshot.equivocations.is_empty()
+        {
+            if path.exists() {
+                if let Err(e) = std::fs::remove_file(&path) {
+                    warn!(error = %e, "删除待提交快照失败");
+                }
+            }
+            return;
+        }
+
+        if let Err(e) = write_atomic(&path, &snapshot.encode()) {
+            warn!(error = %e, "待提交队列持久化失败");
+        }
+    }
+
+    /// 从本地恢复未提交队列（节点启动时调用）
+    pub async fn load_pending_from_disk(&self) {
+        let path = self.pending_path();
+        let bytes = match std::fs::read(&path) {
+            Ok(b) => b,
+            Err(_) => {
+                debug!("无待提交快照，跳过恢复");
+                return;
+            }
+        };
+
+        let snapshot = match PendingSnapshot::decode(&mut &bytes[..]) {
+            Ok(s) => s,
+            Err(e) => {
+                warn!(path = %path.display(), error = %e, "待提交快照解码失败，已忽略");
+                return;
+            }
+        };
+
+        let (c, a, e) = (
+            snapshot.confirmations.len(),
+            snapshot.action_logs.len(),
+            snapshot.equivocations.len(),
+        );
+        self.confirmation_queue.lock().await.extend(snapshot.confirmations);
+        self.action_log_queue.lock().await.extend(
+            snapshot.action_logs.into_iter().filter(|e| action_type_name(e.action_type).is_some()),
+        );
+        self.equivocation_queue.lock().await.extend(snapshot.equivocations);
+        info!(confirmations = c, action_logs = a, equivocations = e, "已从本地恢复待提交队列");
+    }
+
     /// 启动定时提交循环
     pub fn spawn_submit_loop(self: Arc<Self>) {
         let interval = self.submit_interval_secs;
@@ -264,6 +583,187 @@ impl ChainSubmitter {
     }
 }
 
+/// 按提交结果结算批次
+///
+/// 前 `included` 条已上链；被拒绝的一条丢弃；其余按原顺序放回队首。
+/// 返回 (已提交数, 丢弃数)。
+fn settle<E>(queue: &mut VecDeque<E>, batch: Vec<E>, outcome: &BatchOutcome) -> (usize, usize) {
+    let submitted = outcome.included.min(batch.len());
+    let dropped = usize::from(outcome.rejected.is_some() && submitted < batch.len());
+    for entry in batch.into_iter().skip(submitted + dropped).rev() {
+        queue.push_front(entry);
+    }
+    (submitted, dropped)
+}
+
+/// 指数退避: interval × 2^(failures-1)，上限 5 分钟
+fn backoff_delay(interval_secs: u64, failures: u32) -> Duration {
+    const MAX_BACKOFF_SECS: u64 = 300;
+    let exp = failures.saturating_sub(1).min(16);
+    Duration::from_secs(interval_secs.saturating_mul(1u64 << exp).min(MAX_BACKOFF_SECS))
+}
+
+/// 原子写入（临时文件 + rename）
+fn write_atomic(path: &std::path::Path, bytes: &[u8]) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, bytes)?;
+    std::fs::rename(&tmp, path)
+}
+
+// ═══════════════════════════════════════════════════════════════
+// 哈希工具
+// ═══════════════════════════════════════════════════════════════
+
+fn sha256(data: &[u8]) -> [u8; 32] {
+    use sha2::{Digest, Sha256};
+    Sha256::digest(data).into()
+}
+
+/// hex → 定长字节数组（长度不符返回 None）
+fn decode_hex_array<const N: usize>(s: &str) -> Option<[u8; N]> {
+    hex::decode(s).ok()?.try_into().ok()
+}
+
+/// 社区 ID 哈希（与 Agent `compute_community_id_hash` 一致）
+fn community_id_hash(platform: &str, chat_id: i64, owner: &[u8; 32]) -> [u8; 32] {
+    use sha2::{Digest, Sha256};
+    let salt = Sha256::new()
+        .chain_update(owner)
+        .chain_update(b"nexus-community-salt")
+        .finalize();
+    Sha256::new()
+        .chain_update((platform.len() as u32).to_le_bytes())
+        .chain_update(platform.as_bytes())
+        .chain_update(chat_id.to_le_bytes())
+        .chain_update(salt)
+        .finalize()
+        .into()
+}
+
+/// 平台用户 ID 哈希（与 Agent `compute_platform_user_id_hash` 一致）
+fn user_id_hash(platform: &str, user_id: &str, owner: &[u8; 32]) -> [u8; 32] {
+    use sha2::{Digest, Sha256};
+    let salt = Sha256::new()
+        .chain_update(owner)
+        .chain_update(b"nexus-user-salt")
+        .finalize();
+    Sha256::new()
+        .chain_update((platform.len() as u32).to_le_bytes())
+        .chain_update(platform.as_bytes())
+        .chain_update((user_id.len() as u32).to_le_bytes())
+        .chain_update(user_id.as_bytes())
+        .chain_update(salt)
+        .finalize()
+        .into()
+}
+
+// ═══════════════════════════════════════════════════════════════
+// 调用构造
+// ═══════════════════════════════════════════════════════════════
+
+/// pallet-bot-consensus::submit_confirmations(Vec<(msg_id, owner, sequence, msg_hash, confirmed_by)>)
+fn confirmations_call(batch: &[ConfirmationEntry]) -> DynamicPayload {
+    let confirmations: Vec<Value> = batch.iter().map(|c| {
+        Value::unnamed_composite(vec![
+            Value::from_bytes(c.msg_id),
+            Value::from_bytes(c.owner),
+            Value::u128(c.sequence as u128),
+            Value::from_bytes(c.msg_hash),
+            Value::unnamed_composite(c.confirmed_by.iter().map(Value::from_bytes)),
+        ])
+    }).collect();
+
+    subxt::dynamic::tx(
+        "BotConsensus", "submit_confirmations",
+        vec![Value::unnamed_composite(confirmations)],
+    )
+}
+
+/// pallet-bot-group-mgmt::log_action(...)
+fn action_log_call(entry: &ActionLogEntry) -> DynamicPayload {
+    subxt::dynamic::tx(
+        "BotGroupMgmt", "log_action",
+        vec![
+            Value::from_bytes(entry.community_id_hash),
+            Value::unnamed_variant(
+                action_type_name(entry.action_type).expect("action_type 已在入队/恢复时校验"),
+                vec![],
+            ),
+            Value::from_bytes(entry.target_user_hash),
+            Value::from_bytes(entry.executor_node_hash),
+            Value::u128(entry.consensus_count as u128),
+            Value::u128(entry.sequence as u128),
+            Value::from_bytes(entry.msg_hash),
+        ],
+    )
+}
+
+/// pallet-bot-consensus::report_equivocation(bot_id_hash, sequence, proof_a, proof_b)
+///
+/// proof = SignedMessageProof { timestamp, msg_hash, signature }
+fn equivocation_call(entry: &EquivocationEntry) -> DynamicPayload {
+    let proof = |timestamp: u64, msg_hash: [u8; 32], signature: [u8; 64]| {
+        Value::named_composite([
+            ("timestamp", Value::u128(timestamp as u128)),
+            ("msg_hash", Value::from_bytes(msg_hash)),
+            ("signature", Value::from_bytes(signature)),
+        ])
+    };
+
+    subxt::dynamic::tx(
+        "BotConsensus", "report_equivocation",
+        vec![
+            Value::from_bytes(entry.bot_id_hash),
+            Value::u128(entry.sequence as u128),
+            proof(entry.timestamp_a, entry.msg_hash_a, entry.signature_a),
+            proof(entry.timestamp_b, entry.msg_hash_b, entry.signature_b),
+        ],
+    )
+}
+
+/// pallet-bot-group-mgmt::ActionType 变体名（按 pallet 枚举索引）
+///
+/// 索引超出 pallet 枚举范围时返回 None
+fn action_type_name(idx: u8) -> Option<&'static str> {
+    const NAMES: [&str; 11] = [
+        "Ban", "Unban", "Mute", "Unmute",
+        "DeleteMessage", "PinMessage", "UnpinMessage",
+        "ApproveJoin", "DeclineJoin",
+        "SendMessage", "SetPermissions",
+    ];
+    NAMES.get(idx as usize).copied()
+}
+
+/// 节点动作 → pallet-bot-group-mgmt::ActionType 索引
+///
+/// 链上无对应变体的动作（查询、配置更新、编辑消息等）返回 None，不记录动作日志
+pub fn pallet_action_index(action: &ActionType) -> Option<u8> {
+    let idx = match action {
+        ActionType::Admin(AdminAction::Ban | AdminAction::Kick) => 0,
+        ActionType::Admin(AdminAction::Unban) => 1,
+        ActionType::Admin(AdminAction::Mute) => 2,
+        ActionType::Admin(AdminAction::Unmute) => 3,
+        ActionType::Message(MessageAction::Delete | MessageAction::DeleteBatch) => 4,
+        ActionType::Message(MessageAction::Pin) => 5,
+        ActionType::Message(MessageAction::Unpin | MessageAction::UnpinAll) => 6,
+        ActionType::Admin(AdminAction::ApproveJoinRequest) => 7,
+        ActionType::Admin(AdminAction::DeclineJoinRequest) => 8,
+        ActionType::Message(
+            MessageAction::Send
+            | MessageAction::SendPhoto
+            | MessageAction::SendDocument
+            | MessageAction::SendMediaGroup
+            | MessageAction::SendPoll,
+        ) => 9,
+        ActionType::Admin(AdminAction::SetPermissions) => 10,
+        _ => return None,
+    };
+    Some(idx)
+}
+
 /// 序列号追踪器（重放保护）
 ///
 /// 每个 (bot_id_hash, owner_public_key) 维护最近 seen 的序列号窗口
@@ -346,20 +846,43 @@ impl SequenceTracker {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use tempfile::TempDir;
+
+    fn make_confirmation(i: u8) -> ConfirmationEntry {
+        ConfirmationEntry {
+            msg_id: [i + 20; 32],
+            owner: [i + 30; 32],
+            bot_id_hash: [i; 32],
+            msg_hash: [i + 10; 32],
+            sequence: i as u64,
+            timestamp: 1700000000,
+            confirmed_at_ms: 1700000000000,
+            confirmed_by: vec![b"node-1".to_vec(), b"node-2".to_vec()],
+        }
+    }
+
+    fn make_equivocation(sequence: u64) -> EquivocationEntry {
+        EquivocationEntry {
+            bot_id_hash: [8; 32],
+            owner_public_key: [3; 32],
+            sequence,
+            timestamp_a: 1700000000,
+            msg_hash_a: [4; 32],
+            signature_a: [5; 64],
+            timestamp_b: 1700000001,
+            msg_hash_b: [6; 32],
+            signature_b: [7; 64],
+        }
+    }
 
     #[tokio::test]
     async fn test_queue_and_flush() {
-        let submitter = Arc::new(ChainSubmitter::new("test_node".into()));
+        let tmp = TempDir::new().unwrap();
+        let submitter = Arc::new(ChainSubmitter::with_data_dir("test_node".into(), tmp.path().to_path_buf()));
 
         // 入队 3 条确认
         for i in 0..3 {
-            submitter.queue_confirmation(ConfirmationEntry {
-                bot_id_hash: [i; 32],
-                msg_hash: [i + 10; 32],
-                sequence: i as u64,
-                timestamp: 1700000000,
-                confirmed_at_ms: 1700000000000,
-            }).await;
+            submitter.queue_confirmation(make_confirmation(i)).await;
         }
 
         let (c, a, e) = submitter.queue_sizes().await;
@@ -379,24 +902,12 @@ mod tests {
 
     #[tokio::test]
     async fn test_equivocation_priority() {
-        let submitter = Arc::new(ChainSubmitter::new("test_node".into()));
+        let tmp = TempDir::new().unwrap();
+        let submitter = Arc::new(ChainSubmitter::with_data_dir("test_node".into(), tmp.path().to_path_buf()));
 
-        submitter.queue_confirmation(ConfirmationEntry {
-            bot_id_hash: [1; 32],
-            msg_hash: [2; 32],
-            sequence: 1,
-            timestamp: 0,
-            confirmed_at_ms: 0,
-        }).await;
+        submitter.queue_confirmation(make_confirmation(1)).await;
 
-        submitter.queue_equivocation(EquivocationEntry {
-            owner_public_key: [3; 32],
-            sequence: 42,
-            msg_hash_a: [4; 32],
-            signature_a: [5; 64],
-            msg_hash_b: [6; 32],
-            signature_b: [7; 64],
-        }).await;
+        submitter.queue_equivocation(make_equivocation(42)).await;
 
         let result = submitter.flush().await;
         // equivocation 应该被提交
@@ -404,6 +915,187 @@ mod tests {
         assert_eq!(result.confirmations_submitted, 1);
     }
 
+    #[tokio::test]
+    async fn test_enqueue_persists_without_flush() {
+        let tmp = TempDir::new().unwrap();
+        let submitter = ChainSubmitter::with_data_dir("test_node".into(), tmp.path().to_path_buf());
+
+        submitter.queue_confirmation(make_confirmation(1)).await;
+        submitter.queue_equivocation(make_equivocation(9)).await;
+        // 同一 (bot, sequence) 不重复入队
+        submitter.queue_equivocation(make_equivocation(9)).await;
+        assert!(tmp.path().join("submitter/pending.scale").exists());
+
+        // 未 flush 即崩溃 → 重启后仍可恢复
+        let restored = ChainSubmitter::with_data_dir("test_node".into(), tmp.path().to_path_buf());
+        restored.load_pending_from_disk().await;
+        assert_eq!(restored.queue_sizes().await, (1, 0, 1));
+    }
+
+    #[tokio::test]
+    async fn test_queue_action_log_rejects_unknown_type() {
+        let tmp = TempDir::new().unwrap();
+        let submitter = ChainSubmitter::with_data_dir("test_node".into(), tmp.path().to_path_buf());
+        let entry = |action_type| ActionLogEntry {
+            community_id_hash: [1; 32],
+            action_type,
+            target_user_hash: [2; 32],
+            executor_node_hash: [3; 32],
+            consensus_count: 2,
+            sequence: 1,
+            msg_hash: [4; 32],
+        };
+
+        submitter.queue_action_log(entry(10)).await;
+        submitter.queue_action_log(entry(11)).await;
+        assert_eq!(submitter.queue_sizes().await, (0, 1, 0));
+    }
+
+    #[test]
+    fn test_action_type_matches_pallet() {
+        assert_eq!(action_type_name(0), Some("Ban"));
+        assert_eq!(action_type_name(1), Some("Unban"));
+        assert_eq!(action_type_name(2), Some("Mute"));
+        assert_eq!(action_type_name(6), Some("UnpinMessage"));
+        assert_eq!(action_type_name(9), Some("SendMessage"));
+        assert_eq!(action_type_name(10), Some("SetPermissions"));
+        assert_eq!(action_type_name(11), None);
+
+        assert_eq!(pallet_action_index(&ActionType::Admin(AdminAction::Unban)), Some(1));
+        assert_eq!(pallet_action_index(&ActionType::Admin(AdminAction::ApproveJoinRequest)), Some(7));
+        assert_eq!(pallet_action_index(&ActionType::Message(MessageAction::Delete)), Some(4));
+        assert_eq!(pallet_action_index(&ActionType::Admin(AdminAction::Promote)), None);
+        assert_eq!(pallet_action_index(&ActionType::NoAction), None);
+    }
+
+    #[test]
+    fn test_equivocation_evidence_verify() {
+        use ed25519_dalek::{Signer, SigningKey};
+
+        let sk = SigningKey::from_bytes(&[7u8; 32]);
+        let pk = sk.verifying_key().to_bytes();
+        let bot_id_hash = [8u8; 32];
+        let sign = |timestamp: u64, msg_hash: [u8; 32]| {
+            let mut data = Vec::new();
+            data.extend_from_slice(&pk);
+            data.extend_from_slice(&bot_id_hash);
+            data.extend_from_slice(&5u64.to_le_bytes());
+            data.extend_from_slice(&timestamp.to_le_bytes());
+            data.extend_from_slice(&msg_hash);
+            sk.sign(&data).to_bytes()
+        };
+
+        let mut entry = EquivocationEntry {
+            bot_id_hash,
+            owner_public_key: pk,
+            sequence: 5,
+            timestamp_a: 100,
+            msg_hash_a: [1; 32],
+            signature_a: sign(100, [1; 32]),
+            timestamp_b: 101,
+            msg_hash_b: [2; 32],
+            signature_b: sign(101, [2; 32]),
+        };
+        assert!(entry.verify());
+
+        // 时间戳被篡改 → 签名不匹配
+        entry.timestamp_b = 102;
+        assert!(!entry.verify());
+    }
+
+    #[test]
+    fn test_settle_outcome() {
+        let mut queue: VecDeque<u32> = VecDeque::from(vec![100]);
+
+        // 前 2 条上链，第 3 条被拒绝，其余放回队首
+        let outcome = BatchOutcome {
+            included: 2,
+            rejected: Some("BadOrigin".into()),
+            retryable: None,
+        };
+        assert_eq!(settle(&mut queue, vec![1, 2, 3, 4, 5], &outcome), (2, 1));
+        assert_eq!(queue, VecDeque::from(vec![4, 5, 100]));
+
+        // 可重试错误: 未上链部分全部放回
+        let mut queue = VecDeque::new();
+        let outcome = BatchOutcome {
+            included: 1,
+            rejected: None,
+            retryable: Some("connection reset".into()),
+        };
+        assert_eq!(settle(&mut queue, vec![1, 2, 3], &outcome), (1, 0));
+        assert_eq!(queue, VecDeque::from(vec![2, 3]));
+    }
+
+    #[test]
+    fn test_backoff_delay() {
+        assert_eq!(backoff_delay(6, 1), Duration::from_secs(6));
+        assert_eq!(backoff_delay(6, 2), Duration::from_secs(12));
+        assert_eq!(backoff_delay(6, 4), Duration::from_secs(48));
+        assert_eq!(backoff_delay(6, 10), Duration::from_secs(300));
+        assert_eq!(backoff_delay(6, u32::MAX), Duration::from_secs(300));
+    }
+
+    #[tokio::test]
+    async fn test_pending_persist_and_recover() {
+        let tmp = TempDir::new().unwrap();
+        let submitter = ChainSubmitter::with_data_dir("test_node".into(), tmp.path().to_path_buf());
+
+        submitter.queue_confirmation(make_confirmation(1)).await;
+        submitter.queue_confirmation(make_confirmation(2)).await;
+        submitter.queue_equivocation(make_equivocation(7)).await;
+        submitter.persist_pending().await;
+        assert!(tmp.path().join("submitter/pending.scale").exists());
+
+        // 重启后恢复
+        let restored = ChainSubmitter::with_data_dir("test_node".into(), tmp.path().to_path_buf());
+        assert_eq!(restored.queue_sizes().await, (0, 0, 0));
+        restored.load_pending_from_disk().await;
+        assert_eq!(restored.queue_sizes().await, (2, 0, 1));
+        assert_eq!(
+            restored.confirmation_queue.lock().await.front().cloned(),
+            Some(make_confirmation(1)),
+        );
+
+        // 全部提交后快照被删除
+        let result = restored.flush().await;
+        assert_eq!(result.confirmations_submitted, 2);
+        assert_eq!(result.equivocations_submitted, 1);
+        assert!(!tmp.path().join("submitter/pending.scale").exists());
+
+        let health = restored.health().await;
+        assert!(!health.connected);
+        assert!(health.healthy);
+        assert_eq!(health.total_confirmations, 2);
+        assert_eq!(health.total_equivocations, 1);
+        assert!(health.last_success_ms.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_backoff_defers_flush() {
+        let tmp = TempDir::new().unwrap();
+        let submitter = ChainSubmitter::with_data_dir("test_node".into(), tmp.path().to_path_buf());
+        submitter.queue_confirmation(make_confirmation(1)).await;
+
+        let failed = SubmitResult {
+            errors: vec!["confirmations: connection refused".into()],
+            ..Default::default()
+        };
+        for _ in 0..ChainSubmitter::UNHEALTHY_FAILURES {
+            submitter.record_outcome(&failed, Some("confirmations: connection refused".into())).await;
+        }
+
+        let health = submitter.health().await;
+        assert!(!health.healthy);
+        assert_eq!(health.consecutive_failures, 3);
+        assert!(health.retry_in_secs > 0);
+
+        // 退避期内不提交
+        let result = submitter.flush().await;
+        assert!(result.deferred);
+        assert_eq!(submitter.queue_sizes().await, (1, 0, 0));
+    }
+
     #[test]
     fn test_sequence_tracker_normal_flow() {
         let tracker = SequenceTracker::new();
@@ -0,0 +1,347 @@
+use std::path::Path;
+
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::{info, warn};
+
+use crate::chain_cache::ChainCache;
+use crate::types::GossipEnvelope;
+
+/// 握手签名域分隔
+const HANDSHAKE_DOMAIN: &[u8] = b"nexus-gossip-handshake-v1";
+/// Envelope 签名域分隔
+const ENVELOPE_DOMAIN: &[u8] = b"nexus-gossip-envelope-v1";
+
+/// 节点身份（Ed25519 节点密钥）
+///
+/// 公钥须与链上节点记录的 `node_public_key` 一致，
+/// 用于 Gossip 握手证明身份 + 签名所有发出的 GossipEnvelope
+#[derive(Clone)]
+pub struct NodeIdentity {
+    /// 本节点 ID
+    pub node_id: String,
+
+    /// 节点签名密钥
+    signing_key: SigningKey,
+}
+
+impl NodeIdentity {
+    pub fn new(node_id: String, signing_key: SigningKey) -> Self {
+        Self { node_id, signing_key }
+    }
+
+    /// 从 `SIGNING_KEY_PATH` 加载节点密钥，不存在则生成并持久化
+    ///
+    /// 文件内容为 32 字节 Ed25519 私钥种子
+    pub fn load_or_generate(node_id: String, key_path: &str) -> Self {
+        let path = Path::new(key_path);
+        if let Ok(seed_bytes) = std::fs::read(path) {
+            if let Ok(seed) = <[u8; 32]>::try_from(seed_bytes.as_slice()) {
+                let identity = Self::new(node_id, SigningKey::from_bytes(&seed));
+                info!(public_key = identity.public_key_hex(), "节点 Ed25519 密钥已加载");
+                return identity;
+            }
+            warn!(path = key_path, "节点 Ed25519 密钥文件格式错误，重新生成");
+        }
+
+        let signing_key = SigningKey::generate(&mut rand::rngs::OsRng);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).ok();
+        }
+        if let Err(e) = std::fs::write(path, signing_key.to_bytes()) {
+            warn!(error = %e, "节点 Ed25519 密钥持久化失败");
+        }
+        let identity = Self::new(node_id, signing_key);
+        info!(
+            public_key = identity.public_key_hex(),
+            "已生成新的节点 Ed25519 密钥（需在链上登记该公钥）"
+        );
+        identity
+    }
+
+    /// 节点签名密钥（Leader 执行动作签名用）
+    pub fn signing_key(&self) -> SigningKey {
+        self.signing_key.clone()
+    }
+
+    /// 节点公钥 (hex)
+    pub fn public_key_hex(&self) -> String {
+        hex::encode(self.signing_key.verifying_key().as_bytes())
+    }
+
+    /// 应答握手挑战: Sign(domain ‖ nonce ‖ 本节点 ID ‖ 对端 ID)
+    pub fn sign_challenge(&self, nonce: &[u8; 32], peer_node_id: &str) -> String {
+        let data = handshake_sign_data(nonce, &self.node_id, peer_node_id);
+        hex::encode(self.signing_key.sign(&data).to_bytes())
+    }
+
+    /// 签名 Envelope（覆盖 `sender_node_id` 为本节点）
+    pub fn sign_envelope(&self, envelope: &mut GossipEnvelope) {
+        envelope.sender_node_id = self.node_id.clone();
+        match envelope_sign_data(envelope) {
+            Ok(data) => {
+                envelope.sender_signature = hex::encode(self.signing_key.sign(&data).to_bytes());
+            }
+            Err(e) => warn!(error = %e, "GossipEnvelope 签名数据序列化失败"),
+        }
+    }
+}
+
+/// 握手帧（WebSocket Text 帧，JSON）
+///
+/// ① 服务端 → 客户端: Challenge
+/// ② 客户端 → 服务端: Auth（应答服务端 nonce + 发起反向挑战）
+/// ③ 服务端 → 客户端: AuthAck（应答客户端 nonce）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum HandshakeFrame {
+    Challenge {
+        node_id: String,
+        /// 32 字节随机数 (hex)
+        nonce: String,
+    },
+    Auth {
+        node_id: String,
+        /// 对 Challenge.nonce 的签名 (hex)
+        signature: String,
+        /// 客户端发起的反向挑战 (hex)
+        nonce: String,
+    },
+    AuthAck {
+        /// 对 Auth.nonce 的签名 (hex)
+        signature: String,
+    },
+}
+
+/// 节点认证错误
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum AuthError {
+    #[error("unknown node: {0}")]
+    UnknownNode(String),
+    #[error("node {0} has no registered public key")]
+    NoPublicKey(String),
+    #[error("invalid public key for node {0}")]
+    InvalidPublicKey(String),
+    #[error("malformed signature")]
+    MalformedSignature,
+    #[error("signature verification failed")]
+    BadSignature,
+    #[error("sender {claimed} does not match authenticated peer {peer}")]
+    SenderMismatch { claimed: String, peer: String },
+    #[error("malformed handshake: {0}")]
+    Handshake(String),
+}
+
+/// 生成握手随机数
+pub fn new_nonce() -> [u8; 32] {
+    use rand::RngCore;
+    let mut nonce = [0u8; 32];
+    rand::rngs::OsRng.fill_bytes(&mut nonce);
+    nonce
+}
+
+/// 解析 hex 随机数
+pub fn parse_nonce(nonce_hex: &str) -> Result<[u8; 32], AuthError> {
+    hex::decode(nonce_hex)
+        .ok()
+        .and_then(|b| <[u8; 32]>::try_from(b.as_slice()).ok())
+        .ok_or_else(|| AuthError::Handshake("nonce must be 32 bytes hex".into()))
+}
+
+/// 校验对端握手签名（公钥取自 ChainCache 节点记录）
+pub fn verify_challenge_response(
+    cache: &ChainCache,
+    nonce: &[u8; 32],
+    signer_node_id: &str,
+    my_node_id: &str,
+    signature_hex: &str,
+) -> Result<(), AuthError> {
+    let data = handshake_sign_data(nonce, signer_node_id, my_node_id);
+    verify_node_signature(cache, signer_node_id, &data, signature_hex)
+}
+
+/// 校验收到的 Envelope
+///
+/// 1. `sender_node_id` 必须是已认证的对端（不转发第三方消息）
+/// 2. `sender_signature` 必须由该节点链上登记的公钥签出
+pub fn verify_envelope(
+    cache: &ChainCache,
+    envelope: &GossipEnvelope,
+    peer_node_id: &str,
+) -> Result<(), AuthError> {
+    if envelope.sender_node_id != peer_node_id {
+        return Err(AuthError::SenderMismatch {
+            claimed: envelope.sender_node_id.clone(),
+            peer: peer_node_id.to_string(),
+        });
+    }
+    let data = envelope_sign_data(envelope).map_err(|_| AuthError::MalformedSignature)?;
+    verify_node_signature(cache, peer_node_id, &data, &envelope.sender_signature)
+}
+
+/// 用链上节点公钥验证 Ed25519 签名
+fn verify_node_signature(
+    cache: &ChainCache,
+    node_id: &str,
+    data: &[u8],
+    signature_hex: &str,
+) -> Result<(), AuthError> {
+    let node = cache
+        .get_node(node_id)
+        .ok_or_else(|| AuthError::UnknownNode(node_id.to_string()))?;
+    if node.node_public_key == [0u8; 32] {
+        return Err(AuthError::NoPublicKey(node_id.to_string()));
+    }
+    let verifying_key = VerifyingKey::from_bytes(&node.node_public_key)
+        .map_err(|_| AuthError::InvalidPublicKey(node_id.to_string()))?;
+
+    let sig_bytes = hex::decode(signature_hex).map_err(|_| AuthError::MalformedSignature)?;
+    let sig_array: [u8; 64] = sig_bytes
+        .try_into()
+        .map_err(|_| AuthError::MalformedSignature)?;
+    let signature = Signature::from_bytes(&sig_array);
+
+    verifying_key
+        .verify(data, &signature)
+        .map_err(|_| AuthError::BadSignature)
+}
+
+/// 握手签名数据 = SHA256(domain ‖ nonce ‖ len(signer) ‖ signer ‖ peer)
+fn handshake_sign_data(nonce: &[u8; 32], signer_node_id: &str, peer_node_id: &str) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update(HANDSHAKE_DOMAIN);
+    hasher.update(nonce);
+    hasher.update((signer_node_id.len() as u32).to_le_bytes());
+    hasher.update(signer_node_id.as_bytes());
+    hasher.update(peer_node_id.as_bytes());
+    hasher.finalize().to_vec()
+}
+
+/// Envelope 签名数据 = SHA256(domain ‖ JSON(version, msg_type, sender, timestamp, payload))
+fn envelope_sign_data(envelope: &GossipEnvelope) -> Result<Vec<u8>, serde_json::Error> {
+    let body = serde_json::to_vec(&(
+        envelope.version,
+        &envelope.msg_type,
+        &envelope.sender_node_id,
+        envelope.timestamp,
+        &envelope.payload,
+    ))?;
+    let mut hasher = Sha256::new();
+    hasher.update(ENVELOPE_DOMAIN);
+    hasher.update(&body);
+    Ok(hasher.finalize().to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{GossipPayload, GossipType, NodeInfoCache, PullPayload};
+
+    fn identity(node_id: &str, seed: u8) -> NodeIdentity {
+        NodeIdentity::new(node_id.into(), SigningKey::from_bytes(&[seed; 32]))
+    }
+
+    fn cache_with(identities: &[&NodeIdentity]) -> ChainCache {
+        let cache = ChainCache::new();
+        for id in identities {
+            cache.add_node(NodeInfoCache {
+                node_id: id.node_id.clone(),
+                endpoint: String::new(),
+                node_public_key: *id.signing_key.verifying_key().as_bytes(),
+                status: "Active".into(),
+                reputation: 5000,
+            });
+        }
+        cache
+    }
+
+    fn pull_envelope() -> GossipEnvelope {
+        GossipEnvelope {
+            version: 1,
+            msg_type: GossipType::MessagePull,
+            sender_node_id: String::new(),
+            timestamp: 1000,
+            payload: GossipPayload::Pull(PullPayload { msg_id: "m1".into() }),
+            sender_signature: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_challenge_response_roundtrip() {
+        let a = identity("node_a", 1);
+        let b = identity("node_b", 2);
+        let cache = cache_with(&[&a, &b]);
+        let nonce = new_nonce();
+
+        let sig = a.sign_challenge(&nonce, "node_b");
+        assert_eq!(verify_challenge_response(&cache, &nonce, "node_a", "node_b", &sig), Ok(()));
+
+        // 签名绑定对端 ID 与 nonce，不可挪用
+        assert_eq!(
+            verify_challenge_response(&cache, &nonce, "node_a", "node_c", &sig),
+            Err(AuthError::BadSignature)
+        );
+        assert_eq!(
+            verify_challenge_response(&cache, &new_nonce(), "node_a", "node_b", &sig),
+            Err(AuthError::BadSignature)
+        );
+    }
+
+    #[test]
+    fn test_challenge_rejects_unregistered_key() {
+        let a = identity("node_a", 1);
+        let b = identity("node_b", 2);
+        let cache = cache_with(&[&b]);
+        let nonce = new_nonce();
+        let sig = a.sign_challenge(&nonce, "node_b");
+        assert_eq!(
+            verify_challenge_response(&cache, &nonce, "node_a", "node_b", &sig),
+            Err(AuthError::UnknownNode("node_a".into()))
+        );
+
+        // 登记的是零公钥（NODE_LIST 未配置公钥）→ 拒绝
+        cache.add_node(NodeInfoCache {
+            node_id: "node_a".into(),
+            endpoint: String::new(),
+            node_public_key: [0u8; 32],
+            status: "Active".into(),
+            reputation: 5000,
+        });
+        assert_eq!(
+            verify_challenge_response(&cache, &nonce, "node_a", "node_b", &sig),
+            Err(AuthError::NoPublicKey("node_a".into()))
+        );
+    }
+
+    #[test]
+    fn test_envelope_sign_verify() {
+        let a = identity("node_a", 1);
+        let cache = cache_with(&[&a]);
+        let mut envelope = pull_envelope();
+        a.sign_envelope(&mut envelope);
+        assert_eq!(envelope.sender_node_id, "node_a");
+        assert_eq!(verify_envelope(&cache, &envelope, "node_a"), Ok(()));
+
+        // 经 JSON 往返后仍可验证
+        let bytes = serde_json::to_vec(&envelope).unwrap();
+        let decoded: GossipEnvelope = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(verify_envelope(&cache, &decoded, "node_a"), Ok(()));
+
+        // 篡改载荷
+        let mut tampered = envelope.clone();
+        tampered.timestamp += 1;
+        assert_eq!(verify_envelope(&cache, &tampered, "node_a"), Err(AuthError::BadSignature));
+
+        // 冒充其他发送者
+        assert!(matches!(
+            verify_envelope(&cache, &envelope, "node_b"),
+            Err(AuthError::SenderMismatch { .. })
+        ));
+
+        // 未签名
+        let mut unsigned = pull_envelope();
+        unsigned.sender_node_id = "node_a".into();
+        assert_eq!(verify_envelope(&cache, &unsigned, "node_a"), Err(AuthError::MalformedSignature));
+    }
+}
@@ -5,6 +5,9 @@ use tracing::{info, warn, debug};
 use crate::types::*;
 use crate::gossip::state::GossipState;
 use crate::chain_cache::ChainCache;
+use crate::chain_submitter::{ActionLogEntry, ChainSubmitter, ConfirmationEntry, EquivocationEntry};
+use crate::leader::LeaderExecutor;
+use crate::metrics::metrics;
 
 /// Gossip 引擎
 ///
@@ -14,6 +17,7 @@ use crate::chain_cache::ChainCache;
 /// 3. 检查共识 + 触发 Leader 执行
 /// 4. Pull 补偿机制
 /// 5. Equivocation 检测
+/// 6. 共识确认 / 动作日志 / Equivocation 举报送入链上提交器
 pub struct GossipEngine {
     /// 本节点 ID
     pub node_id: String,
@@ -26,6 +30,12 @@ pub struct GossipEngine {
 
     /// 广播通道（内部 → Gossip 网络层）
     pub outbound_tx: broadcast::Sender<GossipEnvelope>,
+
+    /// 链上提交器（未注入时不上链）
+    pub submitter: Option<Arc<ChainSubmitter>>,
+
+    /// Leader 执行器（未注入时 Leader 只标记 Executing，不执行动作）
+    pub leader: Option<Arc<LeaderExecutor>>,
 }
 
 impl GossipEngine {
@@ -40,9 +50,23 @@ impl GossipEngine {
             state,
             chain_cache,
             outbound_tx,
+            submitter: None,
+            leader: None,
         }
     }
 
+    /// 注入链上提交器
+    pub fn with_submitter(mut self, submitter: Arc<ChainSubmitter>) -> Self {
+        self.submitter = Some(submitter);
+        self
+    }
+
+    /// 注入 Leader 执行器
+    pub fn with_leader(mut self, leader: Arc<LeaderExecutor>) -> Self {
+        self.leader = Some(leader);
+        self
+    }
+
     /// 创建 outbound 消息接收通道（供 Gossip 网络层使用）
     ///
     /// 将内部 broadcast channel 桥接到 mpsc::UnboundedReceiver
@@ -75,6 +99,12 @@ impl GossipEngine {
     /// 4. 检查共识
     pub fn on_agent_message(&self, message: SignedMessage) {
         let msg_id = GossipState::make_msg_id(&message.bot_id_hash, message.sequence);
+        metrics().agent_messages.inc();
+
+        // 同一 (bot, sequence) 已持有哈希不同的原始消息 → Equivocation 证据
+        if self.detect_conflict(&msg_id, &message) {
+            return;
+        }
 
         // 确定目标节点 + Leader
         let active_nodes = self.chain_cache.get_active_node_ids();
@@ -109,7 +139,7 @@ impl GossipEngine {
                 node_id: self.node_id.clone(),
                 config_version: self.chain_cache.get_config_version(&message_bot_id_hash),
             }),
-            sender_signature: String::new(), // TODO: 签名
+            sender_signature: String::new(), // 由网络层签名
         };
 
         if let Err(e) = self.outbound_tx.send(seen) {
@@ -125,6 +155,7 @@ impl GossipEngine {
 
     /// 处理收到的 Gossip 消息
     pub fn on_gossip_message(&self, envelope: GossipEnvelope) {
+        metrics().on_gossip_received(envelope.msg_type);
         match envelope.msg_type {
             GossipType::MessageSeen => {
                 if let GossipPayload::Seen(seen) = &envelope.payload {
@@ -203,32 +234,35 @@ impl GossipEngine {
                 }),
                 sender_signature: String::new(),
             };
+            metrics().pulls.with_label_values(&["sent"]).inc();
             let _ = self.outbound_tx.send(pull);
         }
 
         // Equivocation 检测
+        //
+        // Seen 只携带哈希，无法构成举报证据 → Pull 对方持有的原始签名消息，
+        // 收到后由 handle_pull_response 组装双签名证据
         if let Some((hash_a, hash_b)) = self.state.has_conflicting_hashes(&seen.msg_id) {
             warn!(
                 msg_id = seen.msg_id,
                 hash_a, hash_b,
-                "Equivocation 检测到! 广播警报"
+                "Equivocation 检测到! 拉取原始消息取证"
             );
-            let alert = GossipEnvelope {
-                version: 1,
-                msg_type: GossipType::EquivocationAlert,
-                sender_node_id: self.node_id.clone(),
-                timestamp: chrono::Utc::now().timestamp_millis() as u64,
-                payload: GossipPayload::Alert(AlertPayload {
-                    owner_public_key: String::new(), // TODO: 从消息中提取
-                    sequence: 0,
-                    msg_hash_a: hash_a,
-                    signature_a: String::new(),
-                    msg_hash_b: hash_b,
-                    signature_b: String::new(),
-                }),
-                sender_signature: String::new(),
-            };
-            let _ = self.outbound_tx.send(alert);
+            metrics().equivocations.with_label_values(&["detected"]).inc();
+            if !needs_pull {
+                let pull = GossipEnvelope {
+                    version: 1,
+                    msg_type: GossipType::MessagePull,
+                    sender_node_id: self.node_id.clone(),
+                    timestamp: chrono::Utc::now().timestamp_millis() as u64,
+                    payload: GossipPayload::Pull(PullPayload {
+                        msg_id: seen.msg_id.clone(),
+                    }),
+                    sender_signature: String::new(),
+                };
+                metrics().pulls.with_label_values(&["sent"]).inc();
+                let _ = self.outbound_tx.send(pull);
+            }
         }
 
         // 检查共识
@@ -239,6 +273,7 @@ impl GossipEngine {
     fn handle_pull(&self, pull: &PullPayload, requester: &str) {
         if let Some(original) = self.state.get_original_message(&pull.msg_id) {
             debug!(msg_id = pull.msg_id, to = requester, "响应 Pull 请求");
+            metrics().pulls.with_label_values(&["served"]).inc();
             let response = GossipEnvelope {
                 version: 1,
                 msg_type: GossipType::MessagePullResponse,
@@ -257,6 +292,9 @@ impl GossipEngine {
     /// 处理 Pull 响应 → 更新状态
     fn handle_pull_response(&self, resp: PullResponsePayload) {
         let msg = resp.signed_message;
+        if self.detect_conflict(&resp.msg_id, &msg) {
+            return;
+        }
         let active_nodes = self.chain_cache.get_active_node_ids();
         let k = select_k(active_nodes.len());
         let targets = deterministic_select_ids(&active_nodes, &msg.message_hash, msg.sequence, k);
@@ -282,17 +320,89 @@ impl GossipEngine {
         self.state.add_vote(&msg_id, vote);
     }
 
-    /// 处理 Equivocation 警报
+    /// 处理 Equivocation 警报 — 本地验证证据后上链举报
+    ///
+    /// 多个节点可能举报同一 (bot, sequence)，pallet 只接受首个，其余被拒绝后丢弃
     fn handle_equivocation_alert(&self, alert: &AlertPayload) {
+        metrics().equivocations.with_label_values(&["alert"]).inc();
+        match self.verified_evidence(alert) {
+            Some(entry) => {
+                warn!(
+                    owner = alert.owner_public_key,
+                    sequence = alert.sequence,
+                    "收到 Equivocation 警报，证据有效 — 上链举报"
+                );
+                self.enqueue_equivocation(entry);
+            }
+            None => {
+                warn!(
+                    owner = alert.owner_public_key,
+                    sequence = alert.sequence,
+                    "Equivocation 警报证据无效，忽略"
+                );
+            }
+        }
+    }
+
+    /// 检查新到的原始消息是否与已持有的原始消息冲突（同 bot、同 sequence、不同哈希）
+    ///
+    /// 冲突时广播 EquivocationAlert 并上链举报，返回 true（冲突消息不进入状态机）
+    fn detect_conflict(&self, msg_id: &str, incoming: &SignedMessage) -> bool {
+        let Some(existing) = self.state.get_original_message(msg_id) else {
+            return false;
+        };
+        if existing.bot_id_hash != incoming.bot_id_hash
+            || existing.sequence != incoming.sequence
+            || existing.message_hash == incoming.message_hash
+        {
+            return false;
+        }
+
+        let alert = AlertPayload::from_conflict(&existing, incoming);
+        let Some(entry) = self.verified_evidence(&alert) else {
+            warn!(msg_id, "冲突消息签名无效，忽略");
+            return true;
+        };
+
         warn!(
-            owner = alert.owner_public_key,
-            sequence = alert.sequence,
-            "收到 Equivocation 警报 — TODO: 上链举报"
+            msg_id,
+            hash_a = alert.msg_hash_a,
+            hash_b = alert.msg_hash_b,
+            "Equivocation 证据确认! 广播警报并上链举报"
         );
-        // TODO: Sprint 5 — 自动调用 pallet-bot-consensus::report_equivocation
+        metrics().equivocations.with_label_values(&["proven"]).inc();
+        let envelope = GossipEnvelope {
+            version: 1,
+            msg_type: GossipType::EquivocationAlert,
+            sender_node_id: self.node_id.clone(),
+            timestamp: chrono::Utc::now().timestamp_millis() as u64,
+            payload: GossipPayload::Alert(alert),
+            sender_signature: String::new(),
+        };
+        let _ = self.outbound_tx.send(envelope);
+        self.enqueue_equivocation(entry);
+        true
+    }
+
+    /// 解码并验证举报证据
+    ///
+    /// 两条签名均有效，且公钥为链上登记的 Bot 公钥（否则举报必然失败并罚没押金）
+    fn verified_evidence(&self, alert: &AlertPayload) -> Option<EquivocationEntry> {
+        let entry = EquivocationEntry::from_alert(alert)?;
+        let bot = self.chain_cache.get_bot(&alert.bot_id_hash)?;
+        (bot.owner_public_key == entry.owner_public_key && entry.verify()).then_some(entry)
+    }
+
+    /// Equivocation 举报入队（异步持久化）
+    fn enqueue_equivocation(&self, entry: EquivocationEntry) {
+        if let Some(submitter) = self.submitter.clone() {
+            tokio::spawn(async move { submitter.queue_equivocation(entry).await });
+        }
     }
 
     /// 处理 Leader 执行结果
+    ///
+    /// 动作日志由执行节点在本地提交（见 `execute_and_record`），此处只更新状态
     fn handle_execution_result(&self, result: &ResultPayload) {
         if result.success {
             self.state.set_completed(&result.msg_id);
@@ -304,6 +414,7 @@ impl GossipEngine {
 
     /// 处理 Leader 接管
     fn handle_leader_takeover(&self, takeover: &TakeoverPayload) {
+        metrics().leader_takeovers.with_label_values(&["remote"]).inc();
         info!(
             msg_id = takeover.msg_id,
             original = takeover.original_leader,
@@ -405,23 +516,102 @@ impl GossipEngine {
     }
 
     /// 尝试达成 M/K 共识
+    ///
+    /// 首次达成且本节点为 Leader 时: 消息确认入队上链，并执行动作
     fn try_consensus(&self, msg_id: &str) {
-        let (reached, _count, _m) = self.state.check_consensus(msg_id);
-
-        if reached {
-            // 检查自己是否是 Leader
-            if let Some(leader) = self.state.get_leader(msg_id) {
-                if leader == self.node_id {
-                    info!(msg_id, "我是 Leader — TODO: Sprint 4 执行动作");
-                    self.state.set_executing(msg_id);
-                    // TODO: Sprint 4 — Leader 执行 TG API 动作
+        let (reached, count, _m) = self.state.check_consensus(msg_id);
+
+        // count == 0 表示此前已达成（Confirmed / Executing / Completed），不重复处理
+        if !reached || count == 0 {
+            return;
+        }
+        if self.state.get_leader(msg_id).as_deref() != Some(self.node_id.as_str()) {
+            return;
+        }
+        let Some(message) = self.state.get_original_message(msg_id) else {
+            debug!(msg_id, "Leader 尚未持有原始消息，跳过执行");
+            return;
+        };
+
+        info!(msg_id, "我是 Leader — 提交确认并执行动作");
+        self.state.set_executing(msg_id);
+        let consensus_nodes = self.state.consensus_nodes(msg_id);
+
+        if let Some(submitter) = self.submitter.clone() {
+            match ConfirmationEntry::from_message(msg_id, &message, &consensus_nodes) {
+                Some(entry) => {
+                    tokio::spawn(async move { submitter.queue_confirmation(entry).await });
                 }
+                None => warn!(msg_id, "消息字段解码失败，跳过确认上链"),
             }
         }
+
+        if let Some(leader) = self.leader.clone() {
+            tokio::spawn(execute_and_record(
+                leader,
+                self.submitter.clone(),
+                self.outbound_tx.clone(),
+                msg_id.to_string(),
+                message,
+                consensus_nodes,
+            ));
+        }
     }
 
 }
 
+/// Leader 执行动作 → 广播 ExecutionResult → 成功的动作写入动作日志队列
+async fn execute_and_record(
+    leader: Arc<LeaderExecutor>,
+    submitter: Option<Arc<ChainSubmitter>>,
+    outbound_tx: broadcast::Sender<GossipEnvelope>,
+    msg_id: String,
+    message: SignedMessage,
+    consensus_nodes: Vec<String>,
+) {
+    let result = match leader.execute_as_leader(&msg_id, &message, consensus_nodes.clone()).await {
+        Ok(result) => result,
+        Err(e) => {
+            warn!(msg_id, error = %e, "Leader 执行出错");
+            return;
+        }
+    };
+
+    let envelope = GossipEnvelope {
+        version: 1,
+        msg_type: GossipType::ExecutionResult,
+        sender_node_id: leader.node_id.clone(),
+        timestamp: chrono::Utc::now().timestamp_millis() as u64,
+        payload: GossipPayload::Result(ResultPayload {
+            msg_id: msg_id.clone(),
+            action_id: result.action_id.clone(),
+            success: result.success,
+            agent_receipt: result.agent_receipt.clone().unwrap_or_default(),
+            executor_node_id: leader.node_id.clone(),
+        }),
+        sender_signature: String::new(),
+    };
+    let _ = outbound_tx.send(envelope);
+
+    if !result.success {
+        return;
+    }
+    let Some(submitter) = submitter else {
+        return;
+    };
+    let (action, chat_id, params) = leader.determine_action(&message);
+    if let Some(entry) = ActionLogEntry::from_execution(
+        &message,
+        &action,
+        chat_id,
+        &params,
+        &leader.node_id,
+        consensus_nodes.len(),
+    ) {
+        submitter.queue_action_log(entry).await;
+    }
+}
+
 /// 确定 K 值（与 Agent/Verifier 完全一致）
 fn select_k(total_nodes: usize) -> usize {
     if total_nodes <= 3 { return total_nodes; }
@@ -552,6 +742,145 @@ mod tests {
         }
     }
 
+    /// 使用真实 Ed25519 签名构造消息（bot_id_hash / message_hash 为 32 字节 hex）
+    fn make_signed(sk: &ed25519_dalek::SigningKey, seq: u64, body: &str) -> SignedMessage {
+        use ed25519_dalek::Signer;
+        use sha2::{Digest, Sha256};
+
+        let pk = sk.verifying_key().to_bytes();
+        let bot_id_hash = [0xbb; 32];
+        let msg_hash: [u8; 32] = Sha256::digest(body.as_bytes()).into();
+        let timestamp = chrono::Utc::now().timestamp() as u64;
+        let mut data = Vec::new();
+        data.extend_from_slice(&pk);
+        data.extend_from_slice(&bot_id_hash);
+        data.extend_from_slice(&seq.to_le_bytes());
+        data.extend_from_slice(&timestamp.to_le_bytes());
+        data.extend_from_slice(&msg_hash);
+
+        SignedMessage {
+            owner_public_key: hex::encode(pk),
+            bot_id_hash: hex::encode(bot_id_hash),
+            sequence: seq,
+            timestamp,
+            message_hash: hex::encode(msg_hash),
+            platform_event: serde_json::json!({ "body": body }),
+            owner_signature: hex::encode(sk.sign(&data).to_bytes()),
+            platform: "telegram".into(),
+        }
+    }
+
+    fn make_engine_with_submitter(
+        sk: &ed25519_dalek::SigningKey,
+        data_dir: &std::path::Path,
+    ) -> (GossipEngine, Arc<ChainSubmitter>) {
+        let engine = make_test_engine();
+        engine.chain_cache.register_bot(crate::types::BotInfoCache {
+            bot_id_hash: hex::encode([0xbb; 32]),
+            owner_public_key: sk.verifying_key().to_bytes(),
+            platform: "telegram".into(),
+            is_active: true,
+        });
+        let submitter = Arc::new(ChainSubmitter::with_data_dir("node_a".into(), data_dir.to_path_buf()));
+        (engine.with_submitter(submitter.clone()), submitter)
+    }
+
+    #[tokio::test]
+    async fn test_leader_queues_confirmation() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let sk = ed25519_dalek::SigningKey::from_bytes(&[1u8; 32]);
+        let (engine, submitter) = make_engine_with_submitter(&sk, tmp.path());
+
+        // 找一个由 node_a 担任 Leader 的序列号
+        let nodes = engine.chain_cache.get_active_node_ids();
+        let msg = (1..100u64)
+            .map(|seq| make_signed(&sk, seq, "hello"))
+            .find(|m| {
+                let targets = deterministic_select_ids(&nodes, &m.message_hash, m.sequence, select_k(nodes.len()));
+                crate::leader::LeaderExecutor::elect_leader(&targets, m.sequence).leader == "node_a"
+            })
+            .unwrap();
+
+        engine.on_agent_message(msg);
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+
+        assert_eq!(submitter.queue_sizes().await, (1, 0, 0));
+        assert!(tmp.path().join("submitter/pending.scale").exists());
+    }
+
+    #[tokio::test]
+    async fn test_conflicting_originals_reported() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let sk = ed25519_dalek::SigningKey::from_bytes(&[2u8; 32]);
+        let (engine, submitter) = make_engine_with_submitter(&sk, tmp.path());
+        let mut rx = engine.outbound_tx.subscribe();
+
+        let a = make_signed(&sk, 7, "a");
+        let b = make_signed(&sk, 7, "b");
+        let msg_id = GossipState::make_msg_id(&a.bot_id_hash, a.sequence);
+        engine.on_agent_message(a);
+
+        // 其他节点 Pull 响应携带同序列号、不同哈希的签名消息
+        engine.on_gossip_message(GossipEnvelope {
+            version: 1,
+            msg_type: GossipType::MessagePullResponse,
+            sender_node_id: "node_b".into(),
+            timestamp: 0,
+            payload: GossipPayload::PullResponse(PullResponsePayload {
+                msg_id,
+                signed_message: b,
+            }),
+            sender_signature: String::new(),
+        });
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+
+        assert_eq!(submitter.queue_sizes().await.2, 1);
+        let mut alerted = false;
+        while let Ok(envelope) = rx.try_recv() {
+            if let GossipPayload::Alert(alert) = envelope.payload {
+                assert_eq!(alert.sequence, 7);
+                assert_ne!(alert.msg_hash_a, alert.msg_hash_b);
+                alerted = true;
+            }
+        }
+        assert!(alerted);
+    }
+
+    #[tokio::test]
+    async fn test_alert_with_forged_signature_ignored() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let sk = ed25519_dalek::SigningKey::from_bytes(&[3u8; 32]);
+        let (engine, submitter) = make_engine_with_submitter(&sk, tmp.path());
+
+        let a = make_signed(&sk, 9, "a");
+        let mut b = make_signed(&sk, 9, "b");
+        b.owner_signature = a.owner_signature.clone();
+        let alert = AlertPayload::from_conflict(&a, &b);
+        engine.on_gossip_message(GossipEnvelope {
+            version: 1,
+            msg_type: GossipType::EquivocationAlert,
+            sender_node_id: "node_b".into(),
+            timestamp: 0,
+            payload: GossipPayload::Alert(alert),
+            sender_signature: String::new(),
+        });
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        assert_eq!(submitter.queue_sizes().await.2, 0);
+
+        // 有效证据 → 入队
+        let b = make_signed(&sk, 9, "b");
+        engine.on_gossip_message(GossipEnvelope {
+            version: 1,
+            msg_type: GossipType::EquivocationAlert,
+            sender_node_id: "node_b".into(),
+            timestamp: 0,
+            payload: GossipPayload::Alert(AlertPayload::from_conflict(&a, &b)),
+            sender_signature: String::new(),
+        });
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        assert_eq!(submitter.queue_sizes().await.2, 1);
+    }
+
     #[test]
     fn test_consensus_flow() {
         let engine = make_test_engine();
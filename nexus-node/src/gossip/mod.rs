@@ -1,3 +1,4 @@
+pub mod auth;
 pub mod engine;
 pub mod network;
 pub mod state;
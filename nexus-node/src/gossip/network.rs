@@ -1,61 +1,150 @@
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use serde::Serialize;
 use std::collections::HashMap;
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncWrite};
 use tokio::sync::{mpsc, RwLock};
 use tokio::net::TcpListener;
 use futures_util::{StreamExt, SinkExt};
-use tokio_tungstenite::{accept_async, connect_async, tungstenite::Message};
+use tokio_tungstenite::{accept_async, connect_async, tungstenite::Message, WebSocketStream};
 use tracing::{info, warn, debug, error};
 
 use crate::chain_cache::ChainCache;
+use crate::gossip::auth::{self, AuthError, HandshakeFrame, NodeIdentity};
+use crate::metrics::metrics;
 use crate::types::GossipEnvelope;
 
+/// 握手超时
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// 对等节点发现间隔
+const DISCOVERY_INTERVAL: Duration = Duration::from_secs(10);
+
+/// 已认证对等连接
+struct PeerHandle {
+    /// 连接序号（同一节点重连时区分新旧连接）
+    conn_id: u64,
+    sender: mpsc::UnboundedSender<Message>,
+    /// 连接方向: inbound（对端连入）/ outbound（本节点连出）
+    direction: &'static str,
+    /// 对端地址
+    address: String,
+    /// 认证完成时间 (Unix ms)
+    connected_at: u64,
+    stats: Arc<PeerStats>,
+}
+
+/// 单连接收发计数
+#[derive(Default)]
+struct PeerStats {
+    received: AtomicU64,
+    sent: AtomicU64,
+}
+
+/// 对等连接快照（GET /v1/peers）
+#[derive(Debug, Clone, Serialize)]
+pub struct PeerInfo {
+    pub node_id: String,
+    pub direction: &'static str,
+    pub address: String,
+    pub connected_at: u64,
+    pub messages_received: u64,
+    pub messages_sent: u64,
+}
+
+/// 对等节点连接池: 已认证 node_id → 连接
+///
+/// 由 main 创建并与 API 层共享，供 /v1/peers 查询
+#[derive(Default)]
+pub struct GossipPeers {
+    peers: RwLock<HashMap<String, PeerHandle>>,
+}
+
+impl GossipPeers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 已认证连接快照（按 node_id 排序）
+    pub async fn snapshot(&self) -> Vec<PeerInfo> {
+        let peers = self.peers.read().await;
+        let mut list: Vec<PeerInfo> = peers.iter().map(|(node_id, p)| PeerInfo {
+            node_id: node_id.clone(),
+            direction: p.direction,
+            address: p.address.clone(),
+            connected_at: p.connected_at,
+            messages_received: p.stats.received.load(Ordering::Relaxed),
+            messages_sent: p.stats.sent.load(Ordering::Relaxed),
+        }).collect();
+        list.sort_by(|a, b| a.node_id.cmp(&b.node_id));
+        list
+    }
+
+    /// 已认证连接数
+    pub async fn len(&self) -> usize {
+        self.peers.read().await.len()
+    }
+}
+
+/// 网络层共享上下文
+struct NetworkContext {
+    identity: NodeIdentity,
+    chain_cache: Arc<ChainCache>,
+    peers: Arc<GossipPeers>,
+    inbound_tx: mpsc::UnboundedSender<GossipEnvelope>,
+    next_conn_id: AtomicU64,
+}
+
 /// Gossip WebSocket 网络层
 ///
 /// 职责:
 /// 1. 启动 WebSocket 服务端（接收其他节点的连接）
 /// 2. 连接到所有对等节点（WebSocket 客户端）
-/// 3. 广播 outbound 消息到所有对等节点
-/// 4. 接收 inbound 消息转发到 GossipEngine
-
-/// 运行 Gossip 网络层（阻塞）
+/// 3. 握手认证对端节点密钥（ChainCache 节点记录），未认证连接直接断开
+/// 4. 签名并广播 outbound 消息到所有已认证对等节点
+/// 5. 验证 inbound 消息签名后转发到 GossipEngine
+///
+/// 阻塞运行；`listener` 由调用方绑定（`GOSSIP_PORT`），验证通过的 inbound 消息写入 `inbound_tx`
 pub async fn run_gossip_network(
-    node_id: String,
-    gossip_port: u16,
+    identity: NodeIdentity,
+    listener: TcpListener,
     chain_cache: Arc<ChainCache>,
+    peers: Arc<GossipPeers>,
     mut outbound_rx: mpsc::UnboundedReceiver<GossipEnvelope>,
+    inbound_tx: mpsc::UnboundedSender<GossipEnvelope>,
 ) -> anyhow::Result<()> {
-    // 对等节点连接池: node_id → sender
-    let peers: Arc<RwLock<HashMap<String, mpsc::UnboundedSender<Message>>>> =
-        Arc::new(RwLock::new(HashMap::new()));
+    let ctx = Arc::new(NetworkContext {
+        identity,
+        chain_cache,
+        peers,
+        inbound_tx,
+        next_conn_id: AtomicU64::new(0),
+    });
+    let local_addr = listener.local_addr()?;
 
     // 1. 启动 WebSocket 服务端
-    let server_peers = peers.clone();
-    let server_node_id = node_id.clone();
+    let server_ctx = ctx.clone();
     tokio::spawn(async move {
-        if let Err(e) = run_ws_server(gossip_port, server_node_id, server_peers).await {
+        if let Err(e) = run_ws_server(listener, server_ctx).await {
             error!(error = %e, "Gossip WS 服务端退出");
         }
     });
 
     // 2. 定时连接到对等节点
-    let connect_peers = peers.clone();
-    let connect_cache = chain_cache.clone();
-    let connect_node_id = node_id.clone();
+    let connect_ctx = ctx.clone();
     tokio::spawn(async move {
-        let mut interval = tokio::time::interval(std::time::Duration::from_secs(10));
+        let mut interval = tokio::time::interval(DISCOVERY_INTERVAL);
         loop {
             interval.tick().await;
-            discover_and_connect(
-                &connect_node_id,
-                &connect_cache,
-                &connect_peers,
-            ).await;
+            discover_and_connect(&connect_ctx).await;
         }
     });
 
-    // 3. 广播 outbound 消息
-    info!(port = gossip_port, "Gossip 网络层已启动");
-    while let Some(envelope) = outbound_rx.recv().await {
+    // 3. 签名并广播 outbound 消息
+    info!(addr = %local_addr, "Gossip 网络层已启动");
+    while let Some(mut envelope) = outbound_rx.recv().await {
+        ctx.identity.sign_envelope(&mut envelope);
         let msg_bytes = match serde_json::to_vec(&envelope) {
             Ok(b) => b,
             Err(e) => {
@@ -64,14 +153,15 @@ pub async fn run_gossip_network(
             }
         };
         let ws_msg = Message::Binary(msg_bytes);
+        metrics().on_gossip_sent(envelope.msg_type);
 
-        let peers_read = peers.read().await;
-        for (peer_id, sender) in peers_read.iter() {
-            if *peer_id == node_id {
-                continue; // 不发给自己
-            }
-            if let Err(e) = sender.send(ws_msg.clone()) {
-                debug!(peer = peer_id, error = %e, "发送到对等节点失败");
+        let peers_read = ctx.peers.peers.read().await;
+        for (peer_id, peer) in peers_read.iter() {
+            match peer.sender.send(ws_msg.clone()) {
+                Ok(()) => {
+                    peer.stats.sent.fetch_add(1, Ordering::Relaxed);
+                }
+                Err(e) => debug!(peer = peer_id, error = %e, "发送到对等节点失败"),
             }
         }
     }
@@ -80,21 +170,14 @@ pub async fn run_gossip_network(
 }
 
 /// WebSocket 服务端
-async fn run_ws_server(
-    port: u16,
-    node_id: String,
-    peers: Arc<RwLock<HashMap<String, mpsc::UnboundedSender<Message>>>>,
-) -> anyhow::Result<()> {
-    let addr = format!("0.0.0.0:{}", port);
-    let listener = TcpListener::bind(&addr).await?;
-    info!(addr, "Gossip WebSocket 服务端已启动");
+async fn run_ws_server(listener: TcpListener, ctx: Arc<NetworkContext>) -> anyhow::Result<()> {
+    info!(addr = %listener.local_addr()?, "Gossip WebSocket 服务端已启动");
 
     while let Ok((stream, peer_addr)) = listener.accept().await {
-        let peers = peers.clone();
-        let _node_id = node_id.clone();
+        let ctx = ctx.clone();
 
         tokio::spawn(async move {
-            let ws_stream = match accept_async(stream).await {
+            let mut ws_stream = match accept_async(stream).await {
                 Ok(ws) => ws,
                 Err(e) => {
                     warn!(peer = %peer_addr, error = %e, "WebSocket 握手失败");
@@ -102,51 +185,27 @@ async fn run_ws_server(
                 }
             };
 
-            let (mut ws_sender, mut ws_receiver) = ws_stream.split();
-            let (tx, mut rx) = mpsc::unbounded_channel::<Message>();
-
-            // 为此连接注册发送通道
-            let peer_id = format!("inbound_{}", peer_addr);
-            peers.write().await.insert(peer_id.clone(), tx);
-
-            // 发送 → WebSocket
-            let send_task = tokio::spawn(async move {
-                while let Some(msg) = rx.recv().await {
-                    if ws_sender.send(msg).await.is_err() {
-                        break;
-                    }
+            let peer_id = match tokio::time::timeout(
+                HANDSHAKE_TIMEOUT,
+                server_handshake(&mut ws_stream, &ctx),
+            ).await {
+                Ok(Ok(peer_id)) => peer_id,
+                Ok(Err(e)) => {
+                    metrics().gossip_auth_failures.inc();
+                    warn!(peer = %peer_addr, error = %e, "对等节点认证失败，断开连接");
+                    let _ = ws_stream.close(None).await;
+                    return;
                 }
-            });
-
-            // WebSocket → 接收处理
-            while let Some(msg_result) = ws_receiver.next().await {
-                match msg_result {
-                    Ok(Message::Binary(data)) => {
-                        if let Ok(envelope) = serde_json::from_slice::<GossipEnvelope>(&data) {
-                            debug!(
-                                from = envelope.sender_node_id,
-                                msg_type = ?envelope.msg_type,
-                                "收到 Gossip 消息"
-                            );
-                            // TODO: 转发到 GossipEngine 处理
-                        }
-                    }
-                    Ok(Message::Ping(_data)) => {
-                        // Pong 由 tungstenite 自动处理
-                    }
-                    Ok(Message::Close(_)) => break,
-                    Err(e) => {
-                        debug!(error = %e, "WebSocket 接收错误");
-                        break;
-                    }
-                    _ => {}
+                Err(_) => {
+                    metrics().gossip_auth_failures.inc();
+                    warn!(peer = %peer_addr, "对等节点认证超时，断开连接");
+                    let _ = ws_stream.close(None).await;
+                    return;
                 }
-            }
+            };
 
-            // 清理
-            peers.write().await.remove(&peer_id);
-            send_task.abort();
-            debug!(peer = %peer_addr, "对等节点断开");
+            info!(peer = peer_id, addr = %peer_addr, "对等节点已认证 (inbound)");
+            run_peer(ws_stream, peer_id, "inbound", peer_addr.to_string(), ctx).await;
         });
     }
 
@@ -154,21 +213,21 @@ async fn run_ws_server(
 }
 
 /// 发现并连接到对等节点
-async fn discover_and_connect(
-    my_node_id: &str,
-    cache: &ChainCache,
-    peers: &Arc<RwLock<HashMap<String, mpsc::UnboundedSender<Message>>>>,
-) {
-    let all_nodes = cache.get_all_nodes();
-    let connected = peers.read().await;
+async fn discover_and_connect(ctx: &Arc<NetworkContext>) {
+    let all_nodes = ctx.chain_cache.get_all_nodes();
+    let connected = ctx.peers.peers.read().await;
 
     for node in &all_nodes {
-        if node.node_id == my_node_id {
+        if node.node_id == ctx.identity.node_id {
             continue;
         }
         if connected.contains_key(&node.node_id) {
             continue;
         }
+        // 未登记公钥的节点无法通过认证
+        if node.node_public_key == [0u8; 32] {
+            continue;
+        }
 
         // 从 HTTP endpoint 推导 WebSocket endpoint
         // http://host:8080 → ws://host:9090
@@ -178,59 +237,227 @@ async fn discover_and_connect(
         }
 
         let node_id = node.node_id.clone();
-        let peers = peers.clone();
+        let ctx = ctx.clone();
 
         tokio::spawn(async move {
-            match connect_async(&ws_url).await {
-                Ok((ws_stream, _)) => {
-                    info!(peer = node_id, url = ws_url, "已连接到对等节点");
-
-                    let (mut ws_sender, mut ws_receiver) = ws_stream.split();
-                    let (tx, mut rx) = mpsc::unbounded_channel::<Message>();
-
-                    peers.write().await.insert(node_id.clone(), tx);
-
-                    // 发送任务
-                    let send_node_id = node_id.clone();
-                    let send_task = tokio::spawn(async move {
-                        while let Some(msg) = rx.recv().await {
-                            if ws_sender.send(msg).await.is_err() {
-                                break;
-                            }
-                        }
-                    });
-
-                    // 接收处理
-                    while let Some(msg_result) = ws_receiver.next().await {
-                        match msg_result {
-                            Ok(Message::Binary(data)) => {
-                                if let Ok(envelope) = serde_json::from_slice::<GossipEnvelope>(&data) {
-                                    debug!(
-                                        from = envelope.sender_node_id,
-                                        msg_type = ?envelope.msg_type,
-                                        "收到对等消息"
-                                    );
-                                    // TODO: 转发到 GossipEngine
-                                }
-                            }
-                            Ok(Message::Close(_)) => break,
-                            Err(_) => break,
-                            _ => {}
-                        }
-                    }
-
-                    peers.write().await.remove(&node_id);
-                    send_task.abort();
-                    debug!(peer = send_node_id, "对等连接断开");
-                }
+            let mut ws_stream = match connect_async(&ws_url).await {
+                Ok((ws_stream, _)) => ws_stream,
                 Err(e) => {
                     debug!(peer = node_id, error = %e, "连接对等节点失败");
+                    return;
+                }
+            };
+
+            match tokio::time::timeout(
+                HANDSHAKE_TIMEOUT,
+                client_handshake(&mut ws_stream, &ctx, &node_id),
+            ).await {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => {
+                    metrics().gossip_auth_failures.inc();
+                    warn!(peer = node_id, error = %e, "对等节点认证失败，断开连接");
+                    let _ = ws_stream.close(None).await;
+                    return;
+                }
+                Err(_) => {
+                    metrics().gossip_auth_failures.inc();
+                    warn!(peer = node_id, "对等节点认证超时，断开连接");
+                    let _ = ws_stream.close(None).await;
+                    return;
                 }
             }
+
+            info!(peer = node_id, url = ws_url, "已连接到对等节点");
+            run_peer(ws_stream, node_id, "outbound", ws_url, ctx).await;
         });
     }
 }
 
+/// 服务端握手: 发 Challenge → 验 Auth → 回 AuthAck
+///
+/// 返回已认证的对端 node_id
+async fn server_handshake<S>(
+    ws: &mut WebSocketStream<S>,
+    ctx: &NetworkContext,
+) -> Result<String, AuthError>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let my_node_id = &ctx.identity.node_id;
+    let nonce = auth::new_nonce();
+    send_frame(ws, &HandshakeFrame::Challenge {
+        node_id: my_node_id.clone(),
+        nonce: hex::encode(nonce),
+    }).await?;
+
+    let HandshakeFrame::Auth { node_id, signature, nonce: peer_nonce } = recv_frame(ws).await? else {
+        return Err(AuthError::Handshake("expected Auth".into()));
+    };
+    if node_id == *my_node_id {
+        return Err(AuthError::Handshake("peer claims our node id".into()));
+    }
+    auth::verify_challenge_response(&ctx.chain_cache, &nonce, &node_id, my_node_id, &signature)?;
+
+    let peer_nonce = auth::parse_nonce(&peer_nonce)?;
+    send_frame(ws, &HandshakeFrame::AuthAck {
+        signature: ctx.identity.sign_challenge(&peer_nonce, &node_id),
+    }).await?;
+
+    Ok(node_id)
+}
+
+/// 客户端握手: 收 Challenge → 发 Auth（含反向挑战）→ 验 AuthAck
+async fn client_handshake<S>(
+    ws: &mut WebSocketStream<S>,
+    ctx: &NetworkContext,
+    expected_peer: &str,
+) -> Result<(), AuthError>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let HandshakeFrame::Challenge { node_id, nonce } = recv_frame(ws).await? else {
+        return Err(AuthError::Handshake("expected Challenge".into()));
+    };
+    if node_id != expected_peer {
+        return Err(AuthError::Handshake(format!("expected {}, got {}", expected_peer, node_id)));
+    }
+
+    let server_nonce = auth::parse_nonce(&nonce)?;
+    let my_nonce = auth::new_nonce();
+    send_frame(ws, &HandshakeFrame::Auth {
+        node_id: ctx.identity.node_id.clone(),
+        signature: ctx.identity.sign_challenge(&server_nonce, expected_peer),
+        nonce: hex::encode(my_nonce),
+    }).await?;
+
+    let HandshakeFrame::AuthAck { signature } = recv_frame(ws).await? else {
+        return Err(AuthError::Handshake("expected AuthAck".into()));
+    };
+    auth::verify_challenge_response(
+        &ctx.chain_cache,
+        &my_nonce,
+        expected_peer,
+        &ctx.identity.node_id,
+        &signature,
+    )
+}
+
+async fn send_frame<S>(ws: &mut WebSocketStream<S>, frame: &HandshakeFrame) -> Result<(), AuthError>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let text = serde_json::to_string(frame).map_err(|e| AuthError::Handshake(e.to_string()))?;
+    ws.send(Message::Text(text)).await.map_err(|e| AuthError::Handshake(e.to_string()))
+}
+
+/// 读取下一个握手帧（握手阶段只接受 Text 帧）
+async fn recv_frame<S>(ws: &mut WebSocketStream<S>) -> Result<HandshakeFrame, AuthError>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    loop {
+        match ws.next().await {
+            Some(Ok(Message::Text(text))) => {
+                return serde_json::from_str(&text).map_err(|e| AuthError::Handshake(e.to_string()));
+            }
+            Some(Ok(Message::Ping(_))) | Some(Ok(Message::Pong(_))) => continue,
+            Some(Ok(_)) => return Err(AuthError::Handshake("unexpected frame before auth".into())),
+            Some(Err(e)) => return Err(AuthError::Handshake(e.to_string())),
+            None => return Err(AuthError::Handshake("connection closed".into())),
+        }
+    }
+}
+
+/// 已认证连接的收发循环
+///
+/// 签名无效或冒充他人的 Envelope → 断开连接
+async fn run_peer<S>(
+    ws_stream: WebSocketStream<S>,
+    peer_id: String,
+    direction: &'static str,
+    address: String,
+    ctx: Arc<NetworkContext>,
+)
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    let (mut ws_sender, mut ws_receiver) = ws_stream.split();
+    let (tx, mut rx) = mpsc::unbounded_channel::<Message>();
+
+    let conn_id = ctx.next_conn_id.fetch_add(1, Ordering::Relaxed);
+    let stats = Arc::new(PeerStats::default());
+    {
+        let mut peers = ctx.peers.peers.write().await;
+        peers.insert(peer_id.clone(), PeerHandle {
+            conn_id,
+            sender: tx,
+            direction,
+            address,
+            connected_at: chrono::Utc::now().timestamp_millis() as u64,
+            stats: stats.clone(),
+        });
+        metrics().gossip_peers.set(peers.len() as i64);
+    }
+
+    // 发送 → WebSocket
+    let send_task = tokio::spawn(async move {
+        while let Some(msg) = rx.recv().await {
+            if ws_sender.send(msg).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    // WebSocket → 验签 → GossipEngine
+    while let Some(msg_result) = ws_receiver.next().await {
+        match msg_result {
+            Ok(Message::Binary(data)) => {
+                let envelope = match serde_json::from_slice::<GossipEnvelope>(&data) {
+                    Ok(envelope) => envelope,
+                    Err(e) => {
+                        debug!(peer = peer_id, error = %e, "无法解析 GossipEnvelope");
+                        continue;
+                    }
+                };
+                if let Err(e) = auth::verify_envelope(&ctx.chain_cache, &envelope, &peer_id) {
+                    metrics().gossip_rejected.inc();
+                    warn!(peer = peer_id, error = %e, "Gossip 消息验签失败，断开连接");
+                    break;
+                }
+                stats.received.fetch_add(1, Ordering::Relaxed);
+                debug!(
+                    from = envelope.sender_node_id,
+                    msg_type = ?envelope.msg_type,
+                    "收到 Gossip 消息"
+                );
+                if ctx.inbound_tx.send(envelope).is_err() {
+                    break;
+                }
+            }
+            Ok(Message::Ping(_data)) => {
+                // Pong 由 tungstenite 自动处理
+            }
+            Ok(Message::Close(_)) => break,
+            Err(e) => {
+                debug!(error = %e, "WebSocket 接收错误");
+                break;
+            }
+            _ => {}
+        }
+    }
+
+    // 清理（仅移除本连接，避免误删重连后的新连接）
+    {
+        let mut peers = ctx.peers.peers.write().await;
+        if peers.get(&peer_id).is_some_and(|p| p.conn_id == conn_id) {
+            peers.remove(&peer_id);
+        }
+        metrics().gossip_peers.set(peers.len() as i64);
+    }
+    send_task.abort();
+    debug!(peer = peer_id, "对等节点断开");
+}
+
 /// HTTP endpoint → WebSocket endpoint
 ///
 /// http://host:8080 → ws://host:9090
@@ -243,3 +470,184 @@ fn endpoint_to_ws(http_endpoint: &str) -> String {
         .replace(":8082", ":9092")
         .replace(":8083", ":9093")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{GossipPayload, GossipType, HeartbeatPayload, NodeInfoCache};
+    use ed25519_dalek::SigningKey;
+
+    fn identity(node_id: &str, seed: u8) -> NodeIdentity {
+        NodeIdentity::new(node_id.into(), SigningKey::from_bytes(&[seed; 32]))
+    }
+
+    fn register(cache: &ChainCache, node_id: &str, seed: u8, port: u16) {
+        cache.add_node(NodeInfoCache {
+            node_id: node_id.into(),
+            endpoint: format!("ws://127.0.0.1:{}", port),
+            node_public_key: *SigningKey::from_bytes(&[seed; 32]).verifying_key().as_bytes(),
+            status: "Active".into(),
+            reputation: 5000,
+        });
+    }
+
+    struct TestNode {
+        outbound: mpsc::UnboundedSender<GossipEnvelope>,
+        inbound: mpsc::UnboundedReceiver<GossipEnvelope>,
+        peers: Arc<GossipPeers>,
+    }
+
+    fn start_node(identity: NodeIdentity, listener: TcpListener, cache: Arc<ChainCache>) -> TestNode {
+        let (outbound, outbound_rx) = mpsc::unbounded_channel();
+        let (inbound_tx, inbound) = mpsc::unbounded_channel();
+        let peers = Arc::new(GossipPeers::new());
+        tokio::spawn(run_gossip_network(identity, listener, cache, peers.clone(), outbound_rx, inbound_tx));
+        TestNode { outbound, inbound, peers }
+    }
+
+    async fn bind() -> (TcpListener, u16) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        (listener, port)
+    }
+
+    fn heartbeat(node_id: &str) -> GossipEnvelope {
+        GossipEnvelope {
+            version: 1,
+            msg_type: GossipType::Heartbeat,
+            sender_node_id: node_id.into(),
+            timestamp: chrono::Utc::now().timestamp_millis() as u64,
+            payload: GossipPayload::Heartbeat(HeartbeatPayload {
+                node_id: node_id.into(),
+                messages_processed: 0,
+                active_connections: 0,
+            }),
+            sender_signature: String::new(),
+        }
+    }
+
+    /// 反复广播直到目标节点收到（连接建立前发出的消息会被丢弃）
+    async fn deliver(
+        from: &TestNode,
+        from_id: &str,
+        to: &mut TestNode,
+    ) -> Option<GossipEnvelope> {
+        for _ in 0..50 {
+            from.outbound.send(heartbeat(from_id)).unwrap();
+            if let Ok(Some(envelope)) =
+                tokio::time::timeout(Duration::from_millis(100), to.inbound.recv()).await
+            {
+                return Some(envelope);
+            }
+        }
+        None
+    }
+
+    #[tokio::test]
+    async fn test_authenticated_nodes_exchange_messages() {
+        let (la, pa) = bind().await;
+        let (lb, pb) = bind().await;
+        let (lc, pc) = bind().await;
+        let cache = Arc::new(ChainCache::new());
+        register(&cache, "node_a", 1, pa);
+        register(&cache, "node_b", 2, pb);
+        register(&cache, "node_c", 3, pc);
+
+        let mut a = start_node(identity("node_a", 1), la, cache.clone());
+        let mut b = start_node(identity("node_b", 2), lb, cache.clone());
+        let mut c = start_node(identity("node_c", 3), lc, cache.clone());
+
+        let got = deliver(&a, "node_a", &mut b).await.expect("node_b 应收到 node_a 的消息");
+        assert_eq!(got.sender_node_id, "node_a");
+        assert_eq!(got.msg_type, GossipType::Heartbeat);
+        assert!(auth::verify_envelope(&cache, &got, "node_a").is_ok());
+
+        let got = deliver(&a, "node_a", &mut c).await.expect("node_c 应收到 node_a 的消息");
+        assert_eq!(got.sender_node_id, "node_a");
+
+        // 反方向同样可达
+        let got = deliver(&c, "node_c", &mut a).await.expect("node_a 应收到 node_c 的消息");
+        assert_eq!(got.sender_node_id, "node_c");
+
+        // 连接池按认证后的 node_id 登记
+        let peers = a.peers.snapshot().await;
+        let ids: Vec<&str> = peers.iter().map(|p| p.node_id.as_str()).collect();
+        assert_eq!(ids, vec!["node_b", "node_c"]);
+    }
+
+    #[tokio::test]
+    async fn test_peer_with_unregistered_key_is_dropped() {
+        let (la, pa) = bind().await;
+        let (lm, pm) = bind().await;
+
+        // node_a 的缓存中 node_m 登记的是另一把公钥
+        let cache_a = Arc::new(ChainCache::new());
+        register(&cache_a, "node_a", 1, pa);
+        register(&cache_a, "node_m", 99, pm);
+        // node_m 自认为合法
+        let cache_m = Arc::new(ChainCache::new());
+        register(&cache_m, "node_a", 1, pa);
+        register(&cache_m, "node_m", 9, pm);
+
+        let mut a = start_node(identity("node_a", 1), la, cache_a);
+        let mut m = start_node(identity("node_m", 9), lm, cache_m);
+
+        assert!(deliver(&m, "node_m", &mut a).await.is_none());
+        assert!(deliver(&a, "node_a", &mut m).await.is_none());
+        assert_eq!(a.peers.len().await, 0);
+        assert_eq!(m.peers.len().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_unauthenticated_and_forged_frames_are_dropped() {
+        let (la, pa) = bind().await;
+        let cache = Arc::new(ChainCache::new());
+        register(&cache, "node_a", 1, pa);
+        register(&cache, "node_b", 2, 0);
+        let mut a = start_node(identity("node_a", 1), la, cache.clone());
+        let url = format!("ws://127.0.0.1:{}", pa);
+
+        // 1. 跳过握手直接发送已签名消息 → 断开，消息不转发
+        let b = identity("node_b", 2);
+        let mut signed = heartbeat("node_b");
+        b.sign_envelope(&mut signed);
+        let (mut ws, _) = connect_async(&url).await.unwrap();
+        ws.send(Message::Binary(serde_json::to_vec(&signed).unwrap())).await.unwrap();
+        assert!(matches!(recv_frame(&mut ws).await, Ok(HandshakeFrame::Challenge { .. })));
+        assert!(recv_frame(&mut ws).await.is_err());
+        assert!(tokio::time::timeout(Duration::from_millis(200), a.inbound.recv()).await.is_err());
+
+        // 2. 完成握手 → 有效消息转发；伪造签名 → 断开
+        let (mut ws, _) = connect_async(&url).await.unwrap();
+        let client_ctx = NetworkContext {
+            identity: b.clone(),
+            chain_cache: cache.clone(),
+            peers: Arc::new(GossipPeers::new()),
+            inbound_tx: mpsc::unbounded_channel().0,
+            next_conn_id: AtomicU64::new(0),
+        };
+        client_handshake(&mut ws, &client_ctx, "node_a").await.unwrap();
+
+        ws.send(Message::Binary(serde_json::to_vec(&signed).unwrap())).await.unwrap();
+        let got = tokio::time::timeout(Duration::from_secs(2), a.inbound.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(got.sender_node_id, "node_b");
+
+        let mut forged = signed.clone();
+        forged.timestamp += 1;
+        ws.send(Message::Binary(serde_json::to_vec(&forged).unwrap())).await.unwrap();
+        assert!(tokio::time::timeout(Duration::from_millis(200), a.inbound.recv()).await.is_err());
+        // 连接已被服务端关闭
+        let closed = tokio::time::timeout(Duration::from_secs(2), async {
+            loop {
+                match ws.next().await {
+                    Some(Ok(Message::Close(_))) | None | Some(Err(_)) => break,
+                    Some(Ok(_)) => continue,
+                }
+            }
+        }).await;
+        assert!(closed.is_ok());
+    }
+}
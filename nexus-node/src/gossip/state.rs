@@ -162,6 +162,10 @@ impl GossipState {
             if consistent_count >= m {
                 entry.status = MessageStatus::Confirmed;
                 self.messages_processed.fetch_add(1, Ordering::Relaxed);
+                let now = chrono::Utc::now().timestamp_millis() as u64;
+                let latency_ms = now.saturating_sub(entry.created_at);
+                crate::metrics::metrics().consensus_reached.inc();
+                crate::metrics::metrics().consensus_latency.observe(latency_ms as f64 / 1000.0);
                 info!(msg_id, consistent_count, m, k, "M/K 共识达成 → Confirmed");
                 (true, consistent_count, m)
             } else {
@@ -191,6 +195,22 @@ impl GossipState {
         None
     }
 
+    /// 与原始消息哈希一致的 Seen 节点（按节点 ID 排序，作为共识证据）
+    pub fn consensus_nodes(&self, msg_id: &str) -> Vec<String> {
+        let Some(entry) = self.messages.get(msg_id) else {
+            return Vec::new();
+        };
+        let Some(hash) = entry.original_message.as_ref().map(|m| m.message_hash.as_str()) else {
+            return Vec::new();
+        };
+        let mut nodes: Vec<String> = entry.seen_nodes.values()
+            .filter(|s| s.msg_hash == hash)
+            .map(|s| s.node_id.clone())
+            .collect();
+        nodes.sort();
+        nodes
+    }
+
     /// 获取消息状态
     pub fn get_status(&self, msg_id: &str) -> Option<MessageStatus> {
         self.messages.get(msg_id).map(|e| e.status)
@@ -256,6 +276,67 @@ impl GossipState {
     pub fn active_message_count(&self) -> usize {
         self.messages.len()
     }
+
+    /// 消息状态快照（GET /v1/messages，排查卡住的消息）
+    ///
+    /// 按创建时间升序（最老的在前），可按状态 / 最小存活时间过滤
+    pub fn snapshot(
+        &self,
+        status: Option<MessageStatus>,
+        min_age_ms: u64,
+        limit: usize,
+    ) -> Vec<MessageSummary> {
+        let now = chrono::Utc::now().timestamp_millis() as u64;
+        let mut list: Vec<MessageSummary> = self.messages.iter()
+            .filter(|e| status.is_none_or(|s| e.status == s))
+            .filter(|e| now.saturating_sub(e.created_at) >= min_age_ms)
+            .map(|e| {
+                let mut hashes: Vec<String> = e.seen_nodes.values()
+                    .map(|s| s.msg_hash.clone())
+                    .collect();
+                hashes.sort();
+                hashes.dedup();
+                let mut seen_nodes: Vec<String> = e.seen_nodes.keys().cloned().collect();
+                seen_nodes.sort();
+                MessageSummary {
+                    msg_id: e.msg_id.clone(),
+                    status: e.status,
+                    created_at: e.created_at,
+                    age_ms: now.saturating_sub(e.created_at),
+                    has_original: e.original_message.is_some(),
+                    seen_nodes,
+                    distinct_hashes: hashes.len(),
+                    target_nodes: e.target_nodes.clone(),
+                    leader: e.leader.clone(),
+                    backups: e.backups.clone(),
+                    votes: e.decision_votes.len(),
+                }
+            })
+            .collect();
+        list.sort_by(|a, b| a.created_at.cmp(&b.created_at).then_with(|| a.msg_id.cmp(&b.msg_id)));
+        list.truncate(limit);
+        list
+    }
+}
+
+/// 消息状态摘要
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct MessageSummary {
+    pub msg_id: String,
+    pub status: MessageStatus,
+    /// 创建时间 (Unix ms)
+    pub created_at: u64,
+    pub age_ms: u64,
+    /// 是否持有原始消息（false = 仅通过 Seen 得知，等待 Pull）
+    pub has_original: bool,
+    /// 已 Seen 的节点
+    pub seen_nodes: Vec<String>,
+    /// Seen 中不同 msg_hash 数（> 1 即 Equivocation）
+    pub distinct_hashes: usize,
+    pub target_nodes: Vec<String>,
+    pub leader: Option<String>,
+    pub backups: Vec<String>,
+    pub votes: usize,
 }
 
 #[cfg(test)]
@@ -358,6 +439,40 @@ mod tests {
         assert!(conflict.is_some());
     }
 
+    #[test]
+    fn test_snapshot_filters_and_orders() {
+        let state = GossipState::new();
+        state.on_agent_message(
+            "msg_1", make_signed_msg("hash1", 1),
+            vec!["n1".into(), "n2".into(), "n3".into()],
+            Some("n1".into()),
+            vec!["n2".into(), "n3".into()],
+        );
+        state.on_seen("msg_1", "n2", "hash1");
+        state.on_seen("msg_2", "n3", "hash2");
+
+        // msg_2 更早创建
+        if let Some(mut entry) = state.messages.get_mut("msg_2") {
+            entry.created_at = entry.created_at.saturating_sub(10_000);
+        }
+
+        let all = state.snapshot(None, 0, 100);
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].msg_id, "msg_2");
+        assert!(!all[0].has_original);
+        assert_eq!(all[0].status, MessageStatus::HeardViaSeen);
+        assert_eq!(all[1].seen_nodes, vec!["n2".to_string()]);
+        assert_eq!(all[1].leader.as_deref(), Some("n1"));
+
+        let heard = state.snapshot(Some(MessageStatus::HeardViaSeen), 0, 100);
+        assert_eq!(heard.len(), 1);
+        assert_eq!(heard[0].msg_id, "msg_2");
+
+        let old = state.snapshot(None, 5_000, 100);
+        assert_eq!(old.len(), 1);
+        assert_eq!(state.snapshot(None, 0, 1).len(), 1);
+    }
+
     #[test]
     fn test_gc_removes_old_messages() {
         let state = GossipState::new();
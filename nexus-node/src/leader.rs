@@ -5,6 +5,7 @@ use crate::types::*;
 use crate::types::{ActionType, MessageAction, AdminAction};
 use crate::gossip::state::GossipState;
 use crate::chain_cache::ChainCache;
+use crate::metrics::metrics;
 
 /// Leader 执行器
 ///
@@ -171,6 +172,7 @@ impl LeaderExecutor {
 
         if action_type.is_no_action() {
             debug!(msg_id, "无需执行动作");
+            metrics().leader_executions.with_label_values(&["no_action"]).inc();
             self.state.set_completed(msg_id);
             return Ok(ExecuteResult {
                 action_id: msg_id.to_string(),
@@ -203,8 +205,9 @@ impl LeaderExecutor {
 
         // 4. POST 到 Agent
         let timeout = std::time::Duration::from_millis(self.execute_timeout_ms);
+        let timer = metrics().leader_execute_duration.start_timer();
 
-        match self.http_client
+        let result = match self.http_client
             .post(&url)
             .json(&action)
             .timeout(timeout)
@@ -254,14 +257,21 @@ impl LeaderExecutor {
                     agent_receipt: None,
                 })
             }
+        };
+
+        timer.observe_duration();
+        if let Ok(r) = &result {
+            let label = if r.success { "success" } else { "failure" };
+            metrics().leader_executions.with_label_values(&[label]).inc();
         }
+        result
     }
 
     /// 根据平台事件内容判断动作类型
     ///
     /// 根据 message.platform 分发到对应的 NodePlatformAdapter，
     /// 默认回退到 Telegram 适配器以兼容旧消息。
-    fn determine_action(&self, message: &SignedMessage) -> (ActionType, i64, serde_json::Value) {
+    pub fn determine_action(&self, message: &SignedMessage) -> (ActionType, i64, serde_json::Value) {
         let adapter = crate::platform::get_adapter(&message.platform)
             .unwrap_or_else(|| crate::platform::get_adapter("telegram").unwrap());
         adapter.determine_action(message)
@@ -305,6 +315,7 @@ impl FailoverManager {
         // TODO: 检查时间差（需要记录共识达成时间）
         // 当前简化: 由外部定时器调用
 
+        metrics().leader_takeovers.with_label_values(&["local"]).inc();
         Some(my_rank as u32)
     }
 }
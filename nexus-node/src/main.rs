@@ -6,8 +6,10 @@ mod config;
 // pub mod crypto; // TODO: Sprint 后续实现
 mod gossip;
 mod leader;
+mod metrics;
 pub mod platform;
 #[allow(dead_code)]
+mod rule_dsl;
 mod rule_engine;
 #[allow(dead_code)]
 mod types;
@@ -30,7 +32,9 @@ pub struct AppState {
     pub config: NodeConfig,
     pub chain_cache: Arc<ChainCache>,
     pub gossip_engine: GossipEngine,
+    pub gossip_peers: Arc<gossip::network::GossipPeers>,
     pub chain_submitter: Arc<ChainSubmitter>,
+    pub rule_engine: rule_engine::RuleEngine,
     pub start_time: Instant,
 }
 
@@ -63,7 +67,7 @@ async fn main() -> anyhow::Result<()> {
     chain_cache.load_from_env();
 
     // 尝试 subxt 连接链（可选 — 连接失败不阻止启动）
-    let _chain_client = match chain_client::ChainClient::connect(
+    let chain_client = match chain_client::ChainClient::connect(
         &config.chain_rpc,
         load_or_generate_signer(&config.data_dir),
     ).await {
@@ -82,23 +86,44 @@ async fn main() -> anyhow::Result<()> {
         }
     };
 
-    // Gossip 状态 + 引擎
-    let gossip_state = Arc::new(GossipState::new());
-    let gossip_engine = GossipEngine::new(
+    // 节点 Ed25519 身份（Gossip 握手 + 消息签名）
+    let node_identity = gossip::auth::NodeIdentity::load_or_generate(
         config.node_id.clone(),
-        gossip_state.clone(),
-        chain_cache.clone(),
+        &config.signing_key_path,
     );
 
     // 链上提交器
     let submitter = Arc::new(ChainSubmitter::new(config.node_id.clone()));
+    if let Some(client) = &chain_client {
+        submitter.attach_client(client.clone());
+    }
+    submitter.load_pending_from_disk().await;
     submitter.clone().spawn_submit_loop();
 
+    // Gossip 状态 + 引擎（共识确认 / 动作日志 / Equivocation 举报送入提交器）
+    let gossip_state = Arc::new(GossipState::new());
+    let leader_executor = leader::LeaderExecutor::new(
+        config.node_id.clone(),
+        gossip_state.clone(),
+        chain_cache.clone(),
+    ).with_signing_key(node_identity.signing_key());
+    let gossip_engine = GossipEngine::new(
+        config.node_id.clone(),
+        gossip_state.clone(),
+        chain_cache.clone(),
+    )
+    .with_submitter(submitter.clone())
+    .with_leader(Arc::new(leader_executor));
+
+    let gossip_peers = Arc::new(gossip::network::GossipPeers::new());
+
     let state = Arc::new(AppState {
         config: config.clone(),
         chain_cache: chain_cache.clone(),
         gossip_engine,
+        gossip_peers: gossip_peers.clone(),
         chain_submitter: submitter,
+        rule_engine: rule_engine::RuleEngine::default_engine(),
         start_time: Instant::now(),
     });
 
@@ -114,25 +139,39 @@ async fn main() -> anyhow::Result<()> {
 
     // Gossip WebSocket 网络层
     let gossip_outbound_rx = state.gossip_engine.subscribe_outbound();
+    let (gossip_inbound_tx, mut gossip_inbound_rx) = tokio::sync::mpsc::unbounded_channel();
+    let gossip_listener = tokio::net::TcpListener::bind(("0.0.0.0", config.gossip_port)).await?;
     let ws_cache = chain_cache.clone();
-    let ws_node_id = config.node_id.clone();
-    let ws_gossip_port = config.gossip_port;
     tokio::spawn(async move {
         if let Err(e) = gossip::network::run_gossip_network(
-            ws_node_id,
-            ws_gossip_port,
+            node_identity,
+            gossip_listener,
             ws_cache,
+            gossip_peers,
             gossip_outbound_rx,
+            gossip_inbound_tx,
         ).await {
             warn!(error = %e, "Gossip 网络层退出");
         }
     });
 
+    // 已验签的 Gossip 消息 → GossipEngine
+    let inbound_state = state.clone();
+    tokio::spawn(async move {
+        while let Some(envelope) = gossip_inbound_rx.recv().await {
+            inbound_state.gossip_engine.on_gossip_message(envelope);
+        }
+    });
+
     // Axum HTTP API
     let app = Router::new()
         .route("/v1/message", post(api::handle_message))
         .route("/v1/status/{msg_id}", get(api::handle_message_status))
+        .route("/v1/rules/dry-run", post(api::handle_rules_dry_run))
+        .route("/v1/peers", get(api::handle_peers))
+        .route("/v1/messages", get(api::handle_messages))
         .route("/health", get(api::handle_health))
+        .route("/metrics", get(api::handle_metrics))
         .with_state(state);
 
     let addr = format!("0.0.0.0:{}", config.listen_port);
@@ -149,47 +188,56 @@ async fn refresh_cache_from_chain(
     client: &chain_client::ChainClient,
     cache: &ChainCache,
 ) {
-    match client.fetch_active_node_list().await {
-        Ok(node_ids) => {
-            for nid in &node_ids {
-                if let Ok(Some(node_info)) = client.fetch_node(nid).await {
-                    cache.add_node(node_info);
-                }
-            }
-            info!(count = node_ids.len(), "从链上刷新节点列表完成");
-        }
-        Err(e) => warn!(error = %e, "获取链上节点列表失败"),
-    }
+    client.refresh_cache(cache).await;
 }
 
 /// 启动链上事件订阅
+///
+/// 每个 finalized 区块检查 BotRegistry / BotConsensus 事件并增量刷新缓存；
+/// 订阅断开后重连并全量刷新，另每 100 个区块全量刷新一次兜底。
 fn spawn_chain_watcher(
     client: Arc<chain_client::ChainClient>,
     cache: Arc<ChainCache>,
 ) {
     tokio::spawn(async move {
         use futures_util::StreamExt;
-        match client.subscribe_finalized_blocks().await {
-            Ok(mut stream) => {
-                info!("链上区块订阅已启动");
-                let mut block_count: u64 = 0;
-                while let Some(block_result) = stream.next().await {
-                    match block_result {
-                        Ok(_block) => {
-                            block_count += 1;
-                            // 每 100 个区块刷新一次缓存
-                            if block_count % 100 == 0 {
-                                refresh_cache_from_chain(&client, &cache).await;
+        loop {
+            match client.subscribe_finalized_blocks().await {
+                Ok(mut stream) => {
+                    info!("链上区块订阅已启动");
+                    let mut block_count: u64 = 0;
+                    while let Some(block_result) = stream.next().await {
+                        match block_result {
+                            Ok(block) => {
+                                block_count += 1;
+                                match client.block_updates(&block).await {
+                                    Ok(updates) => {
+                                        for update in &updates {
+                                            if let Err(e) = client.apply_update(&cache, update).await {
+                                                warn!(error = %e, ?update, "增量刷新缓存失败");
+                                            }
+                                        }
+                                    }
+                                    Err(e) => warn!(error = %e, "读取区块事件失败"),
+                                }
+                                // 每 100 个区块全量刷新一次缓存
+                                if block_count % 100 == 0 {
+                                    refresh_cache_from_chain(&client, &cache).await;
+                                }
+                            }
+                            Err(e) => {
+                                warn!(error = %e, "区块订阅错误");
+                                tokio::time::sleep(std::time::Duration::from_secs(5)).await;
                             }
-                        }
-                        Err(e) => {
-                            warn!(error = %e, "区块订阅错误");
-                            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
                         }
                     }
+                    warn!("区块订阅已断开，5 秒后重连");
                 }
+                Err(e) => warn!(error = %e, "区块订阅失败，5 秒后重试"),
             }
-            Err(e) => warn!(error = %e, "区块订阅失败"),
+            tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+            // 重连期间可能漏掉事件，先全量刷新
+            refresh_cache_from_chain(&client, &cache).await;
         }
     });
 }
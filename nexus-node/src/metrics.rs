@@ -0,0 +1,216 @@
+use std::sync::OnceLock;
+
+use prometheus::{
+    Encoder, Histogram, HistogramOpts, IntCounter, IntCounterVec, IntGauge, IntGaugeVec, Opts,
+    Registry, TextEncoder,
+};
+
+use crate::types::GossipType;
+
+/// 节点 Prometheus 指标（GET /metrics）
+///
+/// 全局单例，各组件直接通过 `metrics()` 打点；
+/// 队列长度 / 活跃消息数等瞬时值在抓取时由 API 层刷新
+pub struct Metrics {
+    registry: Registry,
+
+    // ── GossipEngine ──
+    /// Agent 消息接收数
+    pub agent_messages: IntCounter,
+    /// 收到的 Gossip 消息数 {msg_type}
+    pub gossip_received: IntCounterVec,
+    /// 发出的 Gossip 消息数 {msg_type}
+    pub gossip_sent: IntCounterVec,
+    /// Pull 请求数 {direction = sent | served}
+    pub pulls: IntCounterVec,
+    /// Equivocation 数 {source = detected | proven | alert}
+    pub equivocations: IntCounterVec,
+    /// 共识达成数
+    pub consensus_reached: IntCounter,
+    /// 共识延迟（消息进入状态机 → Confirmed）
+    pub consensus_latency: Histogram,
+    /// 活跃消息数
+    pub active_messages: IntGauge,
+
+    // ── Gossip 网络层 ──
+    /// 已认证对等连接数
+    pub gossip_peers: IntGauge,
+    /// 握手认证失败数
+    pub gossip_auth_failures: IntCounter,
+    /// 验签失败被丢弃的消息数
+    pub gossip_rejected: IntCounter,
+
+    // ── LeaderExecutor / FailoverManager ──
+    /// Leader 执行数 {result = success | failure | no_action}
+    pub leader_executions: IntCounterVec,
+    /// Leader 执行耗时（POST Agent /v1/execute）
+    pub leader_execute_duration: Histogram,
+    /// Leader 接管数 {source = local | remote}
+    pub leader_takeovers: IntCounterVec,
+
+    // ── RuleEngine ──
+    /// 规则命中数 {rule = Rule::name() | custom}
+    pub rule_hits: IntCounterVec,
+
+    // ── ChainSubmitter ──
+    /// 已上链条目数 {queue}
+    pub chain_submitted: IntCounterVec,
+    /// 被 runtime 拒绝丢弃的条目数
+    pub chain_dropped: IntCounter,
+    /// 可重试提交失败次数
+    pub chain_submit_failures: IntCounter,
+    /// 待提交队列长度 {queue = confirmation | action_log | equivocation}
+    pub chain_queue_size: IntGaugeVec,
+}
+
+/// 全局指标
+pub fn metrics() -> &'static Metrics {
+    static METRICS: OnceLock<Metrics> = OnceLock::new();
+    METRICS.get_or_init(Metrics::new)
+}
+
+/// 延迟分桶（秒）
+const LATENCY_BUCKETS: &[f64] = &[0.01, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0];
+
+impl Metrics {
+    fn new() -> Self {
+        let registry = Registry::new_custom(Some("nexus".into()), None)
+            .expect("有效的指标前缀");
+
+        let metrics = Self {
+            agent_messages: IntCounter::new(
+                "agent_messages_total", "Agent 消息接收数",
+            ).unwrap(),
+            gossip_received: IntCounterVec::new(
+                Opts::new("gossip_received_total", "收到的 Gossip 消息数"),
+                &["msg_type"],
+            ).unwrap(),
+            gossip_sent: IntCounterVec::new(
+                Opts::new("gossip_sent_total", "发出的 Gossip 消息数"),
+                &["msg_type"],
+            ).unwrap(),
+            pulls: IntCounterVec::new(
+                Opts::new("gossip_pulls_total", "Pull 请求数"),
+                &["direction"],
+            ).unwrap(),
+            equivocations: IntCounterVec::new(
+                Opts::new("equivocations_total", "Equivocation 数"),
+                &["source"],
+            ).unwrap(),
+            consensus_reached: IntCounter::new(
+                "consensus_reached_total", "M/K 共识达成数",
+            ).unwrap(),
+            consensus_latency: Histogram::with_opts(
+                HistogramOpts::new("consensus_latency_seconds", "消息进入状态机到共识达成的延迟")
+                    .buckets(LATENCY_BUCKETS.to_vec()),
+            ).unwrap(),
+            active_messages: IntGauge::new(
+                "active_messages", "活跃消息数",
+            ).unwrap(),
+            gossip_peers: IntGauge::new(
+                "gossip_peers", "已认证 Gossip 对等连接数",
+            ).unwrap(),
+            gossip_auth_failures: IntCounter::new(
+                "gossip_auth_failures_total", "Gossip 握手认证失败数",
+            ).unwrap(),
+            gossip_rejected: IntCounter::new(
+                "gossip_rejected_total", "验签失败被丢弃的 Gossip 消息数",
+            ).unwrap(),
+            leader_executions: IntCounterVec::new(
+                Opts::new("leader_executions_total", "Leader 执行数"),
+                &["result"],
+            ).unwrap(),
+            leader_execute_duration: Histogram::with_opts(
+                HistogramOpts::new("leader_execute_duration_seconds", "Leader 执行耗时")
+                    .buckets(LATENCY_BUCKETS.to_vec()),
+            ).unwrap(),
+            leader_takeovers: IntCounterVec::new(
+                Opts::new("leader_takeovers_total", "Leader 接管数"),
+                &["source"],
+            ).unwrap(),
+            rule_hits: IntCounterVec::new(
+                Opts::new("rule_hits_total", "规则命中数"),
+                &["rule"],
+            ).unwrap(),
+            chain_submitted: IntCounterVec::new(
+                Opts::new("chain_submitted_total", "已上链条目数"),
+                &["queue"],
+            ).unwrap(),
+            chain_dropped: IntCounter::new(
+                "chain_dropped_total", "被 runtime 拒绝丢弃的条目数",
+            ).unwrap(),
+            chain_submit_failures: IntCounter::new(
+                "chain_submit_failures_total", "可重试链上提交失败次数",
+            ).unwrap(),
+            chain_queue_size: IntGaugeVec::new(
+                Opts::new("chain_queue_size", "待提交队列长度"),
+                &["queue"],
+            ).unwrap(),
+            registry,
+        };
+
+        let r = &metrics.registry;
+        r.register(Box::new(metrics.agent_messages.clone())).unwrap();
+        r.register(Box::new(metrics.gossip_received.clone())).unwrap();
+        r.register(Box::new(metrics.gossip_sent.clone())).unwrap();
+        r.register(Box::new(metrics.pulls.clone())).unwrap();
+        r.register(Box::new(metrics.equivocations.clone())).unwrap();
+        r.register(Box::new(metrics.consensus_reached.clone())).unwrap();
+        r.register(Box::new(metrics.consensus_latency.clone())).unwrap();
+        r.register(Box::new(metrics.active_messages.clone())).unwrap();
+        r.register(Box::new(metrics.gossip_peers.clone())).unwrap();
+        r.register(Box::new(metrics.gossip_auth_failures.clone())).unwrap();
+        r.register(Box::new(metrics.gossip_rejected.clone())).unwrap();
+        r.register(Box::new(metrics.leader_executions.clone())).unwrap();
+        r.register(Box::new(metrics.leader_execute_duration.clone())).unwrap();
+        r.register(Box::new(metrics.leader_takeovers.clone())).unwrap();
+        r.register(Box::new(metrics.rule_hits.clone())).unwrap();
+        r.register(Box::new(metrics.chain_submitted.clone())).unwrap();
+        r.register(Box::new(metrics.chain_dropped.clone())).unwrap();
+        r.register(Box::new(metrics.chain_submit_failures.clone())).unwrap();
+        r.register(Box::new(metrics.chain_queue_size.clone())).unwrap();
+
+        metrics
+    }
+
+    /// 按消息类型计数收到的 Gossip 消息
+    pub fn on_gossip_received(&self, msg_type: GossipType) {
+        self.gossip_received.with_label_values(&[&format!("{:?}", msg_type)]).inc();
+    }
+
+    /// 按消息类型计数发出的 Gossip 消息
+    pub fn on_gossip_sent(&self, msg_type: GossipType) {
+        self.gossip_sent.with_label_values(&[&format!("{:?}", msg_type)]).inc();
+    }
+
+    /// Prometheus 文本格式输出
+    pub fn render(&self) -> String {
+        let mut buf = Vec::new();
+        let encoder = TextEncoder::new();
+        if let Err(e) = encoder.encode(&self.registry.gather(), &mut buf) {
+            tracing::warn!(error = %e, "指标编码失败");
+        }
+        String::from_utf8(buf).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_contains_registered_metrics() {
+        let m = metrics();
+        m.on_gossip_received(GossipType::MessageSeen);
+        m.rule_hits.with_label_values(&["blacklist"]).inc();
+        m.chain_queue_size.with_label_values(&["confirmation"]).set(3);
+        m.consensus_latency.observe(0.2);
+
+        let text = m.render();
+        assert!(text.contains("nexus_gossip_received_total{msg_type=\"MessageSeen\"}"));
+        assert!(text.contains("nexus_rule_hits_total{rule=\"blacklist\"}"));
+        assert!(text.contains("nexus_chain_queue_size{queue=\"confirmation\"} 3"));
+        assert!(text.contains("nexus_consensus_latency_seconds_bucket"));
+        assert!(text.contains("nexus_agent_messages_total"));
+    }
+}
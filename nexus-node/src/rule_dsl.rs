@@ -0,0 +1,496 @@
+use std::collections::HashSet;
+
+use crate::rule_engine::{is_emoji, RuleAction, RuleContext};
+use crate::types::{
+    ActionType, AdminAction, CompareOp, CounterKind, CustomRule, CustomRuleAction, LockType,
+    MessageAction, MessageKind, RuleCondition, SenderRole,
+};
+
+/// 单个群最多自定义规则数
+pub const MAX_CUSTOM_RULES: usize = 32;
+/// 条件最大嵌套深度
+pub const MAX_CONDITION_DEPTH: usize = 6;
+/// 正则表达式最大长度（字符）
+pub const MAX_REGEX_LEN: usize = 256;
+/// 正则编译后的最大体积（字节，防止病态表达式拖慢所有节点）
+const REGEX_SIZE_LIMIT: usize = 1 << 16;
+
+/// 编译后的自定义规则集
+///
+/// 编译是确定性的：同一份 GroupConfig 在任何节点上得到相同的规则顺序
+/// （priority 降序，相同时按 id 升序），与 JSON 中的书写顺序无关。
+#[derive(Debug, Default)]
+pub struct CompiledRuleSet {
+    rules: Vec<CompiledRule>,
+}
+
+/// 编译后的单条规则
+#[derive(Debug)]
+pub struct CompiledRule {
+    pub id: String,
+    pub priority: i32,
+    condition: CompiledCondition,
+    action: CustomRuleAction,
+}
+
+/// 编译后的条件（正则已预编译）
+#[derive(Debug)]
+enum CompiledCondition {
+    All(Vec<CompiledCondition>),
+    Any(Vec<CompiledCondition>),
+    Not(Box<CompiledCondition>),
+    TextRegex(regex::Regex),
+    SenderRole(Vec<SenderRole>),
+    MessageType(Vec<MessageKind>),
+    TimeWindow { start_hour: u8, end_hour: u8 },
+    Counter { counter: CounterKind, op: CompareOp, value: u32 },
+}
+
+/// 编译自定义规则（同时完成校验）
+///
+/// 校验失败返回第一处错误，调用方应拒绝整份配置。
+/// 已禁用的规则同样校验，但不进入规则集。
+pub fn compile(rules: &[CustomRule]) -> Result<CompiledRuleSet, String> {
+    if rules.len() > MAX_CUSTOM_RULES {
+        return Err(format!("Too many custom rules: {} > {}", rules.len(), MAX_CUSTOM_RULES));
+    }
+
+    let mut seen = HashSet::new();
+    let mut compiled = Vec::with_capacity(rules.len());
+    for rule in rules {
+        if rule.id.is_empty() || rule.id.len() > 64 {
+            return Err("Custom rule id must be 1-64 bytes".into());
+        }
+        if !seen.insert(rule.id.as_str()) {
+            return Err(format!("Duplicate custom rule id: {}", rule.id));
+        }
+        let condition = compile_condition(&rule.when, 1)
+            .map_err(|e| format!("Rule {}: {}", rule.id, e))?;
+        if matches!(rule.then.action_type, ActionType::ConfigUpdate(_)) {
+            return Err(format!("Rule {}: ConfigUpdate actions are not allowed", rule.id));
+        }
+        if !rule.then.params.is_null() && !rule.then.params.is_object() {
+            return Err(format!("Rule {}: params must be an object", rule.id));
+        }
+        if rule.enabled {
+            compiled.push(CompiledRule {
+                id: rule.id.clone(),
+                priority: rule.priority,
+                condition,
+                action: rule.then.clone(),
+            });
+        }
+    }
+
+    compiled.sort_by(|a, b| b.priority.cmp(&a.priority).then_with(|| a.id.cmp(&b.id)));
+    Ok(CompiledRuleSet { rules: compiled })
+}
+
+fn compile_condition(cond: &RuleCondition, depth: usize) -> Result<CompiledCondition, String> {
+    if depth > MAX_CONDITION_DEPTH {
+        return Err(format!("Condition nesting exceeds {}", MAX_CONDITION_DEPTH));
+    }
+
+    Ok(match cond {
+        RuleCondition::All(list) | RuleCondition::Any(list) => {
+            if list.is_empty() {
+                return Err("All/Any requires at least one condition".into());
+            }
+            let inner = list.iter()
+                .map(|c| compile_condition(c, depth + 1))
+                .collect::<Result<Vec<_>, _>>()?;
+            if matches!(cond, RuleCondition::All(_)) {
+                CompiledCondition::All(inner)
+            } else {
+                CompiledCondition::Any(inner)
+            }
+        }
+        RuleCondition::Not(inner) => {
+            CompiledCondition::Not(Box::new(compile_condition(inner, depth + 1)?))
+        }
+        RuleCondition::TextRegex(pattern) => {
+            if pattern.chars().count() > MAX_REGEX_LEN {
+                return Err(format!("Regex longer than {} chars", MAX_REGEX_LEN));
+            }
+            let re = regex::RegexBuilder::new(pattern)
+                .size_limit(REGEX_SIZE_LIMIT)
+                .build()
+                .map_err(|e| format!("Invalid regex: {}", e))?;
+            CompiledCondition::TextRegex(re)
+        }
+        RuleCondition::SenderRole(roles) => {
+            if roles.is_empty() {
+                return Err("SenderRole requires at least one role".into());
+            }
+            CompiledCondition::SenderRole(roles.clone())
+        }
+        RuleCondition::MessageType(kinds) => {
+            if kinds.is_empty() {
+                return Err("MessageType requires at least one kind".into());
+            }
+            CompiledCondition::MessageType(kinds.clone())
+        }
+        RuleCondition::TimeWindow { start_hour, end_hour } => {
+            if *start_hour > 23 || *end_hour > 23 || start_hour == end_hour {
+                return Err("TimeWindow hours must be 0-23 and differ".into());
+            }
+            CompiledCondition::TimeWindow { start_hour: *start_hour, end_hour: *end_hour }
+        }
+        RuleCondition::Counter { counter, op, value } => {
+            CompiledCondition::Counter { counter: *counter, op: *op, value: *value }
+        }
+    })
+}
+
+impl CompiledRuleSet {
+    /// 按评估顺序排列的规则
+    pub fn rules(&self) -> &[CompiledRule] {
+        &self.rules
+    }
+}
+
+impl CompiledRule {
+    /// 评估规则: 条件命中时生成动作
+    pub fn evaluate(&self, ctx: &RuleContext) -> Option<RuleAction> {
+        if !self.condition.matches(ctx) {
+            return None;
+        }
+
+        Some(RuleAction {
+            action_type: self.action.action_type.clone(),
+            chat_id: ctx.chat_id,
+            params: build_params(&self.action, ctx),
+            reason: format!("custom_rule_{}", self.id),
+        })
+    }
+}
+
+impl CompiledCondition {
+    fn matches(&self, ctx: &RuleContext) -> bool {
+        match self {
+            Self::All(list) => list.iter().all(|c| c.matches(ctx)),
+            Self::Any(list) => list.iter().any(|c| c.matches(ctx)),
+            Self::Not(inner) => !inner.matches(ctx),
+            Self::TextRegex(re) => !ctx.text.is_empty() && re.is_match(&ctx.text),
+            Self::SenderRole(roles) => roles.contains(&sender_role(ctx)),
+            Self::MessageType(kinds) => message_kinds(ctx).iter().any(|k| kinds.contains(k)),
+            Self::TimeWindow { start_hour, end_hour } => {
+                // 使用 Agent 签名的消息时间戳，而非节点本地时钟
+                let hour = ((ctx.message.timestamp / 3600) % 24) as u8;
+                if start_hour < end_hour {
+                    hour >= *start_hour && hour < *end_hour
+                } else {
+                    hour >= *start_hour || hour < *end_hour
+                }
+            }
+            Self::Counter { counter, op, value } => {
+                let actual = count(*counter, &ctx.text);
+                match op {
+                    CompareOp::Gt => actual > *value,
+                    CompareOp::Gte => actual >= *value,
+                    CompareOp::Lt => actual < *value,
+                    CompareOp::Lte => actual <= *value,
+                    CompareOp::Eq => actual == *value,
+                }
+            }
+        }
+    }
+}
+
+/// 发送者角色（管理员优先于白名单）
+fn sender_role(ctx: &RuleContext) -> SenderRole {
+    let sender = ctx.sender_id.to_string();
+    match ctx.group_config.as_ref() {
+        Some(c) if c.admins.contains(&sender) => SenderRole::Admin,
+        Some(c) if c.whitelist.contains(&sender) => SenderRole::Whitelisted,
+        _ => SenderRole::Member,
+    }
+}
+
+/// 消息所属的全部类型（例如带文字说明的图片同时是 Photo 和 Text）
+fn message_kinds(ctx: &RuleContext) -> Vec<MessageKind> {
+    let mut kinds = Vec::new();
+    if ctx.is_join_request {
+        kinds.push(MessageKind::JoinRequest);
+    }
+    if ctx.is_callback {
+        kinds.push(MessageKind::Callback);
+    }
+    if ctx.is_command {
+        kinds.push(MessageKind::Command);
+    } else if !ctx.text.is_empty() {
+        kinds.push(MessageKind::Text);
+    }
+    if let Some(lock_type) = ctx.message.platform_event.get("message")
+        .and_then(crate::rule_engine::detect_lock_type)
+    {
+        let kind = match lock_type {
+            LockType::Audio => MessageKind::Audio,
+            LockType::Video => MessageKind::Video,
+            LockType::Photo => MessageKind::Photo,
+            LockType::Document => MessageKind::Document,
+            LockType::Sticker => MessageKind::Sticker,
+            LockType::Gif => MessageKind::Gif,
+            LockType::Forward => MessageKind::Forward,
+            LockType::Voice => MessageKind::Voice,
+            LockType::Contact => MessageKind::Contact,
+            LockType::Location => MessageKind::Location,
+            LockType::Poll => MessageKind::Poll,
+            LockType::Game => MessageKind::Game,
+            LockType::Inline => MessageKind::Inline,
+            LockType::Url => return kinds,
+        };
+        kinds.push(kind);
+    }
+    kinds
+}
+
+/// 计算文本计数项
+fn count(counter: CounterKind, text: &str) -> u32 {
+    let n = match counter {
+        CounterKind::TextLength => text.chars().count(),
+        CounterKind::LineCount => if text.is_empty() { 0 } else { text.lines().count() },
+        CounterKind::LinkCount => ["http://", "https://", "t.me/"].iter()
+            .map(|p| text.matches(p).count())
+            .sum(),
+        CounterKind::MentionCount => text.split_whitespace()
+            .filter(|w| w.len() > 1 && w.starts_with('@'))
+            .count(),
+        CounterKind::EmojiCount => text.chars().filter(|c| is_emoji(*c)).count(),
+    };
+    n.min(u32::MAX as usize) as u32
+}
+
+/// 生成动作参数
+///
+/// 1. 替换字符串占位符（整值匹配，替换为数字）
+/// 2. 补齐常用目标参数: Admin 动作默认作用于发送者，删除默认删除当前消息
+fn build_params(action: &CustomRuleAction, ctx: &RuleContext) -> serde_json::Value {
+    let message_id = ctx.message.platform_event
+        .pointer("/message/message_id")
+        .and_then(|v| v.as_i64());
+
+    let placeholder = |s: &str| -> Option<serde_json::Value> {
+        match s {
+            "{sender_id}" => Some(ctx.sender_id.into()),
+            "{chat_id}" => Some(ctx.chat_id.into()),
+            "{message_id}" => message_id.map(Into::into),
+            "{reply_to_user_id}" => ctx.reply_to_user_id.map(Into::into),
+            "{reply_to_message_id}" => ctx.reply_to_message_id.map(Into::into),
+            _ => None,
+        }
+    };
+
+    let mut params: serde_json::Map<String, serde_json::Value> = match &action.params {
+        serde_json::Value::Object(map) => map.iter()
+            .map(|(k, v)| {
+                let v = v.as_str().and_then(&placeholder).unwrap_or_else(|| v.clone());
+                (k.clone(), v)
+            })
+            .collect(),
+        _ => serde_json::Map::new(),
+    };
+
+    match &action.action_type {
+        ActionType::Admin(AdminAction::Ban | AdminAction::Unban | AdminAction::Mute
+            | AdminAction::Unmute | AdminAction::Kick) => {
+            params.entry("user_id").or_insert_with(|| ctx.sender_id.into());
+        }
+        ActionType::Message(MessageAction::Delete | MessageAction::Pin) => {
+            if let Some(id) = message_id {
+                params.entry("message_id").or_insert_with(|| id.into());
+            }
+        }
+        _ => {}
+    }
+
+    serde_json::Value::Object(params)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::SignedMessage;
+
+    fn make_rule(id: &str, priority: i32, when: RuleCondition, action_type: ActionType) -> CustomRule {
+        CustomRule {
+            id: id.into(),
+            priority,
+            enabled: true,
+            when,
+            then: CustomRuleAction { action_type, params: serde_json::Value::Null },
+        }
+    }
+
+    fn make_ctx(text: &str, timestamp: u64) -> RuleContext {
+        RuleContext {
+            message: SignedMessage {
+                owner_public_key: String::new(),
+                bot_id_hash: String::new(),
+                sequence: 1,
+                timestamp,
+                message_hash: String::new(),
+                platform_event: serde_json::json!({
+                    "message": { "message_id": 42, "text": text }
+                }),
+                owner_signature: String::new(),
+                platform: "telegram".into(),
+            },
+            text: text.into(),
+            chat_id: -100,
+            sender_id: 7,
+            is_command: text.starts_with('/'),
+            command: None,
+            command_args: None,
+            reply_to_user_id: None,
+            reply_to_message_id: None,
+            is_join_request: false,
+            join_request_user_id: None,
+            is_callback: false,
+            callback_data: None,
+            group_config: None,
+        }
+    }
+
+    #[test]
+    fn test_compile_orders_by_priority_then_id() {
+        let rules = vec![
+            make_rule("b", 10, RuleCondition::TextRegex("x".into()), ActionType::NoAction),
+            make_rule("c", 50, RuleCondition::TextRegex("x".into()), ActionType::NoAction),
+            make_rule("a", 10, RuleCondition::TextRegex("x".into()), ActionType::NoAction),
+        ];
+        let set = compile(&rules).unwrap();
+        let ids: Vec<_> = set.rules().iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec!["c", "a", "b"]);
+
+        let mut reversed = rules.clone();
+        reversed.reverse();
+        let ids2: Vec<_> = compile(&reversed).unwrap().rules().iter().map(|r| r.id.clone()).collect();
+        assert_eq!(ids, ids2);
+    }
+
+    #[test]
+    fn test_compile_rejects_invalid() {
+        let bad_regex = make_rule("r", 1, RuleCondition::TextRegex("(".into()), ActionType::NoAction);
+        assert!(compile(&[bad_regex]).is_err());
+
+        let dup = make_rule("d", 1, RuleCondition::SenderRole(vec![SenderRole::Member]), ActionType::NoAction);
+        assert!(compile(&[dup.clone(), dup]).is_err());
+
+        let config_update = make_rule(
+            "u", 1,
+            RuleCondition::SenderRole(vec![SenderRole::Member]),
+            ActionType::ConfigUpdate(crate::types::ConfigUpdateAction::SetFloodLimit),
+        );
+        assert!(compile(&[config_update]).is_err());
+
+        let mut deep = RuleCondition::SenderRole(vec![SenderRole::Member]);
+        for _ in 0..MAX_CONDITION_DEPTH {
+            deep = RuleCondition::Not(Box::new(deep));
+        }
+        assert!(compile(&[make_rule("n", 1, deep, ActionType::NoAction)]).is_err());
+
+        let window = RuleCondition::TimeWindow { start_hour: 3, end_hour: 3 };
+        assert!(compile(&[make_rule("w", 1, window, ActionType::NoAction)]).is_err());
+    }
+
+    #[test]
+    fn test_disabled_rule_not_compiled() {
+        let mut rule = make_rule("off", 1, RuleCondition::TextRegex("x".into()), ActionType::NoAction);
+        rule.enabled = false;
+        assert!(compile(&[rule]).unwrap().rules().is_empty());
+    }
+
+    #[test]
+    fn test_regex_and_counter_conditions() {
+        let cond = RuleCondition::All(vec![
+            RuleCondition::TextRegex("(?i)free\\s+airdrop".into()),
+            RuleCondition::Counter { counter: CounterKind::LinkCount, op: CompareOp::Gte, value: 1 },
+        ]);
+        let set = compile(&[make_rule(
+            "airdrop", 1, cond, ActionType::Admin(AdminAction::Ban),
+        )]).unwrap();
+        let rule = &set.rules()[0];
+
+        let hit = rule.evaluate(&make_ctx("FREE airdrop https://x.io", 0)).unwrap();
+        assert_eq!(hit.action_type, ActionType::Admin(AdminAction::Ban));
+        assert_eq!(hit.params["user_id"], 7);
+        assert_eq!(hit.reason, "custom_rule_airdrop");
+
+        assert!(rule.evaluate(&make_ctx("free airdrop", 0)).is_none());
+    }
+
+    #[test]
+    fn test_time_window_wraps_midnight() {
+        let cond = RuleCondition::TimeWindow { start_hour: 22, end_hour: 6 };
+        let set = compile(&[make_rule("night", 1, cond, ActionType::NoAction)]).unwrap();
+        let rule = &set.rules()[0];
+
+        assert!(rule.evaluate(&make_ctx("hi", 23 * 3600)).is_some());
+        assert!(rule.evaluate(&make_ctx("hi", 86_400 + 2 * 3600)).is_some());
+        assert!(rule.evaluate(&make_ctx("hi", 12 * 3600)).is_none());
+    }
+
+    #[test]
+    fn test_sender_role_and_message_type() {
+        let cond = RuleCondition::All(vec![
+            RuleCondition::SenderRole(vec![SenderRole::Member]),
+            RuleCondition::MessageType(vec![MessageKind::Photo]),
+        ]);
+        let set = compile(&[make_rule(
+            "no_photos", 1, cond, ActionType::Message(MessageAction::Delete),
+        )]).unwrap();
+        let rule = &set.rules()[0];
+
+        let mut ctx = make_ctx("", 0);
+        ctx.message.platform_event = serde_json::json!({
+            "message": { "message_id": 5, "photo": [{"file_id": "f"}] }
+        });
+        let hit = rule.evaluate(&ctx).unwrap();
+        assert_eq!(hit.params["message_id"], 5);
+
+        let mut config: crate::types::GroupConfig = serde_json::from_value(serde_json::json!({
+            "version": 1, "bot_id_hash": "b", "join_policy": "AutoApprove",
+            "filter_links": false, "restrict_mentions": false, "rate_limit_per_minute": 0,
+            "auto_mute_duration": 0, "new_member_restrict_duration": 0, "welcome_message": "",
+            "whitelist": [], "admins": [], "quiet_hours_start": null, "quiet_hours_end": null,
+            "updated_at": 0
+        })).unwrap();
+        config.admins.push("7".into());
+        ctx.group_config = Some(config);
+        assert!(rule.evaluate(&ctx).is_none());
+    }
+
+    #[test]
+    fn test_params_placeholders() {
+        let mut rule = make_rule(
+            "reply", 1,
+            RuleCondition::TextRegex("^!rules$".into()),
+            ActionType::Message(MessageAction::Send),
+        );
+        rule.then.params = serde_json::json!({
+            "text": "See pinned message",
+            "reply_to_message_id": "{message_id}",
+            "target": "{sender_id}",
+        });
+        let set = compile(&[rule]).unwrap();
+        let action = set.rules()[0].evaluate(&make_ctx("!rules", 0)).unwrap();
+        assert_eq!(action.params["text"], "See pinned message");
+        assert_eq!(action.params["reply_to_message_id"], 42);
+        assert_eq!(action.params["target"], 7);
+    }
+
+    #[test]
+    fn test_rule_json_roundtrip() {
+        let json = serde_json::json!({
+            "id": "caps",
+            "when": { "Counter": { "counter": "TextLength", "op": "Gt", "value": 500 } },
+            "then": { "action_type": { "Message": "Delete" } }
+        });
+        let rule: CustomRule = serde_json::from_value(json).unwrap();
+        assert_eq!(rule.priority, 100);
+        assert!(rule.enabled);
+        let back: CustomRule = serde_json::from_str(&serde_json::to_string(&rule).unwrap()).unwrap();
+        assert_eq!(rule, back);
+    }
+}
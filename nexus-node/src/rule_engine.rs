@@ -1,16 +1,24 @@
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
 use serde::{Deserialize, Serialize};
-use tracing::debug;
+use tracing::{debug, warn};
 
 use crate::types::{ActionType, MessageAction, AdminAction, QueryAction, ConfigUpdateAction, SignedMessage, GroupConfig, JoinApprovalPolicy};
 use crate::chain_cache::ChainCache;
+use crate::rule_dsl::{self, CompiledRuleSet};
 
 /// 规则引擎
 ///
 /// 根据链上群规则 + 消息内容 → 决定执行什么动作
 /// 支持可扩展的规则链: 反垃圾 → 命令解析 → 入群审批 → 默认
+///
+/// 群主在 GroupConfig.custom_rules 中声明的自定义规则与内置规则
+/// 按优先级合并评估（优先级相同时内置规则先评估）。
 pub struct RuleEngine {
-    /// 规则列表（按优先级排序）
-    rules: Vec<Box<dyn Rule + Send + Sync>>,
+    /// 内置规则列表（优先级, 规则），按优先级降序
+    rules: Vec<(i32, Box<dyn Rule + Send + Sync>)>,
+    /// 自定义规则编译缓存: bot_id_hash → (配置版本, 规则集)
+    compiled: RwLock<HashMap<String, (u64, Arc<CompiledRuleSet>)>>,
 }
 
 /// 规则 trait
@@ -64,48 +72,164 @@ pub struct RuleAction {
     pub reason: String,
 }
 
+/// 试运行中单条规则的评估记录
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleTrace {
+    /// 规则名（自定义规则为 custom:<id>）
+    pub rule: String,
+    pub priority: i32,
+    pub matched: bool,
+}
+
+/// 试运行报告（不执行任何动作）
+#[derive(Debug, Clone, Serialize)]
+pub struct DryRunReport {
+    pub action: RuleAction,
+    /// 命中的规则名（无命中时为 None）
+    pub matched_rule: Option<String>,
+    /// 按评估顺序的规则记录（止于命中规则）
+    pub trace: Vec<RuleTrace>,
+}
+
 impl RuleEngine {
     /// 创建默认规则引擎（内置规则链）
+    ///
+    /// 内置优先级: join_request 1000 / admin_permission 900 / command 800 /
+    /// antiflood 700 / spam_detector 600 / blacklist 500 / lock 400 /
+    /// welcome 300 / link_filter 200 / default 0。
+    /// default 总会命中，因此优先级 ≤ 0 的自定义规则不会生效。
     pub fn default_engine() -> Self {
-        let rules: Vec<Box<dyn Rule + Send + Sync>> = vec![
-            Box::new(JoinRequestRule),
-            Box::new(AdminPermissionRule),
-            Box::new(CommandRule),
-            Box::new(AntifloodRule),
-            Box::new(SpamDetectorRule),
-            Box::new(BlacklistRule),
-            Box::new(LockRule),
-            Box::new(WelcomeRule),
-            Box::new(LinkFilterRule),
-            Box::new(DefaultRule),
+        let rules: Vec<(i32, Box<dyn Rule + Send + Sync>)> = vec![
+            (1000, Box::new(JoinRequestRule)),
+            (900, Box::new(AdminPermissionRule)),
+            (800, Box::new(CommandRule)),
+            (700, Box::new(AntifloodRule)),
+            (600, Box::new(SpamDetectorRule)),
+            (500, Box::new(BlacklistRule)),
+            (400, Box::new(LockRule)),
+            (300, Box::new(WelcomeRule)),
+            (200, Box::new(LinkFilterRule)),
+            (0, Box::new(DefaultRule)),
         ];
 
-        Self { rules }
+        Self { rules, compiled: RwLock::new(HashMap::new()) }
     }
 
     /// 评估消息 → 返回动作
     pub fn evaluate(&self, message: &SignedMessage, chain_cache: Option<&ChainCache>) -> RuleAction {
         let ctx = Self::build_context(message, chain_cache);
+        let custom = self.compiled_rules(&ctx);
+        self.run(&ctx, custom.as_deref(), None).0
+    }
+
+    /// 试运行: 返回命中规则与评估轨迹
+    ///
+    /// `config_override` 用于在签名发布前测试新规则；为 None 时使用 ChainCache 中的配置。
+    pub fn dry_run(
+        &self,
+        message: &SignedMessage,
+        config_override: Option<GroupConfig>,
+        chain_cache: Option<&ChainCache>,
+    ) -> Result<DryRunReport, String> {
+        let mut ctx = Self::build_context(message, chain_cache);
+        let custom = match config_override {
+            Some(config) => {
+                let set = rule_dsl::compile(&config.custom_rules)?;
+                ctx.group_config = Some(config);
+                Some(Arc::new(set))
+            }
+            None => self.compiled_rules(&ctx),
+        };
 
-        for rule in &self.rules {
-            if let Some(action) = rule.evaluate(&ctx) {
+        let mut trace = Vec::new();
+        let (action, matched_rule) = self.run(&ctx, custom.as_deref(), Some(&mut trace));
+        Ok(DryRunReport { action, matched_rule, trace })
+    }
+
+    /// 按优先级合并内置规则与自定义规则，返回第一条命中的动作
+    fn run(
+        &self,
+        ctx: &RuleContext,
+        custom: Option<&CompiledRuleSet>,
+        mut trace: Option<&mut Vec<RuleTrace>>,
+    ) -> (RuleAction, Option<String>) {
+        let custom_rules = custom.map(|c| c.rules()).unwrap_or(&[]);
+        let (mut b, mut c) = (0, 0);
+
+        while b < self.rules.len() || c < custom_rules.len() {
+            // 相同优先级时内置规则先评估
+            let take_custom = match (self.rules.get(b), custom_rules.get(c)) {
+                (Some((bp, _)), Some(cr)) => cr.priority > *bp,
+                (None, Some(_)) => true,
+                _ => false,
+            };
+
+            let (name, priority, result) = if take_custom {
+                let rule = &custom_rules[c];
+                c += 1;
+                (format!("custom:{}", rule.id), rule.priority, rule.evaluate(ctx))
+            } else {
+                let (priority, rule) = &self.rules[b];
+                b += 1;
+                (rule.name().to_string(), *priority, rule.evaluate(ctx))
+            };
+
+            if let Some(trace) = trace.as_deref_mut() {
+                trace.push(RuleTrace { rule: name.clone(), priority, matched: result.is_some() });
+            }
+
+            if let Some(action) = result {
+                // 试运行不计入命中统计；自定义规则统一归为 custom，避免标签基数失控
+                if trace.is_none() {
+                    let label = if take_custom { "custom" } else { name.as_str() };
+                    crate::metrics::metrics().rule_hits.with_label_values(&[label]).inc();
+                }
                 debug!(
-                    rule = rule.name(),
+                    rule = %name,
                     action_type = ?action.action_type,
                     reason = action.reason,
                     "规则匹配"
                 );
-                return action;
+                return (action, Some(name));
             }
         }
 
         // 兜底: 无动作
-        RuleAction {
+        (RuleAction {
             action_type: ActionType::NoAction,
             chat_id: ctx.chat_id,
             params: serde_json::json!({}),
             reason: "no_rule_matched".into(),
+        }, None)
+    }
+
+    /// 获取当前群配置的已编译自定义规则（按 bot + 配置版本缓存）
+    ///
+    /// 配置在 ChainCache 入库时已校验；此处编译失败（如本地旧文件）则忽略自定义规则，
+    /// 所有节点对同一份配置行为一致。
+    fn compiled_rules(&self, ctx: &RuleContext) -> Option<Arc<CompiledRuleSet>> {
+        let config = ctx.group_config.as_ref()?;
+        if config.custom_rules.is_empty() {
+            return None;
         }
+
+        if let Some((version, set)) = self.compiled.read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&config.bot_id_hash)
+        {
+            if *version == config.version {
+                return Some(set.clone());
+            }
+        }
+
+        let set = Arc::new(rule_dsl::compile(&config.custom_rules).unwrap_or_else(|e| {
+            warn!(bot = config.bot_id_hash, error = %e, "自定义规则编译失败，已忽略");
+            CompiledRuleSet::default()
+        }));
+        self.compiled.write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(config.bot_id_hash.clone(), (config.version, set.clone()));
+        Some(set)
     }
 
     /// 从 ChainCache 查找 GroupConfig
@@ -1134,38 +1258,7 @@ impl Rule for LockRule {
         let update = &ctx.message.platform_event;
         let msg = update.get("message")?;
 
-        // 检测消息类型
-        let detected_type = if msg.get("photo").is_some() {
-            Some(crate::types::LockType::Photo)
-        } else if msg.get("video").is_some() {
-            Some(crate::types::LockType::Video)
-        } else if msg.get("audio").is_some() {
-            Some(crate::types::LockType::Audio)
-        } else if msg.get("document").is_some() {
-            Some(crate::types::LockType::Document)
-        } else if msg.get("sticker").is_some() {
-            Some(crate::types::LockType::Sticker)
-        } else if msg.get("animation").is_some() {
-            Some(crate::types::LockType::Gif)
-        } else if msg.get("voice").is_some() {
-            Some(crate::types::LockType::Voice)
-        } else if msg.get("contact").is_some() {
-            Some(crate::types::LockType::Contact)
-        } else if msg.get("location").is_some() {
-            Some(crate::types::LockType::Location)
-        } else if msg.get("poll").is_some() {
-            Some(crate::types::LockType::Poll)
-        } else if msg.get("game").is_some() {
-            Some(crate::types::LockType::Game)
-        } else if msg.get("forward_from").is_some() || msg.get("forward_from_chat").is_some() {
-            Some(crate::types::LockType::Forward)
-        } else if msg.get("via_bot").is_some() {
-            Some(crate::types::LockType::Inline)
-        } else {
-            None
-        };
-
-        let lock_type = detected_type?;
+        let lock_type = detect_lock_type(msg)?;
 
         if !config.lock_types.contains(&lock_type) {
             return None;
@@ -1188,6 +1281,41 @@ impl Rule for LockRule {
     }
 }
 
+/// 检测 Telegram 消息的可锁定类型（LockRule 与自定义规则共用）
+pub(crate) fn detect_lock_type(msg: &serde_json::Value) -> Option<crate::types::LockType> {
+    use crate::types::LockType;
+
+    if msg.get("photo").is_some() {
+        Some(LockType::Photo)
+    } else if msg.get("video").is_some() {
+        Some(LockType::Video)
+    } else if msg.get("audio").is_some() {
+        Some(LockType::Audio)
+    } else if msg.get("document").is_some() {
+        Some(LockType::Document)
+    } else if msg.get("sticker").is_some() {
+        Some(LockType::Sticker)
+    } else if msg.get("animation").is_some() {
+        Some(LockType::Gif)
+    } else if msg.get("voice").is_some() {
+        Some(LockType::Voice)
+    } else if msg.get("contact").is_some() {
+        Some(LockType::Contact)
+    } else if msg.get("location").is_some() {
+        Some(LockType::Location)
+    } else if msg.get("poll").is_some() {
+        Some(LockType::Poll)
+    } else if msg.get("game").is_some() {
+        Some(LockType::Game)
+    } else if msg.get("forward_from").is_some() || msg.get("forward_from_chat").is_some() {
+        Some(LockType::Forward)
+    } else if msg.get("via_bot").is_some() {
+        Some(LockType::Inline)
+    } else {
+        None
+    }
+}
+
 /// 欢迎消息规则
 ///
 /// 新成员加入时发送欢迎消息。
@@ -1444,7 +1572,7 @@ impl Rule for SpamDetectorRule {
 }
 
 /// 简单 emoji 检测（覆盖常见 Unicode emoji 范围）
-fn is_emoji(c: char) -> bool {
+pub(crate) fn is_emoji(c: char) -> bool {
     let cp = c as u32;
     (0x1F600..=0x1F64F).contains(&cp) || // Emoticons
     (0x1F300..=0x1F5FF).contains(&cp) || // Misc Symbols & Pictographs
@@ -2382,6 +2510,7 @@ mod tests {
             spam_detection_enabled: true,
             spam_max_emoji: 3,
             spam_first_messages_only: 0,
+            custom_rules: vec![],
         }
     }
 
@@ -3462,6 +3591,98 @@ mod tests {
             "Expected Delete for blacklisted word, got {:?}", action.action_type);
     }
 
+    fn spam_ban_rule(priority: i32, pattern: &str) -> crate::types::CustomRule {
+        crate::types::CustomRule {
+            id: "ban_spam".into(),
+            priority,
+            enabled: true,
+            when: crate::types::RuleCondition::TextRegex(pattern.into()),
+            then: crate::types::CustomRuleAction {
+                action_type: ActionType::Admin(AdminAction::Ban),
+                params: serde_json::Value::Null,
+            },
+        }
+    }
+
+    fn spam_text_msg() -> SignedMessage {
+        make_msg(serde_json::json!({
+            "message": {
+                "message_id": 100,
+                "chat": {"id": -100, "type": "supergroup"},
+                "from": {"id": 42, "is_bot": false},
+                "text": "buy spam now"
+            }
+        }))
+    }
+
+    #[test]
+    fn test_custom_rule_priority_interleaves_builtin() {
+        let engine = RuleEngine::default_engine();
+
+        // 高于 blacklist (500) → 自定义规则先命中
+        let mut config = make_bot3_config();
+        config.custom_rules = vec![spam_ban_rule(550, "spam")];
+        let report = engine.dry_run(&spam_text_msg(), Some(config), None).unwrap();
+        assert_eq!(report.matched_rule.as_deref(), Some("custom:ban_spam"));
+        assert!(matches!(report.action.action_type, ActionType::Admin(AdminAction::Ban)));
+        assert_eq!(report.action.params["user_id"], 42);
+
+        // 低于 blacklist → 内置规则先命中
+        let mut config = make_bot3_config();
+        config.custom_rules = vec![spam_ban_rule(450, "spam")];
+        let report = engine.dry_run(&spam_text_msg(), Some(config), None).unwrap();
+        assert_eq!(report.matched_rule.as_deref(), Some("blacklist"));
+    }
+
+    #[test]
+    fn test_dry_run_trace_and_invalid_rules() {
+        let engine = RuleEngine::default_engine();
+        let mut config = make_bot3_config();
+        config.custom_rules = vec![spam_ban_rule(150, "never-matches")];
+        config.blacklist_words.clear();
+
+        let report = engine.dry_run(&spam_text_msg(), Some(config), None).unwrap();
+        let names: Vec<_> = report.trace.iter().map(|t| t.rule.as_str()).collect();
+        assert_eq!(names[..2], ["join_request", "admin_permission"]);
+        assert_eq!(names[names.len() - 2..], ["custom:ban_spam", "default"]);
+        assert!(report.trace.iter().rev().skip(1).all(|t| !t.matched));
+        assert_eq!(report.matched_rule.as_deref(), Some("default"));
+
+        let mut bad = make_bot3_config();
+        bad.custom_rules = vec![spam_ban_rule(150, "(")];
+        assert!(engine.dry_run(&spam_text_msg(), Some(bad), None).is_err());
+    }
+
+    #[test]
+    fn test_custom_rules_from_signed_config() {
+        use ed25519_dalek::{SigningKey, Signer};
+
+        let engine = RuleEngine::default_engine();
+        let (cache, _tmp) = make_e2e_cache();
+        let mut msg = spam_text_msg();
+        msg.bot_id_hash = "test".into();
+
+        // v1: 无自定义规则 → blacklist 删除
+        let action = engine.evaluate(&msg, Some(&cache));
+        assert!(matches!(action.action_type, ActionType::Message(MessageAction::Delete)));
+
+        // v2: 签名发布自定义规则 → 编译缓存按版本刷新
+        let key = SigningKey::from_bytes(&[42u8; 32]);
+        let mut config = make_bot3_config();
+        config.version = 2;
+        config.custom_rules = vec![spam_ban_rule(550, "spam")];
+        let sig = key.sign(serde_json::to_string(&config).unwrap().as_bytes());
+        cache.apply_signed_config("test", crate::types::SignedGroupConfig {
+            config,
+            signature: hex::encode(sig.to_bytes()),
+            signer_public_key: hex::encode(key.verifying_key().as_bytes()),
+        }).unwrap();
+
+        let action = engine.evaluate(&msg, Some(&cache));
+        assert!(matches!(action.action_type, ActionType::Admin(AdminAction::Ban)));
+        assert_eq!(action.reason, "custom_rule_ban_spam");
+    }
+
     #[test]
     fn test_discord_platform_adapter_dispatch() {
         // Verify that get_adapter returns correct adapter for discord
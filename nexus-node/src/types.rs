@@ -265,20 +265,46 @@ pub struct VotePayload {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AlertPayload {
+    /// Bot ID hash (hex)
+    #[serde(default)]
+    pub bot_id_hash: String,
     /// 作弊 owner 公钥
     pub owner_public_key: String,
     /// 序列号
     pub sequence: u64,
+    /// 时间戳 A（签名数据的一部分）
+    #[serde(default)]
+    pub timestamp_a: u64,
     /// 哈希 A
     pub msg_hash_a: String,
     /// 签名 A
     pub signature_a: String,
+    /// 时间戳 B
+    #[serde(default)]
+    pub timestamp_b: u64,
     /// 哈希 B
     pub msg_hash_b: String,
     /// 签名 B
     pub signature_b: String,
 }
 
+impl AlertPayload {
+    /// 由同一 (bot, sequence) 下两条哈希不同的签名消息构造
+    pub fn from_conflict(a: &SignedMessage, b: &SignedMessage) -> Self {
+        Self {
+            bot_id_hash: a.bot_id_hash.clone(),
+            owner_public_key: a.owner_public_key.clone(),
+            sequence: a.sequence,
+            timestamp_a: a.timestamp,
+            msg_hash_a: a.message_hash.clone(),
+            signature_a: a.owner_signature.clone(),
+            timestamp_b: b.timestamp,
+            msg_hash_b: b.message_hash.clone(),
+            signature_b: b.owner_signature.clone(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ResultPayload {
     /// 消息 ID
@@ -577,11 +603,136 @@ pub struct GroupConfig {
     /// 反垃圾：只检查新成员的前 N 条消息（0 = 检查所有）
     #[serde(default)]
     pub spam_first_messages_only: u8,
+
+    // ═══ 自定义规则 DSL ═══
+
+    /// 群主声明的自定义规则（空时不参与序列化，旧签名保持有效）
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub custom_rules: Vec<CustomRule>,
 }
 
 fn default_antiflood_window() -> u16 { 10 }
 fn default_warn_limit() -> u8 { 3 }
 
+// ═══════════════════════════════════════════════════════════════
+// 自定义规则 DSL（随 GroupConfig 签名同步，rule_dsl 模块编译执行）
+// ═══════════════════════════════════════════════════════════════
+
+/// 自定义规则
+///
+/// 与内置规则按 priority 统一排序（值越大越先评估），
+/// 第一条命中的规则决定动作。
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CustomRule {
+    /// 规则 ID（群内唯一，命中时写入 reason）
+    pub id: String,
+    /// 优先级（内置规则优先级见 rule_engine::RuleEngine::default_engine）
+    #[serde(default = "default_custom_rule_priority")]
+    pub priority: i32,
+    /// 是否启用
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// 匹配条件
+    pub when: RuleCondition,
+    /// 命中后的动作
+    pub then: CustomRuleAction,
+}
+
+fn default_custom_rule_priority() -> i32 { 100 }
+fn default_true() -> bool { true }
+
+/// 规则条件（可嵌套组合）
+///
+/// 只依赖签名消息本身的字段，保证所有节点得到相同结果。
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum RuleCondition {
+    /// 全部满足
+    All(Vec<RuleCondition>),
+    /// 任一满足
+    Any(Vec<RuleCondition>),
+    /// 取反
+    Not(Box<RuleCondition>),
+    /// 文本正则匹配
+    TextRegex(String),
+    /// 发送者角色
+    SenderRole(Vec<SenderRole>),
+    /// 消息类型
+    MessageType(Vec<MessageKind>),
+    /// 时间窗口（消息签名时间戳的 UTC 小时，[start, end)，start > end 时跨午夜）
+    TimeWindow { start_hour: u8, end_hour: u8 },
+    /// 计数比较
+    Counter { counter: CounterKind, op: CompareOp, value: u32 },
+}
+
+/// 发送者角色
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SenderRole {
+    /// GroupConfig.admins 中的用户
+    Admin,
+    /// GroupConfig.whitelist 中的用户
+    Whitelisted,
+    /// 普通成员（非管理员、非白名单）
+    Member,
+}
+
+/// 消息类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MessageKind {
+    Text,
+    Command,
+    Callback,
+    JoinRequest,
+    Audio,
+    Video,
+    Photo,
+    Document,
+    Sticker,
+    Gif,
+    Forward,
+    Voice,
+    Contact,
+    Location,
+    Poll,
+    Game,
+    Inline,
+}
+
+/// 计数项（从消息文本计算）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CounterKind {
+    /// 字符数
+    TextLength,
+    /// 行数
+    LineCount,
+    /// 链接数
+    LinkCount,
+    /// @提及数
+    MentionCount,
+    /// emoji 数
+    EmojiCount,
+}
+
+/// 比较运算符
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CompareOp {
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+    Eq,
+}
+
+/// 自定义规则动作
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CustomRuleAction {
+    /// 动作类型（ConfigUpdate 不允许）
+    pub action_type: ActionType,
+    /// 动作参数，字符串值支持占位符:
+    /// {sender_id} / {chat_id} / {message_id} / {reply_to_user_id} / {reply_to_message_id}
+    #[serde(default)]
+    pub params: serde_json::Value,
+}
+
 /// 签名的群配置（含 Agent Ed25519 签名，保证完整性）
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SignedGroupConfig {
@@ -162,6 +162,30 @@ fn verify_ed25519_signature(message: &SignedMessage) -> Result<(), VerifyError>
     Ok(())
 }
 
+/// 验证单条 owner 签名证据（Equivocation 举报用）
+///
+/// 签名数据与 `verify_ed25519_signature` / pallet-bot-consensus `owner_sign_data` 一致:
+/// public_key + bot_id_hash + sequence(LE) + timestamp(LE) + message_hash
+pub fn verify_owner_proof(
+    public_key: &[u8; 32],
+    bot_id_hash: &[u8; 32],
+    sequence: u64,
+    timestamp: u64,
+    msg_hash: &[u8; 32],
+    signature: &[u8; 64],
+) -> bool {
+    let Ok(verifying_key) = VerifyingKey::from_bytes(public_key) else {
+        return false;
+    };
+    let mut sign_data = Vec::with_capacity(32 + 32 + 8 + 8 + 32);
+    sign_data.extend_from_slice(public_key);
+    sign_data.extend_from_slice(bot_id_hash);
+    sign_data.extend_from_slice(&sequence.to_le_bytes());
+    sign_data.extend_from_slice(&timestamp.to_le_bytes());
+    sign_data.extend_from_slice(msg_hash);
+    verifying_key.verify(&sign_data, &Signature::from_bytes(signature)).is_ok()
+}
+
 /// 确定 K 值（与 Agent 侧算法完全一致）
 fn select_k(total_nodes: usize) -> usize {
     if total_nodes <= 3 {
@@ -7,17 +7,24 @@ extern crate alloc;
 pub use pallet::*;
 pub mod weights;
 
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
     use crate::weights::WeightInfo;
     use frame_support::traits::{EnsureOrigin, fungible::{Inspect as FungibleInspect, Mutate as FungibleMutate, MutateHold as FungibleMutateHold}};
-    use frame_support::{pallet_prelude::*, BoundedVec};
+    use frame_support::{pallet_prelude::*, BoundedVec, PalletId};
     use frame_system::pallet_prelude::*;
     use pallet_escrow::pallet::Escrow as EscrowTrait;
     use pallet_storage_service::CidLockManager;
     use pallet_trading_common::PricingProvider;
     use sp_runtime::{Saturating, SaturatedConversion};
+    use sp_runtime::traits::{AccountIdConversion, One, Zero};
     use pallet_storage_lifecycle::block_to_year_month;
     // 基准模块在 pallet 外部声明；此处不在 proc-macro 输入中声明子模块，避免 E0658
 
@@ -32,6 +39,9 @@ pub mod pallet {
     // 🆕 Phase 1-4: 统一投诉系统类型定义
     // ============================================================================
 
+    /// 投诉归档承诺的数据类型标识（叶子为 `(complaint_id, Complaint).encode()`）
+    pub const COMPLAINT_ARCHIVE_DATA_TYPE: &[u8] = b"arb_complaint";
+
     /// 业务域常量 (12个域)
     pub mod domains {
         /// OTC 交易投诉域
@@ -50,8 +60,19 @@ pub mod pallet {
         pub const CREDIT: [u8; 8] = *b"credit__";
         /// 其他
         pub const OTHER: [u8; 8] = *b"other___";
+
+        /// 函数级中文注释：是否为已定义的业务域（陪审员池按域划分）
+        pub fn is_known(domain: &[u8; 8]) -> bool {
+            [OTC_ORDER, LIVESTREAM, MAKER, NFT_TRADE, SWAP, MEMBER, CREDIT, OTHER].contains(domain)
+        }
     }
 
+    /// 陪审团奖惩资金池 PalletId（罚没的陪审员押金与上诉费先入池，再分给投票一致的陪审员）
+    pub const JURY_POT_ID: PalletId = PalletId(*b"arb/jury");
+
+    /// 陪审员投票：0 = Release，1 = Refund（各自的胜诉方由 `ArbitrationRouter::get_parties` 给出）
+    pub const MAX_JURY_VOTE: u8 = 1;
+
     /// 统一投诉类型枚举 (56种类型，覆盖12个业务域)
     /// 
     /// 设计原则：
@@ -292,6 +313,7 @@ pub mod pallet {
     /// - apply_decision：按裁决对 (domain, id) 应用资金与状态变更（由各业务 pallet 内部完成）
     /// - get_counterparty：获取纠纷对方账户（用于双向押金）
     /// - get_order_amount：获取订单/交易金额（用于计算押金比例）
+    /// - get_parties：按订单记录给出 Release / Refund 各自的受益方（用于陪审团确定败诉方）
     pub trait ArbitrationRouter<AccountId, Balance> {
         /// 校验是否允许发起争议
         fn can_dispute(domain: [u8; 8], who: &AccountId, id: u64) -> bool;
@@ -303,6 +325,9 @@ pub mod pallet {
         fn get_order_amount(domain: [u8; 8], id: u64) -> Result<Balance, DispatchError>;
         /// 🆕 获取做市商ID（用于信用分更新，仅OTC域有效）
         fn get_maker_id(_domain: [u8; 8], _id: u64) -> Option<u64> { None }
+        /// 获取纠纷双方 `(Release 受益方, Refund 受益方)`，与 `apply_decision` 的资金去向一致；
+        /// 无订单记录、无法确定双方的域返回错误（该域不支持陪审团）
+        fn get_parties(domain: [u8; 8], id: u64) -> Result<(AccountId, AccountId), DispatchError>;
     }
 
     #[pallet::config]
@@ -364,6 +389,46 @@ pub mod pallet {
         /// - 做市商败诉时扣除信用分
         /// - 做市商胜诉时可选加分
         type CreditUpdater: CreditUpdater;
+
+        /// 归档承诺接口：投诉归档前，完整记录整批上传 IPFS 并在链上保留 Merkle 根
+        type ArchiveCommitter: pallet_storage_lifecycle::ArchiveCommitter;
+
+        // ==================== 陪审团（去中心化仲裁）====================
+
+        /// 抽取种子延迟（区块数）：申请 / 上诉时锁定 `now + JurySeedDelay` 为种子区块，
+        /// 该区块产出后才能以其区块哈希抽取陪审团，申请方无法预知或选择抽取结果
+        #[pallet::constant]
+        type JurySeedDelay: Get<BlockNumberFor<Self>>;
+        /// 加入陪审员池的最低质押
+        #[pallet::constant]
+        type MinJurorStake: Get<BalanceOf<Self>>;
+        /// 每个域陪审员池的最大人数
+        #[pallet::constant]
+        type MaxJurorsPerDomain: Get<u32>;
+        /// 首轮陪审团人数（建议奇数）
+        #[pallet::constant]
+        type InitialPanelSize: Get<u32>;
+        /// 陪审团最大人数：上诉后人数为 `2n + 1`，超过此值的上诉直接交由仲裁委员会终审
+        #[pallet::constant]
+        type MaxPanelSize: Get<u32>;
+        /// 提交投票承诺期（区块数）
+        #[pallet::constant]
+        type CommitPeriod: Get<BlockNumberFor<Self>>;
+        /// 揭示投票期（区块数）
+        #[pallet::constant]
+        type RevealPeriod: Get<BlockNumberFor<Self>>;
+        /// 上诉期（区块数）
+        #[pallet::constant]
+        type AppealPeriod: Get<BlockNumberFor<Self>>;
+        /// 首次上诉费，之后每轮翻倍
+        #[pallet::constant]
+        type AppealBaseFee: Get<BalanceOf<Self>>;
+        /// 投票与裁决不一致（含未揭示）的陪审员罚没比例（基点）
+        #[pallet::constant]
+        type JurorSlashBps: Get<u16>;
+        /// 退出陪审员池后的解绑期（区块数）
+        #[pallet::constant]
+        type JurorUnbondingPeriod: Get<BlockNumberFor<Self>>;
     }
     
     /// 信用分更新接口
@@ -394,6 +459,78 @@ pub mod pallet {
         DisputeRespondent,
         /// 投诉押金（防止恶意投诉）
         ComplaintDeposit,
+        /// 陪审员质押
+        JurorStake,
+    }
+
+    /// 陪审员信息（按域）
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+    pub struct JurorInfo<Balance, BlockNumber> {
+        /// 当前质押（罚没后减少）
+        pub stake: Balance,
+        /// 正在参与（未结算）的陪审团数
+        pub active_cases: u32,
+        /// 已退出池、可取回质押的区块；None 表示仍在池中
+        pub unbonding_at: Option<BlockNumber>,
+    }
+
+    /// 陪审团阶段
+    #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+    pub enum JuryPhase {
+        /// 提交 / 揭示投票中（按 `commit_end`、`reveal_end` 区分）
+        Voting,
+        /// 本轮已裁决，等待上诉期结束
+        Appealable,
+        /// 已交由仲裁委员会终审（平票、无人揭示或超过最大人数的上诉）
+        Escalated,
+        /// 已申请 / 上诉，等待种子区块产出后抽取陪审团
+        Drawing,
+    }
+
+    /// 陪审团抽取种子：种子区块产出后由下一区块的 `on_initialize` 记录其哈希
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, Default, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+    pub struct JurySeed<Hash> {
+        /// 种子区块哈希（未产出时为 None）
+        pub hash: Option<Hash>,
+        /// 以该区块为种子、尚未抽取的案件数，归零时删除
+        pub pending: u32,
+    }
+
+    /// 陪审团案件
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+    #[scale_info(skip_type_params(T))]
+    pub struct JuryCase<T: Config> {
+        /// Release 裁决的受益方（由 Router 按订单给出）
+        pub release_party: T::AccountId,
+        /// Refund 裁决的受益方（由 Router 按订单给出）
+        pub refund_party: T::AccountId,
+        /// 当前轮次（首轮为 0）
+        pub round: u32,
+        /// 本轮陪审团（`Drawing` 阶段为上一轮陪审团，首轮为空）
+        pub panel: BoundedVec<T::AccountId, T::MaxPanelSize>,
+        /// 本轮抽取种子区块（`Drawing` 阶段有效）
+        pub seed_block: BlockNumberFor<T>,
+        /// 提交承诺截止区块
+        pub commit_end: BlockNumberFor<T>,
+        /// 揭示截止区块
+        pub reveal_end: BlockNumberFor<T>,
+        /// 阶段
+        pub phase: JuryPhase,
+        /// 本轮裁决（0=Release, 1=Refund）
+        pub ruling: Option<u8>,
+        /// 上诉截止区块（`Appealable` 阶段有效）
+        pub appeal_end: BlockNumberFor<T>,
+        /// 本轮奖励池（上诉费），结算时与罚没一并分给投票一致的陪审员
+        pub reward_pool: BalanceOf<T>,
+    }
+
+    /// 陪审员投票
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+    pub struct JurorVote<Hash> {
+        /// 承诺：`hash((vote, salt, juror))`
+        pub commitment: Hash,
+        /// 揭示后的投票
+        pub revealed: Option<u8>,
     }
 
     /// 🆕 存储膨胀防护：归档仲裁记录（精简版）
@@ -576,6 +713,59 @@ pub mod pallet {
     #[pallet::storage]
     pub type ComplaintArchiveCursor<T: Config> = StorageValue<_, u64, ValueQuery>;
 
+    // ==================== 陪审团存储 ====================
+
+    /// 陪审员池：domain => 可被抽取的陪审员
+    #[pallet::storage]
+    pub type JurorPools<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        [u8; 8],
+        BoundedVec<T::AccountId, T::MaxJurorsPerDomain>,
+        ValueQuery,
+    >;
+
+    /// 陪审员信息：(domain, juror) => JurorInfo
+    #[pallet::storage]
+    pub type Jurors<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        [u8; 8],
+        Blake2_128Concat,
+        T::AccountId,
+        JurorInfo<BalanceOf<T>, BlockNumberFor<T>>,
+        OptionQuery,
+    >;
+
+    /// 陪审团案件：(domain, object_id) => JuryCase
+    #[pallet::storage]
+    pub type JuryCases<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        [u8; 8],
+        Blake2_128Concat,
+        u64,
+        JuryCase<T>,
+        OptionQuery,
+    >;
+
+    /// 本轮投票：((domain, object_id), juror) => JurorVote
+    #[pallet::storage]
+    pub type JuryVotes<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        ([u8; 8], u64),
+        Blake2_128Concat,
+        T::AccountId,
+        JurorVote<T::Hash>,
+        OptionQuery,
+    >;
+
+    /// 陪审团抽取种子：seed_block => JurySeed
+    #[pallet::storage]
+    pub type JurySeeds<T: Config> =
+        StorageMap<_, Twox64Concat, BlockNumberFor<T>, JurySeed<T::Hash>, OptionQuery>;
+
     // ============================================================================
 
     #[pallet::event]
@@ -656,6 +846,92 @@ pub mod pallet {
         ComplaintArchived {
             complaint_id: u64,
         },
+
+        // ==================== 陪审团事件 ====================
+
+        /// 陪审员加入（或追加质押）
+        JurorJoined {
+            domain: [u8; 8],
+            juror: T::AccountId,
+            stake: BalanceOf<T>,
+        },
+        /// 陪审员退出池，进入解绑期
+        JurorLeft {
+            domain: [u8; 8],
+            juror: T::AccountId,
+            unbonding_at: BlockNumberFor<T>,
+        },
+        /// 陪审员取回质押
+        JurorStakeWithdrawn {
+            domain: [u8; 8],
+            juror: T::AccountId,
+            amount: BalanceOf<T>,
+        },
+        /// 已申请陪审团（含上诉），种子区块产出后可调用 `draw_jury` 抽取
+        JuryRequested {
+            domain: [u8; 8],
+            id: u64,
+            round: u32,
+            seed_block: BlockNumberFor<T>,
+        },
+        /// 已抽取陪审团
+        JuryDrawn {
+            domain: [u8; 8],
+            id: u64,
+            round: u32,
+            panel: alloc::vec::Vec<T::AccountId>,
+            commit_end: BlockNumberFor<T>,
+            reveal_end: BlockNumberFor<T>,
+        },
+        /// 陪审员已提交投票承诺
+        JuryVoteCommitted {
+            domain: [u8; 8],
+            id: u64,
+            juror: T::AccountId,
+        },
+        /// 陪审员已揭示投票
+        JuryVoteRevealed {
+            domain: [u8; 8],
+            id: u64,
+            juror: T::AccountId,
+            vote: u8,
+        },
+        /// 陪审员因投票不一致或未揭示被罚没
+        JurorSlashed {
+            domain: [u8; 8],
+            id: u64,
+            juror: T::AccountId,
+            amount: BalanceOf<T>,
+        },
+        /// 投票一致的陪审员获得奖励
+        JurorRewarded {
+            domain: [u8; 8],
+            id: u64,
+            juror: T::AccountId,
+            amount: BalanceOf<T>,
+        },
+        /// 本轮已裁决，进入上诉期
+        JuryRuled {
+            domain: [u8; 8],
+            id: u64,
+            round: u32,
+            ruling: u8,
+            appeal_end: BlockNumberFor<T>,
+        },
+        /// 败诉方已上诉
+        JuryAppealed {
+            domain: [u8; 8],
+            id: u64,
+            appellant: T::AccountId,
+            round: u32,
+            fee: BalanceOf<T>,
+        },
+        /// 已交由仲裁委员会终审
+        JuryEscalated {
+            domain: [u8; 8],
+            id: u64,
+            round: u32,
+        },
     }
 
     #[pallet::error]
@@ -685,6 +961,61 @@ pub mod pallet {
         TooManyComplaints,
         /// 用户活跃投诉数量已达上限
         TooManyActiveComplaints,
+
+        // ==================== 陪审团错误 ====================
+
+        /// 未定义的业务域
+        UnknownDomain,
+        /// 质押低于最低要求
+        JurorStakeTooLow,
+        /// 陪审员池已满
+        JurorPoolFull,
+        /// 不是该域的陪审员
+        JurorNotFound,
+        /// 陪审员仍有未结算的案件
+        JurorHasActiveCases,
+        /// 已退出池（解绑中）
+        JurorUnbonding,
+        /// 未退出池或解绑期未结束
+        JurorNotUnbonded,
+        /// 该纠纷已有陪审团
+        JuryAlreadyExists,
+        /// 该纠纷没有陪审团
+        JuryNotFound,
+        /// 可抽取的陪审员不足
+        NotEnoughJurors,
+        /// 不是本轮陪审员
+        NotPanelJuror,
+        /// 不在提交承诺期
+        NotInCommitPhase,
+        /// 不在揭示期
+        NotInRevealPhase,
+        /// 已提交承诺
+        AlreadyCommitted,
+        /// 未提交承诺
+        NotCommitted,
+        /// 已揭示
+        AlreadyRevealed,
+        /// 揭示内容与承诺不符
+        CommitmentMismatch,
+        /// 无效投票
+        InvalidVote,
+        /// 揭示期尚未结束
+        RevealNotOver,
+        /// 陪审团阶段不允许该操作
+        InvalidJuryPhase,
+        /// 上诉期已过
+        AppealPeriodOver,
+        /// 上诉期尚未结束
+        AppealPeriodNotOver,
+        /// 只有败诉方可以上诉
+        NotLosingParty,
+        /// Router 无法确定该纠纷的双方（该域不支持陪审团）
+        DisputePartiesNotFound,
+        /// 不是纠纷当事人
+        NotDisputeParty,
+        /// 种子区块尚未产出，暂不能抽取陪审团
+        JurySeedNotReady,
     }
 
     #[pallet::call]
@@ -734,46 +1065,14 @@ pub mod pallet {
                 Disputed::<T>::get(domain, id).is_some(),
                 Error::<T>::NotDisputed
             );
-            // 通过 Router 将裁决应用到对应域的业务 pallet
             let decision = match (decision_code, bps) {
                 (0, _) => Decision::Release,
                 (1, _) => Decision::Refund,
                 (2, Some(p)) => Decision::Partial(p),
                 _ => Decision::Refund,
             };
-            T::Router::apply_decision(domain, id, decision.clone())?;
-
-            // 🆕 处理双向押金
-            Self::handle_deposits_on_arbitration(domain, id, &decision)?;
-
-            // 🆕 P2: 解锁仲裁期间锁定的证据 CID
-            Self::unlock_all_evidence_cids(domain, id)?;
-
-            // 🆕 信用分集成：根据裁决结果更新做市商信用分
-            // - Release（做市商胜诉）：maker_win = true
-            // - Refund/Partial（做市商败诉）：maker_win = false，扣除信用分
-            if let Some(maker_id) = T::Router::get_maker_id(domain, id) {
-                let maker_win = matches!(decision, Decision::Release);
-                // 忽略错误，信用更新失败不影响主流程
-                let _ = T::CreditUpdater::record_maker_dispute_result(maker_id, id, maker_win);
-            }
-            
-            let out = match decision {
-                Decision::Release => (0, None),
-                Decision::Refund => (1, None),
-                Decision::Partial(p) => (2, Some(p)),
-            };
-
-            // 🆕 归档已完成的仲裁并清理存储
-            Self::archive_and_cleanup(domain, id, out.0, out.1.unwrap_or(0));
-
-            Self::deposit_event(Event::Arbitrated {
-                domain,
-                id,
-                decision: out.0,
-                bps: out.1,
-            });
-            Ok(())
+            // 🆕 仲裁委员会为终审：若存在陪审团案件，一并解散
+            Self::do_arbitrate(domain, id, decision)
         }
 
         /// 函数级中文注释：以 evidence_id 的方式发起仲裁登记。
@@ -1377,6 +1676,313 @@ pub mod pallet {
                 Ok(())
             })
         }
+
+        // ============================================================================
+        // 🆕 陪审团：质押陪审员池 + 随机抽取 + 提交/揭示投票 + 上诉
+        // ============================================================================
+
+        /// 函数级详细中文注释：加入某业务域的陪审员池（或为已加入的池追加质押）
+        /// - 首次加入要求质押不低于 `MinJurorStake`，池人数不超过 `MaxJurorsPerDomain`
+        /// - 质押以 `HoldReason::JurorStake` 锁定在陪审员自己账户上
+        #[pallet::call_index(20)]
+        #[pallet::weight(T::WeightInfo::juror_pool())]
+        pub fn join_juror_pool(
+            origin: OriginFor<T>,
+            domain: [u8; 8],
+            stake: BalanceOf<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(domains::is_known(&domain), Error::<T>::UnknownDomain);
+            ensure!(!stake.is_zero(), Error::<T>::JurorStakeTooLow);
+
+            let mut info = match Jurors::<T>::get(domain, &who) {
+                Some(info) => {
+                    ensure!(info.unbonding_at.is_none(), Error::<T>::JurorUnbonding);
+                    info
+                },
+                None => {
+                    ensure!(stake >= T::MinJurorStake::get(), Error::<T>::JurorStakeTooLow);
+                    JurorPools::<T>::try_mutate(domain, |pool| {
+                        pool.try_push(who.clone()).map_err(|_| Error::<T>::JurorPoolFull)
+                    })?;
+                    JurorInfo { stake: Zero::zero(), active_cases: 0, unbonding_at: None }
+                },
+            };
+
+            T::Fungible::hold(&T::RuntimeHoldReason::from(HoldReason::JurorStake), &who, stake)
+                .map_err(|_| Error::<T>::InsufficientDeposit)?;
+            info.stake = info.stake.saturating_add(stake);
+            Jurors::<T>::insert(domain, &who, &info);
+
+            Self::deposit_event(Event::JurorJoined { domain, juror: who, stake: info.stake });
+            Ok(())
+        }
+
+        /// 函数级中文注释：退出陪审员池，进入解绑期（不再被抽取；须无未结算案件）
+        #[pallet::call_index(21)]
+        #[pallet::weight(T::WeightInfo::juror_pool())]
+        pub fn leave_juror_pool(origin: OriginFor<T>, domain: [u8; 8]) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let mut info = Jurors::<T>::get(domain, &who).ok_or(Error::<T>::JurorNotFound)?;
+            ensure!(info.unbonding_at.is_none(), Error::<T>::JurorUnbonding);
+            ensure!(info.active_cases == 0, Error::<T>::JurorHasActiveCases);
+
+            JurorPools::<T>::mutate(domain, |pool| pool.retain(|a| a != &who));
+            let unbonding_at =
+                frame_system::Pallet::<T>::block_number().saturating_add(T::JurorUnbondingPeriod::get());
+            info.unbonding_at = Some(unbonding_at);
+            Jurors::<T>::insert(domain, &who, info);
+
+            Self::deposit_event(Event::JurorLeft { domain, juror: who, unbonding_at });
+            Ok(())
+        }
+
+        /// 函数级中文注释：解绑期结束后取回剩余质押
+        #[pallet::call_index(22)]
+        #[pallet::weight(T::WeightInfo::juror_pool())]
+        pub fn withdraw_juror_stake(origin: OriginFor<T>, domain: [u8; 8]) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let info = Jurors::<T>::get(domain, &who).ok_or(Error::<T>::JurorNotFound)?;
+            let now = frame_system::Pallet::<T>::block_number();
+            ensure!(
+                info.unbonding_at.map_or(false, |at| now >= at),
+                Error::<T>::JurorNotUnbonded
+            );
+
+            let amount = T::Fungible::release(
+                &T::RuntimeHoldReason::from(HoldReason::JurorStake),
+                &who,
+                info.stake,
+                frame_support::traits::tokens::Precision::BestEffort,
+            )?;
+            Jurors::<T>::remove(domain, &who);
+
+            Self::deposit_event(Event::JurorStakeWithdrawn { domain, juror: who, amount });
+            Ok(())
+        }
+
+        /// 函数级详细中文注释：纠纷当事人申请陪审团审理
+        /// - 纠纷须已登记且尚无陪审团
+        /// - 当事人由 Router 按订单给出（Release / Refund 受益方），不依赖谁先发起纠纷；调用方须为其一
+        /// - 该域可抽取的陪审员（排除当事人）须不少于 `InitialPanelSize`
+        /// - 锁定 `now + JurySeedDelay` 为种子区块，该区块产出后由 `draw_jury` 抽取陪审团
+        #[pallet::call_index(23)]
+        #[pallet::weight(T::WeightInfo::draw_jury(T::MaxJurorsPerDomain::get()))]
+        pub fn request_jury(origin: OriginFor<T>, domain: [u8; 8], id: u64) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(domains::is_known(&domain), Error::<T>::UnknownDomain);
+            ensure!(Disputed::<T>::get(domain, id).is_some(), Error::<T>::NotDisputed);
+            ensure!(!JuryCases::<T>::contains_key(domain, id), Error::<T>::JuryAlreadyExists);
+
+            let (release_party, refund_party) = T::Router::get_parties(domain, id)
+                .map_err(|_| Error::<T>::DisputePartiesNotFound)?;
+            ensure!(who == release_party || who == refund_party, Error::<T>::NotDisputeParty);
+
+            let eligible =
+                Self::eligible_jurors(domain, &[release_party.clone(), refund_party.clone()]);
+            ensure!(
+                eligible.len() >= T::InitialPanelSize::get() as usize,
+                Error::<T>::NotEnoughJurors
+            );
+
+            let seed_block = Self::lock_jury_seed();
+            let case = JuryCase::<T> {
+                release_party,
+                refund_party,
+                round: 0,
+                panel: BoundedVec::default(),
+                seed_block,
+                commit_end: Zero::zero(),
+                reveal_end: Zero::zero(),
+                phase: JuryPhase::Drawing,
+                ruling: None,
+                appeal_end: Zero::zero(),
+                reward_pool: Zero::zero(),
+            };
+            JuryCases::<T>::insert(domain, id, case);
+            Self::deposit_event(Event::JuryRequested { domain, id, round: 0, seed_block });
+            Ok(())
+        }
+
+        /// 函数级中文注释：陪审员在提交期内提交投票承诺 `hash((vote, salt, juror))`
+        #[pallet::call_index(24)]
+        #[pallet::weight(T::WeightInfo::jury_vote())]
+        pub fn commit_jury_vote(
+            origin: OriginFor<T>,
+            domain: [u8; 8],
+            id: u64,
+            commitment: T::Hash,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let case = JuryCases::<T>::get(domain, id).ok_or(Error::<T>::JuryNotFound)?;
+            ensure!(case.panel.contains(&who), Error::<T>::NotPanelJuror);
+            let now = frame_system::Pallet::<T>::block_number();
+            ensure!(
+                case.phase == JuryPhase::Voting && now <= case.commit_end,
+                Error::<T>::NotInCommitPhase
+            );
+            ensure!(
+                !JuryVotes::<T>::contains_key((domain, id), &who),
+                Error::<T>::AlreadyCommitted
+            );
+
+            JuryVotes::<T>::insert((domain, id), &who, JurorVote { commitment, revealed: None });
+            Self::deposit_event(Event::JuryVoteCommitted { domain, id, juror: who });
+            Ok(())
+        }
+
+        /// 函数级中文注释：陪审员在揭示期内揭示投票（0=Release, 1=Refund）
+        #[pallet::call_index(25)]
+        #[pallet::weight(T::WeightInfo::jury_vote())]
+        pub fn reveal_jury_vote(
+            origin: OriginFor<T>,
+            domain: [u8; 8],
+            id: u64,
+            vote: u8,
+            salt: [u8; 32],
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(vote <= MAX_JURY_VOTE, Error::<T>::InvalidVote);
+            let case = JuryCases::<T>::get(domain, id).ok_or(Error::<T>::JuryNotFound)?;
+            let now = frame_system::Pallet::<T>::block_number();
+            ensure!(
+                case.phase == JuryPhase::Voting && now > case.commit_end && now <= case.reveal_end,
+                Error::<T>::NotInRevealPhase
+            );
+
+            JuryVotes::<T>::try_mutate((domain, id), &who, |maybe_vote| -> DispatchResult {
+                let record = maybe_vote.as_mut().ok_or(Error::<T>::NotCommitted)?;
+                ensure!(record.revealed.is_none(), Error::<T>::AlreadyRevealed);
+                ensure!(
+                    record.commitment == T::Hashing::hash_of(&(vote, salt, &who)),
+                    Error::<T>::CommitmentMismatch
+                );
+                record.revealed = Some(vote);
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::JuryVoteRevealed { domain, id, juror: who, vote });
+            Ok(())
+        }
+
+        /// 函数级详细中文注释：揭示期结束后结算本轮（任何人可调用）
+        /// - 多数票为本轮裁决，进入上诉期；平票或无人揭示则交由仲裁委员会
+        /// - 与裁决不一致或未揭示的陪审员按 `JurorSlashBps` 罚没质押
+        /// - 罚没所得与本轮奖励池平分给投票一致的陪审员（交由委员会时转入国库）
+        #[pallet::call_index(26)]
+        #[pallet::weight(T::WeightInfo::finalize_jury_round(T::MaxPanelSize::get()))]
+        pub fn finalize_jury_round(origin: OriginFor<T>, domain: [u8; 8], id: u64) -> DispatchResult {
+            ensure_signed(origin)?;
+            let mut case = JuryCases::<T>::get(domain, id).ok_or(Error::<T>::JuryNotFound)?;
+            ensure!(case.phase == JuryPhase::Voting, Error::<T>::InvalidJuryPhase);
+            let now = frame_system::Pallet::<T>::block_number();
+            ensure!(now > case.reveal_end, Error::<T>::RevealNotOver);
+
+            Self::settle_round(domain, id, &mut case);
+            JuryCases::<T>::insert(domain, id, case);
+            Ok(())
+        }
+
+        /// 函数级详细中文注释：败诉方在上诉期内上诉
+        /// - 上诉费 `AppealBaseFee * 2^round` 进入奖励池，作为下一轮一致陪审员的奖励
+        /// - 新陪审团人数为 `2n + 1`，锁定新的种子区块后由 `draw_jury` 抽取；
+        ///   超过 `MaxPanelSize` 或陪审员不足时交由仲裁委员会终审，上诉费转入国库
+        #[pallet::call_index(27)]
+        #[pallet::weight(T::WeightInfo::draw_jury(T::MaxJurorsPerDomain::get()))]
+        pub fn appeal_jury(origin: OriginFor<T>, domain: [u8; 8], id: u64) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let mut case = JuryCases::<T>::get(domain, id).ok_or(Error::<T>::JuryNotFound)?;
+            ensure!(case.phase == JuryPhase::Appealable, Error::<T>::InvalidJuryPhase);
+            let now = frame_system::Pallet::<T>::block_number();
+            ensure!(now <= case.appeal_end, Error::<T>::AppealPeriodOver);
+            // Release 裁决时 Refund 受益方败诉，反之亦然
+            let loser = if case.ruling == Some(0) { &case.refund_party } else { &case.release_party };
+            ensure!(&who == loser, Error::<T>::NotLosingParty);
+
+            let multiplier: BalanceOf<T> = 2u128.saturating_pow(case.round).saturated_into();
+            let fee = T::AppealBaseFee::get().saturating_mul(multiplier);
+            let next_size = Self::next_panel_size(&case);
+            let drawable = next_size <= T::MaxPanelSize::get() &&
+                Self::eligible_jurors(domain, &[case.release_party.clone(), case.refund_party.clone()])
+                    .len() >= next_size as usize;
+
+            let payee = if drawable { Self::jury_pot_account() } else { T::TreasuryAccount::get() };
+            T::Fungible::transfer(
+                &who,
+                &payee,
+                fee,
+                frame_support::traits::tokens::Preservation::Preserve,
+            )?;
+            Self::deposit_event(Event::JuryAppealed {
+                domain,
+                id,
+                appellant: who,
+                round: case.round,
+                fee,
+            });
+
+            case.round = case.round.saturating_add(1);
+            case.ruling = None;
+            if drawable {
+                case.reward_pool = fee;
+                case.seed_block = Self::lock_jury_seed();
+                case.phase = JuryPhase::Drawing;
+                Self::deposit_event(Event::JuryRequested {
+                    domain,
+                    id,
+                    round: case.round,
+                    seed_block: case.seed_block,
+                });
+            } else {
+                case.phase = JuryPhase::Escalated;
+                Self::deposit_event(Event::JuryEscalated { domain, id, round: case.round });
+            }
+            JuryCases::<T>::insert(domain, id, case);
+            Ok(())
+        }
+
+        /// 函数级中文注释：上诉期结束且无人上诉时执行陪审团裁决（任何人可调用）
+        #[pallet::call_index(28)]
+        #[pallet::weight(T::WeightInfo::arbitrate())]
+        pub fn execute_jury_ruling(origin: OriginFor<T>, domain: [u8; 8], id: u64) -> DispatchResult {
+            ensure_signed(origin)?;
+            let case = JuryCases::<T>::get(domain, id).ok_or(Error::<T>::JuryNotFound)?;
+            ensure!(case.phase == JuryPhase::Appealable, Error::<T>::InvalidJuryPhase);
+            let now = frame_system::Pallet::<T>::block_number();
+            ensure!(now > case.appeal_end, Error::<T>::AppealPeriodNotOver);
+
+            let decision = if case.ruling == Some(0) { Decision::Release } else { Decision::Refund };
+            Self::do_arbitrate(domain, id, decision)
+        }
+
+        /// 函数级详细中文注释：种子区块产出后抽取陪审团（任何人可调用）
+        /// - 种子为申请 / 上诉时锁定的未来区块的哈希，由 `on_initialize` 记录，调用时机不影响抽取结果
+        /// - 抽取时可用陪审员不足（期间有人退出池）则交由仲裁委员会终审，奖励池转入国库
+        #[pallet::call_index(29)]
+        #[pallet::weight(T::WeightInfo::draw_jury(T::MaxJurorsPerDomain::get()))]
+        pub fn draw_jury(origin: OriginFor<T>, domain: [u8; 8], id: u64) -> DispatchResult {
+            ensure_signed(origin)?;
+            let mut case = JuryCases::<T>::get(domain, id).ok_or(Error::<T>::JuryNotFound)?;
+            ensure!(case.phase == JuryPhase::Drawing, Error::<T>::InvalidJuryPhase);
+            let seed_hash = JurySeeds::<T>::get(case.seed_block)
+                .and_then(|seed| seed.hash)
+                .ok_or(Error::<T>::JurySeedNotReady)?;
+            Self::release_jury_seed(case.seed_block);
+
+            let size = Self::next_panel_size(&case);
+            let excluded = [case.release_party.clone(), case.refund_party.clone()];
+            match Self::draw_panel(domain, id, case.round, size, &excluded, seed_hash) {
+                Ok(panel) => Self::start_round(domain, id, &mut case, panel),
+                Err(_) => {
+                    Self::pay_treasury_from_pot(core::mem::take(&mut case.reward_pool));
+                    case.panel = BoundedVec::default();
+                    case.phase = JuryPhase::Escalated;
+                    Self::deposit_event(Event::JuryEscalated { domain, id, round: case.round });
+                },
+            }
+            JuryCases::<T>::insert(domain, id, case);
+            Ok(())
+        }
     }
 
     /// 🆕 辅助函数实现
@@ -1386,6 +1992,50 @@ pub mod pallet {
         fn get_escrow_account() -> T::AccountId {
             T::Escrow::escrow_account()
         }
+
+        /// 函数级详细中文注释：执行裁决（仲裁委员会 `arbitrate` 与陪审团 `execute_jury_ruling` 共用）
+        /// 1) 解散陪审团案件（未结算的轮次释放陪审员、奖励池转入国库）；
+        /// 2) 通过 Router 将裁决应用到对应域的业务 pallet；
+        /// 3) 处理双向押金、解锁证据 CID、更新做市商信用分；
+        /// 4) 归档并清理存储，触发 `Arbitrated` 事件。
+        fn do_arbitrate(domain: [u8; 8], id: u64, decision: Decision) -> DispatchResult {
+            Self::dissolve_jury(domain, id);
+
+            // 通过 Router 将裁决应用到对应域的业务 pallet
+            T::Router::apply_decision(domain, id, decision.clone())?;
+
+            // 🆕 处理双向押金
+            Self::handle_deposits_on_arbitration(domain, id, &decision)?;
+
+            // 🆕 P2: 解锁仲裁期间锁定的证据 CID
+            Self::unlock_all_evidence_cids(domain, id)?;
+
+            // 🆕 信用分集成：根据裁决结果更新做市商信用分
+            // - Release（做市商胜诉）：maker_win = true
+            // - Refund/Partial（做市商败诉）：maker_win = false，扣除信用分
+            if let Some(maker_id) = T::Router::get_maker_id(domain, id) {
+                let maker_win = matches!(decision, Decision::Release);
+                // 忽略错误，信用更新失败不影响主流程
+                let _ = T::CreditUpdater::record_maker_dispute_result(maker_id, id, maker_win);
+            }
+            
+            let out = match decision {
+                Decision::Release => (0, None),
+                Decision::Refund => (1, None),
+                Decision::Partial(p) => (2, Some(p)),
+            };
+
+            // 🆕 归档已完成的仲裁并清理存储
+            Self::archive_and_cleanup(domain, id, out.0, out.1.unwrap_or(0));
+
+            Self::deposit_event(Event::Arbitrated {
+                domain,
+                id,
+                decision: out.0,
+                bps: out.1,
+            });
+            Ok(())
+        }
         /// 函数级中文注释：仲裁时处理双向押金
         /// - Release: 买家败诉，罚没买家押金30%，卖家押金全额返还到托管
         /// - Refund: 卖家败诉，罚没卖家押金30%，买家押金全额返还到托管
@@ -1539,6 +2189,255 @@ pub mod pallet {
             Ok(())
         }
 
+        // ============================================================================
+        // 🆕 陪审团辅助函数
+        // ============================================================================
+
+        /// 函数级中文注释：陪审团奖惩资金池账户
+        pub fn jury_pot_account() -> T::AccountId {
+            JURY_POT_ID.into_account_truncating()
+        }
+
+        /// 函数级中文注释：可抽取的陪审员及其质押
+        /// - 池中未解绑、质押不低于 `MinJurorStake`、且不在 `excluded`（当事人）中
+        fn eligible_jurors(
+            domain: [u8; 8],
+            excluded: &[T::AccountId],
+        ) -> alloc::vec::Vec<(T::AccountId, u128)> {
+            let min_stake = T::MinJurorStake::get();
+            JurorPools::<T>::get(domain)
+                .into_iter()
+                .filter(|who| !excluded.contains(who))
+                .filter_map(|who| {
+                    let info = Jurors::<T>::get(domain, &who)?;
+                    if info.unbonding_at.is_some() || info.stake < min_stake {
+                        return None;
+                    }
+                    Some((who, info.stake.saturated_into::<u128>()))
+                })
+                .collect()
+        }
+
+        /// 函数级中文注释：下一次抽取的陪审团人数
+        /// - 首轮（尚无陪审团）为 `InitialPanelSize`，之后为上一轮人数的 `2n + 1`
+        fn next_panel_size(case: &JuryCase<T>) -> u32 {
+            if case.panel.is_empty() {
+                T::InitialPanelSize::get()
+            } else {
+                (case.panel.len() as u32).saturating_mul(2).saturating_add(1)
+            }
+        }
+
+        /// 函数级中文注释：锁定 `now + JurySeedDelay`（至少下一区块）为种子区块并登记引用
+        fn lock_jury_seed() -> BlockNumberFor<T> {
+            let now = frame_system::Pallet::<T>::block_number();
+            let seed_block = now.saturating_add(T::JurySeedDelay::get().max(One::one()));
+            JurySeeds::<T>::mutate(seed_block, |seed| {
+                let seed = seed.get_or_insert_with(Default::default);
+                seed.pending = seed.pending.saturating_add(1);
+            });
+            seed_block
+        }
+
+        /// 函数级中文注释：释放一次种子引用，无待抽取案件时删除
+        fn release_jury_seed(seed_block: BlockNumberFor<T>) {
+            JurySeeds::<T>::mutate_exists(seed_block, |maybe_seed| {
+                if let Some(seed) = maybe_seed {
+                    seed.pending = seed.pending.saturating_sub(1);
+                    if seed.pending == 0 {
+                        *maybe_seed = None;
+                    }
+                }
+            });
+        }
+
+        /// 函数级详细中文注释：按质押加权、无放回地随机抽取陪审团
+        /// - 候选见 [`Self::eligible_jurors`]
+        /// - 种子由种子区块哈希与 (domain, id, round) 派生，第 i 次抽取使用 `hash(seed, i)`
+        fn draw_panel(
+            domain: [u8; 8],
+            id: u64,
+            round: u32,
+            size: u32,
+            excluded: &[T::AccountId],
+            seed_hash: T::Hash,
+        ) -> Result<BoundedVec<T::AccountId, T::MaxPanelSize>, DispatchError> {
+            let mut candidates = Self::eligible_jurors(domain, excluded);
+            ensure!(candidates.len() >= size as usize, Error::<T>::NotEnoughJurors);
+
+            let seed = T::Hashing::hash_of(&(JURY_POT_ID.0, domain, id, round, seed_hash));
+            let mut panel = BoundedVec::<T::AccountId, T::MaxPanelSize>::default();
+            for i in 0..size {
+                let total = candidates.iter().fold(0u128, |acc, (_, s)| acc.saturating_add(*s)).max(1);
+                let random = T::Hashing::hash_of(&(seed, i));
+                let mut point = u128::decode(&mut random.as_ref()).unwrap_or_default() % total;
+                let index = candidates
+                    .iter()
+                    .position(|(_, stake)| {
+                        if point < *stake {
+                            true
+                        } else {
+                            point -= *stake;
+                            false
+                        }
+                    })
+                    .unwrap_or(candidates.len() - 1);
+                let (juror, _) = candidates.swap_remove(index);
+                panel.try_push(juror).map_err(|_| Error::<T>::NotEnoughJurors)?;
+            }
+            Ok(panel)
+        }
+
+        /// 函数级中文注释：开始新一轮投票（记录陪审团、设置期限、占用陪审员）
+        fn start_round(
+            domain: [u8; 8],
+            id: u64,
+            case: &mut JuryCase<T>,
+            panel: BoundedVec<T::AccountId, T::MaxPanelSize>,
+        ) {
+            for juror in panel.iter() {
+                Jurors::<T>::mutate(domain, juror, |info| {
+                    if let Some(info) = info {
+                        info.active_cases = info.active_cases.saturating_add(1);
+                    }
+                });
+            }
+            let now = frame_system::Pallet::<T>::block_number();
+            case.commit_end = now.saturating_add(T::CommitPeriod::get());
+            case.reveal_end = case.commit_end.saturating_add(T::RevealPeriod::get());
+            case.phase = JuryPhase::Voting;
+            case.panel = panel;
+
+            Self::deposit_event(Event::JuryDrawn {
+                domain,
+                id,
+                round: case.round,
+                panel: case.panel.to_vec(),
+                commit_end: case.commit_end,
+                reveal_end: case.reveal_end,
+            });
+        }
+
+        /// 函数级详细中文注释：结算本轮投票
+        /// 1) 统计揭示票，严格多数为裁决；
+        /// 2) 释放陪审员占用，罚没不一致/未揭示者（平票时仅罚没未揭示者）到资金池；
+        /// 3) 有裁决：罚没所得与奖励池平分给一致者，进入上诉期；
+        ///    无裁决：全部转入国库，交由仲裁委员会。
+        fn settle_round(domain: [u8; 8], id: u64, case: &mut JuryCase<T>) {
+            let votes: alloc::vec::Vec<Option<u8>> = case
+                .panel
+                .iter()
+                .map(|juror| JuryVotes::<T>::take((domain, id), juror).and_then(|v| v.revealed))
+                .collect();
+            let refunds = votes.iter().filter(|v| **v == Some(1)).count();
+            let releases = votes.iter().filter(|v| **v == Some(0)).count();
+            let ruling = match releases.cmp(&refunds) {
+                core::cmp::Ordering::Greater => Some(0u8),
+                core::cmp::Ordering::Less => Some(1u8),
+                core::cmp::Ordering::Equal => None,
+            };
+
+            let pot = Self::jury_pot_account();
+            let slash_ratio = sp_runtime::Permill::from_parts((T::JurorSlashBps::get() as u32) * 100);
+            let mut slashed = BalanceOf::<T>::zero();
+            let mut coherent = alloc::vec::Vec::new();
+            for (juror, vote) in case.panel.iter().zip(votes.iter()) {
+                let Some(mut info) = Jurors::<T>::get(domain, juror) else { continue };
+                info.active_cases = info.active_cases.saturating_sub(1);
+                if vote.is_some() && *vote == ruling {
+                    coherent.push(juror.clone());
+                } else if vote.is_none() || ruling.is_some() {
+                    let amount = T::Fungible::transfer_on_hold(
+                        &T::RuntimeHoldReason::from(HoldReason::JurorStake),
+                        juror,
+                        &pot,
+                        slash_ratio.mul_floor(info.stake),
+                        frame_support::traits::tokens::Precision::BestEffort,
+                        frame_support::traits::tokens::Restriction::Free,
+                        frame_support::traits::tokens::Fortitude::Force,
+                    )
+                    .unwrap_or_else(|_| Zero::zero());
+                    if !amount.is_zero() {
+                        info.stake = info.stake.saturating_sub(amount);
+                        slashed = slashed.saturating_add(amount);
+                        Self::deposit_event(Event::JurorSlashed { domain, id, juror: juror.clone(), amount });
+                    }
+                }
+                Jurors::<T>::insert(domain, juror, info);
+            }
+
+            let total = slashed.saturating_add(core::mem::take(&mut case.reward_pool));
+            match ruling {
+                Some(ruling) => {
+                    let divisor: BalanceOf<T> = (coherent.len() as u32).max(1).into();
+                    let share = total / divisor;
+                    if !share.is_zero() {
+                        for juror in coherent {
+                            if T::Fungible::transfer(
+                                &pot,
+                                &juror,
+                                share,
+                                frame_support::traits::tokens::Preservation::Expendable,
+                            )
+                            .is_ok()
+                            {
+                                Self::deposit_event(Event::JurorRewarded { domain, id, juror, amount: share });
+                            }
+                        }
+                    }
+                    case.phase = JuryPhase::Appealable;
+                    case.ruling = Some(ruling);
+                    case.appeal_end = frame_system::Pallet::<T>::block_number()
+                        .saturating_add(T::AppealPeriod::get());
+                    Self::deposit_event(Event::JuryRuled {
+                        domain,
+                        id,
+                        round: case.round,
+                        ruling,
+                        appeal_end: case.appeal_end,
+                    });
+                },
+                None => {
+                    Self::pay_treasury_from_pot(total);
+                    case.phase = JuryPhase::Escalated;
+                    Self::deposit_event(Event::JuryEscalated { domain, id, round: case.round });
+                },
+            }
+        }
+
+        /// 函数级中文注释：解散陪审团案件（裁决执行时调用）
+        /// - 投票中的轮次：释放陪审员占用、清理投票，奖励池转入国库
+        /// - 待抽取的轮次：释放种子引用，奖励池转入国库
+        fn dissolve_jury(domain: [u8; 8], id: u64) {
+            let Some(case) = JuryCases::<T>::take(domain, id) else { return };
+            if case.phase == JuryPhase::Drawing {
+                Self::release_jury_seed(case.seed_block);
+                Self::pay_treasury_from_pot(case.reward_pool);
+            } else if case.phase == JuryPhase::Voting {
+                for juror in case.panel.iter() {
+                    Jurors::<T>::mutate(domain, juror, |info| {
+                        if let Some(info) = info {
+                            info.active_cases = info.active_cases.saturating_sub(1);
+                        }
+                    });
+                }
+                let _ = JuryVotes::<T>::clear_prefix((domain, id), T::MaxPanelSize::get(), None);
+                Self::pay_treasury_from_pot(case.reward_pool);
+            }
+        }
+
+        /// 函数级中文注释：资金池余额转入国库（尽力而为）
+        fn pay_treasury_from_pot(amount: BalanceOf<T>) {
+            if !amount.is_zero() {
+                let _ = T::Fungible::transfer(
+                    &Self::jury_pot_account(),
+                    &T::TreasuryAccount::get(),
+                    amount,
+                    frame_support::traits::tokens::Preservation::Expendable,
+                );
+            }
+        }
+
         // ============================================================================
         // 🆕 P2: CID 锁定管理辅助函数
         // ============================================================================
@@ -1643,12 +2542,13 @@ pub mod pallet {
         // ==================== 🆕 Phase 4: 投诉归档辅助函数 ====================
 
         /// 归档已解决的投诉
-        /// 在 on_idle 中调用，每次最多处理 max_count 个
-        pub fn archive_old_complaints(max_count: u32) -> u32 {
+        /// 在 on_idle 中调用，每次最多处理 max_count 个，返回（归档数量, 归档承诺权重）
+        pub fn archive_old_complaints(max_count: u32) -> (u32, Weight) {
             let now = frame_system::Pallet::<T>::block_number();
             // 归档延迟：30天 = 432000 区块（6秒/块）
             let archive_delay: BlockNumberFor<T> = 432000u32.into();
             let mut archived_count = 0u32;
+            let mut committed = alloc::vec::Vec::new();
             let mut cursor = ComplaintArchiveCursor::<T>::get();
             let max_id = NextComplaintId::<T>::get();
 
@@ -1659,6 +2559,8 @@ pub mod pallet {
                         && now.saturating_sub(complaint.updated_at) >= archive_delay;
 
                     if can_archive {
+                        committed.push((cursor, complaint.encode()));
+
                         // 创建归档记录
                         let decision = match complaint.status {
                             ComplaintStatus::ResolvedComplainantWin => 0,
@@ -1697,7 +2599,21 @@ pub mod pallet {
             }
 
             ComplaintArchiveCursor::<T>::put(cursor);
-            archived_count
+
+            // 完整投诉整批提交归档承诺（失败不影响归档）
+            let mut commit_weight = Weight::zero();
+            if !committed.is_empty() {
+                let bytes: usize = committed.iter().map(|(_, record)| record.len()).sum();
+                commit_weight = <T::ArchiveCommitter as pallet_storage_lifecycle::ArchiveCommitter>::commit_weight(
+                    committed.len() as u32,
+                    bytes.saturated_into(),
+                );
+                let _ = <T::ArchiveCommitter as pallet_storage_lifecycle::ArchiveCommitter>::commit_archive(
+                    COMPLAINT_ARCHIVE_DATA_TYPE,
+                    committed,
+                );
+            }
+            (archived_count, commit_weight)
         }
 
         /// 从用户投诉索引中移除
@@ -1744,6 +2660,19 @@ pub mod pallet {
 
     #[pallet::hooks]
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        /// 记录上一区块（若为陪审团种子区块）的哈希，供 `draw_jury` 使用
+        fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+            let seed_block = now.saturating_sub(One::one());
+            match JurySeeds::<T>::get(seed_block) {
+                Some(mut seed) if seed.hash.is_none() => {
+                    seed.hash = Some(frame_system::Pallet::<T>::block_hash(seed_block));
+                    JurySeeds::<T>::insert(seed_block, seed);
+                    T::DbWeight::get().reads_writes(2, 1)
+                },
+                _ => T::DbWeight::get().reads(1),
+            }
+        }
+
         fn on_idle(_now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
             let mut weight_used = Weight::zero();
             let base_weight = Weight::from_parts(10_000, 0);
@@ -1757,8 +2686,10 @@ pub mod pallet {
             // 阶段2：归档已解决投诉（每次最多10个）
             let remaining = remaining_weight.saturating_sub(weight_used);
             if remaining.ref_time() > base_weight.ref_time() * 10 {
-                let archived = Self::archive_old_complaints(10);
-                weight_used = weight_used.saturating_add(base_weight.saturating_mul(archived as u64));
+                let (archived, commit_weight) = Self::archive_old_complaints(10);
+                weight_used = weight_used
+                    .saturating_add(base_weight.saturating_mul(archived as u64))
+                    .saturating_add(commit_weight);
             }
 
             weight_used
@@ -0,0 +1,216 @@
+//! # Arbitration Pallet Mock Runtime
+//!
+//! 单元测试用 mock 环境（陪审团流程）。
+
+use crate as pallet_arbitration;
+use crate::pallet::{ArbitrationRouter, Decision};
+use frame_support::{
+    derive_impl, parameter_types,
+    traits::{ConstU16, ConstU32, ConstU64, ConstU128},
+    PalletId,
+};
+use frame_system::EnsureRoot;
+use sp_core::H256;
+use sp_runtime::{BuildStorage, DispatchError, DispatchResult};
+use std::cell::RefCell;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// 纠纷双方：Release → BUYER，Refund → SELLER（与 OTC 买单一致）
+pub const BUYER: u64 = 1;
+pub const SELLER: u64 = 2;
+pub const OUTSIDER: u64 = 3;
+pub const TREASURY: u64 = 99;
+
+/// 有订单记录的纠纷对象
+pub const ORDER_ID: u64 = 1;
+/// 无订单记录（Router 无法给出双方）
+pub const UNKNOWN_ORDER_ID: u64 = 2;
+
+pub const INITIAL_BALANCE: u128 = 10_000;
+
+frame_support::construct_runtime!(
+    pub enum Test {
+        System: frame_system,
+        Balances: pallet_balances,
+        Escrow: pallet_escrow,
+        Arbitration: pallet_arbitration,
+    }
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+    type Block = Block;
+    type AccountData = pallet_balances::AccountData<u128>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+    type AccountStore = System;
+    type Balance = u128;
+}
+
+// ==================== Escrow ====================
+
+pub struct NoExpiry;
+impl pallet_escrow::pallet::ExpiryPolicy<u64, u64> for NoExpiry {
+    fn on_expire(_id: u64) -> Result<pallet_escrow::pallet::ExpiryAction<u64>, DispatchError> {
+        Ok(pallet_escrow::pallet::ExpiryAction::Noop)
+    }
+    fn now() -> u64 {
+        System::block_number()
+    }
+}
+
+parameter_types! {
+    pub const EscrowPalletId: PalletId = PalletId(*b"py/escro");
+}
+
+impl pallet_escrow::pallet::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type Currency = Balances;
+    type EscrowPalletId = EscrowPalletId;
+    type AuthorizedOrigin = EnsureRoot<u64>;
+    type AdminOrigin = EnsureRoot<u64>;
+    type MaxExpiringPerBlock = ConstU32<10>;
+    type ExpiryPolicy = NoExpiry;
+}
+
+// ==================== Mock Router ====================
+
+thread_local! {
+    /// 已应用的裁决 (id, decision_code)：0=Release, 1=Refund, 2=Partial
+    pub static APPLIED_DECISIONS: RefCell<Vec<(u64, u8)>> = RefCell::new(Vec::new());
+}
+
+pub fn applied_decisions() -> Vec<(u64, u8)> {
+    APPLIED_DECISIONS.with(|d| d.borrow().clone())
+}
+
+pub struct MockRouter;
+impl ArbitrationRouter<u64, u128> for MockRouter {
+    fn can_dispute(_domain: [u8; 8], who: &u64, id: u64) -> bool {
+        id == ORDER_ID && (*who == BUYER || *who == SELLER)
+    }
+
+    fn apply_decision(_domain: [u8; 8], id: u64, decision: Decision) -> DispatchResult {
+        let code = match decision {
+            Decision::Release => 0,
+            Decision::Refund => 1,
+            Decision::Partial(_) => 2,
+        };
+        APPLIED_DECISIONS.with(|d| d.borrow_mut().push((id, code)));
+        Ok(())
+    }
+
+    fn get_counterparty(_domain: [u8; 8], initiator: &u64, _id: u64) -> Result<u64, DispatchError> {
+        Ok(if *initiator == BUYER { SELLER } else { BUYER })
+    }
+
+    fn get_order_amount(_domain: [u8; 8], _id: u64) -> Result<u128, DispatchError> {
+        Ok(1_000)
+    }
+
+    fn get_parties(_domain: [u8; 8], id: u64) -> Result<(u64, u64), DispatchError> {
+        if id == ORDER_ID {
+            Ok((BUYER, SELLER))
+        } else {
+            Err(DispatchError::Other("OrderNotFound"))
+        }
+    }
+}
+
+/// Mock CidLockManager: 所有操作成功
+pub struct MockCidLockManager;
+impl pallet_storage_service::CidLockManager<H256, u64> for MockCidLockManager {
+    fn lock_cid(_cid_hash: H256, _reason: Vec<u8>, _until: Option<u64>) -> DispatchResult { Ok(()) }
+    fn unlock_cid(_cid_hash: H256, _reason: Vec<u8>) -> DispatchResult { Ok(()) }
+    fn is_locked(_cid_hash: &H256) -> bool { false }
+}
+
+// ==================== Arbitration Config ====================
+
+parameter_types! {
+    pub const TreasuryAccountId: u64 = TREASURY;
+}
+
+/// 陪审员最低质押
+pub const MIN_JUROR_STAKE: u128 = 100;
+/// 首次上诉费
+pub const APPEAL_BASE_FEE: u128 = 50;
+/// 提交 / 揭示 / 上诉期
+pub const COMMIT_PERIOD: u64 = 10;
+pub const REVEAL_PERIOD: u64 = 10;
+pub const APPEAL_PERIOD: u64 = 10;
+/// 解绑期
+pub const UNBONDING_PERIOD: u64 = 20;
+/// 陪审团抽取种子延迟
+pub const JURY_SEED_DELAY: u64 = 2;
+
+impl pallet_arbitration::pallet::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type MaxEvidence = ConstU32<10>;
+    type MaxCidLen = ConstU32<64>;
+    type Escrow = Escrow;
+    type WeightInfo = ();
+    type Router = MockRouter;
+    type DecisionOrigin = EnsureRoot<u64>;
+    type Fungible = Balances;
+    type RuntimeHoldReason = RuntimeHoldReason;
+    type DepositRatioBps = ConstU16<1500>;
+    type ResponseDeadline = ConstU64<100>;
+    type RejectedSlashBps = ConstU16<3000>;
+    type PartialSlashBps = ConstU16<5000>;
+    type ComplaintDeposit = ConstU128<10>;
+    type ComplaintDepositUsd = ConstU64<1_000_000>;
+    type Pricing = ();
+    type ComplaintSlashBps = ConstU16<5000>;
+    type TreasuryAccount = TreasuryAccountId;
+    type CidLockManager = MockCidLockManager;
+    type CreditUpdater = ();
+    type ArchiveCommitter = ();
+    type JurySeedDelay = ConstU64<JURY_SEED_DELAY>;
+    type MinJurorStake = ConstU128<MIN_JUROR_STAKE>;
+    type MaxJurorsPerDomain = ConstU32<20>;
+    type InitialPanelSize = ConstU32<3>;
+    type MaxPanelSize = ConstU32<7>;
+    type CommitPeriod = ConstU64<COMMIT_PERIOD>;
+    type RevealPeriod = ConstU64<REVEAL_PERIOD>;
+    type AppealPeriod = ConstU64<APPEAL_PERIOD>;
+    type AppealBaseFee = ConstU128<APPEAL_BASE_FEE>;
+    type JurorSlashBps = ConstU16<1000>;
+    type JurorUnbondingPeriod = ConstU64<UNBONDING_PERIOD>;
+}
+
+// ==================== Test Helpers ====================
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut t = frame_system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .unwrap();
+
+    pallet_balances::GenesisConfig::<Test> {
+        balances: (1..=30u64).map(|who| (who, INITIAL_BALANCE)).collect(),
+        ..Default::default()
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+
+    let mut ext = sp_io::TestExternalities::new(t);
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        APPLIED_DECISIONS.with(|d| d.borrow_mut().clear());
+    });
+    ext
+}
+
+/// 逐块推进：记录父区块哈希并执行 `on_initialize`（记录陪审团种子）
+pub fn run_to_block(n: u64) {
+    use frame_support::traits::Hooks;
+    while System::block_number() < n {
+        let parent = System::block_number();
+        frame_system::BlockHash::<Test>::insert(parent, H256::from_low_u64_be(parent));
+        System::set_block_number(parent + 1);
+        Arbitration::on_initialize(parent + 1);
+    }
+}
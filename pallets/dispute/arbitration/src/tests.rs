@@ -0,0 +1,517 @@
+//! # Arbitration Pallet 单元测试（陪审团流程）
+
+use crate::{mock::*, pallet::*};
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::fungible::{Inspect, InspectHold},
+};
+use sp_core::H256;
+use sp_runtime::traits::{BlakeTwo256, Hash};
+
+const DOMAIN: [u8; 8] = domains::OTC_ORDER;
+
+fn join(who: u64, stake: u128) {
+    assert_ok!(Arbitration::join_juror_pool(RuntimeOrigin::signed(who), DOMAIN, stake));
+}
+
+fn open_dispute_with_jury() {
+    assert_ok!(Arbitration::dispute(RuntimeOrigin::signed(BUYER), DOMAIN, ORDER_ID, vec![]));
+    assert_ok!(Arbitration::request_jury(RuntimeOrigin::signed(SELLER), DOMAIN, ORDER_ID));
+    draw();
+}
+
+/// 推进到种子区块之后并抽取陪审团
+fn draw() {
+    run_to_block(case().seed_block + 1);
+    assert_ok!(Arbitration::draw_jury(RuntimeOrigin::signed(OUTSIDER), DOMAIN, ORDER_ID));
+}
+
+fn case() -> JuryCase<Test> {
+    JuryCases::<Test>::get(DOMAIN, ORDER_ID).expect("jury case exists")
+}
+
+fn salt(who: u64) -> [u8; 32] {
+    [who as u8; 32]
+}
+
+fn commitment(vote: u8, who: u64) -> H256 {
+    BlakeTwo256::hash_of(&(vote, salt(who), who))
+}
+
+fn held(who: u64) -> u128 {
+    Balances::balance_on_hold(&RuntimeHoldReason::from(HoldReason::JurorStake), &who)
+}
+
+fn juror(who: u64) -> JurorInfo<u128, u64> {
+    Jurors::<Test>::get(DOMAIN, who).expect("juror exists")
+}
+
+/// 本轮陪审员按 `votes` 提交并揭示（None = 不提交），然后结算本轮
+fn vote_and_finalize(votes: &[(u64, Option<u8>)]) {
+    let c = case();
+    for (who, vote) in votes {
+        if let Some(vote) = vote {
+            assert_ok!(Arbitration::commit_jury_vote(
+                RuntimeOrigin::signed(*who),
+                DOMAIN,
+                ORDER_ID,
+                commitment(*vote, *who),
+            ));
+        }
+    }
+    run_to_block(c.commit_end + 1);
+    for (who, vote) in votes {
+        if let Some(vote) = vote {
+            assert_ok!(Arbitration::reveal_jury_vote(
+                RuntimeOrigin::signed(*who),
+                DOMAIN,
+                ORDER_ID,
+                *vote,
+                salt(*who),
+            ));
+        }
+    }
+    run_to_block(c.reveal_end + 1);
+    assert_ok!(Arbitration::finalize_jury_round(RuntimeOrigin::signed(OUTSIDER), DOMAIN, ORDER_ID));
+}
+
+// ============================================================================
+// 抽取陪审团
+// ============================================================================
+
+#[test]
+fn request_jury_draws_panel_from_pool_excluding_parties() {
+    new_test_ext().execute_with(|| {
+        join(BUYER, 1_000);
+        for who in 10..=13 {
+            join(who, 200);
+        }
+        join(14, 200);
+        assert_ok!(Arbitration::leave_juror_pool(RuntimeOrigin::signed(14), DOMAIN));
+
+        open_dispute_with_jury();
+
+        let c = case();
+        assert_eq!(c.release_party, BUYER);
+        assert_eq!(c.refund_party, SELLER);
+        assert_eq!(c.round, 0);
+        assert_eq!(c.phase, JuryPhase::Voting);
+        assert_eq!(c.panel.len(), 3);
+        let mut panel = c.panel.to_vec();
+        panel.sort();
+        panel.dedup();
+        assert_eq!(panel.len(), 3);
+        for who in c.panel.iter() {
+            assert!((10..=13).contains(who), "当事人或解绑中的陪审员不应入选");
+            assert_eq!(juror(*who).active_cases, 1);
+        }
+    });
+}
+
+#[test]
+fn draw_jury_waits_for_seed_block() {
+    new_test_ext().execute_with(|| {
+        for who in 10..=12 {
+            join(who, 200);
+        }
+        assert_ok!(Arbitration::dispute(RuntimeOrigin::signed(BUYER), DOMAIN, ORDER_ID, vec![]));
+        assert_ok!(Arbitration::request_jury(RuntimeOrigin::signed(SELLER), DOMAIN, ORDER_ID));
+
+        let seed_block = 1 + JURY_SEED_DELAY;
+        let c = case();
+        assert_eq!(c.phase, JuryPhase::Drawing);
+        assert_eq!(c.seed_block, seed_block);
+        assert!(c.panel.is_empty());
+        System::assert_last_event(RuntimeEvent::Arbitration(Event::JuryRequested {
+            domain: DOMAIN,
+            id: ORDER_ID,
+            round: 0,
+            seed_block,
+        }));
+
+        // 种子区块产出前（含种子区块本身）不能抽取
+        for block in [1, seed_block] {
+            run_to_block(block);
+            assert_noop!(
+                Arbitration::draw_jury(RuntimeOrigin::signed(OUTSIDER), DOMAIN, ORDER_ID),
+                Error::<Test>::JurySeedNotReady
+            );
+        }
+
+        // 下一区块记录种子区块哈希后可抽取，种子引用随之释放
+        run_to_block(seed_block + 1);
+        assert_eq!(
+            JurySeeds::<Test>::get(seed_block).unwrap().hash,
+            Some(H256::from_low_u64_be(seed_block))
+        );
+        assert_ok!(Arbitration::draw_jury(RuntimeOrigin::signed(OUTSIDER), DOMAIN, ORDER_ID));
+        assert!(JurySeeds::<Test>::get(seed_block).is_none());
+        assert_eq!(case().phase, JuryPhase::Voting);
+        assert_noop!(
+            Arbitration::draw_jury(RuntimeOrigin::signed(OUTSIDER), DOMAIN, ORDER_ID),
+            Error::<Test>::InvalidJuryPhase
+        );
+    });
+}
+
+#[test]
+fn draw_jury_escalates_when_jurors_left_before_seed_block() {
+    new_test_ext().execute_with(|| {
+        for who in 10..=12 {
+            join(who, 200);
+        }
+        assert_ok!(Arbitration::dispute(RuntimeOrigin::signed(BUYER), DOMAIN, ORDER_ID, vec![]));
+        assert_ok!(Arbitration::request_jury(RuntimeOrigin::signed(SELLER), DOMAIN, ORDER_ID));
+        assert_ok!(Arbitration::leave_juror_pool(RuntimeOrigin::signed(10), DOMAIN));
+
+        draw();
+        let c = case();
+        assert_eq!(c.phase, JuryPhase::Escalated);
+        assert!(c.panel.is_empty());
+        for who in 11..=12 {
+            assert_eq!(juror(who).active_cases, 0);
+        }
+    });
+}
+
+#[test]
+fn request_jury_takes_parties_from_router() {
+    new_test_ext().execute_with(|| {
+        for who in 10..=12 {
+            join(who, 200);
+        }
+        assert_ok!(Arbitration::dispute(RuntimeOrigin::signed(SELLER), DOMAIN, ORDER_ID, vec![]));
+
+        assert_noop!(
+            Arbitration::request_jury(RuntimeOrigin::signed(OUTSIDER), DOMAIN, ORDER_ID),
+            Error::<Test>::NotDisputeParty
+        );
+
+        Disputed::<Test>::insert(DOMAIN, UNKNOWN_ORDER_ID, ());
+        assert_noop!(
+            Arbitration::request_jury(RuntimeOrigin::signed(BUYER), DOMAIN, UNKNOWN_ORDER_ID),
+            Error::<Test>::DisputePartiesNotFound
+        );
+
+        // 卖家先发起纠纷，双方仍按订单确定：Release → BUYER，Refund → SELLER
+        assert_ok!(Arbitration::request_jury(RuntimeOrigin::signed(SELLER), DOMAIN, ORDER_ID));
+        assert_eq!(case().release_party, BUYER);
+        assert_eq!(case().refund_party, SELLER);
+    });
+}
+
+#[test]
+fn request_jury_needs_enough_jurors() {
+    new_test_ext().execute_with(|| {
+        join(10, 200);
+        join(11, 200);
+        assert_ok!(Arbitration::dispute(RuntimeOrigin::signed(BUYER), DOMAIN, ORDER_ID, vec![]));
+        assert_noop!(
+            Arbitration::request_jury(RuntimeOrigin::signed(BUYER), DOMAIN, ORDER_ID),
+            Error::<Test>::NotEnoughJurors
+        );
+    });
+}
+
+// ============================================================================
+// 提交 / 揭示
+// ============================================================================
+
+#[test]
+fn commit_reveal_enforces_phases_and_commitment() {
+    new_test_ext().execute_with(|| {
+        for who in 10..=12 {
+            join(who, 200);
+        }
+        open_dispute_with_jury();
+        let c = case();
+        let juror_a = c.panel[0];
+
+        assert_noop!(
+            Arbitration::commit_jury_vote(RuntimeOrigin::signed(OUTSIDER), DOMAIN, ORDER_ID, commitment(0, OUTSIDER)),
+            Error::<Test>::NotPanelJuror
+        );
+        assert_ok!(Arbitration::commit_jury_vote(
+            RuntimeOrigin::signed(juror_a),
+            DOMAIN,
+            ORDER_ID,
+            commitment(1, juror_a),
+        ));
+        assert_noop!(
+            Arbitration::commit_jury_vote(RuntimeOrigin::signed(juror_a), DOMAIN, ORDER_ID, commitment(0, juror_a)),
+            Error::<Test>::AlreadyCommitted
+        );
+        assert_noop!(
+            Arbitration::reveal_jury_vote(RuntimeOrigin::signed(juror_a), DOMAIN, ORDER_ID, 1, salt(juror_a)),
+            Error::<Test>::NotInRevealPhase
+        );
+
+        run_to_block(c.commit_end + 1);
+        assert_noop!(
+            Arbitration::commit_jury_vote(RuntimeOrigin::signed(c.panel[1]), DOMAIN, ORDER_ID, commitment(0, c.panel[1])),
+            Error::<Test>::NotInCommitPhase
+        );
+        assert_noop!(
+            Arbitration::reveal_jury_vote(RuntimeOrigin::signed(c.panel[1]), DOMAIN, ORDER_ID, 0, salt(c.panel[1])),
+            Error::<Test>::NotCommitted
+        );
+        assert_noop!(
+            Arbitration::reveal_jury_vote(RuntimeOrigin::signed(juror_a), DOMAIN, ORDER_ID, 2, salt(juror_a)),
+            Error::<Test>::InvalidVote
+        );
+        // 揭示与承诺不符（票不同 / 盐不同）
+        assert_noop!(
+            Arbitration::reveal_jury_vote(RuntimeOrigin::signed(juror_a), DOMAIN, ORDER_ID, 0, salt(juror_a)),
+            Error::<Test>::CommitmentMismatch
+        );
+        assert_noop!(
+            Arbitration::reveal_jury_vote(RuntimeOrigin::signed(juror_a), DOMAIN, ORDER_ID, 1, [0u8; 32]),
+            Error::<Test>::CommitmentMismatch
+        );
+        assert_ok!(Arbitration::reveal_jury_vote(RuntimeOrigin::signed(juror_a), DOMAIN, ORDER_ID, 1, salt(juror_a)));
+        assert_noop!(
+            Arbitration::reveal_jury_vote(RuntimeOrigin::signed(juror_a), DOMAIN, ORDER_ID, 1, salt(juror_a)),
+            Error::<Test>::AlreadyRevealed
+        );
+
+        assert_noop!(
+            Arbitration::finalize_jury_round(RuntimeOrigin::signed(OUTSIDER), DOMAIN, ORDER_ID),
+            Error::<Test>::RevealNotOver
+        );
+    });
+}
+
+// ============================================================================
+// 结算：按质押比例罚没，罚没所得分给一致者
+// ============================================================================
+
+#[test]
+fn settle_round_slashes_incoherent_vote_by_stake_and_rewards_coherent() {
+    new_test_ext().execute_with(|| {
+        join(10, 200);
+        join(11, 100);
+        join(12, 300);
+        open_dispute_with_jury();
+
+        vote_and_finalize(&[(10, Some(0)), (11, Some(0)), (12, Some(1))]);
+
+        let c = case();
+        assert_eq!(c.phase, JuryPhase::Appealable);
+        assert_eq!(c.ruling, Some(0));
+        // 12 与裁决不一致，罚没 10% × 300 = 30，由 10 / 11 平分
+        assert_eq!(juror(12).stake, 270);
+        assert_eq!(held(12), 270);
+        assert_eq!(Balances::balance(&10), INITIAL_BALANCE - 200 + 15);
+        assert_eq!(Balances::balance(&11), INITIAL_BALANCE - 100 + 15);
+        assert_eq!(held(10), 200);
+        assert_eq!(held(11), 100);
+        for who in 10..=12 {
+            assert_eq!(juror(who).active_cases, 0);
+        }
+    });
+}
+
+#[test]
+fn settle_round_slashes_no_show() {
+    new_test_ext().execute_with(|| {
+        join(10, 200);
+        join(11, 100);
+        join(12, 500);
+        open_dispute_with_jury();
+
+        // 12 未提交也未揭示
+        vote_and_finalize(&[(10, Some(1)), (11, Some(1)), (12, None)]);
+
+        assert_eq!(case().ruling, Some(1));
+        assert_eq!(juror(12).stake, 450);
+        assert_eq!(Balances::balance(&10), INITIAL_BALANCE - 200 + 25);
+        assert_eq!(Balances::balance(&11), INITIAL_BALANCE - 100 + 25);
+    });
+}
+
+#[test]
+fn tie_escalates_and_slashes_only_no_show_to_treasury() {
+    new_test_ext().execute_with(|| {
+        join(10, 200);
+        join(11, 200);
+        join(12, 300);
+        open_dispute_with_jury();
+
+        vote_and_finalize(&[(10, Some(0)), (11, Some(1)), (12, None)]);
+
+        let c = case();
+        assert_eq!(c.phase, JuryPhase::Escalated);
+        assert_eq!(c.ruling, None);
+        assert_eq!(juror(10).stake, 200);
+        assert_eq!(juror(11).stake, 200);
+        assert_eq!(juror(12).stake, 270);
+        assert_eq!(Balances::balance(&TREASURY), 30);
+        assert_noop!(
+            Arbitration::execute_jury_ruling(RuntimeOrigin::signed(OUTSIDER), DOMAIN, ORDER_ID),
+            Error::<Test>::InvalidJuryPhase
+        );
+    });
+}
+
+// ============================================================================
+// 上诉
+// ============================================================================
+
+#[test]
+fn appeal_expands_panel_then_escalates_beyond_max_size() {
+    new_test_ext().execute_with(|| {
+        for who in 10..=16 {
+            join(who, 200);
+        }
+        open_dispute_with_jury();
+        let first: Vec<(u64, Option<u8>)> = case().panel.iter().map(|who| (*who, Some(0))).collect();
+        vote_and_finalize(&first);
+        assert_eq!(case().ruling, Some(0));
+
+        // Release 裁决：只有 Refund 受益方（SELLER）可以上诉
+        assert_noop!(
+            Arbitration::appeal_jury(RuntimeOrigin::signed(BUYER), DOMAIN, ORDER_ID),
+            Error::<Test>::NotLosingParty
+        );
+        assert_ok!(Arbitration::appeal_jury(RuntimeOrigin::signed(SELLER), DOMAIN, ORDER_ID));
+
+        // 上诉锁定新的种子区块，产出后抽取 7 人
+        let c = case();
+        assert_eq!(c.round, 1);
+        assert_eq!(c.phase, JuryPhase::Drawing);
+        assert_eq!(c.seed_block, System::block_number() + JURY_SEED_DELAY);
+        assert_eq!(c.reward_pool, APPEAL_BASE_FEE);
+        assert_eq!(Balances::balance(&SELLER), INITIAL_BALANCE - APPEAL_BASE_FEE);
+
+        draw();
+        let c = case();
+        assert_eq!(c.phase, JuryPhase::Voting);
+        assert_eq!(c.panel.len(), 7);
+
+        // 第二轮全体改判 Refund，上诉费 50 由 7 人平分（每人 7）
+        let second: Vec<(u64, Option<u8>)> = c.panel.iter().map(|who| (*who, Some(1))).collect();
+        vote_and_finalize(&second);
+        assert_eq!(case().ruling, Some(1));
+        for who in 10..=16 {
+            assert_eq!(Balances::balance(&who), INITIAL_BALANCE - 200 + 7);
+            assert_eq!(juror(who).active_cases, 0);
+        }
+
+        // 再上诉人数将为 15 > MaxPanelSize，交由仲裁委员会，上诉费翻倍并转入国库
+        assert_ok!(Arbitration::appeal_jury(RuntimeOrigin::signed(BUYER), DOMAIN, ORDER_ID));
+        let c = case();
+        assert_eq!(c.phase, JuryPhase::Escalated);
+        assert_eq!(c.round, 2);
+        assert_eq!(Balances::balance(&BUYER), INITIAL_BALANCE - 2 * APPEAL_BASE_FEE);
+        assert_eq!(Balances::balance(&TREASURY), 2 * APPEAL_BASE_FEE);
+    });
+}
+
+#[test]
+fn execute_jury_ruling_after_appeal_period() {
+    new_test_ext().execute_with(|| {
+        for who in 10..=12 {
+            join(who, 200);
+        }
+        open_dispute_with_jury();
+        vote_and_finalize(&[(10, Some(1)), (11, Some(1)), (12, Some(1))]);
+
+        assert_noop!(
+            Arbitration::execute_jury_ruling(RuntimeOrigin::signed(OUTSIDER), DOMAIN, ORDER_ID),
+            Error::<Test>::AppealPeriodNotOver
+        );
+        run_to_block(case().appeal_end + 1);
+        assert_noop!(
+            Arbitration::appeal_jury(RuntimeOrigin::signed(BUYER), DOMAIN, ORDER_ID),
+            Error::<Test>::AppealPeriodOver
+        );
+        assert_ok!(Arbitration::execute_jury_ruling(RuntimeOrigin::signed(OUTSIDER), DOMAIN, ORDER_ID));
+
+        assert_eq!(applied_decisions(), vec![(ORDER_ID, 1)]);
+        assert!(JuryCases::<Test>::get(DOMAIN, ORDER_ID).is_none());
+        assert!(Disputed::<Test>::get(DOMAIN, ORDER_ID).is_none());
+    });
+}
+
+// ============================================================================
+// 退出 / 取回质押
+// ============================================================================
+
+#[test]
+fn leave_blocked_while_round_pending_then_withdraw_after_unbonding() {
+    new_test_ext().execute_with(|| {
+        for who in 10..=12 {
+            join(who, 200);
+        }
+        open_dispute_with_jury();
+
+        assert_noop!(
+            Arbitration::leave_juror_pool(RuntimeOrigin::signed(10), DOMAIN),
+            Error::<Test>::JurorHasActiveCases
+        );
+
+        vote_and_finalize(&[(10, Some(0)), (11, Some(0)), (12, Some(0))]);
+
+        assert_ok!(Arbitration::leave_juror_pool(RuntimeOrigin::signed(10), DOMAIN));
+        assert!(!JurorPools::<Test>::get(DOMAIN).contains(&10));
+        assert_noop!(
+            Arbitration::join_juror_pool(RuntimeOrigin::signed(10), DOMAIN, 100),
+            Error::<Test>::JurorUnbonding
+        );
+        assert_noop!(
+            Arbitration::withdraw_juror_stake(RuntimeOrigin::signed(10), DOMAIN),
+            Error::<Test>::JurorNotUnbonded
+        );
+
+        run_to_block(juror(10).unbonding_at.unwrap());
+        assert_ok!(Arbitration::withdraw_juror_stake(RuntimeOrigin::signed(10), DOMAIN));
+        assert_eq!(held(10), 0);
+        assert_eq!(Balances::balance(&10), INITIAL_BALANCE);
+        assert!(Jurors::<Test>::get(DOMAIN, 10).is_none());
+    });
+}
+
+#[test]
+fn committee_arbitrate_dissolves_voting_jury() {
+    new_test_ext().execute_with(|| {
+        for who in 10..=12 {
+            join(who, 200);
+        }
+        open_dispute_with_jury();
+        assert_ok!(Arbitration::commit_jury_vote(
+            RuntimeOrigin::signed(10),
+            DOMAIN,
+            ORDER_ID,
+            commitment(0, 10),
+        ));
+
+        assert_ok!(Arbitration::arbitrate(RuntimeOrigin::root(), DOMAIN, ORDER_ID, 0, None));
+
+        assert!(JuryCases::<Test>::get(DOMAIN, ORDER_ID).is_none());
+        assert!(JuryVotes::<Test>::get((DOMAIN, ORDER_ID), 10).is_none());
+        for who in 10..=12 {
+            assert_eq!(juror(who).active_cases, 0);
+            assert_ok!(Arbitration::leave_juror_pool(RuntimeOrigin::signed(who), DOMAIN));
+        }
+        assert_eq!(applied_decisions(), vec![(ORDER_ID, 0)]);
+    });
+}
+
+#[test]
+fn committee_arbitrate_dissolves_jury_awaiting_draw() {
+    new_test_ext().execute_with(|| {
+        for who in 10..=12 {
+            join(who, 200);
+        }
+        assert_ok!(Arbitration::dispute(RuntimeOrigin::signed(BUYER), DOMAIN, ORDER_ID, vec![]));
+        assert_ok!(Arbitration::request_jury(RuntimeOrigin::signed(SELLER), DOMAIN, ORDER_ID));
+        let seed_block = case().seed_block;
+        assert_eq!(JurySeeds::<Test>::get(seed_block).unwrap().pending, 1);
+
+        assert_ok!(Arbitration::arbitrate(RuntimeOrigin::root(), DOMAIN, ORDER_ID, 1, None));
+
+        assert!(JuryCases::<Test>::get(DOMAIN, ORDER_ID).is_none());
+        assert!(JurySeeds::<Test>::get(seed_block).is_none());
+        assert_eq!(applied_decisions(), vec![(ORDER_ID, 1)]);
+    });
+}
@@ -7,6 +7,14 @@ pub trait WeightInfo {
     fn dispute(evidence_count: u32) -> Weight;
     /// arbitrate：常量开销
     fn arbitrate() -> Weight;
+    /// join/leave/withdraw 陪审员池：常量开销
+    fn juror_pool() -> Weight;
+    /// 抽取陪审团：与陪审员池大小近似线性相关
+    fn draw_jury(pool_size: u32) -> Weight;
+    /// 提交/揭示投票：常量开销
+    fn jury_vote() -> Weight;
+    /// 结算本轮：与陪审团人数近似线性相关
+    fn finalize_jury_round(panel_size: u32) -> Weight;
 }
 
 impl WeightInfo for () {
@@ -16,6 +24,18 @@ impl WeightInfo for () {
     fn arbitrate() -> Weight {
         Weight::from_parts(10_000, 0)
     }
+    fn juror_pool() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+    fn draw_jury(pool_size: u32) -> Weight {
+        Weight::from_parts(10_000, 0).saturating_add(Weight::from_parts(1_000, 0).saturating_mul(pool_size as u64))
+    }
+    fn jury_vote() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+    fn finalize_jury_round(panel_size: u32) -> Weight {
+        Weight::from_parts(10_000, 0).saturating_add(Weight::from_parts(5_000, 0).saturating_mul(panel_size as u64))
+    }
 }
 
 pub struct SubstrateWeight<T>(PhantomData<T>);
@@ -26,4 +46,16 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
     fn arbitrate() -> Weight {
         Weight::from_parts(10_000, 0)
     }
+    fn juror_pool() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+    fn draw_jury(pool_size: u32) -> Weight {
+        Weight::from_parts(10_000, 0).saturating_add(Weight::from_parts(1_000, 0).saturating_mul(pool_size as u64))
+    }
+    fn jury_vote() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+    fn finalize_jury_round(panel_size: u32) -> Weight {
+        Weight::from_parts(10_000, 0).saturating_add(Weight::from_parts(5_000, 0).saturating_mul(panel_size as u64))
+    }
 }
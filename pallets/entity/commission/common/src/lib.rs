@@ -49,13 +49,31 @@ impl CommissionModes {
 // 返佣来源
 // ============================================================================
 
-/// 返佣来源（预留，当前版本返佣统一从 Shop 运营账户出）
+/// 返佣来源（佣金资金由谁出资）
 #[derive(Encode, Decode, codec::DecodeWithMemTracking, Clone, Copy, PartialEq, Eq, TypeInfo, MaxEncodedLen, RuntimeDebug, Default)]
 pub enum CommissionSource {
-    #[default]
+    /// 平台费出资（单笔不超过该订单在 Transaction 模块收取的平台费）
     PlatformFee,
+    /// Shop 运营资金出资
+    #[default]
     ShopFund,
-    Mixed,
+    /// 混合出资：platform_share 基点由平台费出，其余由 Shop 运营资金出
+    Mixed { platform_share: u16 },
+}
+
+impl CommissionSource {
+    /// 平台费出资比例（基点）
+    pub fn platform_share(&self) -> u16 {
+        match self {
+            CommissionSource::PlatformFee => 10000,
+            CommissionSource::ShopFund => 0,
+            CommissionSource::Mixed { platform_share } => *platform_share,
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.platform_share() <= 10000
+    }
 }
 
 // ============================================================================
@@ -221,6 +239,61 @@ impl<AccountId, Balance> CommissionPlugin<AccountId, Balance> for () {
     }
 }
 
+// ============================================================================
+// Team Performance Traits（周期结算）
+// ============================================================================
+
+/// 团队业绩插件接口
+///
+/// 团队奖金按周期总业绩结算，不在单笔订单中直接发放：
+/// 订单完成时累计业绩并从本单预算中预留团队奖金池，订单取消/退款时回退业绩，
+/// 周期结束后由插件按总业绩计算奖金，经 [`TeamBonusPayer`] 从奖金池入账。
+///
+/// 累计/回退沿推荐链逐层读写，开销随层数增长，两者均返回实际消耗的权重，由调用方计入。
+pub trait TeamPerformancePlugin<AccountId, Balance> {
+    /// 订单完成：累计本周期团队业绩，返回本单需预留的团队奖金额度（不超过 `remaining`）与消耗的权重
+    fn record_order(
+        entity_id: u64,
+        shop_id: u64,
+        order_id: u64,
+        buyer: &AccountId,
+        order_amount: Balance,
+        remaining: Balance,
+        enabled_modes: CommissionModes,
+    ) -> (Balance, Weight);
+
+    /// 订单取消/退款：回退该订单累计的团队业绩，返回消耗的权重
+    fn revert_order(order_id: u64) -> Weight;
+}
+
+/// 空团队业绩插件实现（不累计、不预留）
+impl<AccountId, Balance: Default> TeamPerformancePlugin<AccountId, Balance> for () {
+    fn record_order(_: u64, _: u64, _: u64, _: &AccountId, _: Balance, _: Balance, _: CommissionModes) -> (Balance, Weight) {
+        (Balance::default(), Weight::zero())
+    }
+    fn revert_order(_: u64) -> Weight {
+        Weight::zero()
+    }
+}
+
+/// 团队奖金入账接口（由 commission-core 实现）
+pub trait TeamBonusPayer<AccountId, Balance> {
+    /// 从 Entity 团队奖金池向成员入账周期团队奖金，返回实际入账额（奖金池不足时按余额入账）
+    fn pay_team_bonus(
+        entity_id: u64,
+        beneficiary: &AccountId,
+        amount: Balance,
+        period: u32,
+    ) -> Result<Balance, DispatchError>;
+}
+
+/// 空 TeamBonusPayer 实现（不入账）
+impl<AccountId, Balance: Default> TeamBonusPayer<AccountId, Balance> for () {
+    fn pay_team_bonus(_: u64, _: &AccountId, _: Balance, _: u32) -> Result<Balance, DispatchError> {
+        Ok(Balance::default())
+    }
+}
+
 // ============================================================================
 // CommissionProvider Trait（供外部模块调用）
 // ============================================================================
@@ -233,6 +306,7 @@ pub trait CommissionProvider<AccountId, Balance> {
         buyer: &AccountId,
         order_amount: Balance,
         available_pool: Balance,
+        platform_fee: Balance,
     ) -> Result<(), DispatchError>;
 
     fn cancel_commission(order_id: u64) -> Result<(), DispatchError>;
@@ -282,7 +356,7 @@ pub trait CommissionProvider<AccountId, Balance> {
 pub struct NullCommissionProvider;
 
 impl<AccountId, Balance: Default> CommissionProvider<AccountId, Balance> for NullCommissionProvider {
-    fn process_commission(_: u64, _: u64, _: &AccountId, _: Balance, _: Balance) -> Result<(), DispatchError> { Ok(()) }
+    fn process_commission(_: u64, _: u64, _: &AccountId, _: Balance, _: Balance, _: Balance) -> Result<(), DispatchError> { Ok(()) }
     fn cancel_commission(_: u64) -> Result<(), DispatchError> { Ok(()) }
     fn pending_commission(_: u64, _: &AccountId) -> Balance { Balance::default() }
     fn set_commission_modes(_: u64, _: u16) -> Result<(), DispatchError> { Ok(()) }
@@ -317,6 +391,12 @@ pub trait MemberProvider<AccountId> {
     fn update_custom_level(shop_id: u64, level_id: u8, name: Option<&[u8]>, threshold: Option<u128>, discount_rate: Option<u16>, commission_bonus: Option<u16>) -> Result<(), DispatchError>;
     fn remove_custom_level(shop_id: u64, level_id: u8) -> Result<(), DispatchError>;
     fn custom_level_count(shop_id: u64) -> u8;
+
+    /// 删除升级规则（治理调用）
+    fn remove_upgrade_rule(shop_id: u64, rule_id: u32) -> Result<(), DispatchError> {
+        let _ = (shop_id, rule_id);
+        Err(DispatchError::Other("not implemented"))
+    }
 }
 
 /// 空 MemberProvider 实现
@@ -336,6 +416,7 @@ impl<AccountId> MemberProvider<AccountId> for NullMemberProvider {
     fn update_custom_level(_: u64, _: u8, _: Option<&[u8]>, _: Option<u128>, _: Option<u16>, _: Option<u16>) -> Result<(), DispatchError> { Ok(()) }
     fn remove_custom_level(_: u64, _: u8) -> Result<(), DispatchError> { Ok(()) }
     fn custom_level_count(_: u64) -> u8 { 0 }
+    fn remove_upgrade_rule(_: u64, _: u32) -> Result<(), DispatchError> { Ok(()) }
 }
 
 // ============================================================================
@@ -359,10 +440,31 @@ pub enum CommissionPlan {
         platinum: u16,
         diamond: u16,
     },
+    /// 团队业绩（按结算周期累计下线业绩，达档位后逐级极差发放）
+    Team {
+        /// 档位（门槛升序、比例升序，单位基点）
+        tiers: BoundedVec<TeamTier, ConstU32<MAX_TEAM_PLAN_TIERS>>,
+        /// 结算周期（区块数）
+        period_blocks: u32,
+        /// 业绩向上累计的最大层数
+        max_depth: u8,
+        /// 每个成员每周期奖金上限（0 = 不封顶）
+        period_cap: u128,
+    },
     /// 自定义（仅启用佣金开关，参数后续手动配置）
     Custom,
 }
 
+/// 方案模板允许的最大团队档位数
+pub const MAX_TEAM_PLAN_TIERS: u32 = 5;
+
+/// 团队业绩档位：本周期团队业绩达到 threshold 时，按 rate 基点计奖
+#[derive(Encode, Decode, codec::DecodeWithMemTracking, Clone, Copy, PartialEq, Eq, TypeInfo, MaxEncodedLen, RuntimeDebug, Default)]
+pub struct TeamTier {
+    pub threshold: u128,
+    pub rate: u16,
+}
+
 // ============================================================================
 // PlanWriter Traits — 插件写入接口
 // ============================================================================
@@ -397,6 +499,8 @@ impl<Balance> ReferralPlanWriter<Balance> for () {
 pub trait LevelDiffPlanWriter {
     /// 设置全局 5 级极差比例
     fn set_global_rates(shop_id: u64, normal: u16, silver: u16, gold: u16, platinum: u16, diamond: u16) -> Result<(), DispatchError>;
+    /// 设置自定义等级极差比例（按自定义等级顺序）
+    fn set_custom_rates(shop_id: u64, level_rates: Vec<u16>, max_depth: u8) -> Result<(), DispatchError>;
     /// 清除等级极差配置
     fn clear_config(shop_id: u64) -> Result<(), DispatchError>;
 }
@@ -404,5 +508,45 @@ pub trait LevelDiffPlanWriter {
 /// 空 LevelDiffPlanWriter 实现
 impl LevelDiffPlanWriter for () {
     fn set_global_rates(_: u64, _: u16, _: u16, _: u16, _: u16, _: u16) -> Result<(), DispatchError> { Ok(()) }
+    fn set_custom_rates(_: u64, _: Vec<u16>, _: u8) -> Result<(), DispatchError> { Ok(()) }
+    fn clear_config(_: u64) -> Result<(), DispatchError> { Ok(()) }
+}
+
+/// 单线收益插件写入接口（由 commission-single-line 实现）
+pub trait SingleLinePlanWriter {
+    /// 设置单线收益比例与层数（保留原 level_increment_threshold）
+    fn set_single_line_config(
+        shop_id: u64,
+        upline_rate: u16,
+        downline_rate: u16,
+        base_upline_levels: u8,
+        base_downline_levels: u8,
+        max_upline_levels: u8,
+        max_downline_levels: u8,
+    ) -> Result<(), DispatchError>;
+}
+
+/// 空 SingleLinePlanWriter 实现
+impl SingleLinePlanWriter for () {
+    fn set_single_line_config(_: u64, _: u16, _: u16, _: u8, _: u8, _: u8, _: u8) -> Result<(), DispatchError> { Ok(()) }
+}
+
+/// 团队业绩插件写入接口（由 commission-team 实现）
+pub trait TeamPlanWriter {
+    /// 设置团队业绩档位、结算周期、累计层数与周期封顶
+    fn set_team_config(
+        entity_id: u64,
+        tiers: Vec<TeamTier>,
+        period_blocks: u32,
+        max_depth: u8,
+        period_cap: u128,
+    ) -> Result<(), DispatchError>;
+    /// 清除团队业绩配置
+    fn clear_config(entity_id: u64) -> Result<(), DispatchError>;
+}
+
+/// 空 TeamPlanWriter 实现
+impl TeamPlanWriter for () {
+    fn set_team_config(_: u64, _: Vec<TeamTier>, _: u32, _: u8, _: u128) -> Result<(), DispatchError> { Ok(()) }
     fn clear_config(_: u64) -> Result<(), DispatchError> { Ok(()) }
 }
@@ -16,10 +16,19 @@ pub use pallet_commission_common::{
     CommissionModes, CommissionOutput, CommissionPlugin, CommissionPlan, CommissionProvider,
     CommissionRecord, CommissionSource, CommissionStatus, CommissionType,
     LevelDiffPlanWriter, MemberCommissionStatsData, MemberProvider,
-    ReferralPlanWriter, WithdrawalMode, WithdrawalTierConfig,
+    ReferralPlanWriter, TeamBonusPayer, TeamPerformancePlugin, TeamPlanWriter, WithdrawalMode,
+    WithdrawalTierConfig,
 };
 use pallet_entity_common::ShopProvider as ShopProviderT;
 
+pub mod migrations;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
@@ -28,9 +37,12 @@ pub mod pallet {
         traits::{Currency, ExistenceRequirement, Get},
     };
     use frame_system::pallet_prelude::*;
-    use pallet_entity_common::{EntityProvider, ShopProvider};
-    use pallet_commission_common::{CommissionPlan, ReferralPlanWriter, LevelDiffPlanWriter};
-    use sp_runtime::traits::{Saturating, Zero};
+    use pallet_entity_common::{ensure_admin_caller, AdminCaller, EntityProvider, ShopProvider};
+    use pallet_commission_common::{CommissionPlan, ReferralPlanWriter, LevelDiffPlanWriter, TeamPlanWriter};
+    use sp_runtime::{
+        traits::{Saturating, Zero},
+        Perbill, SaturatedConversion,
+    };
 
     pub type BalanceOf<T> =
         <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
@@ -48,7 +60,7 @@ pub mod pallet {
     pub struct CoreCommissionConfig {
         /// 启用的返佣模式（位标志）
         pub enabled_modes: CommissionModes,
-        /// 返佣来源（预留）
+        /// 返佣来源（决定佣金资金由平台费 / Shop 运营资金出资）
         pub source: CommissionSource,
         /// 返佣上限比例（基点，10000 = 100%）
         pub max_commission_rate: u16,
@@ -132,9 +144,15 @@ pub mod pallet {
         /// Entity 查询接口
         type EntityProvider: EntityProvider<Self::AccountId>;
 
+        /// 实体治理来源（`EntityOrigin::Governance`，成功返回 entity_id）
+        type EntityGovernanceOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = u64>;
+
         /// 会员查询接口
         type MemberProvider: MemberProvider<Self::AccountId>;
 
+        /// 平台账户（PlatformFee / Mixed 来源的出资账户，即 Transaction 平台费收款账户）
+        type PlatformAccount: Get<Self::AccountId>;
+
         /// 推荐链返佣插件
         type ReferralPlugin: CommissionPlugin<Self::AccountId, BalanceOf<Self>>;
 
@@ -144,8 +162,8 @@ pub mod pallet {
         /// 单线收益插件
         type SingleLinePlugin: CommissionPlugin<Self::AccountId, BalanceOf<Self>>;
 
-        /// 团队业绩插件（预留）
-        type TeamPlugin: CommissionPlugin<Self::AccountId, BalanceOf<Self>>;
+        /// 团队业绩插件（周期结算：本单只累计业绩并预留奖金池）
+        type TeamPlugin: TeamPerformancePlugin<Self::AccountId, BalanceOf<Self>>;
 
         /// 推荐链方案写入器
         type ReferralWriter: ReferralPlanWriter<BalanceOf<Self>>;
@@ -153,6 +171,9 @@ pub mod pallet {
         /// 等级极差方案写入器
         type LevelDiffWriter: LevelDiffPlanWriter;
 
+        /// 团队业绩方案写入器
+        type TeamWriter: TeamPlanWriter;
+
         /// 最大返佣记录数（每订单）
         #[pallet::constant]
         type MaxCommissionRecordsPerOrder: Get<u32>;
@@ -162,7 +183,11 @@ pub mod pallet {
         type MaxCustomLevels: Get<u32>;
     }
 
+    /// v1: `CommissionSource` 由预留单元枚举改为真实出资来源（见 `migrations::v1`）
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     // ========================================================================
@@ -259,6 +284,33 @@ pub mod pallet {
         ValueQuery,
     >;
 
+    /// 订单佣金出资明细 order_id -> (平台费出资, Shop 出资)，取消时按来源比例退还
+    #[pallet::storage]
+    pub type OrderCommissionFunding<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat, u64,
+        (BalanceOf<T>, BalanceOf<T>),
+        ValueQuery,
+    >;
+
+    /// 团队奖金池 entity_id -> Balance（订单预留、资金已在 Entity 账户，周期结算时入账给成员）
+    #[pallet::storage]
+    #[pallet::getter(fn team_bonus_pool)]
+    pub type TeamBonusPool<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat, u64,
+        BalanceOf<T>,
+        ValueQuery,
+    >;
+
+    /// 订单团队奖金预留 order_id -> (entity_id, shop_id, 预留额)，取消时从奖金池退还
+    #[pallet::storage]
+    pub type OrderTeamReserve<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat, u64,
+        (u64, u64, BalanceOf<T>),
+    >;
+
     /// 全局最低复购比例 entity_id -> u16（万分比，由 Governance 设定）
     /// 提现时实际复购比例 = max(entity 分层配置, 此底线)
     #[pallet::storage]
@@ -309,12 +361,38 @@ pub mod pallet {
             account: T::AccountId,
             amount: BalanceOf<T>,
         },
-        /// 佣金资金从 Shop 转入 Entity 账户
+        /// 佣金资金按来源转入 Entity 账户
         CommissionFundsTransferred {
             entity_id: u64,
             shop_id: u64,
+            order_id: u64,
+            source: CommissionSource,
+            from_platform: BalanceOf<T>,
+            from_shop: BalanceOf<T>,
+        },
+        /// 订单预留团队奖金（进入 Entity 团队奖金池）
+        TeamBonusReserved {
+            entity_id: u64,
+            order_id: u64,
             amount: BalanceOf<T>,
         },
+        /// 周期团队奖金从奖金池入账
+        TeamBonusCredited {
+            entity_id: u64,
+            beneficiary: T::AccountId,
+            period: u32,
+            amount: BalanceOf<T>,
+        },
+        /// 返佣来源资金不足，本单不发放返佣（订单本身照常完成）
+        CommissionFundingInsufficient {
+            entity_id: u64,
+            shop_id: u64,
+            order_id: u64,
+            source: CommissionSource,
+            required: BalanceOf<T>,
+            platform_available: BalanceOf<T>,
+            shop_available: BalanceOf<T>,
+        },
     }
 
     // ========================================================================
@@ -341,6 +419,8 @@ pub mod pallet {
         NotDirectReferral,
         /// 自动注册会员失败
         AutoRegisterFailed,
+        /// 混合来源的平台费比例超过 10000
+        InvalidCommissionSource,
     }
 
     // ========================================================================
@@ -357,8 +437,7 @@ pub mod pallet {
             shop_id: u64,
             modes: CommissionModes,
         ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
-            let entity_id = Self::ensure_entity_owner_via_shop(shop_id, &who)?;
+            let entity_id = Self::ensure_entity_admin_via_shop(origin, shop_id)?;
 
             CommissionConfigs::<T>::mutate(entity_id, |maybe| {
                 let config = maybe.get_or_insert_with(CoreCommissionConfig::default);
@@ -378,9 +457,9 @@ pub mod pallet {
             source: CommissionSource,
             max_rate: u16,
         ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
-            let entity_id = Self::ensure_entity_owner_via_shop(shop_id, &who)?;
+            let entity_id = Self::ensure_entity_admin_via_shop(origin, shop_id)?;
             ensure!(max_rate <= 10000, Error::<T>::InvalidCommissionRate);
+            ensure!(source.is_valid(), Error::<T>::InvalidCommissionSource);
 
             CommissionConfigs::<T>::mutate(entity_id, |maybe| {
                 let config = maybe.get_or_insert_with(CoreCommissionConfig::default);
@@ -400,8 +479,7 @@ pub mod pallet {
             shop_id: u64,
             enabled: bool,
         ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
-            let entity_id = Self::ensure_entity_owner_via_shop(shop_id, &who)?;
+            let entity_id = Self::ensure_entity_admin_via_shop(origin, shop_id)?;
 
             CommissionConfigs::<T>::mutate(entity_id, |maybe| {
                 let config = maybe.get_or_insert_with(CoreCommissionConfig::default);
@@ -476,7 +554,9 @@ pub mod pallet {
                     let remaining_pending = ShopPendingTotal::<T>::get(entity_id)
                         .saturating_sub(total_amount);
                     let shopping_total = ShopShoppingTotal::<T>::get(entity_id);
-                    let required_reserve = remaining_pending.saturating_add(shopping_total);
+                    let required_reserve = remaining_pending
+                        .saturating_add(shopping_total)
+                        .saturating_add(TeamBonusPool::<T>::get(entity_id));
                     ensure!(
                         entity_balance >= split.withdrawal.saturating_add(required_reserve),
                         Error::<T>::InsufficientCommission
@@ -537,8 +617,7 @@ pub mod pallet {
             enabled: bool,
             shopping_balance_generates_commission: bool,
         ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
-            let entity_id = Self::ensure_entity_owner_via_shop(shop_id, &who)?;
+            let entity_id = Self::ensure_entity_admin_via_shop(origin, shop_id)?;
 
             // 校验模式参数
             match &mode {
@@ -587,18 +666,23 @@ pub mod pallet {
             shop_id: u64,
             plan: CommissionPlan,
         ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
-            let entity_id = Self::ensure_entity_owner_via_shop(shop_id, &who)?;
+            let entity_id = Self::ensure_entity_admin_via_shop(origin, shop_id)?;
+
+            // 方案只重置返佣模式，保留已配置的返佣来源
+            let source = CommissionConfigs::<T>::get(entity_id)
+                .map(|c| c.source)
+                .unwrap_or_default();
 
             // 先清除旧配置（entity_id 作为 key）
             T::ReferralWriter::clear_config(entity_id)?;
             T::LevelDiffWriter::clear_config(entity_id)?;
+            T::TeamWriter::clear_config(entity_id)?;
 
             match plan {
                 CommissionPlan::None => {
                     CommissionConfigs::<T>::insert(entity_id, CoreCommissionConfig {
                         enabled_modes: CommissionModes(CommissionModes::NONE),
-                        source: CommissionSource::default(),
+                        source,
                         max_commission_rate: 0,
                         enabled: false,
                         withdrawal_cooldown: 0,
@@ -608,7 +692,7 @@ pub mod pallet {
                     ensure!(rate <= 10000, Error::<T>::InvalidCommissionRate);
                     CommissionConfigs::<T>::insert(entity_id, CoreCommissionConfig {
                         enabled_modes: CommissionModes(CommissionModes::DIRECT_REWARD),
-                        source: CommissionSource::default(),
+                        source,
                         max_commission_rate: rate,
                         enabled: true,
                         withdrawal_cooldown: 0,
@@ -632,7 +716,7 @@ pub mod pallet {
                         enabled_modes: CommissionModes(
                             CommissionModes::DIRECT_REWARD | CommissionModes::MULTI_LEVEL
                         ),
-                        source: CommissionSource::default(),
+                        source,
                         max_commission_rate: total_rate.min(10000),
                         enabled: true,
                         withdrawal_cooldown: 0,
@@ -646,17 +730,32 @@ pub mod pallet {
                         enabled_modes: CommissionModes(
                             CommissionModes::DIRECT_REWARD | CommissionModes::LEVEL_DIFF
                         ),
-                        source: CommissionSource::default(),
+                        source,
                         max_commission_rate: diamond,
                         enabled: true,
                         withdrawal_cooldown: 0,
                     });
                     T::LevelDiffWriter::set_global_rates(entity_id, normal, silver, gold, platinum, diamond)?;
                 }
+                CommissionPlan::Team { ref tiers, period_blocks, max_depth, period_cap } => {
+                    // 每单按最高档比例预留团队奖金池（极差结算总额不超过最高档比例）
+                    let top_rate = tiers.last().map(|t| t.rate).unwrap_or(0);
+                    ensure!(top_rate <= 10000, Error::<T>::InvalidCommissionRate);
+                    CommissionConfigs::<T>::insert(entity_id, CoreCommissionConfig {
+                        enabled_modes: CommissionModes(CommissionModes::TEAM_PERFORMANCE),
+                        source,
+                        max_commission_rate: top_rate,
+                        enabled: true,
+                        withdrawal_cooldown: 0,
+                    });
+                    T::TeamWriter::set_team_config(
+                        entity_id, tiers.to_vec(), period_blocks, max_depth, period_cap,
+                    )?;
+                }
                 CommissionPlan::Custom => {
                     CommissionConfigs::<T>::insert(entity_id, CoreCommissionConfig {
                         enabled_modes: CommissionModes(CommissionModes::NONE),
-                        source: CommissionSource::default(),
+                        source,
                         max_commission_rate: 10000,
                         enabled: true,
                         withdrawal_cooldown: 0,
@@ -702,6 +801,18 @@ pub mod pallet {
             Ok(entity_id)
         }
 
+        /// 验证配置类调用者（Entity 所有者签名或该 Entity 的治理来源）
+        fn ensure_entity_admin_via_shop(origin: OriginFor<T>, shop_id: u64) -> Result<u64, DispatchError> {
+            match ensure_admin_caller::<_, _, T::EntityGovernanceOrigin>(origin)? {
+                AdminCaller::Signed(who) => Self::ensure_entity_owner_via_shop(shop_id, &who),
+                AdminCaller::Governance(gov_entity) => {
+                    let entity_id = Self::resolve_entity_id(shop_id)?;
+                    ensure!(gov_entity == entity_id, Error::<T>::NotEntityOwner);
+                    Ok(entity_id)
+                },
+            }
+        }
+
         /// 使用购物余额内部实现（entity_id 级，供 extrinsic 和 CommissionProvider 调用）
         pub fn do_use_shopping_balance(
             entity_id: u64,
@@ -821,16 +932,28 @@ pub mod pallet {
             WithdrawalSplit { withdrawal, repurchase, bonus }
         }
 
+        /// 计入团队插件沿推荐链累计/回退业绩的权重
+        ///
+        /// 订单路径经 `CommissionProvider` 进入（下单/确认收货/退款及超时处理），调用方权重固定，
+        /// 按插件返回的实际层数权重登记到本区块，避免推荐链深度不受权重约束。
+        fn note_team_weight(weight: Weight) {
+            if !weight.is_zero() {
+                frame_system::Pallet::<T>::register_extra_weight_unchecked(weight, DispatchClass::Normal);
+            }
+        }
+
         /// 调度引擎：处理订单返佣（Entity 级佣金池）
         ///
         /// 订单来自 shop_id，但佣金记账在 entity_id 级。
-        /// 佣金资金从 Shop 账户转入 Entity 账户。
+        /// 佣金资金按返佣来源从平台账户 / Shop 账户转入 Entity 账户，
+        /// 来源资金不足时本单不发放返佣（发出 CommissionFundingInsufficient）。
         pub fn process_commission(
             shop_id: u64,
             order_id: u64,
             buyer: &T::AccountId,
             order_amount: BalanceOf<T>,
             available_pool: BalanceOf<T>,
+            platform_fee: BalanceOf<T>,
         ) -> DispatchResult {
             let entity_id = Self::resolve_entity_id(shop_id)?;
 
@@ -844,12 +967,42 @@ pub mod pallet {
                 .saturating_mul(config.max_commission_rate.into())
                 / 10000u32.into();
 
-            // 偿付安全: Shop 可用 = Shop余额（佣金从 Shop 转出）
+            if max_commission.is_zero() {
+                // 无返佣额度，团队业绩照常累计
+                let (_, team_weight) = T::TeamPlugin::record_order(
+                    entity_id, shop_id, order_id, buyer, order_amount, Zero::zero(), config.enabled_modes,
+                );
+                Self::note_team_weight(team_weight);
+                return Ok(());
+            }
+
+            // 偿付安全: 按返佣来源计算可出资额度
+            let source = config.source;
+            let platform_share = source.platform_share();
             let shop_account = T::ShopProvider::shop_account(shop_id);
-            let shop_balance = T::Currency::free_balance(&shop_account);
-            let mut remaining = max_commission.min(shop_balance);
+            let platform_account = T::PlatformAccount::get();
+            let (platform_available, shop_available) = Self::source_available(
+                shop_id, &shop_account, &platform_account, platform_fee, platform_share,
+            );
+            let budget = max_commission.min(
+                Self::funding_cap(max_commission, platform_share, platform_available, shop_available),
+            );
+            let mut remaining = budget;
 
             if remaining.is_zero() {
+                Self::deposit_event(Event::CommissionFundingInsufficient {
+                    entity_id,
+                    shop_id,
+                    order_id,
+                    source,
+                    required: max_commission,
+                    platform_available,
+                    shop_available,
+                });
+                let (_, team_weight) = T::TeamPlugin::record_order(
+                    entity_id, shop_id, order_id, buyer, order_amount, Zero::zero(), config.enabled_modes,
+                );
+                Self::note_team_weight(team_weight);
                 return Ok(());
             }
 
@@ -894,15 +1047,23 @@ pub mod pallet {
                 )?;
             }
 
-            // 4. Team Plugin (预留)
-            let (outputs, _new_remaining) = T::TeamPlugin::calculate(
-                entity_id, shop_id, buyer, order_amount, remaining, enabled_modes, is_first_order, buyer_stats.order_count,
+            // 4. Team Plugin：本单只累计业绩并预留奖金池，周期结束后按总业绩结算（见 pay_team_bonus）
+            let (team_reserve, team_weight) = T::TeamPlugin::record_order(
+                entity_id, shop_id, order_id, buyer, order_amount, remaining, enabled_modes,
             );
-            for output in outputs {
-                Self::credit_commission(
-                    entity_id, shop_id, order_id, buyer, &output.beneficiary, output.amount,
-                    output.commission_type, output.level, now,
-                )?;
+            Self::note_team_weight(team_weight);
+            let team_reserve = team_reserve.min(remaining);
+            if !team_reserve.is_zero() {
+                remaining = remaining.saturating_sub(team_reserve);
+                TeamBonusPool::<T>::mutate(entity_id, |pool| {
+                    *pool = pool.saturating_add(team_reserve);
+                });
+                OrderTeamReserve::<T>::insert(order_id, (entity_id, shop_id, team_reserve));
+                Self::deposit_event(Event::TeamBonusReserved {
+                    entity_id,
+                    order_id,
+                    amount: team_reserve,
+                });
             }
 
             // 更新买家订单数（Entity 级）
@@ -911,32 +1072,126 @@ pub mod pallet {
             });
 
             // 更新 Entity 统计
-            let distributed = max_commission.min(shop_balance).saturating_sub(remaining);
+            let distributed = budget.saturating_sub(remaining);
             ShopCommissionTotals::<T>::mutate(entity_id, |(total, orders)| {
                 *total = total.saturating_add(distributed);
                 *orders = orders.saturating_add(1);
             });
 
-            // 将佣金资金从 Shop 账户转入 Entity 账户
+            // 将佣金资金按来源比例转入 Entity 账户
             if !distributed.is_zero() {
+                let from_platform = if platform_share >= 10000 {
+                    distributed
+                } else {
+                    distributed.saturating_mul(platform_share.into()) / 10000u32.into()
+                };
+                let from_shop = distributed.saturating_sub(from_platform);
                 let entity_account = T::EntityProvider::entity_account(entity_id);
-                T::Currency::transfer(
-                    &shop_account,
-                    &entity_account,
-                    distributed,
-                    ExistenceRequirement::KeepAlive,
-                )?;
+
+                if !from_platform.is_zero() {
+                    T::Currency::transfer(
+                        &platform_account,
+                        &entity_account,
+                        from_platform,
+                        ExistenceRequirement::KeepAlive,
+                    )?;
+                }
+                if !from_shop.is_zero() {
+                    T::Currency::transfer(
+                        &shop_account,
+                        &entity_account,
+                        from_shop,
+                        ExistenceRequirement::KeepAlive,
+                    )?;
+                }
+
+                OrderCommissionFunding::<T>::mutate(order_id, |(p, s)| {
+                    *p = p.saturating_add(from_platform);
+                    *s = s.saturating_add(from_shop);
+                });
 
                 Self::deposit_event(Event::CommissionFundsTransferred {
                     entity_id,
                     shop_id,
-                    amount: distributed,
+                    order_id,
+                    source,
+                    from_platform,
+                    from_shop,
                 });
             }
 
             Ok(())
         }
 
+        /// 各来源可出资额度 (平台费, Shop 运营资金)
+        ///
+        /// - 平台费：不超过本单平台费，且平台账户保留存在性押金
+        /// - Shop 运营资金：扣除 CommissionFundGuard 保护资金与存在性押金
+        fn source_available(
+            shop_id: u64,
+            shop_account: &T::AccountId,
+            platform_account: &T::AccountId,
+            platform_fee: BalanceOf<T>,
+            platform_share: u16,
+        ) -> (BalanceOf<T>, BalanceOf<T>) {
+            let ed = T::Currency::minimum_balance();
+
+            let platform_available = if platform_share > 0 {
+                T::Currency::free_balance(platform_account)
+                    .saturating_sub(ed)
+                    .min(platform_fee)
+            } else {
+                Zero::zero()
+            };
+
+            let shop_available = if platform_share < 10000 {
+                let protected: BalanceOf<T> =
+                    <Self as pallet_entity_common::CommissionFundGuard>::protected_funds(shop_id)
+                        .saturated_into();
+                T::Currency::free_balance(shop_account)
+                    .saturating_sub(protected)
+                    .saturating_sub(ed)
+            } else {
+                Zero::zero()
+            };
+
+            (platform_available, shop_available)
+        }
+
+        /// 在各来源按比例出资的前提下，可支持的最大返佣总额
+        fn funding_cap(
+            max_commission: BalanceOf<T>,
+            platform_share: u16,
+            platform_available: BalanceOf<T>,
+            shop_available: BalanceOf<T>,
+        ) -> BalanceOf<T> {
+            let shop_share = 10000u16.saturating_sub(platform_share);
+            let mut cap = max_commission;
+            if platform_share > 0 {
+                cap = cap.min(platform_available.saturating_mul(10000u32.into()) / platform_share.into());
+            }
+            if shop_share > 0 {
+                cap = cap.min(shop_available.saturating_mul(10000u32.into()) / shop_share.into());
+            }
+            cap
+        }
+
+        /// 按原出资比例拆分退款 (退还平台, 退还 Shop)；无出资记录的历史订单全部退还 Shop
+        fn split_refund(
+            refund: BalanceOf<T>,
+            funding: (BalanceOf<T>, BalanceOf<T>),
+        ) -> (BalanceOf<T>, BalanceOf<T>) {
+            let (from_platform, from_shop) = funding;
+            let total = from_platform.saturating_add(from_shop);
+            if total.is_zero() {
+                return (Zero::zero(), refund);
+            }
+            let to_platform = Perbill::from_rational(from_platform, total)
+                .mul_floor(refund)
+                .min(from_platform);
+            (to_platform, refund.saturating_sub(to_platform))
+        }
+
         /// 记录并发放返佣（Entity 级记账）
         pub fn credit_commission(
             entity_id: u64,
@@ -991,9 +1246,45 @@ pub mod pallet {
             Ok(())
         }
 
+        /// 从团队奖金池入账周期团队奖金（资金已在 Entity 账户，只做记账）
+        ///
+        /// 奖金池不足时按余额入账，返回实际入账额。
+        pub fn do_pay_team_bonus(
+            entity_id: u64,
+            beneficiary: &T::AccountId,
+            amount: BalanceOf<T>,
+            period: u32,
+        ) -> Result<BalanceOf<T>, DispatchError> {
+            let actual = TeamBonusPool::<T>::mutate(entity_id, |pool| {
+                let actual = amount.min(*pool);
+                *pool = pool.saturating_sub(actual);
+                actual
+            });
+            if actual.is_zero() {
+                return Ok(actual);
+            }
+
+            MemberCommissionStats::<T>::mutate(entity_id, beneficiary, |stats| {
+                stats.total_earned = stats.total_earned.saturating_add(actual);
+                stats.pending = stats.pending.saturating_add(actual);
+            });
+            MemberLastCredited::<T>::insert(entity_id, beneficiary, <frame_system::Pallet<T>>::block_number());
+            ShopPendingTotal::<T>::mutate(entity_id, |total| {
+                *total = total.saturating_add(actual);
+            });
+
+            Self::deposit_event(Event::TeamBonusCredited {
+                entity_id,
+                beneficiary: beneficiary.clone(),
+                period,
+                amount: actual,
+            });
+            Ok(actual)
+        }
+
         /// 取消订单返佣（Entity 级）
         ///
-        /// 退还佣金资金：Entity 账户 → Shop 账户（原订单所属 Shop）
+        /// 退还佣金资金：Entity 账户 → 原出资来源（平台账户 / 原订单所属 Shop，按出资比例）
         pub fn cancel_commission(order_id: u64) -> DispatchResult {
             let mut total_refund_by_shop: alloc::vec::Vec<(u64, u64, BalanceOf<T>)> = alloc::vec::Vec::new(); // (entity_id, shop_id, amount)
 
@@ -1020,18 +1311,45 @@ pub mod pallet {
                 }
             });
 
-            // 退还佣金资金从 Entity 账户回到 Shop 账户
+            // 团队业绩回退；预留奖金中尚未结算发放的部分随本单退还
+            Self::note_team_weight(T::TeamPlugin::revert_order(order_id));
+            if let Some((entity_id, shop_id, reserved)) = OrderTeamReserve::<T>::take(order_id) {
+                let refund = TeamBonusPool::<T>::mutate(entity_id, |pool| {
+                    let refund = reserved.min(*pool);
+                    *pool = pool.saturating_sub(refund);
+                    refund
+                });
+                if let Some(entry) = total_refund_by_shop.iter_mut().find(|(e, s, _)| *e == entity_id && *s == shop_id) {
+                    entry.2 = entry.2.saturating_add(refund);
+                } else {
+                    total_refund_by_shop.push((entity_id, shop_id, refund));
+                }
+            }
+
+            // 退还佣金资金从 Entity 账户回到原出资来源
+            let funding = OrderCommissionFunding::<T>::take(order_id);
             for (entity_id, shop_id, refund_amount) in total_refund_by_shop {
                 if !refund_amount.is_zero() {
                     let entity_account = T::EntityProvider::entity_account(entity_id);
                     let shop_account = T::ShopProvider::shop_account(shop_id);
+                    let (to_platform, to_shop) = Self::split_refund(refund_amount, funding);
                     // best-effort: 如果 Entity 账户余额不足则跳过（不阻塞取消）
-                    let _ = T::Currency::transfer(
-                        &entity_account,
-                        &shop_account,
-                        refund_amount,
-                        ExistenceRequirement::KeepAlive,
-                    );
+                    if !to_platform.is_zero() {
+                        let _ = T::Currency::transfer(
+                            &entity_account,
+                            &T::PlatformAccount::get(),
+                            to_platform,
+                            ExistenceRequirement::KeepAlive,
+                        );
+                    }
+                    if !to_shop.is_zero() {
+                        let _ = T::Currency::transfer(
+                            &entity_account,
+                            &shop_account,
+                            to_shop,
+                            ExistenceRequirement::KeepAlive,
+                        );
+                    }
                 }
             }
 
@@ -1053,6 +1371,18 @@ impl<T: pallet::Config> pallet_entity_common::CommissionFundGuard for pallet::Pa
     }
 }
 
+/// TeamBonusPayer impl: 团队插件周期结算时从奖金池入账
+impl<T: pallet::Config> TeamBonusPayer<T::AccountId, pallet::BalanceOf<T>> for pallet::Pallet<T> {
+    fn pay_team_bonus(
+        entity_id: u64,
+        beneficiary: &T::AccountId,
+        amount: pallet::BalanceOf<T>,
+        period: u32,
+    ) -> Result<pallet::BalanceOf<T>, sp_runtime::DispatchError> {
+        pallet::Pallet::<T>::do_pay_team_bonus(entity_id, beneficiary, amount, period)
+    }
+}
+
 /// CommissionProvider impl: 外部接口仍接收 shop_id，内部解析 entity_id
 impl<T: pallet::Config> CommissionProvider<T::AccountId, pallet::BalanceOf<T>> for pallet::Pallet<T> {
     fn process_commission(
@@ -1061,8 +1391,14 @@ impl<T: pallet::Config> CommissionProvider<T::AccountId, pallet::BalanceOf<T>> f
         buyer: &T::AccountId,
         order_amount: pallet::BalanceOf<T>,
         available_pool: pallet::BalanceOf<T>,
+        platform_fee: pallet::BalanceOf<T>,
     ) -> sp_runtime::DispatchResult {
-        pallet::Pallet::<T>::process_commission(shop_id, order_id, buyer, order_amount, available_pool)
+        // 任一来源转账失败时整体回滚，不留下部分记账
+        frame_support::storage::with_storage_layer(|| {
+            pallet::Pallet::<T>::process_commission(
+                shop_id, order_id, buyer, order_amount, available_pool, platform_fee,
+            )
+        })
     }
 
     fn cancel_commission(order_id: u64) -> sp_runtime::DispatchResult {
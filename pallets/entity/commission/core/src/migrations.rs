@@ -0,0 +1,95 @@
+//! Commission Core 存储迁移
+
+use super::*;
+use frame_support::{
+    migrations::VersionedMigration,
+    pallet_prelude::*,
+    traits::UncheckedOnRuntimeUpgrade,
+};
+use sp_std::marker::PhantomData;
+
+/// v0 → v1：`CommissionSource` 从预留的单元枚举改为真实出资来源
+///
+/// v0 中 `source` 字段仅为预留，返佣实际统一从 Shop 运营账户出资，
+/// 且 `Mixed` 无 `platform_share` 负载，新类型无法直接解码旧值。
+/// 迁移将所有 `CommissionConfigs` 的来源统一改写为 `ShopFund`，保持升级前的出资行为。
+pub mod v1 {
+    use super::*;
+
+    pub(crate) mod v0 {
+        use super::*;
+
+        /// v0 返佣来源（预留，编码为单字节下标）
+        #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+        pub enum OldCommissionSource {
+            PlatformFee,
+            ShopFund,
+            Mixed,
+        }
+
+        /// v0 返佣核心配置
+        #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+        pub struct OldCoreCommissionConfig {
+            pub enabled_modes: CommissionModes,
+            pub source: OldCommissionSource,
+            pub max_commission_rate: u16,
+            pub enabled: bool,
+            pub withdrawal_cooldown: u32,
+        }
+    }
+
+    /// 未做版本检查的迁移逻辑，通过 [`MigrateV0ToV1`] 使用
+    pub struct InnerMigrateV0ToV1<T>(PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV0ToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut translated = 0u64;
+            CommissionConfigs::<T>::translate::<v0::OldCoreCommissionConfig, _>(|_, old| {
+                translated += 1;
+                Some(CoreCommissionConfig {
+                    enabled_modes: old.enabled_modes,
+                    source: CommissionSource::ShopFund,
+                    max_commission_rate: old.max_commission_rate,
+                    enabled: old.enabled,
+                    withdrawal_cooldown: old.withdrawal_cooldown,
+                })
+            });
+            log::info!(
+                target: "runtime::commission-core",
+                "migrated {} commission configs to v1 (source = ShopFund)",
+                translated,
+            );
+            T::DbWeight::get().reads_writes(translated, translated)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<alloc::vec::Vec<u8>, sp_runtime::TryRuntimeError> {
+            Ok((CommissionConfigs::<T>::iter_keys().count() as u32).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: alloc::vec::Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+            let before = u32::decode(&mut &state[..])
+                .map_err(|_| sp_runtime::TryRuntimeError::Other("invalid pre_upgrade state"))?;
+            let mut after = 0u32;
+            for (_, config) in CommissionConfigs::<T>::iter() {
+                ensure!(
+                    config.source == CommissionSource::ShopFund,
+                    "commission source not migrated to ShopFund"
+                );
+                after += 1;
+            }
+            ensure!(before == after, "commission configs lost during migration");
+            Ok(())
+        }
+    }
+
+    /// 带版本检查的 v0 → v1 迁移（仅在链上版本为 0 时执行，完成后写入版本 1）
+    pub type MigrateV0ToV1<T> = VersionedMigration<
+        0,
+        1,
+        InnerMigrateV0ToV1<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
@@ -0,0 +1,301 @@
+use crate as pallet_commission_core;
+use frame_support::{derive_impl, parameter_types, traits::ConstU32, weights::Weight};
+use pallet_commission_common::{
+    CommissionModes, CommissionOutput, CommissionPlugin, CommissionType, TeamPerformancePlugin,
+};
+use sp_runtime::BuildStorage;
+use std::cell::RefCell;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Test accounts
+pub const OWNER: u64 = 1;
+pub const BUYER: u64 = 10;
+pub const REFERRER: u64 = 11;
+pub const PLATFORM: u64 = 97;
+pub const SHOP_ACCOUNT: u64 = 98;
+pub const ENTITY_ACCOUNT: u64 = 99;
+
+// Test IDs
+pub const ENTITY_1: u64 = 100;
+pub const SHOP_1: u64 = 1000;
+
+/// 各账户初始余额
+pub const INITIAL_BALANCE: u128 = 1_000_000;
+
+frame_support::construct_runtime!(
+    pub enum Test {
+        System: frame_system,
+        Balances: pallet_balances,
+        CommissionCore: pallet_commission_core,
+    }
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+    type Block = Block;
+    type AccountData = pallet_balances::AccountData<u128>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+    type AccountStore = System;
+    type Balance = u128;
+}
+
+// ============================================================================
+// Mock EntityProvider
+// ============================================================================
+
+pub struct MockEntityProvider;
+
+impl pallet_entity_common::EntityProvider<u64> for MockEntityProvider {
+    fn entity_exists(entity_id: u64) -> bool {
+        entity_id == ENTITY_1
+    }
+
+    fn is_entity_active(entity_id: u64) -> bool {
+        entity_id == ENTITY_1
+    }
+
+    fn entity_status(entity_id: u64) -> Option<pallet_entity_common::EntityStatus> {
+        if entity_id == ENTITY_1 {
+            Some(pallet_entity_common::EntityStatus::Active)
+        } else {
+            None
+        }
+    }
+
+    fn entity_owner(entity_id: u64) -> Option<u64> {
+        if entity_id == ENTITY_1 {
+            Some(OWNER)
+        } else {
+            None
+        }
+    }
+
+    fn entity_account(_entity_id: u64) -> u64 {
+        ENTITY_ACCOUNT
+    }
+
+    fn update_entity_stats(
+        _entity_id: u64,
+        _sales_amount: u128,
+        _order_count: u32,
+    ) -> Result<(), sp_runtime::DispatchError> {
+        Ok(())
+    }
+
+    fn update_entity_rating(
+        _entity_id: u64,
+        _rating: u8,
+    ) -> Result<(), sp_runtime::DispatchError> {
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Mock ShopProvider
+// ============================================================================
+
+pub struct MockShopProvider;
+
+impl pallet_entity_common::ShopProvider<u64> for MockShopProvider {
+    fn shop_exists(shop_id: u64) -> bool {
+        shop_id == SHOP_1
+    }
+
+    fn is_shop_active(shop_id: u64) -> bool {
+        shop_id == SHOP_1
+    }
+
+    fn shop_entity_id(shop_id: u64) -> Option<u64> {
+        if shop_id == SHOP_1 {
+            Some(ENTITY_1)
+        } else {
+            None
+        }
+    }
+
+    fn shop_owner(shop_id: u64) -> Option<u64> {
+        if shop_id == SHOP_1 {
+            Some(OWNER)
+        } else {
+            None
+        }
+    }
+
+    fn shop_account(_shop_id: u64) -> u64 {
+        SHOP_ACCOUNT
+    }
+
+    fn shop_type(_shop_id: u64) -> Option<pallet_entity_common::ShopType> {
+        Some(pallet_entity_common::ShopType::OnlineStore)
+    }
+
+    fn shop_member_mode(_shop_id: u64) -> pallet_entity_common::MemberMode {
+        pallet_entity_common::MemberMode::Inherit
+    }
+
+    fn is_shop_manager(_shop_id: u64, _account: &u64) -> bool {
+        false
+    }
+
+    fn update_shop_stats(
+        _shop_id: u64,
+        _sales_amount: u128,
+        _order_count: u32,
+    ) -> Result<(), sp_runtime::DispatchError> {
+        Ok(())
+    }
+
+    fn update_shop_rating(
+        _shop_id: u64,
+        _rating: u8,
+    ) -> Result<(), sp_runtime::DispatchError> {
+        Ok(())
+    }
+
+    fn deduct_operating_fund(
+        _shop_id: u64,
+        _amount: u128,
+    ) -> Result<(), sp_runtime::DispatchError> {
+        Ok(())
+    }
+
+    fn operating_balance(_shop_id: u64) -> u128 {
+        0
+    }
+}
+
+// ============================================================================
+// Mock 返佣插件：启用直推模式时，剩余额度全部作为直推奖励发给 REFERRER
+// ============================================================================
+
+pub struct MockReferralPlugin;
+
+impl CommissionPlugin<u64, u128> for MockReferralPlugin {
+    fn calculate(
+        _entity_id: u64,
+        _shop_id: u64,
+        _buyer: &u64,
+        _order_amount: u128,
+        remaining: u128,
+        enabled_modes: CommissionModes,
+        _is_first_order: bool,
+        _buyer_order_count: u32,
+    ) -> (Vec<CommissionOutput<u64, u128>>, u128) {
+        if remaining == 0 || !enabled_modes.contains(CommissionModes::DIRECT_REWARD) {
+            return (Vec::new(), remaining);
+        }
+        let output = CommissionOutput {
+            beneficiary: REFERRER,
+            amount: remaining,
+            commission_type: CommissionType::DirectReward,
+            level: 1,
+        };
+        (vec![output], 0)
+    }
+}
+
+// ============================================================================
+// Mock 团队业绩插件：启用团队模式时预留订单金额的 10%
+// ============================================================================
+
+thread_local! {
+    /// 已回退业绩的订单
+    pub static REVERTED_TEAM_ORDERS: RefCell<Vec<u64>> = RefCell::new(Vec::new());
+}
+
+pub fn reverted_team_orders() -> Vec<u64> {
+    REVERTED_TEAM_ORDERS.with(|r| r.borrow().clone())
+}
+
+pub struct MockTeamPlugin;
+
+impl TeamPerformancePlugin<u64, u128> for MockTeamPlugin {
+    fn record_order(
+        _entity_id: u64,
+        _shop_id: u64,
+        _order_id: u64,
+        _buyer: &u64,
+        order_amount: u128,
+        remaining: u128,
+        enabled_modes: CommissionModes,
+    ) -> (u128, Weight) {
+        if !enabled_modes.contains(CommissionModes::TEAM_PERFORMANCE) {
+            return (0, Weight::zero());
+        }
+        ((order_amount / 10).min(remaining), Weight::from_parts(1_000_000, 0))
+    }
+
+    fn revert_order(order_id: u64) -> Weight {
+        REVERTED_TEAM_ORDERS.with(|r| r.borrow_mut().push(order_id));
+        Weight::from_parts(1_000_000, 0)
+    }
+}
+
+/// 模拟实体治理来源：不授予任何治理权限
+pub struct MockEntityGovernance;
+impl frame_support::traits::EnsureOrigin<RuntimeOrigin> for MockEntityGovernance {
+    type Success = u64;
+    fn try_origin(o: RuntimeOrigin) -> Result<u64, RuntimeOrigin> {
+        Err(o)
+    }
+}
+
+// ============================================================================
+// Pallet Config
+// ============================================================================
+
+parameter_types! {
+    pub const PlatformAccountId: u64 = PLATFORM;
+}
+
+impl pallet_commission_core::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type Currency = Balances;
+    type ShopProvider = MockShopProvider;
+    type EntityProvider = MockEntityProvider;
+    type EntityGovernanceOrigin = MockEntityGovernance;
+    type MemberProvider = pallet_commission_common::NullMemberProvider;
+    type PlatformAccount = PlatformAccountId;
+    type ReferralPlugin = MockReferralPlugin;
+    type LevelDiffPlugin = ();
+    type SingleLinePlugin = ();
+    type TeamPlugin = MockTeamPlugin;
+    type ReferralWriter = ();
+    type LevelDiffWriter = ();
+    type TeamWriter = ();
+    type MaxCommissionRecordsPerOrder = ConstU32<10>;
+    type MaxCustomLevels = ConstU32<10>;
+}
+
+// ============================================================================
+// Test Helpers
+// ============================================================================
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut t = frame_system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .unwrap();
+
+    pallet_balances::GenesisConfig::<Test> {
+        balances: vec![
+            (OWNER, INITIAL_BALANCE),
+            (PLATFORM, INITIAL_BALANCE),
+            (SHOP_ACCOUNT, INITIAL_BALANCE),
+            (ENTITY_ACCOUNT, INITIAL_BALANCE),
+        ],
+        ..Default::default()
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+
+    let mut ext = sp_io::TestExternalities::new(t);
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        REVERTED_TEAM_ORDERS.with(|r| r.borrow_mut().clear());
+    });
+    ext
+}
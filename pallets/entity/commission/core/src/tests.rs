@@ -0,0 +1,324 @@
+//! Unit tests for pallet-commission-core
+
+use crate::{
+    mock::*, migrations, CommissionConfigs, CommissionModes, CommissionSource, Error, Event,
+    OrderCommissionFunding, OrderTeamReserve, TeamBonusPayer,
+};
+use codec::Encode;
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::{Currency, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+};
+
+/// 以 Entity 所有者身份启用直推返佣并设置来源（上限 100%）
+fn enable_with_source(source: CommissionSource) {
+    set_modes(CommissionModes::DIRECT_REWARD);
+    assert_ok!(CommissionCore::set_commission_source(
+        RuntimeOrigin::signed(OWNER),
+        SHOP_1,
+        source,
+        10000,
+    ));
+    assert_ok!(CommissionCore::enable_commission(RuntimeOrigin::signed(OWNER), SHOP_1, true));
+}
+
+fn set_modes(modes: u16) {
+    assert_ok!(CommissionCore::set_commission_modes(
+        RuntimeOrigin::signed(OWNER),
+        SHOP_1,
+        CommissionModes(modes),
+    ));
+}
+
+fn balance(who: u64) -> u128 {
+    Balances::free_balance(who)
+}
+
+// ============================================================================
+// 返佣来源出资
+// ============================================================================
+
+#[test]
+fn shop_fund_source_pays_from_shop_account() {
+    new_test_ext().execute_with(|| {
+        enable_with_source(CommissionSource::ShopFund);
+
+        assert_ok!(CommissionCore::process_commission(SHOP_1, 1, &BUYER, 10_000, 1_000, 100));
+
+        assert_eq!(balance(SHOP_ACCOUNT), INITIAL_BALANCE - 1_000);
+        assert_eq!(balance(PLATFORM), INITIAL_BALANCE);
+        assert_eq!(balance(ENTITY_ACCOUNT), INITIAL_BALANCE + 1_000);
+        assert_eq!(OrderCommissionFunding::<Test>::get(1), (0, 1_000));
+        assert_eq!(CommissionCore::member_commission_stats(ENTITY_1, REFERRER).pending, 1_000);
+        assert_eq!(CommissionCore::shop_pending_total(ENTITY_1), 1_000);
+    });
+}
+
+#[test]
+fn platform_fee_source_is_capped_by_order_platform_fee() {
+    new_test_ext().execute_with(|| {
+        enable_with_source(CommissionSource::PlatformFee);
+
+        // 可分配 1000，但本单平台费只有 300
+        assert_ok!(CommissionCore::process_commission(SHOP_1, 1, &BUYER, 10_000, 1_000, 300));
+
+        assert_eq!(balance(PLATFORM), INITIAL_BALANCE - 300);
+        assert_eq!(balance(SHOP_ACCOUNT), INITIAL_BALANCE);
+        assert_eq!(balance(ENTITY_ACCOUNT), INITIAL_BALANCE + 300);
+        assert_eq!(OrderCommissionFunding::<Test>::get(1), (300, 0));
+        assert_eq!(CommissionCore::member_commission_stats(ENTITY_1, REFERRER).pending, 300);
+    });
+}
+
+#[test]
+fn mixed_source_splits_by_platform_share() {
+    new_test_ext().execute_with(|| {
+        enable_with_source(CommissionSource::Mixed { platform_share: 3000 });
+
+        assert_ok!(CommissionCore::process_commission(SHOP_1, 1, &BUYER, 10_000, 1_000, 1_000));
+
+        assert_eq!(balance(PLATFORM), INITIAL_BALANCE - 300);
+        assert_eq!(balance(SHOP_ACCOUNT), INITIAL_BALANCE - 700);
+        assert_eq!(balance(ENTITY_ACCOUNT), INITIAL_BALANCE + 1_000);
+        assert_eq!(OrderCommissionFunding::<Test>::get(1), (300, 700));
+        System::assert_has_event(RuntimeEvent::CommissionCore(Event::CommissionFundsTransferred {
+            entity_id: ENTITY_1,
+            shop_id: SHOP_1,
+            order_id: 1,
+            source: CommissionSource::Mixed { platform_share: 3000 },
+            from_platform: 300,
+            from_shop: 700,
+        }));
+    });
+}
+
+#[test]
+fn mixed_source_is_capped_by_scarcer_side() {
+    new_test_ext().execute_with(|| {
+        enable_with_source(CommissionSource::Mixed { platform_share: 5000 });
+
+        // 平台费只有 100，按 50% 比例最多支撑 200 的返佣
+        assert_ok!(CommissionCore::process_commission(SHOP_1, 1, &BUYER, 10_000, 1_000, 100));
+
+        assert_eq!(balance(PLATFORM), INITIAL_BALANCE - 100);
+        assert_eq!(balance(SHOP_ACCOUNT), INITIAL_BALANCE - 100);
+        assert_eq!(OrderCommissionFunding::<Test>::get(1), (100, 100));
+        assert_eq!(CommissionCore::member_commission_stats(ENTITY_1, REFERRER).pending, 200);
+    });
+}
+
+#[test]
+fn insufficient_platform_fee_skips_commission() {
+    new_test_ext().execute_with(|| {
+        enable_with_source(CommissionSource::PlatformFee);
+
+        assert_ok!(CommissionCore::process_commission(SHOP_1, 1, &BUYER, 10_000, 1_000, 0));
+
+        System::assert_has_event(RuntimeEvent::CommissionCore(Event::CommissionFundingInsufficient {
+            entity_id: ENTITY_1,
+            shop_id: SHOP_1,
+            order_id: 1,
+            source: CommissionSource::PlatformFee,
+            required: 1_000,
+            platform_available: 0,
+            shop_available: 0,
+        }));
+        assert!(CommissionCore::order_commission_records(1).is_empty());
+        assert_eq!(CommissionCore::member_commission_stats(ENTITY_1, REFERRER).pending, 0);
+        assert_eq!(balance(PLATFORM), INITIAL_BALANCE);
+        assert_eq!(balance(ENTITY_ACCOUNT), INITIAL_BALANCE);
+    });
+}
+
+#[test]
+fn insufficient_shop_fund_skips_commission() {
+    new_test_ext().execute_with(|| {
+        enable_with_source(CommissionSource::ShopFund);
+        // Shop 账户只剩存在性押金
+        Balances::make_free_balance_be(&SHOP_ACCOUNT, 1);
+
+        assert_ok!(CommissionCore::process_commission(SHOP_1, 1, &BUYER, 10_000, 1_000, 1_000));
+
+        System::assert_has_event(RuntimeEvent::CommissionCore(Event::CommissionFundingInsufficient {
+            entity_id: ENTITY_1,
+            shop_id: SHOP_1,
+            order_id: 1,
+            source: CommissionSource::ShopFund,
+            required: 1_000,
+            platform_available: 0,
+            shop_available: 0,
+        }));
+        assert!(CommissionCore::order_commission_records(1).is_empty());
+        assert_eq!(balance(PLATFORM), INITIAL_BALANCE);
+        assert_eq!(balance(SHOP_ACCOUNT), 1);
+    });
+}
+
+#[test]
+fn set_commission_source_rejects_invalid_platform_share() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            CommissionCore::set_commission_source(
+                RuntimeOrigin::signed(OWNER),
+                SHOP_1,
+                CommissionSource::Mixed { platform_share: 10001 },
+                10000,
+            ),
+            Error::<Test>::InvalidCommissionSource
+        );
+    });
+}
+
+// ============================================================================
+// 取消退款
+// ============================================================================
+
+#[test]
+fn cancel_refunds_each_source_by_funding_split() {
+    new_test_ext().execute_with(|| {
+        enable_with_source(CommissionSource::Mixed { platform_share: 3000 });
+        assert_ok!(CommissionCore::process_commission(SHOP_1, 1, &BUYER, 10_000, 1_000, 1_000));
+
+        assert_ok!(CommissionCore::cancel_commission(1));
+
+        assert_eq!(balance(PLATFORM), INITIAL_BALANCE);
+        assert_eq!(balance(SHOP_ACCOUNT), INITIAL_BALANCE);
+        assert_eq!(balance(ENTITY_ACCOUNT), INITIAL_BALANCE);
+        assert!(!OrderCommissionFunding::<Test>::contains_key(1));
+        assert_eq!(CommissionCore::member_commission_stats(ENTITY_1, REFERRER).pending, 0);
+        assert_eq!(CommissionCore::shop_pending_total(ENTITY_1), 0);
+    });
+}
+
+#[test]
+fn cancel_without_funding_record_refunds_shop() {
+    new_test_ext().execute_with(|| {
+        enable_with_source(CommissionSource::PlatformFee);
+        assert_ok!(CommissionCore::process_commission(SHOP_1, 1, &BUYER, 10_000, 1_000, 1_000));
+        // 模拟升级前的历史订单：无出资明细
+        OrderCommissionFunding::<Test>::remove(1);
+
+        assert_ok!(CommissionCore::cancel_commission(1));
+
+        assert_eq!(balance(PLATFORM), INITIAL_BALANCE - 1_000);
+        assert_eq!(balance(SHOP_ACCOUNT), INITIAL_BALANCE + 1_000);
+        assert_eq!(balance(ENTITY_ACCOUNT), INITIAL_BALANCE);
+    });
+}
+
+// ============================================================================
+// 团队奖金池
+// ============================================================================
+
+#[test]
+fn team_reserve_funds_pool_and_period_bonus_is_paid_from_it() {
+    new_test_ext().execute_with(|| {
+        enable_with_source(CommissionSource::ShopFund);
+        set_modes(CommissionModes::TEAM_PERFORMANCE);
+
+        // 团队插件预留 10%，本单不产生返佣记录
+        assert_ok!(CommissionCore::process_commission(SHOP_1, 1, &BUYER, 10_000, 5_000, 0));
+
+        assert_eq!(CommissionCore::team_bonus_pool(ENTITY_1), 1_000);
+        assert_eq!(OrderTeamReserve::<Test>::get(1), Some((ENTITY_1, SHOP_1, 1_000)));
+        assert_eq!(balance(SHOP_ACCOUNT), INITIAL_BALANCE - 1_000);
+        assert_eq!(balance(ENTITY_ACCOUNT), INITIAL_BALANCE + 1_000);
+        assert!(CommissionCore::order_commission_records(1).is_empty());
+        // 插件返回的业绩累计权重计入区块
+        assert_eq!(
+            System::block_weight().get(frame_support::dispatch::DispatchClass::Normal).ref_time(),
+            1_000_000
+        );
+
+        // 周期结算入账，超出奖金池的部分按余额入账
+        assert_eq!(<CommissionCore as TeamBonusPayer<u64, u128>>::pay_team_bonus(ENTITY_1, &REFERRER, 600, 0), Ok(600));
+        assert_eq!(<CommissionCore as TeamBonusPayer<u64, u128>>::pay_team_bonus(ENTITY_1, &REFERRER, 600, 0), Ok(400));
+
+        assert_eq!(CommissionCore::team_bonus_pool(ENTITY_1), 0);
+        assert_eq!(CommissionCore::member_commission_stats(ENTITY_1, REFERRER).pending, 1_000);
+        assert_eq!(CommissionCore::shop_pending_total(ENTITY_1), 1_000);
+        System::assert_has_event(RuntimeEvent::CommissionCore(Event::TeamBonusCredited {
+            entity_id: ENTITY_1,
+            beneficiary: REFERRER,
+            period: 0,
+            amount: 400,
+        }));
+    });
+}
+
+#[test]
+fn cancel_reverts_team_volume_and_refunds_unsettled_reserve() {
+    new_test_ext().execute_with(|| {
+        enable_with_source(CommissionSource::ShopFund);
+        set_modes(CommissionModes::TEAM_PERFORMANCE);
+        assert_ok!(CommissionCore::process_commission(SHOP_1, 1, &BUYER, 10_000, 5_000, 0));
+        assert_ok!(CommissionCore::process_commission(SHOP_1, 2, &BUYER, 10_000, 5_000, 0));
+        // 已结算的部分不退还
+        assert_ok!(<CommissionCore as TeamBonusPayer<u64, u128>>::pay_team_bonus(ENTITY_1, &REFERRER, 1_500, 0));
+
+        assert_ok!(CommissionCore::cancel_commission(2));
+
+        assert_eq!(reverted_team_orders(), vec![2]);
+        // 两次累计 + 一次回退
+        assert_eq!(
+            System::block_weight().get(frame_support::dispatch::DispatchClass::Normal).ref_time(),
+            3_000_000
+        );
+        assert!(!OrderTeamReserve::<Test>::contains_key(2));
+        assert_eq!(CommissionCore::team_bonus_pool(ENTITY_1), 0);
+        assert_eq!(balance(SHOP_ACCOUNT), INITIAL_BALANCE - 2_000 + 500);
+        assert_eq!(CommissionCore::member_commission_stats(ENTITY_1, REFERRER).pending, 1_500);
+    });
+}
+
+// ============================================================================
+// 存储迁移
+// ============================================================================
+
+#[test]
+fn migration_v1_maps_legacy_sources_to_shop_fund() {
+    use migrations::v1::v0::{OldCommissionSource, OldCoreCommissionConfig};
+
+    new_test_ext().execute_with(|| {
+        StorageVersion::new(0).put::<CommissionCore>();
+
+        let legacy = [
+            (1u64, OldCommissionSource::PlatformFee),
+            (2u64, OldCommissionSource::ShopFund),
+            (3u64, OldCommissionSource::Mixed),
+        ];
+        for (entity_id, source) in legacy {
+            let old = OldCoreCommissionConfig {
+                enabled_modes: crate::CommissionModes(0b11),
+                source,
+                max_commission_rate: 5000,
+                enabled: true,
+                withdrawal_cooldown: 7,
+            };
+            frame_support::storage::unhashed::put_raw(
+                &CommissionConfigs::<Test>::hashed_key_for(entity_id),
+                &old.encode(),
+            );
+        }
+
+        migrations::v1::MigrateV0ToV1::<Test>::on_runtime_upgrade();
+
+        assert_eq!(CommissionCore::on_chain_storage_version(), StorageVersion::new(1));
+        for (entity_id, _) in legacy {
+            let config = CommissionConfigs::<Test>::get(entity_id).expect("config migrated");
+            assert_eq!(config.source, CommissionSource::ShopFund);
+            assert_eq!(config.enabled_modes, crate::CommissionModes(0b11));
+            assert_eq!(config.max_commission_rate, 5000);
+            assert!(config.enabled);
+            assert_eq!(config.withdrawal_cooldown, 7);
+        }
+
+        // 已是 v1 时重复执行不再改写
+        enable_with_source(CommissionSource::PlatformFee);
+        migrations::v1::MigrateV0ToV1::<Test>::on_runtime_upgrade();
+        assert_eq!(
+            CommissionConfigs::<Test>::get(ENTITY_1).unwrap().source,
+            CommissionSource::PlatformFee
+        );
+    });
+}
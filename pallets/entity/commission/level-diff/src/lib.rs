@@ -306,6 +306,20 @@ impl<T: pallet::Config> pallet_commission_common::LevelDiffPlanWriter for pallet
         Ok(())
     }
 
+    fn set_custom_rates(shop_id: u64, level_rates: alloc::vec::Vec<u16>, max_depth: u8) -> Result<(), sp_runtime::DispatchError> {
+        // 与 set_custom_level_diff_config 相同的参数校验
+        frame_support::ensure!(level_rates.iter().all(|r| *r <= 10000), pallet::Error::<T>::InvalidRate);
+        frame_support::ensure!(max_depth > 0 && max_depth <= 20, pallet::Error::<T>::InvalidMaxDepth);
+        let level_rates: frame_support::BoundedVec<u16, T::MaxCustomLevels> = level_rates
+            .try_into()
+            .map_err(|_| pallet::Error::<T>::InvalidRate)?;
+        pallet::CustomLevelDiffConfigs::<T>::insert(shop_id, pallet::CustomLevelDiffConfig {
+            level_rates,
+            max_depth,
+        });
+        Ok(())
+    }
+
     fn clear_config(shop_id: u64) -> Result<(), sp_runtime::DispatchError> {
         pallet::LevelDiffConfigs::<T>::remove(shop_id);
         pallet::CustomLevelDiffConfigs::<T>::remove(shop_id);
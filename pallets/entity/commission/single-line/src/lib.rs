@@ -353,3 +353,33 @@ impl<T: pallet::Config> pallet_commission_common::CommissionPlugin<T::AccountId,
         (outputs, remaining)
     }
 }
+
+// ============================================================================
+// SingleLinePlanWriter implementation
+// ============================================================================
+
+impl<T: pallet::Config> pallet_commission_common::SingleLinePlanWriter for pallet::Pallet<T> {
+    fn set_single_line_config(
+        shop_id: u64,
+        upline_rate: u16,
+        downline_rate: u16,
+        base_upline_levels: u8,
+        base_downline_levels: u8,
+        max_upline_levels: u8,
+        max_downline_levels: u8,
+    ) -> Result<(), sp_runtime::DispatchError> {
+        // 与 set_single_line_config extrinsic 相同的比例上限
+        frame_support::ensure!(upline_rate <= 1000 && downline_rate <= 1000, pallet::Error::<T>::InvalidRate);
+        pallet::SingleLineConfigs::<T>::mutate(shop_id, |maybe| {
+            let config = maybe.get_or_insert_with(pallet::SingleLineConfig::default);
+            config.upline_rate = upline_rate;
+            config.downline_rate = downline_rate;
+            config.base_upline_levels = base_upline_levels;
+            config.base_downline_levels = base_downline_levels;
+            config.max_upline_levels = max_upline_levels;
+            config.max_downline_levels = max_downline_levels;
+        });
+        pallet::Pallet::<T>::deposit_event(pallet::Event::SingleLineConfigUpdated { shop_id });
+        Ok(())
+    }
+}
@@ -8,6 +8,7 @@
 //! - `pallet-commission-referral` — 推荐链返佣（Direct/Multi/Fixed/First/Repeat）
 //! - `pallet-commission-level-diff` — 等级极差返佣
 //! - `pallet-commission-single-line` — 单线收益（上线/下线）
+//! - `pallet-commission-team` — 团队业绩（周期档位奖金）
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -17,11 +18,13 @@ pub use pallet_commission_core;
 pub use pallet_commission_referral;
 pub use pallet_commission_level_diff;
 pub use pallet_commission_single_line;
+pub use pallet_commission_team;
 
 // Re-export commonly used traits and types at crate root
 pub use pallet_commission_common::{
     CommissionModes, CommissionOutput, CommissionPlugin, CommissionPlan, CommissionProvider,
     CommissionRecord, CommissionSource, CommissionStatus, CommissionType,
     LevelDiffPlanWriter, MemberCommissionStatsData, MemberProvider,
-    NullCommissionProvider, NullMemberProvider, ReferralPlanWriter, WithdrawalTierConfig,
+    NullCommissionProvider, NullMemberProvider, ReferralPlanWriter, SingleLinePlanWriter,
+    TeamBonusPayer, TeamPerformancePlugin, TeamPlanWriter, TeamTier, WithdrawalTierConfig,
 };
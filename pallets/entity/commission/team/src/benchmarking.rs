@@ -0,0 +1,142 @@
+//! # Commission Team Plugin Benchmarking
+//!
+//! 团队业绩插件基准测试
+//!
+//! 推荐关系来自外部 `MemberProvider`，基准无法构造任意深度的推荐链，
+//! 因此 `record_order` / `revert_order` 直接以 `d` 层上级的订单记录测量业绩累计的存储开销，
+//! 推荐人查询按每层一次读计入权重公式。
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use alloc::vec::Vec;
+use frame_benchmarking::v2::*;
+use frame_support::{traits::EnsureOrigin, BoundedVec};
+use frame_system::RawOrigin;
+use pallet::*;
+use pallet_commission_common::TeamTier;
+
+const PERIOD_BLOCKS: u32 = 100;
+
+/// 门槛、比例严格升序的 `n` 个档位
+fn tiers<T: Config>(n: u32) -> BoundedVec<TeamTier, T::MaxTeamTiers> {
+    (1..=n)
+        .map(|i| TeamTier { threshold: i as u128 * 10_000, rate: (i * 100) as u16 })
+        .collect::<Vec<_>>()
+        .try_into()
+        .expect("n <= MaxTeamTiers")
+}
+
+fn team_config<T: Config>(max_depth: u32) -> TeamConfigOf<T> {
+    TeamConfig {
+        tiers: tiers::<T>(T::MaxTeamTiers::get()),
+        period_blocks: PERIOD_BLOCKS,
+        max_depth: max_depth as u8,
+        period_cap: 0,
+    }
+}
+
+/// `d` 层上级的订单记录，并写入周期配置快照
+fn order_record<T: Config>(entity_id: u64, d: u32) -> OrderTeamVolumeOf<T> {
+    TeamPeriodConfigs::<T>::insert((entity_id, 0), team_config::<T>(d));
+    let ancestors: BoundedVec<T::AccountId, T::MaxTeamDepth> = (0..d)
+        .map(|i| account("ancestor", i, 0))
+        .collect::<Vec<_>>()
+        .try_into()
+        .expect("d <= MaxTeamDepth");
+    OrderTeamVolume {
+        entity_id,
+        period: 0,
+        // 每次累计都跨过最高档门槛，逐层重算扣减基数
+        amount: T::MaxTeamTiers::get() as u128 * 10_000,
+        ancestors,
+        above: Some(account("above", 0, 0)),
+    }
+}
+
+#[benchmarks]
+mod benchmarks {
+    use super::*;
+
+    #[benchmark]
+    fn set_team_config(t: Linear<1, { T::MaxTeamTiers::get() }>) -> Result<(), BenchmarkError> {
+        let origin = T::EntityGovernanceOrigin::try_successful_origin()
+            .map_err(|_| BenchmarkError::Weightless)?;
+        let entity_id = T::EntityGovernanceOrigin::ensure_origin(origin.clone())
+            .map_err(|_| BenchmarkError::Weightless)?;
+
+        #[extrinsic_call]
+        _(origin, entity_id, tiers::<T>(t), PERIOD_BLOCKS, 1, 0);
+
+        assert!(TeamConfigs::<T>::contains_key(entity_id));
+        Ok(())
+    }
+
+    #[benchmark]
+    fn clear_team_config() -> Result<(), BenchmarkError> {
+        let origin = T::EntityGovernanceOrigin::try_successful_origin()
+            .map_err(|_| BenchmarkError::Weightless)?;
+        let entity_id = T::EntityGovernanceOrigin::ensure_origin(origin.clone())
+            .map_err(|_| BenchmarkError::Weightless)?;
+        TeamConfigs::<T>::insert(entity_id, team_config::<T>(1));
+
+        #[extrinsic_call]
+        _(origin, entity_id);
+
+        assert!(!TeamConfigs::<T>::contains_key(entity_id));
+        Ok(())
+    }
+
+    #[benchmark]
+    fn settle_team_bonus() {
+        let caller: T::AccountId = whitelisted_caller();
+        let account: T::AccountId = account("member", 0, 0);
+        let entity_id = 1u64;
+        TeamPeriodAnchors::<T>::insert(
+            entity_id,
+            PeriodAnchor { start_block: 0, period_blocks: PERIOD_BLOCKS, first_period: 0 },
+        );
+        TeamPeriodConfigs::<T>::insert((entity_id, 0), team_config::<T>(1));
+        TeamPeriodStats::<T>::insert(
+            (entity_id, 0),
+            &account,
+            TeamPeriodStat { volume: 1_000_000, ..Default::default() },
+        );
+        frame_system::Pallet::<T>::set_block_number(PERIOD_BLOCKS.into());
+
+        #[extrinsic_call]
+        _(RawOrigin::Signed(caller), entity_id, 0, account.clone());
+
+        assert!(TeamPeriodStats::<T>::get((entity_id, 0), &account).settled);
+    }
+
+    #[benchmark]
+    fn record_order(d: Linear<1, { T::MaxTeamDepth::get() }>) {
+        let record = order_record::<T>(1, d);
+        let config = team_config::<T>(d);
+
+        #[block]
+        {
+            Pallet::<T>::apply_volume(&config, &record, true);
+            OrderTeamVolumes::<T>::insert(1, record);
+        }
+
+        assert!(OrderTeamVolumes::<T>::contains_key(1));
+    }
+
+    #[benchmark]
+    fn revert_order(d: Linear<1, { T::MaxTeamDepth::get() }>) {
+        let record = order_record::<T>(1, d);
+        Pallet::<T>::apply_volume(&team_config::<T>(d), &record, true);
+        OrderTeamVolumes::<T>::insert(1, record);
+
+        #[block]
+        {
+            Pallet::<T>::do_revert_order(1);
+        }
+
+        assert!(!OrderTeamVolumes::<T>::contains_key(1));
+    }
+
+    impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,633 @@
+//! # Commission Team Plugin (pallet-commission-team)
+//!
+//! 团队业绩返佣插件：
+//! - 团队 = 会员经 `DirectReferrals` 递归可达的全部下线
+//! - 每笔订单沿推荐链向上累计各上级的本周期团队业绩（按结算周期分桶），订单取消/退款时回退
+//! - 订单完成时不直接发奖，core 按最高档比例从本单预算预留团队奖金池
+//! - 周期结束后按本周期**总业绩**所达档位结算，逐级极差（上级只拿与下级的比例差），经 core 从奖金池入账
+//! - 每个成员每周期奖金封顶（period_cap，0 = 不封顶）
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub use pallet::*;
+
+pub mod weights;
+pub use weights::WeightInfo;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::*;
+    use alloc::vec::Vec;
+    use frame_support::{
+        pallet_prelude::*,
+        traits::{Currency, Get},
+    };
+    use frame_system::pallet_prelude::*;
+    use pallet_commission_common::{MemberProvider, TeamBonusPayer, TeamTier};
+    use pallet_entity_common::{ensure_admin_caller, AdminCaller, EntityProvider};
+    use sp_runtime::{traits::Zero, SaturatedConversion};
+
+    pub type BalanceOf<T> =
+        <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+    // ========================================================================
+    // Config structs
+    // ========================================================================
+
+    /// 团队业绩配置
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+    #[scale_info(skip_type_params(MaxTiers))]
+    pub struct TeamConfig<MaxTiers: Get<u32>> {
+        /// 档位（门槛、比例均严格升序）
+        pub tiers: BoundedVec<TeamTier, MaxTiers>,
+        /// 结算周期（区块数）
+        pub period_blocks: u32,
+        /// 业绩向上累计的最大层数
+        pub max_depth: u8,
+        /// 每个成员每周期奖金上限（0 = 不封顶）
+        pub period_cap: u128,
+    }
+
+    impl<MaxTiers: Get<u32>> TeamConfig<MaxTiers> {
+        /// 团队业绩对应的档位比例（未达最低档返回 0）
+        pub fn rate_for_volume(&self, volume: u128) -> u16 {
+            self.tiers.iter()
+                .rev()
+                .find(|t| volume >= t.threshold)
+                .map(|t| t.rate)
+                .unwrap_or(0)
+        }
+
+        /// 最高档比例（每单团队奖金预留比例）
+        pub fn top_rate(&self) -> u16 {
+            self.tiers.last().map(|t| t.rate).unwrap_or(0)
+        }
+    }
+
+    pub type TeamConfigOf<T> = TeamConfig<<T as Config>::MaxTeamTiers>;
+
+    /// 周期锚点：自 `start_block` 起每 `period_blocks` 个区块为一个周期，首个周期序号为 `first_period`
+    ///
+    /// 修改周期长度时从当前周期结束处重新锚定，周期序号单调递增，不会与已结束的周期重号。
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+    pub struct PeriodAnchor {
+        pub start_block: u32,
+        pub period_blocks: u32,
+        pub first_period: u32,
+    }
+
+    impl PeriodAnchor {
+        /// `now` 所在的周期序号（新锚点生效前仍处于上一锚点的最后一个周期）
+        pub fn period_at(&self, now: u32) -> u32 {
+            match now.checked_sub(self.start_block) {
+                Some(elapsed) => self.first_period.saturating_add(elapsed / self.period_blocks.max(1)),
+                None => self.first_period.saturating_sub(1),
+            }
+        }
+
+        /// 周期 `period`（≥ `first_period`）的起始区块
+        pub fn period_start(&self, period: u32) -> u32 {
+            self.start_block.saturating_add(
+                period.saturating_sub(self.first_period).saturating_mul(self.period_blocks),
+            )
+        }
+    }
+
+    /// 成员在某结算周期内的团队业绩与结算状态
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, TypeInfo, MaxEncodedLen, RuntimeDebug, Default)]
+    pub struct TeamPeriodStat {
+        /// 本周期团队业绩
+        pub volume: u128,
+        /// 极差扣减基数：Σ 直属下级当前档位比例 × 经该下级流入的业绩（基点 × 金额）
+        pub downline_base: u128,
+        /// 已结算
+        pub settled: bool,
+        /// 结算入账的奖金
+        pub bonus_paid: u128,
+    }
+
+    /// 订单的团队业绩累计记录（取消/退款时回退）
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+    #[scale_info(skip_type_params(MaxDepth))]
+    pub struct OrderTeamVolume<AccountId, MaxDepth: Get<u32>> {
+        pub entity_id: u64,
+        pub period: u32,
+        pub amount: u128,
+        /// 由近及远的上级（p1 = 买家推荐人）
+        pub ancestors: BoundedVec<AccountId, MaxDepth>,
+        /// 超出累计层数的下一位上级（其极差扣减随最远上级档位变化而调整）
+        pub above: Option<AccountId>,
+    }
+
+    pub type OrderTeamVolumeOf<T> =
+        OrderTeamVolume<<T as frame_system::Config>::AccountId, <T as Config>::MaxTeamDepth>;
+
+    // ========================================================================
+    // Pallet Config
+    // ========================================================================
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+        type Currency: Currency<Self::AccountId>;
+        type MemberProvider: MemberProvider<Self::AccountId>;
+        type EntityProvider: EntityProvider<Self::AccountId>;
+
+        /// 实体治理来源（`EntityOrigin::Governance`，成功返回 entity_id）
+        type EntityGovernanceOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = u64>;
+
+        /// 周期团队奖金入账（commission-core 团队奖金池）
+        type BonusPayer: TeamBonusPayer<Self::AccountId, BalanceOf<Self>>;
+
+        /// 最大档位数
+        #[pallet::constant]
+        type MaxTeamTiers: Get<u32>;
+
+        /// 业绩向上累计的最大层数上限
+        #[pallet::constant]
+        type MaxTeamDepth: Get<u32>;
+
+        /// 权重信息
+        type WeightInfo: WeightInfo;
+    }
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(_);
+
+    // ========================================================================
+    // Storage
+    // ========================================================================
+
+    /// 团队业绩配置 entity_id -> TeamConfig
+    #[pallet::storage]
+    #[pallet::getter(fn team_config)]
+    pub type TeamConfigs<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat, u64,
+        TeamConfigOf<T>,
+    >;
+
+    /// 周期锚点 entity_id -> PeriodAnchor（清除配置后保留，重新配置时周期序号延续）
+    #[pallet::storage]
+    pub type TeamPeriodAnchors<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat, u64,
+        PeriodAnchor,
+    >;
+
+    /// 周期配置快照 (entity_id, period) -> TeamConfig
+    ///
+    /// 周期内首笔订单时写入，累计、回退与结算均按快照计算，周期中途改配置从下一周期生效。
+    #[pallet::storage]
+    pub type TeamPeriodConfigs<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat, (u64, u32),
+        TeamConfigOf<T>,
+    >;
+
+    /// 成员周期业绩 (entity_id, period), account -> TeamPeriodStat
+    #[pallet::storage]
+    pub type TeamPeriodStats<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat, (u64, u32),
+        Blake2_128Concat, T::AccountId,
+        TeamPeriodStat,
+        ValueQuery,
+    >;
+
+    /// 经直属下级流入的周期业绩 (entity_id, period, account), child -> volume
+    ///
+    /// 不含下级本人的消费（下级本人消费时其比例视为 0）。
+    #[pallet::storage]
+    pub type TeamChildVolumes<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat, (u64, u32, T::AccountId),
+        Blake2_128Concat, T::AccountId,
+        u128,
+        ValueQuery,
+    >;
+
+    /// 订单业绩记录 order_id -> OrderTeamVolume
+    #[pallet::storage]
+    pub type OrderTeamVolumes<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat, u64,
+        OrderTeamVolumeOf<T>,
+    >;
+
+    // ========================================================================
+    // Events / Errors
+    // ========================================================================
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        TeamConfigUpdated { entity_id: u64 },
+        TeamConfigCleared { entity_id: u64 },
+        /// 订单团队业绩已回退
+        TeamVolumeReverted { entity_id: u64, period: u32, order_id: u64 },
+        /// 周期团队奖金已结算
+        TeamBonusSettled {
+            entity_id: u64,
+            period: u32,
+            account: T::AccountId,
+            volume: u128,
+            bonus: BalanceOf<T>,
+        },
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        EntityNotFound,
+        NotEntityOwner,
+        EmptyTiers,
+        TooManyTiers,
+        InvalidRate,
+        /// 档位门槛或比例未严格升序
+        TiersNotAscending,
+        InvalidPeriod,
+        InvalidMaxDepth,
+        /// 该周期无团队业绩记录
+        PeriodNotFound,
+        /// 周期尚未结束
+        PeriodNotEnded,
+        /// 该成员本周期已结算
+        AlreadySettled,
+        /// 该成员本周期无团队业绩
+        NoTeamVolume,
+    }
+
+    // ========================================================================
+    // Extrinsics
+    // ========================================================================
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// 设置团队业绩配置（Entity owner 或实体治理）
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::WeightInfo::set_team_config(tiers.len() as u32))]
+        pub fn set_team_config(
+            origin: OriginFor<T>,
+            entity_id: u64,
+            tiers: BoundedVec<TeamTier, T::MaxTeamTiers>,
+            period_blocks: u32,
+            max_depth: u8,
+            period_cap: u128,
+        ) -> DispatchResult {
+            Self::ensure_entity_admin(origin, entity_id)?;
+            Self::do_set_team_config(entity_id, tiers.into_inner(), period_blocks, max_depth, period_cap)
+        }
+
+        /// 清除团队业绩配置（已累计的周期按快照照常结算）
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::WeightInfo::clear_team_config())]
+        pub fn clear_team_config(
+            origin: OriginFor<T>,
+            entity_id: u64,
+        ) -> DispatchResult {
+            Self::ensure_entity_admin(origin, entity_id)?;
+
+            TeamConfigs::<T>::remove(entity_id);
+
+            Self::deposit_event(Event::TeamConfigCleared { entity_id });
+            Ok(())
+        }
+
+        /// 结算成员某一已结束周期的团队奖金（任何人可触发，奖金入账给 `account`）
+        ///
+        /// 奖金 = (档位比例 × 周期总业绩 − 极差扣减基数) / 10000，受 period_cap 封顶；
+        /// 奖金池不足时按池内余额入账。
+        #[pallet::call_index(2)]
+        #[pallet::weight(T::WeightInfo::settle_team_bonus())]
+        pub fn settle_team_bonus(
+            origin: OriginFor<T>,
+            entity_id: u64,
+            period: u32,
+            account: T::AccountId,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            let config = TeamPeriodConfigs::<T>::get((entity_id, period))
+                .ok_or(Error::<T>::PeriodNotFound)?;
+            let current = Self::period_index(entity_id).ok_or(Error::<T>::PeriodNotFound)?;
+            ensure!(period < current, Error::<T>::PeriodNotEnded);
+
+            let stat = TeamPeriodStats::<T>::get((entity_id, period), &account);
+            ensure!(!stat.settled, Error::<T>::AlreadySettled);
+            ensure!(stat.volume > 0, Error::<T>::NoTeamVolume);
+
+            let bonus = Self::period_bonus(&config, &stat);
+            let paid = if bonus.is_zero() {
+                Zero::zero()
+            } else {
+                T::BonusPayer::pay_team_bonus(entity_id, &account, bonus.saturated_into(), period)?
+            };
+
+            TeamPeriodStats::<T>::mutate((entity_id, period), &account, |s| {
+                s.settled = true;
+                s.bonus_paid = paid.saturated_into();
+            });
+
+            Self::deposit_event(Event::TeamBonusSettled {
+                entity_id,
+                period,
+                account,
+                volume: stat.volume,
+                bonus: paid,
+            });
+            Ok(())
+        }
+    }
+
+    // ========================================================================
+    // Internal functions
+    // ========================================================================
+
+    impl<T: Config> Pallet<T> {
+        /// 校验 Entity owner 或对应实体的治理来源
+        fn ensure_entity_admin(origin: OriginFor<T>, entity_id: u64) -> DispatchResult {
+            match ensure_admin_caller::<_, _, T::EntityGovernanceOrigin>(origin)? {
+                AdminCaller::Signed(who) => {
+                    let owner = T::EntityProvider::entity_owner(entity_id)
+                        .ok_or(Error::<T>::EntityNotFound)?;
+                    ensure!(owner == who, Error::<T>::NotEntityOwner);
+                },
+                AdminCaller::Governance(gov_entity) => {
+                    ensure!(gov_entity == entity_id, Error::<T>::NotEntityOwner);
+                },
+            }
+            Ok(())
+        }
+
+        /// 校验并写入团队业绩配置（extrinsic 与 TeamPlanWriter 共用）
+        pub fn do_set_team_config(
+            entity_id: u64,
+            tiers: Vec<TeamTier>,
+            period_blocks: u32,
+            max_depth: u8,
+            period_cap: u128,
+        ) -> DispatchResult {
+            ensure!(!tiers.is_empty(), Error::<T>::EmptyTiers);
+            ensure!(tiers.iter().all(|t| t.rate <= 10000), Error::<T>::InvalidRate);
+            ensure!(
+                tiers.windows(2).all(|w| w[0].threshold < w[1].threshold && w[0].rate < w[1].rate),
+                Error::<T>::TiersNotAscending
+            );
+            ensure!(period_blocks > 0, Error::<T>::InvalidPeriod);
+            ensure!(
+                max_depth > 0 && (max_depth as u32) <= T::MaxTeamDepth::get(),
+                Error::<T>::InvalidMaxDepth
+            );
+
+            let tiers: BoundedVec<TeamTier, T::MaxTeamTiers> = tiers
+                .try_into()
+                .map_err(|_| Error::<T>::TooManyTiers)?;
+
+            // 周期长度变化时，新长度从当前周期结束后开始计
+            let now = Self::now();
+            TeamPeriodAnchors::<T>::mutate(entity_id, |anchor| match anchor {
+                Some(a) if a.period_blocks == period_blocks => {},
+                Some(a) => {
+                    let next = a.period_at(now).saturating_add(1);
+                    *a = PeriodAnchor { start_block: a.period_start(next), period_blocks, first_period: next };
+                },
+                None => *anchor = Some(PeriodAnchor { start_block: 0, period_blocks, first_period: 0 }),
+            });
+
+            TeamConfigs::<T>::insert(entity_id, TeamConfig {
+                tiers,
+                period_blocks,
+                max_depth,
+                period_cap,
+            });
+
+            Self::deposit_event(Event::TeamConfigUpdated { entity_id });
+            Ok(())
+        }
+
+        fn now() -> u32 {
+            <frame_system::Pallet<T>>::block_number().saturated_into()
+        }
+
+        /// 按周期锚点计算当前周期序号（从未配置过返回 None）
+        fn period_index(entity_id: u64) -> Option<u32> {
+            TeamPeriodAnchors::<T>::get(entity_id).map(|anchor| anchor.period_at(Self::now()))
+        }
+
+        /// 当前周期序号（未配置返回 None）
+        pub fn current_period(entity_id: u64) -> Option<u32> {
+            if !TeamConfigs::<T>::contains_key(entity_id) {
+                return None;
+            }
+            Self::period_index(entity_id)
+        }
+
+        /// 成员本周期团队业绩
+        pub fn team_volume(entity_id: u64, account: &T::AccountId) -> u128 {
+            let Some(period) = Self::current_period(entity_id) else { return 0 };
+            TeamPeriodStats::<T>::get((entity_id, period), account).volume
+        }
+
+        /// 按周期总业绩计算极差奖金（已封顶）
+        pub fn period_bonus(config: &TeamConfigOf<T>, stat: &TeamPeriodStat) -> u128 {
+            let rate = config.rate_for_volume(stat.volume) as u128;
+            let bonus = stat.volume
+                .saturating_mul(rate)
+                .saturating_sub(stat.downline_base)
+                / 10000;
+            if config.period_cap > 0 { bonus.min(config.period_cap) } else { bonus }
+        }
+
+        /// 订单完成：累计各上级本周期团队业绩
+        ///
+        /// 返回需预留的团队奖金（最高档比例 × 订单金额）与按实际累计层数计算的权重
+        pub fn do_record_order(
+            entity_id: u64,
+            shop_id: u64,
+            order_id: u64,
+            buyer: &T::AccountId,
+            order_amount: u128,
+        ) -> (u128, Weight) {
+            let Some(current) = TeamConfigs::<T>::get(entity_id) else {
+                return (0, T::DbWeight::get().reads(1));
+            };
+            let Some(period) = Self::period_index(entity_id) else {
+                return (0, T::DbWeight::get().reads(2));
+            };
+            let config = match TeamPeriodConfigs::<T>::get((entity_id, period)) {
+                Some(c) => c,
+                None => {
+                    TeamPeriodConfigs::<T>::insert((entity_id, period), &current);
+                    current
+                },
+            };
+
+            let mut ancestors: BoundedVec<T::AccountId, T::MaxTeamDepth> = BoundedVec::new();
+            let mut next = T::MemberProvider::get_referrer(shop_id, buyer);
+            while let Some(ancestor) = next.take() {
+                if ancestors.len() >= config.max_depth as usize {
+                    next = Some(ancestor);
+                    break;
+                }
+                next = T::MemberProvider::get_referrer(shop_id, &ancestor);
+                if ancestors.try_push(ancestor).is_err() {
+                    break;
+                }
+            }
+            if ancestors.is_empty() {
+                return (0, T::WeightInfo::record_order(0));
+            }
+            let weight = T::WeightInfo::record_order(ancestors.len() as u32);
+
+            let record = OrderTeamVolume {
+                entity_id,
+                period,
+                amount: order_amount,
+                ancestors,
+                above: next,
+            };
+            Self::apply_volume(&config, &record, true);
+            OrderTeamVolumes::<T>::insert(order_id, record);
+
+            (order_amount.saturating_mul(config.top_rate() as u128) / 10000, weight)
+        }
+
+        /// 订单取消/退款：回退该订单累计的团队业绩（已结算的奖金不追回），返回消耗的权重
+        pub fn do_revert_order(order_id: u64) -> Weight {
+            let Some(record) = OrderTeamVolumes::<T>::take(order_id) else {
+                return T::DbWeight::get().reads(1);
+            };
+            let weight = T::WeightInfo::revert_order(record.ancestors.len() as u32);
+            let Some(config) = TeamPeriodConfigs::<T>::get((record.entity_id, record.period)) else {
+                return weight;
+            };
+            Self::apply_volume(&config, &record, false);
+            Self::deposit_event(Event::TeamVolumeReverted {
+                entity_id: record.entity_id,
+                period: record.period,
+                order_id,
+            });
+            weight
+        }
+
+        /// 沿上级链增减业绩，并同步维护各上级的极差扣减基数
+        ///
+        /// 上级 A 的扣减基数 = Σ 直属下级 c 的当前档位比例 × 经 c 流入 A 的业绩；
+        /// 下级档位变化时按其流入业绩整体重算对应项，保证结算时按最终档位计差。
+        pub(crate) fn apply_volume(config: &TeamConfigOf<T>, record: &OrderTeamVolumeOf<T>, add: bool) {
+            let key = (record.entity_id, record.period);
+            let amount = record.amount;
+            let adjust = |v: u128| if add { v.saturating_add(amount) } else { v.saturating_sub(amount) };
+
+            // 上一位（更近的）上级的 (旧比例, 新比例)
+            let mut child_rates: Option<(u128, u128)> = None;
+
+            for (i, ancestor) in record.ancestors.iter().enumerate() {
+                let mut stat = TeamPeriodStats::<T>::get(key, ancestor);
+                let old_rate = config.rate_for_volume(stat.volume) as u128;
+                stat.volume = adjust(stat.volume);
+                let new_rate = config.rate_for_volume(stat.volume) as u128;
+
+                if let (Some(child), Some((child_old, child_new))) =
+                    (i.checked_sub(1).and_then(|j| record.ancestors.get(j)), child_rates)
+                {
+                    let flow_key = (record.entity_id, record.period, ancestor.clone());
+                    let old_flow = TeamChildVolumes::<T>::get(&flow_key, child);
+                    let new_flow = adjust(old_flow);
+                    stat.downline_base = stat.downline_base
+                        .saturating_sub(child_old.saturating_mul(old_flow))
+                        .saturating_add(child_new.saturating_mul(new_flow));
+                    if new_flow == 0 {
+                        TeamChildVolumes::<T>::remove(&flow_key, child);
+                    } else {
+                        TeamChildVolumes::<T>::insert(&flow_key, child, new_flow);
+                    }
+                }
+
+                TeamPeriodStats::<T>::insert(key, ancestor, stat);
+                child_rates = Some((old_rate, new_rate));
+            }
+
+            // 最远上级档位变化时，调整其上一级对经它流入的既有业绩的扣减
+            if let (Some(above), Some(top), Some((old_rate, new_rate))) =
+                (&record.above, record.ancestors.last(), child_rates)
+            {
+                if old_rate != new_rate {
+                    let flow = TeamChildVolumes::<T>::get(
+                        (record.entity_id, record.period, above.clone()), top,
+                    );
+                    if flow > 0 {
+                        TeamPeriodStats::<T>::mutate(key, above, |stat| {
+                            stat.downline_base = stat.downline_base
+                                .saturating_sub(old_rate.saturating_mul(flow))
+                                .saturating_add(new_rate.saturating_mul(flow));
+                        });
+                    }
+                }
+            }
+        }
+    }
+}
+
+// ============================================================================
+// TeamPerformancePlugin implementation
+// ============================================================================
+
+impl<T: pallet::Config> pallet_commission_common::TeamPerformancePlugin<T::AccountId, pallet::BalanceOf<T>> for pallet::Pallet<T> {
+    fn record_order(
+        entity_id: u64,
+        shop_id: u64,
+        order_id: u64,
+        buyer: &T::AccountId,
+        order_amount: pallet::BalanceOf<T>,
+        remaining: pallet::BalanceOf<T>,
+        enabled_modes: pallet_commission_common::CommissionModes,
+    ) -> (pallet::BalanceOf<T>, frame_support::weights::Weight) {
+        use pallet_commission_common::CommissionModes;
+        use sp_runtime::SaturatedConversion;
+
+        if !enabled_modes.contains(CommissionModes::TEAM_PERFORMANCE) {
+            return (Default::default(), Default::default());
+        }
+
+        // entity_id for config/volume lookup, shop_id for MemberProvider
+        let (reserve, weight) = pallet::Pallet::<T>::do_record_order(
+            entity_id, shop_id, order_id, buyer, order_amount.saturated_into(),
+        );
+        (reserve.saturated_into::<pallet::BalanceOf<T>>().min(remaining), weight)
+    }
+
+    fn revert_order(order_id: u64) -> frame_support::weights::Weight {
+        pallet::Pallet::<T>::do_revert_order(order_id)
+    }
+}
+
+// ============================================================================
+// TeamPlanWriter implementation
+// ============================================================================
+
+impl<T: pallet::Config> pallet_commission_common::TeamPlanWriter for pallet::Pallet<T> {
+    fn set_team_config(
+        entity_id: u64,
+        tiers: alloc::vec::Vec<pallet_commission_common::TeamTier>,
+        period_blocks: u32,
+        max_depth: u8,
+        period_cap: u128,
+    ) -> Result<(), sp_runtime::DispatchError> {
+        pallet::Pallet::<T>::do_set_team_config(entity_id, tiers, period_blocks, max_depth, period_cap)
+    }
+
+    fn clear_config(entity_id: u64) -> Result<(), sp_runtime::DispatchError> {
+        pallet::TeamConfigs::<T>::remove(entity_id);
+        Ok(())
+    }
+}
@@ -0,0 +1,218 @@
+use crate as pallet_commission_team;
+use frame_support::{derive_impl, traits::ConstU32};
+use pallet_commission_common::{MemberProvider, TeamBonusPayer};
+use sp_runtime::{BuildStorage, DispatchError};
+use std::cell::RefCell;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Test accounts — 推荐链：ALICE ← BOB ← CAROL ← DAVID，BOB ← EVE
+pub const ALICE: u64 = 1;
+pub const BOB: u64 = 2;
+pub const CAROL: u64 = 3;
+pub const DAVID: u64 = 4;
+pub const EVE: u64 = 5;
+pub const OWNER: u64 = 10;
+
+// Test IDs
+pub const ENTITY_1: u64 = 100;
+pub const SHOP_1: u64 = 1000;
+
+/// 结算周期（区块数）
+pub const PERIOD_BLOCKS: u32 = 100;
+
+frame_support::construct_runtime!(
+    pub enum Test {
+        System: frame_system,
+        Balances: pallet_balances,
+        CommissionTeam: pallet_commission_team,
+    }
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+    type Block = Block;
+    type AccountData = pallet_balances::AccountData<u128>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+    type AccountStore = System;
+    type Balance = u128;
+}
+
+// ============================================================================
+// Mock MemberProvider：固定推荐关系
+// ============================================================================
+
+pub struct MockMemberProvider;
+
+impl MemberProvider<u64> for MockMemberProvider {
+    fn is_member(_: u64, account: &u64) -> bool {
+        (ALICE..=EVE).contains(account)
+    }
+    fn get_referrer(_: u64, account: &u64) -> Option<u64> {
+        match *account {
+            BOB => Some(ALICE),
+            CAROL => Some(BOB),
+            DAVID => Some(CAROL),
+            EVE => Some(BOB),
+            _ => None,
+        }
+    }
+    fn member_level(_: u64, _: &u64) -> Option<pallet_entity_common::MemberLevel> { None }
+    fn get_member_stats(_: u64, _: &u64) -> (u32, u32, u128) { (0, 0, 0) }
+    fn uses_custom_levels(_: u64) -> bool { false }
+    fn custom_level_id(_: u64, _: &u64) -> u8 { 0 }
+    fn auto_register(_: u64, _: &u64, _: Option<u64>) -> Result<(), DispatchError> { Ok(()) }
+    fn set_custom_levels_enabled(_: u64, _: bool) -> Result<(), DispatchError> { Ok(()) }
+    fn set_upgrade_mode(_: u64, _: u8) -> Result<(), DispatchError> { Ok(()) }
+    fn add_custom_level(_: u64, _: u8, _: &[u8], _: u128, _: u16, _: u16) -> Result<(), DispatchError> { Ok(()) }
+    fn update_custom_level(_: u64, _: u8, _: Option<&[u8]>, _: Option<u128>, _: Option<u16>, _: Option<u16>) -> Result<(), DispatchError> { Ok(()) }
+    fn remove_custom_level(_: u64, _: u8) -> Result<(), DispatchError> { Ok(()) }
+    fn custom_level_count(_: u64) -> u8 { 0 }
+}
+
+// ============================================================================
+// Mock EntityProvider
+// ============================================================================
+
+pub struct MockEntityProvider;
+
+impl pallet_entity_common::EntityProvider<u64> for MockEntityProvider {
+    fn entity_exists(entity_id: u64) -> bool {
+        entity_id == ENTITY_1
+    }
+
+    fn is_entity_active(entity_id: u64) -> bool {
+        entity_id == ENTITY_1
+    }
+
+    fn entity_status(entity_id: u64) -> Option<pallet_entity_common::EntityStatus> {
+        if entity_id == ENTITY_1 {
+            Some(pallet_entity_common::EntityStatus::Active)
+        } else {
+            None
+        }
+    }
+
+    fn entity_owner(entity_id: u64) -> Option<u64> {
+        if entity_id == ENTITY_1 {
+            Some(OWNER)
+        } else {
+            None
+        }
+    }
+
+    fn entity_account(_entity_id: u64) -> u64 {
+        0
+    }
+
+    fn update_entity_stats(
+        _entity_id: u64,
+        _sales_amount: u128,
+        _order_count: u32,
+    ) -> Result<(), DispatchError> {
+        Ok(())
+    }
+
+    fn update_entity_rating(
+        _entity_id: u64,
+        _rating: u8,
+    ) -> Result<(), DispatchError> {
+        Ok(())
+    }
+}
+
+/// 模拟实体治理来源：不授予任何治理权限（基准测试中以 Root 充当 ENTITY_1 的治理来源）
+pub struct MockEntityGovernance;
+impl frame_support::traits::EnsureOrigin<RuntimeOrigin> for MockEntityGovernance {
+    type Success = u64;
+    fn try_origin(o: RuntimeOrigin) -> Result<u64, RuntimeOrigin> {
+        #[cfg(feature = "runtime-benchmarks")]
+        {
+            let raw: Result<frame_system::RawOrigin<u64>, RuntimeOrigin> = o.clone().into();
+            if matches!(raw, Ok(frame_system::RawOrigin::Root)) {
+                return Ok(ENTITY_1);
+            }
+        }
+        Err(o)
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn try_successful_origin() -> Result<RuntimeOrigin, ()> {
+        Ok(RuntimeOrigin::root())
+    }
+}
+
+// ============================================================================
+// Mock TeamBonusPayer：模拟 core 团队奖金池
+// ============================================================================
+
+thread_local! {
+    /// 奖金池余额
+    pub static BONUS_POOL: RefCell<u128> = RefCell::new(u128::MAX);
+    /// 已入账 (beneficiary, amount, period)
+    pub static PAID_BONUSES: RefCell<Vec<(u64, u128, u32)>> = RefCell::new(Vec::new());
+}
+
+pub fn set_bonus_pool(amount: u128) {
+    BONUS_POOL.with(|p| *p.borrow_mut() = amount);
+}
+
+pub fn paid_bonuses() -> Vec<(u64, u128, u32)> {
+    PAID_BONUSES.with(|p| p.borrow().clone())
+}
+
+pub struct MockBonusPayer;
+
+impl TeamBonusPayer<u64, u128> for MockBonusPayer {
+    fn pay_team_bonus(_entity_id: u64, beneficiary: &u64, amount: u128, period: u32) -> Result<u128, DispatchError> {
+        let actual = BONUS_POOL.with(|p| {
+            let mut pool = p.borrow_mut();
+            let actual = amount.min(*pool);
+            *pool -= actual;
+            actual
+        });
+        PAID_BONUSES.with(|p| p.borrow_mut().push((*beneficiary, actual, period)));
+        Ok(actual)
+    }
+}
+
+// ============================================================================
+// Pallet Config
+// ============================================================================
+
+impl pallet_commission_team::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type Currency = Balances;
+    type MemberProvider = MockMemberProvider;
+    type EntityProvider = MockEntityProvider;
+    type EntityGovernanceOrigin = MockEntityGovernance;
+    type BonusPayer = MockBonusPayer;
+    type MaxTeamTiers = ConstU32<5>;
+    type MaxTeamDepth = ConstU32<10>;
+    type WeightInfo = ();
+}
+
+// ============================================================================
+// Test Helpers
+// ============================================================================
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let t = frame_system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .unwrap();
+
+    let mut ext = sp_io::TestExternalities::new(t);
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        set_bonus_pool(u128::MAX);
+        PAID_BONUSES.with(|p| p.borrow_mut().clear());
+    });
+    ext
+}
+
+pub fn run_to_block(n: u64) {
+    System::set_block_number(n);
+}
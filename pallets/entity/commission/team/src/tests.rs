@@ -0,0 +1,334 @@
+//! Unit tests for pallet-commission-team
+
+use crate::{mock::*, Error, Event, OrderTeamVolumes, TeamChildVolumes, TeamPeriodStats};
+use frame_support::{assert_noop, assert_ok, traits::ConstU32, BoundedVec};
+use pallet_commission_common::{CommissionModes, TeamPerformancePlugin, TeamTier};
+
+/// 档位：≥1万 → 1%，≥5万 → 3%
+fn tiers() -> BoundedVec<TeamTier, ConstU32<5>> {
+    vec![
+        TeamTier { threshold: 10_000, rate: 100 },
+        TeamTier { threshold: 50_000, rate: 300 },
+    ]
+    .try_into()
+    .unwrap()
+}
+
+fn configure(max_depth: u8, period_cap: u128) {
+    assert_ok!(CommissionTeam::set_team_config(
+        RuntimeOrigin::signed(OWNER),
+        ENTITY_1,
+        tiers(),
+        PERIOD_BLOCKS,
+        max_depth,
+        period_cap,
+    ));
+}
+
+/// 以团队业绩模式完成订单，返回本单预留的团队奖金
+fn order(order_id: u64, buyer: u64, amount: u128) -> u128 {
+    <CommissionTeam as TeamPerformancePlugin<u64, u128>>::record_order(
+        ENTITY_1,
+        SHOP_1,
+        order_id,
+        &buyer,
+        amount,
+        u128::MAX,
+        CommissionModes(CommissionModes::TEAM_PERFORMANCE),
+    )
+    .0
+}
+
+fn stat(period: u32, who: u64) -> crate::TeamPeriodStat {
+    TeamPeriodStats::<Test>::get((ENTITY_1, period), who)
+}
+
+fn settle(period: u32, who: u64) -> frame_support::dispatch::DispatchResult {
+    CommissionTeam::settle_team_bonus(RuntimeOrigin::signed(EVE), ENTITY_1, period, who)
+}
+
+// ============================================================================
+// 配置
+// ============================================================================
+
+#[test]
+fn set_team_config_validates_owner_and_tiers() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            CommissionTeam::set_team_config(
+                RuntimeOrigin::signed(ALICE), ENTITY_1, tiers(), PERIOD_BLOCKS, 5, 0,
+            ),
+            Error::<Test>::NotEntityOwner
+        );
+
+        let descending: BoundedVec<TeamTier, ConstU32<5>> = vec![
+            TeamTier { threshold: 50_000, rate: 300 },
+            TeamTier { threshold: 10_000, rate: 100 },
+        ]
+        .try_into()
+        .unwrap();
+        assert_noop!(
+            CommissionTeam::set_team_config(
+                RuntimeOrigin::signed(OWNER), ENTITY_1, descending, PERIOD_BLOCKS, 5, 0,
+            ),
+            Error::<Test>::TiersNotAscending
+        );
+        assert_noop!(
+            CommissionTeam::set_team_config(
+                RuntimeOrigin::signed(OWNER), ENTITY_1, tiers(), PERIOD_BLOCKS, 0, 0,
+            ),
+            Error::<Test>::InvalidMaxDepth
+        );
+    });
+}
+
+// ============================================================================
+// 业绩累计与预留
+// ============================================================================
+
+#[test]
+fn record_order_accumulates_volume_and_reserves_top_rate() {
+    new_test_ext().execute_with(|| {
+        configure(10, 0);
+
+        // 最高档 3% 预留，不直接发奖
+        assert_eq!(order(1, DAVID, 30_000), 900);
+        assert!(paid_bonuses().is_empty());
+
+        assert_eq!(stat(0, CAROL).volume, 30_000);
+        assert_eq!(stat(0, BOB).volume, 30_000);
+        assert_eq!(stat(0, ALICE).volume, 30_000);
+        assert_eq!(stat(0, DAVID).volume, 0);
+        // 买家本人消费不计入流入业绩；经 CAROL 流入 BOB
+        assert_eq!(TeamChildVolumes::<Test>::get((ENTITY_1, 0, CAROL), DAVID), 0);
+        assert_eq!(TeamChildVolumes::<Test>::get((ENTITY_1, 0, BOB), CAROL), 30_000);
+        assert!(OrderTeamVolumes::<Test>::contains_key(1));
+
+        // 预留不超过剩余额度
+        let (reserved, _) = <CommissionTeam as TeamPerformancePlugin<u64, u128>>::record_order(
+            ENTITY_1, SHOP_1, 2, &DAVID, 30_000, 500,
+            CommissionModes(CommissionModes::TEAM_PERFORMANCE),
+        );
+        assert_eq!(reserved, 500);
+    });
+}
+
+#[test]
+fn record_and_revert_weights_scale_with_ancestor_depth() {
+    use crate::WeightInfo;
+
+    new_test_ext().execute_with(|| {
+        configure(10, 0);
+        let modes = CommissionModes(CommissionModes::TEAM_PERFORMANCE);
+
+        // DAVID → [CAROL, BOB, ALICE]；BOB → [ALICE]
+        let (_, deep) = <CommissionTeam as TeamPerformancePlugin<u64, u128>>::record_order(
+            ENTITY_1, SHOP_1, 1, &DAVID, 30_000, u128::MAX, modes,
+        );
+        let (_, shallow) = <CommissionTeam as TeamPerformancePlugin<u64, u128>>::record_order(
+            ENTITY_1, SHOP_1, 2, &BOB, 30_000, u128::MAX, modes,
+        );
+        assert_eq!(deep, <() as WeightInfo>::record_order(3));
+        assert_eq!(shallow, <() as WeightInfo>::record_order(1));
+        assert!(deep.all_gt(shallow));
+
+        assert_eq!(
+            <CommissionTeam as TeamPerformancePlugin<u64, u128>>::revert_order(1),
+            <() as WeightInfo>::revert_order(3)
+        );
+    });
+}
+
+#[test]
+fn record_order_is_skipped_when_mode_disabled() {
+    new_test_ext().execute_with(|| {
+        configure(10, 0);
+
+        let (reserved, _) = <CommissionTeam as TeamPerformancePlugin<u64, u128>>::record_order(
+            ENTITY_1, SHOP_1, 1, &DAVID, 30_000, u128::MAX,
+            CommissionModes(CommissionModes::DIRECT_REWARD),
+        );
+
+        assert_eq!(reserved, 0);
+        assert_eq!(stat(0, CAROL).volume, 0);
+        assert!(!OrderTeamVolumes::<Test>::contains_key(1));
+    });
+}
+
+// ============================================================================
+// 周期结算
+// ============================================================================
+
+#[test]
+fn settle_pays_differential_bonus_on_total_period_volume() {
+    new_test_ext().execute_with(|| {
+        configure(10, 0);
+
+        // 首单时 CAROL 只在 1% 档，周期结束按总业绩 6 万整体适用 3%
+        order(1, DAVID, 30_000);
+        order(2, DAVID, 30_000);
+        order(3, EVE, 20_000);
+
+        assert_noop!(settle(0, CAROL), Error::<Test>::PeriodNotEnded);
+
+        run_to_block(PERIOD_BLOCKS as u64);
+
+        // CAROL: 60000 × 3% = 1800
+        assert_ok!(settle(0, CAROL));
+        // BOB: 80000 × 3% − 经 CAROL 流入 60000 × 3% = 600
+        assert_ok!(settle(0, BOB));
+        // ALICE: 80000 × 3% − 经 BOB 流入 80000 × 3% = 0
+        assert_ok!(settle(0, ALICE));
+
+        assert_eq!(paid_bonuses(), vec![(CAROL, 1_800, 0), (BOB, 600, 0)]);
+        assert!(stat(0, ALICE).settled);
+        assert_eq!(stat(0, BOB).bonus_paid, 600);
+        System::assert_has_event(RuntimeEvent::CommissionTeam(Event::TeamBonusSettled {
+            entity_id: ENTITY_1,
+            period: 0,
+            account: CAROL,
+            volume: 60_000,
+            bonus: 1_800,
+        }));
+
+        assert_noop!(settle(0, CAROL), Error::<Test>::AlreadySettled);
+        assert_noop!(settle(0, DAVID), Error::<Test>::NoTeamVolume);
+        assert_noop!(settle(5, CAROL), Error::<Test>::PeriodNotFound);
+    });
+}
+
+#[test]
+fn revert_order_removes_volume_before_settlement() {
+    new_test_ext().execute_with(|| {
+        configure(10, 0);
+        order(1, DAVID, 30_000);
+        order(2, DAVID, 30_000);
+        order(3, EVE, 20_000);
+
+        <CommissionTeam as TeamPerformancePlugin<u64, u128>>::revert_order(2);
+
+        assert!(!OrderTeamVolumes::<Test>::contains_key(2));
+        assert_eq!(stat(0, CAROL).volume, 30_000);
+        assert_eq!(stat(0, BOB).volume, 50_000);
+        assert_eq!(TeamChildVolumes::<Test>::get((ENTITY_1, 0, BOB), CAROL), 30_000);
+        // 重复回退无副作用
+        <CommissionTeam as TeamPerformancePlugin<u64, u128>>::revert_order(2);
+        assert_eq!(stat(0, BOB).volume, 50_000);
+
+        run_to_block(PERIOD_BLOCKS as u64);
+        // CAROL 回落到 1%：30000 × 1% = 300
+        assert_ok!(settle(0, CAROL));
+        // BOB: 50000 × 3% − 30000 × 1% = 1200
+        assert_ok!(settle(0, BOB));
+        assert_ok!(settle(0, ALICE));
+
+        assert_eq!(paid_bonuses(), vec![(CAROL, 300, 0), (BOB, 1_200, 0)]);
+    });
+}
+
+#[test]
+fn depth_limit_rebases_upline_when_top_ancestor_changes_tier() {
+    new_test_ext().execute_with(|| {
+        configure(2, 0);
+
+        // EVE → [BOB, ALICE]
+        order(1, EVE, 20_000);
+        assert_eq!(stat(0, ALICE).downline_base, 100 * 20_000);
+
+        // DAVID → [CAROL, BOB]，ALICE 超出层数不累计，但 BOB 升到 3% 档
+        order(2, DAVID, 40_000);
+        assert_eq!(stat(0, ALICE).volume, 20_000);
+        assert_eq!(stat(0, ALICE).downline_base, 300 * 20_000);
+        assert_eq!(stat(0, BOB).volume, 60_000);
+
+        run_to_block(PERIOD_BLOCKS as u64);
+        assert_ok!(settle(0, CAROL));
+        assert_ok!(settle(0, BOB));
+        assert_ok!(settle(0, ALICE));
+
+        // CAROL 40000 × 1% = 400；BOB 60000 × 3% − 40000 × 1% = 1400；ALICE 被下级档位覆盖为 0
+        assert_eq!(paid_bonuses(), vec![(CAROL, 400, 0), (BOB, 1_400, 0)]);
+    });
+}
+
+#[test]
+fn settle_applies_period_cap_and_pool_balance() {
+    new_test_ext().execute_with(|| {
+        configure(10, 1_000);
+        order(1, DAVID, 60_000);
+        run_to_block(PERIOD_BLOCKS as u64);
+
+        // 1800 封顶为 1000，奖金池只剩 500
+        set_bonus_pool(500);
+        assert_ok!(settle(0, CAROL));
+
+        assert_eq!(paid_bonuses(), vec![(CAROL, 500, 0)]);
+        assert_eq!(stat(0, CAROL).bonus_paid, 500);
+    });
+}
+
+#[test]
+fn period_length_change_keeps_period_indices_monotonic() {
+    new_test_ext().execute_with(|| {
+        configure(10, 0);
+        order(1, DAVID, 20_000);
+        run_to_block(350);
+        assert_ok!(settle(0, CAROL));
+
+        // 第 3 周期内把周期拉长到 1000：新长度从第 4 周期（区块 400）起算
+        assert_ok!(CommissionTeam::set_team_config(
+            RuntimeOrigin::signed(OWNER), ENTITY_1, tiers(), 1_000, 10, 0,
+        ));
+        assert_eq!(CommissionTeam::current_period(ENTITY_1), Some(3));
+
+        // 不会落入已结束的第 0 周期而被立即结算
+        order(2, DAVID, 20_000);
+        assert_eq!(stat(0, CAROL).volume, 20_000);
+        assert_eq!(stat(3, CAROL).volume, 20_000);
+        assert_noop!(settle(3, CAROL), Error::<Test>::PeriodNotEnded);
+
+        run_to_block(400);
+        assert_eq!(CommissionTeam::current_period(ENTITY_1), Some(4));
+        assert_ok!(settle(3, CAROL));
+
+        run_to_block(1_399);
+        assert_eq!(CommissionTeam::current_period(ENTITY_1), Some(4));
+        run_to_block(1_400);
+        assert_eq!(CommissionTeam::current_period(ENTITY_1), Some(5));
+
+        // 清除后重新配置，周期序号延续
+        assert_ok!(CommissionTeam::clear_team_config(RuntimeOrigin::signed(OWNER), ENTITY_1));
+        assert_eq!(CommissionTeam::current_period(ENTITY_1), None);
+        configure(10, 0);
+        assert_eq!(CommissionTeam::current_period(ENTITY_1), Some(5));
+        order(3, DAVID, 20_000);
+        assert_eq!(stat(5, CAROL).volume, 20_000);
+    });
+}
+
+#[test]
+fn config_change_applies_from_next_period() {
+    new_test_ext().execute_with(|| {
+        configure(10, 0);
+        order(1, DAVID, 60_000);
+
+        let doubled: BoundedVec<TeamTier, ConstU32<5>> = vec![
+            TeamTier { threshold: 10_000, rate: 200 },
+            TeamTier { threshold: 50_000, rate: 600 },
+        ]
+        .try_into()
+        .unwrap();
+        assert_ok!(CommissionTeam::set_team_config(
+            RuntimeOrigin::signed(OWNER), ENTITY_1, doubled, PERIOD_BLOCKS, 10, 0,
+        ));
+        // 本周期仍按快照 3% 预留
+        assert_eq!(order(2, EVE, 10_000), 300);
+
+        run_to_block(PERIOD_BLOCKS as u64);
+        assert_ok!(settle(0, CAROL));
+        assert_eq!(paid_bonuses(), vec![(CAROL, 1_800, 0)]);
+
+        // 新周期按新配置
+        assert_eq!(order(3, EVE, 10_000), 600);
+    });
+}
@@ -0,0 +1,86 @@
+//! # Commission Team Plugin Weights
+//!
+//! 团队业绩插件权重定义（结构与 `benchmarking.rs` 的基准一一对应，
+//! 数值以 `benchmark pallet --pallet pallet_commission_team` 的结果替换）
+//!
+//! 证明大小按各存储项 MaxEncodedLen 估算：
+//! - `TeamConfigs` / `TeamPeriodConfigs`：`MaxTeamTiers` × 18 + 21 字节，按 10 档计 ~250
+//! - `TeamPeriodStats`：~100；`TeamChildVolumes`：~120
+//! - `OrderTeamVolumes`：`MaxTeamDepth` × 32 + 90 字节，按 30 层计 ~1100
+//! - 推荐人查询（会员模块 `EntityMembers`）：~200
+
+use frame_support::{traits::Get, weights::Weight};
+
+/// 权重信息 Trait
+pub trait WeightInfo {
+    /// 设置团队业绩配置，`t` = 档位数
+    fn set_team_config(t: u32) -> Weight;
+    fn clear_team_config() -> Weight;
+    fn settle_team_bonus() -> Weight;
+    /// 订单完成累计业绩，`d` = 实际累计的上级层数
+    fn record_order(d: u32) -> Weight;
+    /// 订单取消/退款回退业绩，`d` = 该订单累计的上级层数
+    fn revert_order(d: u32) -> Weight;
+}
+
+/// Substrate 权重实现
+pub struct SubstrateWeight<T>(core::marker::PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    fn set_team_config(t: u32) -> Weight {
+        // 读：EntityOwner、TeamPeriodAnchors；写：TeamConfigs、TeamPeriodAnchors
+        Weight::from_parts(18_000_000, 1_500)
+            .saturating_add(Weight::from_parts(150_000, 0).saturating_mul(t as u64))
+            .saturating_add(T::DbWeight::get().reads(2))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+    fn clear_team_config() -> Weight {
+        // 读：EntityOwner；写：TeamConfigs
+        Weight::from_parts(14_000_000, 1_500)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn settle_team_bonus() -> Weight {
+        // 读：TeamPeriodConfigs、TeamPeriodAnchors、TeamPeriodStats、core 奖金池与会员统计
+        // 写：TeamPeriodStats、core 奖金池 / 会员统计 / 入账区块 / 待提现总额
+        Weight::from_parts(40_000_000, 2_000)
+            .saturating_add(T::DbWeight::get().reads(6))
+            .saturating_add(T::DbWeight::get().writes(5))
+    }
+    fn record_order(d: u32) -> Weight {
+        // 读：TeamConfigs、TeamPeriodAnchors、TeamPeriodConfigs、超出层数的上级（推荐人 + 流入业绩 + 业绩）
+        // 写：TeamPeriodConfigs（周期首单）、OrderTeamVolumes、上一级业绩
+        // 每层：推荐人查询、TeamPeriodStats、TeamChildVolumes 各读一次，后两者各写一次
+        Weight::from_parts(20_000_000, 2_000)
+            .saturating_add(Weight::from_parts(9_000_000, 420).saturating_mul(d as u64))
+            .saturating_add(T::DbWeight::get().reads(6))
+            .saturating_add(T::DbWeight::get().reads(3u64.saturating_mul(d as u64)))
+            .saturating_add(T::DbWeight::get().writes(3))
+            .saturating_add(T::DbWeight::get().writes(2u64.saturating_mul(d as u64)))
+    }
+    fn revert_order(d: u32) -> Weight {
+        // 读：OrderTeamVolumes、TeamPeriodConfigs、上一级流入业绩与业绩
+        // 写：OrderTeamVolumes、上一级业绩
+        // 每层：TeamPeriodStats、TeamChildVolumes 各读写一次
+        Weight::from_parts(16_000_000, 1_500)
+            .saturating_add(Weight::from_parts(7_000_000, 220).saturating_mul(d as u64))
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().reads(2u64.saturating_mul(d as u64)))
+            .saturating_add(T::DbWeight::get().writes(2))
+            .saturating_add(T::DbWeight::get().writes(2u64.saturating_mul(d as u64)))
+    }
+}
+
+/// 默认权重实现（用于测试）
+impl WeightInfo for () {
+    fn set_team_config(t: u32) -> Weight {
+        Weight::from_parts(18_000_000, 1_500).saturating_add(Weight::from_parts(150_000, 0).saturating_mul(t as u64))
+    }
+    fn clear_team_config() -> Weight { Weight::from_parts(14_000_000, 1_500) }
+    fn settle_team_bonus() -> Weight { Weight::from_parts(40_000_000, 2_000) }
+    fn record_order(d: u32) -> Weight {
+        Weight::from_parts(20_000_000, 2_000).saturating_add(Weight::from_parts(9_000_000, 420).saturating_mul(d as u64))
+    }
+    fn revert_order(d: u32) -> Weight {
+        Weight::from_parts(16_000_000, 1_500).saturating_add(Weight::from_parts(7_000_000, 220).saturating_mul(d as u64))
+    }
+}
@@ -480,6 +480,85 @@ pub struct DividendConfig<Balance, BlockNumber> {
     pub accumulated: Balance,
 }
 
+// ============================================================================
+// 分段解锁（Custom Vesting）
+// ============================================================================
+
+/// 里程碑确认来源
+#[derive(Encode, Decode, codec::DecodeWithMemTracking, Clone, Copy, PartialEq, Eq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub enum MilestoneSource {
+    /// 实体治理提案（`EntityOrigin::Governance`）
+    Governance,
+    /// 实体披露公告（`DisclosureType::Milestone`）
+    Disclosure,
+}
+
+impl MilestoneSource {
+    /// 该来源的确认是否满足 `required` 门控（治理确认满足任意门控）
+    pub fn satisfies(&self, required: MilestoneSource) -> bool {
+        *self == Self::Governance || *self == required
+    }
+}
+
+/// 里程碑门控：档位需等待指定里程碑被指定来源确认
+#[derive(Encode, Decode, codec::DecodeWithMemTracking, Clone, Copy, PartialEq, Eq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub struct MilestoneGate {
+    /// 里程碑 ID（实体内唯一）
+    pub id: u32,
+    /// 要求的确认来源
+    pub source: MilestoneSource,
+}
+
+/// 分段解锁档位
+#[derive(Encode, Decode, codec::DecodeWithMemTracking, Clone, PartialEq, Eq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub struct VestingTranche<BlockNumber> {
+    /// 相对锁仓起点的区块偏移
+    pub offset: BlockNumber,
+    /// 本档解锁比例（基点）
+    pub bps: u16,
+    /// 里程碑门控（None = 到期即解锁）
+    pub milestone: Option<MilestoneGate>,
+}
+
+/// 校验分段解锁档位：非空、每档 bps > 0、合计 10000、偏移非递减
+pub fn validate_vesting_tranches<BlockNumber: PartialOrd>(tranches: &[VestingTranche<BlockNumber>]) -> bool {
+    if tranches.is_empty() {
+        return false;
+    }
+    let mut total_bps: u32 = 0;
+    for (i, tranche) in tranches.iter().enumerate() {
+        if tranche.bps == 0 {
+            return false;
+        }
+        if i > 0 && tranche.offset < tranches[i - 1].offset {
+            return false;
+        }
+        total_bps = total_bps.saturating_add(tranche.bps as u32);
+    }
+    total_bps == 10000
+}
+
+/// 计算已解锁比例（基点）：偏移已到且里程碑已确认的档位合计
+///
+/// 各档独立判断，后一档先满足条件时不必等待前一档的里程碑。
+pub fn vested_tranche_bps<BlockNumber, F>(
+    tranches: &[VestingTranche<BlockNumber>],
+    start: BlockNumber,
+    now: BlockNumber,
+    is_achieved: F,
+) -> u32
+where
+    BlockNumber: sp_runtime::traits::Saturating + Copy + PartialOrd,
+    F: Fn(&MilestoneGate) -> bool,
+{
+    tranches
+        .iter()
+        .filter(|t| now >= start.saturating_add(t.offset))
+        .filter(|t| t.milestone.as_ref().map_or(true, |gate| is_achieved(gate)))
+        .fold(0u32, |acc, t| acc.saturating_add(t.bps as u32))
+        .min(10000)
+}
+
 // ============================================================================
 // 服务/商品相关类型
 // ============================================================================
@@ -553,6 +632,62 @@ pub enum MallOrderStatus {
     Expired,
 }
 
+// ============================================================================
+// 实体治理来源
+// ============================================================================
+
+/// 实体级调用来源
+///
+/// 由 pallet-entity-governance 注册为 pallet origin，`ProposalType::Call`
+/// 通过后以此来源派发调用；各模块的管理类 extrinsic 通过
+/// `EnsureEntityGovernance` 识别，并校验其 entity_id 与目标一致。
+#[derive(Encode, Decode, codec::DecodeWithMemTracking, Clone, Copy, PartialEq, Eq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+pub enum EntityOrigin {
+    /// 实体代币持有人治理（提案已通过）
+    Governance(u64),
+}
+
+/// 识别 `EntityOrigin::Governance`，成功时返回 entity_id
+pub struct EnsureEntityGovernance;
+impl<O> EnsureOrigin<O> for EnsureEntityGovernance
+where
+    O: Into<Result<EntityOrigin, O>> + From<EntityOrigin>,
+{
+    type Success = u64;
+
+    fn try_origin(o: O) -> Result<u64, O> {
+        o.into().map(|EntityOrigin::Governance(entity_id)| entity_id)
+    }
+
+    #[cfg(feature = "runtime-benchmarks")]
+    fn try_successful_origin() -> Result<O, ()> {
+        Ok(O::from(EntityOrigin::Governance(0)))
+    }
+}
+
+/// 管理类 extrinsic 的调用者
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum AdminCaller<AccountId> {
+    /// 签名账户（权限由各模块继续校验）
+    Signed(AccountId),
+    /// 实体治理
+    Governance(u64),
+}
+
+/// 解析管理类 extrinsic 的来源：实体治理来源优先，否则要求签名
+pub fn ensure_admin_caller<O, AccountId, G>(origin: O) -> Result<AdminCaller<AccountId>, DispatchError>
+where
+    O: Into<Result<frame_system::RawOrigin<AccountId>, O>>,
+    G: EnsureOrigin<O, Success = u64>,
+{
+    match G::try_origin(origin) {
+        Ok(entity_id) => Ok(AdminCaller::Governance(entity_id)),
+        Err(origin) => frame_system::ensure_signed(origin)
+            .map(AdminCaller::Signed)
+            .map_err(Into::into),
+    }
+}
+
 // ============================================================================
 // 跨模块 Trait 接口
 // ============================================================================
@@ -724,6 +859,24 @@ pub trait ShopProvider<AccountId> {
         let _ = shop_id;
         Ok(())
     }
+    
+    // ==================== 治理调用接口 ====================
+    
+    /// 从 Shop 金库（运营账户）向指定账户支出（治理调用，不得侵占已承诺佣金）
+    fn treasury_spend(shop_id: u64, to: &AccountId, amount: u128) -> Result<(), DispatchError> {
+        let _ = (shop_id, to, amount);
+        Err(DispatchError::Other("not implemented"))
+    }
+    
+    /// 更新 Shop 名称 / 描述（治理调用，绕过管理员权限检查）
+    fn update_shop_info(
+        shop_id: u64,
+        name: Option<sp_std::vec::Vec<u8>>,
+        description_cid: Option<sp_std::vec::Vec<u8>>,
+    ) -> Result<(), DispatchError> {
+        let _ = (shop_id, name, description_cid);
+        Err(DispatchError::Other("not implemented"))
+    }
 }
 
 /// 商品查询接口
@@ -795,6 +948,18 @@ pub trait OrderProvider<AccountId, Balance> {
     fn is_order_completed(order_id: u64) -> bool;
 }
 
+/// 店铺促销接口
+/// 
+/// 由 governance 模块实现（`Promotion` 提案），供 transaction 模块下单时按折扣计价
+pub trait ShopPromotionProvider<BlockNumber> {
+    /// 店铺在 `now` 时生效的促销折扣（基点），无促销返回 None
+    fn active_discount(shop_id: u64, now: BlockNumber) -> Option<u16>;
+}
+
+impl<BlockNumber> ShopPromotionProvider<BlockNumber> for () {
+    fn active_discount(_shop_id: u64, _now: BlockNumber) -> Option<u16> { None }
+}
+
 // ============================================================================
 // 空实现（用于测试）
 // ============================================================================
@@ -923,6 +1088,47 @@ pub trait EntityTokenProvider<AccountId, Balance> {
     
     /// Phase 8: 获取代币总供应量
     fn total_supply(entity_id: u64) -> Balance;
+    
+    /// 锁仓信息 (locked_amount, locked_since, unlock_at)，区块号统一为 u64
+    /// 
+    /// 供治理按锁仓时长/剩余锁定期计算投票权重；未锁仓返回 None
+    fn lock_info(entity_id: u64, holder: &AccountId) -> Option<(Balance, u64, u64)> {
+        let _ = (entity_id, holder);
+        None
+    }
+    
+    // ==================== 治理调用接口 ====================
+    
+    /// 增发代币（治理调用，受 max_supply 约束）
+    fn governance_mint(entity_id: u64, to: &AccountId, amount: Balance) -> Result<(), DispatchError> {
+        let _ = (entity_id, to, amount);
+        Err(DispatchError::Other("not implemented"))
+    }
+    
+    /// 销毁代币（治理调用）
+    fn governance_burn(entity_id: u64, from: &AccountId, amount: Balance) -> Result<(), DispatchError> {
+        let _ = (entity_id, from, amount);
+        Err(DispatchError::Other("not implemented"))
+    }
+    
+    /// 修改代币返积分/兑换比例（治理调用，基点）
+    fn governance_set_rates(
+        entity_id: u64,
+        reward_rate: Option<u16>,
+        exchange_rate: Option<u16>,
+    ) -> Result<(), DispatchError> {
+        let _ = (entity_id, reward_rate, exchange_rate);
+        Err(DispatchError::Other("not implemented"))
+    }
+    
+    /// 分发分红到待领取（治理调用，受分红周期约束）
+    fn governance_distribute_dividend(
+        entity_id: u64,
+        recipients: sp_std::vec::Vec<(AccountId, Balance)>,
+    ) -> Result<(), DispatchError> {
+        let _ = (entity_id, recipients);
+        Err(DispatchError::Other("not implemented"))
+    }
 }
 
 /// 向后兼容别名
@@ -970,12 +1176,13 @@ impl CommissionFundGuard for () {
 /// 供 Transaction 模块在订单完成时触发佣金计算，
 /// 无需直接依赖 commission 模块。
 pub trait OrderCommissionHandler<AccountId, Balance> {
-    /// 订单完成时处理佣金
+    /// 订单完成时处理佣金（platform_fee 为该订单已转入平台账户的平台费）
     fn on_order_completed(
         shop_id: u64,
         order_id: u64,
         buyer: &AccountId,
         order_amount: Balance,
+        platform_fee: Balance,
     ) -> Result<(), DispatchError>;
 
     /// 订单取消/退款时撤销佣金
@@ -984,10 +1191,34 @@ pub trait OrderCommissionHandler<AccountId, Balance> {
 
 /// 空佣金处理（无佣金系统时使用）
 impl<AccountId, Balance> OrderCommissionHandler<AccountId, Balance> for () {
-    fn on_order_completed(_: u64, _: u64, _: &AccountId, _: Balance) -> Result<(), DispatchError> { Ok(()) }
+    fn on_order_completed(_: u64, _: u64, _: &AccountId, _: Balance, _: Balance) -> Result<(), DispatchError> { Ok(()) }
     fn on_order_cancelled(_: u64) -> Result<(), DispatchError> { Ok(()) }
 }
 
+// ============================================================================
+// 里程碑接口
+// ============================================================================
+
+/// 里程碑查询/确认接口
+///
+/// 由 TokenSale 模块保存里程碑状态；Token 模块据此判断团队锁仓门控，
+/// Disclosure 模块在发布里程碑公告时确认。
+pub trait MilestoneProvider {
+    /// 里程碑是否已被满足 `gate` 要求的来源确认
+    fn is_milestone_achieved(entity_id: u64, gate: &MilestoneGate) -> bool;
+
+    /// 确认里程碑
+    fn mark_milestone_achieved(entity_id: u64, milestone_id: u32, source: MilestoneSource) -> Result<(), DispatchError>;
+}
+
+/// 空里程碑实现（无里程碑系统时使用：所有门控档位保持锁定）
+impl MilestoneProvider for () {
+    fn is_milestone_achieved(_: u64, _: &MilestoneGate) -> bool { false }
+    fn mark_milestone_achieved(_: u64, _: u32, _: MilestoneSource) -> Result<(), DispatchError> {
+        Err(DispatchError::Other("MilestoneProviderNotConfigured"))
+    }
+}
+
 /// 空定价提供者（测试用）
 pub struct NullPricingProvider;
 
@@ -41,7 +41,7 @@ pub mod pallet {
         BoundedVec,
     };
     use frame_system::pallet_prelude::*;
-    use pallet_entity_common::EntityProvider;
+    use pallet_entity_common::{EntityProvider, MilestoneProvider, MilestoneSource};
     use sp_runtime::traits::{Saturating, Zero};
 
     // ==================== 类型定义 ====================
@@ -89,6 +89,10 @@ pub mod pallet {
         Buyback,
         /// 其他
         Other,
+        /// 里程碑达成公告（发布时确认该里程碑，解锁披露门控的分段锁仓档位）
+        ///
+        /// 追加在末尾，保持已存储披露的类型编码不变
+        Milestone(u32),
     }
 
     /// 披露状态
@@ -234,6 +238,9 @@ pub mod pallet {
         /// 大股东阈值（基点，如 500 = 5%）— 预留，供外部模块判断 MajorHolder 角色
         #[pallet::constant]
         type MajorHolderThreshold: Get<u16>;
+
+        /// 里程碑确认接口（发布 `Milestone` 公告时调用）
+        type MilestoneProvider: MilestoneProvider;
     }
 
     #[pallet::pallet]
@@ -458,6 +465,11 @@ pub mod pallet {
                 .map(|s| s.try_into().map_err(|_| Error::<T>::CidTooLong))
                 .transpose()?;
 
+            // 里程碑公告：确认对应里程碑（失败则整笔回滚）
+            if let DisclosureType::Milestone(milestone_id) = disclosure_type {
+                T::MilestoneProvider::mark_milestone_achieved(entity_id, milestone_id, MilestoneSource::Disclosure)?;
+            }
+
             let now = <frame_system::Pallet<T>>::block_number();
             let disclosure_id = NextDisclosureId::<T>::get();
 
@@ -72,6 +72,39 @@ impl pallet_entity_disclosure::Config for Test {
     type StandardDisclosureInterval = StandardDisclosureInterval;
     type EnhancedDisclosureInterval = EnhancedDisclosureInterval;
     type MajorHolderThreshold = ConstU16<500>;
+    type MilestoneProvider = MockMilestones;
+}
+
+// ==================== Mock MilestoneProvider ====================
+
+thread_local! {
+    static MILESTONES: core::cell::RefCell<Vec<(u64, u32)>> = core::cell::RefCell::new(Vec::new());
+}
+
+/// 记录被确认的里程碑，重复确认返回错误
+pub struct MockMilestones;
+
+impl MockMilestones {
+    pub fn achieved(entity_id: u64, milestone_id: u32) -> bool {
+        MILESTONES.with(|m| m.borrow().contains(&(entity_id, milestone_id)))
+    }
+}
+
+impl pallet_entity_common::MilestoneProvider for MockMilestones {
+    fn is_milestone_achieved(entity_id: u64, gate: &pallet_entity_common::MilestoneGate) -> bool {
+        Self::achieved(entity_id, gate.id)
+    }
+    fn mark_milestone_achieved(
+        entity_id: u64,
+        milestone_id: u32,
+        _source: pallet_entity_common::MilestoneSource,
+    ) -> Result<(), DispatchError> {
+        if Self::achieved(entity_id, milestone_id) {
+            return Err(DispatchError::Other("MilestoneAlreadyAchieved"));
+        }
+        MILESTONES.with(|m| m.borrow_mut().push((entity_id, milestone_id)));
+        Ok(())
+    }
 }
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
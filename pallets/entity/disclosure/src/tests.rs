@@ -125,6 +125,27 @@ fn publish_disclosure_no_blackout_when_disabled() {
     });
 }
 
+#[test]
+fn publish_milestone_disclosure_marks_milestone() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(EntityDisclosure::publish_disclosure(
+            RuntimeOrigin::signed(OWNER), ENTITY_ID,
+            DisclosureType::Milestone(7), b"QmMilestone".to_vec(), None,
+        ));
+        assert!(MockMilestones::achieved(ENTITY_ID, 7));
+
+        // 重复确认失败时公告整笔回滚
+        assert_noop!(
+            EntityDisclosure::publish_disclosure(
+                RuntimeOrigin::signed(OWNER), ENTITY_ID,
+                DisclosureType::Milestone(7), b"QmAgain".to_vec(), None,
+            ),
+            sp_runtime::DispatchError::Other("MilestoneAlreadyAchieved")
+        );
+        assert_eq!(NextDisclosureId::<Test>::get(), 1);
+    });
+}
+
 #[test]
 fn publish_disclosure_fails_not_owner() {
     new_test_ext().execute_with(|| {
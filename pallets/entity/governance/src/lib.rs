@@ -29,6 +29,8 @@ extern crate alloc;
 
 pub use pallet::*;
 
+pub mod migrations;
+
 #[cfg(test)]
 mod mock;
 
@@ -39,15 +41,23 @@ mod tests;
 pub mod pallet {
     use super::*;
     use alloc::vec::Vec;
+    use codec::DecodeLimit;
     use frame_support::{
+        dispatch::{GetDispatchInfo, PostDispatchInfo},
         pallet_prelude::*,
-        traits::Get,
+        traits::{Get, PalletInfoAccess},
         BoundedVec,
     };
     use frame_system::pallet_prelude::*;
-    use pallet_entity_common::{GovernanceMode, EntityProvider, EntityTokenProvider, ShopProvider, TokenType};
-    use pallet_entity_commission::{CommissionProvider, MemberProvider};
-    use sp_runtime::traits::{Saturating, Zero};
+    use pallet_entity_common::{
+        EntityOrigin, GovernanceMode, EntityProvider, EntityTokenProvider, ProductProvider, ShopPromotionProvider,
+        ShopProvider, TokenType,
+    };
+    use pallet_entity_commission::{
+        CommissionProvider, LevelDiffPlanWriter, MemberProvider, ReferralPlanWriter, SingleLinePlanWriter,
+    };
+    use sp_runtime::traits::{Dispatchable, Saturating, Zero};
+    use sp_runtime::SaturatedConversion;
 
     // ==================== 类型定义 ====================
 
@@ -73,6 +83,8 @@ pub mod pallet {
         Cancelled,
         /// 已过期
         Expired,
+        /// 执行失败（状态变更已回滚，见 ProposalExecutionFailed 事件）
+        ExecutionFailed,
     }
 
     impl Default for ProposalStatus {
@@ -94,9 +106,21 @@ pub mod pallet {
     }
 
 
+    /// 单个提案最多携带的链上接收人数（空投 / 分红）
+    pub type MaxProposalRecipients = ConstU32<100>;
+
+    /// `ProposalType::Call` 编码调用的最大字节数
+    pub type MaxProposalCallLength = ConstU32<2048>;
+
+    /// 每个实体调用白名单的条目上限
+    pub type MaxWhitelistedCalls = ConstU32<64>;
+
+    /// 解码提案调用时允许的最大嵌套深度
+    const CALL_DECODE_DEPTH_LIMIT: u32 = 64;
+
     /// 提案类型（纯代币投票）
     #[derive(Encode, Decode, codec::DecodeWithMemTracking, Clone, PartialEq, Eq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
-    pub enum ProposalType<Balance> {
+    pub enum ProposalType<AccountId, Balance> {
         // ==================== 商品管理类 ====================
         /// 商品价格调整
         PriceChange { product_id: u64, new_price: Balance },
@@ -123,17 +147,20 @@ pub mod pallet {
         /// 代币配置修改
         TokenConfigChange { reward_rate: Option<u16>, exchange_rate: Option<u16> },
         /// 增发代币
-        TokenMint { amount: Balance, recipient_cid: BoundedVec<u8, ConstU32<64>> },
-        /// 销毁代币（从金库）
+        TokenMint { amount: Balance, recipient: AccountId },
+        /// 销毁代币（从店铺金库账户）
         TokenBurn { amount: Balance },
-        /// 空投分发
-        AirdropDistribution { airdrop_cid: BoundedVec<u8, ConstU32<64>>, total_amount: Balance },
-        /// 分红提案
-        Dividend { rate: u16 },
+        /// 空投分发（从店铺金库账户转出，total_amount 须等于各接收人之和）
+        AirdropDistribution {
+            recipients: BoundedVec<(AccountId, Balance), MaxProposalRecipients>,
+            total_amount: Balance,
+        },
+        /// 分红提案（按执行时各持有人余额 × rate 记入待领取分红）
+        Dividend { rate: u16, holders: BoundedVec<AccountId, MaxProposalRecipients> },
 
         // ==================== 财务管理类 ====================
         /// 店铺金库支出
-        TreasurySpend { amount: Balance, recipient_cid: BoundedVec<u8, ConstU32<64>>, reason_cid: BoundedVec<u8, ConstU32<64>> },
+        TreasurySpend { amount: Balance, recipient: AccountId, reason_cid: BoundedVec<u8, ConstU32<64>> },
         /// 手续费调整
         FeeAdjustment { new_fee_rate: u16 },
         /// 收益分配比例调整
@@ -155,9 +182,9 @@ pub mod pallet {
         /// 直推奖励配置
         DirectRewardChange { rate: u16 },
         /// 多级分销配置
-        MultiLevelChange { 
-            /// 各层级配置 (rate, required_directs, required_team_size, required_spent)
-            levels_cid: BoundedVec<u8, ConstU32<64>>,
+        MultiLevelChange {
+            /// 各层级返佣率（基点，按层级顺序）
+            level_rates: BoundedVec<u16, ConstU32<15>>,
             max_total_rate: u16,
         },
         /// 等级差价配置（全局等级）
@@ -170,8 +197,8 @@ pub mod pallet {
         },
         /// 自定义等级极差配置
         CustomLevelDiffChange {
-            /// 各等级返佣率 CID（JSON 格式）
-            rates_cid: BoundedVec<u8, ConstU32<64>>,
+            /// 各自定义等级返佣率（基点，按等级顺序）
+            level_rates: BoundedVec<u16, ConstU32<10>>,
             max_depth: u8,
         },
         /// 固定金额配置
@@ -243,8 +270,97 @@ pub mod pallet {
         RuleSuggestion { suggestion_cid: BoundedVec<u8, ConstU32<64>> },
         /// 通用提案（自定义内容）
         General { title_cid: BoundedVec<u8, ConstU32<64>>, content_cid: BoundedVec<u8, ConstU32<64>> },
+
+        // ==================== 链上调用类 ====================
+        /// 链上调用（SCALE 编码的 RuntimeCall），通过后以 `EntityOrigin::Governance(entity_id)` 派发，
+        /// 创建与执行时均须命中实体调用白名单
+        Call { call: BoundedVec<u8, MaxProposalCallLength> },
+    }
+
+    /// 提案类别（委托范围按类别划分）
+    #[derive(Encode, Decode, codec::DecodeWithMemTracking, Clone, Copy, PartialEq, Eq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+    pub enum ProposalCategory {
+        /// 商品管理类
+        Product,
+        /// 店铺运营类
+        ShopOperation,
+        /// 代币经济类
+        Token,
+        /// 财务管理类
+        Finance,
+        /// 治理参数类
+        GovernanceParam,
+        /// 返佣配置类
+        Commission,
+        /// 分级提现配置类
+        Withdrawal,
+        /// 会员等级体系类
+        MemberLevel,
+        /// 社区类
+        Community,
+        /// 链上调用类
+        Call,
+    }
+
+    impl<AccountId, Balance> ProposalType<AccountId, Balance> {
+        /// 提案所属类别
+        pub fn category(&self) -> ProposalCategory {
+            use ProposalType::*;
+            match self {
+                PriceChange { .. } | ProductListing { .. } | ProductDelisting { .. }
+                | InventoryAdjustment { .. } => ProposalCategory::Product,
+                Promotion { .. } | ShopNameChange { .. } | ShopDescriptionChange { .. }
+                | ShopPause | ShopResume => ProposalCategory::ShopOperation,
+                TokenConfigChange { .. } | TokenMint { .. } | TokenBurn { .. }
+                | AirdropDistribution { .. } | Dividend { .. } => ProposalCategory::Token,
+                TreasurySpend { .. } | FeeAdjustment { .. } | RevenueShare { .. }
+                | RefundPolicy { .. } => ProposalCategory::Finance,
+                VotingPeriodChange { .. } | QuorumChange { .. }
+                | ProposalThresholdChange { .. } => ProposalCategory::GovernanceParam,
+                CommissionModesChange { .. } | DirectRewardChange { .. } | MultiLevelChange { .. }
+                | LevelDiffChange { .. } | CustomLevelDiffChange { .. } | FixedAmountChange { .. }
+                | FirstOrderChange { .. } | RepeatPurchaseChange { .. }
+                | SingleLineChange { .. } => ProposalCategory::Commission,
+                WithdrawalConfigChange { .. } | MinRepurchaseRateChange { .. } => ProposalCategory::Withdrawal,
+                AddCustomLevel { .. } | UpdateCustomLevel { .. } | RemoveCustomLevel { .. }
+                | SetUpgradeMode { .. } | EnableCustomLevels { .. } | AddUpgradeRule { .. }
+                | RemoveUpgradeRule { .. } => ProposalCategory::MemberLevel,
+                CommunityEvent { .. } | RuleSuggestion { .. } | General { .. } => ProposalCategory::Community,
+                ProposalType::Call { .. } => ProposalCategory::Call,
+            }
+        }
+    }
+
+    /// 投票委托（scope = None 表示全部类别，Some 表示仅该类别；类别委托优先于全局委托）
+    #[derive(Encode, Decode, codec::DecodeWithMemTracking, Clone, PartialEq, Eq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+    pub struct Delegation<AccountId> {
+        /// 受托人
+        pub delegate: AccountId,
+        /// 委托范围
+        pub scope: Option<ProposalCategory>,
+    }
+
+    /// 每个委托人在单个实体下的委托条目上限（1 个全局 + 9 个类别）
+    pub type MaxDelegationsPerAccount = ConstU32<10>;
+
+    /// 投票权重配置（按实体）
+    ///
+    /// 投票权 = 余额 + 锁仓量 × (持有时长加成 + 锁定期倍数) / 10000
+    #[derive(Encode, Decode, codec::DecodeWithMemTracking, Clone, PartialEq, Eq, TypeInfo, MaxEncodedLen, RuntimeDebug, Default)]
+    pub struct VotingPowerConfig<BlockNumber> {
+        /// 持有时长加成满额所需锁仓区块数（0 = 不启用）
+        pub holding_bonus_period: BlockNumber,
+        /// 持有时长满额加成（基点，10000 = +100%）
+        pub max_holding_bonus: u16,
+        /// 每级锁定倍数对应的剩余锁定区块数（0 = 不启用）
+        pub conviction_period: BlockNumber,
+        /// 最高锁定倍数级别（每级 +1x，最多 6 级）
+        pub max_conviction: u8,
     }
 
+    /// 投票权重配置类型别名
+    pub type VotingPowerConfigOf<T> = VotingPowerConfig<BlockNumberFor<T>>;
+
     /// 提案
     #[derive(Encode, Decode, codec::DecodeWithMemTracking, Clone, PartialEq, Eq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
     #[scale_info(skip_type_params(T))]
@@ -256,7 +372,7 @@ pub mod pallet {
         /// 提案者
         pub proposer: T::AccountId,
         /// 提案类型
-        pub proposal_type: ProposalType<BalanceOf<T>>,
+        pub proposal_type: ProposalTypeOf<T>,
         /// 提案标题
         pub title: BoundedVec<u8, T::MaxTitleLength>,
         /// 提案描述 CID
@@ -288,8 +404,10 @@ pub mod pallet {
         pub voter: AccountId,
         /// 投票类型
         pub vote: VoteType,
-        /// 投票权重
+        /// 投票权重（含受托权重）
         pub weight: Balance,
+        /// 其中代委托人投出的权重
+        pub delegated_weight: Balance,
         /// 投票时间
         pub voted_at: BlockNumber,
     }
@@ -300,6 +418,9 @@ pub mod pallet {
     /// 提案类型别名
     pub type ProposalOf<T> = Proposal<T>;
 
+    /// 提案内容类型别名
+    pub type ProposalTypeOf<T> = ProposalType<<T as frame_system::Config>::AccountId, BalanceOf<T>>;
+
     /// 投票记录类型别名
     pub type VoteRecordOf<T> = VoteRecord<
         <T as frame_system::Config>::AccountId,
@@ -408,12 +529,24 @@ pub mod pallet {
         /// 代币余额查询接口
         type TokenProvider: EntityTokenProvider<Self::AccountId, Self::Balance>;
 
+        /// 商品接口（价格/下架/库存提案执行）
+        type ProductProvider: ProductProvider<Self::AccountId, Self::Balance>;
+
         /// 返佣服务接口（治理调用）
         type CommissionProvider: pallet_entity_commission::CommissionProvider<Self::AccountId, Self::Balance>;
 
         /// 会员服务接口（治理调用）
         type MemberProvider: pallet_entity_commission::MemberProvider<Self::AccountId>;
 
+        /// 推荐链返佣方案写入（多级分销提案执行）
+        type ReferralWriter: ReferralPlanWriter<Self::Balance>;
+
+        /// 等级极差方案写入（自定义等级极差提案执行）
+        type LevelDiffWriter: LevelDiffPlanWriter;
+
+        /// 单线收益方案写入（单线收益提案执行）
+        type SingleLineWriter: SingleLinePlanWriter;
+
         /// 投票期（区块数）
         #[pallet::constant]
         type VotingPeriod: Get<BlockNumberFor<Self>>;
@@ -451,9 +584,33 @@ pub mod pallet {
         /// 委员会最大成员数
         #[pallet::constant]
         type MaxCommitteeSize: Get<u32>;
+
+        /// 单个受托人在单个实体下的最大委托人数
+        #[pallet::constant]
+        type MaxDelegators: Get<u32>;
+
+        /// 运行时 Origin（`ProposalType::Call` 以本模块的 `Origin` 派发）
+        type RuntimeOrigin: From<Origin>;
+
+        /// 运行时调用（`ProposalType::Call` 的解码目标）
+        type RuntimeCall: Parameter
+            + Dispatchable<RuntimeOrigin = <Self as Config>::RuntimeOrigin, PostInfo = PostDispatchInfo>
+            + GetDispatchInfo;
+
+        /// 单个 `ProposalType::Call` 允许的最大调用权重
+        #[pallet::constant]
+        type MaxProposalCallWeight: Get<Weight>;
     }
 
+    /// 实体治理来源：`ProposalType::Call` 通过后以 `Governance(entity_id)` 派发
+    #[pallet::origin]
+    pub type Origin = EntityOrigin;
+
+    /// v1: 提案类型改为链上可执行的结构，投票记录增加受托权重（见 `migrations::v1`）
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     // ==================== 存储项 ====================
@@ -496,7 +653,7 @@ pub mod pallet {
         VoteRecordOf<T>,
     >;
 
-    /// 用户首次持有代币时间（用于时间加权）
+    /// 用户首次参与投票时间（仅记录；投票时间加权基于代币模块的锁仓起始区块）
     #[pallet::storage]
     #[pallet::getter(fn first_hold_time)]
     pub type FirstHoldTime<T: Config> = StorageDoubleMap<
@@ -546,6 +703,78 @@ pub mod pallet {
         ValueQuery,
     >;
 
+    /// 治理设定的店铺促销 shop_id -> (折扣基点, 结束区块)，由 transaction 模块下单时查询
+    #[pallet::storage]
+    #[pallet::getter(fn shop_promotions)]
+    pub type ShopPromotions<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        u64,  // shop_id
+        (u16, BlockNumberFor<T>),
+    >;
+
+    /// 投票委托 (entity_id, delegator) -> 委托条目
+    #[pallet::storage]
+    #[pallet::getter(fn delegations)]
+    pub type Delegations<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        u64,  // entity_id
+        Blake2_128Concat,
+        T::AccountId,
+        BoundedVec<Delegation<T::AccountId>, MaxDelegationsPerAccount>,
+        ValueQuery,
+    >;
+
+    /// 受托人反向索引 (entity_id, delegate) -> [(delegator, scope)]
+    #[pallet::storage]
+    #[pallet::getter(fn delegators_of)]
+    pub type DelegatorsOf<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        u64,  // entity_id
+        Blake2_128Concat,
+        T::AccountId,
+        BoundedVec<(T::AccountId, Option<ProposalCategory>), T::MaxDelegators>,
+        ValueQuery,
+    >;
+
+    /// 已被代投的投票权 (proposal_id, delegator) -> (delegate, weight)
+    /// 同一委托人的投票权在一个提案中只会被计入一次（改委托/委托人自投均以此去重）
+    #[pallet::storage]
+    #[pallet::getter(fn delegated_votes)]
+    pub type DelegatedVotes<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        ProposalId,
+        Blake2_128Concat,
+        T::AccountId,
+        (T::AccountId, BalanceOf<T>),
+    >;
+
+    /// 实体投票权重配置
+    #[pallet::storage]
+    #[pallet::getter(fn voting_power_configs)]
+    pub type VotingPowerConfigs<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        u64,  // entity_id
+        VotingPowerConfigOf<T>,
+        ValueQuery,
+    >;
+
+    /// 实体调用白名单 entity_id -> [(pallet_index, call_index)]
+    /// `ProposalType::Call` 只能派发白名单内的调用
+    #[pallet::storage]
+    #[pallet::getter(fn call_whitelist)]
+    pub type CallWhitelist<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        u64,  // entity_id
+        BoundedVec<(u8, u8), MaxWhitelistedCalls>,
+        ValueQuery,
+    >;
+
     // ==================== 事件 ====================
 
     #[pallet::event]
@@ -607,6 +836,40 @@ pub mod pallet {
             proposal_id: ProposalId,
             note: Vec<u8>,
         },
+        /// 提案执行失败（状态变更已回滚，提案标记为 ExecutionFailed）
+        ProposalExecutionFailed {
+            proposal_id: ProposalId,
+            error: DispatchError,
+        },
+        /// 投票委托已设置
+        DelegationSet {
+            entity_id: u64,
+            delegator: T::AccountId,
+            delegate: T::AccountId,
+            scope: Option<ProposalCategory>,
+        },
+        /// 投票委托已撤销
+        DelegationRemoved {
+            entity_id: u64,
+            delegator: T::AccountId,
+            scope: Option<ProposalCategory>,
+        },
+        /// 委托人自行投票，收回已被受托人代投的权重
+        DelegatedVoteOverridden {
+            proposal_id: ProposalId,
+            delegator: T::AccountId,
+            delegate: T::AccountId,
+            weight: BalanceOf<T>,
+        },
+        /// 投票权重配置已更新
+        VotingPowerConfigUpdated {
+            entity_id: u64,
+        },
+        /// 实体调用白名单已更新
+        CallWhitelistUpdated {
+            entity_id: u64,
+            calls: Vec<(u8, u8)>,
+        },
     }
 
     // ==================== 错误 ====================
@@ -664,6 +927,24 @@ pub mod pallet {
         TokenTypeNoVotingPower,
         /// 参数无效
         InvalidParameter,
+        /// 商品不属于该店铺
+        ProductNotInShop,
+        /// 不能委托给自己
+        SelfDelegation,
+        /// 受托人的委托人数已满
+        TooManyDelegators,
+        /// 委托不存在
+        DelegationNotFound,
+        /// 提案调用无法解码
+        CallUndecodable,
+        /// 提案调用不在实体白名单内
+        CallNotWhitelisted,
+        /// 提案调用权重超过上限
+        CallWeightTooHigh,
+        /// 不能将治理模块自身的调用加入白名单
+        CannotWhitelistGovernance,
+        /// 该提案类型没有链上执行方，不再接受
+        ProposalTypeNotSupported,
     }
 
     // ==================== Extrinsics ====================
@@ -682,7 +963,7 @@ pub mod pallet {
         pub fn create_proposal(
             origin: OriginFor<T>,
             shop_id: u64,
-            proposal_type: ProposalType<BalanceOf<T>>,
+            proposal_type: ProposalTypeOf<T>,
             title: Vec<u8>,
             description_cid: Option<Vec<u8>>,
         ) -> DispatchResult {
@@ -700,7 +981,7 @@ pub mod pallet {
             // 无配置时允许（向后兼容，使用全局默认参数）
 
             // H2: 验证提案参数有效性
-            Self::validate_proposal_type(&proposal_type)?;
+            Self::validate_proposal_type(shop_id, &proposal_type)?;
 
             // 验证代币已启用
             ensure!(T::TokenProvider::is_token_enabled(shop_id), Error::<T>::TokenNotEnabled);
@@ -772,7 +1053,8 @@ pub mod pallet {
         /// - `proposal_id`: 提案 ID
         /// - `vote`: 投票类型
         #[pallet::call_index(1)]
-        #[pallet::weight(Weight::from_parts(40_000_000, 5_000))]
+        #[pallet::weight(Weight::from_parts(40_000_000, 5_000)
+            .saturating_add(Weight::from_parts(15_000_000, 3_000).saturating_mul(T::MaxDelegators::get() as u64)))]
         pub fn vote(
             origin: OriginFor<T>,
             proposal_id: ProposalId,
@@ -801,22 +1083,55 @@ pub mod pallet {
             let token_type = T::TokenProvider::get_token_type(proposal.shop_id);
             ensure!(token_type.has_voting_power(), Error::<T>::TokenTypeNoVotingPower);
 
-            // H1 修复: 获取当前投票权重
-            let current_balance = Self::calculate_voting_power(proposal.shop_id, &who);
-            ensure!(!current_balance.is_zero(), Error::<T>::NoVotingPower);
+            let entity_id = Self::entity_of_shop(proposal.shop_id);
+            let category = proposal.proposal_type.category();
+
+            // H1 修复 + P1 安全: 自身投票权取快照与当前权重的较小值（首次投票时锁定快照）
+            let own_weight = Self::snapshot_voting_power(proposal_id, proposal.shop_id, &who);
+
+            // 委托人自行投票：收回受托人已代投的本人权重，避免重复计票
+            if let Some((delegate, delegated)) = DelegatedVotes::<T>::take(proposal_id, &who) {
+                VoteRecords::<T>::mutate(proposal_id, &delegate, |maybe_record| {
+                    if let Some(record) = maybe_record {
+                        record.weight = record.weight.saturating_sub(delegated);
+                        record.delegated_weight = record.delegated_weight.saturating_sub(delegated);
+                        Self::sub_tally(&mut proposal, &record.vote, delegated);
+                    }
+                });
+                Self::deposit_event(Event::DelegatedVoteOverridden {
+                    proposal_id,
+                    delegator: who.clone(),
+                    delegate,
+                    weight: delegated,
+                });
+            }
 
-            // P1 安全: 使用快照机制防止闪电贷（首次投票时锁定权重）
-            let weight = if let Some(snapshot) = VotingPowerSnapshot::<T>::get(proposal_id, &who) {
-                // 已有快照，取快照与当前余额的较小值
-                current_balance.min(snapshot)
-            } else {
-                // 首次投票，保存快照
-                VotingPowerSnapshot::<T>::insert(proposal_id, &who, current_balance);
-                current_balance
-            };
+            // 受托权重：仅计入本提案类别下以 who 为有效受托人、且本人未投票/未被代投的委托人
+            let mut delegated_weight: BalanceOf<T> = Zero::zero();
+            for (delegator, scope) in DelegatorsOf::<T>::get(entity_id, &who).into_iter() {
+                if scope.is_some_and(|c| c != category) {
+                    continue;
+                }
+                if Self::effective_delegate(entity_id, &delegator, category).as_ref() != Some(&who) {
+                    continue;
+                }
+                if VoteRecords::<T>::contains_key(proposal_id, &delegator)
+                    || DelegatedVotes::<T>::contains_key(proposal_id, &delegator)
+                {
+                    continue;
+                }
+                let power = Self::snapshot_voting_power(proposal_id, proposal.shop_id, &delegator);
+                if power.is_zero() {
+                    continue;
+                }
+                DelegatedVotes::<T>::insert(proposal_id, &delegator, (who.clone(), power));
+                delegated_weight = delegated_weight.saturating_add(power);
+            }
+
+            let weight = own_weight.saturating_add(delegated_weight);
             ensure!(!weight.is_zero(), Error::<T>::NoVotingPower);
 
-            // 懒写入 FirstHoldTime（便于未来时间加权）
+            // 懒写入 FirstHoldTime
             if !FirstHoldTime::<T>::contains_key(proposal.shop_id, &who) {
                 FirstHoldTime::<T>::insert(proposal.shop_id, &who, now);
             }
@@ -832,6 +1147,7 @@ pub mod pallet {
                 voter: who.clone(),
                 vote: vote.clone(),
                 weight,
+                delegated_weight,
                 voted_at: now,
             };
 
@@ -916,7 +1232,7 @@ pub mod pallet {
         ///
         /// 任何人都可以调用（执行时间到达后）
         #[pallet::call_index(3)]
-        #[pallet::weight(Weight::from_parts(80_000_000, 10_000))]
+        #[pallet::weight(Weight::from_parts(80_000_000, 10_000).saturating_add(T::MaxProposalCallWeight::get()))]
         pub fn execute_proposal(
             origin: OriginFor<T>,
             proposal_id: ProposalId,
@@ -934,14 +1250,20 @@ pub mod pallet {
             let exec_time = proposal.execution_time.ok_or(Error::<T>::ExecutionTimeNotReached)?;
             ensure!(now >= exec_time, Error::<T>::ExecutionTimeNotReached);
 
-            // 执行提案（根据类型）
-            Self::do_execute_proposal(&proposal)?;
-
-            // 更新状态
-            proposal.status = ProposalStatus::Executed;
-            Proposals::<T>::insert(proposal_id, proposal);
-
-            Self::deposit_event(Event::ProposalExecuted { proposal_id });
+            // 执行提案（根据类型）：在独立存储层中执行，失败时回滚其副作用，
+            // 但提案本身记为 ExecutionFailed，避免通过的提案无声卡在 Passed
+            match frame_support::storage::with_storage_layer(|| Self::do_execute_proposal(&proposal)) {
+                Ok(()) => {
+                    proposal.status = ProposalStatus::Executed;
+                    Proposals::<T>::insert(proposal_id, proposal);
+                    Self::deposit_event(Event::ProposalExecuted { proposal_id });
+                },
+                Err(error) => {
+                    proposal.status = ProposalStatus::ExecutionFailed;
+                    Proposals::<T>::insert(proposal_id, proposal);
+                    Self::deposit_event(Event::ProposalExecutionFailed { proposal_id, error });
+                },
+            }
 
             Ok(())
         }
@@ -1176,28 +1498,197 @@ pub mod pallet {
             });
             Ok(())
         }
+
+        // ==================== 投票委托 / 投票权重 ====================
+
+        /// 委托投票权
+        ///
+        /// 委托不可传递：受托人只代投委托人本人的投票权。同一范围重复委托视为改委托；
+        /// 投票期间改委托不影响已投出的票（同一委托人在一个提案中只计一次）。
+        ///
+        /// # 参数
+        /// - `entity_id`: 实体 ID
+        /// - `delegate`: 受托人
+        /// - `scope`: 委托范围（None = 全部类别）
+        #[pallet::call_index(10)]
+        #[pallet::weight(Weight::from_parts(30_000_000, 5_000))]
+        pub fn delegate_votes(
+            origin: OriginFor<T>,
+            entity_id: u64,
+            delegate: T::AccountId,
+            scope: Option<ProposalCategory>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(T::EntityProvider::entity_exists(entity_id), Error::<T>::ShopNotFound);
+            ensure!(who != delegate, Error::<T>::SelfDelegation);
+
+            let mut delegations = Delegations::<T>::get(entity_id, &who);
+            if let Some(existing) = delegations.iter_mut().find(|d| d.scope == scope) {
+                Self::remove_delegator(entity_id, &existing.delegate, &who, scope);
+                existing.delegate = delegate.clone();
+            } else {
+                delegations
+                    .try_push(Delegation { delegate: delegate.clone(), scope })
+                    .map_err(|_| Error::<T>::InvalidParameter)?;
+            }
+
+            DelegatorsOf::<T>::try_mutate(entity_id, &delegate, |list| {
+                list.try_push((who.clone(), scope)).map_err(|_| Error::<T>::TooManyDelegators)
+            })?;
+            Delegations::<T>::insert(entity_id, &who, delegations);
+
+            Self::deposit_event(Event::DelegationSet {
+                entity_id,
+                delegator: who,
+                delegate,
+                scope,
+            });
+            Ok(())
+        }
+
+        /// 撤销投票委托
+        #[pallet::call_index(11)]
+        #[pallet::weight(Weight::from_parts(25_000_000, 4_000))]
+        pub fn undelegate_votes(
+            origin: OriginFor<T>,
+            entity_id: u64,
+            scope: Option<ProposalCategory>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            Delegations::<T>::try_mutate_exists(entity_id, &who, |maybe_list| -> DispatchResult {
+                let list = maybe_list.as_mut().ok_or(Error::<T>::DelegationNotFound)?;
+                let pos = list.iter().position(|d| d.scope == scope)
+                    .ok_or(Error::<T>::DelegationNotFound)?;
+                let removed = list.remove(pos);
+                Self::remove_delegator(entity_id, &removed.delegate, &who, scope);
+                if list.is_empty() {
+                    *maybe_list = None;
+                }
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::DelegationRemoved {
+                entity_id,
+                delegator: who,
+                scope,
+            });
+            Ok(())
+        }
+
+        /// 配置投票权重（持有时长加成 + 锁定期倍数），仅实体所有者
+        ///
+        /// 已投出的票与已写入的快照不受影响
+        #[pallet::call_index(12)]
+        #[pallet::weight(Weight::from_parts(25_000_000, 3_000))]
+        pub fn configure_voting_power(
+            origin: OriginFor<T>,
+            entity_id: u64,
+            config: VotingPowerConfigOf<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let owner = T::EntityProvider::entity_owner(entity_id).ok_or(Error::<T>::ShopNotFound)?;
+            ensure!(owner == who, Error::<T>::NotShopOwner);
+
+            ensure!(config.max_holding_bonus <= 10000, Error::<T>::InvalidParameter);
+            ensure!(config.max_conviction <= 6, Error::<T>::InvalidParameter);
+
+            VotingPowerConfigs::<T>::insert(entity_id, config);
+
+            Self::deposit_event(Event::VotingPowerConfigUpdated { entity_id });
+            Ok(())
+        }
+
+        /// 设置实体调用白名单（整体替换），仅实体所有者
+        ///
+        /// 条目为 (pallet_index, call_index)，即编码后 RuntimeCall 的前两个字节；
+        /// 不允许放行本模块自身的调用。已创建的 Call 提案在执行时按最新白名单复核。
+        #[pallet::call_index(13)]
+        #[pallet::weight(Weight::from_parts(25_000_000, 3_000))]
+        pub fn set_call_whitelist(
+            origin: OriginFor<T>,
+            entity_id: u64,
+            calls: BoundedVec<(u8, u8), MaxWhitelistedCalls>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let owner = T::EntityProvider::entity_owner(entity_id).ok_or(Error::<T>::ShopNotFound)?;
+            ensure!(owner == who, Error::<T>::NotShopOwner);
+
+            let own_index = <Pallet<T> as PalletInfoAccess>::index();
+            ensure!(
+                calls.iter().all(|(pallet_index, _)| *pallet_index as usize != own_index),
+                Error::<T>::CannotWhitelistGovernance
+            );
+
+            let list = calls.to_vec();
+            CallWhitelist::<T>::insert(entity_id, calls);
+
+            Self::deposit_event(Event::CallWhitelistUpdated { entity_id, calls: list });
+            Ok(())
+        }
     }
 
     // ==================== 内部函数 ====================
 
     impl<T: Config> Pallet<T> {
         /// H2: 验证提案类型参数有效性（basis points ≤ 10000，百分比 ≤ 100）
-        fn validate_proposal_type(pt: &ProposalType<BalanceOf<T>>) -> DispatchResult {
+        fn validate_proposal_type(shop_id: u64, pt: &ProposalTypeOf<T>) -> DispatchResult {
             match pt {
-                ProposalType::Promotion { discount_rate, .. } => {
-                    ensure!(*discount_rate <= 10000, Error::<T>::InvalidParameter);
+                ProposalType::PriceChange { product_id, new_price } => {
+                    ensure!(!new_price.is_zero(), Error::<T>::InvalidParameter);
+                    Self::ensure_product_of_shop(shop_id, *product_id)?;
                 },
-                ProposalType::Dividend { rate } => {
-                    ensure!(*rate <= 10000, Error::<T>::InvalidParameter);
+                ProposalType::ProductDelisting { product_id } => {
+                    Self::ensure_product_of_shop(shop_id, *product_id)?;
+                },
+                ProposalType::InventoryAdjustment { product_id, new_inventory } => {
+                    ensure!(*new_inventory <= u32::MAX as u64, Error::<T>::InvalidParameter);
+                    Self::ensure_product_of_shop(shop_id, *product_id)?;
+                },
+                ProposalType::ShopNameChange { new_name } => {
+                    ensure!(!new_name.is_empty(), Error::<T>::InvalidParameter);
+                },
+                ProposalType::TokenConfigChange { reward_rate, exchange_rate } => {
+                    ensure!(reward_rate.is_some() || exchange_rate.is_some(), Error::<T>::InvalidParameter);
+                    ensure!(reward_rate.map_or(true, |r| r <= 10000), Error::<T>::InvalidParameter);
+                    ensure!(exchange_rate.map_or(true, |r| r <= 10000), Error::<T>::InvalidParameter);
+                },
+                ProposalType::TokenMint { amount, .. }
+                | ProposalType::TokenBurn { amount }
+                | ProposalType::TreasurySpend { amount, .. } => {
+                    ensure!(!amount.is_zero(), Error::<T>::InvalidParameter);
+                },
+                ProposalType::AirdropDistribution { recipients, total_amount } => {
+                    ensure!(!recipients.is_empty(), Error::<T>::InvalidParameter);
+                    let sum = recipients
+                        .iter()
+                        .fold(BalanceOf::<T>::zero(), |acc, (_, amount)| acc.saturating_add(*amount));
+                    ensure!(sum == *total_amount && !sum.is_zero(), Error::<T>::InvalidParameter);
                 },
-                ProposalType::FeeAdjustment { new_fee_rate } => {
-                    ensure!(*new_fee_rate <= 10000, Error::<T>::InvalidParameter);
+                ProposalType::Promotion { discount_rate, duration_blocks } => {
+                    ensure!(*discount_rate > 0 && *discount_rate < 10000, Error::<T>::InvalidParameter);
+                    ensure!(*duration_blocks > 0, Error::<T>::InvalidParameter);
+                },
+                // 商品上架/升级规则需要链下解析 CID；手续费由平台统一设定；
+                // 收益分配与退款政策没有消费方。这些类型执行后不会产生任何链上效果
+                ProposalType::ProductListing { .. }
+                | ProposalType::AddUpgradeRule { .. }
+                | ProposalType::FeeAdjustment { .. }
+                | ProposalType::RevenueShare { .. }
+                | ProposalType::RefundPolicy { .. } => {
+                    return Err(Error::<T>::ProposalTypeNotSupported.into());
                 },
-                ProposalType::RevenueShare { owner_share, token_holder_share } => {
-                    ensure!(
-                        owner_share.saturating_add(*token_holder_share) <= 10000,
-                        Error::<T>::InvalidParameter
-                    );
+                ProposalType::Dividend { rate, holders } => {
+                    ensure!(*rate > 0 && *rate <= 10000, Error::<T>::InvalidParameter);
+                    ensure!(!holders.is_empty(), Error::<T>::InvalidParameter);
+                    // 重复持有人会被重复计入分红
+                    let mut sorted: Vec<&T::AccountId> = holders.iter().collect();
+                    sorted.sort();
+                    sorted.dedup();
+                    ensure!(sorted.len() == holders.len(), Error::<T>::InvalidParameter);
                 },
                 ProposalType::QuorumChange { new_quorum } => {
                     ensure!(*new_quorum <= 100, Error::<T>::InvalidParameter);
@@ -1208,8 +1699,14 @@ pub mod pallet {
                 ProposalType::DirectRewardChange { rate } => {
                     ensure!(*rate <= 10000, Error::<T>::InvalidParameter);
                 },
-                ProposalType::MultiLevelChange { max_total_rate, .. } => {
+                ProposalType::MultiLevelChange { level_rates, max_total_rate } => {
                     ensure!(*max_total_rate <= 10000, Error::<T>::InvalidParameter);
+                    ensure!(!level_rates.is_empty(), Error::<T>::InvalidParameter);
+                    ensure!(level_rates.iter().all(|r| *r <= 10000), Error::<T>::InvalidParameter);
+                },
+                ProposalType::CustomLevelDiffChange { level_rates, max_depth } => {
+                    ensure!(level_rates.iter().all(|r| *r <= 10000), Error::<T>::InvalidParameter);
+                    ensure!(*max_depth > 0 && *max_depth <= 20, Error::<T>::InvalidParameter);
                 },
                 ProposalType::LevelDiffChange { normal_rate, silver_rate, gold_rate, platinum_rate, diamond_rate } => {
                     ensure!(*normal_rate <= 10000, Error::<T>::InvalidParameter);
@@ -1225,8 +1722,9 @@ pub mod pallet {
                     ensure!(*rate <= 10000, Error::<T>::InvalidParameter);
                 },
                 ProposalType::SingleLineChange { upline_rate, downline_rate, .. } => {
-                    ensure!(*upline_rate <= 10000, Error::<T>::InvalidParameter);
-                    ensure!(*downline_rate <= 10000, Error::<T>::InvalidParameter);
+                    // 单线插件上限为 1000 基点
+                    ensure!(*upline_rate <= 1000, Error::<T>::InvalidParameter);
+                    ensure!(*downline_rate <= 1000, Error::<T>::InvalidParameter);
                 },
                 ProposalType::MinRepurchaseRateChange { min_rate } => {
                     ensure!(*min_rate <= 10000, Error::<T>::InvalidParameter);
@@ -1238,12 +1736,57 @@ pub mod pallet {
                 ProposalType::SetUpgradeMode { mode } => {
                     ensure!(*mode <= 2, Error::<T>::InvalidParameter);
                 },
+                ProposalType::Call { call } => {
+                    Self::decode_whitelisted_call(Self::entity_of_shop(shop_id), call)?;
+                },
                 _ => {},
             }
             Ok(())
         }
 
+        /// 商品须存在且属于提案所在店铺
+        fn ensure_product_of_shop(shop_id: u64, product_id: u64) -> DispatchResult {
+            ensure!(
+                T::ProductProvider::product_shop_id(product_id) == Some(shop_id),
+                Error::<T>::ProductNotInShop
+            );
+            Ok(())
+        }
+
+        /// 提案所在店铺对应的 entity_id（commission 插件配置按 entity 维度存储）
+        fn entity_of_shop(shop_id: u64) -> u64 {
+            T::ShopProvider::shop_entity_id(shop_id).unwrap_or(shop_id)
+        }
+
+        /// 解码 `ProposalType::Call`，并校验实体白名单与权重上限
+        fn decode_whitelisted_call(
+            entity_id: u64,
+            encoded: &[u8],
+        ) -> Result<<T as Config>::RuntimeCall, DispatchError> {
+            let call = <<T as Config>::RuntimeCall as DecodeLimit>::decode_all_with_depth_limit(
+                CALL_DECODE_DEPTH_LIMIT,
+                &mut &encoded[..],
+            )
+            .map_err(|_| Error::<T>::CallUndecodable)?;
+
+            let key = match encoded {
+                [pallet_index, call_index, ..] => (*pallet_index, *call_index),
+                _ => return Err(Error::<T>::CallUndecodable.into()),
+            };
+            ensure!(CallWhitelist::<T>::get(entity_id).contains(&key), Error::<T>::CallNotWhitelisted);
+
+            ensure!(
+                call.get_dispatch_info().call_weight.all_lte(T::MaxProposalCallWeight::get()),
+                Error::<T>::CallWeightTooHigh
+            );
+            Ok(call)
+        }
+
         /// 计算投票权重（时间加权）
+        ///
+        /// 未锁仓部分按 1x 计；锁仓部分额外获得：
+        /// - 持有时长加成：锁仓时长 / holding_bonus_period × max_holding_bonus（封顶）
+        /// - 锁定期倍数：剩余锁定区块 / conviction_period 级（封顶 max_conviction），每级 +1x
         pub fn calculate_voting_power(shop_id: u64, holder: &T::AccountId) -> BalanceOf<T> {
             let balance = T::TokenProvider::token_balance(shop_id, holder);
             
@@ -1251,9 +1794,72 @@ pub mod pallet {
                 return Zero::zero();
             }
 
-            // 简单实现：直接返回余额
-            // TODO: 实现时间加权
-            balance
+            let Some((locked, locked_since, unlock_at)) = T::TokenProvider::lock_info(shop_id, holder) else {
+                return balance;
+            };
+            let locked: u128 = locked.min(balance).into();
+            let config = VotingPowerConfigs::<T>::get(Self::entity_of_shop(shop_id));
+            let now: u64 = <frame_system::Pallet<T>>::block_number().saturated_into();
+
+            let mut bonus_bps: u128 = 0;
+            let holding_period: u64 = config.holding_bonus_period.saturated_into();
+            if holding_period > 0 {
+                let held = now.saturating_sub(locked_since).min(holding_period);
+                bonus_bps = bonus_bps.saturating_add(
+                    (config.max_holding_bonus as u128).saturating_mul(held as u128) / holding_period as u128,
+                );
+            }
+            let conviction_period: u64 = config.conviction_period.saturated_into();
+            if conviction_period > 0 {
+                let levels = (unlock_at.saturating_sub(now) / conviction_period).min(config.max_conviction as u64);
+                bonus_bps = bonus_bps.saturating_add((levels as u128).saturating_mul(10000));
+            }
+
+            let bonus = locked.saturating_mul(bonus_bps) / 10000;
+            balance.saturating_add(bonus.into())
+        }
+
+        /// 提案内的投票权：首次读取时写入快照，之后取快照与当前权重的较小值
+        fn snapshot_voting_power(proposal_id: ProposalId, shop_id: u64, who: &T::AccountId) -> BalanceOf<T> {
+            let current = Self::calculate_voting_power(shop_id, who);
+            match VotingPowerSnapshot::<T>::get(proposal_id, who) {
+                Some(snapshot) => current.min(snapshot),
+                None => {
+                    if !current.is_zero() {
+                        VotingPowerSnapshot::<T>::insert(proposal_id, who, current);
+                    }
+                    current
+                },
+            }
+        }
+
+        /// 委托人在指定类别下的有效受托人（类别委托优先于全局委托）
+        pub fn effective_delegate(
+            entity_id: u64,
+            delegator: &T::AccountId,
+            category: ProposalCategory,
+        ) -> Option<T::AccountId> {
+            let delegations = Delegations::<T>::get(entity_id, delegator);
+            delegations.iter()
+                .find(|d| d.scope == Some(category))
+                .or_else(|| delegations.iter().find(|d| d.scope.is_none()))
+                .map(|d| d.delegate.clone())
+        }
+
+        /// 从提案计票中扣除权重
+        fn sub_tally(proposal: &mut ProposalOf<T>, vote: &VoteType, amount: BalanceOf<T>) {
+            match vote {
+                VoteType::Yes => proposal.yes_votes = proposal.yes_votes.saturating_sub(amount),
+                VoteType::No => proposal.no_votes = proposal.no_votes.saturating_sub(amount),
+                VoteType::Abstain => proposal.abstain_votes = proposal.abstain_votes.saturating_sub(amount),
+            }
+        }
+
+        /// 从受托人反向索引中移除委托人
+        fn remove_delegator(entity_id: u64, delegate: &T::AccountId, delegator: &T::AccountId, scope: Option<ProposalCategory>) {
+            DelegatorsOf::<T>::mutate(entity_id, delegate, |list| {
+                list.retain(|(d, s)| !(d == delegator && *s == scope));
+            });
         }
 
         /// 从活跃提案列表移除，并清理投票权快照
@@ -1263,76 +1869,49 @@ pub mod pallet {
             });
             // M4: 清理 VotingPowerSnapshot 避免存储泄漏
             let _ = VotingPowerSnapshot::<T>::clear_prefix(proposal_id, u32::MAX, None);
+            let _ = DelegatedVotes::<T>::clear_prefix(proposal_id, u32::MAX, None);
         }
 
         /// 执行提案
+        ///
+        /// 各分支调用归属模块的治理接口；任一分支返回错误时由 execute_proposal
+        /// 回滚本次副作用并将提案记为 ExecutionFailed。
         fn do_execute_proposal(proposal: &ProposalOf<T>) -> DispatchResult {
             let shop_id = proposal.shop_id;
             
             match &proposal.proposal_type {
                 // ==================== 商品管理类 ====================
                 ProposalType::PriceChange { product_id, new_price } => {
-                    // 价格变更需要 ProductProvider，暂时记录提案已批准
-                    Self::deposit_event(Event::ProposalExecutionNote {
-                        proposal_id: proposal.id,
-                        note: "PriceChange approved".into(),
-                    });
-                    let _ = (product_id, new_price);
-                    Ok(())
+                    // 投票期间商品可能被转移/删除，执行时再次校验归属
+                    Self::ensure_product_of_shop(shop_id, *product_id)?;
+                    T::ProductProvider::update_price(*product_id, *new_price)
                 },
-                ProposalType::ProductListing { product_cid } => {
-                    // 商品上架需要链下解析 CID，记录提案已执行
-                    Self::deposit_event(Event::ProposalExecutionNote {
-                        proposal_id: proposal.id,
-                        note: "ProductListing: requires off-chain CID parsing".into(),
-                    });
-                    let _ = product_cid;
-                    Ok(())
+                ProposalType::ProductListing { .. } => {
+                    // 创建时已拒绝，仅升级前遗留的提案会走到这里
+                    Err(Error::<T>::ProposalTypeNotSupported.into())
                 },
                 ProposalType::ProductDelisting { product_id } => {
-                    // 记录下架请求，实际执行需要 ProductProvider
-                    Self::deposit_event(Event::ProposalExecutionNote {
-                        proposal_id: proposal.id,
-                        note: "ProductDelisting executed".into(),
-                    });
-                    let _ = product_id;
-                    Ok(())
+                    Self::ensure_product_of_shop(shop_id, *product_id)?;
+                    T::ProductProvider::delist_product(*product_id)
                 },
                 ProposalType::InventoryAdjustment { product_id, new_inventory } => {
-                    Self::deposit_event(Event::ProposalExecutionNote {
-                        proposal_id: proposal.id,
-                        note: "InventoryAdjustment executed".into(),
-                    });
-                    let _ = (product_id, new_inventory);
-                    Ok(())
+                    Self::ensure_product_of_shop(shop_id, *product_id)?;
+                    let inventory = u32::try_from(*new_inventory).map_err(|_| Error::<T>::InvalidParameter)?;
+                    T::ProductProvider::set_inventory(*product_id, inventory)
                 },
 
                 // ==================== 店铺运营类 ====================
                 ProposalType::Promotion { discount_rate, duration_blocks } => {
-                    // 促销活动记录到事件，实际实现需要扩展店铺模块
-                    Self::deposit_event(Event::ProposalExecutionNote {
-                        proposal_id: proposal.id,
-                        note: "Promotion created".into(),
-                    });
-                    let _ = (discount_rate, duration_blocks);
+                    let ends_at = <frame_system::Pallet<T>>::block_number()
+                        .saturating_add((*duration_blocks).into());
+                    ShopPromotions::<T>::insert(shop_id, (*discount_rate, ends_at));
                     Ok(())
                 },
                 ProposalType::ShopNameChange { new_name } => {
-                    // 店铺名称变更需要链下确认
-                    Self::deposit_event(Event::ProposalExecutionNote {
-                        proposal_id: proposal.id,
-                        note: "ShopNameChange approved".into(),
-                    });
-                    let _ = new_name;
-                    Ok(())
+                    T::ShopProvider::update_shop_info(shop_id, Some(new_name.to_vec()), None)
                 },
                 ProposalType::ShopDescriptionChange { description_cid } => {
-                    Self::deposit_event(Event::ProposalExecutionNote {
-                        proposal_id: proposal.id,
-                        note: "ShopDescriptionChange approved".into(),
-                    });
-                    let _ = description_cid;
-                    Ok(())
+                    T::ShopProvider::update_shop_info(shop_id, None, Some(description_cid.to_vec()))
                 },
                 ProposalType::ShopPause => {
                     T::ShopProvider::pause_shop(shop_id)
@@ -1343,80 +1922,46 @@ pub mod pallet {
 
                 // ==================== 代币经济类 ====================
                 ProposalType::TokenConfigChange { reward_rate, exchange_rate } => {
-                    // 代币配置变更需要扩展 TokenProvider
-                    Self::deposit_event(Event::ProposalExecutionNote {
-                        proposal_id: proposal.id,
-                        note: "TokenConfigChange approved".into(),
-                    });
-                    let _ = (reward_rate, exchange_rate);
-                    Ok(())
+                    T::TokenProvider::governance_set_rates(shop_id, *reward_rate, *exchange_rate)
                 },
-                ProposalType::TokenMint { amount, recipient_cid } => {
-                    // 代币增发需要链下解析 recipient_cid
-                    Self::deposit_event(Event::ProposalExecutionNote {
-                        proposal_id: proposal.id,
-                        note: "TokenMint approved, requires off-chain execution".into(),
-                    });
-                    let _ = (amount, recipient_cid);
-                    Ok(())
+                ProposalType::TokenMint { amount, recipient } => {
+                    T::TokenProvider::governance_mint(shop_id, recipient, *amount)
                 },
                 ProposalType::TokenBurn { amount } => {
-                    Self::deposit_event(Event::ProposalExecutionNote {
-                        proposal_id: proposal.id,
-                        note: "TokenBurn approved".into(),
-                    });
-                    let _ = amount;
-                    Ok(())
+                    let treasury = T::ShopProvider::shop_account(shop_id);
+                    T::TokenProvider::governance_burn(shop_id, &treasury, *amount)
                 },
-                ProposalType::AirdropDistribution { airdrop_cid, total_amount } => {
-                    Self::deposit_event(Event::ProposalExecutionNote {
-                        proposal_id: proposal.id,
-                        note: "AirdropDistribution approved, requires off-chain execution".into(),
-                    });
-                    let _ = (airdrop_cid, total_amount);
+                ProposalType::AirdropDistribution { recipients, total_amount: _ } => {
+                    let treasury = T::ShopProvider::shop_account(shop_id);
+                    for (recipient, amount) in recipients.iter() {
+                        T::TokenProvider::transfer(shop_id, &treasury, recipient, *amount)?;
+                    }
                     Ok(())
                 },
-                ProposalType::Dividend { rate } => {
-                    Self::deposit_event(Event::ProposalExecutionNote {
-                        proposal_id: proposal.id,
-                        note: "Dividend approved".into(),
-                    });
-                    let _ = rate;
-                    Ok(())
+                ProposalType::Dividend { rate, holders } => {
+                    // 按执行时余额计算，避免创建后余额变化与投票快照无关
+                    let entries: Vec<(T::AccountId, BalanceOf<T>)> = holders
+                        .iter()
+                        .map(|holder| {
+                            let balance = T::TokenProvider::token_balance(shop_id, holder);
+                            let amount = balance.saturating_mul((*rate as u128).into()) / 10000u128.into();
+                            (holder.clone(), amount)
+                        })
+                        .filter(|(_, amount)| !amount.is_zero())
+                        .collect();
+                    ensure!(!entries.is_empty(), Error::<T>::InvalidParameter);
+                    T::TokenProvider::governance_distribute_dividend(shop_id, entries)
                 },
 
                 // ==================== 财务管理类 ====================
-                ProposalType::TreasurySpend { amount, recipient_cid, reason_cid } => {
-                    Self::deposit_event(Event::ProposalExecutionNote {
-                        proposal_id: proposal.id,
-                        note: "TreasurySpend approved, requires off-chain execution".into(),
-                    });
-                    let _ = (amount, recipient_cid, reason_cid);
-                    Ok(())
-                },
-                ProposalType::FeeAdjustment { new_fee_rate } => {
-                    Self::deposit_event(Event::ProposalExecutionNote {
-                        proposal_id: proposal.id,
-                        note: "FeeAdjustment approved".into(),
-                    });
-                    let _ = new_fee_rate;
-                    Ok(())
+                ProposalType::TreasurySpend { amount, recipient, reason_cid: _ } => {
+                    T::ShopProvider::treasury_spend(shop_id, recipient, (*amount).into())
                 },
-                ProposalType::RevenueShare { owner_share, token_holder_share } => {
-                    Self::deposit_event(Event::ProposalExecutionNote {
-                        proposal_id: proposal.id,
-                        note: "RevenueShare approved".into(),
-                    });
-                    let _ = (owner_share, token_holder_share);
-                    Ok(())
-                },
-                ProposalType::RefundPolicy { policy_cid } => {
-                    Self::deposit_event(Event::ProposalExecutionNote {
-                        proposal_id: proposal.id,
-                        note: "RefundPolicy approved".into(),
-                    });
-                    let _ = policy_cid;
-                    Ok(())
+                ProposalType::FeeAdjustment { .. }
+                | ProposalType::RevenueShare { .. }
+                | ProposalType::RefundPolicy { .. } => {
+                    // 创建时已拒绝，仅升级前遗留的提案会走到这里
+                    Err(Error::<T>::ProposalTypeNotSupported.into())
                 },
 
                 // ==================== 治理参数类 ====================
@@ -1453,10 +1998,12 @@ pub mod pallet {
                 ProposalType::DirectRewardChange { rate } => {
                     T::CommissionProvider::set_direct_reward_rate(shop_id, *rate)
                 },
-                ProposalType::MultiLevelChange { levels_cid: _, max_total_rate: _ } => {
-                    // 多级分销配置需要解析 CID，暂不支持链上直接执行
-                    // 可通过链下工作者或预言机解析后调用
-                    Ok(())
+                ProposalType::MultiLevelChange { level_rates, max_total_rate } => {
+                    T::ReferralWriter::set_multi_level(
+                        Self::entity_of_shop(shop_id),
+                        level_rates.to_vec(),
+                        *max_total_rate,
+                    )
                 },
                 ProposalType::LevelDiffChange { normal_rate, silver_rate, gold_rate, platinum_rate, diamond_rate } => {
                     T::CommissionProvider::set_level_diff_config(
@@ -1468,9 +2015,12 @@ pub mod pallet {
                         *diamond_rate,
                     )
                 },
-                ProposalType::CustomLevelDiffChange { rates_cid: _, max_depth: _ } => {
-                    // 自定义等级极差配置需要解析 CID，暂不支持链上直接执行
-                    Ok(())
+                ProposalType::CustomLevelDiffChange { level_rates, max_depth } => {
+                    T::LevelDiffWriter::set_custom_rates(
+                        Self::entity_of_shop(shop_id),
+                        level_rates.to_vec(),
+                        *max_depth,
+                    )
                 },
                 ProposalType::FixedAmountChange { amount } => {
                     T::CommissionProvider::set_fixed_amount(shop_id, *amount)
@@ -1481,10 +2031,23 @@ pub mod pallet {
                 ProposalType::RepeatPurchaseChange { rate, min_orders } => {
                     T::CommissionProvider::set_repeat_purchase_config(shop_id, *rate, *min_orders)
                 },
-                ProposalType::SingleLineChange { upline_rate: _, downline_rate: _, base_upline_levels: _, base_downline_levels: _, max_upline_levels: _, max_downline_levels: _ } => {
-                    // 单线收益配置较复杂，需要扩展 CommissionProvider trait
-                    // 暂时只记录提案，后续实现
-                    Ok(())
+                ProposalType::SingleLineChange {
+                    upline_rate,
+                    downline_rate,
+                    base_upline_levels,
+                    base_downline_levels,
+                    max_upline_levels,
+                    max_downline_levels,
+                } => {
+                    T::SingleLineWriter::set_single_line_config(
+                        Self::entity_of_shop(shop_id),
+                        *upline_rate,
+                        *downline_rate,
+                        *base_upline_levels,
+                        *base_downline_levels,
+                        *max_upline_levels,
+                        *max_downline_levels,
+                    )
                 },
 
                 // ==================== 分级提现配置类 ====================
@@ -1531,13 +2094,12 @@ pub mod pallet {
                 ProposalType::EnableCustomLevels { enabled } => {
                     T::MemberProvider::set_custom_levels_enabled(shop_id, *enabled)
                 },
-                ProposalType::AddUpgradeRule { rule_cid: _ } => {
-                    // 升级规则配置需要解析 CID，暂不支持链上直接执行
-                    Ok(())
+                ProposalType::AddUpgradeRule { .. } => {
+                    // 创建时已拒绝，仅升级前遗留的提案会走到这里
+                    Err(Error::<T>::ProposalTypeNotSupported.into())
                 },
-                ProposalType::RemoveUpgradeRule { rule_id: _ } => {
-                    // 需要扩展 MemberProvider trait 添加删除规则方法
-                    Ok(())
+                ProposalType::RemoveUpgradeRule { rule_id } => {
+                    T::MemberProvider::remove_upgrade_rule(shop_id, *rule_id)
                 },
 
                 // ==================== 社区类 ====================
@@ -1553,7 +2115,26 @@ pub mod pallet {
                     // 通用提案只是记录，无需执行
                     Ok(())
                 },
+
+                // ==================== 链上调用类 ====================
+                ProposalType::Call { call } => {
+                    // 白名单可能在投票期间被收紧，执行时再次校验
+                    let entity_id = Self::entity_of_shop(shop_id);
+                    let call = Self::decode_whitelisted_call(entity_id, call)?;
+                    let origin: <T as Config>::RuntimeOrigin = EntityOrigin::Governance(entity_id).into();
+                    call.dispatch(origin).map(|_| ()).map_err(|e| e.error)
+                },
             }
         }
     }
+
+    // ==================== 店铺促销接口 ====================
+
+    impl<T: Config> ShopPromotionProvider<BlockNumberFor<T>> for Pallet<T> {
+        fn active_discount(shop_id: u64, now: BlockNumberFor<T>) -> Option<u16> {
+            ShopPromotions::<T>::get(shop_id)
+                .filter(|(_, ends_at)| *ends_at > now)
+                .map(|(discount_rate, _)| discount_rate)
+        }
+    }
 }
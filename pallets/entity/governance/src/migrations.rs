@@ -0,0 +1,361 @@
+//! Entity Governance 存储迁移
+
+use super::*;
+use alloc::vec::Vec;
+use frame_support::{
+    migrations::VersionedMigration,
+    pallet_prelude::*,
+    traits::UncheckedOnRuntimeUpgrade,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use sp_runtime::traits::Zero;
+use sp_std::marker::PhantomData;
+
+/// v0 → v1：提案类型改为链上可执行的结构，投票记录增加受托权重
+///
+/// v0 的 `TokenMint` / `TreasurySpend` 以 CID 指向链下收款人，`AirdropDistribution` /
+/// `Dividend` / `MultiLevelChange` / `CustomLevelDiffChange` 以 CID 或单一参数描述链下名单与费率，
+/// 无法换算为 v1 的链上收款人、持有人名单与费率表。这些提案连同投票记录、投票权快照一并删除
+///（v0 的投票不锁仓，删除不涉及解锁）；其余提案类型编码不变，原样重新写入。
+///
+/// v0 没有投票委托，保留下来的投票记录 `delegated_weight` 一律为 0。
+pub mod v1 {
+    use super::*;
+
+    pub(crate) mod v0 {
+        use super::*;
+
+        /// v0 提案类型（`AccountId` 泛型与 `Call` 变体为 v1 新增）
+        #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+        pub enum OldProposalType<Balance> {
+            // ==================== 商品管理类 ====================
+            /// 商品价格调整
+            PriceChange { product_id: u64, new_price: Balance },
+            /// 新商品上架
+            ProductListing { product_cid: BoundedVec<u8, ConstU32<64>> },
+            /// 商品下架
+            ProductDelisting { product_id: u64 },
+            /// 库存调整
+            InventoryAdjustment { product_id: u64, new_inventory: u64 },
+
+            // ==================== 店铺运营类 ====================
+            /// 促销活动
+            Promotion { discount_rate: u16, duration_blocks: u32 },
+            /// 修改店铺名称
+            ShopNameChange { new_name: BoundedVec<u8, ConstU32<64>> },
+            /// 修改店铺描述
+            ShopDescriptionChange { description_cid: BoundedVec<u8, ConstU32<64>> },
+            /// 暂停店铺营业
+            ShopPause,
+            /// 恢复店铺营业
+            ShopResume,
+
+            // ==================== 代币经济类 ====================
+            /// 代币配置修改
+            TokenConfigChange { reward_rate: Option<u16>, exchange_rate: Option<u16> },
+            /// 增发代币
+            TokenMint { amount: Balance, recipient_cid: BoundedVec<u8, ConstU32<64>> },
+            /// 销毁代币（从金库）
+            TokenBurn { amount: Balance },
+            /// 空投分发
+            AirdropDistribution { airdrop_cid: BoundedVec<u8, ConstU32<64>>, total_amount: Balance },
+            /// 分红提案
+            Dividend { rate: u16 },
+
+            // ==================== 财务管理类 ====================
+            /// 店铺金库支出
+            TreasurySpend { amount: Balance, recipient_cid: BoundedVec<u8, ConstU32<64>>, reason_cid: BoundedVec<u8, ConstU32<64>> },
+            /// 手续费调整
+            FeeAdjustment { new_fee_rate: u16 },
+            /// 收益分配比例调整
+            RevenueShare { owner_share: u16, token_holder_share: u16 },
+            /// 退款政策调整
+            RefundPolicy { policy_cid: BoundedVec<u8, ConstU32<64>> },
+
+            // ==================== 治理参数类 ====================
+            /// 投票期调整
+            VotingPeriodChange { new_period_blocks: u32 },
+            /// 法定人数调整
+            QuorumChange { new_quorum: u8 },
+            /// 提案门槛调整
+            ProposalThresholdChange { new_threshold: u16 },
+
+            // ==================== 返佣配置类（新增）====================
+            /// 启用/禁用返佣模式
+            CommissionModesChange { modes: u16 },
+            /// 直推奖励配置
+            DirectRewardChange { rate: u16 },
+            /// 多级分销配置
+            MultiLevelChange {
+                /// 各层级配置 (rate, required_directs, required_team_size, required_spent)
+                levels_cid: BoundedVec<u8, ConstU32<64>>,
+                max_total_rate: u16,
+            },
+            /// 等级差价配置（全局等级）
+            LevelDiffChange {
+                normal_rate: u16,
+                silver_rate: u16,
+                gold_rate: u16,
+                platinum_rate: u16,
+                diamond_rate: u16,
+            },
+            /// 自定义等级极差配置
+            CustomLevelDiffChange {
+                /// 各等级返佣率 CID（JSON 格式）
+                rates_cid: BoundedVec<u8, ConstU32<64>>,
+                max_depth: u8,
+            },
+            /// 固定金额配置
+            FixedAmountChange { amount: Balance },
+            /// 首单奖励配置
+            FirstOrderChange { amount: Balance, rate: u16, use_amount: bool },
+            /// 复购奖励配置
+            RepeatPurchaseChange { rate: u16, min_orders: u32 },
+            /// 单线收益配置
+            SingleLineChange {
+                upline_rate: u16,
+                downline_rate: u16,
+                base_upline_levels: u8,
+                base_downline_levels: u8,
+                max_upline_levels: u8,
+                max_downline_levels: u8,
+            },
+
+            // ==================== 分级提现配置类（新增）====================
+            /// 分级提现配置
+            WithdrawalConfigChange {
+                /// 各等级提现配置 CID（JSON 格式）
+                tier_configs_cid: BoundedVec<u8, ConstU32<64>>,
+                enabled: bool,
+                shopping_balance_generates_commission: bool,
+            },
+
+            /// 设置全局最低复购比例底线（万分比，由 Governance 设定）
+            MinRepurchaseRateChange {
+                /// 最低复购比例（万分比，3000 = 30%）
+                min_rate: u16,
+            },
+
+            // ==================== 会员等级体系类（新增）====================
+            /// 添加自定义等级
+            AddCustomLevel {
+                level_id: u8,
+                name: BoundedVec<u8, ConstU32<32>>,
+                threshold: Balance,
+                discount_rate: u16,
+                commission_bonus: u16,
+            },
+            /// 更新自定义等级
+            UpdateCustomLevel {
+                level_id: u8,
+                name: Option<BoundedVec<u8, ConstU32<32>>>,
+                threshold: Option<Balance>,
+                discount_rate: Option<u16>,
+                commission_bonus: Option<u16>,
+            },
+            /// 删除自定义等级
+            RemoveCustomLevel { level_id: u8 },
+            /// 设置等级升级模式
+            SetUpgradeMode { mode: u8 },  // 0=AutoUpgrade, 1=ManualUpgrade, 2=PeriodReset
+            /// 启用/禁用自定义等级
+            EnableCustomLevels { enabled: bool },
+            /// 添加升级规则
+            AddUpgradeRule {
+                /// 规则配置 CID（JSON 格式）
+                rule_cid: BoundedVec<u8, ConstU32<64>>,
+            },
+            /// 删除升级规则
+            RemoveUpgradeRule { rule_id: u32 },
+
+            // ==================== 社区类 ====================
+            /// 社区活动
+            CommunityEvent { event_cid: BoundedVec<u8, ConstU32<64>> },
+            /// 规则建议
+            RuleSuggestion { suggestion_cid: BoundedVec<u8, ConstU32<64>> },
+            /// 通用提案（自定义内容）
+            General { title_cid: BoundedVec<u8, ConstU32<64>>, content_cid: BoundedVec<u8, ConstU32<64>> },
+        }
+
+        /// v0 提案
+        #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+        pub struct OldProposal<AccountId, Balance, BlockNumber, Title, Cid> {
+            pub id: ProposalId,
+            pub shop_id: u64,
+            pub proposer: AccountId,
+            pub proposal_type: OldProposalType<Balance>,
+            pub title: Title,
+            pub description_cid: Option<Cid>,
+            pub status: ProposalStatus,
+            pub created_at: BlockNumber,
+            pub snapshot_block: BlockNumber,
+            pub voting_start: BlockNumber,
+            pub voting_end: BlockNumber,
+            pub execution_time: Option<BlockNumber>,
+            pub yes_votes: Balance,
+            pub no_votes: Balance,
+            pub abstain_votes: Balance,
+        }
+
+        /// v0 投票记录（`delegated_weight` 为 v1 新增）
+        #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+        pub struct OldVoteRecord<AccountId, Balance, BlockNumber> {
+            pub voter: AccountId,
+            pub vote: VoteType,
+            pub weight: Balance,
+            pub voted_at: BlockNumber,
+        }
+    }
+
+    type OldProposalOf<T> = v0::OldProposal<
+        <T as frame_system::Config>::AccountId,
+        BalanceOf<T>,
+        BlockNumberFor<T>,
+        BoundedVec<u8, <T as Config>::MaxTitleLength>,
+        BoundedVec<u8, <T as Config>::MaxCidLength>,
+    >;
+
+    type OldVoteRecordOf<T> = v0::OldVoteRecord<
+        <T as frame_system::Config>::AccountId,
+        BalanceOf<T>,
+        BlockNumberFor<T>,
+    >;
+
+    /// v0 提案类型转为 v1；结构已变更、无法换算的类型返回 None
+    fn migrate_proposal_type<T: Config>(
+        old: v0::OldProposalType<BalanceOf<T>>,
+    ) -> Option<ProposalTypeOf<T>> {
+        use v0::OldProposalType as Old;
+        match old {
+            Old::TokenMint { .. }
+            | Old::AirdropDistribution { .. }
+            | Old::Dividend { .. }
+            | Old::TreasurySpend { .. }
+            | Old::MultiLevelChange { .. }
+            | Old::CustomLevelDiffChange { .. } => None,
+            // 其余变体的序号与字段未变，编码可直接按 v1 解码
+            unchanged => ProposalTypeOf::<T>::decode(&mut &unchanged.encode()[..]).ok(),
+        }
+    }
+
+    /// 未做版本检查的迁移逻辑，通过 [`MigrateV0ToV1`] 使用
+    pub struct InnerMigrateV0ToV1<T>(PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV0ToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut migrated = 0u64;
+            let mut dropped: Vec<(ProposalId, u64)> = Vec::new();
+            Proposals::<T>::translate::<OldProposalOf<T>, _>(|id, old| {
+                let Some(proposal_type) = migrate_proposal_type::<T>(old.proposal_type) else {
+                    dropped.push((id, old.shop_id));
+                    return None;
+                };
+                migrated += 1;
+                Some(Proposal {
+                    id: old.id,
+                    shop_id: old.shop_id,
+                    proposer: old.proposer,
+                    proposal_type,
+                    title: old.title,
+                    description_cid: old.description_cid,
+                    status: old.status,
+                    created_at: old.created_at,
+                    snapshot_block: old.snapshot_block,
+                    voting_start: old.voting_start,
+                    voting_end: old.voting_end,
+                    execution_time: old.execution_time,
+                    yes_votes: old.yes_votes,
+                    no_votes: old.no_votes,
+                    abstain_votes: old.abstain_votes,
+                })
+            });
+
+            let mut removed = 0u64;
+            for (id, shop_id) in &dropped {
+                ShopProposals::<T>::mutate(shop_id, |ids| ids.retain(|p| p != id));
+                removed = removed
+                    .saturating_add(VoteRecords::<T>::clear_prefix(id, u32::MAX, None).unique as u64)
+                    .saturating_add(
+                        VotingPowerSnapshot::<T>::clear_prefix(id, u32::MAX, None).unique as u64,
+                    );
+            }
+
+            // 被删除提案的投票记录已清理，其余记录补上受托权重
+            let mut votes = 0u64;
+            VoteRecords::<T>::translate::<OldVoteRecordOf<T>, _>(|_, _, old| {
+                votes += 1;
+                Some(VoteRecord {
+                    voter: old.voter,
+                    vote: old.vote,
+                    weight: old.weight,
+                    delegated_weight: Zero::zero(),
+                    voted_at: old.voted_at,
+                })
+            });
+
+            log::info!(
+                target: "runtime::entity-governance",
+                "migrated {} proposals and {} vote records to v1, dropped {} proposals with reshaped types ({} vote entries)",
+                migrated,
+                votes,
+                dropped.len(),
+                removed,
+            );
+            let proposals = migrated.saturating_add(dropped.len() as u64);
+            T::DbWeight::get().reads_writes(
+                proposals.saturating_add(dropped.len() as u64).saturating_add(votes),
+                proposals.saturating_add(dropped.len() as u64).saturating_add(removed).saturating_add(votes),
+            )
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+            let mut kept = 0u32;
+            for raw in Proposals::<T>::iter_keys().filter_map(|id| {
+                frame_support::storage::unhashed::get_raw(&Proposals::<T>::hashed_key_for(id))
+            }) {
+                let old = OldProposalOf::<T>::decode(&mut &raw[..])
+                    .map_err(|_| sp_runtime::TryRuntimeError::Other("undecodable v0 proposal"))?;
+                if migrate_proposal_type::<T>(old.proposal_type).is_some() {
+                    kept += 1;
+                }
+            }
+            Ok(kept.encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+            let kept = u32::decode(&mut &state[..])
+                .map_err(|_| sp_runtime::TryRuntimeError::Other("invalid pre_upgrade state"))?;
+            let mut migrated = 0u32;
+            for (id, proposal) in Proposals::<T>::iter() {
+                ensure!(proposal.id == id, "proposal id mismatch after migration");
+                migrated += 1;
+            }
+            ensure!(kept == migrated, "proposals lost during migration");
+            // 投票记录须全部可按 v1 结构解码
+            for (proposal_id, _) in VoteRecords::<T>::iter_keys() {
+                ensure!(Proposals::<T>::contains_key(proposal_id), "vote record of a dropped proposal");
+            }
+            ensure!(
+                VoteRecords::<T>::iter_keys().count() == VoteRecords::<T>::iter_values().count(),
+                "undecodable vote record after migration"
+            );
+            for (_, ids) in ShopProposals::<T>::iter() {
+                ensure!(
+                    ids.iter().all(|id| Proposals::<T>::contains_key(id)),
+                    "shop proposal index points at a dropped proposal"
+                );
+            }
+            Ok(())
+        }
+    }
+
+    /// 带版本检查的 v0 → v1 迁移（仅在链上版本为 0 时执行，完成后写入版本 1）
+    pub type MigrateV0ToV1<T> = VersionedMigration<
+        0,
+        1,
+        InnerMigrateV0ToV1<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
@@ -6,10 +6,10 @@ use frame_support::{
 };
 use frame_system as system;
 use pallet_entity_common::{
-    EntityProvider, EntityStatus, EntityTokenProvider, MemberMode,
-    ShopProvider, ShopType, TokenType,
+    EntityProvider, EntityStatus, EntityTokenProvider, MemberMode, ProductCategory,
+    ProductProvider, ShopProvider, ShopType, TokenType,
 };
-use pallet_entity_commission::{NullCommissionProvider, NullMemberProvider};
+use pallet_entity_commission::{NullCommissionProvider, NullMemberProvider, ReferralPlanWriter};
 use sp_core::H256;
 use sp_runtime::{
     traits::{BlakeTwo256, IdentityLookup},
@@ -128,6 +128,91 @@ impl ShopProvider<u64> for MockShopProvider {
     fn update_shop_rating(_: u64, _: u8) -> Result<(), DispatchError> { Ok(()) }
     fn deduct_operating_fund(_: u64, _: u128) -> Result<(), DispatchError> { Ok(()) }
     fn operating_balance(_: u64) -> u128 { 1_000_000 }
+    fn treasury_spend(shop_id: u64, to: &u64, amount: u128) -> Result<(), DispatchError> {
+        let spent = TREASURY_SPENT.with(|t| *t.borrow().get(&shop_id).unwrap_or(&0));
+        if spent.saturating_add(amount) > Self::operating_balance(shop_id) {
+            return Err(DispatchError::Other("InsufficientOperatingFund"));
+        }
+        TREASURY_SPENT.with(|t| t.borrow_mut().insert(shop_id, spent + amount));
+        let paid = get_treasury_paid(*to);
+        TREASURY_PAID.with(|t| t.borrow_mut().insert(*to, paid + amount));
+        Ok(())
+    }
+    fn update_shop_info(shop_id: u64, name: Option<Vec<u8>>, _: Option<Vec<u8>>) -> Result<(), DispatchError> {
+        if let Some(n) = name {
+            SHOP_NAMES.with(|m| m.borrow_mut().insert(shop_id, n));
+        }
+        Ok(())
+    }
+}
+
+pub fn get_treasury_paid(who: u64) -> u128 {
+    TREASURY_PAID.with(|t| *t.borrow().get(&who).unwrap_or(&0))
+}
+
+pub fn get_shop_name(shop_id: u64) -> Option<Vec<u8>> {
+    SHOP_NAMES.with(|m| m.borrow().get(&shop_id).cloned())
+}
+
+// ==================== Mock ProductProvider ====================
+
+/// product_id -> (shop_id, price, stock, on_sale)
+pub fn set_product(product_id: u64, shop_id: u64, price: u128, stock: u32) {
+    PRODUCTS.with(|p| p.borrow_mut().insert(product_id, (shop_id, price, stock, true)));
+}
+
+pub fn get_product(product_id: u64) -> Option<(u64, u128, u32, bool)> {
+    PRODUCTS.with(|p| p.borrow().get(&product_id).copied())
+}
+
+pub struct MockProductProvider;
+impl ProductProvider<u64, u128> for MockProductProvider {
+    fn product_exists(product_id: u64) -> bool { get_product(product_id).is_some() }
+    fn is_product_on_sale(product_id: u64) -> bool { get_product(product_id).map(|p| p.3).unwrap_or(false) }
+    fn product_shop_id(product_id: u64) -> Option<u64> { get_product(product_id).map(|p| p.0) }
+    fn product_price(product_id: u64) -> Option<u128> { get_product(product_id).map(|p| p.1) }
+    fn product_stock(product_id: u64) -> Option<u32> { get_product(product_id).map(|p| p.2) }
+    fn product_category(_: u64) -> Option<ProductCategory> { Some(ProductCategory::Physical) }
+    fn deduct_stock(_: u64, _: u32) -> Result<(), DispatchError> { Ok(()) }
+    fn restore_stock(_: u64, _: u32) -> Result<(), DispatchError> { Ok(()) }
+    fn add_sold_count(_: u64, _: u32) -> Result<(), DispatchError> { Ok(()) }
+    fn update_price(product_id: u64, new_price: u128) -> Result<(), DispatchError> {
+        PRODUCTS.with(|p| match p.borrow_mut().get_mut(&product_id) {
+            Some(product) => { product.1 = new_price; Ok(()) },
+            None => Err(DispatchError::Other("ProductNotFound")),
+        })
+    }
+    fn delist_product(product_id: u64) -> Result<(), DispatchError> {
+        PRODUCTS.with(|p| match p.borrow_mut().get_mut(&product_id) {
+            Some(product) => { product.3 = false; Ok(()) },
+            None => Err(DispatchError::Other("ProductNotFound")),
+        })
+    }
+    fn set_inventory(product_id: u64, new_inventory: u32) -> Result<(), DispatchError> {
+        PRODUCTS.with(|p| match p.borrow_mut().get_mut(&product_id) {
+            Some(product) => { product.2 = new_inventory; Ok(()) },
+            None => Err(DispatchError::Other("ProductNotFound")),
+        })
+    }
+}
+
+// ==================== Mock ReferralPlanWriter ====================
+
+pub fn get_multi_level(entity_id: u64) -> Option<(Vec<u16>, u16)> {
+    MULTI_LEVELS.with(|m| m.borrow().get(&entity_id).cloned())
+}
+
+pub struct MockReferralWriter;
+impl ReferralPlanWriter<u128> for MockReferralWriter {
+    fn set_direct_rate(_: u64, _: u16) -> Result<(), DispatchError> { Ok(()) }
+    fn set_multi_level(entity_id: u64, level_rates: Vec<u16>, max_total_rate: u16) -> Result<(), DispatchError> {
+        MULTI_LEVELS.with(|m| m.borrow_mut().insert(entity_id, (level_rates, max_total_rate)));
+        Ok(())
+    }
+    fn set_fixed_amount(_: u64, _: u128) -> Result<(), DispatchError> { Ok(()) }
+    fn set_first_order(_: u64, _: u128, _: u16, _: bool) -> Result<(), DispatchError> { Ok(()) }
+    fn set_repeat_purchase(_: u64, _: u16, _: u32) -> Result<(), DispatchError> { Ok(()) }
+    fn clear_config(_: u64) -> Result<(), DispatchError> { Ok(()) }
 }
 
 // ==================== Mock TokenProvider ====================
@@ -138,6 +223,14 @@ use std::collections::HashMap;
 thread_local! {
     static TOKEN_BALANCES: RefCell<HashMap<(u64, u64), u128>> = RefCell::new(HashMap::new());
     static TOKEN_ENABLED: RefCell<HashMap<u64, bool>> = RefCell::new(HashMap::new());
+    static TOKEN_RATES: RefCell<HashMap<u64, (u16, u16)>> = RefCell::new(HashMap::new());
+    static PENDING_DIVIDENDS: RefCell<HashMap<(u64, u64), u128>> = RefCell::new(HashMap::new());
+    static PRODUCTS: RefCell<HashMap<u64, (u64, u128, u32, bool)>> = RefCell::new(HashMap::new());
+    static TREASURY_SPENT: RefCell<HashMap<u64, u128>> = RefCell::new(HashMap::new());
+    static TREASURY_PAID: RefCell<HashMap<u64, u128>> = RefCell::new(HashMap::new());
+    static SHOP_NAMES: RefCell<HashMap<u64, Vec<u8>>> = RefCell::new(HashMap::new());
+    static MULTI_LEVELS: RefCell<HashMap<u64, (Vec<u16>, u16)>> = RefCell::new(HashMap::new());
+    static TOKEN_LOCKS: RefCell<HashMap<(u64, u64), (u128, u64, u64)>> = RefCell::new(HashMap::new());
 }
 
 pub fn set_token_balance(shop_id: u64, who: u64, amount: u128) {
@@ -152,6 +245,19 @@ pub fn get_token_balance(shop_id: u64, who: u64) -> u128 {
     TOKEN_BALANCES.with(|b| *b.borrow().get(&(shop_id, who)).unwrap_or(&0))
 }
 
+/// 设置锁仓 (amount, locked_since, unlock_at)
+pub fn set_token_lock(shop_id: u64, who: u64, amount: u128, locked_since: u64, unlock_at: u64) {
+    TOKEN_LOCKS.with(|l| l.borrow_mut().insert((shop_id, who), (amount, locked_since, unlock_at)));
+}
+
+pub fn get_token_rates(shop_id: u64) -> Option<(u16, u16)> {
+    TOKEN_RATES.with(|r| r.borrow().get(&shop_id).copied())
+}
+
+pub fn get_pending_dividend(shop_id: u64, who: u64) -> u128 {
+    PENDING_DIVIDENDS.with(|d| *d.borrow().get(&(shop_id, who)).unwrap_or(&0))
+}
+
 pub struct MockTokenProvider;
 impl EntityTokenProvider<u64, u128> for MockTokenProvider {
     fn is_token_enabled(entity_id: u64) -> bool {
@@ -162,12 +268,50 @@ impl EntityTokenProvider<u64, u128> for MockTokenProvider {
     }
     fn reward_on_purchase(_: u64, _: &u64, _: u128) -> Result<u128, DispatchError> { Ok(0) }
     fn redeem_for_discount(_: u64, _: &u64, _: u128) -> Result<u128, DispatchError> { Ok(0) }
-    fn transfer(_: u64, _: &u64, _: &u64, _: u128) -> Result<(), DispatchError> { Ok(()) }
+    fn transfer(entity_id: u64, from: &u64, to: &u64, amount: u128) -> Result<(), DispatchError> {
+        let from_balance = get_token_balance(entity_id, *from);
+        if from_balance < amount {
+            return Err(DispatchError::Other("InsufficientBalance"));
+        }
+        set_token_balance(entity_id, *from, from_balance - amount);
+        set_token_balance(entity_id, *to, get_token_balance(entity_id, *to) + amount);
+        Ok(())
+    }
     fn reserve(_: u64, _: &u64, _: u128) -> Result<(), DispatchError> { Ok(()) }
     fn unreserve(_: u64, _: &u64, _: u128) -> u128 { 0 }
     fn repatriate_reserved(_: u64, _: &u64, _: &u64, _: u128) -> Result<u128, DispatchError> { Ok(0) }
     fn get_token_type(_: u64) -> TokenType { TokenType::Governance }
     fn total_supply(_: u64) -> u128 { TOTAL_SUPPLY }
+    fn lock_info(entity_id: u64, holder: &u64) -> Option<(u128, u64, u64)> {
+        TOKEN_LOCKS.with(|l| l.borrow().get(&(entity_id, *holder)).copied())
+    }
+    fn governance_mint(entity_id: u64, to: &u64, amount: u128) -> Result<(), DispatchError> {
+        set_token_balance(entity_id, *to, get_token_balance(entity_id, *to) + amount);
+        Ok(())
+    }
+    fn governance_burn(entity_id: u64, from: &u64, amount: u128) -> Result<(), DispatchError> {
+        let balance = get_token_balance(entity_id, *from);
+        if balance < amount {
+            return Err(DispatchError::Other("InsufficientBalance"));
+        }
+        set_token_balance(entity_id, *from, balance - amount);
+        Ok(())
+    }
+    fn governance_set_rates(entity_id: u64, reward_rate: Option<u16>, exchange_rate: Option<u16>) -> Result<(), DispatchError> {
+        let (reward, exchange) = get_token_rates(entity_id).unwrap_or((500, 1000));
+        TOKEN_RATES.with(|r| r.borrow_mut().insert(
+            entity_id,
+            (reward_rate.unwrap_or(reward), exchange_rate.unwrap_or(exchange)),
+        ));
+        Ok(())
+    }
+    fn governance_distribute_dividend(entity_id: u64, recipients: Vec<(u64, u128)>) -> Result<(), DispatchError> {
+        for (holder, amount) in recipients {
+            let pending = get_pending_dividend(entity_id, holder);
+            PENDING_DIVIDENDS.with(|d| d.borrow_mut().insert((entity_id, holder), pending + amount));
+        }
+        Ok(())
+    }
 }
 
 // ==================== 常量 ====================
@@ -187,6 +331,8 @@ parameter_types! {
     pub const PassThreshold: u8 = 50;
     pub const QuorumThreshold: u8 = 10;
     pub const MinProposalThreshold: u16 = 100; // 1%
+    pub MaxProposalCallWeight: frame_support::weights::Weight =
+        frame_support::weights::Weight::from_parts(1_000_000_000, 64 * 1024);
 }
 
 impl pallet_entity_governance::Config for Test {
@@ -194,8 +340,12 @@ impl pallet_entity_governance::Config for Test {
     type EntityProvider = MockEntityProvider;
     type ShopProvider = MockShopProvider;
     type TokenProvider = MockTokenProvider;
+    type ProductProvider = MockProductProvider;
     type CommissionProvider = NullCommissionProvider;
     type MemberProvider = NullMemberProvider;
+    type ReferralWriter = MockReferralWriter;
+    type LevelDiffWriter = ();
+    type SingleLineWriter = ();
     type VotingPeriod = VotingPeriod;
     type ExecutionDelay = ExecutionDelay;
     type PassThreshold = PassThreshold;
@@ -205,6 +355,10 @@ impl pallet_entity_governance::Config for Test {
     type MaxCidLength = ConstU32<64>;
     type MaxActiveProposals = ConstU32<10>;
     type MaxCommitteeSize = ConstU32<10>;
+    type MaxDelegators = ConstU32<3>;
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type MaxProposalCallWeight = MaxProposalCallWeight;
 }
 
 // ==================== 构建器 ====================
@@ -240,6 +394,11 @@ impl ExtBuilder {
             set_token_balance(SHOP_ID, BOB, 150_000);
             set_token_balance(SHOP_ID, CHARLIE, 50_000);
             set_token_balance(SHOP_ID, OWNER, 100_000);
+            // 店铺金库持有 50_000 代币
+            set_token_balance(SHOP_ID, MockShopProvider::shop_account(SHOP_ID), 50_000);
+            // 商品 1 属于 SHOP_ID，商品 2 属于 SHOP_ID_2
+            set_product(1, SHOP_ID, 1_000, 10);
+            set_product(2, SHOP_ID_2, 2_000, 10);
         });
         ext
     }
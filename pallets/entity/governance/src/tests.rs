@@ -2,14 +2,14 @@ use crate::mock::*;
 use crate::pallet::*;
 use frame_support::{assert_noop, assert_ok};
 
-fn proposal_type_general() -> ProposalType<u128> {
+fn proposal_type_general() -> ProposalType<u64, u128> {
     ProposalType::General {
         title_cid: b"test".to_vec().try_into().unwrap(),
         content_cid: b"content".to_vec().try_into().unwrap(),
     }
 }
 
-fn proposal_type_price_change() -> ProposalType<u128> {
+fn proposal_type_price_change() -> ProposalType<u64, u128> {
     ProposalType::PriceChange { product_id: 1, new_price: 500 }
 }
 
@@ -563,12 +563,12 @@ fn create_proposal_fails_invalid_discount_rate() {
 }
 
 #[test]
-fn create_proposal_fails_invalid_revenue_share() {
+fn create_proposal_fails_zero_promotion() {
     ExtBuilder::build().execute_with(|| {
         assert_noop!(
             EntityGovernance::create_proposal(
                 RuntimeOrigin::signed(ALICE), SHOP_ID,
-                ProposalType::RevenueShare { owner_share: 8000, token_holder_share: 3000 },
+                ProposalType::Promotion { discount_rate: 1000, duration_blocks: 0 },
                 b"Bad".to_vec(), None,
             ),
             Error::<Test>::InvalidParameter
@@ -576,6 +576,27 @@ fn create_proposal_fails_invalid_revenue_share() {
     });
 }
 
+#[test]
+fn create_proposal_rejects_types_without_executor() {
+    ExtBuilder::build().execute_with(|| {
+        let cid = || b"cid".to_vec().try_into().unwrap();
+        for pt in [
+            ProposalType::ProductListing { product_cid: cid() },
+            ProposalType::AddUpgradeRule { rule_cid: cid() },
+            ProposalType::FeeAdjustment { new_fee_rate: 250 },
+            ProposalType::RevenueShare { owner_share: 7000, token_holder_share: 3000 },
+            ProposalType::RefundPolicy { policy_cid: cid() },
+        ] {
+            assert_noop!(
+                EntityGovernance::create_proposal(
+                    RuntimeOrigin::signed(ALICE), SHOP_ID, pt, b"Bad".to_vec(), None,
+                ),
+                Error::<Test>::ProposalTypeNotSupported
+            );
+        }
+    });
+}
+
 #[test]
 fn create_proposal_fails_invalid_quorum_change() {
     ExtBuilder::build().execute_with(|| {
@@ -607,10 +628,10 @@ fn create_proposal_fails_invalid_upgrade_mode() {
 #[test]
 fn create_proposal_valid_params_pass() {
     ExtBuilder::build().execute_with(|| {
-        // 有效的 RevenueShare（总和刚好 10000）
+        // 有效的 ProposalThresholdChange（刚好 10000）
         assert_ok!(EntityGovernance::create_proposal(
             RuntimeOrigin::signed(ALICE), SHOP_ID,
-            ProposalType::RevenueShare { owner_share: 7000, token_holder_share: 3000 },
+            ProposalType::ProposalThresholdChange { new_threshold: 10000 },
             b"OK".to_vec(), None,
         ));
     });
@@ -664,3 +685,564 @@ fn full_governance_lifecycle() {
         assert_eq!(proposal.status, ProposalStatus::Executed);
     });
 }
+
+// ==================== 提案链上执行 ====================
+
+/// 创建提案 → BOB/CHARLIE 赞成 → 结束投票 → 执行，返回提案 ID
+fn pass_and_execute(proposal_type: ProposalType<u64, u128>) -> ProposalId {
+    let proposal_id = NextProposalId::<Test>::get();
+    assert_ok!(EntityGovernance::create_proposal(
+        RuntimeOrigin::signed(ALICE), SHOP_ID,
+        proposal_type, b"Exec".to_vec(), None,
+    ));
+    assert_ok!(EntityGovernance::vote(RuntimeOrigin::signed(BOB), proposal_id, VoteType::Yes));
+    assert_ok!(EntityGovernance::vote(RuntimeOrigin::signed(CHARLIE), proposal_id, VoteType::Yes));
+    advance_blocks(101);
+    assert_ok!(EntityGovernance::finalize_voting(RuntimeOrigin::signed(ALICE), proposal_id));
+    advance_blocks(50);
+    assert_ok!(EntityGovernance::execute_proposal(RuntimeOrigin::signed(ALICE), proposal_id));
+    proposal_id
+}
+
+#[test]
+fn execute_product_proposals_update_product() {
+    ExtBuilder::build().execute_with(|| {
+        let id = pass_and_execute(proposal_type_price_change());
+        assert_eq!(Proposals::<Test>::get(id).unwrap().status, ProposalStatus::Executed);
+        assert_eq!(get_product(1).unwrap().1, 500);
+
+        pass_and_execute(ProposalType::InventoryAdjustment { product_id: 1, new_inventory: 77 });
+        assert_eq!(get_product(1).unwrap().2, 77);
+
+        pass_and_execute(ProposalType::ProductDelisting { product_id: 1 });
+        assert!(!get_product(1).unwrap().3);
+    });
+}
+
+#[test]
+fn create_proposal_fails_product_not_in_shop() {
+    ExtBuilder::build().execute_with(|| {
+        // 商品 2 属于 SHOP_ID_2
+        assert_noop!(
+            EntityGovernance::create_proposal(
+                RuntimeOrigin::signed(ALICE), SHOP_ID,
+                ProposalType::PriceChange { product_id: 2, new_price: 1 },
+                b"Bad".to_vec(), None,
+            ),
+            Error::<Test>::ProductNotInShop
+        );
+        assert_noop!(
+            EntityGovernance::create_proposal(
+                RuntimeOrigin::signed(ALICE), SHOP_ID,
+                ProposalType::InventoryAdjustment { product_id: 1, new_inventory: u32::MAX as u64 + 1 },
+                b"Bad".to_vec(), None,
+            ),
+            Error::<Test>::InvalidParameter
+        );
+    });
+}
+
+#[test]
+fn execute_token_proposals_use_typed_recipients() {
+    ExtBuilder::build().execute_with(|| {
+        let treasury = MockShopProvider::shop_account(SHOP_ID);
+
+        pass_and_execute(ProposalType::TokenMint { amount: 1_000, recipient: OWNER_2 });
+        assert_eq!(get_token_balance(SHOP_ID, OWNER_2), 1_000);
+
+        pass_and_execute(ProposalType::TokenBurn { amount: 10_000 });
+        assert_eq!(get_token_balance(SHOP_ID, treasury), 40_000);
+
+        pass_and_execute(ProposalType::AirdropDistribution {
+            recipients: vec![(OWNER_2, 300), (ALICE, 700)].try_into().unwrap(),
+            total_amount: 1_000,
+        });
+        assert_eq!(get_token_balance(SHOP_ID, OWNER_2), 1_300);
+        assert_eq!(get_token_balance(SHOP_ID, ALICE), 20_700);
+        assert_eq!(get_token_balance(SHOP_ID, treasury), 39_000);
+
+        pass_and_execute(ProposalType::TokenConfigChange { reward_rate: Some(800), exchange_rate: None });
+        assert_eq!(get_token_rates(SHOP_ID), Some((800, 1000)));
+
+        // 10% 分红：BOB 150_000 → 15_000，CHARLIE 50_000 → 5_000
+        pass_and_execute(ProposalType::Dividend {
+            rate: 1000,
+            holders: vec![BOB, CHARLIE].try_into().unwrap(),
+        });
+        assert_eq!(get_pending_dividend(SHOP_ID, BOB), 15_000);
+        assert_eq!(get_pending_dividend(SHOP_ID, CHARLIE), 5_000);
+    });
+}
+
+#[test]
+fn create_proposal_fails_invalid_recipients() {
+    ExtBuilder::build().execute_with(|| {
+        // 空投总额与明细不符
+        assert_noop!(
+            EntityGovernance::create_proposal(
+                RuntimeOrigin::signed(ALICE), SHOP_ID,
+                ProposalType::AirdropDistribution {
+                    recipients: vec![(BOB, 100)].try_into().unwrap(),
+                    total_amount: 200,
+                },
+                b"Bad".to_vec(), None,
+            ),
+            Error::<Test>::InvalidParameter
+        );
+        // 重复的分红持有人
+        assert_noop!(
+            EntityGovernance::create_proposal(
+                RuntimeOrigin::signed(ALICE), SHOP_ID,
+                ProposalType::Dividend { rate: 100, holders: vec![BOB, BOB].try_into().unwrap() },
+                b"Bad".to_vec(), None,
+            ),
+            Error::<Test>::InvalidParameter
+        );
+    });
+}
+
+#[test]
+fn execute_treasury_and_shop_proposals() {
+    ExtBuilder::build().execute_with(|| {
+        pass_and_execute(ProposalType::TreasurySpend {
+            amount: 400_000,
+            recipient: OWNER_2,
+            reason_cid: b"reason".to_vec().try_into().unwrap(),
+        });
+        assert_eq!(get_treasury_paid(OWNER_2), 400_000);
+
+        pass_and_execute(ProposalType::ShopNameChange { new_name: b"DAO Shop".to_vec().try_into().unwrap() });
+        assert_eq!(get_shop_name(SHOP_ID), Some(b"DAO Shop".to_vec()));
+
+        pass_and_execute(ProposalType::Promotion { discount_rate: 1500, duration_blocks: 1_000 });
+        let now = System::block_number();
+        let (discount, ends_at) = ShopPromotions::<Test>::get(SHOP_ID).unwrap();
+        assert_eq!(discount, 1500);
+        assert_eq!(ends_at, now + 1_000);
+        // transaction 模块通过 ShopPromotionProvider 查询折扣，到期后失效
+        use pallet_entity_common::ShopPromotionProvider;
+        assert_eq!(EntityGovernance::active_discount(SHOP_ID, now), Some(1500));
+        assert_eq!(EntityGovernance::active_discount(SHOP_ID, ends_at), None);
+
+        let id = pass_and_execute(ProposalType::RemoveUpgradeRule { rule_id: 1 });
+        assert_eq!(EntityGovernance::proposals(id).unwrap().status, ProposalStatus::Executed);
+    });
+}
+
+#[test]
+fn execution_failure_marks_proposal_failed() {
+    ExtBuilder::build().execute_with(|| {
+        // 金库余额 1_000_000，支出超额
+        let id = pass_and_execute(ProposalType::TreasurySpend {
+            amount: 2_000_000,
+            recipient: OWNER_2,
+            reason_cid: b"too much".to_vec().try_into().unwrap(),
+        });
+        assert_eq!(Proposals::<Test>::get(id).unwrap().status, ProposalStatus::ExecutionFailed);
+        assert_eq!(get_treasury_paid(OWNER_2), 0);
+        System::assert_last_event(
+            Event::<Test>::ProposalExecutionFailed {
+                proposal_id: id,
+                error: sp_runtime::DispatchError::Other("InsufficientOperatingFund"),
+            }
+            .into(),
+        );
+
+        // 失败的提案不可再次执行
+        assert_noop!(
+            EntityGovernance::execute_proposal(RuntimeOrigin::signed(ALICE), id),
+            Error::<Test>::InvalidProposalStatus
+        );
+    });
+}
+
+#[test]
+fn execute_multi_level_change_writes_referral_plan() {
+    ExtBuilder::build().execute_with(|| {
+        pass_and_execute(ProposalType::MultiLevelChange {
+            level_rates: vec![500, 300, 200].try_into().unwrap(),
+            max_total_rate: 1000,
+        });
+        // 返佣方案按 entity_id 写入（SHOP_ID → entity 1）
+        assert_eq!(get_multi_level(1), Some((vec![500, 300, 200], 1000)));
+    });
+}
+
+// ==================== 链上调用提案 ====================
+
+fn encoded_call(call: RuntimeCall) -> (ProposalType<u64, u128>, (u8, u8)) {
+    use codec::Encode;
+    let bytes = call.encode();
+    let key = (bytes[0], bytes[1]);
+    (ProposalType::Call { call: bytes.try_into().unwrap() }, key)
+}
+
+fn remark_call() -> (ProposalType<u64, u128>, (u8, u8)) {
+    encoded_call(RuntimeCall::System(frame_system::Call::remark { remark: b"gov".to_vec() }))
+}
+
+#[test]
+fn set_call_whitelist_validation() {
+    ExtBuilder::build().execute_with(|| {
+        let (_, key) = remark_call();
+        assert_noop!(
+            EntityGovernance::set_call_whitelist(
+                RuntimeOrigin::signed(ALICE), 1, vec![key].try_into().unwrap(),
+            ),
+            Error::<Test>::NotShopOwner
+        );
+
+        // 不能放行治理模块自身的调用
+        let (_, own) = encoded_call(RuntimeCall::EntityGovernance(crate::Call::execute_proposal { proposal_id: 0 }));
+        assert_noop!(
+            EntityGovernance::set_call_whitelist(
+                RuntimeOrigin::signed(OWNER), 1, vec![key, own].try_into().unwrap(),
+            ),
+            Error::<Test>::CannotWhitelistGovernance
+        );
+
+        assert_ok!(EntityGovernance::set_call_whitelist(
+            RuntimeOrigin::signed(OWNER), 1, vec![key].try_into().unwrap(),
+        ));
+        assert_eq!(CallWhitelist::<Test>::get(1).into_inner(), vec![key]);
+    });
+}
+
+#[test]
+fn create_call_proposal_requires_whitelist() {
+    ExtBuilder::build().execute_with(|| {
+        let (remark, key) = remark_call();
+        assert_noop!(
+            EntityGovernance::create_proposal(
+                RuntimeOrigin::signed(ALICE), SHOP_ID, remark.clone(), b"Call".to_vec(), None,
+            ),
+            Error::<Test>::CallNotWhitelisted
+        );
+
+        assert_noop!(
+            EntityGovernance::create_proposal(
+                RuntimeOrigin::signed(ALICE), SHOP_ID,
+                ProposalType::Call { call: vec![0xff, 0xff, 0xff].try_into().unwrap() },
+                b"Call".to_vec(), None,
+            ),
+            Error::<Test>::CallUndecodable
+        );
+
+        // 白名单按 entity 维度：实体 2 的白名单不影响实体 1
+        assert_ok!(EntityGovernance::set_call_whitelist(
+            RuntimeOrigin::signed(OWNER_2), 2, vec![key].try_into().unwrap(),
+        ));
+        assert_noop!(
+            EntityGovernance::create_proposal(
+                RuntimeOrigin::signed(ALICE), SHOP_ID, remark.clone(), b"Call".to_vec(), None,
+            ),
+            Error::<Test>::CallNotWhitelisted
+        );
+
+        assert_ok!(EntityGovernance::set_call_whitelist(
+            RuntimeOrigin::signed(OWNER), 1, vec![key].try_into().unwrap(),
+        ));
+        assert_ok!(EntityGovernance::create_proposal(
+            RuntimeOrigin::signed(ALICE), SHOP_ID, remark, b"Call".to_vec(), None,
+        ));
+    });
+}
+
+#[test]
+fn call_proposal_dispatches_with_entity_origin() {
+    ExtBuilder::build().execute_with(|| {
+        let (remark, remark_key) = remark_call();
+        // remark_with_event 要求签名来源，实体治理来源派发应失败
+        let (signed_only, signed_key) = encoded_call(RuntimeCall::System(
+            frame_system::Call::remark_with_event { remark: b"gov".to_vec() },
+        ));
+        assert_ok!(EntityGovernance::set_call_whitelist(
+            RuntimeOrigin::signed(OWNER), 1, vec![remark_key, signed_key].try_into().unwrap(),
+        ));
+
+        let id = pass_and_execute(remark);
+        assert_eq!(Proposals::<Test>::get(id).unwrap().status, ProposalStatus::Executed);
+
+        let id = pass_and_execute(signed_only);
+        assert_eq!(Proposals::<Test>::get(id).unwrap().status, ProposalStatus::ExecutionFailed);
+        System::assert_last_event(
+            Event::<Test>::ProposalExecutionFailed {
+                proposal_id: id,
+                error: sp_runtime::DispatchError::BadOrigin,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn call_proposal_rechecks_whitelist_on_execution() {
+    ExtBuilder::build().execute_with(|| {
+        let (remark, key) = remark_call();
+        assert_ok!(EntityGovernance::set_call_whitelist(
+            RuntimeOrigin::signed(OWNER), 1, vec![key].try_into().unwrap(),
+        ));
+        let proposal_id = NextProposalId::<Test>::get();
+        assert_ok!(EntityGovernance::create_proposal(
+            RuntimeOrigin::signed(ALICE), SHOP_ID, remark, b"Call".to_vec(), None,
+        ));
+        assert_ok!(EntityGovernance::vote(RuntimeOrigin::signed(BOB), proposal_id, VoteType::Yes));
+        advance_blocks(101);
+        assert_ok!(EntityGovernance::finalize_voting(RuntimeOrigin::signed(ALICE), proposal_id));
+
+        // 投票结束后白名单被清空
+        assert_ok!(EntityGovernance::set_call_whitelist(
+            RuntimeOrigin::signed(OWNER), 1, Default::default(),
+        ));
+        advance_blocks(50);
+        assert_ok!(EntityGovernance::execute_proposal(RuntimeOrigin::signed(ALICE), proposal_id));
+        assert_eq!(
+            Proposals::<Test>::get(proposal_id).unwrap().status,
+            ProposalStatus::ExecutionFailed
+        );
+    });
+}
+
+// ==================== 投票委托 ====================
+
+#[test]
+fn delegated_power_counted_and_overridable() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(EntityGovernance::delegate_votes(RuntimeOrigin::signed(CHARLIE), 1, BOB, None));
+        assert_eq!(DelegatorsOf::<Test>::get(1, BOB).into_inner(), vec![(CHARLIE, None)]);
+
+        assert_ok!(EntityGovernance::create_proposal(
+            RuntimeOrigin::signed(ALICE), SHOP_ID,
+            proposal_type_general(), b"Test".to_vec(), None,
+        ));
+        // BOB 150_000 + CHARLIE 委托 50_000
+        assert_ok!(EntityGovernance::vote(RuntimeOrigin::signed(BOB), 0, VoteType::Yes));
+        assert_eq!(Proposals::<Test>::get(0).unwrap().yes_votes, 200_000);
+        let record = VoteRecords::<Test>::get(0, BOB).unwrap();
+        assert_eq!((record.weight, record.delegated_weight), (200_000, 50_000));
+        assert_eq!(DelegatedVotes::<Test>::get(0, CHARLIE), Some((BOB, 50_000)));
+
+        // 委托人自投：从受托人票中收回本人权重
+        assert_ok!(EntityGovernance::vote(RuntimeOrigin::signed(CHARLIE), 0, VoteType::No));
+        let proposal = Proposals::<Test>::get(0).unwrap();
+        assert_eq!((proposal.yes_votes, proposal.no_votes), (150_000, 50_000));
+        assert_eq!(VoteRecords::<Test>::get(0, BOB).unwrap().delegated_weight, 0);
+        assert!(DelegatedVotes::<Test>::get(0, CHARLIE).is_none());
+        System::assert_has_event(
+            Event::<Test>::DelegatedVoteOverridden {
+                proposal_id: 0, delegator: CHARLIE, delegate: BOB, weight: 50_000,
+            }
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn redelegation_mid_vote_does_not_double_count() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(EntityGovernance::delegate_votes(RuntimeOrigin::signed(CHARLIE), 1, BOB, None));
+        assert_ok!(EntityGovernance::create_proposal(
+            RuntimeOrigin::signed(ALICE), SHOP_ID,
+            proposal_type_general(), b"Test".to_vec(), None,
+        ));
+        assert_ok!(EntityGovernance::vote(RuntimeOrigin::signed(BOB), 0, VoteType::Yes));
+
+        // 投票期间改委托给 ALICE：CHARLIE 的权重已被 BOB 计入，不再计给 ALICE
+        assert_ok!(EntityGovernance::delegate_votes(RuntimeOrigin::signed(CHARLIE), 1, ALICE, None));
+        assert!(DelegatorsOf::<Test>::get(1, BOB).is_empty());
+        assert_ok!(EntityGovernance::vote(RuntimeOrigin::signed(ALICE), 0, VoteType::Yes));
+
+        assert_eq!(Proposals::<Test>::get(0).unwrap().yes_votes, 200_000 + 20_000);
+        assert_eq!(VoteRecords::<Test>::get(0, ALICE).unwrap().delegated_weight, 0);
+    });
+}
+
+#[test]
+fn category_delegation_takes_precedence() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(EntityGovernance::delegate_votes(RuntimeOrigin::signed(CHARLIE), 1, BOB, None));
+        assert_ok!(EntityGovernance::delegate_votes(
+            RuntimeOrigin::signed(CHARLIE), 1, ALICE, Some(ProposalCategory::Commission),
+        ));
+
+        // 提案 0：社区类 → BOB 代投；提案 1：返佣类 → ALICE 代投
+        assert_ok!(EntityGovernance::create_proposal(
+            RuntimeOrigin::signed(ALICE), SHOP_ID,
+            proposal_type_general(), b"General".to_vec(), None,
+        ));
+        assert_ok!(EntityGovernance::create_proposal(
+            RuntimeOrigin::signed(ALICE), SHOP_ID,
+            ProposalType::DirectRewardChange { rate: 500 }, b"Commission".to_vec(), None,
+        ));
+
+        assert_ok!(EntityGovernance::vote(RuntimeOrigin::signed(BOB), 0, VoteType::Yes));
+        assert_ok!(EntityGovernance::vote(RuntimeOrigin::signed(BOB), 1, VoteType::No));
+        assert_ok!(EntityGovernance::vote(RuntimeOrigin::signed(ALICE), 1, VoteType::Yes));
+
+        assert_eq!(Proposals::<Test>::get(0).unwrap().yes_votes, 200_000);
+        let commission = Proposals::<Test>::get(1).unwrap();
+        assert_eq!((commission.yes_votes, commission.no_votes), (70_000, 150_000));
+    });
+}
+
+#[test]
+fn delegate_votes_validation() {
+    ExtBuilder::build().execute_with(|| {
+        assert_noop!(
+            EntityGovernance::delegate_votes(RuntimeOrigin::signed(BOB), 1, BOB, None),
+            Error::<Test>::SelfDelegation
+        );
+        assert_noop!(
+            EntityGovernance::delegate_votes(RuntimeOrigin::signed(BOB), 99, ALICE, None),
+            Error::<Test>::ShopNotFound
+        );
+        assert_noop!(
+            EntityGovernance::undelegate_votes(RuntimeOrigin::signed(BOB), 1, None),
+            Error::<Test>::DelegationNotFound
+        );
+
+        // MaxDelegators = 3
+        for delegator in [ALICE, CHARLIE, OWNER] {
+            assert_ok!(EntityGovernance::delegate_votes(RuntimeOrigin::signed(delegator), 1, BOB, None));
+        }
+        assert_noop!(
+            EntityGovernance::delegate_votes(RuntimeOrigin::signed(OWNER_2), 1, BOB, None),
+            Error::<Test>::TooManyDelegators
+        );
+
+        assert_ok!(EntityGovernance::undelegate_votes(RuntimeOrigin::signed(ALICE), 1, None));
+        assert!(Delegations::<Test>::get(1, ALICE).is_empty());
+        assert_eq!(DelegatorsOf::<Test>::get(1, BOB).len(), 2);
+    });
+}
+
+// ==================== 时间加权投票权 ====================
+
+#[test]
+fn voting_power_weighted_by_lock_age_and_conviction() {
+    ExtBuilder::build().execute_with(|| {
+        let config = VotingPowerConfig {
+            holding_bonus_period: 100,
+            max_holding_bonus: 5000,
+            conviction_period: 100,
+            max_conviction: 2,
+        };
+        assert_noop!(
+            EntityGovernance::configure_voting_power(RuntimeOrigin::signed(ALICE), 1, config.clone()),
+            Error::<Test>::NotShopOwner
+        );
+        assert_noop!(
+            EntityGovernance::configure_voting_power(
+                RuntimeOrigin::signed(OWNER), 1,
+                VotingPowerConfig { max_conviction: 7, ..config.clone() },
+            ),
+            Error::<Test>::InvalidParameter
+        );
+
+        // 未配置时锁仓不加权
+        set_token_lock(SHOP_ID, CHARLIE, 40_000, 1, 251);
+        assert_eq!(EntityGovernance::calculate_voting_power(SHOP_ID, &CHARLIE), 50_000);
+
+        assert_ok!(EntityGovernance::configure_voting_power(RuntimeOrigin::signed(OWNER), 1, config));
+
+        // 区块 51：锁仓 50 区块 → +25%；剩余 200 区块 → 2 级 +200%
+        advance_blocks(50);
+        assert_eq!(EntityGovernance::calculate_voting_power(SHOP_ID, &CHARLIE), 50_000 + 40_000 * 225 / 100);
+
+        // 区块 151：持有加成封顶 +50%；剩余 100 区块 → 1 级 +100%
+        advance_blocks(100);
+        assert_eq!(EntityGovernance::calculate_voting_power(SHOP_ID, &CHARLIE), 50_000 + 40_000 * 150 / 100);
+
+        // 无锁仓账户不受影响
+        assert_eq!(EntityGovernance::calculate_voting_power(SHOP_ID, &BOB), 150_000);
+    });
+}
+
+#[test]
+fn weighted_power_uses_snapshot_for_delegators() {
+    ExtBuilder::build().execute_with(|| {
+        assert_ok!(EntityGovernance::configure_voting_power(
+            RuntimeOrigin::signed(OWNER), 1,
+            VotingPowerConfig { holding_bonus_period: 0, max_holding_bonus: 0, conviction_period: 100, max_conviction: 6 },
+        ));
+        // CHARLIE 锁仓 50_000 剩余 100 区块 → 1 级，投票权 100_000
+        set_token_lock(SHOP_ID, CHARLIE, 50_000, 1, 101);
+        assert_ok!(EntityGovernance::delegate_votes(RuntimeOrigin::signed(CHARLIE), 1, BOB, None));
+        assert_ok!(EntityGovernance::create_proposal(
+            RuntimeOrigin::signed(ALICE), SHOP_ID,
+            proposal_type_general(), b"Test".to_vec(), None,
+        ));
+        assert_ok!(EntityGovernance::vote(RuntimeOrigin::signed(BOB), 0, VoteType::Yes));
+        assert_eq!(Proposals::<Test>::get(0).unwrap().yes_votes, 250_000);
+        assert_eq!(VotingPowerSnapshot::<Test>::get(0, CHARLIE), Some(100_000));
+
+        // 之后延长锁仓不会放大已快照的权重（委托人自投取快照与当前的较小值）
+        set_token_lock(SHOP_ID, CHARLIE, 50_000, 1, 601);
+        assert_ok!(EntityGovernance::vote(RuntimeOrigin::signed(CHARLIE), 0, VoteType::Abstain));
+        let proposal = Proposals::<Test>::get(0).unwrap();
+        assert_eq!((proposal.yes_votes, proposal.abstain_votes), (150_000, 100_000));
+    });
+}
+
+// ==================== 存储迁移 ====================
+
+#[test]
+fn migration_v1_keeps_unchanged_proposal_types_and_drops_reshaped_ones() {
+    use crate::migrations::v1::{v0::{OldProposal, OldProposalType, OldVoteRecord}, MigrateV0ToV1};
+    use codec::Encode;
+    use frame_support::storage::unhashed;
+    use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+    ExtBuilder::build().execute_with(|| {
+        StorageVersion::new(0).put::<EntityGovernance>();
+        let old = |id: u64, proposal_type: OldProposalType<u128>| OldProposal {
+            id,
+            shop_id: SHOP_ID,
+            proposer: ALICE,
+            proposal_type,
+            title: frame_support::BoundedVec::<u8, frame_support::traits::ConstU32<128>>::try_from(b"Old".to_vec()).unwrap(),
+            description_cid: None::<frame_support::BoundedVec<u8, frame_support::traits::ConstU32<64>>>,
+            status: ProposalStatus::Voting,
+            created_at: 1u64,
+            snapshot_block: 1u64,
+            voting_start: 1u64,
+            voting_end: 101u64,
+            execution_time: None,
+            yes_votes: 20_000u128,
+            no_votes: 0u128,
+            abstain_votes: 0u128,
+        };
+        unhashed::put_raw(
+            &Proposals::<Test>::hashed_key_for(0),
+            &old(0, OldProposalType::PriceChange { product_id: 1, new_price: 500 }).encode(),
+        );
+        unhashed::put_raw(
+            &Proposals::<Test>::hashed_key_for(1),
+            &old(1, OldProposalType::TokenMint {
+                amount: 1_000,
+                recipient_cid: b"QmRecipients".to_vec().try_into().unwrap(),
+            }).encode(),
+        );
+        ShopProposals::<Test>::insert(SHOP_ID, frame_support::BoundedVec::try_from(vec![0u64, 1]).unwrap());
+        VotingPowerSnapshot::<Test>::insert(1, ALICE, 20_000u128);
+        unhashed::put_raw(&VoteRecords::<Test>::hashed_key_for(1, ALICE), &[0u8; 8]);
+        unhashed::put_raw(
+            &VoteRecords::<Test>::hashed_key_for(0, ALICE),
+            &OldVoteRecord { voter: ALICE, vote: VoteType::Yes, weight: 20_000u128, voted_at: 5u64 }.encode(),
+        );
+
+        MigrateV0ToV1::<Test>::on_runtime_upgrade();
+
+        assert_eq!(EntityGovernance::on_chain_storage_version(), 1);
+        let kept = Proposals::<Test>::get(0).expect("unchanged proposal type is kept");
+        assert_eq!(kept.proposal_type, proposal_type_price_change());
+        assert_eq!((kept.status, kept.yes_votes), (ProposalStatus::Voting, 20_000));
+        assert!(Proposals::<Test>::get(1).is_none());
+        assert_eq!(ShopProposals::<Test>::get(SHOP_ID).into_inner(), vec![0]);
+        assert!(VotingPowerSnapshot::<Test>::get(1, ALICE).is_none());
+        assert!(!VoteRecords::<Test>::contains_key(1, ALICE));
+        // v0 没有委托，保留的投票记录受托权重为 0
+        let vote = VoteRecords::<Test>::get(0, ALICE).expect("vote record of kept proposal is migrated");
+        assert_eq!((vote.vote, vote.weight, vote.delegated_weight, vote.voted_at), (VoteType::Yes, 20_000, 0, 5));
+    });
+}
@@ -4,7 +4,8 @@
 //!
 //! 本模块实现店铺代币的 P2P 交易市场，支持：
 //! - NXS 通道：使用原生 NXS 代币买卖店铺代币（链上即时结算）
-//! - USDT 通道：使用 TRC20 USDT 买卖店铺代币（需 OCW 验证）
+//! - USDT 通道：使用稳定币买卖店铺代币（需 OCW 验证），收款方式由支付通道
+//!   （`PaymentRailProvider`，默认 USDT-TRC20）决定，支持 USDC-TRC20、USDT-ERC20/BEP20
 //!
 //! ## 交易模式
 //!
@@ -15,6 +16,8 @@
 //!
 //! - v0.1.0 (2026-02-01): 初始版本，实现 NXS 通道限价单
 //! - v0.2.0 (2026-02-01): Phase 2，实现 USDT 通道 + OCW 验证
+//! - v0.9.0 (2026-10-17): USDT 订单支持多支付通道（多代币 / 多链）
+//! - v0.10.0 (2026-10-17): 链上 OHLCV K线（1m/1h/1d）、真实 24h 统计、Runtime API、on_idle 清理
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -30,8 +33,12 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+pub mod migrations;
+
 pub mod ocw;
 
+pub mod runtime_api;
+
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
@@ -42,7 +49,12 @@ pub mod pallet {
         BoundedVec,
     };
     use frame_system::pallet_prelude::*;
-    use pallet_entity_common::{EntityProvider, EntityTokenProvider, ShopProvider};
+    use pallet_entity_common::{
+        ensure_admin_caller, AdminCaller, EntityProvider, EntityTokenProvider, ShopProvider,
+    };
+    use pallet_trading_common::{
+        PaymentChain, PaymentRail, PaymentRailProvider, PayoutAddress, RailId, DEFAULT_RAIL_ID,
+    };
     use sp_runtime::traits::{CheckedAdd, CheckedMul, CheckedSub, Saturating, Zero};
     use sp_runtime::SaturatedConversion;
     use sp_runtime::transaction_validity::{
@@ -176,8 +188,10 @@ pub mod pallet {
         pub price: BalanceOf<T>,
         /// USDT 价格（仅 USDT 通道使用，精度 10^6）
         pub usdt_price: u64,
-        /// TRON 收款地址（仅 USDT 卖单使用）
-        pub tron_address: Option<TronAddress>,
+        /// 收款地址（仅 USDT 卖单使用，格式由支付通道所在链决定）
+        pub tron_address: Option<PayoutAddress>,
+        /// 支付通道（仅 USDT 通道使用，NXS 通道为 `DEFAULT_RAIL_ID`）
+        pub rail_id: RailId,
         /// 订单状态
         pub status: OrderStatus,
         /// 创建区块
@@ -204,8 +218,10 @@ pub mod pallet {
         pub token_amount: T::TokenBalance,
         /// USDT 金额（精度 10^6）
         pub usdt_amount: u64,
-        /// 卖家 TRON 收款地址
-        pub seller_tron_address: TronAddress,
+        /// 卖家收款地址（TRON 或 EVM，格式由支付通道决定）
+        pub seller_tron_address: PayoutAddress,
+        /// 支付通道（继承自订单）
+        pub rail_id: RailId,
         /// 买家提交的 TRON 交易哈希
         pub tron_tx_hash: Option<TronTxHash>,
         /// 交易状态
@@ -358,6 +374,75 @@ pub mod pallet {
         OneWeek,
     }
 
+    // ==================== K线（OHLCV）数据结构 ====================
+
+    /// 1 分钟 K 线保留数量（24 小时）
+    pub const CANDLE_RETENTION_MINUTE: u32 = 1440;
+    /// 1 小时 K 线保留数量（30 天）
+    pub const CANDLE_RETENTION_HOUR: u32 = 720;
+    /// 1 日 K 线保留数量（1 年）
+    pub const CANDLE_RETENTION_DAY: u32 = 365;
+    /// 待清理 K 线队列容量
+    pub const MAX_CANDLE_PRUNE_QUEUE: u32 = 256;
+
+    /// K 线周期
+    #[derive(Encode, Decode, codec::DecodeWithMemTracking, Clone, Copy, PartialEq, Eq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+    pub enum CandleInterval {
+        /// 1 分钟（BlocksPerHour / 60 个区块）
+        OneMinute,
+        /// 1 小时（BlocksPerHour 个区块）
+        OneHour,
+        /// 1 日（BlocksPerDay 个区块）
+        OneDay,
+    }
+
+    impl CandleInterval {
+        /// 所有周期（成交时逐一更新）
+        pub const ALL: [CandleInterval; 3] =
+            [CandleInterval::OneMinute, CandleInterval::OneHour, CandleInterval::OneDay];
+
+        /// 保留的 K 线数量（超出部分由 on_idle 清理）
+        pub fn retention(&self) -> u32 {
+            match self {
+                CandleInterval::OneMinute => CANDLE_RETENTION_MINUTE,
+                CandleInterval::OneHour => CANDLE_RETENTION_HOUR,
+                CandleInterval::OneDay => CANDLE_RETENTION_DAY,
+            }
+        }
+    }
+
+    /// 单根 K 线
+    #[derive(Encode, Decode, Clone, PartialEq, Eq, TypeInfo, MaxEncodedLen, RuntimeDebug, Default)]
+    pub struct Candle<Balance, TokenBalance> {
+        /// 桶编号（区块号 / 周期区块数）
+        pub bucket: u32,
+        /// 开盘价
+        pub open: Balance,
+        /// 最高价
+        pub high: Balance,
+        /// 最低价
+        pub low: Balance,
+        /// 收盘价
+        pub close: Balance,
+        /// 成交量（Token）
+        pub volume: TokenBalance,
+        /// 成交额（NXS）
+        pub turnover: Balance,
+        /// 成交笔数
+        pub trades: u32,
+        /// 下一根有成交的 K 线桶（on_idle 清理时沿此链前进）
+        pub next_bucket: Option<u32>,
+    }
+
+    /// 每个店铺每个周期已存储的 K 线桶范围
+    #[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, TypeInfo, MaxEncodedLen, RuntimeDebug, Default)]
+    pub struct CandleRange {
+        /// 最旧的桶编号（含）
+        pub oldest: u32,
+        /// 最新的桶编号（含）
+        pub newest: u32,
+    }
+
     /// 价格保护配置
     #[derive(Encode, Decode, codec::DecodeWithMemTracking, Clone, PartialEq, Eq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
     pub struct PriceProtectionConfig<Balance> {
@@ -440,6 +525,9 @@ pub mod pallet {
         /// Shop 查询接口（Entity-Shop 分离架构）
         type ShopProvider: ShopProvider<Self::AccountId>;
 
+        /// 实体治理来源（`EntityOrigin::Governance`，成功返回 entity_id）
+        type EntityGovernanceOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = u64>;
+
         /// 实体代币接口
         type TokenProvider: EntityTokenProvider<Self::AccountId, Self::TokenBalance>;
 
@@ -508,15 +596,27 @@ pub mod pallet {
 
         /// 🆕 国库账户（没收的保证金归入国库）
         type TreasuryAccount: Get<Self::AccountId>;
+
+        /// 支付通道查询（由 `pallet-trading-p2p` 注册表提供）
+        type PaymentRails: PaymentRailProvider;
     }
 
+    /// v1: 订单与 USDT 交易新增 `rail_id`（见 `migrations::v1`）
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     // ==================== Hooks ====================
 
     #[pallet::hooks]
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        /// 空闲权重：清理超出保留数量的历史 K 线
+        fn on_idle(_n: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+            Self::prune_candles(remaining_weight)
+        }
+
         /// OCW: 自动验证待处理的 USDT 交易
         fn offchain_worker(block_number: BlockNumberFor<T>) {
             log::info!(target: "entity-market-ocw", 
@@ -683,6 +783,31 @@ pub mod pallet {
         PriceProtectionConfig<BalanceOf<T>>,
     >;
 
+    // ==================== K线存储 ====================
+
+    /// OHLCV K 线：(shop_id, 周期, 桶编号) → K 线
+    #[pallet::storage]
+    pub type Candles<T: Config> = StorageNMap<
+        _,
+        (
+            NMapKey<Blake2_128Concat, u64>,
+            NMapKey<Twox64Concat, CandleInterval>,
+            NMapKey<Twox64Concat, u32>,
+        ),
+        Candle<BalanceOf<T>, T::TokenBalance>,
+    >;
+
+    /// K 线桶范围：(shop_id, 周期) → [oldest, newest]
+    #[pallet::storage]
+    #[pallet::getter(fn candle_range)]
+    pub type CandleRanges<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, u64, Twox64Concat, CandleInterval, CandleRange>;
+
+    /// 超出保留数量、等待 on_idle 清理的 (shop_id, 周期)
+    #[pallet::storage]
+    pub type CandlePruneQueue<T: Config> =
+        StorageValue<_, BoundedVec<(u64, CandleInterval), ConstU32<MAX_CANDLE_PRUNE_QUEUE>>, ValueQuery>;
+
     // ==================== 事件 ====================
 
     #[pallet::event]
@@ -716,7 +841,7 @@ pub mod pallet {
             maker: T::AccountId,
             token_amount: T::TokenBalance,
             usdt_price: u64,
-            tron_address: TronAddress,
+            tron_address: PayoutAddress,
         },
         /// USDT 买单已创建
         UsdtBuyOrderCreated {
@@ -911,6 +1036,12 @@ pub mod pallet {
         InvalidFeeRate,
         /// 基点参数无效（超过 10000）
         InvalidBasisPoints,
+        /// 支付通道不存在
+        PaymentRailNotFound,
+        /// 支付通道已停用
+        PaymentRailDisabled,
+        /// 收款地址与支付通道所在链不匹配
+        InvalidPayoutAddress,
     }
 
     // ==================== Extrinsics ====================
@@ -961,6 +1092,7 @@ pub mod pallet {
                 price,
                 0,    // usdt_price (NXS 通道不使用)
                 None, // tron_address (NXS 通道不使用)
+                DEFAULT_RAIL_ID,
             )?;
 
             // 更新最优价格
@@ -1022,6 +1154,7 @@ pub mod pallet {
                 price,
                 0,    // usdt_price (NXS 通道不使用)
                 None, // tron_address (NXS 通道不使用)
+                DEFAULT_RAIL_ID,
             )?;
 
             // 更新最优价格
@@ -1192,7 +1325,7 @@ pub mod pallet {
 
             // 更新最优价格和 TWAP
             Self::update_best_prices(order.shop_id);
-            Self::on_trade_completed(order.shop_id, order.price);
+            Self::on_trade_completed(order.shop_id, order.price, fill_amount, total_nxst);
 
             Self::deposit_event(Event::OrderFilled {
                 order_id,
@@ -1275,12 +1408,11 @@ pub mod pallet {
             order_ttl: u32,
             usdt_timeout: u32,
         ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
+            let caller = ensure_admin_caller::<_, _, T::EntityGovernanceOrigin>(origin)?;
 
             // 验证店主
             ensure!(T::ShopProvider::shop_exists(shop_id), Error::<T>::ShopNotFound);
-            let owner = T::ShopProvider::shop_owner(shop_id).ok_or(Error::<T>::ShopNotFound)?;
-            ensure!(owner == who, Error::<T>::NotShopOwner);
+            Self::ensure_caller_owns_shop(shop_id, &caller)?;
 
             // H8: 手续费率上限验证（最高 50%）
             ensure!(fee_rate <= 5000, Error::<T>::InvalidFeeRate);
@@ -1322,11 +1454,10 @@ pub mod pallet {
             circuit_breaker_threshold: u16,
             min_trades_for_twap: u64,
         ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
+            let caller = ensure_admin_caller::<_, _, T::EntityGovernanceOrigin>(origin)?;
 
             // 验证店主
-            let owner = T::ShopProvider::shop_owner(shop_id).ok_or(Error::<T>::ShopNotFound)?;
-            ensure!(owner == who, Error::<T>::NotShopOwner);
+            Self::ensure_caller_owns_shop(shop_id, &caller)?;
 
             // M4: 参数验证（基点不超过 10000）
             ensure!(max_price_deviation <= 10000, Error::<T>::InvalidBasisPoints);
@@ -1361,11 +1492,10 @@ pub mod pallet {
             origin: OriginFor<T>,
             shop_id: u64,
         ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
+            let caller = ensure_admin_caller::<_, _, T::EntityGovernanceOrigin>(origin)?;
 
             // 验证店主
-            let owner = T::ShopProvider::shop_owner(shop_id).ok_or(Error::<T>::ShopNotFound)?;
-            ensure!(owner == who, Error::<T>::NotShopOwner);
+            Self::ensure_caller_owns_shop(shop_id, &caller)?;
 
             let current_block: u32 = <frame_system::Pallet<T>>::block_number().saturated_into();
 
@@ -1403,11 +1533,10 @@ pub mod pallet {
             shop_id: u64,
             initial_price: BalanceOf<T>,
         ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
+            let caller = ensure_admin_caller::<_, _, T::EntityGovernanceOrigin>(origin)?;
 
             // 验证店主
-            let owner = T::ShopProvider::shop_owner(shop_id).ok_or(Error::<T>::ShopNotFound)?;
-            ensure!(owner == who, Error::<T>::NotShopOwner);
+            Self::ensure_caller_owns_shop(shop_id, &caller)?;
 
             // 验证价格
             ensure!(!initial_price.is_zero(), Error::<T>::ZeroPrice);
@@ -1464,49 +1593,7 @@ pub mod pallet {
             tron_address: Vec<u8>,
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
-
-            // 验证 USDT 市场
-            Self::ensure_usdt_market_enabled(shop_id)?;
-
-            // 验证参数
-            ensure!(usdt_price > 0, Error::<T>::ZeroPrice);
-            ensure!(!token_amount.is_zero(), Error::<T>::AmountTooSmall);
-
-            // 验证 TRON 地址格式（Base58，以 T 开头，34 字符）
-            ensure!(tron_address.len() == 34, Error::<T>::InvalidTronAddress);
-            ensure!(tron_address.first() == Some(&b'T'), Error::<T>::InvalidTronAddress);
-            let tron_addr: TronAddress = tron_address.try_into().map_err(|_| Error::<T>::InvalidTronAddress)?;
-
-            // 检查用户 Token 余额
-            let balance = T::TokenProvider::token_balance(shop_id, &who);
-            ensure!(balance >= token_amount, Error::<T>::InsufficientTokenBalance);
-
-            // 锁定 Token
-            T::TokenProvider::reserve(shop_id, &who, token_amount)?;
-
-            // 创建订单
-            let order_id = Self::do_create_order(
-                shop_id,
-                who.clone(),
-                OrderSide::Sell,
-                OrderType::Limit,
-                PaymentChannel::USDT,
-                token_amount,
-                Zero::zero(), // NXS price (USDT 通道不使用)
-                usdt_price,
-                Some(tron_addr.clone()),
-            )?;
-
-            Self::deposit_event(Event::UsdtSellOrderCreated {
-                order_id,
-                shop_id,
-                maker: who,
-                token_amount,
-                usdt_price,
-                tron_address: tron_addr,
-            });
-
-            Ok(())
+            Self::do_place_usdt_sell_order(who, shop_id, token_amount, usdt_price, tron_address, DEFAULT_RAIL_ID)
         }
 
         /// 挂 USDT 买单（用 USDT 买 Token）
@@ -1524,37 +1611,40 @@ pub mod pallet {
             usdt_price: u64,
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
+            Self::do_place_usdt_buy_order(who, shop_id, token_amount, usdt_price, DEFAULT_RAIL_ID)
+        }
 
-            // 验证 USDT 市场
-            Self::ensure_usdt_market_enabled(shop_id)?;
-
-            // 验证参数
-            ensure!(usdt_price > 0, Error::<T>::ZeroPrice);
-            ensure!(!token_amount.is_zero(), Error::<T>::AmountTooSmall);
-
-            // USDT 买单不需要锁定链上资产（USDT 在链下）
-            // 创建订单
-            let order_id = Self::do_create_order(
-                shop_id,
-                who.clone(),
-                OrderSide::Buy,
-                OrderType::Limit,
-                PaymentChannel::USDT,
-                token_amount,
-                Zero::zero(), // NXS price (USDT 通道不使用)
-                usdt_price,
-                None, // 买单不需要 TRON 地址
-            )?;
-
-            Self::deposit_event(Event::UsdtBuyOrderCreated {
-                order_id,
-                shop_id,
-                maker: who,
-                token_amount,
-                usdt_price,
-            });
+        /// 挂指定支付通道的 USDT 卖单
+        ///
+        /// # 参数
+        /// - `tron_address`: 卖家收款地址（TRON Base58 或 EVM `0x` 地址，需与通道所在链一致）
+        /// - `rail_id`: 支付通道 ID（见 `pallet-trading-p2p` 通道注册表）
+        #[pallet::call_index(20)]
+        #[pallet::weight(Weight::from_parts(55_000_000, 6_000))]
+        pub fn place_usdt_sell_order_on_rail(
+            origin: OriginFor<T>,
+            shop_id: u64,
+            token_amount: T::TokenBalance,
+            usdt_price: u64,
+            tron_address: Vec<u8>,
+            rail_id: RailId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::do_place_usdt_sell_order(who, shop_id, token_amount, usdt_price, tron_address, rail_id)
+        }
 
-            Ok(())
+        /// 挂指定支付通道的 USDT 买单（买家按该通道付款）
+        #[pallet::call_index(21)]
+        #[pallet::weight(Weight::from_parts(45_000_000, 5_000))]
+        pub fn place_usdt_buy_order_on_rail(
+            origin: OriginFor<T>,
+            shop_id: u64,
+            token_amount: T::TokenBalance,
+            usdt_price: u64,
+            rail_id: RailId,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            Self::do_place_usdt_buy_order(who, shop_id, token_amount, usdt_price, rail_id)
         }
 
         /// 预锁定 USDT 卖单（买家发起）🆕
@@ -1623,6 +1713,7 @@ pub mod pallet {
                 fill_amount,
                 usdt_amount,
                 seller_tron_address,
+                order.rail_id,
                 buyer_deposit,
             )?;
 
@@ -1689,10 +1780,9 @@ pub mod pallet {
             );
             ensure!(buyer != who, Error::<T>::CannotTakeOwnOrder);
 
-            // 验证 TRON 地址
-            ensure!(tron_address.len() == 34, Error::<T>::InvalidTronAddress);
-            ensure!(tron_address.first() == Some(&b'T'), Error::<T>::InvalidTronAddress);
-            let tron_addr: TronAddress = tron_address.try_into().map_err(|_| Error::<T>::InvalidTronAddress)?;
+            // 按买单支付通道验证收款地址（通道停用不影响已挂出的买单）
+            let rail = T::PaymentRails::payment_rail(order.rail_id).ok_or(Error::<T>::PaymentRailNotFound)?;
+            let tron_addr = Self::validate_payout_address(&rail, tron_address)?;
 
             // 计算成交数量
             let available = order.token_amount.checked_sub(&order.filled_amount)
@@ -1730,6 +1820,7 @@ pub mod pallet {
                 fill_amount,
                 usdt_amount,
                 tron_addr,
+                order.rail_id,
                 buyer_deposit,
             )?;
 
@@ -2232,6 +2323,17 @@ pub mod pallet {
     // ==================== 内部函数 ====================
 
     impl<T: Config> Pallet<T> {
+        /// 校验管理类调用者：签名账户须为店主，治理来源须为店铺所属实体
+        fn ensure_caller_owns_shop(shop_id: u64, caller: &AdminCaller<T::AccountId>) -> DispatchResult {
+            let owner = T::ShopProvider::shop_owner(shop_id).ok_or(Error::<T>::ShopNotFound)?;
+            let allowed = match caller {
+                AdminCaller::Signed(who) => *who == owner,
+                AdminCaller::Governance(entity_id) => T::ShopProvider::shop_entity_id(shop_id) == Some(*entity_id),
+            };
+            ensure!(allowed, Error::<T>::NotShopOwner);
+            Ok(())
+        }
+
         /// 验证市场是否启用
         fn ensure_market_enabled(shop_id: u64) -> DispatchResult {
             ensure!(T::ShopProvider::shop_exists(shop_id), Error::<T>::ShopNotFound);
@@ -2256,6 +2358,126 @@ pub mod pallet {
             Ok(total.into())
         }
 
+        /// 挂 USDT 卖单内部实现
+        fn do_place_usdt_sell_order(
+            who: T::AccountId,
+            shop_id: u64,
+            token_amount: T::TokenBalance,
+            usdt_price: u64,
+            tron_address: Vec<u8>,
+            rail_id: RailId,
+        ) -> DispatchResult {
+            // 验证 USDT 市场
+            Self::ensure_usdt_market_enabled(shop_id)?;
+
+            // 验证参数
+            ensure!(usdt_price > 0, Error::<T>::ZeroPrice);
+            ensure!(!token_amount.is_zero(), Error::<T>::AmountTooSmall);
+
+            // 验证支付通道与收款地址
+            let rail = Self::enabled_payment_rail(rail_id)?;
+            let tron_addr = Self::validate_payout_address(&rail, tron_address)?;
+
+            // 检查用户 Token 余额
+            let balance = T::TokenProvider::token_balance(shop_id, &who);
+            ensure!(balance >= token_amount, Error::<T>::InsufficientTokenBalance);
+
+            // 锁定 Token
+            T::TokenProvider::reserve(shop_id, &who, token_amount)?;
+
+            // 创建订单
+            let order_id = Self::do_create_order(
+                shop_id,
+                who.clone(),
+                OrderSide::Sell,
+                OrderType::Limit,
+                PaymentChannel::USDT,
+                token_amount,
+                Zero::zero(), // NXS price (USDT 通道不使用)
+                usdt_price,
+                Some(tron_addr.clone()),
+                rail_id,
+            )?;
+
+            Self::deposit_event(Event::UsdtSellOrderCreated {
+                order_id,
+                shop_id,
+                maker: who,
+                token_amount,
+                usdt_price,
+                tron_address: tron_addr,
+            });
+
+            Ok(())
+        }
+
+        /// 挂 USDT 买单内部实现
+        fn do_place_usdt_buy_order(
+            who: T::AccountId,
+            shop_id: u64,
+            token_amount: T::TokenBalance,
+            usdt_price: u64,
+            rail_id: RailId,
+        ) -> DispatchResult {
+            // 验证 USDT 市场
+            Self::ensure_usdt_market_enabled(shop_id)?;
+
+            // 验证参数
+            ensure!(usdt_price > 0, Error::<T>::ZeroPrice);
+            ensure!(!token_amount.is_zero(), Error::<T>::AmountTooSmall);
+
+            // 验证支付通道（买家按该通道付款）
+            Self::enabled_payment_rail(rail_id)?;
+
+            // USDT 买单不需要锁定链上资产（USDT 在链下）
+            // 创建订单
+            let order_id = Self::do_create_order(
+                shop_id,
+                who.clone(),
+                OrderSide::Buy,
+                OrderType::Limit,
+                PaymentChannel::USDT,
+                token_amount,
+                Zero::zero(), // NXS price (USDT 通道不使用)
+                usdt_price,
+                None, // 买单不需要收款地址
+                rail_id,
+            )?;
+
+            Self::deposit_event(Event::UsdtBuyOrderCreated {
+                order_id,
+                shop_id,
+                maker: who,
+                token_amount,
+                usdt_price,
+            });
+
+            Ok(())
+        }
+
+        /// 查询可用于新订单的支付通道
+        fn enabled_payment_rail(rail_id: RailId) -> Result<PaymentRail, DispatchError> {
+            let rail = T::PaymentRails::payment_rail(rail_id).ok_or(Error::<T>::PaymentRailNotFound)?;
+            ensure!(rail.enabled, Error::<T>::PaymentRailDisabled);
+            Ok(rail)
+        }
+
+        /// 按支付通道校验收款地址
+        ///
+        /// TRON 通道要求 Base58（以 T 开头，34 字符）；EVM 通道要求 `0x` + 40 位十六进制。
+        fn validate_payout_address(
+            rail: &PaymentRail,
+            address: Vec<u8>,
+        ) -> Result<PayoutAddress, DispatchError> {
+            if rail.chain == PaymentChain::Tron {
+                ensure!(address.len() == 34, Error::<T>::InvalidTronAddress);
+                ensure!(address.first() == Some(&b'T'), Error::<T>::InvalidTronAddress);
+            } else {
+                ensure!(rail.chain.is_valid_address(&address), Error::<T>::InvalidPayoutAddress);
+            }
+            address.try_into().map_err(|_| Error::<T>::InvalidPayoutAddress.into())
+        }
+
         /// 创建订单（通用）
         fn do_create_order(
             shop_id: u64,
@@ -2266,7 +2488,8 @@ pub mod pallet {
             token_amount: T::TokenBalance,
             price: BalanceOf<T>,
             usdt_price: u64,
-            tron_address: Option<TronAddress>,
+            tron_address: Option<PayoutAddress>,
+            rail_id: RailId,
         ) -> Result<u64, DispatchError> {
             let order_id = NextOrderId::<T>::get();
             NextOrderId::<T>::put(order_id.saturating_add(1));
@@ -2292,6 +2515,7 @@ pub mod pallet {
                 price,
                 usdt_price,
                 tron_address,
+                rail_id,
                 status: OrderStatus::Open,
                 created_at: now,
                 expires_at,
@@ -2361,9 +2585,16 @@ pub mod pallet {
             log::info!(target: "entity-market-ocw", 
                 "Verifying trade {} with tx_hash len={}", trade_id, tx_hash.len());
 
-            // 调用 OCW 验证 TRC20 交易
-            let result = ocw::verify_trc20_transaction(
-                tx_hash,
+            let Some(rail) = T::PaymentRails::payment_rail(trade.rail_id) else {
+                log::error!(target: "entity-market-ocw",
+                    "Trade {} references unknown payment rail {}", trade_id, trade.rail_id);
+                return;
+            };
+
+            // 按支付通道验证链上转账
+            let result = ocw::verify_rail_payment(
+                &rail,
+                &ocw::normalize_tx_hash(tx_hash),
                 trade.seller_tron_address.as_slice(),
                 trade.usdt_amount,
             );
@@ -2628,7 +2859,8 @@ pub mod pallet {
             buyer: T::AccountId,
             token_amount: T::TokenBalance,
             usdt_amount: u64,
-            seller_tron_address: TronAddress,
+            seller_tron_address: PayoutAddress,
+            rail_id: RailId,
         ) -> Result<u64, DispatchError> {
             Self::do_create_usdt_trade_with_deposit(
                 order_id,
@@ -2638,6 +2870,7 @@ pub mod pallet {
                 token_amount,
                 usdt_amount,
                 seller_tron_address,
+                rail_id,
                 Zero::zero(), // 无保证金
             )
         }
@@ -2650,7 +2883,8 @@ pub mod pallet {
             buyer: T::AccountId,
             token_amount: T::TokenBalance,
             usdt_amount: u64,
-            seller_tron_address: TronAddress,
+            seller_tron_address: PayoutAddress,
+            rail_id: RailId,
             buyer_deposit: BalanceOf<T>,
         ) -> Result<u64, DispatchError> {
             let trade_id = NextUsdtTradeId::<T>::get();
@@ -2675,6 +2909,7 @@ pub mod pallet {
                 token_amount,
                 usdt_amount,
                 seller_tron_address,
+                rail_id,
                 tron_tx_hash: None,
                 status: UsdtTradeStatus::AwaitingPayment,
                 created_at: now,
@@ -2899,7 +3134,7 @@ pub mod pallet {
                 Self::update_best_prices(shop_id);
                 if !total_filled.is_zero() {
                     let avg_price = total_nxst.checked_div(&total_filled.into().into()).unwrap_or_else(Zero::zero);
-                    Self::on_trade_completed(shop_id, avg_price);
+                    Self::on_trade_completed(shop_id, avg_price, total_filled, total_nxst);
                 }
             }
 
@@ -3007,7 +3242,7 @@ pub mod pallet {
                 let total_gross = total_receive.saturating_add(total_fees);
                 if !total_gross.is_zero() {
                     let avg_price = total_gross.checked_div(&total_filled.into().into()).unwrap_or_else(Zero::zero);
-                    Self::on_trade_completed(shop_id, avg_price);
+                    Self::on_trade_completed(shop_id, avg_price, total_filled, total_gross);
                 }
             }
 
@@ -3339,11 +3574,22 @@ pub mod pallet {
             }
         }
 
-        /// 在成交后更新 TWAP 并检查熔断
-        fn on_trade_completed(shop_id: u64, trade_price: BalanceOf<T>) {
+        /// 在成交后更新 TWAP、K 线并检查熔断
+        ///
+        /// - `volume`: 本次成交的 Token 数量
+        /// - `turnover`: 本次成交的 NXS 金额（含手续费）
+        fn on_trade_completed(
+            shop_id: u64,
+            trade_price: BalanceOf<T>,
+            volume: T::TokenBalance,
+            turnover: BalanceOf<T>,
+        ) {
             // 更新 TWAP 累积器
             Self::update_twap_accumulator(shop_id, trade_price);
 
+            // 更新 1m/1h/1d K 线
+            Self::record_candles(shop_id, trade_price, volume, turnover);
+
             // 更新最新成交价
             Self::update_last_trade_price(shop_id, trade_price);
 
@@ -3362,6 +3608,132 @@ pub mod pallet {
             // 检查熔断
             Self::check_circuit_breaker(shop_id, trade_price);
         }
+
+        // ==================== K线维护 ====================
+
+        /// 周期对应的区块数（1 分钟 = BlocksPerHour / 60，至少 1）
+        pub fn candle_interval_blocks(interval: CandleInterval) -> u32 {
+            match interval {
+                CandleInterval::OneMinute => (T::BlocksPerHour::get() / 60).max(1),
+                CandleInterval::OneHour => T::BlocksPerHour::get().max(1),
+                CandleInterval::OneDay => T::BlocksPerDay::get().max(1),
+            }
+        }
+
+        /// 区块所在的 K 线桶编号
+        pub fn candle_bucket_at(interval: CandleInterval, block: u32) -> u32 {
+            block / Self::candle_interval_blocks(interval)
+        }
+
+        /// 将一笔成交计入三个周期的 K 线
+        fn record_candles(
+            shop_id: u64,
+            price: BalanceOf<T>,
+            volume: T::TokenBalance,
+            turnover: BalanceOf<T>,
+        ) {
+            let current_block: u32 = <frame_system::Pallet<T>>::block_number().saturated_into();
+
+            for interval in CandleInterval::ALL {
+                let bucket = Self::candle_bucket_at(interval, current_block);
+                let key = (shop_id, interval, bucket);
+
+                if let Some(mut candle) = Candles::<T>::get(key) {
+                    candle.high = candle.high.max(price);
+                    candle.low = candle.low.min(price);
+                    candle.close = price;
+                    candle.volume = candle.volume.saturating_add(volume);
+                    candle.turnover = candle.turnover.saturating_add(turnover);
+                    candle.trades = candle.trades.saturating_add(1);
+                    Candles::<T>::insert(key, candle);
+                    continue;
+                }
+
+                // 新开一根 K 线：链接到上一根，并更新桶范围
+                Candles::<T>::insert(key, Candle {
+                    bucket,
+                    open: price,
+                    high: price,
+                    low: price,
+                    close: price,
+                    volume,
+                    turnover,
+                    trades: 1,
+                    next_bucket: None,
+                });
+
+                let range = match CandleRanges::<T>::get(shop_id, interval) {
+                    Some(mut range) => {
+                        Candles::<T>::mutate((shop_id, interval, range.newest), |prev| {
+                            if let Some(prev) = prev {
+                                prev.next_bucket = Some(bucket);
+                            }
+                        });
+                        range.newest = bucket;
+                        range
+                    }
+                    None => CandleRange { oldest: bucket, newest: bucket },
+                };
+                CandleRanges::<T>::insert(shop_id, interval, range);
+
+                if range.newest.saturating_sub(range.oldest) >= interval.retention() {
+                    CandlePruneQueue::<T>::mutate(|queue| {
+                        if !queue.contains(&(shop_id, interval)) {
+                            // 队列已满时忽略，下次开新 K 线会再次入队
+                            let _ = queue.try_push((shop_id, interval));
+                        }
+                    });
+                }
+            }
+        }
+
+        /// 清理超出保留数量的 K 线（on_idle 调用，受剩余权重限制）
+        ///
+        /// 沿 `next_bucket` 链从最旧的 K 线开始删除，直到
+        /// `newest - oldest < retention` 或权重耗尽。
+        pub(crate) fn prune_candles(remaining_weight: Weight) -> Weight {
+            let db = T::DbWeight::get();
+            let per_candle = db.reads_writes(1, 1);
+            let mut used = db.reads_writes(1, 1);
+            if remaining_weight.any_lt(used.saturating_add(per_candle)) {
+                return Weight::zero();
+            }
+
+            let mut queue = CandlePruneQueue::<T>::get();
+            if queue.is_empty() {
+                return db.reads(1);
+            }
+
+            while let Some(&(shop_id, interval)) = queue.first() {
+                used = used.saturating_add(db.reads_writes(1, 1));
+                let Some(mut range) = CandleRanges::<T>::get(shop_id, interval) else {
+                    queue.remove(0);
+                    continue;
+                };
+
+                let retention = interval.retention();
+                let mut exhausted = false;
+                while range.newest.saturating_sub(range.oldest) >= retention {
+                    if remaining_weight.any_lt(used.saturating_add(per_candle)) {
+                        exhausted = true;
+                        break;
+                    }
+                    used = used.saturating_add(per_candle);
+                    let next = Candles::<T>::take((shop_id, interval, range.oldest))
+                        .and_then(|c| c.next_bucket);
+                    range.oldest = next.unwrap_or(range.newest);
+                }
+                CandleRanges::<T>::insert(shop_id, interval, range);
+
+                if exhausted {
+                    break;
+                }
+                queue.remove(0);
+            }
+
+            CandlePruneQueue::<T>::put(queue);
+            used
+        }
     }
 }
 
@@ -3547,18 +3919,72 @@ impl<T: Config> Pallet<T> {
                 acc.saturating_add(o.token_amount.saturating_sub(o.filled_amount))
             });
 
+        let (high_24h, low_24h, volume_24h) = Self::get_24h_stats(shop_id)
+            .unwrap_or((Zero::zero(), Zero::zero(), Zero::zero()));
+
         MarketSummary {
             best_ask,
             best_bid,
-            high_24h: Zero::zero(), // TODO: 需要历史数据
-            low_24h: Zero::zero(),  // TODO: 需要历史数据
-            volume_24h: Zero::zero(), // TODO: 需要历史数据
+            high_24h,
+            low_24h,
+            volume_24h,
             last_price,
             total_ask_amount,
             total_bid_amount,
         }
     }
 
+    // ==================== K线查询接口 ====================
+
+    /// 获取最近 `limit` 个周期的 K 线（按时间升序，无成交的周期不返回）
+    ///
+    /// # 参数
+    /// - `shop_id`: 店铺 ID
+    /// - `interval`: K 线周期
+    /// - `limit`: 向前回溯的周期数（不超过该周期的保留数量）
+    pub fn get_candles(
+        shop_id: u64,
+        interval: CandleInterval,
+        limit: u32,
+    ) -> Vec<Candle<BalanceOf<T>, T::TokenBalance>> {
+        use sp_runtime::SaturatedConversion;
+
+        let Some(range) = CandleRanges::<T>::get(shop_id, interval) else {
+            return Vec::new();
+        };
+        let current_block: u32 = <frame_system::Pallet<T>>::block_number().saturated_into();
+        let current_bucket = Self::candle_bucket_at(interval, current_block);
+        let limit = limit.min(interval.retention());
+        if limit == 0 {
+            return Vec::new();
+        }
+
+        let first = current_bucket.saturating_sub(limit - 1).max(range.oldest);
+        let last = current_bucket.min(range.newest);
+        if first > last {
+            return Vec::new();
+        }
+        (first..=last)
+            .filter_map(|bucket| Candles::<T>::get((shop_id, interval, bucket)))
+            .collect()
+    }
+
+    /// 滚动 24 小时统计：(最高价, 最低价, 成交量)
+    ///
+    /// 基于最近 24 根 1 小时 K 线（含当前未收盘的一根）；无成交返回 None。
+    pub fn get_24h_stats(shop_id: u64) -> Option<(BalanceOf<T>, BalanceOf<T>, T::TokenBalance)> {
+        use sp_runtime::traits::{Saturating, Zero};
+
+        let candles = Self::get_candles(shop_id, CandleInterval::OneHour, 24);
+        let first = candles.first()?;
+        Some(candles.iter().fold(
+            (first.high, first.low, Zero::zero()),
+            |(high, low, volume): (BalanceOf<T>, BalanceOf<T>, T::TokenBalance), c| {
+                (high.max(c.high), low.min(c.low), volume.saturating_add(c.volume))
+            },
+        ))
+    }
+
     /// 获取订单簿快照（简化版）
     pub fn get_order_book_snapshot(shop_id: u64) -> (Vec<(BalanceOf<T>, T::TokenBalance)>, Vec<(BalanceOf<T>, T::TokenBalance)>) {
         let depth = Self::get_order_book_depth(shop_id, 20);
@@ -0,0 +1,172 @@
+//! Entity Market 存储迁移
+
+use super::*;
+use frame_support::{
+    migrations::VersionedMigration,
+    pallet_prelude::*,
+    traits::UncheckedOnRuntimeUpgrade,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use pallet_trading_common::{PayoutAddress, DEFAULT_RAIL_ID};
+use sp_std::marker::PhantomData;
+
+/// v0 → v1：订单与 USDT 交易新增 `rail_id`
+///
+/// v0 的 `TradeOrder` / `UsdtTrade` 没有 `rail_id` 字段，收款地址为 `TronAddress`
+///（与 `PayoutAddress` 编码相同，上限更小）。v0 的 USDT 通道只支持 USDT-TRC20，
+/// NXS 通道订单本就使用 `DEFAULT_RAIL_ID`，迁移为所有存量记录补上 `DEFAULT_RAIL_ID`。
+pub mod v1 {
+    use super::*;
+
+    pub(crate) mod v0 {
+        use super::*;
+
+        /// v0 交易订单（无 `rail_id`）
+        #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+        pub struct OldTradeOrder<AccountId, TokenBalance, Balance, BlockNumber> {
+            pub order_id: u64,
+            pub shop_id: u64,
+            pub maker: AccountId,
+            pub side: OrderSide,
+            pub order_type: OrderType,
+            pub channel: PaymentChannel,
+            pub token_amount: TokenBalance,
+            pub filled_amount: TokenBalance,
+            pub price: Balance,
+            pub usdt_price: u64,
+            pub tron_address: Option<PayoutAddress>,
+            pub status: OrderStatus,
+            pub created_at: BlockNumber,
+            pub expires_at: BlockNumber,
+        }
+
+        /// v0 USDT 交易记录（无 `rail_id`）
+        #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+        pub struct OldUsdtTrade<AccountId, TokenBalance, Balance, BlockNumber> {
+            pub trade_id: u64,
+            pub order_id: u64,
+            pub shop_id: u64,
+            pub seller: AccountId,
+            pub buyer: AccountId,
+            pub token_amount: TokenBalance,
+            pub usdt_amount: u64,
+            pub seller_tron_address: PayoutAddress,
+            pub tron_tx_hash: Option<TronTxHash>,
+            pub status: UsdtTradeStatus,
+            pub created_at: BlockNumber,
+            pub timeout_at: BlockNumber,
+            pub buyer_deposit: Balance,
+            pub deposit_status: BuyerDepositStatus,
+        }
+    }
+
+    type OldTradeOrderOf<T> = v0::OldTradeOrder<
+        <T as frame_system::Config>::AccountId,
+        <T as Config>::TokenBalance,
+        BalanceOf<T>,
+        BlockNumberFor<T>,
+    >;
+
+    type OldUsdtTradeOf<T> = v0::OldUsdtTrade<
+        <T as frame_system::Config>::AccountId,
+        <T as Config>::TokenBalance,
+        BalanceOf<T>,
+        BlockNumberFor<T>,
+    >;
+
+    /// 未做版本检查的迁移逻辑，通过 [`MigrateV0ToV1`] 使用
+    pub struct InnerMigrateV0ToV1<T>(PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV0ToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut orders = 0u64;
+            Orders::<T>::translate::<OldTradeOrderOf<T>, _>(|_, old| {
+                orders += 1;
+                Some(TradeOrder {
+                    order_id: old.order_id,
+                    shop_id: old.shop_id,
+                    maker: old.maker,
+                    side: old.side,
+                    order_type: old.order_type,
+                    channel: old.channel,
+                    token_amount: old.token_amount,
+                    filled_amount: old.filled_amount,
+                    price: old.price,
+                    usdt_price: old.usdt_price,
+                    tron_address: old.tron_address,
+                    rail_id: DEFAULT_RAIL_ID,
+                    status: old.status,
+                    created_at: old.created_at,
+                    expires_at: old.expires_at,
+                })
+            });
+
+            let mut trades = 0u64;
+            UsdtTrades::<T>::translate::<OldUsdtTradeOf<T>, _>(|_, old| {
+                trades += 1;
+                Some(UsdtTrade {
+                    trade_id: old.trade_id,
+                    order_id: old.order_id,
+                    shop_id: old.shop_id,
+                    seller: old.seller,
+                    buyer: old.buyer,
+                    token_amount: old.token_amount,
+                    usdt_amount: old.usdt_amount,
+                    seller_tron_address: old.seller_tron_address,
+                    rail_id: DEFAULT_RAIL_ID,
+                    tron_tx_hash: old.tron_tx_hash,
+                    status: old.status,
+                    created_at: old.created_at,
+                    timeout_at: old.timeout_at,
+                    buyer_deposit: old.buyer_deposit,
+                    deposit_status: old.deposit_status,
+                })
+            });
+
+            log::info!(
+                target: "runtime::entity-market",
+                "migrated {} orders and {} usdt trades to v1 (rail_id = {})",
+                orders,
+                trades,
+                DEFAULT_RAIL_ID,
+            );
+            let total = orders.saturating_add(trades);
+            T::DbWeight::get().reads_writes(total, total)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<alloc::vec::Vec<u8>, sp_runtime::TryRuntimeError> {
+            let orders = Orders::<T>::iter_keys().count() as u32;
+            let trades = UsdtTrades::<T>::iter_keys().count() as u32;
+            Ok((orders, trades).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: alloc::vec::Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+            let (orders, trades) = <(u32, u32)>::decode(&mut &state[..])
+                .map_err(|_| sp_runtime::TryRuntimeError::Other("invalid pre_upgrade state"))?;
+            let mut migrated_orders = 0u32;
+            for (_, order) in Orders::<T>::iter() {
+                ensure!(order.rail_id == DEFAULT_RAIL_ID, "order rail_id not migrated");
+                migrated_orders += 1;
+            }
+            let mut migrated_trades = 0u32;
+            for (_, trade) in UsdtTrades::<T>::iter() {
+                ensure!(trade.rail_id == DEFAULT_RAIL_ID, "usdt trade rail_id not migrated");
+                migrated_trades += 1;
+            }
+            ensure!(orders == migrated_orders, "orders lost during migration");
+            ensure!(trades == migrated_trades, "usdt trades lost during migration");
+            Ok(())
+        }
+    }
+
+    /// 带版本检查的 v0 → v1 迁移（仅在链上版本为 0 时执行，完成后写入版本 1）
+    pub type MigrateV0ToV1<T> = VersionedMigration<
+        0,
+        1,
+        InnerMigrateV0ToV1<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
@@ -160,6 +160,30 @@ impl ShopProvider<u64> for MockShopProvider {
     }
 }
 
+// ==================== Mock PaymentRailProvider ====================
+
+/// USDT-BEP20 通道 ID
+pub const BSC_RAIL_ID: u32 = 2;
+
+/// 内置 USDT-TRC20 + USDT-BEP20 通道
+pub struct MockPaymentRails;
+impl pallet_trading_common::PaymentRailProvider for MockPaymentRails {
+    fn payment_rail(rail_id: u32) -> Option<pallet_trading_common::PaymentRail> {
+        match rail_id {
+            BSC_RAIL_ID => Some(pallet_trading_common::PaymentRail {
+                chain: pallet_trading_common::PaymentChain::Bsc,
+                token_contract: frame_support::BoundedVec::truncate_from(
+                    pallet_trading_trc20_verifier::USDT_BEP20_CONTRACT.as_bytes().to_vec(),
+                ),
+                decimals: 18,
+                min_confirmations: 15,
+                enabled: true,
+            }),
+            _ => <() as pallet_trading_common::PaymentRailProvider>::payment_rail(rail_id),
+        }
+    }
+}
+
 // ==================== Mock EntityTokenProvider ====================
 
 use std::cell::RefCell;
@@ -303,6 +327,24 @@ impl pallet_entity_market::Config for Test {
     type DepositForfeitRate = DepositForfeitRate;
     type UsdtToNxsRate = UsdtToNxsRate;
     type TreasuryAccount = TreasuryAccountId;
+    type PaymentRails = MockPaymentRails;
+    type EntityGovernanceOrigin = MockEntityGovernance;
+}
+
+/// 模拟实体治理来源：账户 GOV_ENTITY_1 / GOV_ENTITY_2 签名分别视为实体 1 / 2 的治理来源
+pub const GOV_ENTITY_1: u64 = 900;
+pub const GOV_ENTITY_2: u64 = 901;
+
+pub struct MockEntityGovernance;
+impl frame_support::traits::EnsureOrigin<RuntimeOrigin> for MockEntityGovernance {
+    type Success = u64;
+    fn try_origin(o: RuntimeOrigin) -> Result<u64, RuntimeOrigin> {
+        match o.clone().into() {
+            Ok(frame_system::RawOrigin::Signed(GOV_ENTITY_1)) => Ok(1),
+            Ok(frame_system::RawOrigin::Signed(GOV_ENTITY_2)) => Ok(2),
+            _ => Err(o),
+        }
+    }
 }
 
 // ==================== 测试构建器 ====================
@@ -344,6 +386,18 @@ impl ExtBuilder {
         });
         ext
     }
+
+    /// 带 offchain 扩展的测试环境（OCW 测试使用）
+    pub fn build_with_offchain(
+        offchain: sp_core::offchain::testing::TestOffchainExt,
+    ) -> sp_io::TestExternalities {
+        use sp_core::offchain::{OffchainDbExt, OffchainWorkerExt};
+
+        let mut ext = Self::build();
+        ext.register_extension(OffchainDbExt::new(offchain.clone()));
+        ext.register_extension(OffchainWorkerExt::new(offchain));
+        ext
+    }
 }
 
 /// 配置市场（启用 NXS + USDT）
@@ -0,0 +1,46 @@
+//! Runtime API 定义：用于前端 / 看板查询店铺代币市场行情
+//!
+//! 提供以下接口：
+//! - `candles`: 获取 OHLCV K 线（1m/1h/1d）
+//! - `order_book_depth`: 获取按价格档位聚合的订单簿深度
+//! - `market_summary`: 获取市场摘要（含 24h 最高/最低/成交量）
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+use crate::{Candle, CandleInterval, MarketSummary, OrderBookDepth};
+
+sp_api::decl_runtime_apis! {
+    /// 店铺代币市场 Runtime API
+    ///
+    /// 用于前端 K 线图、深度图和市场概览
+    pub trait EntityMarketApi<Balance, TokenBalance>
+    where
+        Balance: Codec,
+        TokenBalance: Codec,
+    {
+        /// 获取 K 线
+        ///
+        /// ### 参数
+        /// - `shop_id`: 店铺 ID
+        /// - `interval`: K 线周期
+        /// - `limit`: 向前回溯的周期数
+        ///
+        /// ### 返回
+        /// - 按时间升序的 K 线，无成交的周期不返回
+        fn candles(shop_id: u64, interval: CandleInterval, limit: u32) -> Vec<Candle<Balance, TokenBalance>>;
+
+        /// 获取订单簿深度
+        ///
+        /// ### 参数
+        /// - `shop_id`: 店铺 ID
+        /// - `depth`: 每边返回的价格档位数
+        fn order_book_depth(shop_id: u64, depth: u32) -> OrderBookDepth<Balance, TokenBalance>;
+
+        /// 获取市场摘要
+        ///
+        /// ### 参数
+        /// - `shop_id`: 店铺 ID
+        fn market_summary(shop_id: u64) -> MarketSummary<Balance, TokenBalance>;
+    }
+}
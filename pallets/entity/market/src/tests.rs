@@ -287,6 +287,28 @@ fn configure_market_fails_not_owner() {
     });
 }
 
+#[test]
+fn configure_market_by_entity_governance_origin() {
+    ExtBuilder::build().execute_with(|| {
+        // 其他实体的治理来源无权配置
+        assert_noop!(
+            EntityMarket::configure_market(
+                RuntimeOrigin::signed(GOV_ENTITY_2), SHOP_ID,
+                true, true, 100, 1, 1000, 300,
+            ),
+            Error::<Test>::NotShopOwner
+        );
+
+        assert_ok!(EntityMarket::configure_market(
+            RuntimeOrigin::signed(GOV_ENTITY_1), SHOP_ID,
+            true, false, 150, 1, 1000, 300,
+        ));
+        let config = MarketConfigs::<Test>::get(SHOP_ID).expect("config exists");
+        assert!(!config.usdt_enabled);
+        assert_eq!(config.fee_rate, 150);
+    });
+}
+
 #[test]
 fn configure_market_fails_invalid_fee_rate() {
     ExtBuilder::build().execute_with(|| {
@@ -416,6 +438,60 @@ fn place_usdt_buy_order_works() {
     });
 }
 
+const EVM_PAYOUT_ADDRESS: &[u8] = b"0x1111111111111111111111111111111111111111";
+
+#[test]
+fn place_usdt_sell_order_on_rail_validates_address_per_chain() {
+    ExtBuilder::build().execute_with(|| {
+        configure_market_enabled(SHOP_ID);
+        let tron_addr = b"T9yD14Nj9j7xAB4dbGeiX9h8unkKHxuWwb".to_vec();
+
+        assert_noop!(
+            EntityMarket::place_usdt_sell_order_on_rail(
+                RuntimeOrigin::signed(ALICE), SHOP_ID, 1000, 1_000_000, EVM_PAYOUT_ADDRESS.to_vec(), 9,
+            ),
+            Error::<Test>::PaymentRailNotFound
+        );
+        // BEP20 通道拒绝 TRON 地址
+        assert_noop!(
+            EntityMarket::place_usdt_sell_order_on_rail(
+                RuntimeOrigin::signed(ALICE), SHOP_ID, 1000, 1_000_000, tron_addr, BSC_RAIL_ID,
+            ),
+            Error::<Test>::InvalidPayoutAddress
+        );
+        assert_ok!(EntityMarket::place_usdt_sell_order_on_rail(
+            RuntimeOrigin::signed(ALICE), SHOP_ID, 1000, 1_000_000, EVM_PAYOUT_ADDRESS.to_vec(), BSC_RAIL_ID,
+        ));
+        let order = Orders::<Test>::get(0).expect("order exists");
+        assert_eq!(order.rail_id, BSC_RAIL_ID);
+        assert_eq!(order.tron_address.map(|a| a.to_vec()), Some(EVM_PAYOUT_ADDRESS.to_vec()));
+
+        // 成交记录继承订单通道
+        assert_ok!(EntityMarket::reserve_usdt_sell_order(RuntimeOrigin::signed(BOB), 0, None));
+        assert_eq!(UsdtTrades::<Test>::get(0).expect("trade").rail_id, BSC_RAIL_ID);
+    });
+}
+
+#[test]
+fn accept_usdt_buy_order_uses_buy_order_rail() {
+    ExtBuilder::build().execute_with(|| {
+        configure_market_enabled(SHOP_ID);
+        assert_ok!(EntityMarket::place_usdt_buy_order_on_rail(
+            RuntimeOrigin::signed(ALICE), SHOP_ID, 1000, 1_000_000, BSC_RAIL_ID,
+        ));
+        assert_noop!(
+            EntityMarket::accept_usdt_buy_order(
+                RuntimeOrigin::signed(BOB), 0, None, b"T9yD14Nj9j7xAB4dbGeiX9h8unkKHxuWwb".to_vec(),
+            ),
+            Error::<Test>::InvalidPayoutAddress
+        );
+        assert_ok!(EntityMarket::accept_usdt_buy_order(
+            RuntimeOrigin::signed(BOB), 0, None, EVM_PAYOUT_ADDRESS.to_vec(),
+        ));
+        assert_eq!(UsdtTrades::<Test>::get(0).expect("trade").rail_id, BSC_RAIL_ID);
+    });
+}
+
 // ==================== 市价单 ====================
 
 #[test]
@@ -547,6 +623,121 @@ fn trade_updates_stats_and_twap() {
     });
 }
 
+// ==================== K线（OHLCV）与 24h 统计 ====================
+
+#[test]
+fn fills_record_ohlcv_candles() {
+    ExtBuilder::build().execute_with(|| {
+        configure_market_enabled(SHOP_ID);
+
+        // 区块 1：吃单 1000 @ 100
+        assert_ok!(EntityMarket::place_sell_order(RuntimeOrigin::signed(ALICE), SHOP_ID, 1000, 100));
+        assert_ok!(EntityMarket::take_order(RuntimeOrigin::signed(BOB), 0, None));
+
+        // 区块 5（同一分钟桶）：市价买 500 @ 110
+        System::set_block_number(5);
+        assert_ok!(EntityMarket::place_sell_order(RuntimeOrigin::signed(ALICE), SHOP_ID, 500, 110));
+        assert_ok!(EntityMarket::market_buy(RuntimeOrigin::signed(BOB), SHOP_ID, 500, 100_000));
+
+        // 区块 25（下一分钟桶，1 分钟 = 600 / 60 = 10 区块）：市价卖 200 @ 90
+        System::set_block_number(25);
+        assert_ok!(EntityMarket::place_buy_order(RuntimeOrigin::signed(ALICE), SHOP_ID, 200, 90));
+        assert_ok!(EntityMarket::market_sell(RuntimeOrigin::signed(BOB), SHOP_ID, 200, 1));
+
+        let minutes = EntityMarket::get_candles(SHOP_ID, CandleInterval::OneMinute, 10);
+        assert_eq!(minutes.len(), 2);
+        assert_eq!(minutes[0].bucket, 0);
+        assert_eq!((minutes[0].open, minutes[0].high, minutes[0].low, minutes[0].close), (100, 110, 100, 110));
+        assert_eq!(minutes[0].volume, 1500);
+        assert_eq!(minutes[0].turnover, 100_000 + 55_000);
+        assert_eq!(minutes[0].trades, 2);
+        assert_eq!(minutes[0].next_bucket, Some(2));
+        assert_eq!(minutes[1].bucket, 2);
+        assert_eq!((minutes[1].open, minutes[1].close, minutes[1].volume), (90, 90, 200));
+
+        // 1h / 1d 周期合并为一根
+        let hours = EntityMarket::get_candles(SHOP_ID, CandleInterval::OneHour, 24);
+        assert_eq!(hours.len(), 1);
+        assert_eq!((hours[0].open, hours[0].high, hours[0].low, hours[0].close), (100, 110, 90, 90));
+        assert_eq!(hours[0].trades, 3);
+        assert_eq!(EntityMarket::get_candles(SHOP_ID, CandleInterval::OneDay, 1).len(), 1);
+        assert_eq!(
+            CandleRanges::<Test>::get(SHOP_ID, CandleInterval::OneMinute),
+            Some(CandleRange { oldest: 0, newest: 2 })
+        );
+    });
+}
+
+#[test]
+fn market_summary_reports_rolling_24h_stats() {
+    ExtBuilder::build().execute_with(|| {
+        configure_market_enabled(SHOP_ID);
+
+        // 无成交时为 0
+        let summary = EntityMarket::get_market_summary(SHOP_ID);
+        assert_eq!((summary.high_24h, summary.low_24h, summary.volume_24h), (0, 0, 0));
+
+        assert_ok!(EntityMarket::place_sell_order(RuntimeOrigin::signed(ALICE), SHOP_ID, 1000, 100));
+        assert_ok!(EntityMarket::take_order(RuntimeOrigin::signed(BOB), 0, Some(400)));
+
+        // 2 小时后再成交
+        System::set_block_number(1 + 2 * 600);
+        assert_ok!(EntityMarket::place_sell_order(RuntimeOrigin::signed(ALICE), SHOP_ID, 300, 120));
+        assert_ok!(EntityMarket::take_order(RuntimeOrigin::signed(BOB), 1, None));
+
+        let summary = EntityMarket::get_market_summary(SHOP_ID);
+        assert_eq!(summary.high_24h, 120);
+        assert_eq!(summary.low_24h, 100);
+        assert_eq!(summary.volume_24h, 400 + 300);
+
+        // 25 小时后第一笔成交滑出窗口
+        System::set_block_number(1 + 25 * 600);
+        let summary = EntityMarket::get_market_summary(SHOP_ID);
+        assert_eq!(summary.volume_24h, 300);
+        assert_eq!(summary.low_24h, 120);
+    });
+}
+
+#[test]
+fn on_idle_prunes_candles_beyond_retention() {
+    use frame_support::traits::Hooks;
+    use frame_support::weights::Weight;
+
+    ExtBuilder::build().execute_with(|| {
+        configure_market_enabled(SHOP_ID);
+
+        // 每 500 个分钟桶成交一次，共 4 根（跨度 1500 > 保留 1440）
+        let mut sell_id = 0u64;
+        for i in 0..4u64 {
+            System::set_block_number(1 + i * 500 * 10);
+            assert_ok!(EntityMarket::place_sell_order(RuntimeOrigin::signed(ALICE), SHOP_ID, 10, 100));
+            assert_ok!(EntityMarket::take_order(RuntimeOrigin::signed(BOB), sell_id, None));
+            sell_id += 1;
+        }
+        assert_eq!(
+            CandleRanges::<Test>::get(SHOP_ID, CandleInterval::OneMinute),
+            Some(CandleRange { oldest: 0, newest: 1500 })
+        );
+        assert_eq!(
+            CandlePruneQueue::<Test>::get().to_vec(),
+            vec![(SHOP_ID, CandleInterval::OneMinute)]
+        );
+
+        EntityMarket::on_idle(System::block_number(), Weight::MAX);
+
+        // 桶 0 被清理，沿链前进到桶 500
+        assert!(Candles::<Test>::get((SHOP_ID, CandleInterval::OneMinute, 0)).is_none());
+        assert!(Candles::<Test>::get((SHOP_ID, CandleInterval::OneMinute, 500)).is_some());
+        assert_eq!(
+            CandleRanges::<Test>::get(SHOP_ID, CandleInterval::OneMinute),
+            Some(CandleRange { oldest: 500, newest: 1500 })
+        );
+        assert!(CandlePruneQueue::<Test>::get().is_empty());
+        // 小时 K 线未超出保留数量，不受影响
+        assert!(Candles::<Test>::get((SHOP_ID, CandleInterval::OneHour, 0)).is_some());
+    });
+}
+
 // ==================== H7: filled_amount 回滚 ====================
 
 #[test]
@@ -615,3 +806,151 @@ fn submit_ocw_result_works() {
         assert!(OcwVerificationResults::<Test>::get(0).is_some());
     });
 }
+
+// ==================== OCW 端到端验证（录制响应回放） ====================
+
+#[test]
+fn offchain_worker_verifies_usdt_trade_with_replayed_response() {
+    use frame_support::traits::Hooks;
+    use pallet_trading_trc20_verifier::{replay, TronSourceConfig, TRONGRID_MAINNET};
+    use sp_core::offchain::testing::TestOffchainExt;
+
+    let (offchain, offchain_state) = TestOffchainExt::new();
+    ExtBuilder::build_with_offchain(offchain).execute_with(|| {
+        configure_market_enabled(SHOP_ID);
+        let tron_addr = b"T9yD14Nj9j7xAB4dbGeiX9h8unkKHxuWwb".to_vec();
+        assert_ok!(EntityMarket::place_usdt_sell_order(
+            RuntimeOrigin::signed(ALICE), SHOP_ID, 1000, 1_000_000, tron_addr.clone(),
+        ));
+        assert_ok!(EntityMarket::reserve_usdt_sell_order(
+            RuntimeOrigin::signed(BOB), 0, None
+        ));
+        let tx_hash = b"a1b2c3d4e5f6a1b2c3d4e5f6a1b2c3d4e5f6a1b2c3d4e5f6a1b2c3d4e5f6a1b2".to_vec();
+        assert_ok!(EntityMarket::confirm_usdt_payment(
+            RuntimeOrigin::signed(BOB), 0, tx_hash.clone(),
+        ));
+        let usdt_amount = UsdtTrades::<Test>::get(0).expect("trade").usdt_amount;
+
+        // 链上保存的是十六进制文本，请求中应使用解码后的原始哈希
+        let source = TronSourceConfig::trongrid(TRONGRID_MAINNET);
+        replay::use_sources(vec![source.clone()]);
+        replay::expect_lookup(
+            &mut offchain_state.write(),
+            &source,
+            &crate::ocw::normalize_tx_hash(&tx_hash),
+            &replay::trongrid_transfer(&tron_addr, usdt_amount),
+        );
+
+        EntityMarket::offchain_worker(1);
+
+        assert_eq!(EntityMarket::get_ocw_result(0), Some((true, usdt_amount)));
+    });
+}
+
+#[test]
+fn offchain_worker_verifies_bep20_trade_through_evm_receipt() {
+    use frame_support::traits::Hooks;
+    use pallet_trading_trc20_verifier::{replay, set_evm_endpoints, USDT_BEP20_CONTRACT};
+    use sp_core::offchain::testing::TestOffchainExt;
+
+    let (offchain, offchain_state) = TestOffchainExt::new();
+    ExtBuilder::build_with_offchain(offchain).execute_with(|| {
+        configure_market_enabled(SHOP_ID);
+        assert_ok!(EntityMarket::place_usdt_sell_order_on_rail(
+            RuntimeOrigin::signed(ALICE), SHOP_ID, 1000, 1_000_000, EVM_PAYOUT_ADDRESS.to_vec(), BSC_RAIL_ID,
+        ));
+        assert_ok!(EntityMarket::reserve_usdt_sell_order(
+            RuntimeOrigin::signed(BOB), 0, None
+        ));
+        let tx_hash = b"a1b2c3d4e5f6a1b2c3d4e5f6a1b2c3d4e5f6a1b2c3d4e5f6a1b2c3d4e5f6a1b2".to_vec();
+        assert_ok!(EntityMarket::confirm_usdt_payment(
+            RuntimeOrigin::signed(BOB), 0, tx_hash.clone(),
+        ));
+        let usdt_amount = UsdtTrades::<Test>::get(0).expect("trade").usdt_amount;
+
+        // BEP20 为 18 位精度，链上原始金额 = 订单金额 × 10^12
+        let endpoint = "http://127.0.0.1:8545";
+        set_evm_endpoints(56, vec![endpoint.into()]);
+        replay::expect_evm_lookup(
+            &mut offchain_state.write(),
+            endpoint,
+            &crate::ocw::normalize_tx_hash(&tx_hash),
+            &replay::evm_receipt(
+                USDT_BEP20_CONTRACT.as_bytes(),
+                EVM_PAYOUT_ADDRESS,
+                usdt_amount as u128 * 10u128.pow(12),
+                1_000,
+            ),
+            Some(1_020),
+        );
+
+        EntityMarket::offchain_worker(1);
+
+        assert_eq!(EntityMarket::get_ocw_result(0), Some((true, usdt_amount)));
+    });
+}
+
+// ==================== 存储迁移 ====================
+
+#[test]
+fn migration_v1_adds_default_rail_to_orders_and_trades() {
+    use crate::migrations::{self, v1::v0::{OldTradeOrder, OldUsdtTrade}};
+    use codec::Encode;
+    use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+    use pallet_trading_common::DEFAULT_RAIL_ID;
+
+    ExtBuilder::build().execute_with(|| {
+        StorageVersion::new(0).put::<EntityMarket>();
+
+        let address: pallet_trading_common::PayoutAddress =
+            b"TA4Y62o6YC2Zsck9rZVGTvqW1AQ7X9zTnj".to_vec().try_into().unwrap();
+        let old_order = OldTradeOrder::<u64, u128, u128, u64> {
+            order_id: 3,
+            shop_id: SHOP_ID,
+            maker: ALICE,
+            side: OrderSide::Sell,
+            order_type: OrderType::Limit,
+            channel: PaymentChannel::USDT,
+            token_amount: 1_000,
+            filled_amount: 400,
+            price: 0,
+            usdt_price: 2_000_000,
+            tron_address: Some(address.clone()),
+            status: OrderStatus::PartiallyFilled,
+            created_at: 1,
+            expires_at: 1_000,
+        };
+        let old_trade = OldUsdtTrade::<u64, u128, u128, u64> {
+            trade_id: 5,
+            order_id: 3,
+            shop_id: SHOP_ID,
+            seller: ALICE,
+            buyer: BOB,
+            token_amount: 400,
+            usdt_amount: 800_000_000,
+            seller_tron_address: address.clone(),
+            tron_tx_hash: None,
+            status: UsdtTradeStatus::AwaitingPayment,
+            created_at: 2,
+            timeout_at: 200,
+            buyer_deposit: 10,
+            deposit_status: BuyerDepositStatus::Locked,
+        };
+        frame_support::storage::unhashed::put_raw(&Orders::<Test>::hashed_key_for(3), &old_order.encode());
+        frame_support::storage::unhashed::put_raw(&UsdtTrades::<Test>::hashed_key_for(5), &old_trade.encode());
+
+        migrations::v1::MigrateV0ToV1::<Test>::on_runtime_upgrade();
+
+        assert_eq!(EntityMarket::on_chain_storage_version(), StorageVersion::new(1));
+        let order = Orders::<Test>::get(3).expect("order migrated");
+        assert_eq!(order.rail_id, DEFAULT_RAIL_ID);
+        assert_eq!(order.tron_address, Some(address.clone()));
+        assert_eq!(order.filled_amount, 400);
+        assert_eq!(order.status, OrderStatus::PartiallyFilled);
+        let trade = UsdtTrades::<Test>::get(5).expect("trade migrated");
+        assert_eq!(trade.rail_id, DEFAULT_RAIL_ID);
+        assert_eq!(trade.seller_tron_address, address);
+        assert_eq!(trade.deposit_status, BuyerDepositStatus::Locked);
+        assert_eq!(trade.buyer_deposit, 10);
+    });
+}
@@ -1186,20 +1186,7 @@ pub mod pallet {
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
             let entity_id = Self::ensure_shop_owner(shop_id, &who)?;
-
-            EntityUpgradeRules::<T>::try_mutate(entity_id, |maybe_system| -> DispatchResult {
-                let system = maybe_system.as_mut().ok_or(Error::<T>::UpgradeRuleSystemNotInitialized)?;
-
-                let pos = system.rules.iter()
-                    .position(|r| r.id == rule_id)
-                    .ok_or(Error::<T>::UpgradeRuleNotFound)?;
-
-                system.rules.remove(pos);
-
-                Self::deposit_event(Event::UpgradeRuleRemoved { shop_id, rule_id });
-
-                Ok(())
-            })
+            Self::do_remove_upgrade_rule(entity_id, shop_id, rule_id)
         }
 
         /// 设置升级规则系统启用状态
@@ -1927,6 +1914,29 @@ pub mod pallet {
             })
         }
 
+        /// 删除升级规则（治理调用）
+        pub fn governance_remove_upgrade_rule(shop_id: u64, rule_id: u32) -> DispatchResult {
+            let entity_id = Self::resolve_entity_id(shop_id).ok_or(Error::<T>::ShopNotFound)?;
+            Self::do_remove_upgrade_rule(entity_id, shop_id, rule_id)
+        }
+
+        /// 从实体升级规则系统中删除规则
+        fn do_remove_upgrade_rule(entity_id: u64, shop_id: u64, rule_id: u32) -> DispatchResult {
+            EntityUpgradeRules::<T>::try_mutate(entity_id, |maybe_system| -> DispatchResult {
+                let system = maybe_system.as_mut().ok_or(Error::<T>::UpgradeRuleSystemNotInitialized)?;
+
+                let pos = system.rules.iter()
+                    .position(|r| r.id == rule_id)
+                    .ok_or(Error::<T>::UpgradeRuleNotFound)?;
+
+                system.rules.remove(pos);
+
+                Self::deposit_event(Event::UpgradeRuleRemoved { shop_id, rule_id });
+
+                Ok(())
+            })
+        }
+
         /// 获取自定义等级数量
         pub fn custom_level_count(shop_id: u64) -> u8 {
             let entity_id = match Self::resolve_entity_id(shop_id) {
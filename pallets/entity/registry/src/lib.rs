@@ -45,7 +45,10 @@ pub mod pallet {
         BoundedVec, PalletId,
     };
     use frame_system::pallet_prelude::*;
-    use pallet_entity_common::{EntityStatus, EntityType, GovernanceMode, PricingProvider, EntityProvider, ShopProvider, ShopType, MemberMode};
+    use pallet_entity_common::{
+        ensure_admin_caller, AdminCaller, EntityStatus, EntityType, GovernanceMode, PricingProvider,
+        EntityProvider, ShopProvider, ShopType, MemberMode,
+    };
     use sp_runtime::{
         traits::{AccountIdConversion, Saturating, Zero},
         SaturatedConversion,
@@ -163,6 +166,9 @@ pub mod pallet {
         /// 治理 Origin
         type GovernanceOrigin: EnsureOrigin<Self::RuntimeOrigin>;
 
+        /// 实体治理来源（`EntityOrigin::Governance`，成功返回 entity_id）
+        type EntityGovernanceOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = u64>;
+
         /// 定价提供者（用于计算 USDT 等值 NXS 押金）
         type PricingProvider: PricingProvider;
 
@@ -528,11 +534,11 @@ pub mod pallet {
             description_cid: Option<Vec<u8>>,
             metadata_uri: Option<Vec<u8>>,
         ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
+            let caller = ensure_admin_caller::<_, _, T::EntityGovernanceOrigin>(origin)?;
 
             Entities::<T>::try_mutate(entity_id, |maybe_entity| -> DispatchResult {
                 let entity = maybe_entity.as_mut().ok_or(Error::<T>::EntityNotFound)?;
-                ensure!(entity.owner == who, Error::<T>::NotEntityOwner);
+                ensure!(Self::caller_is_owner(entity_id, &entity.owner, &caller), Error::<T>::NotEntityOwner);
                 ensure!(
                     !matches!(entity.status, EntityStatus::Banned | EntityStatus::Closed),
                     Error::<T>::InvalidEntityStatus
@@ -565,11 +571,11 @@ pub mod pallet {
         #[pallet::call_index(2)]
         #[pallet::weight(Weight::from_parts(80_000_000, 5_000))]
         pub fn request_close_entity(origin: OriginFor<T>, entity_id: u64) -> DispatchResult {
-            let who = ensure_signed(origin)?;
+            let caller = ensure_admin_caller::<_, _, T::EntityGovernanceOrigin>(origin)?;
 
             let was_active = Entities::<T>::try_mutate(entity_id, |maybe_entity| -> Result<bool, sp_runtime::DispatchError> {
                 let entity = maybe_entity.as_mut().ok_or(Error::<T>::EntityNotFound)?;
-                ensure!(entity.owner == who, Error::<T>::NotEntityOwner);
+                ensure!(Self::caller_is_owner(entity_id, &entity.owner, &caller), Error::<T>::NotEntityOwner);
                 ensure!(
                     entity.status == EntityStatus::Active || entity.status == EntityStatus::Suspended,
                     Error::<T>::InvalidEntityStatus
@@ -891,13 +897,13 @@ pub mod pallet {
             entity_id: u64,
             new_admin: T::AccountId,
         ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
+            let caller = ensure_admin_caller::<_, _, T::EntityGovernanceOrigin>(origin)?;
             
             Entities::<T>::try_mutate(entity_id, |maybe_entity| -> DispatchResult {
                 let entity = maybe_entity.as_mut().ok_or(Error::<T>::EntityNotFound)?;
                 
                 // 只有所有者可以添加管理员
-                ensure!(entity.owner == who, Error::<T>::NotEntityOwner);
+                ensure!(Self::caller_is_owner(entity_id, &entity.owner, &caller), Error::<T>::NotEntityOwner);
                 // H2: 不允许对 Banned/Closed 实体操作
                 ensure!(
                     !matches!(entity.status, EntityStatus::Banned | EntityStatus::Closed),
@@ -930,13 +936,13 @@ pub mod pallet {
             entity_id: u64,
             admin: T::AccountId,
         ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
+            let caller = ensure_admin_caller::<_, _, T::EntityGovernanceOrigin>(origin)?;
             
             Entities::<T>::try_mutate(entity_id, |maybe_entity| -> DispatchResult {
                 let entity = maybe_entity.as_mut().ok_or(Error::<T>::EntityNotFound)?;
                 
                 // 只有所有者可以移除管理员
-                ensure!(entity.owner == who, Error::<T>::NotEntityOwner);
+                ensure!(Self::caller_is_owner(entity_id, &entity.owner, &caller), Error::<T>::NotEntityOwner);
                 // H2: 不允许对 Banned/Closed 实体操作
                 ensure!(
                     !matches!(entity.status, EntityStatus::Banned | EntityStatus::Closed),
@@ -969,13 +975,13 @@ pub mod pallet {
             entity_id: u64,
             new_owner: T::AccountId,
         ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
+            let caller = ensure_admin_caller::<_, _, T::EntityGovernanceOrigin>(origin)?;
             
             let old_owner = Entities::<T>::try_mutate(entity_id, |maybe_entity| -> Result<T::AccountId, DispatchError> {
                 let entity = maybe_entity.as_mut().ok_or(Error::<T>::EntityNotFound)?;
                 
                 // 只有所有者可以转移所有权
-                ensure!(entity.owner == who, Error::<T>::NotEntityOwner);
+                ensure!(Self::caller_is_owner(entity_id, &entity.owner, &caller), Error::<T>::NotEntityOwner);
                 // H3: 不允许对 Banned/Closed 实体转移所有权
                 ensure!(
                     !matches!(entity.status, EntityStatus::Banned | EntityStatus::Closed),
@@ -1323,6 +1329,14 @@ pub mod pallet {
 
         // ==================== Phase 3 新增辅助函数 ====================
 
+        /// 管理类调用者是否为实体所有者（或该实体的治理来源）
+        fn caller_is_owner(entity_id: u64, owner: &T::AccountId, caller: &AdminCaller<T::AccountId>) -> bool {
+            match caller {
+                AdminCaller::Signed(who) => who == owner,
+                AdminCaller::Governance(id) => *id == entity_id,
+            }
+        }
+
         /// 检查是否是管理员（所有者或管理员列表中）
         pub fn is_admin(entity_id: u64, who: &T::AccountId) -> bool {
             Entities::<T>::get(entity_id)
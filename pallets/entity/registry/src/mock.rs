@@ -100,6 +100,23 @@ impl pallet_entity_registry::Config for Test {
     type MaxEntitiesPerUser = ConstU32<3>;
     type ShopProvider = MockShopProvider;
     type PlatformAccount = PlatformAccountId;
+    type EntityGovernanceOrigin = MockEntityGovernance;
+}
+
+/// 模拟实体治理来源：账户 GOV_ENTITY_0 / GOV_ENTITY_1 签名分别视为实体 0 / 1 的治理来源
+pub const GOV_ENTITY_0: u64 = 900;
+pub const GOV_ENTITY_1: u64 = 901;
+
+pub struct MockEntityGovernance;
+impl frame_support::traits::EnsureOrigin<RuntimeOrigin> for MockEntityGovernance {
+    type Success = u64;
+    fn try_origin(o: RuntimeOrigin) -> Result<u64, RuntimeOrigin> {
+        match o.clone().into() {
+            Ok(frame_system::RawOrigin::Signed(GOV_ENTITY_0)) => Ok(0),
+            Ok(frame_system::RawOrigin::Signed(GOV_ENTITY_1)) => Ok(1),
+            _ => Err(o),
+        }
+    }
 }
 
 // ==================== Test Externalities Builder ====================
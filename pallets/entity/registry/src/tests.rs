@@ -548,6 +548,26 @@ fn add_admin_fails_banned_entity() {
     });
 }
 
+#[test]
+fn entity_governance_origin_can_manage_admins() {
+    new_test_ext().execute_with(|| {
+        let id = create_default_entity(ALICE);
+        assert_eq!(id, 0);
+
+        // 其他实体的治理来源无权操作
+        assert_noop!(
+            EntityRegistry::add_admin(RuntimeOrigin::signed(GOV_ENTITY_1), id, BOB),
+            Error::<Test>::NotEntityOwner
+        );
+
+        // 本实体治理来源可增删管理员
+        assert_ok!(EntityRegistry::add_admin(RuntimeOrigin::signed(GOV_ENTITY_0), id, BOB));
+        assert!(Entities::<Test>::get(id).unwrap().admins.contains(&BOB));
+        assert_ok!(EntityRegistry::remove_admin(RuntimeOrigin::signed(GOV_ENTITY_0), id, BOB));
+        assert!(!Entities::<Test>::get(id).unwrap().admins.contains(&BOB));
+    });
+}
+
 // ==================== remove_admin ====================
 
 #[test]
@@ -43,7 +43,10 @@ pub mod pallet {
         BoundedVec, PalletId,
     };
     use frame_system::pallet_prelude::*;
-    use pallet_entity_common::{PricingProvider, ProductCategory, ProductProvider, ProductStatus, EntityProvider, ShopProvider};
+    use pallet_entity_common::{
+        ensure_admin_caller, AdminCaller, PricingProvider, ProductCategory, ProductProvider,
+        ProductStatus, EntityProvider, ShopProvider,
+    };
     use sp_runtime::{
         traits::{AccountIdConversion, Zero},
         SaturatedConversion,
@@ -118,6 +121,9 @@ pub mod pallet {
         /// Shop 查询接口（Entity-Shop 分离架构）
         type ShopProvider: ShopProvider<Self::AccountId>;
 
+        /// 实体治理来源（`EntityOrigin::Governance`，成功返回 entity_id）
+        type EntityGovernanceOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = u64>;
+
         /// 定价提供者（用于计算 USDT 等值 NXS 押金）
         type PricingProvider: PricingProvider;
 
@@ -366,15 +372,13 @@ pub mod pallet {
             stock: Option<u32>,
             category: Option<ProductCategory>,
         ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
+            let caller = ensure_admin_caller::<_, _, T::EntityGovernanceOrigin>(origin)?;
 
             Products::<T>::try_mutate(product_id, |maybe_product| -> DispatchResult {
                 let product = maybe_product.as_mut().ok_or(Error::<T>::ProductNotFound)?;
 
                 // 验证店主
-                let owner = T::ShopProvider::shop_owner(product.shop_id)
-                    .ok_or(Error::<T>::ShopNotFound)?;
-                ensure!(owner == who, Error::<T>::NotShopOwner);
+                Self::ensure_caller_owns_shop(product.shop_id, &caller)?;
 
                 if let Some(c) = name_cid {
                     product.name_cid = c.try_into().map_err(|_| Error::<T>::CidTooLong)?;
@@ -414,14 +418,12 @@ pub mod pallet {
         #[pallet::call_index(2)]
         #[pallet::weight(Weight::from_parts(120_000_000, 6_000))]
         pub fn publish_product(origin: OriginFor<T>, product_id: u64) -> DispatchResult {
-            let who = ensure_signed(origin)?;
+            let caller = ensure_admin_caller::<_, _, T::EntityGovernanceOrigin>(origin)?;
 
             Products::<T>::try_mutate(product_id, |maybe_product| -> DispatchResult {
                 let product = maybe_product.as_mut().ok_or(Error::<T>::ProductNotFound)?;
 
-                let owner = T::ShopProvider::shop_owner(product.shop_id)
-                    .ok_or(Error::<T>::ShopNotFound)?;
-                ensure!(owner == who, Error::<T>::NotShopOwner);
+                Self::ensure_caller_owns_shop(product.shop_id, &caller)?;
                 ensure!(T::ShopProvider::is_shop_active(product.shop_id), Error::<T>::ShopNotActive);
                 // C3: 只能从 Draft/OffShelf 上架，防止重复计数
                 ensure!(
@@ -449,14 +451,12 @@ pub mod pallet {
         #[pallet::call_index(3)]
         #[pallet::weight(Weight::from_parts(120_000_000, 6_000))]
         pub fn unpublish_product(origin: OriginFor<T>, product_id: u64) -> DispatchResult {
-            let who = ensure_signed(origin)?;
+            let caller = ensure_admin_caller::<_, _, T::EntityGovernanceOrigin>(origin)?;
 
             Products::<T>::try_mutate(product_id, |maybe_product| -> DispatchResult {
                 let product = maybe_product.as_mut().ok_or(Error::<T>::ProductNotFound)?;
 
-                let owner = T::ShopProvider::shop_owner(product.shop_id)
-                    .ok_or(Error::<T>::ShopNotFound)?;
-                ensure!(owner == who, Error::<T>::NotShopOwner);
+                Self::ensure_caller_owns_shop(product.shop_id, &caller)?;
                 // C4: 只能从 OnSale/SoldOut 下架，防止统计错误
                 ensure!(
                     product.status == ProductStatus::OnSale || product.status == ProductStatus::SoldOut,
@@ -487,14 +487,12 @@ pub mod pallet {
         #[pallet::call_index(4)]
         #[pallet::weight(Weight::from_parts(200_000_000, 10_000))]
         pub fn delete_product(origin: OriginFor<T>, product_id: u64) -> DispatchResult {
-            let who = ensure_signed(origin)?;
+            let caller = ensure_admin_caller::<_, _, T::EntityGovernanceOrigin>(origin)?;
 
             let product = Products::<T>::get(product_id).ok_or(Error::<T>::ProductNotFound)?;
 
             // 验证店主
-            let owner = T::ShopProvider::shop_owner(product.shop_id)
-                .ok_or(Error::<T>::ShopNotFound)?;
-            ensure!(owner == who, Error::<T>::NotShopOwner);
+            Self::ensure_caller_owns_shop(product.shop_id, &caller)?;
 
             // 只能删除草稿或已下架的商品
             ensure!(
@@ -543,6 +541,17 @@ pub mod pallet {
     // ==================== 辅助函数 ====================
 
     impl<T: Config> Pallet<T> {
+        /// 校验管理类调用者：签名账户须为店主，治理来源须为店铺所属实体
+        fn ensure_caller_owns_shop(shop_id: u64, caller: &AdminCaller<T::AccountId>) -> DispatchResult {
+            let owner = T::ShopProvider::shop_owner(shop_id).ok_or(Error::<T>::ShopNotFound)?;
+            let allowed = match caller {
+                AdminCaller::Signed(who) => *who == owner,
+                AdminCaller::Governance(entity_id) => T::ShopProvider::shop_entity_id(shop_id) == Some(*entity_id),
+            };
+            ensure!(allowed, Error::<T>::NotShopOwner);
+            Ok(())
+        }
+
         /// 获取 Pallet 账户
         pub fn pallet_account() -> T::AccountId {
             PRODUCT_PALLET_ID.into_account_truncating()
@@ -649,5 +658,60 @@ pub mod pallet {
                 Ok(())
             })
         }
+
+        // ==================== 治理调用接口 ====================
+
+        fn update_price(product_id: u64, new_price: BalanceOf<T>) -> Result<(), sp_runtime::DispatchError> {
+            ensure!(!new_price.is_zero(), Error::<T>::InvalidPrice);
+            Products::<T>::try_mutate(product_id, |maybe_product| -> Result<(), sp_runtime::DispatchError> {
+                let product = maybe_product.as_mut().ok_or(Error::<T>::ProductNotFound)?;
+                product.price = new_price;
+                product.updated_at = <frame_system::Pallet<T>>::block_number();
+                Ok(())
+            })?;
+            Self::deposit_event(Event::ProductUpdated { product_id });
+            Ok(())
+        }
+
+        fn delist_product(product_id: u64) -> Result<(), sp_runtime::DispatchError> {
+            Products::<T>::try_mutate(product_id, |maybe_product| -> Result<(), sp_runtime::DispatchError> {
+                let product = maybe_product.as_mut().ok_or(Error::<T>::ProductNotFound)?;
+                // 与 unpublish_product 一致：只能从 OnSale/SoldOut 下架
+                ensure!(
+                    product.status == ProductStatus::OnSale || product.status == ProductStatus::SoldOut,
+                    Error::<T>::InvalidProductStatus
+                );
+                if product.status == ProductStatus::OnSale {
+                    ProductStats::<T>::mutate(|stats| {
+                        stats.on_sale_products = stats.on_sale_products.saturating_sub(1);
+                    });
+                }
+                product.status = ProductStatus::OffShelf;
+                product.updated_at = <frame_system::Pallet<T>>::block_number();
+                Ok(())
+            })?;
+            Self::deposit_event(Event::ProductStatusChanged {
+                product_id,
+                status: ProductStatus::OffShelf,
+            });
+            Ok(())
+        }
+
+        fn set_inventory(product_id: u64, new_inventory: u32) -> Result<(), sp_runtime::DispatchError> {
+            Products::<T>::try_mutate(product_id, |maybe_product| -> Result<(), sp_runtime::DispatchError> {
+                let product = maybe_product.as_mut().ok_or(Error::<T>::ProductNotFound)?;
+                product.stock = new_inventory;
+                if new_inventory > 0 && product.status == ProductStatus::SoldOut {
+                    product.status = ProductStatus::OnSale;
+                    ProductStats::<T>::mutate(|stats| {
+                        stats.on_sale_products = stats.on_sale_products.saturating_add(1);
+                    });
+                }
+                product.updated_at = <frame_system::Pallet<T>>::block_number();
+                Ok(())
+            })?;
+            Self::deposit_event(Event::ProductUpdated { product_id });
+            Ok(())
+        }
     }
 }
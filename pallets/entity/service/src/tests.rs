@@ -302,6 +302,25 @@ fn publish_product_works() {
     });
 }
 
+#[test]
+fn entity_governance_origin_can_publish_product() {
+    new_test_ext().execute_with(|| {
+        create_default_product();
+
+        // 其他实体的治理来源无权操作
+        assert_noop!(
+            EntityService::publish_product(RuntimeOrigin::signed(GOV_ENTITY_2), 0),
+            Error::<Test>::NotShopOwner
+        );
+
+        // 店铺所属实体的治理来源可上/下架
+        assert_ok!(EntityService::publish_product(RuntimeOrigin::signed(GOV_ENTITY_1), 0));
+        assert_eq!(Products::<Test>::get(0).unwrap().status, ProductStatus::OnSale);
+        assert_ok!(EntityService::unpublish_product(RuntimeOrigin::signed(GOV_ENTITY_1), 0));
+        assert_eq!(Products::<Test>::get(0).unwrap().status, ProductStatus::OffShelf);
+    });
+}
+
 #[test]
 fn publish_product_fails_already_on_sale() {
     new_test_ext().execute_with(|| {
@@ -682,6 +701,42 @@ fn add_sold_count_works() {
 
 // ==================== 押金机制测试 ====================
 
+#[test]
+fn governance_product_hooks_work() {
+    new_test_ext().execute_with(|| {
+        use pallet_entity_common::ProductProvider;
+
+        create_default_product();
+        assert_ok!(EntityService::publish_product(RuntimeOrigin::signed(1), 0));
+
+        assert_ok!(<EntityService as ProductProvider<u64, u128>>::update_price(0, 42));
+        assert_eq!(Products::<Test>::get(0).unwrap().price, 42);
+        assert_noop!(
+            <EntityService as ProductProvider<u64, u128>>::update_price(0, 0),
+            Error::<Test>::InvalidPrice
+        );
+
+        assert_ok!(<EntityService as ProductProvider<u64, u128>>::set_inventory(0, 7));
+        assert_eq!(Products::<Test>::get(0).unwrap().stock, 7);
+
+        let on_sale_before = ProductStats::<Test>::get().on_sale_products;
+        assert_ok!(<EntityService as ProductProvider<u64, u128>>::delist_product(0));
+        let product = Products::<Test>::get(0).unwrap();
+        assert_eq!(product.status, ProductStatus::OffShelf);
+        assert_eq!(ProductStats::<Test>::get().on_sale_products, on_sale_before - 1);
+
+        // 已下架商品不可重复下架
+        assert_noop!(
+            <EntityService as ProductProvider<u64, u128>>::delist_product(0),
+            Error::<Test>::InvalidProductStatus
+        );
+        assert_noop!(
+            <EntityService as ProductProvider<u64, u128>>::update_price(999, 1),
+            Error::<Test>::ProductNotFound
+        );
+    });
+}
+
 #[test]
 fn deposit_calculation_respects_pricing() {
     new_test_ext().execute_with(|| {
@@ -43,8 +43,8 @@ pub mod pallet {
     };
     use frame_system::pallet_prelude::*;
     use pallet_entity_common::{
-        CommissionFundGuard, EffectiveShopStatus, EntityProvider, EntityStatus,
-        MemberMode, ShopOperatingStatus, ShopProvider, ShopType,
+        ensure_admin_caller, AdminCaller, CommissionFundGuard, EffectiveShopStatus, EntityProvider,
+        EntityStatus, MemberMode, ShopOperatingStatus, ShopProvider, ShopType,
     };
     use sp_runtime::{
         traits::{AccountIdConversion, Saturating, Zero},
@@ -184,6 +184,9 @@ pub mod pallet {
 
         /// 佣金资金保护（查询已承诺的 pending + shopping 总额）
         type CommissionFundGuard: pallet_entity_common::CommissionFundGuard;
+
+        /// 实体治理来源（`EntityOrigin::Governance`，成功返回 entity_id）
+        type EntityGovernanceOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = u64>;
     }
 
     #[pallet::pallet]
@@ -275,6 +278,8 @@ pub mod pallet {
         FundWarning { shop_id: u64, balance: BalanceOf<T> },
         /// 资金耗尽
         FundDepleted { shop_id: u64 },
+        /// 治理金库支出
+        TreasurySpent { shop_id: u64, to: T::AccountId, amount: BalanceOf<T>, new_balance: BalanceOf<T> },
     }
 
     // ========================================================================
@@ -327,6 +332,8 @@ pub mod pallet {
         InvalidConfig,
         /// 不能关闭主 Shop
         CannotClosePrimaryShop,
+        /// CID 过长
+        CidTooLong,
     }
 
     // ========================================================================
@@ -388,13 +395,13 @@ pub mod pallet {
             logo_cid: Option<BoundedVec<u8, T::MaxCidLength>>,
             description_cid: Option<BoundedVec<u8, T::MaxCidLength>>,
         ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
+            let caller = ensure_admin_caller::<_, _, T::EntityGovernanceOrigin>(origin)?;
             
             Shops::<T>::try_mutate(shop_id, |maybe_shop| -> DispatchResult {
                 let shop = maybe_shop.as_mut().ok_or(Error::<T>::ShopNotFound)?;
                 
                 // 检查权限
-                ensure!(Self::can_manage_shop(shop, &who), Error::<T>::NotAuthorized);
+                ensure!(Self::caller_can_manage(shop, &caller), Error::<T>::NotAuthorized);
                 
                 // H3: 已关闭的 Shop 不可修改
                 ensure!(shop.status != ShopOperatingStatus::Closed, Error::<T>::ShopAlreadyClosed);
@@ -424,15 +431,13 @@ pub mod pallet {
             shop_id: u64,
             manager: T::AccountId,
         ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
+            let caller = ensure_admin_caller::<_, _, T::EntityGovernanceOrigin>(origin)?;
             
             Shops::<T>::try_mutate(shop_id, |maybe_shop| -> DispatchResult {
                 let shop = maybe_shop.as_mut().ok_or(Error::<T>::ShopNotFound)?;
                 
                 // 只有 Entity owner 可以添加管理员
-                let owner = T::EntityProvider::entity_owner(shop.entity_id)
-                    .ok_or(Error::<T>::EntityNotFound)?;
-                ensure!(who == owner, Error::<T>::NotAuthorized);
+                ensure!(Self::caller_is_entity_owner(shop.entity_id, &caller)?, Error::<T>::NotAuthorized);
                 
                 // 检查是否已存在
                 ensure!(!shop.managers.contains(&manager), Error::<T>::ManagerAlreadyExists);
@@ -454,15 +459,13 @@ pub mod pallet {
             shop_id: u64,
             manager: T::AccountId,
         ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
+            let caller = ensure_admin_caller::<_, _, T::EntityGovernanceOrigin>(origin)?;
             
             Shops::<T>::try_mutate(shop_id, |maybe_shop| -> DispatchResult {
                 let shop = maybe_shop.as_mut().ok_or(Error::<T>::ShopNotFound)?;
                 
                 // 只有 Entity owner 可以移除管理员
-                let owner = T::EntityProvider::entity_owner(shop.entity_id)
-                    .ok_or(Error::<T>::EntityNotFound)?;
-                ensure!(who == owner, Error::<T>::NotAuthorized);
+                ensure!(Self::caller_is_entity_owner(shop.entity_id, &caller)?, Error::<T>::NotAuthorized);
                 
                 // 查找并移除
                 let pos = shop.managers.iter().position(|m| m == &manager)
@@ -514,13 +517,13 @@ pub mod pallet {
             origin: OriginFor<T>,
             shop_id: u64,
         ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
+            let caller = ensure_admin_caller::<_, _, T::EntityGovernanceOrigin>(origin)?;
             
             Shops::<T>::try_mutate(shop_id, |maybe_shop| -> DispatchResult {
                 let shop = maybe_shop.as_mut().ok_or(Error::<T>::ShopNotFound)?;
                 
                 // 检查权限
-                ensure!(Self::can_manage_shop(shop, &who), Error::<T>::NotAuthorized);
+                ensure!(Self::caller_can_manage(shop, &caller), Error::<T>::NotAuthorized);
                 
                 // 检查状态
                 ensure!(shop.status == ShopOperatingStatus::Active, Error::<T>::ShopAlreadyPaused);
@@ -539,13 +542,13 @@ pub mod pallet {
             origin: OriginFor<T>,
             shop_id: u64,
         ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
+            let caller = ensure_admin_caller::<_, _, T::EntityGovernanceOrigin>(origin)?;
             
             Shops::<T>::try_mutate(shop_id, |maybe_shop| -> DispatchResult {
                 let shop = maybe_shop.as_mut().ok_or(Error::<T>::ShopNotFound)?;
                 
                 // 检查权限
-                ensure!(Self::can_manage_shop(shop, &who), Error::<T>::NotAuthorized);
+                ensure!(Self::caller_can_manage(shop, &caller), Error::<T>::NotAuthorized);
                 
                 // 检查状态
                 ensure!(shop.status.can_resume(), Error::<T>::ShopNotPaused);
@@ -572,13 +575,13 @@ pub mod pallet {
             address_cid: Option<BoundedVec<u8, T::MaxCidLength>>,
             business_hours_cid: Option<BoundedVec<u8, T::MaxCidLength>>,
         ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
+            let caller = ensure_admin_caller::<_, _, T::EntityGovernanceOrigin>(origin)?;
             
             Shops::<T>::try_mutate(shop_id, |maybe_shop| -> DispatchResult {
                 let shop = maybe_shop.as_mut().ok_or(Error::<T>::ShopNotFound)?;
                 
                 // 检查权限
-                ensure!(Self::can_manage_shop(shop, &who), Error::<T>::NotAuthorized);
+                ensure!(Self::caller_can_manage(shop, &caller), Error::<T>::NotAuthorized);
                 ensure!(shop.status != ShopOperatingStatus::Closed, Error::<T>::ShopAlreadyClosed);
                 
                 // 验证位置（经度 -180~180，纬度 -90~90，精度 10^6）
@@ -612,12 +615,12 @@ pub mod pallet {
             exchange_rate: u16,
             transferable: bool,
         ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
+            let caller = ensure_admin_caller::<_, _, T::EntityGovernanceOrigin>(origin)?;
             
             let shop = Shops::<T>::get(shop_id).ok_or(Error::<T>::ShopNotFound)?;
             
             // 检查权限
-            ensure!(Self::can_manage_shop(&shop, &who), Error::<T>::NotAuthorized);
+            ensure!(Self::caller_can_manage(&shop, &caller), Error::<T>::NotAuthorized);
             ensure!(shop.status != ShopOperatingStatus::Closed, Error::<T>::ShopAlreadyClosed);
             
             // 检查是否已启用
@@ -648,15 +651,13 @@ pub mod pallet {
             origin: OriginFor<T>,
             shop_id: u64,
         ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
+            let caller = ensure_admin_caller::<_, _, T::EntityGovernanceOrigin>(origin)?;
             
             Shops::<T>::try_mutate(shop_id, |maybe_shop| -> DispatchResult {
                 let shop = maybe_shop.as_mut().ok_or(Error::<T>::ShopNotFound)?;
                 
                 // 只有 Entity owner 可以关闭 Shop
-                let owner = T::EntityProvider::entity_owner(shop.entity_id)
-                    .ok_or(Error::<T>::EntityNotFound)?;
-                ensure!(who == owner, Error::<T>::NotAuthorized);
+                ensure!(Self::caller_is_entity_owner(shop.entity_id, &caller)?, Error::<T>::NotAuthorized);
                 
                 // 主 Shop 不可关闭
                 ensure!(!shop.is_primary, Error::<T>::CannotClosePrimaryShop);
@@ -681,10 +682,10 @@ pub mod pallet {
             origin: OriginFor<T>,
             shop_id: u64,
         ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
+            let caller = ensure_admin_caller::<_, _, T::EntityGovernanceOrigin>(origin)?;
 
             let shop = Shops::<T>::get(shop_id).ok_or(Error::<T>::ShopNotFound)?;
-            ensure!(Self::can_manage_shop(&shop, &who), Error::<T>::NotAuthorized);
+            ensure!(Self::caller_can_manage(&shop, &caller), Error::<T>::NotAuthorized);
             ensure!(ShopPointsConfigs::<T>::contains_key(shop_id), Error::<T>::PointsNotEnabled);
 
             ShopPointsConfigs::<T>::remove(shop_id);
@@ -703,10 +704,10 @@ pub mod pallet {
             exchange_rate: Option<u16>,
             transferable: Option<bool>,
         ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
+            let caller = ensure_admin_caller::<_, _, T::EntityGovernanceOrigin>(origin)?;
 
             let shop = Shops::<T>::get(shop_id).ok_or(Error::<T>::ShopNotFound)?;
-            ensure!(Self::can_manage_shop(&shop, &who), Error::<T>::NotAuthorized);
+            ensure!(Self::caller_can_manage(&shop, &caller), Error::<T>::NotAuthorized);
 
             ShopPointsConfigs::<T>::try_mutate(shop_id, |maybe_config| -> DispatchResult {
                 let config = maybe_config.as_mut().ok_or(Error::<T>::PointsNotEnabled)?;
@@ -787,6 +788,25 @@ pub mod pallet {
             shop.managers.contains(account)
         }
 
+        /// 管理类调用者是否有权管理 Shop（实体治理来源须为 Shop 所属实体）
+        fn caller_can_manage(shop: &ShopOf<T>, caller: &AdminCaller<T::AccountId>) -> bool {
+            match caller {
+                AdminCaller::Signed(who) => Self::can_manage_shop(shop, who),
+                AdminCaller::Governance(entity_id) => *entity_id == shop.entity_id,
+            }
+        }
+
+        /// 管理类调用者是否为实体所有者（或该实体的治理来源）
+        fn caller_is_entity_owner(entity_id: u64, caller: &AdminCaller<T::AccountId>) -> Result<bool, DispatchError> {
+            match caller {
+                AdminCaller::Signed(who) => {
+                    let owner = T::EntityProvider::entity_owner(entity_id).ok_or(Error::<T>::EntityNotFound)?;
+                    Ok(*who == owner)
+                },
+                AdminCaller::Governance(id) => Ok(*id == entity_id),
+            }
+        }
+
         /// 发放积分（供外部模块调用，如订单完成后返积分）
         pub fn issue_points(
             shop_id: u64,
@@ -1093,5 +1113,61 @@ pub mod pallet {
                 Ok(())
             })
         }
+
+        fn treasury_spend(shop_id: u64, to: &T::AccountId, amount: u128) -> Result<(), DispatchError> {
+            ensure!(Shops::<T>::contains_key(shop_id), Error::<T>::ShopNotFound);
+            let shop_account = Self::shop_account_id(shop_id);
+            let balance = T::Currency::free_balance(&shop_account);
+            let amount_balance: BalanceOf<T> = amount.saturated_into();
+
+            // 偿付安全：与 deduct_operating_fund 一致，支出不得侵占已承诺的佣金资金
+            let protected: BalanceOf<T> = T::CommissionFundGuard::protected_funds(shop_id).saturated_into();
+            let available = balance.saturating_sub(protected);
+            ensure!(available >= amount_balance, Error::<T>::InsufficientOperatingFund);
+
+            T::Currency::transfer(&shop_account, to, amount_balance, ExistenceRequirement::KeepAlive)?;
+
+            let new_balance = T::Currency::free_balance(&shop_account);
+            Self::deposit_event(Event::TreasurySpent {
+                shop_id,
+                to: to.clone(),
+                amount: amount_balance,
+                new_balance,
+            });
+
+            if new_balance < T::MinOperatingBalance::get() {
+                Shops::<T>::mutate(shop_id, |maybe_shop| {
+                    if let Some(s) = maybe_shop {
+                        if s.status == ShopOperatingStatus::Active {
+                            s.status = ShopOperatingStatus::FundDepleted;
+                        }
+                    }
+                });
+                Self::deposit_event(Event::FundDepleted { shop_id });
+            }
+            Ok(())
+        }
+
+        fn update_shop_info(
+            shop_id: u64,
+            name: Option<sp_std::vec::Vec<u8>>,
+            description_cid: Option<sp_std::vec::Vec<u8>>,
+        ) -> Result<(), DispatchError> {
+            Shops::<T>::try_mutate(shop_id, |maybe_shop| -> Result<(), DispatchError> {
+                let shop = maybe_shop.as_mut().ok_or(Error::<T>::ShopNotFound)?;
+                ensure!(shop.status != ShopOperatingStatus::Closed, Error::<T>::ShopAlreadyClosed);
+
+                if let Some(n) = name {
+                    ensure!(!n.is_empty(), Error::<T>::ShopNameEmpty);
+                    shop.name = n.try_into().map_err(|_| Error::<T>::NameTooLong)?;
+                }
+                if let Some(cid) = description_cid {
+                    shop.description_cid = Some(cid.try_into().map_err(|_| Error::<T>::CidTooLong)?);
+                }
+
+                Self::deposit_event(Event::ShopUpdated { shop_id });
+                Ok(())
+            })
+        }
     }
 }
@@ -100,6 +100,23 @@ impl pallet_entity_shop::Config for Test {
     type MinOperatingBalance = MinOperatingBalance;
     type WarningThreshold = WarningThreshold;
     type CommissionFundGuard = ();
+    type EntityGovernanceOrigin = MockEntityGovernance;
+}
+
+/// 模拟实体治理来源：账户 GOV_ENTITY_1 / GOV_ENTITY_2 签名分别视为实体 1 / 2 的治理来源
+pub const GOV_ENTITY_1: u64 = 900;
+pub const GOV_ENTITY_2: u64 = 901;
+
+pub struct MockEntityGovernance;
+impl frame_support::traits::EnsureOrigin<RuntimeOrigin> for MockEntityGovernance {
+    type Success = u64;
+    fn try_origin(o: RuntimeOrigin) -> Result<u64, RuntimeOrigin> {
+        match o.clone().into() {
+            Ok(frame_system::RawOrigin::Signed(GOV_ENTITY_1)) => Ok(1),
+            Ok(frame_system::RawOrigin::Signed(GOV_ENTITY_2)) => Ok(2),
+            _ => Err(o),
+        }
+    }
 }
 
 // Build genesis storage according to the mock runtime.
@@ -272,6 +272,32 @@ fn pause_shop_fails_already_paused() {
     });
 }
 
+#[test]
+fn entity_governance_origin_can_pause_shop() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Shop::create_shop(
+            RuntimeOrigin::signed(1),
+            1,
+            bounded_name(b"Test Shop"),
+            ShopType::OnlineStore,
+            MemberMode::Inherit,
+            1000,
+        ));
+
+        // 其他实体的治理来源无权操作
+        assert_noop!(
+            Shop::pause_shop(RuntimeOrigin::signed(GOV_ENTITY_2), 1),
+            Error::<Test>::NotAuthorized
+        );
+
+        // 所属实体的治理来源可暂停/恢复
+        assert_ok!(Shop::pause_shop(RuntimeOrigin::signed(GOV_ENTITY_1), 1));
+        assert_eq!(Shop::shops(1).unwrap().status, ShopOperatingStatus::Paused);
+        assert_ok!(Shop::resume_shop(RuntimeOrigin::signed(GOV_ENTITY_1), 1));
+        assert_eq!(Shop::shops(1).unwrap().status, ShopOperatingStatus::Active);
+    });
+}
+
 // ============================================================================
 // close_shop tests
 // ============================================================================
@@ -426,3 +452,64 @@ fn multiple_shops_per_entity_works() {
         assert_eq!(Shop::shops(2).unwrap().shop_type, ShopType::PhysicalStore);
     });
 }
+
+// ============================================================================
+// Governance hooks tests
+// ============================================================================
+
+#[test]
+fn governance_treasury_spend_works() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Shop::create_shop(
+            RuntimeOrigin::signed(1),
+            1,
+            bounded_name(b"Test Shop"),
+            ShopType::OnlineStore,
+            MemberMode::Inherit,
+            1000,
+        ));
+        let before = Balances::free_balance(3);
+
+        assert_ok!(<Shop as ShopProvider<u64>>::treasury_spend(1, &3, 400));
+        assert_eq!(Balances::free_balance(3), before + 400);
+        assert_eq!(Shop::get_operating_balance(1), 600);
+
+        // 超出可用余额
+        assert_noop!(
+            <Shop as ShopProvider<u64>>::treasury_spend(1, &3, 10_000),
+            Error::<Test>::InsufficientOperatingFund
+        );
+        assert_noop!(
+            <Shop as ShopProvider<u64>>::treasury_spend(99, &3, 1),
+            Error::<Test>::ShopNotFound
+        );
+    });
+}
+
+#[test]
+fn governance_update_shop_info_works() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Shop::create_shop(
+            RuntimeOrigin::signed(1),
+            1,
+            bounded_name(b"Test Shop"),
+            ShopType::OnlineStore,
+            MemberMode::Inherit,
+            1000,
+        ));
+
+        assert_ok!(<Shop as ShopProvider<u64>>::update_shop_info(
+            1,
+            Some(b"DAO Shop".to_vec()),
+            Some(b"QmDescription".to_vec()),
+        ));
+        let shop = Shop::shops(1).unwrap();
+        assert_eq!(shop.name.to_vec(), b"DAO Shop".to_vec());
+        assert_eq!(shop.description_cid.map(|c| c.to_vec()), Some(b"QmDescription".to_vec()));
+
+        assert_noop!(
+            <Shop as ShopProvider<u64>>::update_shop_info(1, Some(Vec::new()), None),
+            Error::<Test>::ShopNameEmpty
+        );
+    });
+}
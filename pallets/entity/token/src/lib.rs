@@ -9,6 +9,7 @@
 //! - 积分/通证兑换
 //! - 通证转让
 //! - 分红功能
+//! - 团队分配分段锁仓（可选治理/披露里程碑门控）
 //!
 //! ## 架构
 //!
@@ -47,8 +48,13 @@ pub mod pallet {
         BoundedVec,
     };
     use frame_system::pallet_prelude::*;
-    use pallet_entity_common::{DividendConfig, EntityProvider, ShopProvider, TokenType, TransferRestrictionMode};
+    use pallet_entity_common::{
+        ensure_admin_caller, validate_vesting_tranches, vested_tranche_bps, AdminCaller,
+        DividendConfig, EntityProvider, MilestoneProvider, ShopProvider, TokenType,
+        TransferRestrictionMode, VestingTranche,
+    };
     use sp_runtime::traits::{AtLeast32BitUnsigned, Saturating, Zero};
+    use sp_runtime::SaturatedConversion;
 
     /// 实体通证配置（原 ShopTokenConfig，Phase 2 扩展）
     #[derive(Encode, Decode, codec::DecodeWithMemTracking, Clone, PartialEq, Eq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
@@ -90,6 +96,27 @@ pub mod pallet {
         BlockNumberFor<T>,
     >;
 
+    /// 团队分配分段锁仓（铸造后预留在受益人账户，按档位释放）
+    #[derive(Encode, Decode, codec::DecodeWithMemTracking, Clone, PartialEq, Eq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+    #[scale_info(skip_type_params(MaxTranches))]
+    pub struct TeamVestingGrant<Balance, BlockNumber, MaxTranches: Get<u32>> {
+        /// 锁仓总量
+        pub total: Balance,
+        /// 已释放数量
+        pub released: Balance,
+        /// 锁仓起点（铸造区块）
+        pub start: BlockNumber,
+        /// 解锁档位
+        pub tranches: BoundedVec<VestingTranche<BlockNumber>, MaxTranches>,
+    }
+
+    /// 团队锁仓类型别名
+    pub type TeamVestingGrantOf<T> = TeamVestingGrant<
+        <T as Config>::AssetBalance,
+        BlockNumberFor<T>,
+        <T as Config>::MaxVestingTranches,
+    >;
+
     #[pallet::config]
     pub trait Config: frame_system::Config {
         /// 运行时事件类型
@@ -145,6 +172,16 @@ pub mod pallet {
 
         /// 成员查询接口（可选）
         type MemberProvider: EntityMemberProvider<Self::AccountId>;
+
+        /// 实体治理来源（`EntityOrigin::Governance`，成功返回 entity_id）
+        type EntityGovernanceOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = u64>;
+
+        /// 里程碑查询接口（团队锁仓门控档位）
+        type MilestoneProvider: MilestoneProvider;
+
+        /// 团队锁仓最大档位数
+        #[pallet::constant]
+        type MaxVestingTranches: Get<u32>;
     }
 
     /// KYC 级别查询 Trait
@@ -222,6 +259,19 @@ pub mod pallet {
         (T::AssetBalance, BlockNumberFor<T>),  // (amount, unlock_at)
     >;
 
+    /// 锁仓起始区块 (entity_id, holder) -> locked_since
+    /// 追加锁仓时按金额加权平均，供治理按锁仓时长计算投票权重
+    #[pallet::storage]
+    #[pallet::getter(fn locked_since)]
+    pub type LockedSince<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        u64,  // entity_id
+        Blake2_128Concat,
+        T::AccountId,
+        BlockNumberFor<T>,
+    >;
+
     /// 待领取分红 (entity_id, holder) -> amount
     #[pallet::storage]
     #[pallet::getter(fn pending_dividends)]
@@ -285,6 +335,18 @@ pub mod pallet {
         ValueQuery,
     >;
 
+    /// 团队分配锁仓 (entity_id, beneficiary) -> TeamVestingGrant
+    #[pallet::storage]
+    #[pallet::getter(fn team_vesting)]
+    pub type TeamVesting<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        u64,  // entity_id
+        Blake2_128Concat,
+        T::AccountId,
+        TeamVestingGrantOf<T>,
+    >;
+
     // ==================== 事件 ====================
 
     #[pallet::event]
@@ -388,6 +450,19 @@ pub mod pallet {
             added: u32,
             removed: u32,
         },
+        /// 团队分配已铸造（预留锁仓）
+        VestedTokensMinted {
+            entity_id: u64,
+            beneficiary: T::AccountId,
+            amount: T::AssetBalance,
+            tranches: u32,
+        },
+        /// 团队锁仓已释放
+        VestedTokensReleased {
+            entity_id: u64,
+            beneficiary: T::AccountId,
+            amount: T::AssetBalance,
+        },
     }
 
     // ==================== 错误 ====================
@@ -470,6 +545,14 @@ pub mod pallet {
         EmptyName,
         /// 符号为空
         EmptySymbol,
+        /// 锁仓档位无效（非空、每档 bps > 0、合计 10000、偏移非递减）
+        InvalidVestingTranches,
+        /// 受益人已有团队锁仓
+        TeamVestingExists,
+        /// 无团队锁仓
+        NoTeamVesting,
+        /// 暂无可释放的锁仓代币
+        NothingToRelease,
     }
 
     // ==================== Extrinsics ====================
@@ -583,11 +666,8 @@ pub mod pallet {
             transferable: Option<bool>,
             enabled: Option<bool>,
         ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
-
             // 验证店主
-            let owner = T::ShopProvider::shop_owner(shop_id).ok_or(Error::<T>::ShopNotFound)?;
-            ensure!(owner == who, Error::<T>::NotShopOwner);
+            Self::ensure_shop_owner_or_governance(origin, shop_id)?;
 
             ShopTokenConfigs::<T>::try_mutate(shop_id, |maybe_config| -> DispatchResult {
                 let config = maybe_config.as_mut().ok_or(Error::<T>::TokenNotEnabled)?;
@@ -634,11 +714,8 @@ pub mod pallet {
             to: T::AccountId,
             amount: T::AssetBalance,
         ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
-
             // 验证店主
-            let owner = T::ShopProvider::shop_owner(shop_id).ok_or(Error::<T>::ShopNotFound)?;
-            ensure!(owner == who, Error::<T>::NotShopOwner);
+            Self::ensure_shop_owner_or_governance(origin, shop_id)?;
 
             // 检查代币是否启用
             let config = ShopTokenConfigs::<T>::get(shop_id).ok_or(Error::<T>::TokenNotEnabled)?;
@@ -713,11 +790,8 @@ pub mod pallet {
             enabled: bool,
             min_period: BlockNumberFor<T>,
         ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
-
             // 验证所有者
-            let owner = T::ShopProvider::shop_owner(entity_id).ok_or(Error::<T>::ShopNotFound)?;
-            ensure!(owner == who, Error::<T>::NotShopOwner);
+            Self::ensure_shop_owner_or_governance(origin, entity_id)?;
 
             // 检查代币是否存在
             ShopTokenConfigs::<T>::try_mutate(entity_id, |maybe_config| -> DispatchResult {
@@ -749,66 +823,10 @@ pub mod pallet {
             total_amount: T::AssetBalance,
             recipients: Vec<(T::AccountId, T::AssetBalance)>,
         ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
-
             // 验证所有者
-            let owner = T::ShopProvider::shop_owner(entity_id).ok_or(Error::<T>::ShopNotFound)?;
-            ensure!(owner == who, Error::<T>::NotShopOwner);
-
-            // H5: 限制接收人数量
-            ensure!(
-                recipients.len() <= T::MaxDividendRecipients::get() as usize,
-                Error::<T>::TooManyRecipients
-            );
-
-            // 检查分红配置
-            let config = ShopTokenConfigs::<T>::get(entity_id).ok_or(Error::<T>::TokenNotEnabled)?;
-            ensure!(config.dividend_config.enabled, Error::<T>::DividendNotEnabled);
-            // M6: 检查通证类型是否支持分红
-            ensure!(config.token_type.has_dividend_rights(), Error::<T>::TokenTypeNotSupported);
-
-            let now = <frame_system::Pallet<T>>::block_number();
-            let last = config.dividend_config.last_distribution;
-            let min_period = config.dividend_config.min_period;
-            
-            // 检查分红周期（首次分红跳过检查）
-            if !last.is_zero() {
-                ensure!(now >= last + min_period, Error::<T>::DividendPeriodNotReached);
-            }
-
-            // H6: 校验 total_amount == sum(recipients)
-            let mut sum = T::AssetBalance::zero();
-            for (_, amount) in recipients.iter() {
-                sum = sum.saturating_add(*amount);
-            }
-            ensure!(sum == total_amount, Error::<T>::DividendAmountMismatch);
-
-            // 分配分红到待领取
-            let mut count = 0u32;
-            for (holder, amount) in recipients.iter() {
-                if !amount.is_zero() {
-                    PendingDividends::<T>::mutate(entity_id, holder, |pending| {
-                        *pending = pending.saturating_add(*amount);
-                    });
-                    count = count.saturating_add(1);
-                }
-            }
-
-            // 更新上次分红时间和累计金额
-            ShopTokenConfigs::<T>::mutate(entity_id, |maybe_config| {
-                if let Some(config) = maybe_config {
-                    config.dividend_config.last_distribution = now;
-                    // L5: 更新累计分红金额
-                    config.dividend_config.accumulated = config.dividend_config.accumulated.saturating_add(total_amount);
-                }
-            });
+            Self::ensure_shop_owner_or_governance(origin, entity_id)?;
 
-            Self::deposit_event(Event::DividendDistributed {
-                entity_id,
-                total_amount,
-                recipients_count: count,
-            });
-            Ok(())
+            Self::do_distribute_dividend(entity_id, total_amount, recipients)
         }
 
         /// 领取分红
@@ -883,6 +901,22 @@ pub mod pallet {
             let now = <frame_system::Pallet<T>>::block_number();
             let unlock_at = now.saturating_add(lock_duration);
 
+            // 锁仓起始区块：追加锁仓按金额加权平均，避免小额早锁、大额晚追加获得完整时长
+            let locked_since = match LockedSince::<T>::get(entity_id, &who) {
+                Some(since) if !existing_locked.is_zero() => {
+                    let old_amt: u128 = existing_locked.into();
+                    let new_amt: u128 = amount.into();
+                    let since_u: u128 = since.saturated_into();
+                    let now_u: u128 = now.saturated_into();
+                    let weighted = old_amt.saturating_mul(since_u)
+                        .saturating_add(new_amt.saturating_mul(now_u))
+                        / old_amt.saturating_add(new_amt).max(1);
+                    weighted.saturated_into()
+                },
+                _ => now,
+            };
+            LockedSince::<T>::insert(entity_id, &who, locked_since);
+
             // 更新锁仓记录（合并或创建）
             LockedTokens::<T>::mutate(entity_id, &who, |maybe_locked| {
                 match maybe_locked {
@@ -925,6 +959,7 @@ pub mod pallet {
 
             // 移除锁仓记录
             LockedTokens::<T>::remove(entity_id, &who);
+            LockedSince::<T>::remove(entity_id, &who);
 
             Self::deposit_event(Event::TokensUnlocked {
                 entity_id,
@@ -942,11 +977,8 @@ pub mod pallet {
             entity_id: u64,
             new_type: TokenType,
         ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
-
             // 验证所有者
-            let owner = T::ShopProvider::shop_owner(entity_id).ok_or(Error::<T>::ShopNotFound)?;
-            ensure!(owner == who, Error::<T>::NotShopOwner);
+            Self::ensure_shop_owner_or_governance(origin, entity_id)?;
 
             let old_type = ShopTokenConfigs::<T>::try_mutate(entity_id, |maybe_config| -> Result<TokenType, DispatchError> {
                 let config = maybe_config.as_mut().ok_or(Error::<T>::TokenNotEnabled)?;
@@ -978,11 +1010,8 @@ pub mod pallet {
             entity_id: u64,
             max_supply: T::AssetBalance,
         ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
-
             // 验证所有者
-            let owner = T::ShopProvider::shop_owner(entity_id).ok_or(Error::<T>::ShopNotFound)?;
-            ensure!(owner == who, Error::<T>::NotShopOwner);
+            Self::ensure_shop_owner_or_governance(origin, entity_id)?;
 
             ShopTokenConfigs::<T>::try_mutate(entity_id, |maybe_config| -> DispatchResult {
                 let config = maybe_config.as_mut().ok_or(Error::<T>::TokenNotEnabled)?;
@@ -1012,11 +1041,8 @@ pub mod pallet {
             mode: TransferRestrictionMode,
             min_receiver_kyc: u8,
         ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
-
             // 验证所有者
-            let owner = T::ShopProvider::shop_owner(entity_id).ok_or(Error::<T>::ShopNotFound)?;
-            ensure!(owner == who, Error::<T>::NotShopOwner);
+            Self::ensure_shop_owner_or_governance(origin, entity_id)?;
 
             let clamped_kyc = min_receiver_kyc.min(4);
             ShopTokenConfigs::<T>::try_mutate(entity_id, |maybe_config| -> DispatchResult {
@@ -1043,14 +1069,11 @@ pub mod pallet {
             entity_id: u64,
             accounts: Vec<T::AccountId>,
         ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
-
             // M7: 限制输入列表长度
             ensure!(accounts.len() <= T::MaxTransferListSize::get() as usize, Error::<T>::TransferListFull);
 
             // 验证所有者
-            let owner = T::ShopProvider::shop_owner(entity_id).ok_or(Error::<T>::ShopNotFound)?;
-            ensure!(owner == who, Error::<T>::NotShopOwner);
+            Self::ensure_shop_owner_or_governance(origin, entity_id)?;
 
             let mut added = 0u32;
             TransferWhitelist::<T>::try_mutate(entity_id, |list| -> DispatchResult {
@@ -1079,14 +1102,11 @@ pub mod pallet {
             entity_id: u64,
             accounts: Vec<T::AccountId>,
         ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
-
             // M7: 限制输入列表长度
             ensure!(accounts.len() <= T::MaxTransferListSize::get() as usize, Error::<T>::TransferListFull);
 
             // 验证所有者
-            let owner = T::ShopProvider::shop_owner(entity_id).ok_or(Error::<T>::ShopNotFound)?;
-            ensure!(owner == who, Error::<T>::NotShopOwner);
+            Self::ensure_shop_owner_or_governance(origin, entity_id)?;
 
             let mut removed = 0u32;
             TransferWhitelist::<T>::mutate(entity_id, |list| {
@@ -1114,14 +1134,11 @@ pub mod pallet {
             entity_id: u64,
             accounts: Vec<T::AccountId>,
         ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
-
             // M7: 限制输入列表长度
             ensure!(accounts.len() <= T::MaxTransferListSize::get() as usize, Error::<T>::TransferListFull);
 
             // 验证所有者
-            let owner = T::ShopProvider::shop_owner(entity_id).ok_or(Error::<T>::ShopNotFound)?;
-            ensure!(owner == who, Error::<T>::NotShopOwner);
+            Self::ensure_shop_owner_or_governance(origin, entity_id)?;
 
             let mut added = 0u32;
             TransferBlacklist::<T>::try_mutate(entity_id, |list| -> DispatchResult {
@@ -1150,14 +1167,11 @@ pub mod pallet {
             entity_id: u64,
             accounts: Vec<T::AccountId>,
         ) -> DispatchResult {
-            let who = ensure_signed(origin)?;
-
             // M7: 限制输入列表长度
             ensure!(accounts.len() <= T::MaxTransferListSize::get() as usize, Error::<T>::TransferListFull);
 
             // 验证所有者
-            let owner = T::ShopProvider::shop_owner(entity_id).ok_or(Error::<T>::ShopNotFound)?;
-            ensure!(owner == who, Error::<T>::NotShopOwner);
+            Self::ensure_shop_owner_or_governance(origin, entity_id)?;
 
             let mut removed = 0u32;
             TransferBlacklist::<T>::mutate(entity_id, |list| {
@@ -1176,6 +1190,81 @@ pub mod pallet {
             });
             Ok(())
         }
+
+        /// 铸造团队分配（分段锁仓）
+        ///
+        /// 代币铸造到受益人账户并全额预留，按档位（偏移相对铸造区块）
+        /// 经 `release_vested_tokens` 释放；门控档位需对应里程碑被确认。
+        #[pallet::call_index(16)]
+        #[pallet::weight(Weight::from_parts(200_000_000, 6_000))]
+        pub fn mint_vested_tokens(
+            origin: OriginFor<T>,
+            shop_id: u64,
+            beneficiary: T::AccountId,
+            amount: T::AssetBalance,
+            tranches: BoundedVec<VestingTranche<BlockNumberFor<T>>, T::MaxVestingTranches>,
+        ) -> DispatchResult {
+            Self::ensure_shop_owner_or_governance(origin, shop_id)?;
+
+            let config = ShopTokenConfigs::<T>::get(shop_id).ok_or(Error::<T>::TokenNotEnabled)?;
+            ensure!(config.enabled, Error::<T>::TokenNotEnabled);
+            ensure!(!amount.is_zero(), Error::<T>::ZeroAmount);
+            ensure!(validate_vesting_tranches(&tranches), Error::<T>::InvalidVestingTranches);
+            ensure!(
+                !TeamVesting::<T>::contains_key(shop_id, &beneficiary),
+                Error::<T>::TeamVestingExists
+            );
+            Self::ensure_within_max_supply(shop_id, &config, amount)?;
+
+            let asset_id = Self::shop_to_asset_id(shop_id);
+            T::Assets::mint_into(asset_id, &beneficiary, amount)?;
+            ReservedTokens::<T>::mutate(shop_id, &beneficiary, |r| *r = r.saturating_add(amount));
+
+            let count = tranches.len() as u32;
+            TeamVesting::<T>::insert(shop_id, &beneficiary, TeamVestingGrant {
+                total: amount,
+                released: Zero::zero(),
+                start: <frame_system::Pallet<T>>::block_number(),
+                tranches,
+            });
+
+            Self::deposit_event(Event::VestedTokensMinted {
+                entity_id: shop_id,
+                beneficiary,
+                amount,
+                tranches: count,
+            });
+            Ok(())
+        }
+
+        /// 释放团队锁仓（受益人调用，释放已满足档位的预留）
+        #[pallet::call_index(17)]
+        #[pallet::weight(Weight::from_parts(100_000_000, 4_000))]
+        pub fn release_vested_tokens(
+            origin: OriginFor<T>,
+            entity_id: u64,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let mut grant = TeamVesting::<T>::get(entity_id, &who).ok_or(Error::<T>::NoTeamVesting)?;
+            let releasable = Self::vested_of(entity_id, &grant).saturating_sub(grant.released);
+            ensure!(!releasable.is_zero(), Error::<T>::NothingToRelease);
+
+            ReservedTokens::<T>::mutate(entity_id, &who, |r| *r = r.saturating_sub(releasable));
+            grant.released = grant.released.saturating_add(releasable);
+            if grant.released >= grant.total {
+                TeamVesting::<T>::remove(entity_id, &who);
+            } else {
+                TeamVesting::<T>::insert(entity_id, &who, grant);
+            }
+
+            Self::deposit_event(Event::VestedTokensReleased {
+                entity_id,
+                beneficiary: who,
+                amount: releasable,
+            });
+            Ok(())
+        }
     }
 
     // ==================== 内部函数 ====================
@@ -1186,6 +1275,23 @@ pub mod pallet {
             (T::ShopTokenOffset::get() + shop_id).into()
         }
 
+        /// 团队锁仓按档位累计已解锁数量
+        fn vested_of(entity_id: u64, grant: &TeamVestingGrantOf<T>) -> T::AssetBalance {
+            let now = <frame_system::Pallet<T>>::block_number();
+            let bps = vested_tranche_bps(&grant.tranches, grant.start, now, |gate| {
+                T::MilestoneProvider::is_milestone_achieved(entity_id, gate)
+            });
+            let total: u128 = grant.total.into();
+            (total.saturating_mul(bps as u128) / 10000).into()
+        }
+
+        /// 查询团队锁仓当前可释放数量
+        pub fn releasable_vested(entity_id: u64, beneficiary: &T::AccountId) -> T::AssetBalance {
+            TeamVesting::<T>::get(entity_id, beneficiary)
+                .map(|grant| Self::vested_of(entity_id, &grant).saturating_sub(grant.released))
+                .unwrap_or_else(Zero::zero)
+        }
+
         /// Phase 8: 检查转账限制
         fn check_transfer_restriction(
             entity_id: u64,
@@ -1225,8 +1331,84 @@ pub mod pallet {
             }
         }
 
+        /// 分红分配到待领取（extrinsic 与治理调用共用）
+        pub(crate) fn do_distribute_dividend(
+            entity_id: u64,
+            total_amount: T::AssetBalance,
+            recipients: Vec<(T::AccountId, T::AssetBalance)>,
+        ) -> DispatchResult {
+            // H5: 限制接收人数量
+            ensure!(
+                recipients.len() <= T::MaxDividendRecipients::get() as usize,
+                Error::<T>::TooManyRecipients
+            );
+
+            // 检查分红配置
+            let config = ShopTokenConfigs::<T>::get(entity_id).ok_or(Error::<T>::TokenNotEnabled)?;
+            ensure!(config.dividend_config.enabled, Error::<T>::DividendNotEnabled);
+            // M6: 检查通证类型是否支持分红
+            ensure!(config.token_type.has_dividend_rights(), Error::<T>::TokenTypeNotSupported);
+
+            let now = <frame_system::Pallet<T>>::block_number();
+            let last = config.dividend_config.last_distribution;
+            let min_period = config.dividend_config.min_period;
+            
+            // 检查分红周期（首次分红跳过检查）
+            if !last.is_zero() {
+                ensure!(now >= last + min_period, Error::<T>::DividendPeriodNotReached);
+            }
+
+            // H6: 校验 total_amount == sum(recipients)
+            let mut sum = T::AssetBalance::zero();
+            for (_, amount) in recipients.iter() {
+                sum = sum.saturating_add(*amount);
+            }
+            ensure!(sum == total_amount, Error::<T>::DividendAmountMismatch);
+
+            // 分配分红到待领取
+            let mut count = 0u32;
+            for (holder, amount) in recipients.iter() {
+                if !amount.is_zero() {
+                    PendingDividends::<T>::mutate(entity_id, holder, |pending| {
+                        *pending = pending.saturating_add(*amount);
+                    });
+                    count = count.saturating_add(1);
+                }
+            }
+
+            // 更新上次分红时间和累计金额
+            ShopTokenConfigs::<T>::mutate(entity_id, |maybe_config| {
+                if let Some(config) = maybe_config {
+                    config.dividend_config.last_distribution = now;
+                    // L5: 更新累计分红金额
+                    config.dividend_config.accumulated = config.dividend_config.accumulated.saturating_add(total_amount);
+                }
+            });
+
+            Self::deposit_event(Event::DividendDistributed {
+                entity_id,
+                total_amount,
+                recipients_count: count,
+            });
+            Ok(())
+        }
+
+        /// 店主签名，或店铺所属实体的治理来源
+        fn ensure_shop_owner_or_governance(origin: OriginFor<T>, shop_id: u64) -> DispatchResult {
+            let caller = ensure_admin_caller::<_, _, T::EntityGovernanceOrigin>(origin)?;
+            let owner = T::ShopProvider::shop_owner(shop_id).ok_or(Error::<T>::ShopNotFound)?;
+            match caller {
+                AdminCaller::Signed(who) => ensure!(owner == who, Error::<T>::NotShopOwner),
+                AdminCaller::Governance(entity_id) => ensure!(
+                    T::ShopProvider::shop_entity_id(shop_id) == Some(entity_id),
+                    Error::<T>::NotShopOwner
+                ),
+            }
+            Ok(())
+        }
+
         /// 检查铸造是否在 max_supply 范围内
-        fn ensure_within_max_supply(
+        pub(crate) fn ensure_within_max_supply(
             entity_id: u64,
             config: &ShopTokenConfigOf<T>,
             mint_amount: T::AssetBalance,
@@ -1487,4 +1669,105 @@ impl<T: Config> EntityTokenProvider<T::AccountId, T::AssetBalance> for Pallet<T>
     fn total_supply(entity_id: u64) -> T::AssetBalance {
         Pallet::<T>::get_total_supply(entity_id)
     }
+
+    fn lock_info(entity_id: u64, holder: &T::AccountId) -> Option<(T::AssetBalance, u64, u64)> {
+        use sp_runtime::SaturatedConversion;
+        let (amount, unlock_at) = pallet::LockedTokens::<T>::get(entity_id, holder)?;
+        let locked_since = pallet::LockedSince::<T>::get(entity_id, holder)
+            .unwrap_or(unlock_at);
+        Some((amount, locked_since.saturated_into(), unlock_at.saturated_into()))
+    }
+
+    // ==================== 治理调用接口 ====================
+
+    fn governance_mint(
+        entity_id: u64,
+        to: &T::AccountId,
+        amount: T::AssetBalance,
+    ) -> Result<(), sp_runtime::DispatchError> {
+        use frame_support::traits::fungibles::Mutate;
+        let config = ShopTokenConfigs::<T>::get(entity_id).ok_or(pallet::Error::<T>::TokenNotEnabled)?;
+        frame_support::ensure!(config.enabled, pallet::Error::<T>::TokenNotEnabled);
+        Pallet::<T>::ensure_within_max_supply(entity_id, &config, amount)?;
+
+        let asset_id = Pallet::<T>::shop_to_asset_id(entity_id);
+        T::Assets::mint_into(asset_id, to, amount)?;
+
+        Pallet::<T>::deposit_event(pallet::Event::TokensMinted {
+            shop_id: entity_id,
+            to: to.clone(),
+            amount,
+        });
+        Ok(())
+    }
+
+    fn governance_burn(
+        entity_id: u64,
+        from: &T::AccountId,
+        amount: T::AssetBalance,
+    ) -> Result<(), sp_runtime::DispatchError> {
+        use frame_support::traits::fungibles::{Inspect, Mutate};
+        frame_support::ensure!(
+            ShopTokenConfigs::<T>::contains_key(entity_id),
+            pallet::Error::<T>::TokenNotEnabled
+        );
+
+        // 只能销毁未锁仓、未预留的部分
+        let asset_id = Pallet::<T>::shop_to_asset_id(entity_id);
+        let balance = T::Assets::balance(asset_id, from);
+        let locked = pallet::LockedTokens::<T>::get(entity_id, from)
+            .map(|(amt, _)| amt)
+            .unwrap_or_default();
+        let reserved = pallet::ReservedTokens::<T>::get(entity_id, from);
+        let available = balance.saturating_sub(locked).saturating_sub(reserved);
+        frame_support::ensure!(available >= amount, pallet::Error::<T>::InsufficientBalance);
+
+        T::Assets::burn_from(
+            asset_id,
+            from,
+            amount,
+            frame_support::traits::tokens::Preservation::Expendable,
+            frame_support::traits::tokens::Precision::Exact,
+            frame_support::traits::tokens::Fortitude::Polite,
+        )?;
+
+        Pallet::<T>::deposit_event(pallet::Event::TokensBurned {
+            shop_id: entity_id,
+            from: from.clone(),
+            amount,
+        });
+        Ok(())
+    }
+
+    fn governance_set_rates(
+        entity_id: u64,
+        reward_rate: Option<u16>,
+        exchange_rate: Option<u16>,
+    ) -> Result<(), sp_runtime::DispatchError> {
+        ShopTokenConfigs::<T>::try_mutate(entity_id, |maybe_config| -> sp_runtime::DispatchResult {
+            let config = maybe_config.as_mut().ok_or(pallet::Error::<T>::TokenNotEnabled)?;
+            if let Some(rate) = reward_rate {
+                frame_support::ensure!(rate <= 10000, pallet::Error::<T>::InvalidRewardRate);
+                config.reward_rate = rate;
+            }
+            if let Some(rate) = exchange_rate {
+                frame_support::ensure!(rate <= 10000, pallet::Error::<T>::InvalidExchangeRate);
+                config.exchange_rate = rate;
+            }
+            Ok(())
+        })?;
+
+        Pallet::<T>::deposit_event(pallet::Event::TokenConfigUpdated { shop_id: entity_id });
+        Ok(())
+    }
+
+    fn governance_distribute_dividend(
+        entity_id: u64,
+        recipients: alloc::vec::Vec<(T::AccountId, T::AssetBalance)>,
+    ) -> Result<(), sp_runtime::DispatchError> {
+        let total_amount = recipients
+            .iter()
+            .fold(T::AssetBalance::default(), |acc, (_, amount)| acc.saturating_add(*amount));
+        Pallet::<T>::do_distribute_dividend(entity_id, total_amount, recipients)
+    }
 }
@@ -126,6 +126,8 @@ thread_local! {
     static KYC_LEVELS: RefCell<HashMap<u64, u8>> = RefCell::new(HashMap::new());
     /// (entity_id, account) -> is_member
     static MEMBERS: RefCell<HashMap<(u64, u64), bool>> = RefCell::new(HashMap::new());
+    /// (entity_id, milestone_id) -> 已确认
+    static MILESTONES: RefCell<HashMap<(u64, u32), bool>> = RefCell::new(HashMap::new());
 }
 
 pub struct MockShopProvider;
@@ -212,6 +214,40 @@ impl pallet_entity_token::Config for Test {
     type MaxDividendRecipients = ConstU32<50>;
     type KycProvider = MockKycProvider;
     type MemberProvider = MockMemberProvider;
+    type EntityGovernanceOrigin = MockEntityGovernance;
+    type MilestoneProvider = MockMilestoneProvider;
+    type MaxVestingTranches = ConstU32<10>;
+}
+
+pub struct MockMilestoneProvider;
+impl pallet_entity_common::MilestoneProvider for MockMilestoneProvider {
+    fn is_milestone_achieved(entity_id: u64, gate: &pallet_entity_common::MilestoneGate) -> bool {
+        MILESTONES.with(|m| m.borrow().get(&(entity_id, gate.id)).copied().unwrap_or(false))
+    }
+    fn mark_milestone_achieved(
+        entity_id: u64,
+        milestone_id: u32,
+        _source: pallet_entity_common::MilestoneSource,
+    ) -> Result<(), sp_runtime::DispatchError> {
+        MILESTONES.with(|m| m.borrow_mut().insert((entity_id, milestone_id), true));
+        Ok(())
+    }
+}
+
+/// 模拟实体治理来源：账户 GOV_ENTITY_1 / GOV_ENTITY_2 签名分别视为实体 1 / 2 的治理来源
+pub const GOV_ENTITY_1: u64 = 900;
+pub const GOV_ENTITY_2: u64 = 901;
+
+pub struct MockEntityGovernance;
+impl frame_support::traits::EnsureOrigin<RuntimeOrigin> for MockEntityGovernance {
+    type Success = u64;
+    fn try_origin(o: RuntimeOrigin) -> Result<u64, RuntimeOrigin> {
+        match o.clone().into() {
+            Ok(frame_system::RawOrigin::Signed(GOV_ENTITY_1)) => Ok(1),
+            Ok(frame_system::RawOrigin::Signed(GOV_ENTITY_2)) => Ok(2),
+            _ => Err(o),
+        }
+    }
 }
 
 // ==================== 工具函数 ====================
@@ -236,6 +272,11 @@ pub fn set_member(entity_id: u64, who: u64, is_member: bool) {
     MEMBERS.with(|m| m.borrow_mut().insert((entity_id, who), is_member));
 }
 
+/// 确认里程碑
+pub fn achieve_milestone(entity_id: u64, milestone_id: u32) {
+    MILESTONES.with(|m| m.borrow_mut().insert((entity_id, milestone_id), true));
+}
+
 // ==================== Test Externalities Builder ====================
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
@@ -194,6 +194,31 @@ fn update_config_min_gt_max_rejected() {
     });
 }
 
+#[test]
+fn entity_governance_origin_can_administer_token() {
+    new_test_ext().execute_with(|| {
+        setup_token();
+        // 实体 1 的治理来源可代替店主调用管理类 extrinsic
+        assert_ok!(EntityToken::update_token_config(
+            RuntimeOrigin::signed(GOV_ENTITY_1), SHOP_ID,
+            Some(900), None, None, None, None, None,
+        ));
+        assert_eq!(EntityToken::shop_token_configs(SHOP_ID).unwrap().reward_rate, 900);
+        assert_ok!(EntityToken::mint_tokens(RuntimeOrigin::signed(GOV_ENTITY_1), SHOP_ID, USER_A, 100));
+        assert_eq!(EntityToken::get_balance(SHOP_ID, &USER_A), 100);
+
+        // 其他实体的治理来源无权操作
+        assert_noop!(
+            EntityToken::mint_tokens(RuntimeOrigin::signed(GOV_ENTITY_2), SHOP_ID, USER_A, 100),
+            Error::<Test>::NotShopOwner
+        );
+        assert_noop!(
+            EntityToken::mint_tokens(RuntimeOrigin::none(), SHOP_ID, USER_A, 100),
+            sp_runtime::DispatchError::BadOrigin
+        );
+    });
+}
+
 // ==================== mint_tokens ====================
 
 #[test]
@@ -495,6 +520,41 @@ fn lock_and_unlock_works() {
     });
 }
 
+#[test]
+fn lock_tokens_tracks_weighted_locked_since() {
+    new_test_ext().execute_with(|| {
+        use pallet_entity_common::EntityTokenProvider;
+        setup_token();
+        assert_ok!(EntityToken::mint_tokens(
+            RuntimeOrigin::signed(OWNER), SHOP_ID, USER_A, 1000,
+        ));
+        assert_ok!(EntityToken::lock_tokens(
+            RuntimeOrigin::signed(USER_A), SHOP_ID, 500, 100,
+        ));
+        assert_eq!(EntityToken::locked_since(SHOP_ID, USER_A), Some(1));
+
+        // 追加锁仓：起始区块按金额加权 (500×1 + 500×11) / 1000 = 6
+        run_to_block(11);
+        assert_ok!(EntityToken::lock_tokens(
+            RuntimeOrigin::signed(USER_A), SHOP_ID, 500, 10,
+        ));
+        assert_eq!(EntityToken::locked_since(SHOP_ID, USER_A), Some(6));
+        assert_eq!(
+            <EntityToken as EntityTokenProvider<u64, u128>>::lock_info(SHOP_ID, &USER_A),
+            Some((1000, 6, 101))
+        );
+
+        // 解锁后清理
+        run_to_block(101);
+        assert_ok!(EntityToken::unlock_tokens(RuntimeOrigin::signed(USER_A), SHOP_ID));
+        assert_eq!(EntityToken::locked_since(SHOP_ID, USER_A), None);
+        assert_eq!(
+            <EntityToken as EntityTokenProvider<u64, u128>>::lock_info(SHOP_ID, &USER_A),
+            None
+        );
+    });
+}
+
 #[test]
 fn lock_tokens_fails_zero_amount() {
     new_test_ext().execute_with(|| {
@@ -844,3 +904,136 @@ fn whitelist_input_length_limited() {
         );
     });
 }
+
+// ==================== governance hooks ====================
+
+#[test]
+fn governance_token_hooks_work() {
+    new_test_ext().execute_with(|| {
+        use pallet_entity_common::EntityTokenProvider;
+        setup_token();
+
+        assert_ok!(<EntityToken as EntityTokenProvider<u64, u128>>::governance_mint(SHOP_ID, &USER_A, 1000));
+        assert_eq!(EntityToken::get_balance(SHOP_ID, &USER_A), 1000);
+
+        // 锁仓部分不可销毁
+        assert_ok!(EntityToken::lock_tokens(RuntimeOrigin::signed(USER_A), SHOP_ID, 800, 100));
+        assert_noop!(
+            <EntityToken as EntityTokenProvider<u64, u128>>::governance_burn(SHOP_ID, &USER_A, 300),
+            Error::<Test>::InsufficientBalance
+        );
+        assert_ok!(<EntityToken as EntityTokenProvider<u64, u128>>::governance_burn(SHOP_ID, &USER_A, 200));
+        assert_eq!(EntityToken::get_balance(SHOP_ID, &USER_A), 800);
+
+        assert_ok!(<EntityToken as EntityTokenProvider<u64, u128>>::governance_set_rates(SHOP_ID, Some(700), None));
+        let config = EntityToken::shop_token_configs(SHOP_ID).unwrap();
+        assert_eq!(config.reward_rate, 700);
+        assert_eq!(config.exchange_rate, 1000);
+        assert_noop!(
+            <EntityToken as EntityTokenProvider<u64, u128>>::governance_set_rates(SHOP_ID, None, Some(10001)),
+            Error::<Test>::InvalidExchangeRate
+        );
+
+        assert_ok!(EntityToken::change_token_type(
+            RuntimeOrigin::signed(OWNER), SHOP_ID, TokenType::Equity,
+        ));
+        assert_ok!(EntityToken::configure_dividend(
+            RuntimeOrigin::signed(OWNER), SHOP_ID, true, 5,
+        ));
+        assert_ok!(<EntityToken as EntityTokenProvider<u64, u128>>::governance_distribute_dividend(
+            SHOP_ID,
+            vec![(USER_A, 40), (USER_B, 60)],
+        ));
+        assert_eq!(EntityToken::pending_dividends(SHOP_ID, &USER_B), 60);
+        assert_eq!(EntityToken::shop_token_configs(SHOP_ID).unwrap().dividend_config.accumulated, 100);
+    });
+}
+
+// ==================== team vesting ====================
+
+fn team_tranches(
+    items: Vec<(u64, u16, Option<u32>)>,
+) -> frame_support::BoundedVec<pallet_entity_common::VestingTranche<u64>, frame_support::traits::ConstU32<10>> {
+    items
+        .into_iter()
+        .map(|(offset, bps, milestone)| pallet_entity_common::VestingTranche {
+            offset,
+            bps,
+            milestone: milestone.map(|id| pallet_entity_common::MilestoneGate {
+                id,
+                source: pallet_entity_common::MilestoneSource::Governance,
+            }),
+        })
+        .collect::<Vec<_>>()
+        .try_into()
+        .unwrap()
+}
+
+#[test]
+fn mint_vested_tokens_reserves_and_releases_by_tranche() {
+    new_test_ext().execute_with(|| {
+        setup_token();
+        System::set_block_number(1);
+        // 起点 1：+10 区块 40%，里程碑 3 确认后 60%
+        assert_ok!(EntityToken::mint_vested_tokens(
+            RuntimeOrigin::signed(OWNER), SHOP_ID, USER_A, 1_000,
+            team_tranches(vec![(10, 4000, None), (10, 6000, Some(3))]),
+        ));
+        assert_eq!(EntityToken::get_balance(SHOP_ID, &USER_A), 1_000);
+        assert_eq!(EntityToken::reserved_tokens(SHOP_ID, USER_A), 1_000);
+
+        // 预留部分不可转让
+        assert_noop!(
+            EntityToken::transfer_tokens(RuntimeOrigin::signed(USER_A), SHOP_ID, USER_B, 1),
+            Error::<Test>::InsufficientBalance
+        );
+        assert_noop!(
+            EntityToken::release_vested_tokens(RuntimeOrigin::signed(USER_A), SHOP_ID),
+            Error::<Test>::NothingToRelease
+        );
+
+        System::set_block_number(11);
+        assert_eq!(EntityToken::releasable_vested(SHOP_ID, &USER_A), 400);
+        assert_ok!(EntityToken::release_vested_tokens(RuntimeOrigin::signed(USER_A), SHOP_ID));
+        assert_eq!(EntityToken::reserved_tokens(SHOP_ID, USER_A), 600);
+        assert_ok!(EntityToken::transfer_tokens(RuntimeOrigin::signed(USER_A), SHOP_ID, USER_B, 400));
+
+        // 里程碑确认后释放剩余并清除记录
+        achieve_milestone(SHOP_ID, 3);
+        assert_ok!(EntityToken::release_vested_tokens(RuntimeOrigin::signed(USER_A), SHOP_ID));
+        assert_eq!(EntityToken::reserved_tokens(SHOP_ID, USER_A), 0);
+        assert!(EntityToken::team_vesting(SHOP_ID, USER_A).is_none());
+    });
+}
+
+#[test]
+fn mint_vested_tokens_rejects_invalid_input() {
+    new_test_ext().execute_with(|| {
+        setup_token();
+        assert_noop!(
+            EntityToken::mint_vested_tokens(
+                RuntimeOrigin::signed(USER_A), SHOP_ID, USER_A, 1_000,
+                team_tranches(vec![(0, 10000, None)]),
+            ),
+            Error::<Test>::NotShopOwner
+        );
+        assert_noop!(
+            EntityToken::mint_vested_tokens(
+                RuntimeOrigin::signed(OWNER), SHOP_ID, USER_A, 1_000,
+                team_tranches(vec![(0, 6000, None), (0, 3000, None)]),
+            ),
+            Error::<Test>::InvalidVestingTranches
+        );
+        assert_ok!(EntityToken::mint_vested_tokens(
+            RuntimeOrigin::signed(GOV_ENTITY_1), SHOP_ID, USER_A, 1_000,
+            team_tranches(vec![(0, 10000, None)]),
+        ));
+        assert_noop!(
+            EntityToken::mint_vested_tokens(
+                RuntimeOrigin::signed(OWNER), SHOP_ID, USER_A, 1_000,
+                team_tranches(vec![(0, 10000, None)]),
+            ),
+            Error::<Test>::TeamVestingExists
+        );
+    });
+}
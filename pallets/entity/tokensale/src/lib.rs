@@ -3,9 +3,9 @@
 //! ## 概述
 //!
 //! 本模块实现实体代币公开发售功能：
-//! - 多种发售模式（固定价格、荷兰拍卖、白名单分配）
+//! - 多种发售模式（固定价格、荷兰拍卖、白名单分配、抽签发售）
 //! - 多轮发售支持
-//! - 代币锁仓和线性解锁
+//! - 代币锁仓和线性解锁，自定义分段解锁（可选治理/披露里程碑门控）
 //! - 多资产支付支持（当前仅支持原生 NXS）
 //! - KYC 集成
 //! - 实际资金转账（认购扣款、代币发放、退款）
@@ -17,6 +17,7 @@
 //! - claim_tokens / unlock_tokens: Entity 代币 → 认购者
 //! - end_sale: 释放未售代币
 //! - cancel_sale + claim_refund: NXS 退还认购者 + 释放代币
+//! - Lottery: end_sale 生成随机种子 → draw_lottery 分页抽签 → 未中签者 claim_refund
 //! - withdraw_funds: NXS → Entity 派生账户
 //!
 //! ## 版本历史
@@ -59,9 +60,12 @@ pub mod pallet {
         BoundedVec, PalletId,
     };
     use frame_system::pallet_prelude::*;
-    use pallet_entity_common::{EntityProvider, EntityTokenProvider};
+    use pallet_entity_common::{
+        validate_vesting_tranches, vested_tranche_bps, EntityProvider, EntityTokenProvider,
+        MilestoneGate, MilestoneSource, VestingTranche,
+    };
     use sp_runtime::{
-        traits::{AccountIdConversion, Saturating, Zero},
+        traits::{AccountIdConversion, Hash as HashT, One, Saturating, Zero},
         SaturatedConversion,
     };
 
@@ -124,7 +128,7 @@ pub mod pallet {
         Linear,
         /// 阶梯解锁
         Cliff,
-        /// 自定义解锁
+        /// 自定义分段解锁（档位见 `CustomVestingSchedules`）
         Custom,
     }
 
@@ -236,6 +240,30 @@ pub mod pallet {
         pub refunded: bool,
     }
 
+    /// 抽签状态（end_sale 时锁定种子区块，首次 draw_lottery 确定种子并分页推进）
+    #[derive(Encode, Decode, codec::DecodeWithMemTracking, Clone, PartialEq, Eq, TypeInfo, MaxEncodedLen, RuntimeDebug)]
+    pub struct LotteryDraw<Hash, BlockNumber> {
+        /// 随机种子（种子区块产出后确定，链上公开，任何人可据此复现抽签顺序）
+        pub seed: Option<Hash>,
+        /// 种子区块（end_sale 时固定为 `当前区块 + max(LotterySeedDelay, 1)`）
+        pub seed_block: BlockNumber,
+        /// 参与人数
+        pub total: u32,
+        /// 已抽取人数
+        pub cursor: u32,
+        /// 中签人数
+        pub winners: u32,
+    }
+
+    /// 抽签种子：种子区块产出后由下一区块的 `on_initialize` 记录其哈希
+    #[derive(Encode, Decode, codec::DecodeWithMemTracking, Clone, PartialEq, Eq, TypeInfo, MaxEncodedLen, RuntimeDebug, Default)]
+    pub struct LotterySeed<Hash> {
+        /// 种子区块哈希（未产出时为 None）
+        pub hash: Option<Hash>,
+        /// 以该区块为种子、尚未确定种子的轮次数，归零时删除
+        pub pending: u32,
+    }
+
     /// 认购记录类型别名
     pub type SubscriptionOf<T> = Subscription<
         <T as frame_system::Config>::AccountId,
@@ -287,6 +315,21 @@ pub mod pallet {
         /// 最大认购人数（每轮次）
         #[pallet::constant]
         type MaxSubscriptionsPerRound: Get<u32>;
+
+        /// 单次 draw_lottery 最多抽取人数
+        #[pallet::constant]
+        type MaxLotteryDrawsPerCall: Get<u32>;
+
+        /// 抽签种子区块相对 end_sale 的延迟（种子取自该区块哈希，结束发售时尚不可知）
+        #[pallet::constant]
+        type LotterySeedDelay: Get<BlockNumberFor<Self>>;
+
+        /// 自定义分段解锁最大档位数
+        #[pallet::constant]
+        type MaxVestingTranches: Get<u32>;
+
+        /// 实体治理来源（`EntityOrigin::Governance`，成功返回 entity_id）
+        type EntityGovernanceOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = u64>;
     }
 
     #[pallet::pallet]
@@ -378,6 +421,63 @@ pub mod pallet {
         ValueQuery,
     >;
 
+    /// 抽签状态 round_id -> LotteryDraw
+    #[pallet::storage]
+    pub type LotteryDraws<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        u64,  // round_id
+        LotteryDraw<T::Hash, BlockNumberFor<T>>,
+    >;
+
+    /// 抽签种子 seed_block -> LotterySeed
+    #[pallet::storage]
+    pub type LotterySeeds<T: Config> =
+        StorageMap<_, Twox64Concat, BlockNumberFor<T>, LotterySeed<T::Hash>, OptionQuery>;
+
+    /// 抽签队列（参与者副本，抽签过程中逐步洗牌，完成后删除）
+    #[pallet::storage]
+    pub type LotteryQueue<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        u64,  // round_id
+        BoundedVec<T::AccountId, T::MaxSubscriptionsPerRound>,
+        ValueQuery,
+    >;
+
+    /// 中签记录 (round_id, subscriber) -> bool
+    #[pallet::storage]
+    pub type LotteryWinners<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        u64,  // round_id
+        Blake2_128Concat,
+        T::AccountId,
+        bool,
+        ValueQuery,
+    >;
+
+    /// 自定义分段解锁档位 round_id -> tranches（偏移相对发售结束区块 end_block）
+    #[pallet::storage]
+    pub type CustomVestingSchedules<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        u64,  // round_id
+        BoundedVec<VestingTranche<BlockNumberFor<T>>, T::MaxVestingTranches>,
+        ValueQuery,
+    >;
+
+    /// 已确认里程碑 (entity_id, milestone_id) -> (确认来源, 确认区块)
+    #[pallet::storage]
+    pub type Milestones<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        u64,  // entity_id
+        Blake2_128Concat,
+        u32,  // milestone_id
+        (MilestoneSource, BlockNumberFor<T>),
+    >;
+
     // ==================== 事件 ====================
 
     #[pallet::event]
@@ -449,6 +549,40 @@ pub mod pallet {
             subscriber: T::AccountId,
             amount: BalanceOf<T>,
         },
+        /// 抽签已开始（种子区块已锁定）
+        LotteryDrawStarted {
+            round_id: u64,
+            seed_block: BlockNumberFor<T>,
+            participants: u32,
+        },
+        /// 抽签种子已确定
+        LotterySeedFixed {
+            round_id: u64,
+            seed: T::Hash,
+        },
+        /// 单个认购者抽签结果
+        LotteryDrawn {
+            round_id: u64,
+            subscriber: T::AccountId,
+            won: bool,
+        },
+        /// 抽签完成（未分配代币已释放）
+        LotteryCompleted {
+            round_id: u64,
+            winners: u32,
+            sold_amount: BalanceOf<T>,
+        },
+        /// 自定义分段解锁已设置
+        CustomVestingSet {
+            round_id: u64,
+            tranches: u32,
+        },
+        /// 里程碑已确认
+        MilestoneAchieved {
+            entity_id: u64,
+            milestone_id: u32,
+            source: MilestoneSource,
+        },
     }
 
     // ==================== 错误 ====================
@@ -533,10 +667,36 @@ pub mod pallet {
         AlreadyRefunded,
         /// 购买量限额配置无效（max 须 >= min）
         InvalidPurchaseLimits,
+        /// 未中签（抽签轮次）
+        NotLotteryWinner,
+        /// 抽签人数无效（须 1..=MaxLotteryDrawsPerCall）
+        InvalidDrawCount,
+        /// 抽签种子区块尚未产出
+        LotterySeedNotReady,
+        /// 分段解锁档位无效（非空、每档 bps > 0、合计 10000、偏移非递减）
+        InvalidVestingTranches,
+        /// 里程碑已确认
+        MilestoneAlreadyAchieved,
     }
 
     // ==================== Extrinsics ====================
 
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        /// 记录上一区块（若为抽签种子区块）的哈希，供 `draw_lottery` 使用
+        fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+            let seed_block = now.saturating_sub(One::one());
+            match LotterySeeds::<T>::get(seed_block) {
+                Some(mut seed) if seed.hash.is_none() => {
+                    seed.hash = Some(frame_system::Pallet::<T>::block_hash(seed_block));
+                    LotterySeeds::<T>::insert(seed_block, seed);
+                    T::DbWeight::get().reads_writes(2, 1)
+                },
+                _ => T::DbWeight::get().reads(1),
+            }
+        }
+    }
+
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         /// 创建发售轮次（需 Entity owner/admin 权限）
@@ -670,6 +830,9 @@ pub mod pallet {
                 ensure!(initial_unlock_bps <= 10000, Error::<T>::InvalidVestingConfig);
                 // H7: total_duration >= cliff_duration
                 ensure!(total_duration >= cliff_duration, Error::<T>::InvalidVestingDuration);
+                // Custom 档位须通过 set_custom_vesting 设置
+                ensure!(vesting_type != VestingType::Custom, Error::<T>::InvalidVestingConfig);
+                CustomVestingSchedules::<T>::remove(round_id);
 
                 round.vesting_config = VestingConfig {
                     vesting_type,
@@ -786,7 +949,12 @@ pub mod pallet {
 
             // 验证状态
             ensure!(round.status == RoundStatus::Active, Error::<T>::InvalidRoundStatus);
-            ensure!(round.remaining_amount >= amount, Error::<T>::SoldOut);
+            // 抽签轮次允许超额认购，仅要求单笔不超过总量
+            if round.mode == SaleMode::Lottery {
+                ensure!(round.total_supply >= amount, Error::<T>::SoldOut);
+            } else {
+                ensure!(round.remaining_amount >= amount, Error::<T>::SoldOut);
+            }
 
             // H9: 时间窗口校验
             let now = <frame_system::Pallet<T>>::block_number();
@@ -843,11 +1011,13 @@ pub mod pallet {
 
             Subscriptions::<T>::insert(round_id, &who, subscription);
 
-            // 更新轮次数据
+            // 更新轮次数据（抽签轮次的售出量在 draw_lottery 中按中签结果计入）
             SaleRounds::<T>::mutate(round_id, |maybe_round| {
                 if let Some(round) = maybe_round {
-                    round.sold_amount = round.sold_amount.saturating_add(amount);
-                    round.remaining_amount = round.remaining_amount.saturating_sub(amount);
+                    if round.mode != SaleMode::Lottery {
+                        round.sold_amount = round.sold_amount.saturating_add(amount);
+                        round.remaining_amount = round.remaining_amount.saturating_sub(amount);
+                    }
                     round.participants_count = round.participants_count.saturating_add(1);
                 }
             });
@@ -872,9 +1042,10 @@ pub mod pallet {
             Ok(())
         }
 
-        /// 结束发售（释放未售 Entity 代币）
+        /// 结束发售（释放未售 Entity 代币；抽签轮次锁定种子区块、复制参与者队列并进入 Settling）
         #[pallet::call_index(7)]
-        #[pallet::weight(Weight::from_parts(200_000_000, 10_000))]
+        #[pallet::weight(Weight::from_parts(200_000_000, 10_000)
+            .saturating_add(Pallet::<T>::lottery_queue_weight()))]
         pub fn end_sale(
             origin: OriginFor<T>,
             round_id: u64,
@@ -886,6 +1057,12 @@ pub mod pallet {
                 ensure!(round.creator == who, Error::<T>::Unauthorized);
                 ensure!(round.status == RoundStatus::Active, Error::<T>::InvalidRoundStatus);
 
+                if round.mode == SaleMode::Lottery && round.participants_count > 0 {
+                    Self::start_lottery_draw(round_id);
+                    round.status = RoundStatus::Settling;
+                    return Ok(());
+                }
+
                 // 释放未售 Entity 代币
                 if !round.remaining_amount.is_zero() {
                     let entity_account = T::EntityProvider::entity_account(round.entity_id);
@@ -921,9 +1098,17 @@ pub mod pallet {
             Subscriptions::<T>::try_mutate(round_id, &who, |maybe_sub| -> DispatchResult {
                 let sub = maybe_sub.as_mut().ok_or(Error::<T>::NotSubscribed)?;
                 ensure!(!sub.claimed, Error::<T>::AlreadyClaimed);
+                if round.mode == SaleMode::Lottery {
+                    ensure!(LotteryWinners::<T>::get(round_id, &who), Error::<T>::NotLotteryWinner);
+                }
 
-                // 计算初始解锁量
-                let initial_unlock = Self::calculate_initial_unlock(&round.vesting_config, sub.amount);
+                // 计算初始解锁量（Custom 按已满足的档位计算）
+                let initial_unlock = if round.vesting_config.vesting_type == VestingType::Custom {
+                    let now = <frame_system::Pallet<T>>::block_number();
+                    Self::calculate_custom_vested(&round, sub.amount, now)
+                } else {
+                    Self::calculate_initial_unlock(&round.vesting_config, sub.amount)
+                };
 
                 // H5: 实际转移 Entity 代币
                 if !initial_unlock.is_zero() {
@@ -966,13 +1151,18 @@ pub mod pallet {
                 let now = <frame_system::Pallet<T>>::block_number();
 
                 // 计算可解锁量
-                let unlockable = Self::calculate_unlockable(
-                    &round.vesting_config,
-                    sub.amount,
-                    sub.unlocked_amount,
-                    sub.subscribed_at,
-                    now,
-                )?;
+                let unlockable = if round.vesting_config.vesting_type == VestingType::Custom {
+                    Self::calculate_custom_vested(&round, sub.amount, now)
+                        .saturating_sub(sub.unlocked_amount)
+                } else {
+                    Self::calculate_unlockable(
+                        &round.vesting_config,
+                        sub.amount,
+                        sub.unlocked_amount,
+                        sub.subscribed_at,
+                        now,
+                    )?
+                };
 
                 ensure!(!unlockable.is_zero(), Error::<T>::NoTokensToUnlock);
 
@@ -1026,7 +1216,7 @@ pub mod pallet {
             })
         }
 
-        /// 认购者领取退款（仅 Cancelled 状态，释放对应 Entity 代币 + 退还 NXS）
+        /// 认购者领取退款（Cancelled 状态，或抽签轮次结束后的未中签者；释放对应 Entity 代币 + 退还 NXS）
         #[pallet::call_index(11)]
         #[pallet::weight(Weight::from_parts(250_000_000, 12_000))]
         pub fn claim_refund(
@@ -1036,15 +1226,20 @@ pub mod pallet {
             let who = ensure_signed(origin)?;
 
             let round = SaleRounds::<T>::get(round_id).ok_or(Error::<T>::RoundNotFound)?;
-            ensure!(round.status == RoundStatus::Cancelled, Error::<T>::SaleNotCancelled);
+            let lottery_loser = round.mode == SaleMode::Lottery
+                && round.status == RoundStatus::Ended
+                && !LotteryWinners::<T>::get(round_id, &who);
+            ensure!(round.status == RoundStatus::Cancelled || lottery_loser, Error::<T>::SaleNotCancelled);
 
             Subscriptions::<T>::try_mutate(round_id, &who, |maybe_sub| -> DispatchResult {
                 let sub = maybe_sub.as_mut().ok_or(Error::<T>::NotSubscribed)?;
                 ensure!(!sub.refunded, Error::<T>::AlreadyRefunded);
 
-                // 释放认购者对应的 Entity 代币锁定
-                let entity_account = T::EntityProvider::entity_account(round.entity_id);
-                T::TokenProvider::unreserve(round.entity_id, &entity_account, sub.amount);
+                // 释放认购者对应的 Entity 代币锁定（抽签轮次认购不占用额度，未售部分已整体释放）
+                if round.mode != SaleMode::Lottery {
+                    let entity_account = T::EntityProvider::entity_account(round.entity_id);
+                    T::TokenProvider::unreserve(round.entity_id, &entity_account, sub.amount);
+                }
 
                 // 退还 NXS
                 let pallet_account = Self::pallet_account();
@@ -1108,6 +1303,148 @@ pub mod pallet {
                 Ok(())
             })
         }
+
+        /// 抽签（任何人可调用，分页推进；全部抽完后释放未分配代币并结束轮次）
+        ///
+        /// 种子在 end_sale 时尚不可知：种子区块产出后由下一区块的 `on_initialize` 记录其哈希，
+        /// 首次调用以该哈希确定种子。
+        /// 抽签顺序由种子确定：第 i 步从 [i, n) 中取
+        /// `i + u64(hash(seed, i)[..8]) % (n - i)` 与第 i 位交换（Fisher-Yates），
+        /// 再按顺序整笔分配：认购量 ≤ 剩余量即中签，否则未中签。
+        ///
+        /// 洗牌队列每次调用整体读出并写回，权重按 `MaxSubscriptionsPerRound` 上界计入。
+        #[pallet::call_index(13)]
+        #[pallet::weight(Weight::from_parts(50_000_000, 4_000)
+            .saturating_add(Weight::from_parts(40_000_000, 3_000).saturating_mul(*max_draws as u64))
+            .saturating_add(Pallet::<T>::lottery_queue_weight()))]
+        pub fn draw_lottery(
+            origin: OriginFor<T>,
+            round_id: u64,
+            max_draws: u32,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+            ensure!(
+                max_draws > 0 && max_draws <= T::MaxLotteryDrawsPerCall::get(),
+                Error::<T>::InvalidDrawCount
+            );
+
+            let mut round = SaleRounds::<T>::get(round_id).ok_or(Error::<T>::RoundNotFound)?;
+            ensure!(
+                round.mode == SaleMode::Lottery && round.status == RoundStatus::Settling,
+                Error::<T>::InvalidRoundStatus
+            );
+            let mut draw = LotteryDraws::<T>::get(round_id).ok_or(Error::<T>::InvalidRoundStatus)?;
+            let seed = match draw.seed {
+                Some(seed) => seed,
+                None => {
+                    let anchor = LotterySeeds::<T>::get(draw.seed_block)
+                        .and_then(|seed| seed.hash)
+                        .ok_or(Error::<T>::LotterySeedNotReady)?;
+                    Self::release_lottery_seed(draw.seed_block);
+                    let seed = Self::lottery_seed(round_id, anchor);
+                    draw.seed = Some(seed);
+                    Self::deposit_event(Event::LotterySeedFixed { round_id, seed });
+                    seed
+                }
+            };
+            let mut queue = LotteryQueue::<T>::get(round_id).into_inner();
+
+            let end = draw.cursor.saturating_add(max_draws).min(draw.total);
+            while draw.cursor < end {
+                let i = draw.cursor;
+                let j = i.saturating_add(Self::lottery_offset(&seed, i, draw.total - i));
+                queue.swap(i as usize, j as usize);
+
+                let who = queue[i as usize].clone();
+                if let Some(sub) = Subscriptions::<T>::get(round_id, &who) {
+                    let won = sub.amount <= round.remaining_amount;
+                    if won {
+                        round.sold_amount = round.sold_amount.saturating_add(sub.amount);
+                        round.remaining_amount = round.remaining_amount.saturating_sub(sub.amount);
+                        LotteryWinners::<T>::insert(round_id, &who, true);
+                        draw.winners = draw.winners.saturating_add(1);
+                    } else {
+                        // 未中签者的认购款不计入募集资金，由其 claim_refund 取回
+                        RaisedFunds::<T>::mutate(round_id, sub.payment_asset, |funds| {
+                            *funds = funds.saturating_sub(sub.payment_amount);
+                        });
+                    }
+                    Self::deposit_event(Event::LotteryDrawn { round_id, subscriber: who, won });
+                }
+                draw.cursor = i.saturating_add(1);
+            }
+
+            if draw.cursor >= draw.total {
+                if !round.remaining_amount.is_zero() {
+                    let entity_account = T::EntityProvider::entity_account(round.entity_id);
+                    T::TokenProvider::unreserve(round.entity_id, &entity_account, round.remaining_amount);
+                }
+                round.status = RoundStatus::Ended;
+                LotteryQueue::<T>::remove(round_id);
+
+                Self::deposit_event(Event::LotteryCompleted {
+                    round_id,
+                    winners: draw.winners,
+                    sold_amount: round.sold_amount,
+                });
+                Self::deposit_event(Event::SaleRoundEnded {
+                    round_id,
+                    sold_amount: round.sold_amount,
+                    participants_count: round.participants_count,
+                });
+            } else {
+                LotteryQueue::<T>::insert(round_id, BoundedVec::truncate_from(queue));
+            }
+
+            LotteryDraws::<T>::insert(round_id, draw);
+            SaleRounds::<T>::insert(round_id, round);
+            Ok(())
+        }
+
+        /// 设置自定义分段解锁（仅 NotStarted 状态，锁仓类型置为 Custom）
+        ///
+        /// 档位偏移相对发售结束区块 `end_block`；带里程碑门控的档位需对应里程碑被确认后才可解锁。
+        #[pallet::call_index(14)]
+        #[pallet::weight(Weight::from_parts(120_000_000, 6_000))]
+        pub fn set_custom_vesting(
+            origin: OriginFor<T>,
+            round_id: u64,
+            tranches: BoundedVec<VestingTranche<BlockNumberFor<T>>, T::MaxVestingTranches>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(validate_vesting_tranches(&tranches), Error::<T>::InvalidVestingTranches);
+
+            SaleRounds::<T>::try_mutate(round_id, |maybe_round| -> DispatchResult {
+                let round = maybe_round.as_mut().ok_or(Error::<T>::RoundNotFound)?;
+                ensure!(round.creator == who, Error::<T>::Unauthorized);
+                ensure!(round.status == RoundStatus::NotStarted, Error::<T>::InvalidRoundStatus);
+
+                round.vesting_config = VestingConfig {
+                    vesting_type: VestingType::Custom,
+                    ..Default::default()
+                };
+                Ok(())
+            })?;
+
+            let count = tranches.len() as u32;
+            CustomVestingSchedules::<T>::insert(round_id, tranches);
+            Self::deposit_event(Event::CustomVestingSet { round_id, tranches: count });
+            Ok(())
+        }
+
+        /// 确认里程碑（仅实体治理来源，即治理提案通过后派发）
+        #[pallet::call_index(15)]
+        #[pallet::weight(Weight::from_parts(50_000_000, 3_000))]
+        pub fn mark_milestone_achieved(
+            origin: OriginFor<T>,
+            entity_id: u64,
+            milestone_id: u32,
+        ) -> DispatchResult {
+            let governed = T::EntityGovernanceOrigin::ensure_origin(origin)?;
+            ensure!(governed == entity_id, Error::<T>::Unauthorized);
+
+            Self::do_mark_milestone(entity_id, milestone_id, MilestoneSource::Governance)
+        }
     }
 
     // ==================== 辅助函数 ====================
@@ -1118,6 +1455,78 @@ pub mod pallet {
             SALE_PALLET_ID.into_account_truncating()
         }
 
+        /// 锁定抽签种子区块并复制参与者队列
+        fn start_lottery_draw(round_id: u64) {
+            let seed_block = Self::lock_lottery_seed();
+            let queue = RoundParticipants::<T>::get(round_id);
+            let total = queue.len() as u32;
+
+            LotteryQueue::<T>::insert(round_id, queue);
+            LotteryDraws::<T>::insert(round_id, LotteryDraw {
+                seed: None,
+                seed_block,
+                total,
+                cursor: 0,
+                winners: 0,
+            });
+
+            Self::deposit_event(Event::LotteryDrawStarted { round_id, seed_block, participants: total });
+        }
+
+        /// 锁定 `now + LotterySeedDelay`（至少下一区块）为种子区块并登记引用
+        fn lock_lottery_seed() -> BlockNumberFor<T> {
+            let now = frame_system::Pallet::<T>::block_number();
+            let seed_block = now.saturating_add(T::LotterySeedDelay::get().max(One::one()));
+            LotterySeeds::<T>::mutate(seed_block, |seed| {
+                let seed = seed.get_or_insert_with(Default::default);
+                seed.pending = seed.pending.saturating_add(1);
+            });
+            seed_block
+        }
+
+        /// 释放一次种子引用，无待确定种子的轮次时删除
+        fn release_lottery_seed(seed_block: BlockNumberFor<T>) {
+            LotterySeeds::<T>::mutate_exists(seed_block, |maybe_seed| {
+                if let Some(seed) = maybe_seed {
+                    seed.pending = seed.pending.saturating_sub(1);
+                    if seed.pending == 0 {
+                        *maybe_seed = None;
+                    }
+                }
+            });
+        }
+
+        /// 确定抽签种子
+        ///
+        /// 种子 = `hash_of(((b"et/sale/lottery", round_id), block_hash(seed_block)))`。
+        /// 种子区块在 end_sale 时固定，其哈希在结束发售时不可预知，由下一区块的 `on_initialize`
+        /// 记录后不再随调用时机变化，创建者与调用者均无法挑选种子；仅种子区块出块者可通过放弃出块影响结果。
+        fn lottery_seed(round_id: u64, anchor: T::Hash) -> T::Hash {
+            T::Hashing::hash_of(&((b"et/sale/lottery", round_id), anchor))
+        }
+
+        /// 抽签队列整体读写的权重（按 `MaxSubscriptionsPerRound` 上界）
+        pub fn lottery_queue_weight() -> Weight {
+            let max = T::MaxSubscriptionsPerRound::get() as u64;
+            T::DbWeight::get()
+                .reads_writes(1, 1)
+                .saturating_add(Weight::from_parts(
+                    1_000_000u64.saturating_mul(max),
+                    (T::AccountId::max_encoded_len() as u64).saturating_mul(max),
+                ))
+        }
+
+        /// 抽签第 step 步的随机偏移（[0, range)）
+        pub fn lottery_offset(seed: &T::Hash, step: u32, range: u32) -> u32 {
+            if range <= 1 {
+                return 0;
+            }
+            let hash = T::Hashing::hash_of(&(seed, step));
+            let mut bytes = [0u8; 8];
+            bytes.copy_from_slice(&hash.as_ref()[..8]);
+            (u64::from_le_bytes(bytes) % range as u64) as u32
+        }
+
         /// 计算支付金额（H10: 使用 checked_mul 防止溢出）
         fn calculate_payment_amount(
             round: &SaleRoundOf<T>,
@@ -1182,6 +1591,44 @@ pub mod pallet {
             initial.saturated_into()
         }
 
+        /// 计算自定义分段解锁的累计解锁量（起点为发售结束区块 end_block）
+        pub(crate) fn calculate_custom_vested(
+            round: &SaleRoundOf<T>,
+            total: BalanceOf<T>,
+            now: BlockNumberFor<T>,
+        ) -> BalanceOf<T> {
+            let tranches = CustomVestingSchedules::<T>::get(round.id);
+            let bps = vested_tranche_bps(&tranches, round.end_block, now, |gate| {
+                Self::milestone_satisfied(round.entity_id, gate)
+            });
+
+            let total_u128: u128 = total.saturated_into();
+            let vested: u128 = total_u128.saturating_mul(bps as u128) / 10000;
+            vested.saturated_into()
+        }
+
+        /// 里程碑是否已被满足门控要求的来源确认
+        pub fn milestone_satisfied(entity_id: u64, gate: &MilestoneGate) -> bool {
+            Milestones::<T>::get(entity_id, gate.id)
+                .map_or(false, |(source, _)| source.satisfies(gate.source))
+        }
+
+        /// 记录里程碑确认（已被同等或更高来源确认时拒绝）
+        pub(crate) fn do_mark_milestone(
+            entity_id: u64,
+            milestone_id: u32,
+            source: MilestoneSource,
+        ) -> DispatchResult {
+            if let Some((existing, _)) = Milestones::<T>::get(entity_id, milestone_id) {
+                ensure!(!existing.satisfies(source), Error::<T>::MilestoneAlreadyAchieved);
+            }
+
+            let now = <frame_system::Pallet<T>>::block_number();
+            Milestones::<T>::insert(entity_id, milestone_id, (source, now));
+            Self::deposit_event(Event::MilestoneAchieved { entity_id, milestone_id, source });
+            Ok(())
+        }
+
         /// 计算可解锁量
         fn calculate_unlockable(
             vesting: &VestingConfig<BlockNumberFor<T>>,
@@ -1272,6 +1719,10 @@ pub mod pallet {
             }
 
             let now = <frame_system::Pallet<T>>::block_number();
+            if round.vesting_config.vesting_type == VestingType::Custom {
+                return Self::calculate_custom_vested(&round, sub.amount, now)
+                    .saturating_sub(sub.unlocked_amount);
+            }
             Self::calculate_unlockable(
                 &round.vesting_config,
                 sub.amount,
@@ -1282,3 +1733,19 @@ pub mod pallet {
         }
     }
 }
+
+// ==================== MilestoneProvider 实现 ====================
+
+impl<T: pallet::Config> pallet_entity_common::MilestoneProvider for pallet::Pallet<T> {
+    fn is_milestone_achieved(entity_id: u64, gate: &pallet_entity_common::MilestoneGate) -> bool {
+        Self::milestone_satisfied(entity_id, gate)
+    }
+
+    fn mark_milestone_achieved(
+        entity_id: u64,
+        milestone_id: u32,
+        source: pallet_entity_common::MilestoneSource,
+    ) -> Result<(), sp_runtime::DispatchError> {
+        Self::do_mark_milestone(entity_id, milestone_id, source)
+    }
+}
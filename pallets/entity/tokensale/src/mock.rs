@@ -1,7 +1,7 @@
 //! 代币发售模块测试 mock
 
 use crate as pallet_entity_tokensale;
-use frame_support::{derive_impl, parameter_types, traits::ConstU32};
+use frame_support::{derive_impl, parameter_types, traits::{ConstU32, ConstU64}};
 use sp_runtime::BuildStorage;
 use frame_support::traits::Currency;
 use frame_support::sp_runtime::DispatchError;
@@ -162,6 +162,9 @@ impl pallet_entity_tokensale::KycChecker<u64> for MockKycChecker {
 
 // ==================== Pallet Config ====================
 
+/// 抽签种子区块延迟
+pub const LOTTERY_SEED_DELAY: u64 = 5;
+
 parameter_types! {
     pub const ExistentialDeposit: u128 = 1;
 }
@@ -177,6 +180,24 @@ impl pallet_entity_tokensale::Config for Test {
     type MaxWhitelistSize = ConstU32<100>;
     type MaxRoundsHistory = ConstU32<50>;
     type MaxSubscriptionsPerRound = ConstU32<1000>;
+    type MaxLotteryDrawsPerCall = ConstU32<50>;
+    type LotterySeedDelay = ConstU64<LOTTERY_SEED_DELAY>;
+    type MaxVestingTranches = ConstU32<10>;
+    type EntityGovernanceOrigin = MockEntityGovernance;
+}
+
+/// 模拟实体治理来源：账户 GOV_ENTITY 签名视为 ENTITY_ID 的治理来源
+pub const GOV_ENTITY: u64 = 900;
+
+pub struct MockEntityGovernance;
+impl frame_support::traits::EnsureOrigin<RuntimeOrigin> for MockEntityGovernance {
+    type Success = u64;
+    fn try_origin(o: RuntimeOrigin) -> Result<u64, RuntimeOrigin> {
+        match o.clone().into() {
+            Ok(frame_system::RawOrigin::Signed(GOV_ENTITY)) => Ok(ENTITY_ID),
+            _ => Err(o),
+        }
+    }
 }
 
 pub fn new_test_ext() -> sp_io::TestExternalities {
@@ -1,7 +1,8 @@
 //! 代币发售模块测试
 
 use crate::{mock::*, pallet::*};
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{assert_noop, assert_ok, traits::{Get, Hooks}};
+use sp_runtime::traits::Hash;
 use frame_system::RawOrigin;
 
 /// 创建标准发售轮次的辅助函数
@@ -563,3 +564,290 @@ fn subscribe_rejects_overflow() {
         );
     });
 }
+
+// ==================== Lottery ====================
+
+/// 推进到种子区块的下一区块，由 on_initialize 记录种子区块哈希
+fn pass_seed_block(round_id: u64) {
+    let seed_block = LotteryDraws::<Test>::get(round_id).unwrap().seed_block;
+    frame_system::Pallet::<Test>::set_block_number(seed_block + 1);
+    EntityTokenSale::on_initialize(seed_block + 1);
+}
+
+/// 创建抽签轮次（总量 150，两人各认购 100 即超额）
+fn setup_lottery_round() -> u64 {
+    assert_ok!(EntityTokenSale::create_sale_round(
+        RuntimeOrigin::signed(CREATOR), ENTITY_ID,
+        SaleMode::Lottery, 150u128,
+        10u64.into(), 100u64.into(), false, 0,
+    ));
+    assert_ok!(EntityTokenSale::add_payment_option(
+        RuntimeOrigin::signed(CREATOR), 0, None, 100u128, 10u128, 100_000u128,
+    ));
+    assert_ok!(EntityTokenSale::start_sale(RuntimeOrigin::signed(CREATOR), 0));
+    frame_system::Pallet::<Test>::set_block_number(10);
+    0
+}
+
+#[test]
+fn lottery_oversubscribed_draw_and_refund() {
+    new_test_ext().execute_with(|| {
+        let round_id = setup_lottery_round();
+        // 超额认购：200 > 150
+        assert_ok!(EntityTokenSale::subscribe(RuntimeOrigin::signed(BUYER), round_id, 100u128, None));
+        assert_ok!(EntityTokenSale::subscribe(RuntimeOrigin::signed(BUYER2), round_id, 100u128, None));
+        assert_eq!(Balances::free_balance(EntityTokenSale::pallet_account()), 20_000u128);
+
+        assert_ok!(EntityTokenSale::end_sale(RuntimeOrigin::signed(CREATOR), round_id));
+        assert_eq!(SaleRounds::<Test>::get(round_id).unwrap().status, RoundStatus::Settling);
+        pass_seed_block(round_id);
+
+        // 分页抽签：任何人可推进
+        assert_ok!(EntityTokenSale::draw_lottery(RuntimeOrigin::signed(BUYER), round_id, 1));
+        assert_eq!(SaleRounds::<Test>::get(round_id).unwrap().status, RoundStatus::Settling);
+        assert_ok!(EntityTokenSale::draw_lottery(RuntimeOrigin::signed(BUYER), round_id, 1));
+
+        let round = SaleRounds::<Test>::get(round_id).unwrap();
+        assert_eq!(round.status, RoundStatus::Ended);
+        assert_eq!(round.sold_amount, 100u128);
+        assert_eq!(LotteryDraws::<Test>::get(round_id).unwrap().winners, 1);
+        assert_eq!(RaisedFunds::<Test>::get(round_id, Option::<u64>::None), 10_000u128);
+        assert!(LotteryQueue::<Test>::get(round_id).is_empty());
+
+        let (winner, loser) = if LotteryWinners::<Test>::get(round_id, BUYER) {
+            (BUYER, BUYER2)
+        } else {
+            (BUYER2, BUYER)
+        };
+
+        // 中签者领取代币，不可退款
+        assert_ok!(EntityTokenSale::claim_tokens(RuntimeOrigin::signed(winner), round_id));
+        assert_noop!(
+            EntityTokenSale::claim_refund(RuntimeOrigin::signed(winner), round_id),
+            Error::<Test>::SaleNotCancelled
+        );
+
+        // 未中签者不可领币，全额退款
+        assert_noop!(
+            EntityTokenSale::claim_tokens(RuntimeOrigin::signed(loser), round_id),
+            Error::<Test>::NotLotteryWinner
+        );
+        let before = Balances::free_balance(loser);
+        assert_ok!(EntityTokenSale::claim_refund(RuntimeOrigin::signed(loser), round_id));
+        assert_eq!(Balances::free_balance(loser) - before, 10_000u128);
+    });
+}
+
+#[test]
+fn lottery_draw_is_reproducible_from_seed() {
+    new_test_ext().execute_with(|| {
+        let round_id = setup_lottery_round();
+        assert_ok!(EntityTokenSale::subscribe(RuntimeOrigin::signed(BUYER), round_id, 100u128, None));
+        assert_ok!(EntityTokenSale::subscribe(RuntimeOrigin::signed(BUYER2), round_id, 100u128, None));
+        assert_ok!(EntityTokenSale::end_sale(RuntimeOrigin::signed(CREATOR), round_id));
+
+        // 种子取自 end_sale 时锁定的种子区块哈希
+        let anchor = sp_core::H256::repeat_byte(7);
+        frame_system::BlockHash::<Test>::insert(10 + LOTTERY_SEED_DELAY, anchor);
+        pass_seed_block(round_id);
+        let seed = <Test as frame_system::Config>::Hashing::hash_of(&((b"et/sale/lottery", round_id), anchor));
+
+        // 区块哈希移出保留窗口后，延迟抽签仍使用已记录的哈希
+        frame_system::BlockHash::<Test>::remove(10 + LOTTERY_SEED_DELAY);
+        frame_system::Pallet::<Test>::set_block_number(10_000);
+        assert_ok!(EntityTokenSale::draw_lottery(RuntimeOrigin::signed(BUYER2), round_id, 50));
+        assert_eq!(LotteryDraws::<Test>::get(round_id).unwrap().seed, Some(seed));
+        assert!(LotterySeeds::<Test>::get(10 + LOTTERY_SEED_DELAY).is_none());
+        System::assert_has_event(RuntimeEvent::EntityTokenSale(Event::LotterySeedFixed { round_id, seed }));
+
+        // 按公开种子复现 Fisher-Yates 第一步：首位整笔中签
+        let mut order = vec![BUYER, BUYER2];
+        order.swap(0, EntityTokenSale::lottery_offset(&seed, 0, 2) as usize);
+        assert!(LotteryWinners::<Test>::get(round_id, order[0]));
+        assert!(!LotteryWinners::<Test>::get(round_id, order[1]));
+    });
+}
+
+#[test]
+fn draw_lottery_waits_for_seed_block() {
+    new_test_ext().execute_with(|| {
+        let round_id = setup_lottery_round();
+        assert_ok!(EntityTokenSale::subscribe(RuntimeOrigin::signed(BUYER), round_id, 100u128, None));
+        assert_ok!(EntityTokenSale::end_sale(RuntimeOrigin::signed(CREATOR), round_id));
+
+        let draw = LotteryDraws::<Test>::get(round_id).unwrap();
+        assert_eq!((draw.seed, draw.seed_block), (None, 10 + LOTTERY_SEED_DELAY));
+        System::assert_has_event(RuntimeEvent::EntityTokenSale(Event::LotteryDrawStarted {
+            round_id,
+            seed_block: 10 + LOTTERY_SEED_DELAY,
+            participants: 1,
+        }));
+
+        assert_eq!(LotterySeeds::<Test>::get(10 + LOTTERY_SEED_DELAY).unwrap().pending, 1);
+
+        // 种子区块（含）之前种子不可知
+        frame_system::Pallet::<Test>::set_block_number(10 + LOTTERY_SEED_DELAY);
+        EntityTokenSale::on_initialize(10 + LOTTERY_SEED_DELAY);
+        assert_noop!(
+            EntityTokenSale::draw_lottery(RuntimeOrigin::signed(BUYER), round_id, 1),
+            Error::<Test>::LotterySeedNotReady
+        );
+
+        // 未经下一区块 on_initialize 记录哈希时同样不可抽签
+        frame_system::Pallet::<Test>::set_block_number(11 + LOTTERY_SEED_DELAY);
+        assert_noop!(
+            EntityTokenSale::draw_lottery(RuntimeOrigin::signed(BUYER), round_id, 1),
+            Error::<Test>::LotterySeedNotReady
+        );
+
+        EntityTokenSale::on_initialize(11 + LOTTERY_SEED_DELAY);
+        assert!(LotterySeeds::<Test>::get(10 + LOTTERY_SEED_DELAY).unwrap().hash.is_some());
+        assert_ok!(EntityTokenSale::draw_lottery(RuntimeOrigin::signed(BUYER), round_id, 1));
+        assert!(LotteryDraws::<Test>::get(round_id).unwrap().seed.is_some());
+        assert_eq!(SaleRounds::<Test>::get(round_id).unwrap().status, RoundStatus::Ended);
+    });
+}
+
+#[test]
+fn draw_lottery_weight_covers_queue_copy() {
+    let per_call = EntityTokenSale::lottery_queue_weight();
+    let max = <Test as Config>::MaxSubscriptionsPerRound::get() as u64;
+    assert!(per_call.proof_size() >= 8 * max);
+    assert!(per_call.ref_time() >= max);
+}
+
+#[test]
+fn draw_lottery_rejects_invalid_count_and_status() {
+    new_test_ext().execute_with(|| {
+        let round_id = setup_lottery_round();
+        assert_ok!(EntityTokenSale::subscribe(RuntimeOrigin::signed(BUYER), round_id, 100u128, None));
+
+        // 未 end_sale
+        assert_noop!(
+            EntityTokenSale::draw_lottery(RuntimeOrigin::signed(BUYER), round_id, 1),
+            Error::<Test>::InvalidRoundStatus
+        );
+
+        assert_ok!(EntityTokenSale::end_sale(RuntimeOrigin::signed(CREATOR), round_id));
+        assert_noop!(
+            EntityTokenSale::draw_lottery(RuntimeOrigin::signed(BUYER), round_id, 0),
+            Error::<Test>::InvalidDrawCount
+        );
+        assert_noop!(
+            EntityTokenSale::draw_lottery(RuntimeOrigin::signed(BUYER), round_id, 51),
+            Error::<Test>::InvalidDrawCount
+        );
+    });
+}
+
+// ==================== 自定义分段解锁 ====================
+
+use pallet_entity_common::{EntityTokenProvider, MilestoneGate, MilestoneProvider, MilestoneSource, VestingTranche};
+
+fn tranche(offset: u64, bps: u16, milestone: Option<(u32, MilestoneSource)>) -> VestingTranche<u64> {
+    VestingTranche {
+        offset,
+        bps,
+        milestone: milestone.map(|(id, source)| MilestoneGate { id, source }),
+    }
+}
+
+#[test]
+fn set_custom_vesting_validates_tranches() {
+    new_test_ext().execute_with(|| {
+        let round_id = setup_round();
+        // 合计不足 10000
+        assert_noop!(
+            EntityTokenSale::set_custom_vesting(
+                RuntimeOrigin::signed(CREATOR), round_id,
+                vec![tranche(0, 5000, None)].try_into().unwrap(),
+            ),
+            Error::<Test>::InvalidVestingTranches
+        );
+        // 偏移递减
+        assert_noop!(
+            EntityTokenSale::set_custom_vesting(
+                RuntimeOrigin::signed(CREATOR), round_id,
+                vec![tranche(100, 5000, None), tranche(50, 5000, None)].try_into().unwrap(),
+            ),
+            Error::<Test>::InvalidVestingTranches
+        );
+        // Custom 不能通过 set_vesting_config 设置
+        assert_noop!(
+            EntityTokenSale::set_vesting_config(
+                RuntimeOrigin::signed(CREATOR), round_id,
+                VestingType::Custom, 0, 0u64.into(), 0u64.into(), 0u64.into(),
+            ),
+            Error::<Test>::InvalidVestingConfig
+        );
+
+        assert_ok!(EntityTokenSale::set_custom_vesting(
+            RuntimeOrigin::signed(CREATOR), round_id,
+            vec![tranche(0, 2500, None), tranche(100, 7500, None)].try_into().unwrap(),
+        ));
+        let round = SaleRounds::<Test>::get(round_id).unwrap();
+        assert_eq!(round.vesting_config.vesting_type, VestingType::Custom);
+        assert_eq!(CustomVestingSchedules::<Test>::get(round_id).len(), 2);
+    });
+}
+
+#[test]
+fn custom_vesting_unlocks_by_tranche_and_milestone() {
+    new_test_ext().execute_with(|| {
+        let round_id = setup_round();
+        // end_block = 100：结束时 20%，+50 区块 30%，里程碑 1（治理）后 50%
+        assert_ok!(EntityTokenSale::set_custom_vesting(
+            RuntimeOrigin::signed(CREATOR), round_id,
+            vec![
+                tranche(0, 2000, None),
+                tranche(50, 3000, None),
+                tranche(50, 5000, Some((1, MilestoneSource::Governance))),
+            ].try_into().unwrap(),
+        ));
+        assert_ok!(EntityTokenSale::add_payment_option(
+            RuntimeOrigin::signed(CREATOR), round_id,
+            None, 100u128, 10u128, 100_000u128,
+        ));
+        assert_ok!(EntityTokenSale::start_sale(RuntimeOrigin::signed(CREATOR), round_id));
+        frame_system::Pallet::<Test>::set_block_number(10);
+        assert_ok!(EntityTokenSale::subscribe(RuntimeOrigin::signed(BUYER), round_id, 1_000u128, None));
+        assert_ok!(EntityTokenSale::end_sale(RuntimeOrigin::signed(CREATOR), round_id));
+
+        // 未到 end_block：初始领取为 0
+        assert_ok!(EntityTokenSale::claim_tokens(RuntimeOrigin::signed(BUYER), round_id));
+        assert_eq!(MockTokenProvider::token_balance(ENTITY_ID, &BUYER), 0);
+
+        frame_system::Pallet::<Test>::set_block_number(100);
+        assert_ok!(EntityTokenSale::unlock_tokens(RuntimeOrigin::signed(BUYER), round_id));
+        assert_eq!(MockTokenProvider::token_balance(ENTITY_ID, &BUYER), 200);
+
+        // 偏移已到，但里程碑未确认：仅解锁无门控档位
+        frame_system::Pallet::<Test>::set_block_number(150);
+        assert_eq!(EntityTokenSale::get_unlockable_amount(round_id, &BUYER), 300);
+        assert_ok!(EntityTokenSale::unlock_tokens(RuntimeOrigin::signed(BUYER), round_id));
+        assert_noop!(
+            EntityTokenSale::unlock_tokens(RuntimeOrigin::signed(BUYER), round_id),
+            Error::<Test>::NoTokensToUnlock
+        );
+
+        // 披露来源不满足治理门控
+        assert_ok!(<EntityTokenSale as MilestoneProvider>::mark_milestone_achieved(
+            ENTITY_ID, 1, MilestoneSource::Disclosure,
+        ));
+        assert_eq!(EntityTokenSale::get_unlockable_amount(round_id, &BUYER), 0);
+
+        // 只有实体治理来源可确认
+        assert_noop!(
+            EntityTokenSale::mark_milestone_achieved(RuntimeOrigin::signed(CREATOR), ENTITY_ID, 1),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(EntityTokenSale::mark_milestone_achieved(RuntimeOrigin::signed(GOV_ENTITY), ENTITY_ID, 1));
+        assert_noop!(
+            EntityTokenSale::mark_milestone_achieved(RuntimeOrigin::signed(GOV_ENTITY), ENTITY_ID, 1),
+            Error::<Test>::MilestoneAlreadyAchieved
+        );
+
+        assert_ok!(EntityTokenSale::unlock_tokens(RuntimeOrigin::signed(BUYER), round_id));
+        assert_eq!(MockTokenProvider::token_balance(ENTITY_ID, &BUYER), 1_000);
+    });
+}
@@ -38,7 +38,7 @@ pub mod pallet {
     };
     use frame_system::pallet_prelude::*;
     use pallet_escrow::pallet::Escrow as EscrowTrait;
-    use pallet_entity_common::{MallOrderStatus, OrderCommissionHandler, OrderProvider, ProductCategory, ProductProvider, EntityProvider, EntityTokenProvider, ShopProvider};
+    use pallet_entity_common::{MallOrderStatus, OrderCommissionHandler, OrderProvider, ProductCategory, ProductProvider, EntityProvider, EntityTokenProvider, ShopPromotionProvider, ShopProvider};
     use sp_runtime::{traits::{Saturating, Zero}, SaturatedConversion};
 
     /// 货币余额类型别名
@@ -160,6 +160,9 @@ pub mod pallet {
         /// 佣金处理接口（订单完成时触发返佣）
         type CommissionHandler: OrderCommissionHandler<Self::AccountId, BalanceOf<Self>>;
 
+        /// 店铺促销接口（治理 `Promotion` 提案设定的折扣）
+        type Promotions: ShopPromotionProvider<BlockNumberFor<Self>>;
+
         /// CID 最大长度
         #[pallet::constant]
         type MaxCidLength: Get<u32>;
@@ -362,8 +365,14 @@ pub mod pallet {
             let seller = T::ShopProvider::shop_owner(shop_id).ok_or(Error::<T>::ShopNotFound)?;
             ensure!(seller != buyer, Error::<T>::CannotBuyOwnProduct);
 
-            // 计算金额
-            let total_amount = price.saturating_mul(quantity.into());
+            let now = <frame_system::Pallet<T>>::block_number();
+
+            // 计算金额（店铺促销期内按折扣计价）
+            let mut total_amount = price.saturating_mul(quantity.into());
+            if let Some(discount_rate) = T::Promotions::active_discount(shop_id, now) {
+                let discount = total_amount.saturating_mul(discount_rate.into()) / 10000u32.into();
+                total_amount = total_amount.saturating_sub(discount);
+            }
             
             // 积分抵扣
             let mut final_amount = total_amount;
@@ -397,7 +406,6 @@ pub mod pallet {
             };
 
             let order_id = NextOrderId::<T>::get();
-            let now = <frame_system::Pallet<T>>::block_number();
 
             // 锁定资金到托管（扣除积分抵扣后的金额）
             T::Escrow::lock_from(&buyer, order_id, final_amount)?;
@@ -733,6 +741,7 @@ pub mod pallet {
                 order_id,
                 &order.buyer,
                 order.total_amount,
+                order.platform_fee,
             );
 
             // 发放购物积分奖励
@@ -389,7 +389,7 @@ pub fn get_cancelled_orders() -> Vec<u64> {
 }
 
 impl pallet_entity_common::OrderCommissionHandler<u64, u64> for MockCommissionHandler {
-    fn on_order_completed(_shop_id: u64, _order_id: u64, _buyer: &u64, _amount: u64) -> Result<(), sp_runtime::DispatchError> {
+    fn on_order_completed(_shop_id: u64, _order_id: u64, _buyer: &u64, _amount: u64, _platform_fee: u64) -> Result<(), sp_runtime::DispatchError> {
         Ok(())
     }
 
@@ -399,6 +399,27 @@ impl pallet_entity_common::OrderCommissionHandler<u64, u64> for MockCommissionHa
     }
 }
 
+// ==================== Mock Promotions ====================
+
+pub struct MockPromotions;
+
+thread_local! {
+    /// shop_id -> (折扣基点, 结束区块)
+    static PROMOTIONS: RefCell<HashMap<u64, (u16, u64)>> = RefCell::new(HashMap::new());
+}
+
+pub fn set_promotion(shop_id: u64, discount_rate: u16, ends_at: u64) {
+    PROMOTIONS.with(|p| p.borrow_mut().insert(shop_id, (discount_rate, ends_at)));
+}
+
+impl pallet_entity_common::ShopPromotionProvider<u64> for MockPromotions {
+    fn active_discount(shop_id: u64, now: u64) -> Option<u16> {
+        PROMOTIONS.with(|p| {
+            p.borrow().get(&shop_id).and_then(|&(rate, ends_at)| (ends_at > now).then_some(rate))
+        })
+    }
+}
+
 // ==================== Test Constants ====================
 
 pub const BUYER: u64 = 1;
@@ -428,6 +449,7 @@ impl pallet_entity_transaction::Config for Test {
     type ConfirmTimeout = ConstU64<200>;
     type ServiceConfirmTimeout = ConstU64<150>;
     type CommissionHandler = MockCommissionHandler;
+    type Promotions = MockPromotions;
     type MaxCidLength = ConstU32<64>;
 }
 
@@ -458,6 +480,7 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
         ESCROW_BALANCES.with(|b| b.borrow_mut().clear());
         PRODUCT_STOCK.with(|s| s.borrow_mut().clear());
         CANCELLED_ORDERS.with(|c| c.borrow_mut().clear());
+        PROMOTIONS.with(|p| p.borrow_mut().clear());
     });
     ext
 }
@@ -67,6 +67,21 @@ fn place_order_service_works() {
     });
 }
 
+#[test]
+fn place_order_applies_active_promotion() {
+    new_test_ext().execute_with(|| {
+        // 店铺 1 八折促销，至区块 10 结束
+        set_promotion(SHOP_1, 2000, 10);
+        assert_ok!(Transaction::place_order(RuntimeOrigin::signed(BUYER), 1, 2, None, None));
+        assert_eq!(Transaction::orders(1).unwrap().total_amount, 160);
+
+        // 促销结束后恢复原价
+        System::set_block_number(10);
+        assert_ok!(Transaction::place_order(RuntimeOrigin::signed(BUYER), 1, 2, None, None));
+        assert_eq!(Transaction::orders(2).unwrap().total_amount, 200);
+    });
+}
+
 #[test]
 fn place_order_fails_zero_quantity() {
     new_test_ext().execute_with(|| {
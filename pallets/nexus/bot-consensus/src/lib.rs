@@ -26,6 +26,8 @@ extern crate alloc;
 
 pub use pallet::*;
 
+pub mod migrations;
+
 #[cfg(test)]
 mod mock;
 
@@ -110,6 +112,8 @@ pub struct MessageConfirmation<T: Config> {
 pub struct EquivocationRecord<T: Config> {
 	/// 作弊的 owner
 	pub owner: T::AccountId,
+	/// Bot ID 哈希
+	pub bot_id_hash: [u8; 32],
 	/// 消息序列号
 	pub sequence: u64,
 	/// 第一个消息哈希
@@ -126,6 +130,22 @@ pub struct EquivocationRecord<T: Config> {
 	pub reported_at: BlockNumberFor<T>,
 	/// 是否已处理
 	pub resolved: bool,
+	/// 实际 Slash 金额
+	pub slashed: BalanceOf<T>,
+}
+
+/// 群主签名消息证明（Equivocation 举报用）
+///
+/// 签名数据 = owner_public_key ‖ bot_id_hash ‖ sequence(LE) ‖ timestamp(LE) ‖ msg_hash，
+/// 与 Agent 签名、节点验签格式一致。
+#[derive(Encode, Decode, codec::DecodeWithMemTracking, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
+pub struct SignedMessageProof {
+	/// 消息时间戳（Agent 签名时填写）
+	pub timestamp: u64,
+	/// 消息内容哈希
+	pub msg_hash: [u8; 32],
+	/// 群主 Ed25519 签名
+	pub signature: [u8; 64],
 }
 
 /// Leader 执行统计
@@ -214,11 +234,17 @@ pub type BalanceOf<T> = <<T as Config>::Currency as frame_support::traits::Curre
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
-	use frame_support::traits::{Currency, ReservableCurrency};
+	use frame_support::traits::{Currency, Imbalance, ReservableCurrency};
 	use frame_system::pallet_prelude::BlockNumberFor;
 	use sp_std::vec::Vec;
 
+	/// 存储版本
+	///
+	/// v1: `EquivocationRecords` 键由 owner 改为 bot_id_hash（见 `migrations::v1`）
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::config]
@@ -247,6 +273,10 @@ pub mod pallet {
 		#[pallet::constant]
 		type ReporterRewardPercentage: Get<u32>;
 
+		/// Equivocation 举报押金（证据签名无效时罚没）
+		#[pallet::constant]
+		type EquivocationReportDeposit: Get<BalanceOf<Self>>;
+
 		/// 信誉低于此值自动 Suspend
 		#[pallet::constant]
 		type SuspendThreshold: Get<u16>;
@@ -331,13 +361,13 @@ pub mod pallet {
 		MessageConfirmation<T>,
 	>;
 
-	/// Equivocation 证据记录: (owner, sequence) → EquivocationRecord
+	/// Equivocation 证据记录: (bot_id_hash, sequence) → EquivocationRecord
 	#[pallet::storage]
 	#[pallet::getter(fn equivocation_records)]
 	pub type EquivocationRecords<T: Config> = StorageDoubleMap<
 		_,
 		Blake2_128Concat,
-		T::AccountId,
+		[u8; 32],
 		Blake2_128Concat,
 		u64,
 		EquivocationRecord<T>,
@@ -464,7 +494,7 @@ pub mod pallet {
 			submitter: T::AccountId,
 			count: u32,
 		},
-		/// Equivocation 举报
+		/// Equivocation 举报（证据已验证）
 		EquivocationReported {
 			owner: T::AccountId,
 			sequence: u64,
@@ -476,6 +506,13 @@ pub mod pallet {
 			slash_amount: BalanceOf<T>,
 			reporter_reward: BalanceOf<T>,
 		},
+		/// Equivocation 举报证据无效，举报押金被罚没
+		InvalidEquivocationReport {
+			reporter: T::AccountId,
+			bot_id_hash: [u8; 32],
+			sequence: u64,
+			deposit_slashed: BalanceOf<T>,
+		},
 		/// 信誉分变化
 		ReputationUpdated {
 			node_id: NodeId,
@@ -603,6 +640,10 @@ pub mod pallet {
 		SameTier,
 		/// 订阅已取消
 		SubscriptionAlreadyCancelled,
+		/// 余额不足以锁定举报押金
+		InsufficientReportDeposit,
+		/// 不能举报自己的 Bot（Bot 所有者或订阅者）
+		CannotReportOwnBot,
 	}
 
 	// ═══════════════════════════════════════════════════════════════
@@ -833,56 +874,99 @@ pub mod pallet {
 			Ok(())
 		}
 
-		/// 提交 Equivocation 证据（任何节点可调用）
+		/// 提交 Equivocation 证据（任何账户可调用）
 		///
-		/// 证据: 同一 (owner, sequence) 对应两个不同的 msg_hash
+		/// 证据: 同一 (bot_id_hash, sequence) 下两条 msg_hash 不同、均由 Bot 注册公钥签名的消息。
+		/// - 举报者先锁定 `EquivocationReportDeposit`
+		/// - 签名验证失败 → 押金罚没，调用本身成功（记录 `InvalidEquivocationReport`）
+		/// - 验证通过 → 押金退还，Slash 群主订阅 Escrow，按比例奖励举报者，并暂停订阅与 Bot
 		#[pallet::call_index(4)]
-		#[pallet::weight(Weight::from_parts(60_000_000, 0))]
+		#[pallet::weight(Weight::from_parts(120_000_000, 5_000))]
 		pub fn report_equivocation(
 			origin: OriginFor<T>,
-			owner: T::AccountId,
+			bot_id_hash: [u8; 32],
 			sequence: u64,
-			msg_hash_a: [u8; 32],
-			signature_a: [u8; 64],
-			msg_hash_b: [u8; 32],
-			signature_b: [u8; 64],
+			proof_a: SignedMessageProof,
+			proof_b: SignedMessageProof,
 		) -> DispatchResult {
 			let reporter = ensure_signed(origin)?;
 
 			// 两个哈希必须不同
-			ensure!(msg_hash_a != msg_hash_b, Error::<T>::InvalidEquivocationEvidence);
+			ensure!(proof_a.msg_hash != proof_b.msg_hash, Error::<T>::InvalidEquivocationEvidence);
 
 			// 不能重复举报
 			ensure!(
-				!EquivocationRecords::<T>::contains_key(&owner, sequence),
+				!EquivocationRecords::<T>::contains_key(&bot_id_hash, sequence),
 				Error::<T>::EquivocationAlreadyReported
 			);
 
-			// TODO: Sprint 5 — 验证两个 Ed25519 签名的有效性
-			// 当前简化版: 存储证据，后续完善链上验签
+			let owner = T::BotRegistry::bot_owner(&bot_id_hash)
+				.ok_or(Error::<T>::BotNotRegistered)?;
+			let public_key = T::BotRegistry::bot_public_key(&bot_id_hash)
+				.ok_or(Error::<T>::BotNotRegistered)?;
+
+			// 群主自举报会把 Slash 的奖励部分领回，不允许
+			ensure!(
+				reporter != owner
+					&& Subscriptions::<T>::get(&bot_id_hash).map_or(true, |sub| sub.owner != reporter),
+				Error::<T>::CannotReportOwnBot
+			);
+
+			// 锁定举报押金
+			let deposit = T::EquivocationReportDeposit::get();
+			T::Currency::reserve(&reporter, deposit)
+				.map_err(|_| Error::<T>::InsufficientReportDeposit)?;
+
+			let valid = Self::verify_owner_signature(&public_key, &bot_id_hash, sequence, &proof_a)
+				&& Self::verify_owner_signature(&public_key, &bot_id_hash, sequence, &proof_b);
+
+			if !valid {
+				// 罚没押金（销毁）
+				let (imbalance, _) = T::Currency::slash_reserved(&reporter, deposit);
+				Self::deposit_event(Event::InvalidEquivocationReport {
+					reporter,
+					bot_id_hash,
+					sequence,
+					deposit_slashed: imbalance.peek(),
+				});
+				return Ok(());
+			}
+
+			T::Currency::unreserve(&reporter, deposit);
+
+			let (slashed, reporter_reward) =
+				Self::slash_equivocation(&bot_id_hash, &reporter);
 
 			let current_block = <frame_system::Pallet<T>>::block_number();
 
 			let record = EquivocationRecord {
 				owner: owner.clone(),
+				bot_id_hash,
 				sequence,
-				msg_hash_a,
-				signature_a,
-				msg_hash_b,
-				signature_b,
+				msg_hash_a: proof_a.msg_hash,
+				signature_a: proof_a.signature,
+				msg_hash_b: proof_b.msg_hash,
+				signature_b: proof_b.signature,
 				reporter: reporter.clone(),
 				reported_at: current_block,
-				resolved: false,
+				resolved: true,
+				slashed,
 			};
 
-			EquivocationRecords::<T>::insert(&owner, sequence, record);
+			EquivocationRecords::<T>::insert(&bot_id_hash, sequence, record);
 
 			Self::deposit_event(Event::EquivocationReported {
-				owner,
+				owner: owner.clone(),
 				sequence,
 				reporter,
 			});
 
+			Self::deposit_event(Event::EquivocationSlashed {
+				owner,
+				slash_amount: slashed,
+				reporter_reward,
+			});
+
 			Ok(())
 		}
 
@@ -1318,6 +1402,93 @@ pub mod pallet {
 			});
 		}
 
+		/// 构造群主签名数据
+		///
+		/// owner_public_key ‖ bot_id_hash ‖ sequence(LE) ‖ timestamp(LE) ‖ msg_hash
+		pub fn owner_sign_data(
+			public_key: &[u8; 32],
+			bot_id_hash: &[u8; 32],
+			sequence: u64,
+			proof: &SignedMessageProof,
+		) -> Vec<u8> {
+			let mut data = Vec::with_capacity(32 + 32 + 8 + 8 + 32);
+			data.extend_from_slice(public_key);
+			data.extend_from_slice(bot_id_hash);
+			data.extend_from_slice(&sequence.to_le_bytes());
+			data.extend_from_slice(&proof.timestamp.to_le_bytes());
+			data.extend_from_slice(&proof.msg_hash);
+			data
+		}
+
+		/// 验证群主 Ed25519 签名
+		pub fn verify_owner_signature(
+			public_key: &[u8; 32],
+			bot_id_hash: &[u8; 32],
+			sequence: u64,
+			proof: &SignedMessageProof,
+		) -> bool {
+			use sp_runtime::traits::Verify;
+
+			let data = Self::owner_sign_data(public_key, bot_id_hash, sequence, proof);
+			let signature = sp_core::ed25519::Signature::from_raw(proof.signature);
+			let signer = sp_core::ed25519::Public::from_raw(*public_key);
+			signature.verify(data.as_slice(), &signer)
+		}
+
+		/// 执行 Equivocation 惩罚
+		///
+		/// 1. Slash 订阅 Escrow 的 `SlashPercentage`%
+		/// 2. Slash 金额的 `ReporterRewardPercentage`% 奖励举报者，其余销毁
+		/// 3. 暂停订阅与 Bot（Escrow 为空时仅暂停）
+		///
+		/// 返回 (实际 Slash 金额, 举报者奖励)
+		fn slash_equivocation(
+			bot_id_hash: &[u8; 32],
+			reporter: &T::AccountId,
+		) -> (BalanceOf<T>, BalanceOf<T>) {
+			use sp_runtime::traits::Zero;
+
+			let mut slashed: BalanceOf<T> = Zero::zero();
+			let mut reward: BalanceOf<T> = Zero::zero();
+
+			if let Some(sub) = Subscriptions::<T>::get(bot_id_hash) {
+				let escrow = SubscriptionEscrow::<T>::get(bot_id_hash);
+				let slash_amount = escrow
+					.saturating_mul(T::SlashPercentage::get().into()) / 100u32.into();
+
+				if !slash_amount.is_zero() {
+					let (imbalance, _) = T::Currency::slash_reserved(&sub.owner, slash_amount);
+					slashed = imbalance.peek();
+					SubscriptionEscrow::<T>::mutate(bot_id_hash, |e| {
+						*e = e.saturating_sub(slashed);
+					});
+
+					let reward_amount = slashed
+						.saturating_mul(T::ReporterRewardPercentage::get().into()) / 100u32.into();
+					let (to_reporter, _burned) = imbalance.split(reward_amount);
+					reward = to_reporter.peek();
+					T::Currency::resolve_creating(reporter, to_reporter);
+				}
+
+				if sub.status != SubscriptionStatus::Cancelled
+					&& sub.status != SubscriptionStatus::Suspended
+				{
+					Subscriptions::<T>::mutate(bot_id_hash, |maybe_sub| {
+						if let Some(s) = maybe_sub {
+							s.status = SubscriptionStatus::Suspended;
+						}
+					});
+					Self::deposit_event(Event::SubscriptionSuspended {
+						bot_id_hash: *bot_id_hash,
+					});
+				}
+			}
+
+			T::BotRegistry::suspend_bot(bot_id_hash);
+
+			(slashed, reward)
+		}
+
 		/// 获取层级对应的每 Era 费用
 		pub fn tier_fee(tier: &SubscriptionTier) -> BalanceOf<T> {
 			match tier {
@@ -1500,4 +1671,10 @@ pub trait BotRegistryProvider<AccountId> {
 	fn bot_exists(bot_id_hash: &[u8; 32]) -> bool;
 	/// 是否为 Bot 的所有者
 	fn is_bot_owner(bot_id_hash: &[u8; 32], who: &AccountId) -> bool;
+	/// Bot 所有者
+	fn bot_owner(bot_id_hash: &[u8; 32]) -> Option<AccountId>;
+	/// Bot 注册的群主 Ed25519 公钥
+	fn bot_public_key(bot_id_hash: &[u8; 32]) -> Option<[u8; 32]>;
+	/// 因违规暂停 Bot（Equivocation 惩罚）
+	fn suspend_bot(bot_id_hash: &[u8; 32]);
 }
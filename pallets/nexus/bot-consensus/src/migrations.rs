@@ -0,0 +1,47 @@
+//! Bot Consensus 存储迁移
+
+use super::*;
+use frame_support::{migrations::VersionedMigration, traits::UncheckedOnRuntimeUpgrade};
+use sp_std::marker::PhantomData;
+
+/// v0 → v1：清空旧版 `EquivocationRecords`
+///
+/// v0 以 (owner, sequence) 为键、记录中无 `bot_id_hash` / `slashed` 字段，且证据未经验签、
+/// 从未执行 Slash。新键 (bot_id_hash, sequence) 无法由旧记录推出，旧值也无法按新类型解码，
+/// 故整体清除；仍有效的双签证据可按新流程重新举报。
+pub mod v1 {
+	use super::*;
+
+	/// 未做版本检查的迁移逻辑，通过 [`MigrateV0ToV1`] 使用
+	pub struct InnerMigrateV0ToV1<T>(PhantomData<T>);
+
+	impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV0ToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let result = EquivocationRecords::<T>::clear(u32::MAX, None);
+			log::info!(
+				target: "runtime::bot-consensus",
+				"cleared {} legacy equivocation records for v1",
+				result.unique,
+			);
+			T::DbWeight::get().reads_writes(result.loops as u64, result.unique as u64)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: alloc::vec::Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+			ensure!(
+				EquivocationRecords::<T>::iter_keys().next().is_none(),
+				"legacy equivocation records not cleared"
+			);
+			Ok(())
+		}
+	}
+
+	/// 带版本检查的 v0 → v1 迁移（仅在链上版本为 0 时执行，完成后写入版本 1）
+	pub type MigrateV0ToV1<T> = VersionedMigration<
+		0,
+		1,
+		InnerMigrateV0ToV1<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
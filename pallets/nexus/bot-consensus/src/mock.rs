@@ -48,6 +48,10 @@ impl pallet_balances::Config for Test {
 thread_local! {
 	/// bot_id_hash → owner_account_id
 	static BOTS: RefCell<HashMap<[u8; 32], u64>> = RefCell::new(HashMap::new());
+	/// bot_id_hash → 群主 Ed25519 公钥
+	static BOT_KEYS: RefCell<HashMap<[u8; 32], [u8; 32]>> = RefCell::new(HashMap::new());
+	/// 被暂停的 Bot
+	static SUSPENDED_BOTS: RefCell<Vec<[u8; 32]>> = RefCell::new(Vec::new());
 }
 
 /// 注册一个 Mock Bot（测试辅助）
@@ -55,6 +59,17 @@ pub fn register_mock_bot(bot_id_hash: [u8; 32], owner: u64) {
 	BOTS.with(|b| b.borrow_mut().insert(bot_id_hash, owner));
 }
 
+/// 注册一个带群主公钥的 Mock Bot（测试辅助）
+pub fn register_mock_bot_with_key(bot_id_hash: [u8; 32], owner: u64, public_key: [u8; 32]) {
+	register_mock_bot(bot_id_hash, owner);
+	BOT_KEYS.with(|k| k.borrow_mut().insert(bot_id_hash, public_key));
+}
+
+/// Bot 是否已被暂停（测试辅助）
+pub fn is_mock_bot_suspended(bot_id_hash: &[u8; 32]) -> bool {
+	SUSPENDED_BOTS.with(|s| s.borrow().contains(bot_id_hash))
+}
+
 pub struct MockBotRegistry;
 
 impl pallet_bot_consensus::BotRegistryProvider<u64> for MockBotRegistry {
@@ -67,6 +82,18 @@ impl pallet_bot_consensus::BotRegistryProvider<u64> for MockBotRegistry {
 			b.borrow().get(bot_id_hash).map(|o| o == who).unwrap_or(false)
 		})
 	}
+
+	fn bot_owner(bot_id_hash: &[u8; 32]) -> Option<u64> {
+		BOTS.with(|b| b.borrow().get(bot_id_hash).copied())
+	}
+
+	fn bot_public_key(bot_id_hash: &[u8; 32]) -> Option<[u8; 32]> {
+		BOT_KEYS.with(|k| k.borrow().get(bot_id_hash).copied())
+	}
+
+	fn suspend_bot(bot_id_hash: &[u8; 32]) {
+		SUSPENDED_BOTS.with(|s| s.borrow_mut().push(*bot_id_hash));
+	}
 }
 
 parameter_types! {
@@ -75,6 +102,7 @@ parameter_types! {
 	pub const MaxNodes: u32 = 100;
 	pub const SlashPercentage: u32 = 10;
 	pub const ReporterRewardPercentage: u32 = 50;
+	pub const EquivocationReportDeposit: u128 = 100;
 	pub const SuspendThreshold: u16 = 2000;
 	pub const ForceExitThreshold: u16 = 1000;
 	// 奖励参数
@@ -94,6 +122,7 @@ impl pallet_bot_consensus::Config for Test {
 	type MaxNodes = MaxNodes;
 	type SlashPercentage = SlashPercentage;
 	type ReporterRewardPercentage = ReporterRewardPercentage;
+	type EquivocationReportDeposit = EquivocationReportDeposit;
 	type SuspendThreshold = SuspendThreshold;
 	type ForceExitThreshold = ForceExitThreshold;
 	type EraLength = EraLength;
@@ -110,6 +139,8 @@ impl pallet_bot_consensus::Config for Test {
 pub fn new_test_ext() -> sp_io::TestExternalities {
 	// 清理 thread_local 状态
 	BOTS.with(|b| b.borrow_mut().clear());
+	BOT_KEYS.with(|k| k.borrow_mut().clear());
+	SUSPENDED_BOTS.with(|s| s.borrow_mut().clear());
 
 	let mut t = frame_system::GenesisConfig::<Test>::default()
 		.build_storage()
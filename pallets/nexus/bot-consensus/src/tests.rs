@@ -1,5 +1,9 @@
-use crate::{mock::*, Error, NodeStatus, NodeId, SubscriptionTier, SubscriptionStatus};
-use frame_support::{assert_ok, assert_noop, traits::Hooks};
+use crate::{migrations, mock::*, Error, NodeStatus, NodeId, SignedMessageProof, SubscriptionTier, SubscriptionStatus};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{GetStorageVersion, Hooks, OnRuntimeUpgrade, StorageVersion},
+};
+use sp_core::{ed25519, Pair};
 
 fn make_node_id(id: u8) -> NodeId {
 	let v: sp_std::vec::Vec<u8> = sp_std::vec![id; 4];
@@ -232,43 +236,90 @@ fn submit_confirmations_skips_duplicates() {
 	});
 }
 
+fn owner_pair() -> ed25519::Pair {
+	ed25519::Pair::from_seed(&[7u8; 32])
+}
+
+/// 构造群主签名的消息证明
+fn signed_proof(
+	pair: &ed25519::Pair,
+	bot_id_hash: [u8; 32],
+	sequence: u64,
+	timestamp: u64,
+	msg_hash: [u8; 32],
+) -> SignedMessageProof {
+	let proof = SignedMessageProof { timestamp, msg_hash, signature: [0u8; 64] };
+	let data = BotConsensus::owner_sign_data(&pair.public().0, &bot_id_hash, sequence, &proof);
+	SignedMessageProof { signature: pair.sign(&data).0, ..proof }
+}
+
+/// 注册带公钥的 Bot（owner = 2）并预存订阅 1000
+fn setup_equivocation_bot() -> ([u8; 32], ed25519::Pair) {
+	let bh = bot_hash(1);
+	let pair = owner_pair();
+	register_mock_bot_with_key(bh, 2, pair.public().0);
+	assert_ok!(BotConsensus::subscribe(
+		RuntimeOrigin::signed(2),
+		bh,
+		SubscriptionTier::Basic,
+		1000,
+	));
+	(bh, pair)
+}
+
 #[test]
 fn report_equivocation_works() {
 	new_test_ext().execute_with(|| {
-		let owner = 2u64;
+		System::set_block_number(1);
+		let (bh, pair) = setup_equivocation_bot();
 		let sequence = 42u64;
 
 		assert_ok!(BotConsensus::report_equivocation(
 			RuntimeOrigin::signed(1),
-			owner,
+			bh,
 			sequence,
-			[10u8; 32],
-			[11u8; 64],
-			[20u8; 32],
-			[21u8; 64],
+			signed_proof(&pair, bh, sequence, 1_700_000_000, [10u8; 32]),
+			signed_proof(&pair, bh, sequence, 1_700_000_001, [20u8; 32]),
 		));
 
-		// 记录存在
-		let record = BotConsensus::equivocation_records(owner, sequence).unwrap();
+		// 记录存在且已处理
+		let record = BotConsensus::equivocation_records(bh, sequence).unwrap();
+		assert_eq!(record.owner, 2);
 		assert_eq!(record.reporter, 1);
 		assert_eq!(record.msg_hash_a, [10u8; 32]);
 		assert_eq!(record.msg_hash_b, [20u8; 32]);
-		assert!(!record.resolved);
+		assert!(record.resolved);
+		assert_eq!(record.slashed, 100);
+
+		// Escrow 1000 × 10% = 100 被 Slash，举报者获得 50%，押金退还
+		assert_eq!(BotConsensus::subscription_escrow(&bh), 900);
+		assert_eq!(Balances::reserved_balance(2), 900);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 100_050);
+
+		// 订阅与 Bot 被暂停
+		assert_eq!(BotConsensus::subscriptions(&bh).unwrap().status, SubscriptionStatus::Suspended);
+		assert!(is_mock_bot_suspended(&bh));
+
+		System::assert_has_event(RuntimeEvent::BotConsensus(crate::Event::EquivocationSlashed {
+			owner: 2,
+			slash_amount: 100,
+			reporter_reward: 50,
+		}));
 	});
 }
 
 #[test]
 fn report_equivocation_fails_same_hash() {
 	new_test_ext().execute_with(|| {
+		let (bh, pair) = setup_equivocation_bot();
 		assert_noop!(
 			BotConsensus::report_equivocation(
 				RuntimeOrigin::signed(1),
-				2,
+				bh,
 				42,
-				[10u8; 32],
-				[11u8; 64],
-				[10u8; 32],  // 相同的 hash
-				[21u8; 64],
+				signed_proof(&pair, bh, 42, 1, [10u8; 32]),
+				signed_proof(&pair, bh, 42, 2, [10u8; 32]),  // 相同的 hash
 			),
 			Error::<Test>::InvalidEquivocationEvidence
 		);
@@ -278,25 +329,128 @@ fn report_equivocation_fails_same_hash() {
 #[test]
 fn report_equivocation_fails_duplicate() {
 	new_test_ext().execute_with(|| {
+		let (bh, pair) = setup_equivocation_bot();
 		assert_ok!(BotConsensus::report_equivocation(
 			RuntimeOrigin::signed(1),
-			2, 42,
-			[10u8; 32], [11u8; 64],
-			[20u8; 32], [21u8; 64],
+			bh, 42,
+			signed_proof(&pair, bh, 42, 1, [10u8; 32]),
+			signed_proof(&pair, bh, 42, 2, [20u8; 32]),
 		));
 
 		assert_noop!(
 			BotConsensus::report_equivocation(
 				RuntimeOrigin::signed(3),
-				2, 42,
-				[30u8; 32], [31u8; 64],
-				[40u8; 32], [41u8; 64],
+				bh, 42,
+				signed_proof(&pair, bh, 42, 3, [30u8; 32]),
+				signed_proof(&pair, bh, 42, 4, [40u8; 32]),
 			),
 			Error::<Test>::EquivocationAlreadyReported
 		);
 	});
 }
 
+#[test]
+fn report_equivocation_fails_unknown_bot() {
+	new_test_ext().execute_with(|| {
+		let pair = owner_pair();
+		let bh = bot_hash(9);
+		assert_noop!(
+			BotConsensus::report_equivocation(
+				RuntimeOrigin::signed(1),
+				bh, 1,
+				signed_proof(&pair, bh, 1, 1, [10u8; 32]),
+				signed_proof(&pair, bh, 1, 2, [20u8; 32]),
+			),
+			Error::<Test>::BotNotRegistered
+		);
+	});
+}
+
+#[test]
+fn invalid_equivocation_report_forfeits_deposit() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let (bh, pair) = setup_equivocation_bot();
+
+		// 伪造签名
+		let mut forged = signed_proof(&pair, bh, 42, 2, [20u8; 32]);
+		forged.signature = [21u8; 64];
+		assert_ok!(BotConsensus::report_equivocation(
+			RuntimeOrigin::signed(3),
+			bh, 42,
+			signed_proof(&pair, bh, 42, 1, [10u8; 32]),
+			forged,
+		));
+
+		// 他人密钥签名
+		let other = ed25519::Pair::from_seed(&[8u8; 32]);
+		assert_ok!(BotConsensus::report_equivocation(
+			RuntimeOrigin::signed(3),
+			bh, 42,
+			signed_proof(&pair, bh, 42, 1, [10u8; 32]),
+			signed_proof(&other, bh, 42, 2, [20u8; 32]),
+		));
+
+		// 签名的 sequence 与举报的 sequence 不一致
+		assert_ok!(BotConsensus::report_equivocation(
+			RuntimeOrigin::signed(3),
+			bh, 42,
+			signed_proof(&pair, bh, 42, 1, [10u8; 32]),
+			signed_proof(&pair, bh, 43, 2, [20u8; 32]),
+		));
+
+		// 三次押金均被罚没，群主不受影响
+		assert_eq!(Balances::free_balance(3), 100_000 - 300);
+		assert_eq!(Balances::reserved_balance(3), 0);
+		assert_eq!(BotConsensus::subscription_escrow(&bh), 1000);
+		assert!(BotConsensus::equivocation_records(bh, 42).is_none());
+		assert!(!is_mock_bot_suspended(&bh));
+
+		System::assert_has_event(RuntimeEvent::BotConsensus(crate::Event::InvalidEquivocationReport {
+			reporter: 3,
+			bot_id_hash: bh,
+			sequence: 42,
+			deposit_slashed: 100,
+		}));
+	});
+}
+
+#[test]
+fn report_equivocation_rejects_own_bot() {
+	new_test_ext().execute_with(|| {
+		let (bh, pair) = setup_equivocation_bot();
+		// Bot 所有者（同时是订阅者）举报自己
+		assert_noop!(
+			BotConsensus::report_equivocation(
+				RuntimeOrigin::signed(2),
+				bh, 42,
+				signed_proof(&pair, bh, 42, 1, [10u8; 32]),
+				signed_proof(&pair, bh, 42, 2, [20u8; 32]),
+			),
+			Error::<Test>::CannotReportOwnBot
+		);
+		assert_eq!(BotConsensus::subscription_escrow(&bh), 1000);
+		assert!(!is_mock_bot_suspended(&bh));
+	});
+}
+
+#[test]
+fn report_equivocation_requires_deposit() {
+	new_test_ext().execute_with(|| {
+		let (bh, pair) = setup_equivocation_bot();
+		// 账户 9 无余额
+		assert_noop!(
+			BotConsensus::report_equivocation(
+				RuntimeOrigin::signed(9),
+				bh, 42,
+				signed_proof(&pair, bh, 42, 1, [10u8; 32]),
+				signed_proof(&pair, bh, 42, 2, [20u8; 32]),
+			),
+			Error::<Test>::InsufficientReportDeposit
+		);
+	});
+}
+
 #[test]
 fn report_node_offline_reduces_reputation() {
 	new_test_ext().execute_with(|| {
@@ -976,3 +1130,32 @@ fn deposit_reactivates_past_due_subscription() {
 		assert_eq!(BotConsensus::subscriptions(&bh).unwrap().status, SubscriptionStatus::Active);
 	});
 }
+
+#[test]
+fn migration_v1_clears_legacy_equivocation_records() {
+	new_test_ext().execute_with(|| {
+		let (bh, pair) = setup_equivocation_bot();
+		assert_ok!(BotConsensus::report_equivocation(
+			RuntimeOrigin::signed(1),
+			bh, 42,
+			signed_proof(&pair, bh, 42, 1, [10u8; 32]),
+			signed_proof(&pair, bh, 42, 2, [20u8; 32]),
+		));
+		StorageVersion::new(0).put::<BotConsensus>();
+
+		migrations::v1::MigrateV0ToV1::<Test>::on_runtime_upgrade();
+
+		assert!(BotConsensus::equivocation_records(bh, 42).is_none());
+		assert_eq!(BotConsensus::on_chain_storage_version(), StorageVersion::new(1));
+
+		// 已是 v1 时不再执行
+		assert_ok!(BotConsensus::report_equivocation(
+			RuntimeOrigin::signed(1),
+			bh, 43,
+			signed_proof(&pair, bh, 43, 1, [10u8; 32]),
+			signed_proof(&pair, bh, 43, 2, [20u8; 32]),
+		));
+		migrations::v1::MigrateV0ToV1::<Test>::on_runtime_upgrade();
+		assert!(BotConsensus::equivocation_records(bh, 43).is_some());
+	});
+}
@@ -583,6 +583,21 @@ pub mod pallet {
 			Bots::<T>::get(bot_id_hash).map(|b| b.owner)
 		}
 
+		/// 因违规暂停 Bot（供 pallet-bot-consensus 在 Equivocation 确认后调用）
+		///
+		/// 仅 Active 状态的 Bot 会被暂停；owner 可通过 `reactivate_bot` 恢复。
+		pub fn suspend_for_misbehavior(bot_id_hash: &[u8; 32]) {
+			Bots::<T>::mutate(bot_id_hash, |maybe_bot| {
+				if let Some(bot) = maybe_bot {
+					if bot.status == BotStatus::Active {
+						bot.status = BotStatus::Suspended;
+						bot.updated_at = <frame_system::Pallet<T>>::block_number();
+						Self::deposit_event(Event::BotSuspended { bot_id_hash: *bot_id_hash });
+					}
+				}
+			});
+		}
+
 		/// 获取 Bot 的哈希盐值
 		pub fn get_bot_salt(bot_id_hash: &[u8; 32]) -> Option<[u8; 16]> {
 			Bots::<T>::get(bot_id_hash).map(|b| b.hash_salt)
@@ -0,0 +1,177 @@
+//! # 归档批次序列化与 Merkle 承诺
+//!
+//! 归档前的完整记录按批次序列化为 `ArchiveBlob`（SCALE 编码）上传 IPFS，链上只保留批次 Merkle 根。
+//!
+//! ## 叶子
+//! 每条记录的叶子字节为 `id(u64 LE) ‖ record.encode()`，即 `(id, record).encode()`，
+//! 持有者可直接按 `(u64, 原记录类型)` 解码。
+//!
+//! ## Merkle 规则
+//! 与 `pallet_storage_service::merkle` 一致：叶子 `H(0x00‖leaf)`，节点 `H(0x01‖l‖r)`，
+//! 奇数层末尾节点直接晋升。
+//!
+//! ## 证明流程（链下）
+//! 1. 按承诺中的 CID 从 IPFS 取回内容，`ArchiveBlob::decode`
+//! 2. `ArchiveBlob::prove::<Hashing>(record_id)` 得到（序号, 叶子, 路径）
+//! 3. 调用 `verify_archived_record`（extrinsic 或 runtime API）
+
+use codec::{Decode, Encode};
+use pallet_storage_service::merkle;
+use sp_runtime::traits::Hash;
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+/// 归档内容格式版本
+pub const ARCHIVE_BLOB_VERSION: u8 = 1;
+
+/// 单批内容的头部预留字节（版本、数据类型、批次ID、长度前缀）
+const BLOB_HEADER_RESERVE: usize = 64;
+
+/// 每条叶子的长度前缀预留字节（Compact<u32> 最多 5 字节）
+const LEAF_PREFIX_RESERVE: usize = 5;
+
+/// 归档批次内容（上传 IPFS 的原文）
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct ArchiveBlob {
+    /// 格式版本
+    pub version: u8,
+    /// 数据类型标识
+    pub data_type: Vec<u8>,
+    /// 批次ID（与链上承诺一致，保证不同批次内容不同）
+    pub batch_id: u64,
+    /// 叶子字节，顺序即 Merkle 叶子序号
+    pub leaves: Vec<Vec<u8>>,
+}
+
+/// 函数级中文注释：构造记录的叶子字节（`id LE ‖ record`）
+pub fn leaf_bytes(id: u64, record: &[u8]) -> Vec<u8> {
+    let mut leaf = Vec::with_capacity(8 + record.len());
+    leaf.extend_from_slice(&id.to_le_bytes());
+    leaf.extend_from_slice(record);
+    leaf
+}
+
+/// 函数级中文注释：从叶子字节读取记录ID
+pub fn leaf_id(leaf: &[u8]) -> Option<u64> {
+    let bytes: [u8; 8] = leaf.get(..8)?.try_into().ok()?;
+    Some(u64::from_le_bytes(bytes))
+}
+
+impl ArchiveBlob {
+    /// 由（记录ID, 编码后记录）构造批次内容
+    pub fn new(data_type: Vec<u8>, batch_id: u64, entries: &[(u64, Vec<u8>)]) -> Self {
+        Self {
+            version: ARCHIVE_BLOB_VERSION,
+            data_type,
+            batch_id,
+            leaves: entries.iter().map(|(id, record)| leaf_bytes(*id, record)).collect(),
+        }
+    }
+
+    /// 叶子哈希
+    pub fn leaf_hashes<H: Hash>(&self) -> Vec<H::Output> {
+        self.leaves.iter().map(|leaf| merkle::leaf_hash::<H>(leaf)).collect()
+    }
+
+    /// 批次 Merkle 根（空批次返回 None）
+    pub fn root<H: Hash>(&self) -> Option<H::Output> {
+        merkle::merkle_root::<H>(&self.leaf_hashes::<H>())
+    }
+
+    /// 函数级中文注释：为指定记录生成证明，返回（叶子序号, 叶子字节, Merkle 路径）
+    pub fn prove<H: Hash>(&self, record_id: u64) -> Option<(u32, Vec<u8>, Vec<H::Output>)> {
+        let index = self.leaves.iter().position(|leaf| leaf_id(leaf) == Some(record_id))? as u32;
+        let proof = merkle::merkle_proof::<H>(&self.leaf_hashes::<H>(), index)?;
+        Some((index, self.leaves[index as usize].clone(), proof))
+    }
+}
+
+/// 函数级详细中文注释：将待归档记录切分为批次
+///
+/// 每批不超过 `max_count` 条，且序列化后不超过 `max_bytes`（保证单块上传、CID 可复现）；
+/// 单条记录本身超过 `max_bytes` 时独占一批。
+pub fn split_batches(
+    entries: Vec<(u64, Vec<u8>)>,
+    max_count: u32,
+    max_bytes: usize,
+) -> Vec<Vec<(u64, Vec<u8>)>> {
+    let max_count = max_count.max(1) as usize;
+    let mut batches = Vec::new();
+    let mut current: Vec<(u64, Vec<u8>)> = Vec::new();
+    let mut size = BLOB_HEADER_RESERVE;
+    for (id, record) in entries {
+        let leaf_size = 8 + record.len() + LEAF_PREFIX_RESERVE;
+        if !current.is_empty() && (current.len() >= max_count || size + leaf_size > max_bytes) {
+            batches.push(core::mem::take(&mut current));
+            size = BLOB_HEADER_RESERVE;
+        }
+        size += leaf_size;
+        current.push((id, record));
+    }
+    if !current.is_empty() {
+        batches.push(current);
+    }
+    batches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sp_runtime::traits::BlakeTwo256;
+
+    fn entries(n: u64) -> Vec<(u64, Vec<u8>)> {
+        (10..10 + n).map(|id| (id, (id as u32).encode())).collect()
+    }
+
+    #[test]
+    fn blob_roundtrip_and_prove_every_record() {
+        let blob = ArchiveBlob::new(b"maker_penalty".to_vec(), 3, &entries(7));
+        let decoded = ArchiveBlob::decode(&mut &blob.encode()[..]).unwrap();
+        assert_eq!(decoded, blob);
+
+        let root = blob.root::<BlakeTwo256>().unwrap();
+        for id in 10..17u64 {
+            let (index, leaf, proof) = decoded.prove::<BlakeTwo256>(id).unwrap();
+            // 叶子即 `(id, record).encode()`
+            assert_eq!(<(u64, u32)>::decode(&mut &leaf[..]).unwrap(), (id, id as u32));
+            assert!(merkle::verify_proof::<BlakeTwo256>(
+                &root,
+                merkle::leaf_hash::<BlakeTwo256>(&leaf),
+                index,
+                7,
+                &proof,
+            ));
+        }
+        assert!(blob.prove::<BlakeTwo256>(99).is_none());
+    }
+
+    #[test]
+    fn tampered_record_fails_verification() {
+        let blob = ArchiveBlob::new(b"arb_complaint".to_vec(), 1, &entries(4));
+        let root = blob.root::<BlakeTwo256>().unwrap();
+        let (index, mut leaf, proof) = blob.prove::<BlakeTwo256>(12).unwrap();
+        *leaf.last_mut().unwrap() ^= 1;
+        assert!(!merkle::verify_proof::<BlakeTwo256>(
+            &root,
+            merkle::leaf_hash::<BlakeTwo256>(&leaf),
+            index,
+            4,
+            &proof,
+        ));
+    }
+
+    #[test]
+    fn split_respects_count_and_size() {
+        let batches = split_batches(entries(25), 10, usize::MAX);
+        assert_eq!(batches.iter().map(|b| b.len()).collect::<Vec<_>>(), vec![10, 10, 5]);
+
+        // 每条 8 + 4 + 5 = 17 字节，头部 64：上限 64 + 17 * 3 → 每批 3 条
+        let batches = split_batches(entries(7), 100, 64 + 17 * 3);
+        assert_eq!(batches.iter().map(|b| b.len()).collect::<Vec<_>>(), vec![3, 3, 1]);
+
+        // 超大记录独占一批
+        let batches = split_batches(vec![(1, vec![0u8; 500])], 100, 100);
+        assert_eq!(batches.len(), 1);
+        assert!(split_batches(Vec::new(), 100, 100).is_empty());
+    }
+}
@@ -11,15 +11,78 @@
 //! - L1归档：保留核心字段，压缩存储（~50-80%节省）
 //! - L2归档：仅保留统计摘要（~90%+节省）
 //! - 清除：完全删除，仅保留永久统计
+//!
+//! ## IPFS 归档承诺
+//! 业务模块在丢弃完整记录前通过 `ArchiveCommitter` 提交整批记录：
+//! - 批次序列化为 `archive::ArchiveBlob`，计算确定性 CID（raw / blake2b-256），
+//!   经 offchain 索引交给存储服务 OCW 上传，并通过 `ContentRegistry` 登记 Pin
+//! - 链上仅保留 `ArchiveCommitments`（Merkle 根、CID、ID 范围）
+//! - 任何持有归档内容的人可用 `verify_archived_record` 证明某条历史记录
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
 pub use pallet::*;
 
+pub mod archive;
+pub mod runtime_api;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
 use codec::{Decode, Encode};
 use frame_support::pallet_prelude::*;
-use sp_runtime::traits::Saturating;
-use sp_std::marker::PhantomData;
+use pallet_storage_service::{cid, cluster, merkle, ContentRegistry, PinTier};
+use sp_runtime::traits::{SaturatedConversion, Saturating};
+use sp_std::{marker::PhantomData, vec::Vec};
+
+use archive::ArchiveBlob;
+
+/// 数据类型标识
+pub type DataType = BoundedVec<u8, ConstU32<32>>;
+
+/// 归档内容在存储服务中登记的域
+pub const ARCHIVE_PIN_DOMAIN: &[u8] = b"lifecycle-archive";
+
+/// Merkle 路径最大长度（支持 2^32 叶子）
+pub const MAX_PROOF_LEN: u32 = 32;
+
+/// 每批承诺的 Pin 登记计算开销（与 `retry_archive_pin` 一致）
+const PIN_REF_TIME: u64 = 50_000_000;
+
+/// 每条记录的叶子哈希与 Merkle 建树开销
+const RECORD_REF_TIME: u64 = 2_000_000;
+
+/// 每字节的序列化、CID 与叶子哈希开销
+const BYTE_REF_TIME: u64 = 3_000;
+
+/// 归档承诺提交接口（供业务 pallet 在丢弃完整记录前调用）
+pub trait ArchiveCommitter {
+    /// 函数级详细中文注释：提交一批待归档记录
+    ///
+    /// - `data_type`: 数据类型标识（≤32 字节，如 `b"maker_penalty"`）
+    /// - `entries`: （记录ID, 完整记录 SCALE 编码），按 ID 升序
+    ///
+    /// 记录按 `MaxBatchSize` 与单块大小切分为若干批次，每批生成一条承诺。
+    fn commit_archive(data_type: &[u8], entries: Vec<(u64, Vec<u8>)>) -> DispatchResult;
+
+    /// 函数级详细中文注释：提交 `count` 条、共 `bytes` 字节记录的权重上界
+    ///
+    /// 调用方在归档钩子返回的权重中计入此项（含批次存储读写、Pin 登记与哈希计算）。
+    fn commit_weight(count: u32, bytes: u32) -> Weight;
+}
+
+/// 空实现：不生成归档承诺（测试或未启用 IPFS 归档的运行时）
+impl ArchiveCommitter for () {
+    fn commit_archive(_data_type: &[u8], _entries: Vec<(u64, Vec<u8>)>) -> DispatchResult {
+        Ok(())
+    }
+
+    fn commit_weight(_count: u32, _bytes: u32) -> Weight {
+        Weight::zero()
+    }
+}
 
 /// 可归档数据 Trait
 ///
@@ -134,6 +197,27 @@ pub struct ArchiveBatch {
     pub level: u8,
 }
 
+/// 归档批次承诺（链上仅保留此摘要，原文在 IPFS）
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ArchiveCommitment<Hash> {
+    /// 批次记录的 Merkle 根
+    pub root: Hash,
+    /// 批次内容的 CID（CIDv1 raw / blake2b-256，base32 文本）
+    pub cid: BoundedVec<u8, ConstU32<64>>,
+    /// 数据ID范围起始
+    pub id_start: u64,
+    /// 数据ID范围结束
+    pub id_end: u64,
+    /// 记录数（即 Merkle 叶子数）
+    pub count: u32,
+    /// 提交时间（区块号）
+    pub committed_at: u64,
+    /// 在存储服务中登记 Pin 使用的 subject_id（全局唯一，跨数据类型不重复）
+    pub subject_id: u64,
+    /// 是否已成功登记 Pin
+    pub pinned: bool,
+}
+
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
@@ -163,9 +247,12 @@ pub mod pallet {
         #[pallet::constant]
         type EnablePurge: Get<bool>;
 
-        /// 每次on_idle最大处理数量
+        /// 每次on_idle最大处理数量（同时是每个归档承诺批次的最大记录数）
         #[pallet::constant]
         type MaxBatchSize: Get<u32>;
+
+        /// 归档内容 Pin 登记接口
+        type ContentRegistry: ContentRegistry;
     }
 
     /// 归档游标（按数据类型）
@@ -201,6 +288,35 @@ pub mod pallet {
         ValueQuery,
     >;
 
+    /// 归档承诺：(数据类型, 批次ID) → 承诺
+    #[pallet::storage]
+    #[pallet::getter(fn archive_commitment)]
+    pub type ArchiveCommitments<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        DataType,
+        Twox64Concat,
+        u64,
+        ArchiveCommitment<T::Hash>,
+        OptionQuery,
+    >;
+
+    /// 最近一次分配的归档 Pin subject_id（全局，从 1 开始）
+    ///
+    /// 批次ID按数据类型各自计数，不能直接作为存储服务的 subject_id。
+    #[pallet::storage]
+    pub type LastArchiveSubjectId<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+    /// 最近一次分配的承诺批次ID（按数据类型，从 1 开始）
+    #[pallet::storage]
+    pub type LastCommitmentId<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        DataType,
+        u64,
+        ValueQuery,
+    >;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -227,6 +343,26 @@ pub mod pallet {
             batch_id: u64,
             level: u8, // 0=Active, 1=L1, 2=L2, 3=Purged
         },
+        /// 归档批次已提交承诺
+        ArchiveCommitted {
+            data_type: DataType,
+            batch_id: u64,
+            root: T::Hash,
+            cid: BoundedVec<u8, ConstU32<64>>,
+            count: u32,
+            pinned: bool,
+        },
+        /// 归档内容已（重新）登记 Pin
+        ArchivePinned {
+            data_type: DataType,
+            batch_id: u64,
+        },
+        /// 历史记录已通过承诺验证
+        ArchivedRecordVerified {
+            data_type: DataType,
+            batch_id: u64,
+            record_id: u64,
+        },
     }
 
     #[pallet::error]
@@ -239,6 +375,72 @@ pub mod pallet {
         DataNotFound,
         /// 数据状态不允许归档
         InvalidArchiveState,
+        /// 归档承诺不存在
+        CommitmentNotFound,
+        /// 归档内容已登记 Pin
+        AlreadyPinned,
+        /// 记录或 Merkle 路径与承诺不符
+        InvalidProof,
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// 函数级详细中文注释：重新登记归档内容的 Pin
+        ///
+        /// 提交承诺时 Pin 登记失败（如暂无可用运营者）的批次，任何人可在条件恢复后重试。
+        /// 原文仍保存在节点的 offchain 存储中，由存储服务 OCW 上传。
+        #[pallet::call_index(0)]
+        #[pallet::weight(T::DbWeight::get().reads_writes(2, 10).saturating_add(Weight::from_parts(50_000_000, 0)))]
+        pub fn retry_archive_pin(
+            origin: OriginFor<T>,
+            data_type: DataType,
+            batch_id: u64,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+            let mut commitment = ArchiveCommitments::<T>::get(&data_type, batch_id)
+                .ok_or(Error::<T>::CommitmentNotFound)?;
+            ensure!(!commitment.pinned, Error::<T>::AlreadyPinned);
+
+            Pallet::<T>::pin_archive(&commitment.cid, commitment.subject_id)?;
+            commitment.pinned = true;
+            ArchiveCommitments::<T>::insert(&data_type, batch_id, commitment);
+            Self::deposit_event(Event::ArchivePinned { data_type, batch_id });
+            Ok(())
+        }
+
+        /// 函数级详细中文注释：链上证明一条历史记录
+        ///
+        /// - `index`: 叶子序号
+        /// - `leaf`: 叶子字节（`(record_id, record).encode()`）
+        /// - `proof`: 自底向上的 Merkle 路径
+        ///
+        /// 验证通过发出 `ArchivedRecordVerified`，供争议、申诉等流程引用。
+        #[pallet::call_index(1)]
+        #[pallet::weight(T::DbWeight::get().reads(1).saturating_add(Weight::from_parts(
+            10_000_000u64.saturating_add(2_000_000u64.saturating_mul(proof.len() as u64)),
+            0,
+        )))]
+        pub fn verify_archived_record(
+            origin: OriginFor<T>,
+            data_type: DataType,
+            batch_id: u64,
+            index: u32,
+            leaf: BoundedVec<u8, ConstU32<4096>>,
+            proof: BoundedVec<T::Hash, ConstU32<MAX_PROOF_LEN>>,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+            ensure!(
+                ArchiveCommitments::<T>::contains_key(&data_type, batch_id),
+                Error::<T>::CommitmentNotFound
+            );
+            ensure!(
+                Pallet::<T>::verify_record(&data_type, batch_id, index, &leaf, &proof),
+                Error::<T>::InvalidProof
+            );
+            let record_id = archive::leaf_id(&leaf).ok_or(Error::<T>::InvalidProof)?;
+            Self::deposit_event(Event::ArchivedRecordVerified { data_type, batch_id, record_id });
+            Ok(())
+        }
     }
 }
 
@@ -372,6 +574,127 @@ impl<T: Config> StorageLifecycleManager<T> {
     }
 }
 
+// ==================== IPFS 归档承诺 ====================
+
+impl<T: Config> Pallet<T> {
+    /// 函数级详细中文注释：提交归档批次承诺
+    ///
+    /// 每批：
+    /// 1. 分配批次ID，序列化为 `ArchiveBlob` 并计算 Merkle 根
+    /// 2. 计算确定性 CID，原文写入 offchain 索引（`/memo/ipfs/blob/<cid>`），由存储服务 OCW 上传
+    /// 3. 分配全局唯一的 subject_id，通过 `ContentRegistry` 以 Critical 等级登记 Pin；
+    ///    失败时保留承诺，可经 `retry_archive_pin` 重试
+    /// 4. 写入 `ArchiveCommitments` 并发出 `ArchiveCommitted`
+    pub fn commit_archive_batches(data_type: &[u8], entries: Vec<(u64, Vec<u8>)>) -> DispatchResult {
+        let key: DataType = data_type.to_vec().try_into().map_err(|_| Error::<T>::DataTypeTooLong)?;
+        let now: u64 = frame_system::Pallet::<T>::block_number().saturated_into();
+
+        for batch in archive::split_batches(entries, T::MaxBatchSize::get(), cid::MAX_RAW_BLOCK_SIZE) {
+            let (Some((id_start, _)), Some((id_end, _))) = (batch.first(), batch.last()) else {
+                continue;
+            };
+            let (id_start, id_end) = (*id_start, *id_end);
+            let batch_id = LastCommitmentId::<T>::mutate(&key, |id| {
+                *id = id.saturating_add(1);
+                *id
+            });
+
+            let blob = ArchiveBlob::new(key.to_vec(), batch_id, &batch);
+            let root = blob.root::<T::Hashing>().ok_or(Error::<T>::DataNotFound)?;
+            let encoded = blob.encode();
+            let blob_cid: BoundedVec<u8, ConstU32<64>> = cid::raw_cid(&encoded)
+                .try_into()
+                .map_err(|_| Error::<T>::InvalidArchiveState)?;
+            sp_io::offchain_index::set(&cluster::blob_key(&blob_cid), &encoded);
+
+            let subject_id = LastArchiveSubjectId::<T>::mutate(|id| {
+                *id = id.saturating_add(1);
+                *id
+            });
+            let pinned = Self::pin_archive(&blob_cid, subject_id).is_ok();
+            let count = batch.len() as u32;
+            ArchiveCommitments::<T>::insert(&key, batch_id, ArchiveCommitment {
+                root,
+                cid: blob_cid.clone(),
+                id_start,
+                id_end,
+                count,
+                committed_at: now,
+                subject_id,
+                pinned,
+            });
+            ArchiveStats::<T>::mutate(&key, |stats| stats.last_archive_at = now);
+            Self::deposit_event(Event::ArchiveCommitted {
+                data_type: key.clone(),
+                batch_id,
+                root,
+                cid: blob_cid,
+                count,
+                pinned,
+            });
+        }
+        Ok(())
+    }
+
+    /// 登记归档内容 Pin；失败时回滚存储服务中的部分写入
+    fn pin_archive(blob_cid: &[u8], subject_id: u64) -> DispatchResult {
+        frame_support::storage::with_storage_layer(|| {
+            T::ContentRegistry::register_content(
+                ARCHIVE_PIN_DOMAIN.to_vec(),
+                subject_id,
+                blob_cid.to_vec(),
+                PinTier::Critical,
+            )
+        })
+    }
+
+    /// 函数级详细中文注释：验证叶子属于指定批次承诺
+    ///
+    /// 供 extrinsic 与 runtime API 共用；承诺不存在、序号越界或路径不符均返回 false。
+    pub fn verify_record(data_type: &[u8], batch_id: u64, index: u32, leaf: &[u8], proof: &[T::Hash]) -> bool {
+        let Ok(key) = DataType::try_from(data_type.to_vec()) else {
+            return false;
+        };
+        let Some(commitment) = ArchiveCommitments::<T>::get(&key, batch_id) else {
+            return false;
+        };
+        merkle::verify_proof::<T::Hashing>(
+            &commitment.root,
+            merkle::leaf_hash::<T::Hashing>(leaf),
+            index,
+            commitment.count,
+            proof,
+        )
+    }
+}
+
+impl<T: Config> ArchiveCommitter for Pallet<T> {
+    fn commit_archive(data_type: &[u8], entries: Vec<(u64, Vec<u8>)>) -> DispatchResult {
+        Self::commit_archive_batches(data_type, entries)
+    }
+
+    fn commit_weight(count: u32, bytes: u32) -> Weight {
+        if count == 0 {
+            return Weight::zero();
+        }
+        // 批次数上界：按条数切分 + 按单块大小切分
+        let batches = (count / T::MaxBatchSize::get().max(1)) as u64
+            + (bytes as u64) / (cid::MAX_RAW_BLOCK_SIZE as u64).max(1)
+            + 1;
+        let per_batch = T::DbWeight::get()
+            .reads_writes(4, 14)
+            .saturating_add(Weight::from_parts(PIN_REF_TIME, 0));
+        per_batch
+            .saturating_mul(batches)
+            .saturating_add(Weight::from_parts(
+                RECORD_REF_TIME
+                    .saturating_mul(count as u64)
+                    .saturating_add(BYTE_REF_TIME.saturating_mul(bytes as u64)),
+                0,
+            ))
+    }
+}
+
 /// 辅助函数：将区块号转换为年月格式
 pub fn block_to_year_month(block_number: u32, blocks_per_day: u32) -> u16 {
     // 假设创世区块是2024年1月
@@ -0,0 +1,97 @@
+//! # Storage Lifecycle Pallet Mock Runtime
+//!
+//! 单元测试用 mock 环境（IPFS 归档承诺）。
+
+use crate as pallet_storage_lifecycle;
+use frame_support::{
+    derive_impl,
+    traits::{ConstBool, ConstU32},
+};
+use pallet_storage_service::{ContentRegistry, PinTier, SubjectType};
+use sp_runtime::{BuildStorage, DispatchError, DispatchResult};
+use std::cell::RefCell;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+/// 每批承诺最大记录数
+pub const MAX_BATCH_SIZE: u32 = 4;
+
+frame_support::construct_runtime!(
+    pub enum Test {
+        System: frame_system,
+        Lifecycle: pallet_storage_lifecycle,
+    }
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+    type Block = Block;
+}
+
+// ==================== Mock ContentRegistry ====================
+
+thread_local! {
+    /// Pin 登记是否失败（模拟暂无可用运营者）
+    pub static PIN_FAILS: RefCell<bool> = RefCell::new(false);
+    /// 已登记的 (domain, subject_id, cid)
+    pub static REGISTERED: RefCell<Vec<(Vec<u8>, u64, Vec<u8>)>> = RefCell::new(Vec::new());
+}
+
+pub fn set_pin_fails(fails: bool) {
+    PIN_FAILS.with(|f| *f.borrow_mut() = fails);
+}
+
+pub fn registered() -> Vec<(Vec<u8>, u64, Vec<u8>)> {
+    REGISTERED.with(|r| r.borrow().clone())
+}
+
+pub struct MockContentRegistry;
+impl ContentRegistry for MockContentRegistry {
+    fn register_content(domain: Vec<u8>, subject_id: u64, cid: Vec<u8>, _tier: PinTier) -> DispatchResult {
+        if PIN_FAILS.with(|f| *f.borrow()) {
+            return Err(DispatchError::Other("NoOperator"));
+        }
+        REGISTERED.with(|r| r.borrow_mut().push((domain, subject_id, cid)));
+        Ok(())
+    }
+
+    fn is_domain_registered(_domain: &[u8]) -> bool {
+        true
+    }
+
+    fn get_domain_subject_type(_domain: &[u8]) -> Option<SubjectType> {
+        None
+    }
+
+    fn unregister_content(_domain: Vec<u8>, _cid: Vec<u8>) -> DispatchResult {
+        Ok(())
+    }
+}
+
+// ==================== Lifecycle Config ====================
+
+impl pallet_storage_lifecycle::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type L1ArchiveDelay = ConstU32<100>;
+    type L2ArchiveDelay = ConstU32<100>;
+    type PurgeDelay = ConstU32<100>;
+    type EnablePurge = ConstBool<false>;
+    type MaxBatchSize = ConstU32<MAX_BATCH_SIZE>;
+    type ContentRegistry = MockContentRegistry;
+}
+
+// ==================== Test Helpers ====================
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let t = frame_system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .unwrap();
+
+    let mut ext = sp_io::TestExternalities::new(t);
+    ext.execute_with(|| {
+        System::set_block_number(1);
+        set_pin_fails(false);
+        REGISTERED.with(|r| r.borrow_mut().clear());
+    });
+    ext
+}
@@ -0,0 +1,42 @@
+//! Runtime API 定义：归档承诺查询与历史记录验证
+//!
+//! 提供以下接口：
+//! - `archive_commitment`: 查询归档批次承诺（Merkle 根、CID、ID 范围）
+//! - `verify_archived_record`: 验证记录属于指定批次（不上链、无手续费）
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+use crate::ArchiveCommitment;
+
+sp_api::decl_runtime_apis! {
+    /// 存储生命周期 Runtime API
+    pub trait StorageLifecycleApi<Hash>
+    where
+        Hash: Codec,
+    {
+        /// 查询归档批次承诺
+        ///
+        /// ### 参数
+        /// - `data_type`: 数据类型标识（如 `b"maker_penalty"`）
+        /// - `batch_id`: 批次ID
+        fn archive_commitment(data_type: Vec<u8>, batch_id: u64) -> Option<ArchiveCommitment<Hash>>;
+
+        /// 验证历史记录
+        ///
+        /// ### 参数
+        /// - `index`: 叶子序号
+        /// - `leaf`: 叶子字节（`(record_id, record).encode()`）
+        /// - `proof`: 自底向上的 Merkle 路径
+        ///
+        /// ### 返回
+        /// - 记录与路径是否与链上承诺一致
+        fn verify_archived_record(
+            data_type: Vec<u8>,
+            batch_id: u64,
+            index: u32,
+            leaf: Vec<u8>,
+            proof: Vec<Hash>,
+        ) -> bool;
+    }
+}
@@ -0,0 +1,203 @@
+//! Unit tests for pallet-storage-lifecycle（IPFS 归档承诺）
+
+use crate::{
+    archive::ArchiveBlob, mock::*, ArchiveCommitments, ArchiveCommitter, DataType, Error, Event,
+    LastArchiveSubjectId, LastCommitmentId, ARCHIVE_PIN_DOMAIN,
+};
+use codec::Encode;
+use frame_support::{assert_noop, assert_ok, weights::Weight, BoundedVec};
+use sp_runtime::{traits::BlakeTwo256, DispatchError};
+
+const PENALTY: &[u8] = b"maker_penalty";
+const COMPLAINT: &[u8] = b"arb_complaint";
+
+fn data_type(raw: &[u8]) -> DataType {
+    raw.to_vec().try_into().unwrap()
+}
+
+/// 记录 ID 从 `first` 开始的 `n` 条 (id, u32 记录)
+fn entries(first: u64, n: u64) -> Vec<(u64, Vec<u8>)> {
+    (first..first + n).map(|id| (id, (id as u32).encode())).collect()
+}
+
+/// 按链上同样的规则重建批次内容（持有归档原文的一方）
+fn rebuild(raw: &[u8], batch_id: u64, batch: &[(u64, Vec<u8>)]) -> ArchiveBlob {
+    ArchiveBlob::new(raw.to_vec(), batch_id, batch)
+}
+
+fn verify(
+    raw: &[u8],
+    batch_id: u64,
+    index: u32,
+    leaf: Vec<u8>,
+    proof: Vec<sp_core::H256>,
+) -> frame_support::dispatch::DispatchResult {
+    Lifecycle::verify_archived_record(
+        RuntimeOrigin::signed(1),
+        data_type(raw),
+        batch_id,
+        index,
+        leaf.try_into().unwrap(),
+        BoundedVec::truncate_from(proof),
+    )
+}
+
+// ==================== 承诺提交 ====================
+
+#[test]
+fn commit_splits_batches_and_records_commitments() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(<Lifecycle as ArchiveCommitter>::commit_archive(PENALTY, entries(10, 6)));
+
+        // MaxBatchSize = 4 → 两批：10..=13, 14..=15
+        assert_eq!(LastCommitmentId::<Test>::get(data_type(PENALTY)), 2);
+        let first = ArchiveCommitments::<Test>::get(data_type(PENALTY), 1).unwrap();
+        let second = ArchiveCommitments::<Test>::get(data_type(PENALTY), 2).unwrap();
+        assert_eq!((first.id_start, first.id_end, first.count), (10, 13, 4));
+        assert_eq!((second.id_start, second.id_end, second.count), (14, 15, 2));
+        assert!(first.pinned && second.pinned);
+
+        let blob = rebuild(PENALTY, 1, &entries(10, 4));
+        assert_eq!(first.root, blob.root::<BlakeTwo256>().unwrap());
+        assert_eq!(first.cid.to_vec(), pallet_storage_service::cid::raw_cid(&blob.encode()));
+
+        System::assert_has_event(RuntimeEvent::Lifecycle(Event::ArchiveCommitted {
+            data_type: data_type(PENALTY),
+            batch_id: 2,
+            root: second.root,
+            cid: second.cid.clone(),
+            count: 2,
+            pinned: true,
+        }));
+    });
+}
+
+#[test]
+fn pin_subject_id_is_unique_across_data_types() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(<Lifecycle as ArchiveCommitter>::commit_archive(PENALTY, entries(1, 1)));
+        assert_ok!(<Lifecycle as ArchiveCommitter>::commit_archive(COMPLAINT, entries(1, 1)));
+
+        // 两种数据类型的批次ID都是 1，Pin subject_id 不同
+        let penalty = ArchiveCommitments::<Test>::get(data_type(PENALTY), 1).unwrap();
+        let complaint = ArchiveCommitments::<Test>::get(data_type(COMPLAINT), 1).unwrap();
+        assert_eq!((penalty.subject_id, complaint.subject_id), (1, 2));
+        assert_eq!(LastArchiveSubjectId::<Test>::get(), 2);
+
+        let pins = registered();
+        assert!(pins.iter().all(|(domain, _, _)| domain.as_slice() == ARCHIVE_PIN_DOMAIN));
+        assert_eq!(pins.iter().map(|(_, subject, _)| *subject).collect::<Vec<_>>(), vec![1, 2]);
+    });
+}
+
+// ==================== 记录证明 ====================
+
+#[test]
+fn archived_record_proves_against_committed_root() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(<Lifecycle as ArchiveCommitter>::commit_archive(PENALTY, entries(10, 6)));
+
+        let blob = rebuild(PENALTY, 2, &entries(14, 2));
+        let (index, leaf, proof) = blob.prove::<BlakeTwo256>(15).unwrap();
+        assert!(Lifecycle::verify_record(PENALTY, 2, index, &leaf, &proof));
+
+        assert_ok!(verify(PENALTY, 2, index, leaf, proof));
+        System::assert_last_event(RuntimeEvent::Lifecycle(Event::ArchivedRecordVerified {
+            data_type: data_type(PENALTY),
+            batch_id: 2,
+            record_id: 15,
+        }));
+    });
+}
+
+#[test]
+fn proof_with_wrong_index_or_leaf_is_rejected() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(<Lifecycle as ArchiveCommitter>::commit_archive(PENALTY, entries(10, 4)));
+        let blob = rebuild(PENALTY, 1, &entries(10, 4));
+        let (index, leaf, proof) = blob.prove::<BlakeTwo256>(12).unwrap();
+
+        // 序号错位
+        assert_noop!(
+            verify(PENALTY, 1, index + 1, leaf.clone(), proof.clone()),
+            Error::<Test>::InvalidProof
+        );
+
+        // 篡改记录
+        let mut tampered = leaf.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert_noop!(
+            verify(PENALTY, 1, index, tampered, proof.clone()),
+            Error::<Test>::InvalidProof
+        );
+
+        // 其他批次 / 数据类型的承诺
+        assert_noop!(
+            verify(PENALTY, 2, index, leaf.clone(), proof.clone()),
+            Error::<Test>::CommitmentNotFound
+        );
+        assert_noop!(
+            verify(COMPLAINT, 1, index, leaf, proof),
+            Error::<Test>::CommitmentNotFound
+        );
+    });
+}
+
+// ==================== Pin 重试 ====================
+
+#[test]
+fn failed_pin_keeps_commitment_and_can_be_retried() {
+    new_test_ext().execute_with(|| {
+        set_pin_fails(true);
+        assert_ok!(<Lifecycle as ArchiveCommitter>::commit_archive(PENALTY, entries(10, 2)));
+
+        let commitment = ArchiveCommitments::<Test>::get(data_type(PENALTY), 1).unwrap();
+        assert!(!commitment.pinned);
+        assert!(registered().is_empty());
+
+        // 条件未恢复时重试失败
+        assert_noop!(
+            Lifecycle::retry_archive_pin(RuntimeOrigin::signed(1), data_type(PENALTY), 1),
+            DispatchError::Other("NoOperator")
+        );
+
+        set_pin_fails(false);
+        assert_ok!(Lifecycle::retry_archive_pin(RuntimeOrigin::signed(1), data_type(PENALTY), 1));
+
+        assert!(ArchiveCommitments::<Test>::get(data_type(PENALTY), 1).unwrap().pinned);
+        assert_eq!(
+            registered(),
+            vec![(ARCHIVE_PIN_DOMAIN.to_vec(), commitment.subject_id, commitment.cid.to_vec())]
+        );
+        System::assert_last_event(RuntimeEvent::Lifecycle(Event::ArchivePinned {
+            data_type: data_type(PENALTY),
+            batch_id: 1,
+        }));
+
+        assert_noop!(
+            Lifecycle::retry_archive_pin(RuntimeOrigin::signed(1), data_type(PENALTY), 1),
+            Error::<Test>::AlreadyPinned
+        );
+        assert_noop!(
+            Lifecycle::retry_archive_pin(RuntimeOrigin::signed(1), data_type(PENALTY), 9),
+            Error::<Test>::CommitmentNotFound
+        );
+    });
+}
+
+// ==================== 权重 ====================
+
+#[test]
+fn commit_weight_grows_with_batches_records_and_bytes() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(<Lifecycle as ArchiveCommitter>::commit_weight(0, 0), Weight::zero());
+
+        let one_batch = <Lifecycle as ArchiveCommitter>::commit_weight(MAX_BATCH_SIZE - 1, 100);
+        let two_batches = <Lifecycle as ArchiveCommitter>::commit_weight(MAX_BATCH_SIZE + 1, 100);
+        let more_bytes = <Lifecycle as ArchiveCommitter>::commit_weight(MAX_BATCH_SIZE - 1, 10_000);
+        assert!(two_batches.ref_time() > one_batch.ref_time());
+        assert!(more_bytes.ref_time() > one_batch.ref_time());
+        // 每批至少计入一次 Pin 登记与承诺写入
+        assert!(one_batch.ref_time() >= 50_000_000);
+    });
+}
@@ -0,0 +1,69 @@
+//! # 确定性原始 CID（CIDv1 / raw / blake2b-256）
+//!
+//! 链上生成的内容（如生命周期归档批次）需要在上链时就确定 CID，以便直接登记 Pin。
+//! 单块内容按以下参数上传可得到同一 CID：
+//! `ipfs add --cid-version=1 --raw-leaves --hash=blake2b-256`（集群 `/add` 同名参数）。
+//!
+//! 仅当内容不超过 `MAX_RAW_BLOCK_SIZE`（默认分块大小）时上传结果为单个 raw 块，超出则根为 dag-pb，
+//! 调用方须自行切分。
+//!
+//! 文本形式为 multibase base32 小写（前缀 `b`），与 `ipfs cid format -b base32` 一致。
+
+use alloc::vec::Vec;
+
+/// 单个 raw 块的最大字节数（IPFS 默认分块大小 256 KiB）
+pub const MAX_RAW_BLOCK_SIZE: usize = 256 * 1024;
+
+/// CID 版本
+const CID_V1: u8 = 0x01;
+
+/// multicodec：raw
+const RAW_CODEC: u8 = 0x55;
+
+/// multihash：blake2b-256（0xb220 的 varint 编码）
+const BLAKE2B_256: [u8; 3] = [0xa0, 0xe4, 0x02];
+
+/// 摘要长度
+const DIGEST_LEN: u8 = 32;
+
+/// multibase base32 前缀
+const BASE32_PREFIX: u8 = b'b';
+
+const BASE32_ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+/// 函数级中文注释：计算内容的二进制 CID（版本 ‖ codec ‖ multihash）
+pub fn raw_cid_bytes(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(2 + BLAKE2B_256.len() + 1 + DIGEST_LEN as usize);
+    out.push(CID_V1);
+    out.push(RAW_CODEC);
+    out.extend_from_slice(&BLAKE2B_256);
+    out.push(DIGEST_LEN);
+    out.extend_from_slice(&sp_io::hashing::blake2_256(data));
+    out
+}
+
+/// 函数级中文注释：计算内容的文本 CID（`b` + base32 小写，无填充）
+pub fn raw_cid(data: &[u8]) -> Vec<u8> {
+    let bytes = raw_cid_bytes(data);
+    let mut out = Vec::with_capacity(1 + (bytes.len() * 8 + 4) / 5);
+    out.push(BASE32_PREFIX);
+    base32_lower(&bytes, &mut out);
+    out
+}
+
+/// RFC 4648 base32（小写字母表，无填充）
+fn base32_lower(bytes: &[u8], out: &mut Vec<u8>) {
+    let mut buffer: u16 = 0;
+    let mut bits: u8 = 0;
+    for byte in bytes {
+        buffer = (buffer << 8) | *byte as u16;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(BASE32_ALPHABET[((buffer >> bits) & 0x1f) as usize]);
+        }
+    }
+    if bits > 0 {
+        out.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 0x1f) as usize]);
+    }
+}
@@ -0,0 +1,302 @@
+//! # IPFS Cluster REST 客户端（OCW）
+//!
+//! 请求构造与响应解析与 HTTP 发送分离，便于 `mock_cluster` 按同一构造函数预设响应：
+//! - `POST /pins/{cid}?replication-min=&replication-max=&user-allocations=`：携带运营者分配发起 Pin；
+//!   对已存在的 Pin 重复提交即按新分配重新 Pin（副本修复）
+//! - `GET /pins?cids=a,b`：批量查询全局 Pin 状态（NDJSON，每行一个 GlobalPinInfo）
+//! - `POST /pins/{cid}` + `X-HTTP-Method-Override: DELETE`：取消 Pin（兼容不放行 DELETE 的代理）
+//! - `POST /add?cid-version=1&raw-leaves=true&hash=blake2b-256`：上传链上生成、经 offchain 索引
+//!   写入本地存储（`/memo/ipfs/blob/<cid>`）的内容，并按同样的分配与副本数 Pin
+//!
+//! 端点与令牌从节点本地存储读取（`/memo/ipfs/cluster_endpoint`、`/memo/ipfs/token`），不上链。
+
+use alloc::{format, string::String, vec::Vec};
+use serde_json::Value as JsonValue;
+use sp_runtime::offchain::{http, Duration, StorageKind};
+
+/// 本地存储：集群 REST 端点
+pub const ENDPOINT_KEY: &[u8] = b"/memo/ipfs/cluster_endpoint";
+
+/// 本地存储：集群 API Bearer 令牌（可选）
+pub const TOKEN_KEY: &[u8] = b"/memo/ipfs/token";
+
+/// 本地存储：待上传内容前缀（`/memo/ipfs/blob/<明文 CID>`，由链上 offchain 索引写入）
+pub const BLOB_KEY_PREFIX: &[u8] = b"/memo/ipfs/blob/";
+
+/// `/add` 请求的 multipart 分隔符
+pub const MULTIPART_BOUNDARY: &str = "memo-ipfs-blob-boundary";
+
+/// 未配置端点时使用的本机集群地址
+pub const DEFAULT_ENDPOINT: &str = "http://127.0.0.1:9094";
+
+/// 单次请求超时（毫秒）
+pub const REQUEST_TIMEOUT_MS: u64 = 5_000;
+
+/// Pin 失败上报码：集群报告 `pin_error`
+pub const FAILURE_PIN_ERROR: u16 = 1;
+
+/// Pin 失败上报码：集群中不存在该 Pin（被移除或从未分配到本节点）
+pub const FAILURE_NOT_ALLOCATED: u16 = 2;
+
+// ==================== 配置 ====================
+
+/// 集群连接配置
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ClusterConfig {
+    pub endpoint: String,
+    pub token: Option<String>,
+}
+
+impl ClusterConfig {
+    /// 从节点本地存储读取配置（去掉端点末尾的 `/`）
+    pub fn load() -> Self {
+        let read = |key: &[u8]| {
+            sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, key)
+                .and_then(|v| String::from_utf8(v).ok())
+        };
+        Self {
+            endpoint: read(ENDPOINT_KEY)
+                .map(|e| String::from(e.trim_end_matches('/')))
+                .unwrap_or_else(|| String::from(DEFAULT_ENDPOINT)),
+            token: read(TOKEN_KEY).filter(|t| !t.is_empty()),
+        }
+    }
+
+    /// 写入本地存储（节点运维 / 测试使用）
+    pub fn save(&self) {
+        sp_io::offchain::local_storage_set(StorageKind::PERSISTENT, ENDPOINT_KEY, self.endpoint.as_bytes());
+        if let Some(token) = &self.token {
+            sp_io::offchain::local_storage_set(StorageKind::PERSISTENT, TOKEN_KEY, token.as_bytes());
+        }
+    }
+}
+
+// ==================== 请求构造 ====================
+
+/// HTTP 方法
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Method {
+    Get,
+    Post,
+}
+
+impl Method {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Method::Get => "GET",
+            Method::Post => "POST",
+        }
+    }
+}
+
+/// 待发送的集群请求
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ClusterRequest {
+    pub method: Method,
+    pub url: String,
+    pub body: Vec<u8>,
+    pub headers: Vec<(String, String)>,
+}
+
+impl ClusterRequest {
+    fn new(config: &ClusterConfig, method: Method, path: String) -> Self {
+        let mut headers = Vec::new();
+        if let Some(token) = &config.token {
+            headers.push((String::from("Authorization"), format!("Bearer {}", token)));
+        }
+        Self { method, url: format!("{}{}", config.endpoint, path), body: Vec::new(), headers }
+    }
+
+    fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((String::from(name), String::from(value)));
+        self
+    }
+}
+
+/// 发起 / 更新 Pin：固定副本数并指定分配的运营者 peer
+pub fn pin_request(config: &ClusterConfig, cid: &str, allocations: &[String], replication: u32) -> ClusterRequest {
+    let mut path = format!(
+        "/pins/{}?replication-min={}&replication-max={}",
+        cid, replication, replication
+    );
+    if !allocations.is_empty() {
+        path.push_str("&user-allocations=");
+        path.push_str(&allocations.join(","));
+    }
+    ClusterRequest::new(config, Method::Post, path)
+}
+
+/// 待上传内容在本地存储中的键
+pub fn blob_key(cid: &[u8]) -> Vec<u8> {
+    let mut key = BLOB_KEY_PREFIX.to_vec();
+    key.extend_from_slice(cid);
+    key
+}
+
+/// 上传并 Pin 单块内容：参数与 `cid::raw_cid` 的计算方式一致，集群返回的 CID 与链上登记相同
+pub fn add_request(config: &ClusterConfig, blob: &[u8], allocations: &[String], replication: u32) -> ClusterRequest {
+    let mut path = format!(
+        "/add?cid-version=1&raw-leaves=true&hash=blake2b-256&replication-min={}&replication-max={}",
+        replication, replication
+    );
+    if !allocations.is_empty() {
+        path.push_str("&user-allocations=");
+        path.push_str(&allocations.join(","));
+    }
+    let mut body = format!(
+        "--{}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"blob\"\r\nContent-Type: application/octet-stream\r\n\r\n",
+        MULTIPART_BOUNDARY
+    )
+    .into_bytes();
+    body.extend_from_slice(blob);
+    body.extend_from_slice(format!("\r\n--{}--\r\n", MULTIPART_BOUNDARY).as_bytes());
+    let mut request = ClusterRequest::new(config, Method::Post, path)
+        .with_header("Content-Type", &format!("multipart/form-data; boundary={}", MULTIPART_BOUNDARY));
+    request.body = body;
+    request
+}
+
+/// 批量查询 Pin 状态
+pub fn status_request(config: &ClusterConfig, cids: &[String]) -> ClusterRequest {
+    ClusterRequest::new(config, Method::Get, format!("/pins?cids={}", cids.join(",")))
+}
+
+/// 取消 Pin
+pub fn unpin_request(config: &ClusterConfig, cid: &str) -> ClusterRequest {
+    ClusterRequest::new(config, Method::Post, format!("/pins/{}", cid))
+        .with_header("X-HTTP-Method-Override", "DELETE")
+}
+
+// ==================== 发送 ====================
+
+/// 集群请求错误
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ClusterError {
+    /// 网络错误或超时
+    Network,
+    /// 非 2xx 响应
+    Status(u16),
+}
+
+/// 发送请求，2xx 时返回响应体
+pub fn send(request: &ClusterRequest) -> Result<Vec<u8>, ClusterError> {
+    let chunks: Vec<Vec<u8>> = if request.body.is_empty() {
+        Vec::new()
+    } else {
+        alloc::vec![request.body.clone()]
+    };
+    let method = match request.method {
+        Method::Get => http::Method::Get,
+        Method::Post => http::Method::Post,
+    };
+    let mut req = http::Request::<Vec<Vec<u8>>>::new(&request.url).method(method).body(chunks);
+    for (name, value) in request.headers.iter() {
+        req = req.add_header(name, value);
+    }
+    let deadline = sp_io::offchain::timestamp().add(Duration::from_millis(REQUEST_TIMEOUT_MS));
+    let pending = req.deadline(deadline).send().map_err(|_| ClusterError::Network)?;
+    let response = pending
+        .try_wait(deadline)
+        .map_err(|_| ClusterError::Network)?
+        .map_err(|_| ClusterError::Network)?;
+    if (200..300).contains(&response.code) {
+        Ok(response.body().collect())
+    } else {
+        Err(ClusterError::Status(response.code))
+    }
+}
+
+// ==================== 响应解析 ====================
+
+/// 单个集群 peer 上的 Pin 状态
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PeerPinStatus {
+    Pinned,
+    Pinning,
+    Queued,
+    PinError,
+    Unpinned,
+    /// 不在该 peer 的分配中
+    Remote,
+    Other,
+}
+
+impl PeerPinStatus {
+    pub fn parse(status: &str) -> Self {
+        match status {
+            "pinned" => Self::Pinned,
+            "pinning" => Self::Pinning,
+            "pin_queued" => Self::Queued,
+            "pin_error" => Self::PinError,
+            "unpinned" => Self::Unpinned,
+            "remote" => Self::Remote,
+            _ => Self::Other,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pinned => "pinned",
+            Self::Pinning => "pinning",
+            Self::Queued => "pin_queued",
+            Self::PinError => "pin_error",
+            Self::Unpinned => "unpinned",
+            Self::Remote => "remote",
+            Self::Other => "undefined",
+        }
+    }
+}
+
+/// 一个 CID 在集群内的全局状态（GlobalPinInfo 的精简视图）
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct GlobalPinStatus {
+    pub cid: String,
+    pub peers: Vec<(String, PeerPinStatus)>,
+}
+
+impl GlobalPinStatus {
+    /// 指定 peer 上的状态
+    pub fn status_of(&self, peer_id: &str) -> Option<PeerPinStatus> {
+        self.peers.iter().find(|(p, _)| p == peer_id).map(|(_, s)| *s)
+    }
+
+    /// 已完成 Pin 的 peer 数
+    pub fn pinned_count(&self) -> u32 {
+        self.peers.iter().filter(|(_, s)| *s == PeerPinStatus::Pinned).count() as u32
+    }
+}
+
+/// 解析状态响应：兼容 NDJSON 流、JSON 数组与单个对象；
+/// `cid` 字段兼容 `"Qm.."` 与 `{"/": "Qm.."}` 两种编码。
+pub fn parse_status_stream(body: &[u8]) -> Vec<GlobalPinStatus> {
+    if let Ok(json) = serde_json::from_slice::<JsonValue>(body) {
+        return match json {
+            JsonValue::Array(items) => items.iter().filter_map(parse_global_pin_info).collect(),
+            other => parse_global_pin_info(&other).into_iter().collect(),
+        };
+    }
+    body.split(|b| *b == b'\n')
+        .filter_map(|line| serde_json::from_slice::<JsonValue>(line).ok())
+        .filter_map(|json| parse_global_pin_info(&json))
+        .collect()
+}
+
+fn parse_global_pin_info(json: &JsonValue) -> Option<GlobalPinStatus> {
+    let cid = match json.get("cid")? {
+        JsonValue::String(s) => s.clone(),
+        other => other.get("/")?.as_str()?.into(),
+    };
+    let peers = json
+        .get("peer_map")
+        .and_then(|v| v.as_object())
+        .map(|map| {
+            map.iter()
+                .map(|(peer, info)| {
+                    let status = info.get("status").and_then(|s| s.as_str()).unwrap_or("");
+                    (peer.clone(), PeerPinStatus::parse(status))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    Some(GlobalPinStatus { cid, peers })
+}
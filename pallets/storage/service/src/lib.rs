@@ -13,7 +13,6 @@ use frame_system::pallet_prelude::*;
 // （已下线）移除对 memo-endowment 的接口依赖
 use alloc::string::String;
 use codec::Encode;
-use serde_json::Value as JsonValue;
 use sp_core::crypto::KeyTypeId;
 use sp_runtime::{
     offchain::{http, StorageKind},
@@ -30,16 +29,23 @@ use sp_std::vec::Vec;
 /// - 周期扣费（BillingTask, ChargeLayer, GraceStatus）
 pub mod types;
 pub mod runtime_api;
+pub mod merkle;
+pub mod cid;
+pub mod cluster;
+pub mod ocw;
+/// IPFS Cluster 模拟服务（测试工具），按 `cluster` 模块的请求构造预设 HTTP 期望
+#[cfg(test)]
+pub mod mock_cluster;
 
 // 导出 runtime API
 pub use runtime_api::*;
 
 // 导出常用类型，方便其他模块使用
 pub use types::{
-    BillingTask, ChargeLayer, ChargeResult, DomainStats, GraceStatus, GlobalHealthStats, HealthCheckTask,
+    BillingTask, ChallengeOutcome, ChargeLayer, ChargeResult, CidLock, ContentCommitment, DomainStats, GraceStatus, GlobalHealthStats, HealthCheckTask,
     HealthStatus, LayeredOperatorSelection, LayeredPinAssignment, OperatorLayer,
     OperatorMetrics, OperatorPinHealth, PinTier, SimpleNodeStats, SimplePinStatus,
-    StorageLayerConfig, SubjectInfo, SubjectType, TierConfig, UnpinReason,
+    StorageChallenge, StorageLayerConfig, SubjectInfo, SubjectType, TierConfig, UnpinReason,
 };
 
 /// 函数级详细中文注释：Subject所有者只读提供者（低耦合）
@@ -71,13 +77,27 @@ pub trait SubjectOwnerProvider<AccountId> {
 /// 专用 Offchain 签名 KeyType。注意：需要在节点端注册对应密钥。
 pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"ipfs");
 
+/// 函数级中文注释：每个区块最多提交的内容 Merkle 根数（同一背书截止区块的结算上限）
+pub const MAX_ROOT_COMMITS_PER_BLOCK: u32 = 32;
+
 /// 函数级详细中文注释：OCW 专用签名算法类型
 /// - 使用 sr25519 作为默认曲线；
 /// - 节点 keystore 中通过 `--key` 或 RPC 注入该类型的密钥；
 pub mod sr25519_app {
     use super::KEY_TYPE;
     use sp_application_crypto::{app_crypto, sr25519};
+    use sp_runtime::{MultiSignature, MultiSigner};
+
     app_crypto!(sr25519, KEY_TYPE);
+
+    /// 运行时使用的签名身份：密钥对应的账户即运营者账户
+    pub struct IpfsAuthId;
+
+    impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for IpfsAuthId {
+        type RuntimeAppPublic = Public;
+        type GenericSignature = sp_core::sr25519::Signature;
+        type GenericPublic = sp_core::sr25519::Public;
+    }
 }
 
 pub type AuthorityId = sr25519_app::Public;
@@ -268,11 +288,12 @@ pub mod pallet {
     use frame_support::traits::StorageVersion;
     use sp_runtime::traits::Saturating;
     use sp_runtime::SaturatedConversion;
-    // 已移除签名交易上报，避免对 CreateSignedTransaction 约束
     use alloc::string::ToString;
     use frame_support::traits::tokens::Imbalance;
     use frame_support::PalletId;
     use sp_runtime::traits::AccountIdConversion;
+    use frame_system::offchain::{AppCrypto, CreateBare, SignedPayload, SigningTypes};
+    use sp_runtime::traits::IdentifyAccount;
 
     /// 余额别名
     pub type BalanceOf<T> = <T as Config>::Balance;
@@ -291,8 +312,30 @@ pub mod pallet {
         pub last_activity: BlockNumber,
     }
 
+    /// 函数级详细中文注释：OCW Pin 状态上报签名载荷
+    /// - `pinned=true` 对应 `mark_pinned`（`replicas` 为集群报告的已 Pin 副本数）；
+    /// - `pinned=false` 对应 `mark_pin_failed`（`code` 为失败码，见 `cluster` 模块常量）；
+    /// - `public` 为运营者在 `ipfs` KeyType 下的公钥，其账户即上报者。
+    #[derive(Encode, Decode, codec::DecodeWithMemTracking, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+    pub struct PinReportPayload<Public, Hash, BlockNumber> {
+        pub cid_hash: Hash,
+        pub pinned: bool,
+        pub replicas: u32,
+        pub code: u16,
+        /// 生成载荷时的区块号
+        pub block_number: BlockNumber,
+        /// OCW 签名公钥
+        pub public: Public,
+    }
+
+    impl<T: SigningTypes> SignedPayload<T> for PinReportPayload<T::Public, T::Hash, BlockNumberFor<T>> {
+        fn public(&self) -> T::Public {
+            self.public.clone()
+        }
+    }
+
     #[pallet::config]
-    pub trait Config: frame_system::Config {
+    pub trait Config: frame_system::Config + SigningTypes + CreateBare<Call<Self>> {
         /// 事件类型
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
         /// 货币接口（用于预留押金或扣费）
@@ -307,7 +350,8 @@ pub mod pallet {
         /// 治理 Origin（用于参数/黑名单/配额）
         type GovernanceOrigin: EnsureOrigin<Self::RuntimeOrigin>;
 
-        // 已移除：OCW 签名标识（当前版本不从 OCW 发送签名交易）
+        /// OCW 签名密钥类型（Pin 状态上报签名载荷，密钥账户须为已注册运营者）
+        type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
 
         /// 最大支持的 `cid_hash` 长度（字节）
         #[pallet::constant]
@@ -376,6 +420,47 @@ pub mod pallet {
     /// - 可通过治理调整
     #[pallet::constant]
     type DefaultBillingPeriod: Get<u32>;
+    
+    /// 函数级中文注释：存储挑战随机源
+    /// 
+    /// 说明：
+    /// - 用于抽取被挑战的（运营者, CID, 分块序号）
+    type Randomness: frame_support::traits::Randomness<Self::Hash, BlockNumberFor<Self>>;
+    
+    /// 函数级中文注释：挑战分块大小（字节）
+    /// 
+    /// 说明：
+    /// - 内容所有者按此大小切块构建 Merkle 树
+    /// - 运营者响应挑战时提交的分块不得超过此大小
+    #[pallet::constant]
+    type ChallengeChunkSize: Get<u32>;
+    
+    /// 函数级中文注释：挑战轮间隔（区块数）
+    #[pallet::constant]
+    type ChallengeInterval: Get<BlockNumberFor<Self>>;
+    
+    /// 函数级中文注释：挑战响应窗口（区块数），超时视为未响应
+    #[pallet::constant]
+    type ChallengeResponseWindow: Get<BlockNumberFor<Self>>;
+    
+    /// 函数级中文注释：每轮最多发起的挑战数
+    #[pallet::constant]
+    type ChallengesPerRound: Get<u32>;
+    
+    /// 函数级中文注释：挑战失败的基础罚没额
+    /// 
+    /// 说明：
+    /// - 实际罚没 = 基础罚没额 × 连续失败次数（最多 10 倍）
+    #[pallet::constant]
+    type ChallengeSlashAmount: Get<BalanceOf<Self>>;
+    
+    /// 函数级中文注释：背书窗口（区块数）
+    /// 
+    /// 说明：
+    /// - 所有者提交 Merkle 根后，指派运营者须在此窗口内背书或拒绝
+    /// - 逾期未表态的运营者记为一次检验失败，按挑战失败规则阶梯罚没
+    #[pallet::constant]
+    type AttestationWindow: Get<BlockNumberFor<Self>>;
 }
 
     const STORAGE_VERSION: StorageVersion = StorageVersion::new(0);
@@ -717,6 +802,98 @@ pub mod pallet {
     pub type PinBilling<T: Config> =
         StorageMap<_, Blake2_128Concat, T::Hash, (BlockNumberFor<T>, u128, u8), OptionQuery>;
 
+    /// 函数级中文注释：CID 锁定表（仲裁期间证据保护），同一 CID 最多 16 个锁定原因。
+    /// 存在未过期锁时，手动 Unpin / 宽限期过期 / OCW 物理删除均被阻止。
+    #[pallet::storage]
+    pub type CidLocks<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::Hash,
+        BoundedVec<CidLock<BlockNumberFor<T>>, ConstU32<16>>,
+        ValueQuery,
+    >;
+
+    /// 函数级中文注释：内容 Merkle 承诺（Pin 时由所有者提交，存储挑战据此验证）
+    #[pallet::storage]
+    pub type ContentCommitments<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::Hash,
+        ContentCommitment<T::Hash, BlockNumberFor<T>>,
+        OptionQuery,
+    >;
+
+    /// 函数级中文注释：已提交承诺的 CID 稠密索引（0..CommittedCidCount），用于 O(1) 随机抽取
+    #[pallet::storage]
+    pub type CommittedCids<T: Config> = StorageMap<_, Twox64Concat, u32, T::Hash, OptionQuery>;
+
+    /// 函数级中文注释：CID 在 `CommittedCids` 中的位置（删除时 swap-remove）
+    #[pallet::storage]
+    pub type CommittedCidIndex<T: Config> = StorageMap<_, Blake2_128Concat, T::Hash, u32, OptionQuery>;
+
+    /// 函数级中文注释：已提交承诺的 CID 数量
+    #[pallet::storage]
+    pub type CommittedCidCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
+    /// 函数级中文注释：下一个挑战 ID
+    #[pallet::storage]
+    pub type NextChallengeId<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+    /// 函数级中文注释：未结挑战（challenge_id → 挑战详情）
+    #[pallet::storage]
+    pub type Challenges<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        u64,
+        StorageChallenge<T::AccountId, T::Hash, BlockNumberFor<T>>,
+        OptionQuery,
+    >;
+
+    /// 函数级中文注释：按截止区块索引的挑战（deadline, challenge_id），用于超时结算
+    #[pallet::storage]
+    pub type ChallengeDeadlines<T: Config> =
+        StorageDoubleMap<_, Twox64Concat, BlockNumberFor<T>, Twox64Concat, u64, (), OptionQuery>;
+
+    /// 函数级中文注释：运营者对内容 Merkle 根的背书 (CID, 运营者)
+    ///
+    /// 运营者持有内容后自行分块计算并确认所有者提交的根；只有背书过的运营者才会被挑战，
+    /// 避免所有者提交伪造的根导致诚实运营者被罚没
+    #[pallet::storage]
+    pub type RootAttestations<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, T::Hash, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
+    /// 函数级中文注释：运营者对内容 Merkle 根的拒绝 (CID, 运营者)
+    ///
+    /// 存在拒绝即进入争议：该 CID 暂停挑战，由所有者撤回承诺或治理裁决
+    #[pallet::storage]
+    pub type RootRejections<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, T::Hash, Blake2_128Concat, T::AccountId, (), OptionQuery>;
+
+    /// 函数级中文注释：按背书截止区块索引的承诺 CID，用于结算逾期未背书的运营者
+    #[pallet::storage]
+    pub type AttestationDeadlines<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        BlockNumberFor<T>,
+        BoundedVec<T::Hash, ConstU32<MAX_ROOT_COMMITS_PER_BLOCK>>,
+        ValueQuery,
+    >;
+
+    /// 函数级中文注释：(运营者, CID) 的未结挑战，避免同一副本重复挑战
+    #[pallet::storage]
+    pub type OpenChallengeOf<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, T::Hash, u64, OptionQuery>;
+
+    /// 函数级中文注释：副本最近一次挑战结果 (CID, 运营者) → 是否通过，用于健康评估
+    #[pallet::storage]
+    pub type ReplicaProven<T: Config> =
+        StorageDoubleMap<_, Blake2_128Concat, T::Hash, Blake2_128Concat, T::AccountId, bool, OptionQuery>;
+
+    /// 函数级中文注释：运营者连续挑战失败次数（通过即清零），决定阶梯罚没倍数
+    #[pallet::storage]
+    pub type ChallengeFailStreak<T: Config> =
+        StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
     /// 函数级中文注释：记录 CID 的 funding 来源（owner, subject_id），用于从派生账户自动扣款。
     #[pallet::storage]
     pub type PinSubjectOf<T: Config> =
@@ -1283,6 +1460,94 @@ pub mod pallet {
             reason: UnpinReason,
         },
         
+        /// 函数级详细中文注释：CID 已锁定（仲裁证据保护）
+        CidLocked {
+            cid_hash: T::Hash,
+            reason: BoundedVec<u8, ConstU32<64>>,
+            until: Option<BlockNumberFor<T>>,
+        },
+        
+        /// 函数级详细中文注释：CID 锁定已解除
+        CidUnlocked {
+            cid_hash: T::Hash,
+            reason: BoundedVec<u8, ConstU32<64>>,
+        },
+        
+        /// 函数级详细中文注释：锁定 CID 的宽限期已过，但因锁定保留并延长宽限
+        /// 
+        /// 触发时机：用户资金、公共池、争议兜底账户均不足以支付锁定 CID 的存储费
+        UnpinBlockedByLock {
+            cid_hash: T::Hash,
+        },
+        
+        /// 函数级详细中文注释：锁定 CID 的存储费由争议兜底账户支付
+        ChargedFromDisputeFunding {
+            cid_hash: T::Hash,
+            amount: BalanceOf<T>,
+        },
+        
+        /// 函数级详细中文注释：内容 Merkle 根已提交
+        ContentRootCommitted {
+            cid_hash: T::Hash,
+            merkle_root: T::Hash,
+            chunk_count: u32,
+        },
+        
+        /// 函数级详细中文注释：运营者背书内容 Merkle 根（此后可被挑战）
+        ContentRootAttested {
+            cid_hash: T::Hash,
+            operator: T::AccountId,
+        },
+        
+        /// 函数级详细中文注释：运营者拒绝内容 Merkle 根（与实际内容不符），CID 进入争议，
+        /// 等待所有者撤回承诺或治理裁决
+        ContentRootRejected {
+            cid_hash: T::Hash,
+            operator: T::AccountId,
+            owner: T::AccountId,
+        },
+        
+        /// 函数级详细中文注释：指派运营者在背书窗口内未背书也未拒绝（记为检验失败）
+        AttestationMissed {
+            cid_hash: T::Hash,
+            operator: T::AccountId,
+        },
+        
+        /// 函数级详细中文注释：所有者撤回内容 Merkle 根（可重新提交）
+        ContentRootWithdrawn {
+            cid_hash: T::Hash,
+        },
+        
+        /// 函数级详细中文注释：治理裁决根争议（root_valid=true 根有效，拒绝者记失败；
+        /// false 根无效，背书者记失败且承诺撤销）
+        RootDisputeResolved {
+            cid_hash: T::Hash,
+            root_valid: bool,
+        },
+        
+        /// 函数级详细中文注释：已发起存储挑战
+        ChallengeIssued {
+            challenge_id: u64,
+            operator: T::AccountId,
+            cid_hash: T::Hash,
+            chunk_index: u32,
+            deadline: BlockNumberFor<T>,
+        },
+        
+        /// 函数级详细中文注释：存储挑战已结算（通过/失败/超时）
+        ChallengeResolved {
+            challenge_id: u64,
+            operator: T::AccountId,
+            cid_hash: T::Hash,
+            outcome: ChallengeOutcome,
+        },
+        
+        /// 函数级详细中文注释：运营者保证金被罚没
+        OperatorSlashed {
+            operator: T::AccountId,
+            amount: BalanceOf<T>,
+        },
+        
         /// 函数级详细中文注释：CID已从IPFS物理删除（OCW执行unpin成功）
         /// 
         /// 触发时机：
@@ -1731,6 +1996,44 @@ pub mod pallet {
         /// 函数级详细中文注释：域已存在（尝试重复注册）
         DomainAlreadyExists,
         
+        // ============================================================================
+        // CID 锁定（仲裁证据保护）相关Errors
+        // ============================================================================
+        
+        /// 函数级详细中文注释：CID 已被锁定（仲裁进行中），禁止 Unpin
+        CidLocked,
+        /// 函数级详细中文注释：未找到匹配原因的 CID 锁
+        CidLockNotFound,
+        /// 函数级详细中文注释：CID 锁定原因过多（最多 16 个）
+        TooManyCidLocks,
+        /// 函数级详细中文注释：锁定原因过长（最多 64 字节）
+        LockReasonTooLong,
+        
+        // ============================================================================
+        // 存储挑战相关Errors
+        // ============================================================================
+        
+        /// 函数级详细中文注释：内容 Merkle 根已提交（不可覆盖）
+        ContentRootAlreadyCommitted,
+        /// 函数级详细中文注释：挑战不存在或已结算
+        ChallengeNotFound,
+        /// 函数级详细中文注释：调用者不是被挑战的运营者
+        NotChallengedOperator,
+        /// 函数级详细中文注释：已超过挑战响应截止区块
+        ChallengeExpired,
+        /// 函数级详细中文注释：CID 尚未提交内容 Merkle 根
+        ContentRootNotCommitted,
+        /// 函数级详细中文注释：背书的根/分块数与已提交承诺不一致
+        ContentRootMismatch,
+        /// 函数级详细中文注释：调用者不是该 CID 已完成 Pin 的指派运营者
+        NotPinnedOperator,
+        /// 函数级详细中文注释：已背书或拒绝该根，不能再次表态
+        ContentRootAlreadyAttested,
+        /// 函数级详细中文注释：本区块提交的 Merkle 根已达上限，请下一区块再提交
+        TooManyRootCommits,
+        /// 函数级详细中文注释：该 CID 的根没有被拒绝，无需裁决
+        ContentRootNotDisputed,
+        
     }
 
     impl<T: Config> Pallet<T> {
@@ -1772,6 +2075,252 @@ pub mod pallet {
             T::SubjectPalletId::get().into_sub_account_truncating((b"user", user))
         }
 
+        /// 函数级详细中文注释：争议兜底资金账户
+        /// 
+        /// 被仲裁锁定的 CID 在用户资金与公共池均不足时由此账户支付存储费，
+        /// 保证证据在仲裁期间不因欠费被删除。仲裁费用 / 国库可直接向该地址转账充值。
+        /// 
+        /// 派生公式：SubjectPalletId.into_sub_account_truncating("dispute")
+        #[inline]
+        pub fn dispute_funding_account() -> T::AccountId {
+            T::SubjectPalletId::get().into_sub_account_truncating(b"dispute")
+        }
+
+        /// 函数级详细中文注释：CID 当前是否存在未过期的锁
+        pub fn is_cid_locked(cid_hash: &T::Hash) -> bool {
+            let now = <frame_system::Pallet<T>>::block_number();
+            CidLocks::<T>::get(cid_hash).iter().any(|lock| lock.is_active(&now))
+        }
+
+        // ============================================================================
+        // 存储挑战（可验证存储证明）
+        // ============================================================================
+
+        /// 函数级详细中文注释：罚没运营者保留的保证金并同步 OperatorBond，返回实际罚没额
+        pub fn do_slash_operator(who: &T::AccountId, amount: BalanceOf<T>) -> BalanceOf<T> {
+            let (slashed, _remaining) = <T as Config>::Currency::slash_reserved(who, amount);
+            // 记录剩余 bond（slash_reserved 返回负不平衡，使用 peek 获取相应余额值再进行安全减法）
+            let slashed_amount = slashed.peek();
+            OperatorBond::<T>::mutate(who, |bond| *bond = bond.saturating_sub(slashed_amount));
+            if !slashed_amount.is_zero() {
+                Self::deposit_event(Event::OperatorSlashed {
+                    operator: who.clone(),
+                    amount: slashed_amount,
+                });
+            }
+            slashed_amount
+        }
+
+        /// 函数级中文注释：CID 的受挑战运营者（优先 LayeredPinAssignments，回退 PinAssignments）
+        pub fn challengeable_operators(cid_hash: &T::Hash) -> Vec<T::AccountId> {
+            match LayeredPinAssignments::<T>::get(cid_hash) {
+                Some(layered) => layered
+                    .core_operators
+                    .iter()
+                    .chain(layered.community_operators.iter())
+                    .cloned()
+                    .collect(),
+                None => PinAssignments::<T>::get(cid_hash)
+                    .map(|ops| ops.into_inner())
+                    .unwrap_or_default(),
+            }
+        }
+
+        /// 函数级详细中文注释：发起一轮存储挑战
+        /// 
+        /// 每个挑战用 `hash(seed, i)` 依次抽取 CID（已提交承诺）、该 CID 的受挑战运营者和分块序号；
+        /// 已过期待删除或根处于争议中的 CID、未背书该根的运营者、非 Active 运营者以及已有未结挑战的副本会被跳过。
+        pub fn issue_challenge_round(now: BlockNumberFor<T>) {
+            use frame_support::traits::Randomness;
+            use sp_runtime::traits::{Hash, TrailingZeroInput};
+            
+            let count = CommittedCidCount::<T>::get();
+            if count == 0 {
+                return;
+            }
+            let (seed, _) = T::Randomness::random(&(b"ipfs/challenge", now).encode());
+            let deadline = now.saturating_add(T::ChallengeResponseWindow::get());
+            
+            for i in 0..T::ChallengesPerRound::get() {
+                let draw = T::Hashing::hash_of(&(seed, i));
+                let [cid_pick, op_pick, chunk_pick]: [u32; 3] =
+                    Decode::decode(&mut TrailingZeroInput::new(draw.as_ref())).unwrap_or_default();
+                
+                let Some(cid_hash) = CommittedCids::<T>::get(cid_pick % count) else { continue };
+                let Some(commitment) = ContentCommitments::<T>::get(&cid_hash) else { continue };
+                if matches!(PinBilling::<T>::get(&cid_hash), Some((_, _, 2u8)))
+                    || Self::root_disputed(&cid_hash)
+                {
+                    continue;
+                }
+                // 仅挑战背书过该根的运营者（所有者单方提交的根不作为罚没依据）
+                let operators: Vec<T::AccountId> = Self::challengeable_operators(&cid_hash)
+                    .into_iter()
+                    .filter(|op| RootAttestations::<T>::contains_key(&cid_hash, op))
+                    .collect();
+                if operators.is_empty() {
+                    continue;
+                }
+                let operator = operators[op_pick as usize % operators.len()].clone();
+                if OpenChallengeOf::<T>::contains_key(&operator, &cid_hash)
+                    || Operators::<T>::get(&operator).map_or(true, |op| op.status != 0)
+                {
+                    continue;
+                }
+                
+                let chunk_index = chunk_pick % commitment.chunk_count;
+                let challenge_id = NextChallengeId::<T>::mutate(|id| {
+                    let current = *id;
+                    *id = id.saturating_add(1);
+                    current
+                });
+                Challenges::<T>::insert(challenge_id, StorageChallenge {
+                    operator: operator.clone(),
+                    cid_hash,
+                    chunk_index,
+                    issued_at: now,
+                    deadline,
+                });
+                ChallengeDeadlines::<T>::insert(deadline, challenge_id, ());
+                OpenChallengeOf::<T>::insert(&operator, &cid_hash, challenge_id);
+                
+                Self::deposit_event(Event::ChallengeIssued {
+                    challenge_id,
+                    operator,
+                    cid_hash,
+                    chunk_index,
+                    deadline,
+                });
+            }
+        }
+
+        /// 函数级中文注释：结算截止区块为 `now` 且仍未响应的挑战（记为 Missed）
+        pub fn settle_expired_challenges(now: BlockNumberFor<T>) {
+            let expired: Vec<u64> = ChallengeDeadlines::<T>::drain_prefix(now)
+                .map(|(challenge_id, _)| challenge_id)
+                .collect();
+            for challenge_id in expired {
+                if let Some(challenge) = Challenges::<T>::get(challenge_id) {
+                    Self::resolve_challenge(challenge_id, challenge, ChallengeOutcome::Missed);
+                }
+            }
+        }
+
+        /// 函数级中文注释：结算挑战（移除挑战索引并按结果更新 SLA）
+        pub fn resolve_challenge(
+            challenge_id: u64,
+            challenge: StorageChallenge<T::AccountId, T::Hash, BlockNumberFor<T>>,
+            outcome: ChallengeOutcome,
+        ) {
+            let StorageChallenge { operator, cid_hash, deadline, .. } = challenge;
+            Challenges::<T>::remove(challenge_id);
+            ChallengeDeadlines::<T>::remove(deadline, challenge_id);
+            OpenChallengeOf::<T>::remove(&operator, &cid_hash);
+            
+            Self::deposit_event(Event::ChallengeResolved {
+                challenge_id,
+                operator: operator.clone(),
+                cid_hash,
+                outcome,
+            });
+            Self::record_probe(&operator, &cid_hash, outcome == ChallengeOutcome::Passed);
+        }
+
+        /// 函数级详细中文注释：记录副本检验结果并更新 SLA
+        /// 
+        /// - 通过：probe_ok + 1，连续失败清零
+        /// - 未通过（挑战失败/超时、逾期未背书、争议裁决败诉）：probe_fail + 1，连续失败 + 1，
+        ///   自动罚没 `ChallengeSlashAmount × min(连续失败次数, 10)`
+        fn record_probe(operator: &T::AccountId, cid_hash: &T::Hash, passed: bool) {
+            ReplicaProven::<T>::insert(cid_hash, operator, passed);
+            OperatorSla::<T>::mutate(operator, |s| {
+                if passed {
+                    s.probe_ok = s.probe_ok.saturating_add(1);
+                } else {
+                    s.probe_fail = s.probe_fail.saturating_add(1);
+                }
+                s.last_update = <frame_system::Pallet<T>>::block_number();
+            });
+            
+            if passed {
+                ChallengeFailStreak::<T>::remove(operator);
+            } else {
+                let streak = ChallengeFailStreak::<T>::mutate(operator, |n| {
+                    *n = n.saturating_add(1);
+                    *n
+                });
+                let amount = T::ChallengeSlashAmount::get().saturating_mul(streak.min(10).into());
+                Self::do_slash_operator(operator, amount);
+            }
+        }
+
+        /// 函数级详细中文注释：结算背书截止区块为 `now` 的承诺
+        /// 
+        /// 指派运营者中既未背书也未拒绝的 Active 运营者记为一次检验失败（阶梯罚没），
+        /// 之后仍可补背书；承诺已撤回或重新提交（截止区块不同）的 CID 跳过。
+        pub fn expire_attestations(now: BlockNumberFor<T>) {
+            for cid_hash in AttestationDeadlines::<T>::take(now) {
+                let Some(commitment) = ContentCommitments::<T>::get(&cid_hash) else { continue };
+                if commitment.committed_at.saturating_add(T::AttestationWindow::get()) != now {
+                    continue;
+                }
+                for operator in Self::challengeable_operators(&cid_hash) {
+                    if RootAttestations::<T>::contains_key(&cid_hash, &operator)
+                        || RootRejections::<T>::contains_key(&cid_hash, &operator)
+                        || Operators::<T>::get(&operator).map_or(true, |op| op.status != 0)
+                    {
+                        continue;
+                    }
+                    Self::deposit_event(Event::AttestationMissed {
+                        cid_hash,
+                        operator: operator.clone(),
+                    });
+                    Self::record_probe(&operator, &cid_hash, false);
+                }
+            }
+        }
+
+        /// 函数级中文注释：CID 的根是否处于争议中（存在运营者拒绝）
+        pub fn root_disputed(cid_hash: &T::Hash) -> bool {
+            RootRejections::<T>::iter_prefix(cid_hash).next().is_some()
+        }
+
+        /// 函数级中文注释：本区块是否发起新一轮挑战
+        fn challenge_round_due(n: BlockNumberFor<T>) -> bool {
+            let interval = T::ChallengeInterval::get();
+            !interval.is_zero() && (n % interval).is_zero()
+        }
+
+        /// 函数级中文注释：移除 CID 的内容承诺与挑战索引（swap-remove 保持索引稠密）
+        pub fn remove_content_commitment(cid_hash: &T::Hash) {
+            ContentCommitments::<T>::remove(cid_hash);
+            if let Some(index) = CommittedCidIndex::<T>::take(cid_hash) {
+                let last = CommittedCidCount::<T>::get().saturating_sub(1);
+                if index != last {
+                    if let Some(moved) = CommittedCids::<T>::get(last) {
+                        CommittedCids::<T>::insert(index, moved);
+                        CommittedCidIndex::<T>::insert(moved, index);
+                    }
+                }
+                CommittedCids::<T>::remove(last);
+                CommittedCidCount::<T>::put(last);
+            }
+            let _ = ReplicaProven::<T>::clear_prefix(cid_hash, u32::MAX, None);
+            let _ = RootAttestations::<T>::clear_prefix(cid_hash, u32::MAX, None);
+            let _ = RootRejections::<T>::clear_prefix(cid_hash, u32::MAX, None);
+        }
+
+        /// 函数级中文注释：撤销 CID 上所有未结挑战（不计成败、不罚没），用于根被拒绝或撤回时
+        fn cancel_open_challenges(cid_hash: &T::Hash) {
+            for operator in Self::challengeable_operators(cid_hash) {
+                if let Some(challenge_id) = OpenChallengeOf::<T>::take(&operator, cid_hash) {
+                    if let Some(challenge) = Challenges::<T>::take(challenge_id) {
+                        ChallengeDeadlines::<T>::remove(challenge.deadline, challenge_id);
+                    }
+                }
+            }
+        }
+
         /// 函数级中文注释：将 SubjectType 转换为 domain 编号（用于 SubjectUsage 记账）
         #[inline]
         pub fn subject_type_to_domain(subject_type: &SubjectType) -> u8 {
@@ -1805,19 +2354,88 @@ pub mod pallet {
         }
         
         /// 函数级详细中文注释：CID 解密/映射内部工具函数（非外部可调用）
-        /// - 从 offchain local storage 读取 `/memo/ipfs/cid/<hash_hex>` 对应的明文 CID；
-        /// - 若不存在，返回占位 `"<redacted>"`，用于上层降级处理。
-        #[inline]
-        fn resolve_cid(cid_hash: &T::Hash) -> alloc::string::String {
+        /// - 优先使用链上 `CidRegistry` 登记的明文 CID；
+        /// - 其次从 offchain local storage 读取 `/memo/ipfs/cid/<hash_hex>`（私密内容由节点本地映射）；
+        /// - 均不存在时返回 `None`。
+        pub(crate) fn plaintext_cid(cid_hash: &T::Hash) -> Option<alloc::string::String> {
+            if let Some(cid) = CidRegistry::<T>::get(cid_hash) {
+                if let Ok(s) = alloc::string::String::from_utf8(cid.into_inner()) {
+                    return Some(s);
+                }
+            }
             let mut key = b"/memo/ipfs/cid/".to_vec();
             let hex = hex::encode(cid_hash.as_ref());
             key.extend_from_slice(hex.as_bytes());
-            if let Some(bytes) = sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, &key) {
-                if let Ok(s) = core::str::from_utf8(&bytes) {
-                    return s.into();
+            sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, &key)
+                .and_then(|bytes| alloc::string::String::from_utf8(bytes).ok())
+        }
+
+        /// 函数级详细中文注释：解析明文 CID，不存在时返回占位 `"<redacted>"`，用于上层降级处理。
+        #[inline]
+        fn resolve_cid(cid_hash: &T::Hash) -> alloc::string::String {
+            Self::plaintext_cid(cid_hash).unwrap_or_else(|| "<redacted>".into())
+        }
+
+        /// 函数级详细中文注释：校验 Pin 状态上报者
+        /// - 必须是活跃运营者；
+        /// - 该 CID 仍在 `PendingPins` 中；
+        /// - 上报者属于该 CID 的副本分配。
+        pub(crate) fn ensure_pin_reporter(who: &T::AccountId, cid_hash: &T::Hash) -> DispatchResult {
+            let op = Operators::<T>::get(who).ok_or(Error::<T>::OperatorNotFound)?;
+            ensure!(op.status == 0, Error::<T>::OperatorBanned);
+            ensure!(
+                PendingPins::<T>::contains_key(cid_hash),
+                Error::<T>::OrderNotFound
+            );
+            // 必须是该 cid 的指派运营者之一
+            let assign = PinAssignments::<T>::get(cid_hash).ok_or(Error::<T>::AssignmentNotFound)?;
+            ensure!(
+                assign.iter().any(|a| a == who),
+                Error::<T>::OperatorNotAssigned
+            );
+            Ok(())
+        }
+
+        /// 函数级详细中文注释：记录运营者 Pin 成功（`mark_pinned` 与 OCW 上报共用）
+        /// - 成功副本数达到 `PinMeta.replicas` 时清理 pending 并置为 Pinned，否则置为 Pinning。
+        pub(crate) fn do_mark_pinned(who: T::AccountId, cid_hash: T::Hash, replicas: u32) -> DispatchResult {
+            Self::ensure_pin_reporter(&who, &cid_hash)?;
+            // 标记该运营者完成
+            PinSuccess::<T>::insert(&cid_hash, &who, true);
+            // 达到副本数则完成
+            if let Some(meta) = PinMeta::<T>::get(&cid_hash) {
+                let expect = meta.replicas;
+                let mut ok_count: u32 = 0;
+                if let Some(ops) = PinAssignments::<T>::get(&cid_hash) {
+                    for o in ops.iter() {
+                        if PinSuccess::<T>::get(&cid_hash, o) {
+                            ok_count = ok_count.saturating_add(1);
+                        }
+                    }
+                }
+                if ok_count >= expect {
+                    // 清理 pending，设置状态
+                    PendingPins::<T>::remove(&cid_hash);
+                    PinStateOf::<T>::insert(&cid_hash, 2u8); // Pinned
+                    Self::deposit_event(Event::PinStateChanged(cid_hash, 2));
+                } else {
+                    PinStateOf::<T>::insert(&cid_hash, 1u8); // Pinning
+                    Self::deposit_event(Event::PinStateChanged(cid_hash, 1));
                 }
             }
-            "<redacted>".into()
+            Self::deposit_event(Event::PinMarkedPinned(cid_hash, replicas));
+            Ok(())
+        }
+
+        /// 函数级详细中文注释：记录运营者 Pin 失败（`mark_pin_failed` 与 OCW 上报共用）
+        pub(crate) fn do_mark_pin_failed(who: T::AccountId, cid_hash: T::Hash, code: u16) -> DispatchResult {
+            Self::ensure_pin_reporter(&who, &cid_hash)?;
+            // 标记失败并置为 Pinning/Failed
+            PinSuccess::<T>::insert(&cid_hash, &who, false);
+            PinStateOf::<T>::insert(&cid_hash, 1u8);
+            Self::deposit_event(Event::PinStateChanged(cid_hash, 1));
+            Self::deposit_event(Event::PinMarkedFailed(cid_hash, code));
+            Ok(())
         }
 
         // ⭐ P1优化：已删除 derive_subject_funding_account() 函数（39行）
@@ -2528,6 +3146,30 @@ pub mod pallet {
                 });
             }
             
+            // ===== 争议兜底：被仲裁锁定的 CID 由争议账户支付 =====
+            if Self::is_cid_locked(cid_hash) {
+                let dispute_account = Self::dispute_funding_account();
+                if T::Currency::free_balance(&dispute_account) >= amount {
+                    T::Currency::transfer(
+                        &dispute_account,
+                        &pool_account,
+                        amount,
+                        ExistenceRequirement::KeepAlive,
+                    ).map_err(|_| Error::<T>::IpfsPoolInsufficientBalance)?;
+                    
+                    let _ = Self::distribute_to_pin_operators(cid_hash, amount);
+                    
+                    Self::deposit_event(Event::ChargedFromDisputeFunding {
+                        cid_hash: *cid_hash,
+                        amount,
+                    });
+                    
+                    return Ok(ChargeResult::Success {
+                        layer: ChargeLayer::DisputeFunding,
+                    });
+                }
+            }
+            
             // ===== 第4层：GracePeriod（宽限期）=====
             match &task.grace_status {
                 GraceStatus::Normal => {
@@ -2664,7 +3306,8 @@ pub mod pallet {
         /// 函数级详细中文注释：健康巡检（检查Pin状态）
         /// 
         /// 功能：
-        /// 1. 调用IPFS Cluster status API查询副本状态
+        /// 1. 依据存储挑战结果（ReplicaProven）统计有效副本数
+        ///    - 最近一次挑战失败/超时的副本视为失效，尚未被挑战的副本视为有效
         /// 2. 比对目标副本数与当前副本数
         /// 3. 返回健康状态（Healthy/Degraded/Critical/Unknown）
         /// 
@@ -2672,13 +3315,31 @@ pub mod pallet {
         /// - cid_hash: CID哈希
         /// 
         /// 返回：
-        /// - HealthStatus枚举值
-        /// 
-        /// 注意：此函数应在OCW中调用，不应在链上执行
-        pub fn check_pin_health(_cid_hash: &T::Hash) -> HealthStatus {
-            // TODO: 实际实现需要在OCW中调用IPFS Cluster API
-            // 这里返回默认值，后续在on_finalize中实现
-            HealthStatus::Unknown
+        /// - HealthStatus枚举值；未提交内容承诺或尚无任何挑战结果时返回 Unknown
+        pub fn check_pin_health(cid_hash: &T::Hash) -> HealthStatus {
+            if !ContentCommitments::<T>::contains_key(cid_hash) {
+                return HealthStatus::Unknown;
+            }
+            let operators = Self::challengeable_operators(cid_hash);
+            let results: Vec<Option<bool>> = operators
+                .iter()
+                .map(|op| ReplicaProven::<T>::get(cid_hash, op))
+                .collect();
+            if results.iter().all(|r| r.is_none()) {
+                return HealthStatus::Unknown;
+            }
+            
+            let current_replicas = results.iter().filter(|r| **r != Some(false)).count() as u32;
+            let target = PinMeta::<T>::get(cid_hash)
+                .map(|meta| meta.replicas)
+                .unwrap_or(operators.len() as u32);
+            if current_replicas >= target {
+                HealthStatus::Healthy { current_replicas }
+            } else if current_replicas < 2 {
+                HealthStatus::Critical { current_replicas }
+            } else {
+                HealthStatus::Degraded { current_replicas, target }
+            }
         }
         
         /// 函数级详细中文注释：计算初始Pin费用（一次性预扣30天费用）
@@ -3176,6 +3837,13 @@ pub mod pallet {
                                         Self::enqueue_due(cid, expires_at);
                                         Self::deposit_event(Event::PinGrace(cid));
                                     },
+                                    Err(_) if Self::is_cid_locked(&cid) => {
+                                        // 宽限期已过但 CID 被锁定：保留并延长宽限，等待解锁或充值
+                                        let expires_at = now.saturating_add(GraceBlocks::<T>::get().into());
+                                        PinBilling::<T>::insert(&cid, (expires_at, unit_price, 1u8));
+                                        Self::enqueue_due(cid, expires_at);
+                                        Self::deposit_event(Event::UnpinBlockedByLock { cid_hash: cid });
+                                    },
                                     Err(_) => {
                                         // 扣费失败（宽限期已过）：标记过期
                                         PinBilling::<T>::insert(&cid, (now, unit_price, 2u8));
@@ -3413,9 +4081,9 @@ pub mod pallet {
             Ok(())
         }
 
-        /// 函数级详细中文注释：OCW 上报标记已 Pin 成功
-        /// - 需要节点 keystore 的专用 key 签名；
-        /// - 仅更新状态并发出事件（骨架）。
+        /// 函数级详细中文注释：运营者上报标记已 Pin 成功
+        /// - 运营者账户直接签名提交；OCW 自动上报走 `report_pin_status`；
+        /// - 仅更新状态并发出事件。
         #[pallet::call_index(1)]
         #[pallet::weight(T::WeightInfo::mark_pinned())]
         pub fn mark_pinned(
@@ -3424,50 +4092,10 @@ pub mod pallet {
             replicas: u32,
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
-            // 仅允许活跃运营者上报
-            let op = Operators::<T>::get(&who).ok_or(Error::<T>::OperatorNotFound)?;
-            ensure!(op.status == 0, Error::<T>::OperatorBanned);
-            ensure!(
-                PendingPins::<T>::contains_key(&cid_hash),
-                Error::<T>::OrderNotFound
-            );
-            // 必须是该 cid 的指派运营者之一
-            if let Some(assign) = PinAssignments::<T>::get(&cid_hash) {
-                ensure!(
-                    assign.iter().any(|a| a == &who),
-                    Error::<T>::OperatorNotAssigned
-                );
-            } else {
-                return Err(Error::<T>::AssignmentNotFound.into());
-            }
-            // 标记该运营者完成
-            PinSuccess::<T>::insert(&cid_hash, &who, true);
-            // 达到副本数则完成
-            if let Some(meta) = PinMeta::<T>::get(&cid_hash) {
-                let expect = meta.replicas;
-                let mut ok_count: u32 = 0;
-                if let Some(ops) = PinAssignments::<T>::get(&cid_hash) {
-                    for o in ops.iter() {
-                        if PinSuccess::<T>::get(&cid_hash, o) {
-                            ok_count = ok_count.saturating_add(1);
-                        }
-                    }
-                }
-                if ok_count >= expect {
-                    // 清理 pending，设置状态
-                    PendingPins::<T>::remove(&cid_hash);
-                    PinStateOf::<T>::insert(&cid_hash, 2u8); // Pinned
-                    Self::deposit_event(Event::PinStateChanged(cid_hash, 2));
-                } else {
-                    PinStateOf::<T>::insert(&cid_hash, 1u8); // Pinning
-                    Self::deposit_event(Event::PinStateChanged(cid_hash, 1));
-                }
-            }
-            Self::deposit_event(Event::PinMarkedPinned(cid_hash, replicas));
-            Ok(())
+            Self::do_mark_pinned(who, cid_hash, replicas)
         }
 
-        /// 函数级详细中文注释：OCW 上报标记 Pin 失败
+        /// 函数级详细中文注释：运营者上报标记 Pin 失败
         /// - 记录错误码，便于外部审计。
         #[pallet::call_index(2)]
         #[pallet::weight(T::WeightInfo::mark_pin_failed())]
@@ -3477,26 +4105,7 @@ pub mod pallet {
             code: u16,
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
-            let op = Operators::<T>::get(&who).ok_or(Error::<T>::OperatorNotFound)?;
-            ensure!(op.status == 0, Error::<T>::OperatorBanned);
-            ensure!(
-                PendingPins::<T>::contains_key(&cid_hash),
-                Error::<T>::OrderNotFound
-            );
-            if let Some(assign) = PinAssignments::<T>::get(&cid_hash) {
-                ensure!(
-                    assign.iter().any(|a| a == &who),
-                    Error::<T>::OperatorNotAssigned
-                );
-            } else {
-                return Err(Error::<T>::AssignmentNotFound.into());
-            }
-            // 标记失败并置为 Pinning/Failed
-            PinSuccess::<T>::insert(&cid_hash, &who, false);
-            PinStateOf::<T>::insert(&cid_hash, 1u8);
-            Self::deposit_event(Event::PinStateChanged(cid_hash, 1));
-            Self::deposit_event(Event::PinMarkedFailed(cid_hash, code));
-            Ok(())
+            Self::do_mark_pin_failed(who, cid_hash, code)
         }
 
         /// 函数级详细中文注释：申请成为运营者并存入保证金
@@ -3877,12 +4486,244 @@ pub mod pallet {
                 Operators::<T>::contains_key(&who),
                 Error::<T>::OperatorNotFound
             );
-            let (slashed, _remaining) = <T as Config>::Currency::slash_reserved(&who, amount);
-            // 记录剩余 bond（slash_reserved 返回负不平衡，使用 peek 获取相应余额值再进行安全减法）
-            let old = OperatorBond::<T>::get(&who);
-            let slashed_amount = slashed.peek();
-            let new = old.saturating_sub(slashed_amount);
-            OperatorBond::<T>::insert(&who, new);
+            Self::do_slash_operator(&who, amount);
+            Ok(())
+        }
+
+        /// 函数级详细中文注释：内容所有者提交 Merkle 根（作为存储挑战的验证依据）
+        /// 
+        /// ### 参数
+        /// - `cid_hash`: 已 Pin 的 CID 哈希
+        /// - `merkle_root`: 按 `ChallengeChunkSize` 切块构建的 Merkle 根（规则见 `merkle` 模块）
+        /// - `chunk_count`: 分块总数（>0）
+        /// 
+        /// ### 权限
+        /// - 仅 CID 所有者（PinSubjectOf.owner），且每个 CID 只能提交一次
+        /// 
+        /// ### 背书
+        /// - 提交后不会立即生效：指派运营者须在 `AttestationWindow` 内通过 `attest_content_root`
+        ///   按实际内容确认该根，只有背书过的运营者才会被挑战；逾期未表态的运营者记为检验失败
+        /// - 运营者发现不符可拒绝，CID 进入争议：所有者可 `withdraw_content_root` 撤回后重新提交，
+        ///   或由治理 `resolve_root_dispute` 裁决
+        #[pallet::call_index(33)]
+        #[pallet::weight(T::WeightInfo::commit_content_root())]
+        pub fn commit_content_root(
+            origin: OriginFor<T>,
+            cid_hash: T::Hash,
+            merkle_root: T::Hash,
+            chunk_count: u32,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            ensure!(chunk_count > 0, Error::<T>::BadParams);
+            ensure!(PinMeta::<T>::contains_key(&cid_hash), Error::<T>::OrderNotFound);
+            let (owner, _subject_id) = PinSubjectOf::<T>::get(&cid_hash)
+                .ok_or(Error::<T>::NotOwner)?;
+            ensure!(who == owner, Error::<T>::NotOwner);
+            ensure!(
+                !ContentCommitments::<T>::contains_key(&cid_hash),
+                Error::<T>::ContentRootAlreadyCommitted
+            );
+            
+            let now = <frame_system::Pallet<T>>::block_number();
+            AttestationDeadlines::<T>::try_append(now.saturating_add(T::AttestationWindow::get()), cid_hash)
+                .map_err(|_| Error::<T>::TooManyRootCommits)?;
+            ContentCommitments::<T>::insert(&cid_hash, ContentCommitment {
+                merkle_root,
+                chunk_count,
+                committed_at: now,
+            });
+            let index = CommittedCidCount::<T>::get();
+            CommittedCids::<T>::insert(index, cid_hash);
+            CommittedCidIndex::<T>::insert(&cid_hash, index);
+            CommittedCidCount::<T>::put(index.saturating_add(1));
+            
+            Self::deposit_event(Event::ContentRootCommitted { cid_hash, merkle_root, chunk_count });
+            Ok(())
+        }
+
+        /// 函数级详细中文注释：运营者响应存储挑战
+        /// 
+        /// ### 参数
+        /// - `challenge_id`: 挑战 ID
+        /// - `chunk`: 被挑战分块的原文（≤ ChallengeChunkSize）
+        /// - `proof`: 自底向上的 Merkle 路径
+        /// 
+        /// ### 结算
+        /// - 证明通过：计入 SLA probe_ok，连续失败清零
+        /// - 证明无效：计入 SLA probe_fail 并自动阶梯罚没（交易本身成功，结果写入事件）
+        #[pallet::call_index(34)]
+        #[pallet::weight(T::WeightInfo::respond_challenge(T::ChallengeChunkSize::get()))]
+        pub fn respond_challenge(
+            origin: OriginFor<T>,
+            challenge_id: u64,
+            chunk: BoundedVec<u8, T::ChallengeChunkSize>,
+            proof: BoundedVec<T::Hash, ConstU32<32>>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let challenge = Challenges::<T>::get(challenge_id)
+                .ok_or(Error::<T>::ChallengeNotFound)?;
+            ensure!(challenge.operator == who, Error::<T>::NotChallengedOperator);
+            ensure!(
+                <frame_system::Pallet<T>>::block_number() <= challenge.deadline,
+                Error::<T>::ChallengeExpired
+            );
+            
+            let verified = ContentCommitments::<T>::get(&challenge.cid_hash)
+                .map(|c| {
+                    merkle::verify_proof::<T::Hashing>(
+                        &c.merkle_root,
+                        merkle::leaf_hash::<T::Hashing>(&chunk),
+                        challenge.chunk_index,
+                        c.chunk_count,
+                        &proof,
+                    )
+                })
+                .unwrap_or(false);
+            let outcome = if verified { ChallengeOutcome::Passed } else { ChallengeOutcome::Failed };
+            Self::resolve_challenge(challenge_id, challenge, outcome);
+            Ok(())
+        }
+
+        /// 函数级详细中文注释：OCW 上报集群 Pin 状态（签名载荷 + 无签名交易）
+        /// 
+        /// - 载荷由运营者在 `ipfs` KeyType 下的密钥签名，签名账户即上报运营者；
+        /// - `pinned=true` 等价于该运营者调用 `mark_pinned`，否则等价于 `mark_pin_failed`；
+        /// - 签名与上报者资格在 `ValidateUnsigned` 中预校验，执行时再次校验状态。
+        #[pallet::call_index(35)]
+        #[pallet::weight(T::WeightInfo::mark_pinned().max(T::WeightInfo::mark_pin_failed()))]
+        pub fn report_pin_status(
+            origin: OriginFor<T>,
+            payload: PinReportPayload<T::Public, T::Hash, BlockNumberFor<T>>,
+            _signature: T::Signature,
+        ) -> DispatchResult {
+            ensure_none(origin)?;
+            let who = payload.public.into_account();
+            if payload.pinned {
+                Self::do_mark_pinned(who, payload.cid_hash, payload.replicas)
+            } else {
+                Self::do_mark_pin_failed(who, payload.cid_hash, payload.code)
+            }
+        }
+
+        /// 函数级详细中文注释：指派运营者按实际内容背书或拒绝所有者提交的 Merkle 根
+        /// 
+        /// ### 参数
+        /// - `merkle_root` / `chunk_count`: 运营者本地分块计算的结果，必须与承诺一致（绑定所背书的根）
+        /// - `accept`: true 背书；false 拒绝（运营者计算结果与承诺不符时，传入承诺中的值表示拒绝该根）
+        /// 
+        /// ### 权限
+        /// - 该 CID 的指派运营者，且已上报 Pin 成功（持有内容）
+        /// 
+        /// ### 结果
+        /// - 背书：该运营者此后参与存储挑战
+        /// - 拒绝：承诺保留，CID 进入争议并通知所有者；取消该 CID 的未结挑战（不罚没）并暂停挑战，
+        ///   直至所有者撤回承诺或治理裁决
+        /// - 每个运营者只能表态一次（已背书不能再拒绝，反之亦然）
+        #[pallet::call_index(36)]
+        #[pallet::weight(T::WeightInfo::attest_content_root())]
+        pub fn attest_content_root(
+            origin: OriginFor<T>,
+            cid_hash: T::Hash,
+            merkle_root: T::Hash,
+            chunk_count: u32,
+            accept: bool,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let commitment = ContentCommitments::<T>::get(&cid_hash)
+                .ok_or(Error::<T>::ContentRootNotCommitted)?;
+            ensure!(
+                commitment.merkle_root == merkle_root && commitment.chunk_count == chunk_count,
+                Error::<T>::ContentRootMismatch
+            );
+            ensure!(
+                Self::challengeable_operators(&cid_hash).contains(&who)
+                    && PinSuccess::<T>::get(&cid_hash, &who),
+                Error::<T>::NotPinnedOperator
+            );
+            ensure!(
+                !RootAttestations::<T>::contains_key(&cid_hash, &who)
+                    && !RootRejections::<T>::contains_key(&cid_hash, &who),
+                Error::<T>::ContentRootAlreadyAttested
+            );
+            
+            if accept {
+                RootAttestations::<T>::insert(&cid_hash, &who, ());
+                Self::deposit_event(Event::ContentRootAttested { cid_hash, operator: who });
+            } else {
+                let (owner, _subject_id) = PinSubjectOf::<T>::get(&cid_hash)
+                    .ok_or(Error::<T>::NotOwner)?;
+                Self::cancel_open_challenges(&cid_hash);
+                RootRejections::<T>::insert(&cid_hash, &who, ());
+                Self::deposit_event(Event::ContentRootRejected { cid_hash, operator: who, owner });
+            }
+            Ok(())
+        }
+
+        /// 函数级详细中文注释：所有者撤回已提交的内容 Merkle 根
+        /// 
+        /// ### 用途
+        /// - 根被运营者拒绝后，所有者确认提交有误时撤回，随后可按实际内容重新提交
+        /// 
+        /// ### 结果
+        /// - 取消该 CID 的未结挑战（不计成败），清除承诺、背书与拒绝记录
+        #[pallet::call_index(37)]
+        #[pallet::weight(T::WeightInfo::withdraw_content_root())]
+        pub fn withdraw_content_root(origin: OriginFor<T>, cid_hash: T::Hash) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let (owner, _subject_id) = PinSubjectOf::<T>::get(&cid_hash)
+                .ok_or(Error::<T>::NotOwner)?;
+            ensure!(who == owner, Error::<T>::NotOwner);
+            ensure!(
+                ContentCommitments::<T>::contains_key(&cid_hash),
+                Error::<T>::ContentRootNotCommitted
+            );
+            
+            Self::cancel_open_challenges(&cid_hash);
+            Self::remove_content_commitment(&cid_hash);
+            Self::deposit_event(Event::ContentRootWithdrawn { cid_hash });
+            Ok(())
+        }
+
+        /// 函数级详细中文注释：治理裁决内容 Merkle 根争议
+        /// 
+        /// ### 参数
+        /// - `root_valid`: true 根与内容一致；false 根伪造或有误
+        /// 
+        /// ### 结果
+        /// - 根有效：拒绝的运营者各记一次检验失败（阶梯罚没），拒绝记录清除，CID 恢复挑战
+        /// - 根无效：背书的运营者各记一次检验失败，承诺撤销，所有者可重新提交
+        #[pallet::call_index(38)]
+        #[pallet::weight(T::WeightInfo::resolve_root_dispute())]
+        pub fn resolve_root_dispute(
+            origin: OriginFor<T>,
+            cid_hash: T::Hash,
+            root_valid: bool,
+        ) -> DispatchResult {
+            T::GovernanceOrigin::ensure_origin(origin)?;
+            ensure!(
+                ContentCommitments::<T>::contains_key(&cid_hash),
+                Error::<T>::ContentRootNotCommitted
+            );
+            ensure!(Self::root_disputed(&cid_hash), Error::<T>::ContentRootNotDisputed);
+            
+            if root_valid {
+                let rejecters: Vec<T::AccountId> = RootRejections::<T>::drain_prefix(&cid_hash)
+                    .map(|(operator, _)| operator)
+                    .collect();
+                for operator in rejecters {
+                    Self::record_probe(&operator, &cid_hash, false);
+                }
+            } else {
+                let attesters: Vec<T::AccountId> = RootAttestations::<T>::iter_prefix(&cid_hash)
+                    .map(|(operator, _)| operator)
+                    .collect();
+                for operator in attesters {
+                    Self::record_probe(&operator, &cid_hash, false);
+                }
+                Self::cancel_open_challenges(&cid_hash);
+                Self::remove_content_commitment(&cid_hash);
+            }
+            Self::deposit_event(Event::RootDisputeResolved { cid_hash, root_valid });
             Ok(())
         }
         
@@ -4187,6 +5028,9 @@ pub mod pallet {
                 .ok_or(Error::<T>::NotOwner)?;
             ensure!(caller == owner, Error::<T>::NotOwner);
             
+            // 仲裁锁定期间禁止 Unpin
+            ensure!(!Self::is_cid_locked(&cid_hash), Error::<T>::CidLocked);
+            
             // 4. 标记为待删除状态
             let current_block = <frame_system::Pallet<T>>::block_number();
             if let Some((_, unit_price, _)) = PinBilling::<T>::get(&cid_hash) {
@@ -4254,292 +5098,64 @@ pub mod pallet {
         
     }
 
+    // ============================================================================
+    // OCW 无签名交易验证（Pin 状态上报）
+    // ============================================================================
+
+    #[pallet::validate_unsigned]
+    impl<T: Config> ValidateUnsigned for Pallet<T> {
+        type Call = Call<T>;
+
+        /// 函数级详细中文注释：校验 OCW Pin 状态上报
+        /// - Custom(1)：载荷区块号晚于当前区块；
+        /// - Custom(2)：签名账户不是该 CID 的活跃指派运营者，或 CID 已不在待处理队列；
+        /// - Custom(3)：该运营者已被记为成功，重复的成功上报无意义。
+        fn validate_unsigned(source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+            match call {
+                Call::report_pin_status { payload, signature } => {
+                    if payload.block_number > <frame_system::Pallet<T>>::block_number() {
+                        return InvalidTransaction::Custom(1).into();
+                    }
+                    // 签名校验：载荷必须由其声明的公钥签名
+                    if !SignedPayload::<T>::verify::<T::AuthorityId>(payload, signature.clone()) {
+                        return InvalidTransaction::BadProof.into();
+                    }
+                    let reporter = payload.public.clone().into_account();
+                    if Self::ensure_pin_reporter(&reporter, &payload.cid_hash).is_err() {
+                        return InvalidTransaction::Custom(2).into();
+                    }
+                    if payload.pinned && PinSuccess::<T>::get(&payload.cid_hash, &reporter) {
+                        return InvalidTransaction::Custom(3).into();
+                    }
+
+                    let priority = match source {
+                        TransactionSource::Local => 100,
+                        TransactionSource::InBlock => 80,
+                        TransactionSource::External => 50,
+                    };
+
+                    ValidTransaction::with_tag_prefix("IpfsPinReport")
+                        .priority(priority)
+                        .longevity(10)
+                        .and_provides([&(b"ipfs_pin_report", payload.cid_hash, reporter, payload.pinned)])
+                        .propagate(true)
+                        .build()
+                },
+                _ => InvalidTransaction::Call.into(),
+            }
+        }
+    }
+
     #[pallet::hooks]
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
         /// 函数级详细中文注释：Offchain Worker 入口
-        /// - 周期性扫描 `PendingPins`，对每个 `cid_hash` 调用 ipfs-cluster API 进行 Pin；
-        /// - 成功则提交 `mark_pinned`，失败则提交 `mark_pin_failed`；
+        /// - 集群同步（见 `ocw` 模块）：为本节点运营者批量发起携带分配的 Pin、批量轮询状态、
+        ///   以签名载荷上报 `mark_pinned` / `mark_pin_failed`，并对副本不足的 CID 重新 Pin；
+        /// - 过期 CID 从集群 unpin；
         /// - HTTP 令牌与集群端点从本地 offchain storage 读取，避免上链泄露。
-        fn offchain_worker(_n: BlockNumberFor<T>) {
-            // 读取本地配置（示例键）："/memo/ipfs/cluster_endpoint" 与 "/memo/ipfs/token"
-            let endpoint: alloc::string::String = sp_io::offchain::local_storage_get(
-                StorageKind::PERSISTENT,
-                b"/memo/ipfs/cluster_endpoint",
-            )
-            .and_then(|v| core::str::from_utf8(&v).ok().map(|s| s.to_string()))
-            .unwrap_or_else(|| alloc::string::String::from("http://127.0.0.1:9094"));
-            let token: Option<alloc::string::String> =
-                sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, b"/memo/ipfs/token")
-                    .and_then(|v| core::str::from_utf8(&v).ok().map(|s| s.to_string()));
-
-            // 分配与 Pin：遍历 PendingPins，若无分配则创建；否则尝试 POST /pins 携带 allocations
-            if let Some((cid_hash, (_payer, _replicas, _subject_id, _size, _price))) =
-                <PendingPins<T>>::iter().next()
-            {
-                // ⭐ P0优化：使用分层选择算法替代旧版权重选择
-                if LayeredPinAssignments::<T>::get(&cid_hash).is_none() {
-                    // 获取CID的Tier（默认Standard，ValueQuery自动返回默认值）和SubjectType（默认Custom）
-                    let tier = CidTier::<T>::get(&cid_hash);
-                    let subject_type = SubjectType::Custom(Default::default());
-                    
-                    // 使用分层选择算法：Layer 1 + Layer 2
-                    match Self::select_operators_by_layer(subject_type, tier.clone()) {
-                        Ok(selection) => {
-                            // 合并Layer 1和Layer 2运营者
-                            let mut all_operators = selection.core_operators.to_vec();
-                            all_operators.extend(selection.community_operators.to_vec());
-                            
-                            // 记录分层Pin分配
-                            let core_ops = BoundedVec::truncate_from(selection.core_operators.to_vec());
-                            let community_ops = BoundedVec::truncate_from(selection.community_operators.to_vec());
-                            
-                            LayeredPinAssignments::<T>::insert(
-                                &cid_hash,
-                                LayeredPinAssignment {
-                                    core_operators: core_ops.clone(),
-                                    community_operators: community_ops.clone(),
-                                    external_used: false,
-                                    external_network: None,
-                                },
-                            );
-                            
-                            // 向后兼容：同时更新旧版PinAssignments
-                            if let Ok(operators_bounded) = BoundedVec::try_from(all_operators) {
-                                PinAssignments::<T>::insert(&cid_hash, operators_bounded);
-                            }
-                            
-                            // 发送分层Pin分配完成事件
-                            Self::deposit_event(Event::LayeredPinAssigned {
-                                cid_hash,
-                                core_operators: core_ops,
-                                community_operators: community_ops,
-                                external_used: false,
-                            });
-                        },
-                        Err(_) => {
-                            // 选择失败，跳过（运营者不足等）
-                        }
-                    }
-                }
-                // 发起 Pin 请求（MVP 不在 body 中传 allocations，真实集群应携带）
-                let _ = Self::submit_pin_request(&endpoint, &token, cid_hash);
-                PinStateOf::<T>::insert(&cid_hash, 1u8);
-                Self::deposit_event(Event::PinStateChanged(cid_hash, 1));
-            }
-
-            // 探测自身是否在线（运营者必须运行集群节点）：读取 /peers 并查找自身 peer_id
-            // 探测自身是否在线：简化为本地统计，避免依赖 CreateSignedTransaction
-            let _ = Self::http_get_bytes(&endpoint, &token, "/peers");
-
-            // 巡检：针对已 Pinned/Pinning 的对象，GET /pins/{cid} 矫正副本；若缺少则再 Pin；并统计上报
-            // 注意：演示中未持有明文 CID，这里仅示意调用；生产需有 CID 解密/映射。
-            // 逻辑：遍历 PinStateOf in {1=Pinning,2=Pinned}，若 assignments 存在，检查成功标记数；不足副本则再次发起 submit_pin_request。
-            let mut sample: u32 = 0;
-            let mut pinning: u32 = 0;
-            let mut pinned: u32 = 0;
-            let mut missing: u32 = 0;
-            for (cid_hash, state) in PinStateOf::<T>::iter() {
-                if state == 1u8 || state == 2u8 {
-                    sample = sample.saturating_add(1);
-                    if let Some(assign) = PinAssignments::<T>::get(&cid_hash) {
-                        let expect = PinMeta::<T>::get(&cid_hash)
-                            .map(|m| m.replicas)
-                            .unwrap_or(assign.len() as u32);
-                        let mut ok_count: u32 = 0;
-                        for o in assign.iter() {
-                            if PinSuccess::<T>::get(&cid_hash, o) {
-                                ok_count = ok_count.saturating_add(1);
-                            }
-                        }
-                        if ok_count < expect {
-                            // 副本不足，需要补充
-                            let shortage = expect.saturating_sub(ok_count);
-                            
-                            // 解析 /pins/{cid}，对比分配并触发降级/修复事件
-                            let cid_str = Self::resolve_cid(&cid_hash);
-                            // 直接 GET /pins/{cid} 获取状态（Plan B 替换 submit_get_pin_status_collect）
-                            if let Some(body) = Self::http_get_bytes(
-                                &endpoint,
-                                &token,
-                                &alloc::format!("/pins/{}", cid_str),
-                            ) {
-                                let mut online_peers: Vec<Vec<u8>> = Vec::new();
-                                if let Ok(json) = serde_json::from_slice::<JsonValue>(&body) {
-                                    // 兼容两类结构：{peer_map:{"peerid":{status:"pinned"|...}}} 或 {allocations:["peerid",...]}
-                                    if let Some(map) =
-                                        json.get("peer_map").and_then(|v| v.as_object())
-                                    {
-                                        for (pid, st) in map.iter() {
-                                            if st.get("status").and_then(|s| s.as_str())
-                                                == Some("pinned")
-                                            {
-                                                online_peers.push(pid.as_bytes().to_vec());
-                                            }
-                                        }
-                                    } else if let Some(arr) =
-                                        json.get("allocations").and_then(|v| v.as_array())
-                                    {
-                                        for v in arr.iter() {
-                                            if let Some(s) = v.as_str() {
-                                                online_peers.push(s.as_bytes().to_vec());
-                                            }
-                                        }
-                                    }
-                                }
-                                // 标记降级与修复：对比本地分配和在线列表
-                                for op_acc in assign.iter() {
-                                    if let Some(info) = Operators::<T>::get(op_acc) {
-                                        let present = online_peers
-                                            .iter()
-                                            .any(|p| p.as_slice() == info.peer_id.as_slice());
-                                        let success = PinSuccess::<T>::get(&cid_hash, op_acc);
-                                        if present && !success {
-                                            PinSuccess::<T>::insert(&cid_hash, op_acc, true);
-                                            
-                                            // P0-4：自动更新healthy_pins统计
-                                            // 注意：这里不修改total_pins（已在分配时+1）
-                                            // 仅需要在Pinned时更新healthy_pins
-                                            let _ = OperatorPinStats::<T>::try_mutate(op_acc, |stats| {
-                                                stats.healthy_pins = stats.healthy_pins.saturating_add(1);
-                                                stats.last_check = _n;
-                                                // 重新计算健康度得分
-                                                stats.health_score = Self::calculate_health_score(op_acc);
-                                                Ok::<(), ()>(())
-                                            });
-                                            
-                                            Self::deposit_event(Event::ReplicaRepaired(
-                                                cid_hash,
-                                                op_acc.clone(),
-                                            ));
-                                            
-                                            // 发送运营者Pin成功事件
-                                            if let Some(meta) = PinMeta::<T>::get(&cid_hash) {
-                                                Self::deposit_event(Event::OperatorPinSuccess {
-                                                    operator: op_acc.clone(),
-                                                    cid_hash,
-                                                    replicas_confirmed: meta.replicas,
-                                                });
-                                            }
-                                        }
-                                        if !present && success {
-                                            PinSuccess::<T>::insert(&cid_hash, op_acc, false);
-                                            
-                                            // P0-4：自动更新监控统计 - Pin失败
-                                            // healthy_pins -1, failed_pins +1
-                                            let _ = OperatorPinStats::<T>::try_mutate(op_acc, |stats| {
-                                                stats.healthy_pins = stats.healthy_pins.saturating_sub(1);
-                                                stats.failed_pins = stats.failed_pins.saturating_add(1);
-                                                stats.last_check = _n;
-                                                
-                                                // 重新计算健康度得分
-                                                let old_score = stats.health_score;
-                                                stats.health_score = Self::calculate_health_score(op_acc);
-                                                
-                                                // 如果健康度下降超过10分，发送告警
-                                                if old_score.saturating_sub(stats.health_score) >= 10 {
-                                                    Self::deposit_event(Event::OperatorHealthDegraded {
-                                                        operator: op_acc.clone(),
-                                                        old_score,
-                                                        new_score: stats.health_score,
-                                                        total_pins: stats.total_pins,
-                                                        failed_pins: stats.failed_pins,
-                                                    });
-                                                }
-                                                
-                                                Ok::<(), ()>(())
-                                            });
-                                            
-                                            // 发送运营者Pin失败事件
-                                            let reason = alloc::format!("Pin degraded - operator offline or unreachable")
-                                                .as_bytes()
-                                                .to_vec();
-                                            if let Ok(bounded_reason) = BoundedVec::try_from(reason) {
-                                                Self::deposit_event(Event::OperatorPinFailed {
-                                                    operator: op_acc.clone(),
-                                                    cid_hash,
-                                                    reason: bounded_reason,
-                                                });
-                                            }
-                                            
-                                            // 统计降级次数并触发告警建议
-                                            OperatorSla::<T>::mutate(op_acc, |s| {
-                                                s.degraded = s.degraded.saturating_add(1);
-                                                if s.degraded % 10 == 0 {
-                                                    // 简单阈值：每 10 次降级告警
-                                                    Self::deposit_event(
-                                                        Event::OperatorDegradationAlert(
-                                                            op_acc.clone(),
-                                                            s.degraded,
-                                                        ),
-                                                    );
-                                                }
-                                            });
-                                            Self::deposit_event(Event::ReplicaDegraded(
-                                                cid_hash,
-                                                op_acc.clone(),
-                                            ));
-                                        }
-                                    }
-                                }
-                                
-                                // ⭐ P0优化：自动补充副本使用分层选择算法
-                                if shortage > 0 {
-                                    // 获取CID的Tier（ValueQuery自动返回默认值）和SubjectType
-                                    let tier = CidTier::<T>::get(&cid_hash);
-                                    let subject_type = SubjectType::Custom(Default::default());
-                                    
-                                    // 使用分层选择算法获取新运营者
-                                    if let Ok(selection) = Self::select_operators_by_layer(subject_type, tier) {
-                                        // 合并Layer 1和Layer 2运营者
-                                        let mut new_candidates = selection.core_operators.to_vec();
-                                        new_candidates.extend(selection.community_operators.to_vec());
-                                        
-                                        // 获取当前已分配的运营者（用于去重）
-                                        let current_operators: alloc::vec::Vec<T::AccountId> = 
-                                            assign.iter().cloned().collect();
-                                        
-                                        // 过滤出新的运营者（未在当前分配列表中）
-                                        let mut updated_assign = assign.clone();
-                                        let mut added_count = 0u32;
-                                        
-                                        for new_op in new_candidates.iter() {
-                                            if !current_operators.contains(new_op) && added_count < shortage {
-                                                if updated_assign.try_push(new_op.clone()).is_ok() {
-                                                    added_count += 1;
-                                                }
-                                            }
-                                        }
-                                        
-                                        if added_count > 0 {
-                                            PinAssignments::<T>::insert(&cid_hash, &updated_assign);
-                                            
-                                            // 触发事件：已添加新运营者补充副本
-                                            Self::deposit_event(Event::AssignmentCreated(
-                                                cid_hash,
-                                                added_count,
-                                            ));
-                                        }
-                                    }
-                                }
-                            }
-                            // 再 Pin（带退避）
-                            let _ = Self::submit_pin_request(&endpoint, &token, cid_hash);
-                            PinStateOf::<T>::insert(&cid_hash, 1u8);
-                            Self::deposit_event(Event::PinStateChanged(cid_hash, 1));
-                            pinning = pinning.saturating_add(1);
-                        } else {
-                            pinned = pinned.saturating_add(1);
-                        }
-                    } else {
-                        // 无分配但状态为 pinning/pinned，视作缺失
-                        missing = missing.saturating_add(1);
-                    }
-                }
-            }
-            // 事件上报（轻量只读）：不改变状态，仅供监控
-            if sample > 0 {
-                Self::deposit_event(Event::PinProbe(sample, pinning, pinned, missing));
-            }
+        fn offchain_worker(n: BlockNumberFor<T>) {
+            Self::offchain_sync_cluster(n);
+            let cluster_config = crate::cluster::ClusterConfig::load();
             
             // ============================================================================
             // 过期CID物理删除（OCW调用IPFS unpin）
@@ -4552,12 +5168,14 @@ pub mod pallet {
             const MAX_UNPIN_PER_BLOCK: u32 = 5; // 每块最多删除5个，避免阻塞
             
             for (cid_hash, (_, _, state)) in PinBilling::<T>::iter() {
-                if state == 2u8 && unpinned_count < MAX_UNPIN_PER_BLOCK {
+                // 被仲裁锁定的 CID 暂缓删除，解锁后自动恢复处理
+                if state == 2u8 && unpinned_count < MAX_UNPIN_PER_BLOCK && !Self::is_cid_locked(&cid_hash) {
                     // 获取明文CID
                     let cid_str = Self::resolve_cid(&cid_hash);
                     
                     // 调用IPFS Cluster API执行物理删除
-                    if Self::submit_delete_pin(&endpoint, &token, &cid_str).is_ok() {
+                    let unpin = crate::cluster::unpin_request(&cluster_config, &cid_str);
+                    if crate::cluster::send(&unpin).is_ok() {
                         // 删除成功：清理链上存储
                         PinBilling::<T>::remove(&cid_hash);
                         PinMeta::<T>::remove(&cid_hash);
@@ -4569,6 +5187,7 @@ pub mod pallet {
                         CidRegistry::<T>::remove(&cid_hash);
                         LayeredPinAssignments::<T>::remove(&cid_hash);
                         SimplePinAssignments::<T>::remove(&cid_hash);
+                        Self::remove_content_commitment(&cid_hash);
                         
                         // 从域索引中移除
                         for (domain, hash, _) in DomainPins::<T>::iter() {
@@ -4677,9 +5296,28 @@ pub mod pallet {
             }
         }
         
+        /// 函数级详细中文注释：区块开始时预留 `on_finalize` 中存储挑战任务的权重
+        /// 
+        /// - 结算超时挑战：本块截止的挑战均来自同一轮，至多 `ChallengesPerRound` 个
+        /// - 结算逾期背书：按本块背书截止队列的实际长度
+        /// - 发起新一轮挑战：仅在 `ChallengeInterval` 整数倍区块
+        fn on_initialize(n: BlockNumberFor<T>) -> Weight {
+            let per_round = T::ChallengesPerRound::get();
+            let expiring = AttestationDeadlines::<T>::decode_len(n).unwrap_or(0) as u32;
+            let mut weight = T::DbWeight::get()
+                .reads(1)
+                .saturating_add(T::WeightInfo::settle_challenges(per_round))
+                .saturating_add(T::WeightInfo::expire_attestations(expiring));
+            if Self::challenge_round_due(n) {
+                weight = weight.saturating_add(T::WeightInfo::issue_challenge_round(per_round));
+            }
+            weight
+        }
+        
         /// 函数级详细中文注释：区块结束时的自动化任务（优化改造）
         /// 
         /// 执行顺序（优先级从高到低）：
+        /// 0. 存储挑战（结算超时挑战与逾期背书、按 ChallengeInterval 发起新一轮；权重在 on_initialize 预留）
         /// 1. 周期扣费（确保资金流转）
         /// 2. 健康巡检（确保数据安全）
         /// 3. 统计更新（链上仪表板）
@@ -4691,6 +5329,14 @@ pub mod pallet {
         fn on_finalize(n: BlockNumberFor<T>) {
             let current_block = n;
             
+            // ======== 任务0：存储挑战（不受扣费暂停影响）========
+            // 先结算本块截止的挑战与背书（本块内的响应已执行），再按间隔发起新一轮
+            Self::settle_expired_challenges(current_block);
+            Self::expire_attestations(current_block);
+            if Self::challenge_round_due(current_block) {
+                Self::issue_challenge_round(current_block);
+            }
+            
             // 检查是否暂停扣费
             if BillingPaused::<T>::get() {
                 return;
@@ -4745,6 +5391,22 @@ pub mod pallet {
                         // 移除旧的队列项
                         BillingQueue::<T>::remove(due_block, &cid_hash);
                     },
+                    Err(_) if Self::is_cid_locked(&cid_hash) => {
+                        // 宽限期已过但 CID 被锁定：保留并延长宽限，1小时后再试
+                        let tier_config = Self::get_tier_config(&CidTier::<T>::get(&cid_hash))
+                            .unwrap_or_default();
+                        task.grace_status = GraceStatus::InGrace {
+                            entered_at: current_block,
+                            expires_at: current_block + tier_config.grace_period_blocks.into(),
+                        };
+                        let next_billing = current_block + 1200u32.into();
+                        BillingQueue::<T>::insert(next_billing, &cid_hash, task);
+                        BillingQueue::<T>::remove(due_block, &cid_hash);
+                        
+                        Self::deposit_event(Event::UnpinBlockedByLock {
+                            cid_hash: cid_hash.clone(),
+                        });
+                    },
                     Err(_) => {
                         // 宽限期已过，标记Unpin
                         task.grace_status = GraceStatus::Expired;
@@ -5141,95 +5803,6 @@ pub mod pallet {
             }
             if !inserted { /* 放弃，治理可通过扫描修复 */ }
         }
-        /// 函数级详细中文注释：GET 请求帮助函数，返回主体字节（2xx 才返回）
-        fn http_get_bytes(endpoint: &str, token: &Option<String>, path: &str) -> Option<Vec<u8>> {
-            let url = alloc::format!("{}{}", endpoint, path);
-            let mut req = http::Request::get(&url);
-            if let Some(t) = token.as_ref() {
-                req = req.add_header("Authorization", &alloc::format!("Bearer {}", t));
-            }
-            let timeout = sp_io::offchain::timestamp()
-                .add(sp_runtime::offchain::Duration::from_millis(3_000));
-            let pending = req.deadline(timeout).send().ok()?;
-            // try_wait 返回 Result<Option<Response>, _> → ok()?.ok()? 解包为 Response
-            let resp = pending.try_wait(timeout).ok()?.ok()?;
-            let code: u16 = resp.code;
-            if (200..300).contains(&code) {
-                Some(resp.body().collect::<Vec<u8>>())
-            } else {
-                None
-            }
-        }
-
-        /// 函数级详细中文注释：通过 OCW 发送 HTTP POST /pins 请求到 ipfs-cluster
-        /// - 仅示例：构造最小 JSON 体，包含 `cid` 字段（此处我们只有 `cid_hash`，生产应由 OCW 从密文解出 CID）。
-        /// - 返回：若 HTTP 状态为 2xx 则认为提交成功，随后发起 `mark_pinned` 外部交易。
-        fn submit_pin_request(
-            endpoint: &str,
-            token: &Option<String>,
-            cid_hash: T::Hash,
-        ) -> Result<(), ()> {
-            let cid_hex = hex::encode(cid_hash.as_ref());
-            // 构造最小 JSON（根据你的 API 需要调整）
-            let body_json = alloc::format!(r#"{{"cid":"{}"}}"#, cid_hex);
-            let body_vec: Vec<u8> = body_json.into_bytes();
-            let url = alloc::format!("{}/pins", endpoint);
-            // 不用切片：使用 Vec<Vec<u8>> 作为 POST body，以满足 add_header/deadline 的 T: Default 约束
-            let chunks: Vec<Vec<u8>> = alloc::vec![body_vec];
-            let mut req = http::Request::post(&url, chunks);
-            if let Some(t) = token.as_ref() {
-                req = req
-                    .add_header("Authorization", &alloc::format!("Bearer {}", t))
-                    .add_header("Content-Type", "application/json");
-            }
-            let timeout = sp_io::offchain::timestamp()
-                .add(sp_runtime::offchain::Duration::from_millis(5_000));
-            let pending = req.deadline(timeout).send().map_err(|_| ())?;
-            let resp = pending.try_wait(timeout).map_err(|_| ())?.map_err(|_| ())?;
-            let code: u16 = resp.code;
-            if (200..300).contains(&code) {
-                Ok(())
-            } else {
-                Err(())
-            }
-        }
-
-        /// 函数级详细中文注释：通过 OCW 发送 HTTP DELETE /pins/{cid}
-        /// 
-        /// 功能：
-        /// - 调用IPFS Cluster API执行物理unpin操作
-        /// - 某些环境下使用 `X-HTTP-Method-Override: DELETE` 搭配 POST 以规避代理限制
-        /// 
-        /// 调用时机：
-        /// - OCW扫描到过期CID（PinBilling.state=2）时自动调用
-        /// 
-        /// 返回：
-        /// - Ok(())：删除成功（HTTP 2xx）
-        /// - Err(())：删除失败（网络错误或HTTP非2xx）
-        fn submit_delete_pin(
-            endpoint: &str,
-            token: &Option<String>,
-            cid_str: &str,
-        ) -> Result<(), ()> {
-            let url = alloc::format!("{}/pins/{}", endpoint, cid_str);
-            // 不用切片：空体使用 Vec<Vec<u8>>
-            let chunks: Vec<Vec<u8>> = alloc::vec![Vec::new()];
-            let mut req =
-                http::Request::post(&url, chunks).add_header("X-HTTP-Method-Override", "DELETE");
-            if let Some(t) = token.as_ref() {
-                req = req.add_header("Authorization", &alloc::format!("Bearer {}", t));
-            }
-            let timeout = sp_io::offchain::timestamp()
-                .add(sp_runtime::offchain::Duration::from_millis(5_000));
-            let pending = req.deadline(timeout).send().map_err(|_| ())?;
-            let resp = pending.try_wait(timeout).map_err(|_| ())?.map_err(|_| ())?;
-            let code: u16 = resp.code;
-            if (200..300).contains(&code) {
-                Ok(())
-            } else {
-                Err(())
-            }
-        }
     }
 
     impl<T: Config> Pallet<T> {
@@ -5349,6 +5922,9 @@ impl<T: Config> IpfsPinner<<T as frame_system::Config>::AccountId, BalanceOf<T>>
             return Err(Error::<T>::NotOwner.into());
         }
         
+        // 仲裁锁定期间禁止 Unpin
+        ensure!(!Self::is_cid_locked(&cid_hash), Error::<T>::CidLocked);
+        
         // 4. 标记为待删除状态
         // 更新 PinBilling 状态为 2（Expired/待删除）
         let current_block = <frame_system::Pallet<T>>::block_number();
@@ -5492,6 +6068,9 @@ impl<T: Config> ContentRegistry for Pallet<T> {
             return Ok(());
         }
         
+        // 仲裁锁定期间禁止 Unpin
+        ensure!(!Self::is_cid_locked(&cid_hash), Error::<T>::CidLocked);
+        
         // 3. 标记为待删除状态
         let current_block = <frame_system::Pallet<T>>::block_number();
         if let Some((_, unit_price, _)) = PinBilling::<T>::get(&cid_hash) {
@@ -5514,21 +6093,58 @@ impl<T: Config> ContentRegistry for Pallet<T> {
 // 删除日期：2025-10-26
 
 /// CidLockManager trait 实现 - 证据锁定机制
+/// 
+/// - 锁按 (cid_hash, reason) 区分，同一原因重复锁定只更新到期时间
+/// - 锁定时顺带清理已过期的锁
+/// - `until` 到期后锁自动失效，无需显式解锁
 impl<T: Config> CidLockManager<T::Hash, BlockNumberFor<T>> for Pallet<T> {
-    fn lock_cid(_cid_hash: T::Hash, _reason: Vec<u8>, _until: Option<BlockNumberFor<T>>) -> DispatchResult {
-        // TODO: 实现 CID 锁定存储逻辑
-        // 当前为 stub 实现，允许编译通过
+    fn lock_cid(cid_hash: T::Hash, reason: Vec<u8>, until: Option<BlockNumberFor<T>>) -> DispatchResult {
+        let reason: BoundedVec<u8, ConstU32<64>> = reason.try_into()
+            .map_err(|_| Error::<T>::LockReasonTooLong)?;
+        let now = <frame_system::Pallet<T>>::block_number();
+        if let Some(until) = until {
+            ensure!(until > now, Error::<T>::BadParams);
+        }
+        
+        CidLocks::<T>::try_mutate(&cid_hash, |locks| -> DispatchResult {
+            locks.retain(|lock| lock.is_active(&now));
+            if let Some(existing) = locks.iter_mut().find(|lock| lock.reason == reason) {
+                existing.until = until;
+            } else {
+                locks.try_push(CidLock {
+                    reason: reason.clone(),
+                    locked_at: now,
+                    until,
+                }).map_err(|_| Error::<T>::TooManyCidLocks)?;
+            }
+            Ok(())
+        })?;
+        
+        Self::deposit_event(Event::CidLocked { cid_hash, reason, until });
         Ok(())
     }
     
-    fn unlock_cid(_cid_hash: T::Hash, _reason: Vec<u8>) -> DispatchResult {
-        // TODO: 实现 CID 解锁逻辑
+    fn unlock_cid(cid_hash: T::Hash, reason: Vec<u8>) -> DispatchResult {
+        let mut locks = CidLocks::<T>::get(&cid_hash);
+        let before = locks.len();
+        locks.retain(|lock| lock.reason.as_slice() != reason.as_slice());
+        ensure!(locks.len() < before, Error::<T>::CidLockNotFound);
+        
+        if locks.is_empty() {
+            CidLocks::<T>::remove(&cid_hash);
+        } else {
+            CidLocks::<T>::insert(&cid_hash, locks);
+        }
+        
+        // reason 已与存储中的记录匹配，长度必在上限内
+        let reason: BoundedVec<u8, ConstU32<64>> = reason.try_into()
+            .map_err(|_| Error::<T>::LockReasonTooLong)?;
+        Self::deposit_event(Event::CidUnlocked { cid_hash, reason });
         Ok(())
     }
     
-    fn is_locked(_cid_hash: &T::Hash) -> bool {
-        // TODO: 实现锁定状态查询
-        false
+    fn is_locked(cid_hash: &T::Hash) -> bool {
+        Self::is_cid_locked(cid_hash)
     }
 }
 
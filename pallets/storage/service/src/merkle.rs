@@ -0,0 +1,98 @@
+//! 函数级详细中文注释：存储挑战使用的二叉 Merkle 树工具
+//! 
+//! 规则（链上验证与链下构建必须一致）：
+//! - 叶子：`H(0x00 ‖ chunk)`
+//! - 内部节点：`H(0x01 ‖ left ‖ right)`
+//! - 某层节点数为奇数时，末尾节点直接晋升到上一层（不复制、不哈希）
+//! - 证明按自底向上排列，只包含实际存在的兄弟节点
+
+use sp_runtime::traits::Hash;
+use sp_std::vec::Vec;
+
+/// 函数级中文注释：计算分块的叶子哈希
+pub fn leaf_hash<H: Hash>(chunk: &[u8]) -> H::Output {
+    let mut buf = Vec::with_capacity(chunk.len() + 1);
+    buf.push(0u8);
+    buf.extend_from_slice(chunk);
+    H::hash(&buf)
+}
+
+/// 函数级中文注释：计算内部节点哈希
+pub fn node_hash<H: Hash>(left: &H::Output, right: &H::Output) -> H::Output {
+    let mut buf = Vec::with_capacity(1 + left.as_ref().len() + right.as_ref().len());
+    buf.push(1u8);
+    buf.extend_from_slice(left.as_ref());
+    buf.extend_from_slice(right.as_ref());
+    H::hash(&buf)
+}
+
+/// 函数级中文注释：由下一层节点计算上一层
+fn next_level<H: Hash>(level: &[H::Output]) -> Vec<H::Output> {
+    level
+        .chunks(2)
+        .map(|pair| if pair.len() == 2 { node_hash::<H>(&pair[0], &pair[1]) } else { pair[0] })
+        .collect()
+}
+
+/// 函数级中文注释：由叶子哈希构建 Merkle 根（无叶子时返回 None）
+pub fn merkle_root<H: Hash>(leaves: &[H::Output]) -> Option<H::Output> {
+    if leaves.is_empty() {
+        return None;
+    }
+    let mut level = leaves.to_vec();
+    while level.len() > 1 {
+        level = next_level::<H>(&level);
+    }
+    Some(level[0])
+}
+
+/// 函数级中文注释：生成第 `index` 个叶子的 Merkle 路径（供运营者/测试在链下使用）
+pub fn merkle_proof<H: Hash>(leaves: &[H::Output], index: u32) -> Option<Vec<H::Output>> {
+    let mut idx = index as usize;
+    if idx >= leaves.len() {
+        return None;
+    }
+    let mut level = leaves.to_vec();
+    let mut proof = Vec::new();
+    while level.len() > 1 {
+        if let Some(sibling) = level.get(idx ^ 1) {
+            proof.push(*sibling);
+        }
+        level = next_level::<H>(&level);
+        idx /= 2;
+    }
+    Some(proof)
+}
+
+/// 函数级详细中文注释：验证叶子在给定根下的 Merkle 路径
+/// 
+/// - `index` 必须小于 `leaf_count`
+/// - 路径长度必须与树形严格匹配（多余或缺失的节点均视为无效）
+pub fn verify_proof<H: Hash>(
+    root: &H::Output,
+    leaf: H::Output,
+    index: u32,
+    leaf_count: u32,
+    proof: &[H::Output],
+) -> bool {
+    if index >= leaf_count {
+        return false;
+    }
+    let mut idx = index;
+    let mut width = leaf_count;
+    let mut acc = leaf;
+    let mut siblings = proof.iter();
+    while width > 1 {
+        if (idx ^ 1) < width {
+            let Some(sibling) = siblings.next() else { return false };
+            acc = if idx % 2 == 0 {
+                node_hash::<H>(&acc, sibling)
+            } else {
+                node_hash::<H>(sibling, &acc)
+            };
+        }
+        idx /= 2;
+        width = width.div_ceil(2);
+    }
+    siblings.next().is_none() && acc == *root
+}
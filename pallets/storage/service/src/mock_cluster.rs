@@ -0,0 +1,141 @@
+//! # IPFS Cluster 模拟服务（测试工具）
+//!
+//! 进程内模拟集群：维护每个 CID 在各 peer 上的 Pin 状态，并按 `cluster` 模块的请求构造
+//! 向 `TestOffchainExt` 预设 HTTP 期望与响应。OCW 发出的请求（方法、URL、头）与真实集群一致，
+//! 请求不匹配或多余请求会直接导致测试失败。
+//!
+//! ## 用法
+//! ```ignore
+//! let mut cluster = MockCluster::new(ClusterConfig::load());
+//! cluster.expect_pin(&mut state.write(), "bafy..", &["peer-a".into()], 1);
+//! cluster.set_status("bafy..", "peer-a", PeerPinStatus::Pinned);
+//! cluster.expect_status(&mut state.write(), &["bafy..".into()]);
+//! ```
+
+use std::collections::BTreeMap;
+
+use sp_core::offchain::testing::{OffchainState, PendingRequest};
+
+use crate::cluster::{
+    add_request, pin_request, status_request, unpin_request, ClusterConfig, ClusterRequest, PeerPinStatus,
+};
+
+/// 模拟集群状态
+#[derive(Clone, Debug)]
+pub struct MockCluster {
+    pub config: ClusterConfig,
+    /// cid -> (peer_id -> 状态)
+    pins: BTreeMap<String, BTreeMap<String, PeerPinStatus>>,
+}
+
+impl MockCluster {
+    pub fn new(config: ClusterConfig) -> Self {
+        Self { config, pins: BTreeMap::new() }
+    }
+
+    /// 设置 CID 在某 peer 上的状态
+    pub fn set_status(&mut self, cid: &str, peer_id: &str, status: PeerPinStatus) {
+        self.pins.entry(cid.into()).or_default().insert(peer_id.into(), status);
+    }
+
+    /// 集群内已完成 Pin 的副本数
+    pub fn pinned_count(&self, cid: &str) -> u32 {
+        self.pins
+            .get(cid)
+            .map(|peers| peers.values().filter(|s| **s == PeerPinStatus::Pinned).count() as u32)
+            .unwrap_or(0)
+    }
+
+    /// 预设一次 Pin 请求：受理后分配的 peer 进入 `pin_queued`（已有状态保持不变）
+    pub fn expect_pin(
+        &mut self,
+        state: &mut OffchainState,
+        cid: &str,
+        allocations: &[String],
+        replication: u32,
+    ) {
+        for peer in allocations {
+            self.pins
+                .entry(cid.into())
+                .or_default()
+                .entry(peer.clone())
+                .or_insert(PeerPinStatus::Queued);
+        }
+        let response = self.global_pin_info(cid);
+        expect_request(state, pin_request(&self.config, cid, allocations, replication), &response);
+    }
+
+    /// 预设一次内容上传（`/add`）：受理后分配的 peer 进入 `pin_queued`，响应为 AddedOutput
+    pub fn expect_add(
+        &mut self,
+        state: &mut OffchainState,
+        cid: &str,
+        blob: &[u8],
+        allocations: &[String],
+        replication: u32,
+    ) {
+        for peer in allocations {
+            self.pins
+                .entry(cid.into())
+                .or_default()
+                .entry(peer.clone())
+                .or_insert(PeerPinStatus::Queued);
+        }
+        let response = format!(r#"{{"name":"blob","cid":"{}","size":{}}}"#, cid, blob.len());
+        expect_request(state, add_request(&self.config, blob, allocations, replication), &response);
+    }
+
+    /// 预设一次批量状态查询，响应为各 CID 的 GlobalPinInfo（NDJSON）
+    pub fn expect_status(&self, state: &mut OffchainState, cids: &[String]) {
+        let response = cids
+            .iter()
+            .filter(|cid| self.pins.contains_key(cid.as_str()))
+            .map(|cid| self.global_pin_info(cid))
+            .collect::<Vec<_>>()
+            .join("\n");
+        expect_request(state, status_request(&self.config, cids), &response);
+    }
+
+    /// 预设一次 unpin 请求并移除该 CID
+    pub fn expect_unpin(&mut self, state: &mut OffchainState, cid: &str) {
+        let response = self.global_pin_info(cid);
+        self.pins.remove(cid);
+        expect_request(state, unpin_request(&self.config, cid), &response);
+    }
+
+    /// 渲染 GlobalPinInfo JSON（字段子集与 ipfs-cluster `/pins` 响应一致）
+    pub fn global_pin_info(&self, cid: &str) -> String {
+        let peer_map = self
+            .pins
+            .get(cid)
+            .map(|peers| {
+                peers
+                    .iter()
+                    .map(|(peer, status)| {
+                        format!(
+                            r#""{}":{{"peername":"{}","ipfs_peer_id":"{}","status":"{}","timestamp":"2024-01-01T00:00:00Z","error":""}}"#,
+                            peer,
+                            peer,
+                            peer,
+                            status.as_str(),
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join(",")
+            })
+            .unwrap_or_default();
+        format!(r#"{{"cid":"{}","name":"","allocations":[],"origins":[],"peer_map":{{{}}}}}"#, cid, peer_map)
+    }
+}
+
+fn expect_request(state: &mut OffchainState, request: ClusterRequest, response: &str) {
+    state.expect_request(PendingRequest {
+        method: request.method.as_str().into(),
+        uri: request.url,
+        body: request.body,
+        headers: request.headers,
+        response: Some(response.as_bytes().to_vec()),
+        sent: true,
+        ..Default::default()
+    });
+}
@@ -0,0 +1,391 @@
+//! # 存储服务 OCW：IPFS Cluster 同步
+//!
+//! 运营者节点的 OCW 通过 IPFS Cluster REST API（见 `cluster` 模块）维护分配给本节点的 Pin。
+//!
+//! ## 流程（每区块）
+//! 1. 从 keystore（`ipfs` KeyType）找出本节点持有密钥的活跃运营者；无则跳过
+//! 2. 扫描 `PendingPins` 中分配给本节点运营者的 CID，批量发起 Pin：
+//!    `user-allocations` 为全部指派运营者的 peer_id，`replication` 为 `PinMeta.replicas`；
+//!    本地存储中有该 CID 的待上传内容（链上 offchain 索引写入）时改用 `/add` 上传后 Pin
+//! 3. 对待处理 CID 与已 Pinned CID 发起一次批量状态查询
+//! 4. 本节点 peer 上 `pinned` → 上报 `mark_pinned`；`pin_error` / 未分配 → 上报 `mark_pin_failed`
+//!    （签名载荷 + 无签名交易 `report_pin_status`，签名账户即运营者）
+//! 5. 已 Pinned 但集群内完成副本数低于 `PinMeta.replicas` → 按原分配重新 Pin
+//!
+//! ## 去重与退避
+//! - 本地存储记录每个 CID 的 Pin 提交状态，`compare_and_set` 防止并发 OCW 重复提交；
+//!   失败按 `RETRY_BASE_BLOCKS * 2^attempts` 指数退避
+//! - 本地存储记录每个（CID, 运营者）最近一次上报，冷却期内不重复上报相同结果
+//! - 链上：`ValidateUnsigned` 拒绝重复的成功上报
+//!
+//! ## 密钥
+//! 运营者需在节点 keystore 中插入 `ipfs` 类型的 sr25519 密钥，其账户即 `join_operator` 注册的账户。
+
+use alloc::{string::String, vec, vec::Vec};
+use codec::{Decode, Encode};
+use frame_system::offchain::{AppCrypto, SendUnsignedTransaction, Signer};
+use frame_system::pallet_prelude::BlockNumberFor;
+use sp_core::offchain::StorageKind;
+use sp_runtime::traits::{IdentifyAccount, SaturatedConversion};
+use sp_runtime::RuntimeAppPublic;
+
+use crate::cluster::{self, ClusterConfig, GlobalPinStatus, PeerPinStatus};
+use crate::pallet::{
+    Call, Config, Operators, Pallet, PendingPins, PinAssignments, PinMeta, PinReportPayload,
+    PinStateOf, PinSuccess,
+};
+
+// ==================== 常量配置 ====================
+
+/// 每个区块最多发起的 Pin 请求数（含重新 Pin）
+pub const MAX_PINS_PER_BLOCK: u32 = 10;
+
+/// 单次批量状态查询的 CID 上限
+pub const MAX_STATUS_PER_BLOCK: u32 = 32;
+
+/// 重试退避基数（区块）
+pub const RETRY_BASE_BLOCKS: u32 = 5;
+
+/// 重试退避上限（区块）
+pub const RETRY_MAX_BACKOFF_BLOCKS: u32 = 300;
+
+/// Pin 请求被集群受理后，到下一次允许重新 Pin 的冷却期（区块）
+///
+/// 集群需要时间完成分配与拉取，冷却期内只轮询状态。
+pub const REPIN_COOLDOWN_BLOCKS: u32 = 50;
+
+/// 相同上报结果的重发冷却期（区块），与交易 longevity 一致
+///
+/// 交易可能被交易池丢弃；冷却期过后链上仍未更新则重新上报。
+pub const REPORT_COOLDOWN_BLOCKS: u32 = 10;
+
+/// CID Pin 提交状态存储键前缀
+const PIN_STATE_PREFIX: &[u8] = b"ipfs_ocw::pin_state::";
+
+/// 上报状态存储键前缀
+const REPORT_STATE_PREFIX: &[u8] = b"ipfs_ocw::report::";
+
+// ==================== 本地状态 ====================
+
+/// 单个 CID 的 Pin 提交状态（offchain 本地存储）
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, Debug)]
+pub struct PinOcwState {
+    /// 连续失败次数
+    pub attempts: u32,
+    /// 下次允许提交 Pin 的区块
+    pub next_attempt_at: u32,
+    /// 集群是否已受理 Pin 请求
+    pub submitted: bool,
+}
+
+/// 单个（CID, 运营者）最近一次上报（offchain 本地存储）
+#[derive(Encode, Decode, Clone, PartialEq, Eq, Debug)]
+pub struct PinReportState {
+    pub pinned: bool,
+    pub code: u16,
+    pub reported_at: u32,
+}
+
+/// 本节点持有密钥的活跃运营者
+pub struct LocalOperator<T: Config> {
+    pub public: T::Public,
+    pub account: T::AccountId,
+    pub peer_id: String,
+}
+
+/// 第 `attempts` 次失败后的退避区块数：`RETRY_BASE_BLOCKS * 2^attempts`，封顶 `RETRY_MAX_BACKOFF_BLOCKS`
+pub fn retry_backoff(attempts: u32) -> u32 {
+    RETRY_BASE_BLOCKS
+        .saturating_mul(1u32 << attempts.min(16))
+        .min(RETRY_MAX_BACKOFF_BLOCKS)
+}
+
+fn pin_state_key<H: Encode>(cid_hash: &H) -> Vec<u8> {
+    let mut key = PIN_STATE_PREFIX.to_vec();
+    key.extend_from_slice(&cid_hash.encode());
+    key
+}
+
+fn report_state_key<H: Encode, A: Encode>(cid_hash: &H, operator: &A) -> Vec<u8> {
+    let mut key = REPORT_STATE_PREFIX.to_vec();
+    key.extend_from_slice(&cid_hash.encode());
+    key.extend_from_slice(&operator.encode());
+    key
+}
+
+/// 读取 CID 的 Pin 提交状态
+pub fn get_pin_ocw_state<H: Encode>(cid_hash: &H) -> PinOcwState {
+    sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, &pin_state_key(cid_hash))
+        .and_then(|data| PinOcwState::decode(&mut &data[..]).ok())
+        .unwrap_or_default()
+}
+
+fn save_pin_ocw_state<H: Encode>(cid_hash: &H, state: &PinOcwState) {
+    sp_io::offchain::local_storage_set(StorageKind::PERSISTENT, &pin_state_key(cid_hash), &state.encode());
+}
+
+/// 通过 compare-and-set 占用本次 Pin 提交，返回占用前的状态
+fn claim_pin_attempt<H: Encode>(cid_hash: &H, now: u32) -> Option<PinOcwState> {
+    let key = pin_state_key(cid_hash);
+    let old_raw = sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, &key);
+    let old = old_raw
+        .as_ref()
+        .and_then(|data| PinOcwState::decode(&mut &data[..]).ok())
+        .unwrap_or_default();
+
+    let mut claimed = old.clone();
+    claimed.next_attempt_at = now.saturating_add(retry_backoff(old.attempts));
+
+    if sp_io::offchain::local_storage_compare_and_set(StorageKind::PERSISTENT, &key, old_raw, &claimed.encode()) {
+        Some(old)
+    } else {
+        None
+    }
+}
+
+// ==================== OCW 主流程 ====================
+
+impl<T: Config> Pallet<T> {
+    /// OCW 入口：同步分配给本节点运营者的集群 Pin
+    pub fn offchain_sync_cluster(block_number: BlockNumberFor<T>) {
+        let locals = Self::local_operators();
+        if locals.is_empty() {
+            log::debug!(target: "ipfs-ocw", "No local operator key, skip cluster sync");
+            return;
+        }
+        let config = ClusterConfig::load();
+        let now: u32 = block_number.saturated_into();
+        let mut pins_sent = 0u32;
+
+        // 1. 待处理：分配给本节点运营者的 PendingPins，未受理的发起 Pin
+        let mut pending: Vec<(T::Hash, String)> = Vec::new();
+        for (cid_hash, (_, replicas, _, _, _)) in PendingPins::<T>::iter() {
+            if pending.len() as u32 >= MAX_STATUS_PER_BLOCK {
+                break;
+            }
+            let Some((assigned, cid)) = Self::local_assignment(&locals, &cid_hash) else {
+                continue;
+            };
+            let state = get_pin_ocw_state(&cid_hash);
+            if !state.submitted && state.next_attempt_at <= now && pins_sent < MAX_PINS_PER_BLOCK {
+                if let Some(old) = claim_pin_attempt(&cid_hash, now) {
+                    pins_sent += 1;
+                    let replication = PinMeta::<T>::get(&cid_hash).map(|m| m.replicas).unwrap_or(replicas);
+                    Self::submit_cluster_pin(&config, &cid_hash, &cid, &assigned, replication, old, now);
+                }
+            }
+            pending.push((cid_hash, cid));
+        }
+
+        // 2. 已 Pinned：冷却期外的纳入副本巡检
+        let mut pinned: Vec<(T::Hash, String)> = Vec::new();
+        for (cid_hash, state) in PinStateOf::<T>::iter() {
+            if (pending.len() + pinned.len()) as u32 >= MAX_STATUS_PER_BLOCK {
+                break;
+            }
+            if state != 2u8 || get_pin_ocw_state(&cid_hash).next_attempt_at > now {
+                continue;
+            }
+            if let Some((_, cid)) = Self::local_assignment(&locals, &cid_hash) {
+                pinned.push((cid_hash, cid));
+            }
+        }
+
+        if pending.is_empty() && pinned.is_empty() {
+            return;
+        }
+
+        // 3. 批量查询状态
+        let cids: Vec<String> = pending.iter().chain(pinned.iter()).map(|(_, cid)| cid.clone()).collect();
+        let statuses = match cluster::send(&cluster::status_request(&config, &cids)) {
+            Ok(body) => cluster::parse_status_stream(&body),
+            Err(e) => {
+                log::warn!(target: "ipfs-ocw", "Cluster status query failed: {:?}", e);
+                return;
+            },
+        };
+        let find = |cid: &str| statuses.iter().find(|s| s.cid == cid);
+
+        // 4. 上报本节点运营者的 Pin 结果
+        for (cid_hash, cid) in pending.iter() {
+            if let Some(status) = find(cid) {
+                Self::report_local_status(&locals, cid_hash, status, block_number);
+            }
+        }
+
+        // 5. 副本不足：按原分配重新 Pin
+        for (cid_hash, cid) in pinned.iter() {
+            if pins_sent >= MAX_PINS_PER_BLOCK {
+                break;
+            }
+            let expect = PinMeta::<T>::get(cid_hash).map(|m| m.replicas).unwrap_or(0);
+            let have = find(cid).map(|s| s.pinned_count()).unwrap_or(0);
+            if have >= expect {
+                continue;
+            }
+            let Some(assigned) = PinAssignments::<T>::get(cid_hash) else {
+                continue;
+            };
+            if let Some(old) = claim_pin_attempt(cid_hash, now) {
+                pins_sent += 1;
+                log::warn!(target: "ipfs-ocw", "CID {} under-replicated ({}/{}), re-pinning", cid, have, expect);
+                Self::submit_cluster_pin(&config, cid_hash, cid, &assigned, expect, old, now);
+            }
+        }
+    }
+
+    /// 本节点 keystore 中的 `ipfs` 密钥对应的活跃运营者（peer_id 须为 UTF-8 文本）
+    pub fn local_operators() -> Vec<LocalOperator<T>> {
+        <T::AuthorityId as AppCrypto<T::Public, T::Signature>>::RuntimeAppPublic::all()
+            .into_iter()
+            .filter_map(|key| {
+                let generic = <T::AuthorityId as AppCrypto<T::Public, T::Signature>>::GenericPublic::from(key);
+                let public: T::Public = generic.into();
+                let account = public.clone().into_account();
+                let info = Operators::<T>::get(&account).filter(|op| op.status == 0)?;
+                let peer_id = String::from_utf8(info.peer_id.into_inner()).ok()?;
+                Some(LocalOperator { public, account, peer_id })
+            })
+            .collect()
+    }
+
+    /// CID 的指派运营者包含本节点运营者且明文 CID 可解析时，返回（指派列表, 明文 CID）
+    fn local_assignment(locals: &[LocalOperator<T>], cid_hash: &T::Hash) -> Option<(Vec<T::AccountId>, String)> {
+        let assigned = PinAssignments::<T>::get(cid_hash)?.into_inner();
+        if !locals.iter().any(|l| assigned.contains(&l.account)) {
+            return None;
+        }
+        Some((assigned, Self::plaintext_cid(cid_hash)?))
+    }
+
+    /// 指派运营者的集群 peer_id（用作 `user-allocations`）
+    pub fn peer_allocations(assigned: &[T::AccountId]) -> Vec<String> {
+        assigned
+            .iter()
+            .filter_map(|who| Operators::<T>::get(who))
+            .filter_map(|op| String::from_utf8(op.peer_id.into_inner()).ok())
+            .filter(|peer| !peer.is_empty())
+            .collect()
+    }
+
+    /// 向集群提交 Pin 并记录本地状态
+    fn submit_cluster_pin(
+        config: &ClusterConfig,
+        cid_hash: &T::Hash,
+        cid: &str,
+        assigned: &[T::AccountId],
+        replication: u32,
+        mut state: PinOcwState,
+        now: u32,
+    ) {
+        let allocations = Self::peer_allocations(assigned);
+        // 链上生成的内容（offchain 索引写入）尚未进入集群，需先上传
+        let blob_key = cluster::blob_key(cid.as_bytes());
+        let request = match sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, &blob_key) {
+            Some(blob) => cluster::add_request(config, &blob, &allocations, replication),
+            None => cluster::pin_request(config, cid, &allocations, replication),
+        };
+        match cluster::send(&request) {
+            Ok(_) => {
+                if !request.body.is_empty() {
+                    // 内容已进入集群，后续副本修复按 CID 重新 Pin 即可
+                    sp_io::offchain::local_storage_clear(StorageKind::PERSISTENT, &blob_key);
+                }
+                state.attempts = 0;
+                state.submitted = true;
+                state.next_attempt_at = now.saturating_add(REPIN_COOLDOWN_BLOCKS);
+                log::info!(target: "ipfs-ocw", "Pinned {} on cluster, allocations={:?}", cid, allocations);
+            },
+            Err(e) => {
+                state.attempts = state.attempts.saturating_add(1);
+                state.next_attempt_at = now.saturating_add(retry_backoff(state.attempts));
+                log::warn!(target: "ipfs-ocw", "Pin {} failed ({:?}), retry at block {}", cid, e, state.next_attempt_at);
+            },
+        }
+        save_pin_ocw_state(cid_hash, &state);
+    }
+
+    /// 根据集群状态为本节点每个指派运营者上报结果
+    fn report_local_status(
+        locals: &[LocalOperator<T>],
+        cid_hash: &T::Hash,
+        status: &GlobalPinStatus,
+        block_number: BlockNumberFor<T>,
+    ) {
+        let Some(assigned) = PinAssignments::<T>::get(cid_hash) else {
+            return;
+        };
+        let now: u32 = block_number.saturated_into();
+        for local in locals.iter().filter(|l| assigned.contains(&l.account)) {
+            let (pinned, code) = match status.status_of(&local.peer_id) {
+                Some(PeerPinStatus::Pinned) => {
+                    if PinSuccess::<T>::get(cid_hash, &local.account) {
+                        continue;
+                    }
+                    (true, 0)
+                },
+                Some(PeerPinStatus::PinError) => {
+                    // 本地 Pin 出错：解除已受理标记，退避后重新提交
+                    let mut state = get_pin_ocw_state(cid_hash);
+                    if state.submitted {
+                        state.submitted = false;
+                        state.attempts = state.attempts.saturating_add(1);
+                        state.next_attempt_at = now.saturating_add(retry_backoff(state.attempts));
+                        save_pin_ocw_state(cid_hash, &state);
+                    }
+                    (false, cluster::FAILURE_PIN_ERROR)
+                },
+                Some(PeerPinStatus::Remote) | Some(PeerPinStatus::Unpinned) => {
+                    (false, cluster::FAILURE_NOT_ALLOCATED)
+                },
+                _ => continue,
+            };
+            Self::submit_pin_report(local, cid_hash, pinned, code, status.pinned_count(), block_number);
+        }
+    }
+
+    /// 签名载荷 + 无签名交易上报 Pin 结果（冷却期内不重复上报相同结果）
+    fn submit_pin_report(
+        local: &LocalOperator<T>,
+        cid_hash: &T::Hash,
+        pinned: bool,
+        code: u16,
+        replicas: u32,
+        block_number: BlockNumberFor<T>,
+    ) {
+        let now: u32 = block_number.saturated_into();
+        let key = report_state_key(cid_hash, &local.account);
+        let last = sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, &key)
+            .and_then(|data| PinReportState::decode(&mut &data[..]).ok());
+        if let Some(last) = last {
+            let cooling = now < last.reported_at.saturating_add(REPORT_COOLDOWN_BLOCKS);
+            if last.pinned == pinned && last.code == code && cooling {
+                return;
+            }
+        }
+
+        let signer = Signer::<T, T::AuthorityId>::any_account().with_filter(vec![local.public.clone()]);
+        let sent = signer.send_unsigned_transaction(
+            |account| PinReportPayload {
+                cid_hash: *cid_hash,
+                pinned,
+                replicas,
+                code,
+                block_number,
+                public: account.public.clone(),
+            },
+            |payload, signature| Call::report_pin_status { payload, signature },
+        );
+
+        match sent {
+            Some((_, Ok(()))) => {
+                let state = PinReportState { pinned, code, reported_at: now };
+                sp_io::offchain::local_storage_set(StorageKind::PERSISTENT, &key, &state.encode());
+                log::info!(target: "ipfs-ocw", "Reported pin status for {:?}: pinned={} code={}", cid_hash, pinned, code);
+            },
+            _ => {
+                log::error!(target: "ipfs-ocw", "Failed to submit pin report for {:?}", cid_hash);
+            },
+        }
+    }
+}
@@ -9,7 +9,12 @@ use frame_support::{
 #[allow(unused_imports)]
 use frame_support::{assert_noop, assert_err};
 use sp_core::H256;
+use sp_core::offchain::{
+    testing::{TestOffchainExt, TestTransactionPoolExt},
+    OffchainDbExt, OffchainWorkerExt, TransactionPoolExt,
+};
 use sp_runtime::{
+    testing::{TestSignature, TestXt, UintAuthorityId},
     traits::{BlakeTwo256, IdentityLookup},
     BuildStorage,
 };
@@ -129,6 +134,60 @@ impl crate::Config for Test {
     type MonthlyPublicFeeQuota = MonthlyPublicFeeQuota;
     type QuotaResetPeriod = QuotaResetPeriod;
     type DefaultBillingPeriod = frame_support::traits::ConstU32<100>; // 100块测试周期
+    type Randomness = MockRandomness;
+    type ChallengeChunkSize = frame_support::traits::ConstU32<64>;
+    type ChallengeInterval = frame_support::traits::ConstU64<10>;
+    type ChallengeResponseWindow = frame_support::traits::ConstU64<5>;
+    type ChallengesPerRound = frame_support::traits::ConstU32<4>;
+    type ChallengeSlashAmount = frame_support::traits::ConstU128<1_000>;
+    type AttestationWindow = frame_support::traits::ConstU64<8>;
+    type AuthorityId = TestAuthId;
+}
+
+// ---- OCW 签名 ----
+
+pub type Extrinsic = TestXt<RuntimeCall, ()>;
+
+impl frame_system::offchain::SigningTypes for Test {
+    type Public = UintAuthorityId;
+    type Signature = TestSignature;
+}
+
+impl<LocalCall> frame_system::offchain::CreateTransactionBase<LocalCall> for Test
+where
+    RuntimeCall: From<LocalCall>,
+{
+    type RuntimeCall = RuntimeCall;
+    type Extrinsic = Extrinsic;
+}
+
+impl<LocalCall> frame_system::offchain::CreateBare<LocalCall> for Test
+where
+    RuntimeCall: From<LocalCall>,
+{
+    fn create_bare(call: RuntimeCall) -> Extrinsic {
+        Extrinsic::new_bare(call)
+    }
+}
+
+/// 测试签名身份：UintAuthorityId(n) 对应账户 n
+pub struct TestAuthId;
+impl frame_system::offchain::AppCrypto<UintAuthorityId, TestSignature> for TestAuthId {
+    type RuntimeAppPublic = UintAuthorityId;
+    type GenericPublic = UintAuthorityId;
+    type GenericSignature = TestSignature;
+}
+
+/// 本地 keystore 中的运营者密钥（账户 100）
+const OCW_OPERATOR: AccountId = 100;
+
+/// 确定性随机源：按 subject 哈希，便于测试复现挑战抽取
+pub struct MockRandomness;
+
+impl frame_support::traits::Randomness<H256, u64> for MockRandomness {
+    fn random(subject: &[u8]) -> (H256, u64) {
+        (H256::from(sp_io::hashing::blake2_256(subject)), System::block_number())
+    }
 }
 
 fn new_test_ext() -> sp_io::TestExternalities {
@@ -147,6 +206,21 @@ fn new_test_ext() -> sp_io::TestExternalities {
     t.into()
 }
 
+/// 创建带 OCW 扩展（HTTP / 本地存储 / 交易池）的测试环境
+///
+/// 本地 keystore 中仅有 `OCW_OPERATOR` 一个签名密钥。
+fn new_test_ext_with_ocw(
+    offchain: TestOffchainExt,
+    pool: TestTransactionPoolExt,
+) -> sp_io::TestExternalities {
+    let mut t = new_test_ext();
+    t.register_extension(OffchainDbExt::new(offchain.clone()));
+    t.register_extension(OffchainWorkerExt::new(offchain));
+    t.register_extension(TransactionPoolExt::new(pool));
+    UintAuthorityId::set_all_keys(vec![OCW_OPERATOR]);
+    t
+}
+
 /// Helper: 推进到指定块号
 fn run_to_block(n: u64) {
     while System::block_number() < n {
@@ -1051,3 +1125,939 @@ fn emergency_pause_and_resume_billing() {
     });
 }
 
+
+// ========================================
+// CID 锁定（CidLockManager）测试
+// ========================================
+
+/// 函数级中文注释：模拟 pallet-arbitration 生成的锁定原因（arb:{domain}:{id}）
+fn arb_reason(domain: [u8; 8], id: u64) -> Vec<u8> {
+    let mut reason = b"arb:".to_vec();
+    reason.extend_from_slice(&domain);
+    reason.push(b':');
+    reason.extend_from_slice(&id.to_le_bytes());
+    reason
+}
+
+/// 函数级中文注释：测试20 - 多原因锁定/解锁与到期
+#[test]
+fn cid_lock_unlock_and_expiry() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let cid = H256::repeat_byte(0x21);
+        let r1 = arb_reason(*b"otc_ord_", 7);
+        let r2 = b"manual-hold".to_vec();
+
+        assert_ok!(Ipfs::lock_cid(cid, r1.clone(), None));
+        assert_ok!(Ipfs::lock_cid(cid, r2.clone(), Some(20)));
+        assert!(Ipfs::is_locked(&cid));
+        assert_eq!(crate::CidLocks::<Test>::get(cid).len(), 2);
+
+        // 参数校验
+        assert_noop!(Ipfs::lock_cid(cid, vec![0u8; 65], None), Error::<Test>::LockReasonTooLong);
+        assert_noop!(Ipfs::lock_cid(cid, b"x".to_vec(), Some(1)), Error::<Test>::BadParams);
+        assert_noop!(Ipfs::unlock_cid(cid, b"unknown".to_vec()), Error::<Test>::CidLockNotFound);
+
+        // 解除仲裁锁后，仍有一条带期限的锁
+        assert_ok!(Ipfs::unlock_cid(cid, r1.clone()));
+        System::assert_last_event(RuntimeEvent::Ipfs(crate::Event::CidUnlocked {
+            cid_hash: cid,
+            reason: r1.try_into().unwrap(),
+        }));
+        assert!(Ipfs::is_locked(&cid));
+
+        // 到期后自动失效
+        System::set_block_number(19);
+        assert!(Ipfs::is_locked(&cid));
+        System::set_block_number(20);
+        assert!(!Ipfs::is_locked(&cid));
+
+        // 移除最后一条锁后存储被清理
+        assert_ok!(Ipfs::unlock_cid(cid, r2));
+        assert!(!crate::CidLocks::<Test>::contains_key(cid));
+    });
+}
+
+/// 函数级中文注释：测试21 - 锁定期间所有 Unpin 入口均被拒绝，解锁后恢复
+#[test]
+fn locked_cid_blocks_unpin_until_arbitration_closes() {
+    use sp_runtime::traits::Hash;
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let cid = b"bafy-evidence".to_vec();
+        let cid_hash = BlakeTwo256::hash(&cid[..]);
+        <crate::pallet::PinMeta<Test>>::insert(cid_hash, crate::PinMetadata {
+            replicas: 1,
+            size: 1_024u64,
+            created_at: 1u64,
+            last_activity: 1u64,
+        });
+        <crate::pallet::PinSubjectOf<Test>>::insert(cid_hash, (1u64, 1u64));
+        <crate::pallet::PinBilling<Test>>::insert(cid_hash, (10u64, 1u128, 0u8));
+
+        let reason = arb_reason(*b"otc_ord_", 42);
+        assert_ok!(Ipfs::lock_cid(cid_hash, reason.clone(), None));
+
+        assert_noop!(
+            Ipfs::request_unpin(RuntimeOrigin::signed(1), cid.clone()),
+            Error::<Test>::CidLocked
+        );
+        assert_noop!(
+            <Ipfs as crate::IpfsPinner<AccountId, Balance>>::unpin_cid(1, cid.clone()),
+            Error::<Test>::CidLocked
+        );
+        assert_noop!(
+            <Ipfs as crate::ContentRegistry>::unregister_content(b"evidence".to_vec(), cid.clone()),
+            Error::<Test>::CidLocked
+        );
+
+        // 仲裁结案 → 解锁 → 可正常 Unpin
+        assert_ok!(Ipfs::unlock_cid(cid_hash, reason));
+        assert_ok!(Ipfs::request_unpin(RuntimeOrigin::signed(1), cid));
+        let (_, _, state) = <crate::pallet::PinBilling<Test>>::get(cid_hash).unwrap();
+        assert_eq!(state, 2);
+    });
+}
+
+/// 函数级中文注释：测试22 - 锁定 CID 余额不足时由争议资金账户兜底扣费
+#[test]
+fn locked_cid_charged_from_dispute_funding() {
+    use crate::types::{SubjectInfo, SubjectType};
+    use frame_support::traits::Currency;
+
+    new_test_ext().execute_with(|| {
+        let price: Balance = 1_000_000_000_000_000;
+        crate::Pallet::<Test>::set_billing_params(
+            frame_system::RawOrigin::Root.into(),
+            Some(price),
+            Some(10),
+            Some(5),
+            Some(10),
+            Some(0),
+            Some(false),
+        )
+        .unwrap();
+        let cid = H256::repeat_byte(0x22);
+        <crate::pallet::PinMeta<Test>>::insert(cid, crate::PinMetadata {
+            replicas: 1,
+            size: 1_073_741_824u64,
+            created_at: 1u64,
+            last_activity: 1u64,
+        });
+        <crate::pallet::PinSubjectOf<Test>>::insert(cid, (2u64, 1u64));
+        let subject_vec = frame_support::BoundedVec::try_from(vec![SubjectInfo {
+            subject_type: SubjectType::General,
+            subject_id: 1,
+            funding_share: 100,
+        }])
+        .unwrap();
+        crate::CidToSubject::<Test>::insert(&cid, subject_vec);
+        let empty_operators: frame_support::BoundedVec<AccountId, frame_support::traits::ConstU32<16>> = Default::default();
+        crate::PinAssignments::<Test>::insert(&cid, empty_operators);
+        <crate::pallet::PinBilling<Test>>::insert(cid, (10u64, price, 0u8));
+        <crate::pallet::DueQueue<Test>>::mutate(10u64, |v| {
+            let _ = v.try_push(cid);
+        });
+
+        let dispute = crate::Pallet::<Test>::dispute_funding_account();
+        let _ = <Balances as Currency<AccountId>>::deposit_creating(&dispute, 2 * price);
+        assert_ok!(Ipfs::lock_cid(cid, arb_reason(*b"otc_ord_", 1), None));
+        run_to_block(10);
+
+        assert_ok!(crate::Pallet::<Test>::charge_due(frame_system::RawOrigin::Root.into(), 1));
+        let (next, _u, state) = <crate::pallet::PinBilling<Test>>::get(cid).unwrap();
+        assert_eq!(state, 0);
+        assert_eq!(next, 20);
+        assert_eq!(Balances::free_balance(dispute), price);
+        System::assert_has_event(RuntimeEvent::Ipfs(crate::Event::ChargedFromDisputeFunding {
+            cid_hash: cid,
+            amount: price,
+        }));
+    });
+}
+
+/// 函数级中文注释：测试23 - 锁定 CID 宽限期到期且无资金时延后处理，解锁后才标记过期
+#[test]
+fn locked_cid_grace_expiry_is_deferred() {
+    use crate::types::{BillingTask, ChargeLayer, GraceStatus, SubjectInfo, SubjectType};
+    use frame_support::traits::Hooks;
+
+    new_test_ext().execute_with(|| {
+        let cid = H256::repeat_byte(0x23);
+        let subject_vec = frame_support::BoundedVec::try_from(vec![SubjectInfo {
+            subject_type: SubjectType::General,
+            subject_id: 1,
+            funding_share: 100,
+        }])
+        .unwrap();
+        crate::CidToSubject::<Test>::insert(&cid, subject_vec);
+        <crate::pallet::PinBilling<Test>>::insert(cid, (10u64, 1u128, 1u8));
+        let task = BillingTask {
+            billing_period: 100u32,
+            amount_per_period: 1_000_000_000_000_000_000u128,
+            last_charge: 1u64,
+            grace_status: GraceStatus::InGrace { entered_at: 1u64, expires_at: 5u64 },
+            charge_layer: ChargeLayer::IpfsPool,
+        };
+        crate::BillingQueue::<Test>::insert(10u64, cid, task.clone());
+        assert_ok!(Ipfs::lock_cid(cid, arb_reason(*b"otc_ord_", 2), None));
+
+        // 锁定中：不标记过期，1小时后重试
+        run_to_block(10);
+        crate::Pallet::<Test>::on_finalize(10);
+        System::assert_has_event(RuntimeEvent::Ipfs(crate::Event::UnpinBlockedByLock { cid_hash: cid }));
+        assert!(!crate::BillingQueue::<Test>::contains_key(10u64, cid));
+        assert!(crate::BillingQueue::<Test>::contains_key(10u64 + 1200, cid));
+        assert_eq!(<crate::pallet::PinBilling<Test>>::get(cid).unwrap().2, 1);
+
+        // 解锁后宽限期再次到期 → 标记过期
+        assert_ok!(Ipfs::unlock_cid(cid, arb_reason(*b"otc_ord_", 2)));
+        crate::BillingQueue::<Test>::remove(10u64 + 1200, cid);
+        crate::BillingQueue::<Test>::insert(11u64, cid, task);
+        run_to_block(11);
+        crate::Pallet::<Test>::on_finalize(11);
+        assert_eq!(<crate::pallet::PinBilling<Test>>::get(cid).unwrap().2, 2);
+    });
+}
+
+// ========================================
+// 存储挑战（可验证存储证明）测试
+// ========================================
+
+/// 函数级中文注释：构造 5 个 64 字节分块（奇数叶子，覆盖节点晋升规则）
+fn challenge_chunks() -> Vec<Vec<u8>> {
+    (0u8..5).map(|i| vec![i; 64]).collect()
+}
+
+fn challenge_leaves() -> Vec<H256> {
+    challenge_chunks()
+        .iter()
+        .map(|c| crate::merkle::leaf_hash::<BlakeTwo256>(c))
+        .collect()
+}
+
+/// 函数级中文注释：注册运营者 100（保证金 100_000 已保留），为 CID 提交 Merkle 根并由运营者背书
+fn setup_challenged_pin() -> (H256, AccountId) {
+    let (cid, operator) = setup_committed_pin();
+    let root = crate::merkle::merkle_root::<BlakeTwo256>(&challenge_leaves()).unwrap();
+    assert_ok!(Ipfs::attest_content_root(RuntimeOrigin::signed(operator), cid, root, 5, true));
+    (cid, operator)
+}
+
+/// 函数级中文注释：注册运营者 100（已 Pin 成功）并由所有者提交 Merkle 根（尚未背书）
+fn setup_committed_pin() -> (H256, AccountId) {
+    use crate::{OperatorInfo, OperatorLayer};
+    use frame_support::traits::{Currency, ReservableCurrency};
+
+    let operator: AccountId = 100;
+    let _ = Balances::deposit_creating(&operator, 1_000_000);
+    assert_ok!(Balances::reserve(&operator, 100_000));
+    crate::OperatorBond::<Test>::insert(operator, 100_000u128);
+    crate::Operators::<Test>::insert(operator, OperatorInfo {
+        peer_id: frame_support::BoundedVec::try_from(b"QmOperator".to_vec()).unwrap(),
+        capacity_gib: 1000,
+        endpoint_hash: H256::repeat_byte(1),
+        cert_fingerprint: None,
+        status: 0,
+        registered_at: 1,
+        layer: OperatorLayer::Core,
+        priority: 10,
+    });
+
+    let cid = H256::repeat_byte(0x30);
+    <crate::pallet::PinMeta<Test>>::insert(cid, crate::PinMetadata {
+        replicas: 1,
+        size: 5 * 64,
+        created_at: 1u64,
+        last_activity: 1u64,
+    });
+    <crate::pallet::PinSubjectOf<Test>>::insert(cid, (1u64, 1u64));
+    crate::PinAssignments::<Test>::insert(cid, frame_support::BoundedVec::try_from(vec![operator]).unwrap());
+    crate::PinSuccess::<Test>::insert(cid, operator, true);
+
+    let root = crate::merkle::merkle_root::<BlakeTwo256>(&challenge_leaves()).unwrap();
+    assert_ok!(Ipfs::commit_content_root(RuntimeOrigin::signed(1), cid, root, 5));
+    (cid, operator)
+}
+
+/// 函数级中文注释：为挑战构造正确的分块与路径
+fn challenge_response(
+    chunk_index: u32,
+) -> (
+    frame_support::BoundedVec<u8, frame_support::traits::ConstU32<64>>,
+    frame_support::BoundedVec<H256, frame_support::traits::ConstU32<32>>,
+) {
+    let chunk = challenge_chunks()[chunk_index as usize].clone();
+    let proof = crate::merkle::merkle_proof::<BlakeTwo256>(&challenge_leaves(), chunk_index).unwrap();
+    (chunk.try_into().unwrap(), proof.try_into().unwrap())
+}
+
+/// 函数级中文注释：测试24 - Merkle 路径构建与验证（含奇数层晋升、篡改与越界）
+#[test]
+fn merkle_proof_round_trip() {
+    use crate::merkle::{leaf_hash, merkle_proof, merkle_root, verify_proof};
+
+    for count in 1u32..=9 {
+        let leaves: Vec<H256> = (0..count).map(|i| leaf_hash::<BlakeTwo256>(&i.to_le_bytes())).collect();
+        let root = merkle_root::<BlakeTwo256>(&leaves).unwrap();
+        for index in 0..count {
+            let proof = merkle_proof::<BlakeTwo256>(&leaves, index).unwrap();
+            assert!(verify_proof::<BlakeTwo256>(&root, leaves[index as usize], index, count, &proof));
+            // 错误分块
+            assert!(!verify_proof::<BlakeTwo256>(&root, leaf_hash::<BlakeTwo256>(b"x"), index, count, &proof));
+            // 多余路径节点
+            let mut longer = proof.clone();
+            longer.push(root);
+            assert!(!verify_proof::<BlakeTwo256>(&root, leaves[index as usize], index, count, &longer));
+        }
+        assert!(!verify_proof::<BlakeTwo256>(&root, leaves[0], count, count, &[]));
+    }
+    assert!(merkle_root::<BlakeTwo256>(&[]).is_none());
+}
+
+/// 函数级中文注释：测试25 - 提交 Merkle 根的权限与一次性约束
+#[test]
+fn commit_content_root_checks_owner_and_uniqueness() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let (cid, _) = setup_challenged_pin();
+        assert_eq!(crate::CommittedCidCount::<Test>::get(), 1);
+        assert_noop!(
+            Ipfs::commit_content_root(RuntimeOrigin::signed(1), cid, H256::zero(), 5),
+            Error::<Test>::ContentRootAlreadyCommitted
+        );
+
+        let other = H256::repeat_byte(0x31);
+        <crate::pallet::PinMeta<Test>>::insert(other, crate::PinMetadata {
+            replicas: 1,
+            size: 64,
+            created_at: 1u64,
+            last_activity: 1u64,
+        });
+        <crate::pallet::PinSubjectOf<Test>>::insert(other, (1u64, 1u64));
+        assert_noop!(
+            Ipfs::commit_content_root(RuntimeOrigin::signed(2), other, H256::zero(), 1),
+            Error::<Test>::NotOwner
+        );
+        assert_noop!(
+            Ipfs::commit_content_root(RuntimeOrigin::signed(1), other, H256::zero(), 0),
+            Error::<Test>::BadParams
+        );
+
+        // 删除承诺后索引保持稠密
+        assert_ok!(Ipfs::commit_content_root(RuntimeOrigin::signed(1), other, H256::zero(), 1));
+        crate::Pallet::<Test>::remove_content_commitment(&cid);
+        assert_eq!(crate::CommittedCidCount::<Test>::get(), 1);
+        assert_eq!(crate::CommittedCids::<Test>::get(0), Some(other));
+        assert_eq!(crate::CommittedCidIndex::<Test>::get(other), Some(0));
+    });
+}
+
+/// 函数级中文注释：测试26 - 挑战通过：计入 SLA，健康状态由挑战结果推导
+#[test]
+fn storage_challenge_passes_with_valid_proof() {
+    use frame_support::traits::Hooks;
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let (cid, operator) = setup_challenged_pin();
+        assert_eq!(crate::Pallet::<Test>::check_pin_health(&cid), crate::HealthStatus::Unknown);
+
+        run_to_block(10);
+        crate::Pallet::<Test>::on_finalize(10);
+        // 单一副本：同一轮内不会重复挑战
+        assert_eq!(crate::NextChallengeId::<Test>::get(), 1);
+        let challenge = crate::Challenges::<Test>::get(0).unwrap();
+        assert_eq!((challenge.operator, challenge.cid_hash, challenge.deadline), (operator, cid, 15));
+
+        let (chunk, proof) = challenge_response(challenge.chunk_index);
+        assert_noop!(
+            Ipfs::respond_challenge(RuntimeOrigin::signed(1), 0, chunk.clone(), proof.clone()),
+            Error::<Test>::NotChallengedOperator
+        );
+        assert_ok!(Ipfs::respond_challenge(RuntimeOrigin::signed(operator), 0, chunk.clone(), proof.clone()));
+        System::assert_has_event(RuntimeEvent::Ipfs(crate::Event::ChallengeResolved {
+            challenge_id: 0,
+            operator,
+            cid_hash: cid,
+            outcome: crate::ChallengeOutcome::Passed,
+        }));
+        assert_noop!(
+            Ipfs::respond_challenge(RuntimeOrigin::signed(operator), 0, chunk, proof),
+            Error::<Test>::ChallengeNotFound
+        );
+
+        assert_eq!(crate::OperatorSla::<Test>::get(operator).probe_ok, 1);
+        assert_eq!(crate::OperatorBond::<Test>::get(operator), 100_000);
+        assert_eq!(
+            crate::Pallet::<Test>::check_pin_health(&cid),
+            crate::HealthStatus::Healthy { current_replicas: 1 }
+        );
+    });
+}
+
+/// 函数级中文注释：测试27 - 无效证明与超时均记失败，并按连续失败阶梯罚没
+#[test]
+fn storage_challenge_failures_slash_progressively() {
+    use frame_support::traits::Hooks;
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let (cid, operator) = setup_challenged_pin();
+
+        // 第 1 轮：提交错误分块 → Failed，罚没 1×
+        run_to_block(10);
+        crate::Pallet::<Test>::on_finalize(10);
+        let challenge = crate::Challenges::<Test>::get(0).unwrap();
+        let (_, proof) = challenge_response(challenge.chunk_index);
+        let bad_chunk = frame_support::BoundedVec::try_from(vec![0xffu8; 64]).unwrap();
+        assert_ok!(Ipfs::respond_challenge(RuntimeOrigin::signed(operator), 0, bad_chunk, proof));
+        assert_eq!(crate::OperatorBond::<Test>::get(operator), 99_000);
+        assert_eq!(Balances::reserved_balance(operator), 99_000);
+
+        // 第 2 轮：不响应 → 截止区块结算为 Missed，罚没 2×
+        run_to_block(20);
+        crate::Pallet::<Test>::on_finalize(20);
+        let challenge = crate::Challenges::<Test>::get(1).unwrap();
+        run_to_block(challenge.deadline + 1);
+        let (chunk, proof) = challenge_response(challenge.chunk_index);
+        assert_noop!(
+            Ipfs::respond_challenge(RuntimeOrigin::signed(operator), 1, chunk, proof),
+            Error::<Test>::ChallengeExpired
+        );
+        crate::Pallet::<Test>::on_finalize(challenge.deadline);
+        System::assert_has_event(RuntimeEvent::Ipfs(crate::Event::ChallengeResolved {
+            challenge_id: 1,
+            operator,
+            cid_hash: cid,
+            outcome: crate::ChallengeOutcome::Missed,
+        }));
+        System::assert_has_event(RuntimeEvent::Ipfs(crate::Event::OperatorSlashed {
+            operator,
+            amount: 2_000,
+        }));
+        assert!(crate::Challenges::<Test>::get(1).is_none());
+        assert_eq!(crate::OperatorBond::<Test>::get(operator), 97_000);
+        assert_eq!(crate::ChallengeFailStreak::<Test>::get(operator), 2);
+        assert_eq!(crate::OperatorSla::<Test>::get(operator).probe_fail, 2);
+        assert_eq!(
+            crate::Pallet::<Test>::check_pin_health(&cid),
+            crate::HealthStatus::Critical { current_replicas: 0 }
+        );
+    });
+}
+
+/// 函数级中文注释：测试32 - 未经运营者背书的根不发起挑战（所有者单方提交的根不作为罚没依据）
+#[test]
+fn unattested_root_is_never_challenged() {
+    use frame_support::traits::Hooks;
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let (_cid, operator) = setup_committed_pin();
+
+        for block in [10u64, 20, 30] {
+            run_to_block(block);
+            crate::Pallet::<Test>::on_finalize(block);
+        }
+        assert_eq!(crate::NextChallengeId::<Test>::get(), 0);
+        assert_eq!(crate::OperatorBond::<Test>::get(operator), 100_000);
+        assert_eq!(crate::ChallengeFailStreak::<Test>::get(operator), 0);
+    });
+}
+
+/// 函数级中文注释：测试33 - 背书权限：根必须一致，且仅限已 Pin 成功的指派运营者
+#[test]
+fn attest_content_root_checks_operator_and_root() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let (cid, operator) = setup_committed_pin();
+        let root = crate::merkle::merkle_root::<BlakeTwo256>(&challenge_leaves()).unwrap();
+
+        assert_noop!(
+            Ipfs::attest_content_root(RuntimeOrigin::signed(operator), cid, H256::zero(), 5, true),
+            Error::<Test>::ContentRootMismatch
+        );
+        assert_noop!(
+            Ipfs::attest_content_root(RuntimeOrigin::signed(operator), cid, root, 4, true),
+            Error::<Test>::ContentRootMismatch
+        );
+        // 非指派运营者
+        assert_noop!(
+            Ipfs::attest_content_root(RuntimeOrigin::signed(101), cid, root, 5, true),
+            Error::<Test>::NotPinnedOperator
+        );
+        // 指派但尚未 Pin 成功
+        crate::PinSuccess::<Test>::insert(cid, operator, false);
+        assert_noop!(
+            Ipfs::attest_content_root(RuntimeOrigin::signed(operator), cid, root, 5, true),
+            Error::<Test>::NotPinnedOperator
+        );
+        crate::PinSuccess::<Test>::insert(cid, operator, true);
+
+        assert_ok!(Ipfs::attest_content_root(RuntimeOrigin::signed(operator), cid, root, 5, true));
+        System::assert_has_event(RuntimeEvent::Ipfs(crate::Event::ContentRootAttested {
+            cid_hash: cid,
+            operator,
+        }));
+        assert!(crate::RootAttestations::<Test>::contains_key(cid, operator));
+        // 已背书不能再拒绝
+        assert_noop!(
+            Ipfs::attest_content_root(RuntimeOrigin::signed(operator), cid, root, 5, false),
+            Error::<Test>::ContentRootAlreadyAttested
+        );
+        assert_noop!(
+            Ipfs::attest_content_root(RuntimeOrigin::signed(operator), H256::repeat_byte(0x99), root, 5, true),
+            Error::<Test>::ContentRootNotCommitted
+        );
+    });
+}
+
+/// 函数级中文注释：为 CID 增加第二个指派运营者 101（已 Pin 成功、未表态，保证金 100_000 已保留）
+fn add_second_operator(cid: H256, operator: AccountId) -> AccountId {
+    use frame_support::traits::{Currency, ReservableCurrency};
+
+    let second: AccountId = 101;
+    let _ = Balances::deposit_creating(&second, 1_000_000);
+    assert_ok!(Balances::reserve(&second, 100_000));
+    crate::OperatorBond::<Test>::insert(second, 100_000u128);
+    crate::Operators::<Test>::insert(second, crate::Operators::<Test>::get(operator).unwrap());
+    crate::PinAssignments::<Test>::insert(
+        cid,
+        frame_support::BoundedVec::try_from(vec![operator, second]).unwrap(),
+    );
+    crate::PinSuccess::<Test>::insert(cid, second, true);
+    second
+}
+
+/// 函数级中文注释：测试34 - 运营者拒绝根：承诺保留并通知所有者，未结挑战取消且暂停挑战，所有者可撤回后重新提交
+#[test]
+fn rejected_root_opens_dispute_for_owner() {
+    use frame_support::traits::Hooks;
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let (cid, operator) = setup_challenged_pin();
+        let second = add_second_operator(cid, operator);
+
+        // 已背书的运营者被挑战
+        run_to_block(10);
+        crate::Pallet::<Test>::on_finalize(10);
+        let challenge = crate::Challenges::<Test>::get(0).unwrap();
+        assert_eq!(challenge.operator, operator);
+
+        let root = crate::merkle::merkle_root::<BlakeTwo256>(&challenge_leaves()).unwrap();
+        assert_ok!(Ipfs::attest_content_root(RuntimeOrigin::signed(second), cid, root, 5, false));
+        System::assert_has_event(RuntimeEvent::Ipfs(crate::Event::ContentRootRejected {
+            cid_hash: cid,
+            operator: second,
+            owner: 1,
+        }));
+        assert_noop!(
+            Ipfs::attest_content_root(RuntimeOrigin::signed(second), cid, root, 5, true),
+            Error::<Test>::ContentRootAlreadyAttested
+        );
+        // 承诺与背书保留，未结挑战取消
+        assert!(crate::ContentCommitments::<Test>::get(cid).is_some());
+        assert_eq!(crate::CommittedCidCount::<Test>::get(), 1);
+        assert!(crate::RootAttestations::<Test>::contains_key(cid, operator));
+        assert!(crate::Challenges::<Test>::get(0).is_none());
+        assert!(crate::OpenChallengeOf::<Test>::get(operator, cid).is_none());
+
+        // 截止区块到达不会结算为 Missed，争议期间不再发起挑战
+        run_to_block(challenge.deadline);
+        crate::Pallet::<Test>::on_finalize(challenge.deadline);
+        run_to_block(20);
+        crate::Pallet::<Test>::on_finalize(20);
+        assert_eq!(crate::NextChallengeId::<Test>::get(), 1);
+        assert_eq!(crate::OperatorBond::<Test>::get(operator), 100_000);
+        assert_eq!(crate::ChallengeFailStreak::<Test>::get(operator), 0);
+
+        // 仅所有者可撤回，撤回后可重新提交
+        assert_noop!(
+            Ipfs::withdraw_content_root(RuntimeOrigin::signed(2), cid),
+            Error::<Test>::NotOwner
+        );
+        assert_ok!(Ipfs::withdraw_content_root(RuntimeOrigin::signed(1), cid));
+        System::assert_has_event(RuntimeEvent::Ipfs(crate::Event::ContentRootWithdrawn { cid_hash: cid }));
+        assert!(crate::ContentCommitments::<Test>::get(cid).is_none());
+        assert_eq!(crate::CommittedCidCount::<Test>::get(), 0);
+        assert!(!crate::RootAttestations::<Test>::contains_key(cid, operator));
+        assert!(!crate::RootRejections::<Test>::contains_key(cid, second));
+        assert_ok!(Ipfs::commit_content_root(RuntimeOrigin::signed(1), cid, root, 5));
+    });
+}
+
+/// 函数级中文注释：测试35 - 治理裁决根争议：败诉方记一次失败并罚没
+#[test]
+fn root_dispute_resolution_slashes_losing_side() {
+    use frame_support::traits::Hooks;
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let (cid, operator) = setup_challenged_pin();
+        let second = add_second_operator(cid, operator);
+        let root = crate::merkle::merkle_root::<BlakeTwo256>(&challenge_leaves()).unwrap();
+
+        assert_noop!(
+            Ipfs::resolve_root_dispute(RuntimeOrigin::root(), cid, true),
+            Error::<Test>::ContentRootNotDisputed
+        );
+        assert_ok!(Ipfs::attest_content_root(RuntimeOrigin::signed(second), cid, root, 5, false));
+        assert_noop!(
+            Ipfs::resolve_root_dispute(RuntimeOrigin::signed(1), cid, true),
+            sp_runtime::DispatchError::BadOrigin
+        );
+
+        // 根有效：拒绝者罚没，争议解除，挑战恢复
+        assert_ok!(Ipfs::resolve_root_dispute(RuntimeOrigin::root(), cid, true));
+        System::assert_has_event(RuntimeEvent::Ipfs(crate::Event::RootDisputeResolved {
+            cid_hash: cid,
+            root_valid: true,
+        }));
+        assert_eq!(crate::OperatorBond::<Test>::get(second), 99_000);
+        assert_eq!(crate::OperatorSla::<Test>::get(second).probe_fail, 1);
+        assert_eq!(crate::OperatorBond::<Test>::get(operator), 100_000);
+        assert!(!crate::Pallet::<Test>::root_disputed(&cid));
+        run_to_block(10);
+        crate::Pallet::<Test>::on_finalize(10);
+        assert_eq!(crate::Challenges::<Test>::get(0).unwrap().operator, operator);
+
+        // 再次拒绝后裁决根无效：背书者罚没，承诺撤销
+        assert_ok!(Ipfs::attest_content_root(RuntimeOrigin::signed(second), cid, root, 5, false));
+        assert_ok!(Ipfs::resolve_root_dispute(RuntimeOrigin::root(), cid, false));
+        assert_eq!(crate::OperatorBond::<Test>::get(operator), 99_000);
+        assert_eq!(crate::OperatorBond::<Test>::get(second), 99_000);
+        assert!(crate::ContentCommitments::<Test>::get(cid).is_none());
+        assert!(crate::Challenges::<Test>::get(0).is_none());
+        assert!(!crate::RootRejections::<Test>::contains_key(cid, second));
+    });
+}
+
+/// 函数级中文注释：测试36 - 背书窗口到期：未表态的指派运营者记为失败并罚没，已背书/已拒绝者不受影响
+#[test]
+fn missed_attestation_counts_as_failure() {
+    use frame_support::traits::Hooks;
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let (cid, operator) = setup_committed_pin();
+        let second = add_second_operator(cid, operator);
+        // 窗口 8 块：截止区块 9
+        assert_eq!(crate::AttestationDeadlines::<Test>::get(9).into_inner(), vec![cid]);
+
+        run_to_block(8);
+        crate::Pallet::<Test>::on_finalize(8);
+        assert_eq!(crate::OperatorBond::<Test>::get(operator), 100_000);
+
+        let root = crate::merkle::merkle_root::<BlakeTwo256>(&challenge_leaves()).unwrap();
+        assert_ok!(Ipfs::attest_content_root(RuntimeOrigin::signed(second), cid, root, 5, true));
+        run_to_block(9);
+        crate::Pallet::<Test>::on_finalize(9);
+        System::assert_has_event(RuntimeEvent::Ipfs(crate::Event::AttestationMissed {
+            cid_hash: cid,
+            operator,
+        }));
+        assert_eq!(crate::OperatorBond::<Test>::get(operator), 99_000);
+        assert_eq!(crate::ChallengeFailStreak::<Test>::get(operator), 1);
+        assert_eq!(crate::OperatorSla::<Test>::get(operator).probe_fail, 1);
+        assert_eq!(crate::OperatorBond::<Test>::get(second), 100_000);
+        assert!(crate::AttestationDeadlines::<Test>::get(9).is_empty());
+
+        // 逾期后仍可补背书
+        assert_ok!(Ipfs::attest_content_root(RuntimeOrigin::signed(operator), cid, root, 5, true));
+
+        // 撤回后重新提交：旧截止区块不再结算
+        assert_ok!(Ipfs::withdraw_content_root(RuntimeOrigin::signed(1), cid));
+        assert_ok!(Ipfs::commit_content_root(RuntimeOrigin::signed(1), cid, root, 5));
+        crate::AttestationDeadlines::<Test>::insert(
+            12,
+            frame_support::BoundedVec::try_from(vec![cid]).unwrap(),
+        );
+        run_to_block(12);
+        crate::Pallet::<Test>::on_finalize(12);
+        assert_eq!(crate::OperatorBond::<Test>::get(operator), 99_000);
+        assert_eq!(crate::OperatorBond::<Test>::get(second), 100_000);
+    });
+}
+
+/// 函数级中文注释：测试37 - on_initialize 预留 on_finalize 中挑战任务的权重
+#[test]
+fn on_initialize_reserves_challenge_weight() {
+    use crate::weights::WeightInfo;
+    use frame_support::traits::{Get, Hooks};
+
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let _ = setup_committed_pin();
+        let settle = <() as WeightInfo>::settle_challenges(4);
+        let read = <Test as frame_system::Config>::DbWeight::get().reads(1);
+
+        // 普通区块：仅结算超时挑战
+        assert_eq!(
+            crate::Pallet::<Test>::on_initialize(7),
+            read + settle + <() as WeightInfo>::expire_attestations(0)
+        );
+        // 背书截止区块：按队列长度计入
+        assert_eq!(
+            crate::Pallet::<Test>::on_initialize(9),
+            read + settle + <() as WeightInfo>::expire_attestations(1)
+        );
+        // 挑战轮区块：计入发起新一轮
+        assert_eq!(
+            crate::Pallet::<Test>::on_initialize(10),
+            read + settle
+                + <() as WeightInfo>::expire_attestations(0)
+                + <() as WeightInfo>::issue_challenge_round(4)
+        );
+    });
+}
+
+// ========================================
+// IPFS Cluster OCW 集成测试（模拟集群）
+// ========================================
+
+const CLUSTER_CID: &str = "bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
+
+/// 函数级中文注释：注册运营者 100（本节点，peer-a）与 101（远端，peer-b），
+/// 创建 2 副本的待处理 Pin 并指派给二者
+fn setup_cluster_pin() -> H256 {
+    use crate::{OperatorInfo, OperatorLayer};
+
+    for (operator, peer) in [(OCW_OPERATOR, b"peer-a"), (101, b"peer-b")] {
+        crate::Operators::<Test>::insert(operator, OperatorInfo {
+            peer_id: frame_support::BoundedVec::try_from(peer.to_vec()).unwrap(),
+            capacity_gib: 1000,
+            endpoint_hash: H256::repeat_byte(1),
+            cert_fingerprint: None,
+            status: 0,
+            registered_at: 1,
+            layer: OperatorLayer::Core,
+            priority: 10,
+        });
+    }
+
+    let cid = H256::repeat_byte(0x40);
+    <crate::pallet::PinMeta<Test>>::insert(cid, crate::PinMetadata {
+        replicas: 2,
+        size: 1024,
+        created_at: 1u64,
+        last_activity: 1u64,
+    });
+    crate::PendingPins::<Test>::insert(cid, (1u64, 2u32, 1u64, 1024u64, 0u128));
+    crate::PinAssignments::<Test>::insert(
+        cid,
+        frame_support::BoundedVec::try_from(vec![OCW_OPERATOR, 101]).unwrap(),
+    );
+    crate::CidRegistry::<Test>::insert(
+        cid,
+        frame_support::BoundedVec::try_from(CLUSTER_CID.as_bytes().to_vec()).unwrap(),
+    );
+    cid
+}
+
+fn cluster_allocations() -> Vec<String> {
+    vec!["peer-a".into(), "peer-b".into()]
+}
+
+fn take_submitted_call(pool_state: &mut sp_core::offchain::testing::PoolState) -> crate::Call<Test> {
+    use codec::Decode;
+
+    assert_eq!(pool_state.transactions.len(), 1);
+    let raw = pool_state.transactions.pop().unwrap();
+    let tx = Extrinsic::decode(&mut &*raw).unwrap();
+    match tx.function {
+        RuntimeCall::Ipfs(call) => call,
+        other => panic!("unexpected call: {:?}", other),
+    }
+}
+
+/// 函数级中文注释：测试28 - OCW 携带分配发起 Pin，轮询到本节点完成后上报 mark_pinned
+#[test]
+fn ocw_pins_with_allocations_and_reports_pinned() {
+    use crate::cluster::{ClusterConfig, PeerPinStatus};
+    use crate::mock_cluster::MockCluster;
+    use frame_support::traits::Hooks;
+    use sp_runtime::traits::ValidateUnsigned;
+    use sp_runtime::transaction_validity::{InvalidTransaction, TransactionSource};
+
+    let (offchain, offchain_state) = TestOffchainExt::new();
+    let (pool, pool_state) = TestTransactionPoolExt::new();
+    new_test_ext_with_ocw(offchain, pool).execute_with(|| {
+        System::set_block_number(1);
+        let cid = setup_cluster_pin();
+        let mut cluster = MockCluster::new(ClusterConfig::load());
+
+        // 第 1 块：发起 Pin（两个指派运营者均在 user-allocations 中），随后轮询
+        cluster.expect_pin(&mut offchain_state.write(), CLUSTER_CID, &cluster_allocations(), 2);
+        cluster.set_status(CLUSTER_CID, "peer-a", PeerPinStatus::Pinned);
+        cluster.expect_status(&mut offchain_state.write(), &[CLUSTER_CID.into()]);
+        Ipfs::offchain_worker(1);
+        assert!(crate::ocw::get_pin_ocw_state(&cid).submitted);
+
+        let call = take_submitted_call(&mut pool_state.write());
+        assert!(Ipfs::validate_unsigned(TransactionSource::Local, &call).is_ok());
+        let crate::Call::report_pin_status { payload, signature } = call.clone() else {
+            panic!("unexpected call");
+        };
+        assert_eq!(payload.cid_hash, cid);
+        assert!(payload.pinned);
+        assert_eq!(payload.replicas, 1);
+        assert_eq!(payload.public, UintAuthorityId(OCW_OPERATOR));
+
+        assert_ok!(Ipfs::report_pin_status(RuntimeOrigin::none(), payload, signature));
+        assert!(crate::PinSuccess::<Test>::get(cid, OCW_OPERATOR));
+        assert_eq!(crate::PinStateOf::<Test>::get(cid), 1);
+        assert!(crate::PendingPins::<Test>::contains_key(cid));
+        // 重复的成功上报被交易池拒绝
+        assert_eq!(
+            Ipfs::validate_unsigned(TransactionSource::Local, &call),
+            InvalidTransaction::Custom(3).into()
+        );
+
+        // 第 2 块：已受理不再重复 Pin，仅轮询；本节点已记成功，不再上报
+        cluster.expect_status(&mut offchain_state.write(), &[CLUSTER_CID.into()]);
+        Ipfs::offchain_worker(2);
+        assert!(pool_state.read().transactions.is_empty());
+
+        // 远端运营者完成后达到副本数
+        assert_ok!(Ipfs::mark_pinned(RuntimeOrigin::signed(101), cid, 2));
+        assert_eq!(crate::PinStateOf::<Test>::get(cid), 2);
+        assert!(!crate::PendingPins::<Test>::contains_key(cid));
+    });
+}
+
+/// 函数级中文注释：测试29 - 本节点 pin_error 上报 mark_pin_failed，并在退避后重新 Pin
+#[test]
+fn ocw_reports_pin_error_and_retries() {
+    use crate::cluster::{ClusterConfig, PeerPinStatus, FAILURE_PIN_ERROR};
+    use crate::mock_cluster::MockCluster;
+    use frame_support::traits::Hooks;
+
+    let (offchain, offchain_state) = TestOffchainExt::new();
+    let (pool, pool_state) = TestTransactionPoolExt::new();
+    new_test_ext_with_ocw(offchain, pool).execute_with(|| {
+        System::set_block_number(1);
+        let cid = setup_cluster_pin();
+        let mut cluster = MockCluster::new(ClusterConfig::load());
+
+        cluster.expect_pin(&mut offchain_state.write(), CLUSTER_CID, &cluster_allocations(), 2);
+        cluster.set_status(CLUSTER_CID, "peer-a", PeerPinStatus::PinError);
+        cluster.expect_status(&mut offchain_state.write(), &[CLUSTER_CID.into()]);
+        Ipfs::offchain_worker(1);
+
+        let crate::Call::report_pin_status { payload, signature } =
+            take_submitted_call(&mut pool_state.write())
+        else {
+            panic!("unexpected call");
+        };
+        assert!(!payload.pinned);
+        assert_eq!(payload.code, FAILURE_PIN_ERROR);
+        assert_ok!(Ipfs::report_pin_status(RuntimeOrigin::none(), payload, signature));
+        System::assert_has_event(RuntimeEvent::Ipfs(crate::Event::PinMarkedFailed(cid, FAILURE_PIN_ERROR)));
+        assert!(!crate::PinSuccess::<Test>::get(cid, OCW_OPERATOR));
+
+        // 解除已受理标记并退避
+        let state = crate::ocw::get_pin_ocw_state(&cid);
+        assert!(!state.submitted);
+        assert_eq!(state.next_attempt_at, 1 + crate::ocw::retry_backoff(1));
+
+        // 退避期内只轮询；相同失败结果在冷却期内不重复上报
+        cluster.expect_status(&mut offchain_state.write(), &[CLUSTER_CID.into()]);
+        Ipfs::offchain_worker(2);
+        assert!(pool_state.read().transactions.is_empty());
+
+        // 退避结束后重新 Pin，集群恢复后上报成功
+        let retry_at = state.next_attempt_at as u64;
+        cluster.set_status(CLUSTER_CID, "peer-a", PeerPinStatus::Pinned);
+        cluster.expect_pin(&mut offchain_state.write(), CLUSTER_CID, &cluster_allocations(), 2);
+        cluster.expect_status(&mut offchain_state.write(), &[CLUSTER_CID.into()]);
+        Ipfs::offchain_worker(retry_at);
+        let crate::Call::report_pin_status { payload, .. } = take_submitted_call(&mut pool_state.write()) else {
+            panic!("unexpected call");
+        };
+        assert!(payload.pinned);
+    });
+}
+
+/// 函数级中文注释：测试30 - 已 Pinned 的 CID 集群副本不足时按原分配重新 Pin
+#[test]
+fn ocw_repins_under_replicated_cid() {
+    use crate::cluster::{ClusterConfig, PeerPinStatus};
+    use crate::mock_cluster::MockCluster;
+    use frame_support::traits::Hooks;
+
+    let (offchain, offchain_state) = TestOffchainExt::new();
+    let (pool, pool_state) = TestTransactionPoolExt::new();
+    new_test_ext_with_ocw(offchain, pool).execute_with(|| {
+        System::set_block_number(1);
+        let cid = setup_cluster_pin();
+        assert_ok!(Ipfs::mark_pinned(RuntimeOrigin::signed(OCW_OPERATOR), cid, 2));
+        assert_ok!(Ipfs::mark_pinned(RuntimeOrigin::signed(101), cid, 2));
+        assert_eq!(crate::PinStateOf::<Test>::get(cid), 2);
+
+        // 副本齐全：仅轮询
+        let mut cluster = MockCluster::new(ClusterConfig::load());
+        cluster.set_status(CLUSTER_CID, "peer-a", PeerPinStatus::Pinned);
+        cluster.set_status(CLUSTER_CID, "peer-b", PeerPinStatus::Pinned);
+        cluster.expect_status(&mut offchain_state.write(), &[CLUSTER_CID.into()]);
+        Ipfs::offchain_worker(1);
+        assert!(!crate::ocw::get_pin_ocw_state(&cid).submitted);
+
+        // 远端副本丢失：重新 Pin，进入冷却期
+        cluster.set_status(CLUSTER_CID, "peer-b", PeerPinStatus::Unpinned);
+        cluster.expect_status(&mut offchain_state.write(), &[CLUSTER_CID.into()]);
+        cluster.expect_pin(&mut offchain_state.write(), CLUSTER_CID, &cluster_allocations(), 2);
+        Ipfs::offchain_worker(2);
+        let state = crate::ocw::get_pin_ocw_state(&cid);
+        assert!(state.submitted);
+        assert_eq!(state.next_attempt_at, 2 + crate::ocw::REPIN_COOLDOWN_BLOCKS);
+
+        // 冷却期内不再巡检该 CID，也不上报
+        Ipfs::offchain_worker(3);
+        assert!(pool_state.read().transactions.is_empty());
+    });
+}
+
+/// 函数级中文注释：测试31 - 链上生成内容的确定性 CID，OCW 经 `/add` 上传本地待上传内容后清除
+#[test]
+fn ocw_uploads_indexed_blob_via_add() {
+    use crate::cluster::{blob_key, ClusterConfig, PeerPinStatus};
+    use crate::mock_cluster::MockCluster;
+    use frame_support::traits::Hooks;
+    use sp_core::offchain::StorageKind;
+
+    let (offchain, offchain_state) = TestOffchainExt::new();
+    let (pool, pool_state) = TestTransactionPoolExt::new();
+    new_test_ext_with_ocw(offchain, pool).execute_with(|| {
+        System::set_block_number(1);
+        let cid = setup_cluster_pin();
+
+        // raw + blake2b-256 的 CIDv1 文本形式固定以 `bafk2bzace` 开头
+        let blob = b"archived batch".to_vec();
+        let text_cid = crate::cid::raw_cid(&blob);
+        assert!(text_cid.starts_with(b"bafk2bzace"));
+        assert_eq!(text_cid, crate::cid::raw_cid(&blob));
+        assert_ne!(text_cid, crate::cid::raw_cid(b"other batch"));
+        let text_cid = String::from_utf8(text_cid).unwrap();
+        crate::CidRegistry::<Test>::insert(
+            cid,
+            frame_support::BoundedVec::try_from(text_cid.as_bytes().to_vec()).unwrap(),
+        );
+        sp_io::offchain::local_storage_set(StorageKind::PERSISTENT, &blob_key(text_cid.as_bytes()), &blob);
+
+        let mut cluster = MockCluster::new(ClusterConfig::load());
+        cluster.expect_add(&mut offchain_state.write(), &text_cid, &blob, &cluster_allocations(), 2);
+        cluster.set_status(&text_cid, "peer-a", PeerPinStatus::Pinned);
+        cluster.expect_status(&mut offchain_state.write(), &[text_cid.clone()]);
+        Ipfs::offchain_worker(1);
+
+        assert!(crate::ocw::get_pin_ocw_state(&cid).submitted);
+        assert!(sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, &blob_key(text_cid.as_bytes())).is_none());
+        let crate::Call::report_pin_status { payload, .. } = take_submitted_call(&mut pool_state.write()) else {
+            panic!("unexpected call");
+        };
+        assert!(payload.pinned);
+    });
+}
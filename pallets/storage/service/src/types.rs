@@ -475,6 +475,8 @@ pub enum ChargeLayer {
     SubjectFunding,
     /// 第3层：宽限期（不扣费，等待充值）
     GracePeriod,
+    /// 争议兜底账户（仅被仲裁锁定的 CID，用户与公共池均不足时使用）
+    DisputeFunding,
 }
 
 impl Default for ChargeLayer {
@@ -550,6 +552,85 @@ pub enum UnpinReason {
     OperatorOffline,
 }
 
+/// 函数级详细中文注释：CID 锁定记录
+/// 
+/// 由 `CidLockManager::lock_cid` 写入，用于仲裁期间保护证据 CID：
+/// - 存在任一未过期锁时，所有 Unpin 路径（手动、宽限期过期、OCW 物理删除）均被阻止
+/// - 同一 CID 可被多个原因同时锁定（如同一证据被多个案件引用），需逐个解锁
+/// 
+/// 字段说明：
+/// - reason：锁定原因（如 "arb:" + domain + ":" + id），解锁时必须匹配
+/// - locked_at：锁定区块
+/// - until：可选的到期区块（None = 直到显式解锁）
+#[derive(Clone, Encode, Decode, DecodeWithMemTracking, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[scale_info(skip_type_params(BlockNumber))]
+pub struct CidLock<BlockNumber> {
+    /// 锁定原因
+    pub reason: BoundedVec<u8, ConstU32<64>>,
+    /// 锁定区块
+    pub locked_at: BlockNumber,
+    /// 到期区块（None = 永久，直到解锁）
+    pub until: Option<BlockNumber>,
+}
+
+impl<BlockNumber: PartialOrd> CidLock<BlockNumber> {
+    /// 在区块 `now` 时锁是否仍然有效
+    pub fn is_active(&self, now: &BlockNumber) -> bool {
+        self.until.as_ref().map_or(true, |until| now < until)
+    }
+}
+
+// ============================================================================
+// 存储挑战（可验证存储证明）相关类型
+// ============================================================================
+
+/// 函数级详细中文注释：内容 Merkle 承诺
+/// 
+/// Pin 时由内容所有者提交，存储挑战据此在链上验证运营者返回的分块：
+/// - merkle_root：按 `ChallengeChunkSize` 切块后构建的 Merkle 根（规则见 `merkle` 模块）
+/// - chunk_count：分块总数（叶子数）
+/// - committed_at：提交区块
+#[derive(Clone, Encode, Decode, DecodeWithMemTracking, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[scale_info(skip_type_params(Hash, BlockNumber))]
+pub struct ContentCommitment<Hash, BlockNumber> {
+    /// Merkle 根
+    pub merkle_root: Hash,
+    /// 分块总数
+    pub chunk_count: u32,
+    /// 提交区块
+    pub committed_at: BlockNumber,
+}
+
+/// 函数级详细中文注释：存储挑战
+/// 
+/// 链上随机抽取（运营者, CID, 分块序号），运营者须在 deadline 前
+/// 返回该分块原文与 Merkle 路径。
+#[derive(Clone, Encode, Decode, DecodeWithMemTracking, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[scale_info(skip_type_params(AccountId, Hash, BlockNumber))]
+pub struct StorageChallenge<AccountId, Hash, BlockNumber> {
+    /// 被挑战的运营者
+    pub operator: AccountId,
+    /// 被挑战的 CID 哈希
+    pub cid_hash: Hash,
+    /// 被挑战的分块序号（从 0 开始）
+    pub chunk_index: u32,
+    /// 发起区块
+    pub issued_at: BlockNumber,
+    /// 响应截止区块（含）
+    pub deadline: BlockNumber,
+}
+
+/// 函数级详细中文注释：存储挑战结果
+#[derive(Clone, Copy, Encode, Decode, DecodeWithMemTracking, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum ChallengeOutcome {
+    /// 证明通过
+    Passed,
+    /// 证明无效（分块或路径不匹配）
+    Failed,
+    /// 超时未响应
+    Missed,
+}
+
 // ============================================================================
 // 运营者分层架构相关类型（Layer 1/Layer 2/Layer 3）
 // ============================================================================
@@ -11,6 +11,14 @@ pub trait WeightInfo {
     fn mark_pin_failed() -> Weight;
     fn charge_due(n: u32) -> Weight;
     fn set_billing_params() -> Weight;
+    fn commit_content_root() -> Weight;
+    fn attest_content_root() -> Weight;
+    fn respond_challenge(n: u32) -> Weight;
+    fn withdraw_content_root() -> Weight;
+    fn resolve_root_dispute() -> Weight;
+    fn settle_challenges(n: u32) -> Weight;
+    fn expire_attestations(n: u32) -> Weight;
+    fn issue_challenge_round(n: u32) -> Weight;
 }
 
 /// Substrate 权重实现
@@ -42,6 +50,62 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(1))
             .saturating_add(T::DbWeight::get().writes(1))
     }
+    /// PinMeta + PinSubjectOf + ContentCommitments + CommittedCidCount 读；
+    /// 承诺与稠密索引 4 写
+    fn commit_content_root() -> Weight {
+        Weight::from_parts(35_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().writes(4))
+    }
+    /// 最坏情况为拒绝：按 16 个指派运营者撤销未结挑战，并写入拒绝记录
+    fn attest_content_root() -> Weight {
+        Weight::from_parts(60_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(6 + 16 * 2))
+            .saturating_add(T::DbWeight::get().writes(1 + 16 * 3))
+    }
+    /// 分块哈希（按字节线性）+ 最多 32 层 Merkle 路径验证；
+    /// 结算写挑战索引、SLA、连续失败计数，失败时罚没保证金
+    fn respond_challenge(n: u32) -> Weight {
+        Weight::from_parts(50_000_000, 0)
+            .saturating_add(Weight::from_parts(2_000, 0).saturating_mul(n as u64))
+            .saturating_add(Weight::from_parts(32 * 1_500_000, 0))
+            .saturating_add(T::DbWeight::get().reads(5))
+            .saturating_add(T::DbWeight::get().writes(9))
+    }
+    /// 按 16 个指派运营者撤销未结挑战，并清理承诺、索引、挑战结果、背书与拒绝
+    fn withdraw_content_root() -> Weight {
+        Weight::from_parts(50_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(5 + 16 * 2))
+            .saturating_add(T::DbWeight::get().writes(7 + 16 * 3))
+    }
+    /// 最坏情况为根无效：16 个背书者各记一次失败并罚没，随后撤回承诺
+    fn resolve_root_dispute() -> Weight {
+        Weight::from_parts(50_000_000, 0)
+            .saturating_add(Weight::from_parts(16 * 30_000_000, 0))
+            .saturating_add(T::DbWeight::get().reads(5 + 16 * 6))
+            .saturating_add(T::DbWeight::get().writes(7 + 16 * 8))
+    }
+    /// 每个超时挑战：读挑战，删除挑战索引，更新 SLA、连续失败并罚没
+    fn settle_challenges(n: u32) -> Weight {
+        Weight::from_parts(5_000_000, 0)
+            .saturating_add(Weight::from_parts(30_000_000, 0).saturating_mul(n as u64))
+            .saturating_add(T::DbWeight::get().reads(1 + n as u64 * 4))
+            .saturating_add(T::DbWeight::get().writes(1 + n as u64 * 8))
+    }
+    /// 每个到期承诺：按 16 个指派运营者检查背书、拒绝与状态，未表态者记失败并罚没
+    fn expire_attestations(n: u32) -> Weight {
+        Weight::from_parts(5_000_000, 0)
+            .saturating_add(Weight::from_parts(16 * 30_000_000, 0).saturating_mul(n as u64))
+            .saturating_add(T::DbWeight::get().reads(1 + n as u64 * (3 + 16 * 6)))
+            .saturating_add(T::DbWeight::get().writes(1 + n as u64 * 16 * 5))
+    }
+    /// 每次抽取：读索引、承诺、计费状态、争议、指派与背书，写挑战及索引
+    fn issue_challenge_round(n: u32) -> Weight {
+        Weight::from_parts(10_000_000, 0)
+            .saturating_add(Weight::from_parts(15_000_000, 0).saturating_mul(n as u64))
+            .saturating_add(T::DbWeight::get().reads(2 + n as u64 * (6 + 16)))
+            .saturating_add(T::DbWeight::get().writes(n as u64 * 4))
+    }
 }
 
 /// 默认权重实现（用于测试）
@@ -51,4 +115,12 @@ impl WeightInfo for () {
     fn mark_pin_failed() -> Weight { Weight::from_parts(30_000_000, 0) }
     fn charge_due(n: u32) -> Weight { Weight::from_parts(50_000_000 + 30_000_000 * n as u64, 0) }
     fn set_billing_params() -> Weight { Weight::from_parts(20_000_000, 0) }
+    fn commit_content_root() -> Weight { Weight::from_parts(35_000_000, 0) }
+    fn attest_content_root() -> Weight { Weight::from_parts(60_000_000, 0) }
+    fn respond_challenge(n: u32) -> Weight { Weight::from_parts(98_000_000 + 2_000 * n as u64, 0) }
+    fn withdraw_content_root() -> Weight { Weight::from_parts(50_000_000, 0) }
+    fn resolve_root_dispute() -> Weight { Weight::from_parts(530_000_000, 0) }
+    fn settle_challenges(n: u32) -> Weight { Weight::from_parts(5_000_000 + 30_000_000 * n as u64, 0) }
+    fn expire_attestations(n: u32) -> Weight { Weight::from_parts(5_000_000 + 480_000_000 * n as u64, 0) }
+    fn issue_challenge_round(n: u32) -> Weight { Weight::from_parts(10_000_000 + 15_000_000 * n as u64, 0) }
 }
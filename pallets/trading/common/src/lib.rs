@@ -8,7 +8,8 @@
 //! - 公共类型定义（TronAddress, MomentOf 等）
 //! - 公共 Trait 定义（PricingProvider, MakerInterface, MakerCreditInterface）
 //! - 脱敏函数（姓名、身份证、生日）
-//! - TRON 地址验证
+//! - TRON / EVM 地址验证
+//! - 支付通道（PaymentRail）定义与查询接口
 //! - 时间转换工具
 //!
 //! ## 特点
@@ -23,6 +24,7 @@
 //! - v0.2.0 (2026-01-18): 添加统一的 MakerCreditInterface trait
 //! - v0.3.0 (2026-01-18): 添加时间转换工具函数
 //! - v0.4.0 (2026-01-18): 统一公共类型和 Trait 定义
+//! - v0.6.0 (2026-10-17): 多链多币种支付通道（PaymentRail）
 
 pub mod types;
 pub mod traits;
@@ -30,6 +32,7 @@ pub mod mask;
 pub mod validation;
 pub mod time;
 pub mod macros;
+pub mod rail;
 
 // ===== 🆕 v0.4.0: 重新导出公共类型 =====
 pub use types::{
@@ -53,7 +56,19 @@ pub use traits::{
 
 // 重新导出工具函数
 pub use mask::{mask_name, mask_id_card, mask_birthday};
-pub use validation::is_valid_tron_address;
+pub use validation::{is_valid_tron_address, is_valid_evm_address};
+
+// ===== 🆕 v0.6.0: 支付通道 =====
+pub use rail::{
+    RailId,
+    DEFAULT_RAIL_ID,
+    RAIL_AMOUNT_DECIMALS,
+    TokenContract,
+    PayoutAddress,
+    PaymentChain,
+    PaymentRail,
+    PaymentRailProvider,
+};
 pub use time::{
     blocks_to_seconds,
     seconds_to_blocks,
@@ -0,0 +1,157 @@
+//! # 支付通道（Payment Rail）
+//!
+//! 描述一种"链 + 稳定币合约"的收款方式，供 P2P Sell 订单与 Entity Market
+//! USDT 订单共享。通道注册表存储在 `pallet-trading-p2p` 中，
+//! 其他模块通过 [`PaymentRailProvider`] 查询。
+//!
+//! ## 约定
+//! - 链上金额统一为 6 位精度（与 USDT-TRC20 一致），
+//!   验证时按 `decimals` 换算链上原始金额
+//! - `rail_id = 0` 为内置 USDT-TRC20 通道，未注册时也可用
+//! - 通道一经注册不可修改合约，只能启用/停用，保证进行中的订单按原通道验证
+//!
+//! ## 版本历史
+//! - v0.1.0 (2026-10-17): 初始版本
+
+use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
+use frame_support::{pallet_prelude::ConstU32, BoundedVec};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+
+use crate::validation::{is_valid_evm_address, is_valid_tron_address};
+
+/// 支付通道 ID
+pub type RailId = u32;
+
+/// 内置 USDT-TRC20 通道 ID
+pub const DEFAULT_RAIL_ID: RailId = 0;
+
+/// 链上金额精度（10^6）
+pub const RAIL_AMOUNT_DECIMALS: u8 = 6;
+
+/// 代币合约地址（TRON Base58 或 EVM `0x` 十六进制，最大 64 字节）
+pub type TokenContract = BoundedVec<u8, ConstU32<64>>;
+
+/// 收款地址（TRON Base58 或 EVM `0x` 十六进制，最大 64 字节）
+pub type PayoutAddress = BoundedVec<u8, ConstU32<64>>;
+
+/// 官方 USDT TRC20 合约地址 (Mainnet)
+pub const USDT_TRC20_CONTRACT: &[u8] = b"TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t";
+
+/// TRON 默认确认数（与 trc20-verifier `MIN_CONFIRMATIONS` 一致）
+pub const TRON_DEFAULT_CONFIRMATIONS: u32 = 19;
+
+/// 支付链
+#[derive(Encode, Decode, DecodeWithMemTracking, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum PaymentChain {
+    /// TRON 主网（TRC20）
+    Tron,
+    /// Ethereum 主网（ERC20）
+    Ethereum,
+    /// BNB Smart Chain（BEP20）
+    Bsc,
+}
+
+impl PaymentChain {
+    /// EVM 链 ID（TRON 返回 None）
+    pub fn evm_chain_id(&self) -> Option<u64> {
+        match self {
+            PaymentChain::Tron => None,
+            PaymentChain::Ethereum => Some(1),
+            PaymentChain::Bsc => Some(56),
+        }
+    }
+
+    /// 校验该链上的地址格式
+    pub fn is_valid_address(&self, address: &[u8]) -> bool {
+        match self {
+            PaymentChain::Tron => is_valid_tron_address(address),
+            PaymentChain::Ethereum | PaymentChain::Bsc => is_valid_evm_address(address),
+        }
+    }
+}
+
+/// 支付通道定义
+#[derive(Encode, Decode, DecodeWithMemTracking, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct PaymentRail {
+    /// 所在链
+    pub chain: PaymentChain,
+    /// 代币合约地址
+    pub token_contract: TokenContract,
+    /// 代币精度（USDT-TRC20 为 6，USDT-BEP20 为 18）
+    pub decimals: u8,
+    /// 最小确认数
+    pub min_confirmations: u32,
+    /// 是否接受新订单（停用后进行中的订单仍按此通道验证）
+    pub enabled: bool,
+}
+
+impl PaymentRail {
+    /// 内置 USDT-TRC20 通道
+    pub fn tron_usdt() -> Self {
+        Self {
+            chain: PaymentChain::Tron,
+            token_contract: BoundedVec::truncate_from(USDT_TRC20_CONTRACT.to_vec()),
+            decimals: 6,
+            min_confirmations: TRON_DEFAULT_CONFIRMATIONS,
+            enabled: true,
+        }
+    }
+
+    /// 通道参数是否合法
+    ///
+    /// 精度低于 6 位无法表示链上金额，高于 36 位超出 u128 换算范围。
+    pub fn is_valid(&self) -> bool {
+        self.chain.is_valid_address(&self.token_contract)
+            && self.decimals >= RAIL_AMOUNT_DECIMALS
+            && self.decimals <= 36
+            && self.min_confirmations > 0
+    }
+}
+
+/// 函数级详细中文注释：支付通道查询接口
+///
+/// ## 使用者
+/// - `pallet-entity-market`: USDT 订单选择收款通道、OCW 验证
+///
+/// ## 实现者
+/// - `pallet-trading-p2p`: 通道注册表
+pub trait PaymentRailProvider {
+    /// 查询支付通道（未注册返回 None；`DEFAULT_RAIL_ID` 始终可用）
+    fn payment_rail(rail_id: RailId) -> Option<PaymentRail>;
+}
+
+/// 仅提供内置 USDT-TRC20 通道（测试 / 未接入注册表的运行时）
+impl PaymentRailProvider for () {
+    fn payment_rail(rail_id: RailId) -> Option<PaymentRail> {
+        (rail_id == DEFAULT_RAIL_ID).then(PaymentRail::tron_usdt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builtin_rail_is_valid() {
+        let rail = PaymentRail::tron_usdt();
+        assert!(rail.is_valid());
+        assert_eq!(<() as PaymentRailProvider>::payment_rail(DEFAULT_RAIL_ID), Some(rail));
+        assert_eq!(<() as PaymentRailProvider>::payment_rail(1), None);
+    }
+
+    #[test]
+    fn rail_validation_checks_chain_address_and_decimals() {
+        let bsc = PaymentRail {
+            chain: PaymentChain::Bsc,
+            token_contract: BoundedVec::truncate_from(b"0x55d398326f99059fF775485246999027B3197955".to_vec()),
+            decimals: 18,
+            min_confirmations: 15,
+            enabled: true,
+        };
+        assert!(bsc.is_valid());
+        assert!(!PaymentRail { decimals: 2, ..bsc.clone() }.is_valid());
+        assert!(!PaymentRail { chain: PaymentChain::Tron, ..bsc.clone() }.is_valid());
+        assert!(!PaymentRail { min_confirmations: 0, ..bsc }.is_valid());
+    }
+}
@@ -1,6 +1,6 @@
 //! # 验证函数模块
 //!
-//! 提供 TRON 地址验证
+//! 提供 TRON / EVM 地址验证
 
 
 /// 函数级详细中文注释：验证 TRON 地址格式
@@ -38,6 +38,22 @@ pub fn is_valid_tron_address(address: &[u8]) -> bool {
     true
 }
 
+/// 函数级详细中文注释：验证 EVM 地址格式
+///
+/// # 规则
+/// - `0x` 前缀 + 40 个十六进制字符（不校验 EIP-55 大小写校验和）
+///
+/// # 参数
+/// - address: EVM 地址字节数组（ASCII）
+///
+/// # 返回
+/// - bool: 有效返回 true，无效返回 false
+pub fn is_valid_evm_address(address: &[u8]) -> bool {
+    address.len() == 42
+        && address.starts_with(b"0x")
+        && address[2..].iter().all(|b| b.is_ascii_hexdigit())
+}
+
 // ===== 单元测试 =====
 
 #[cfg(test)]
@@ -51,4 +67,12 @@ mod tests {
         assert!(!is_valid_tron_address(b"AYASr5UV6HEcXatwdFQfmLVUqQQQMUxHLS")); // 不是T开头
         assert!(!is_valid_tron_address(b"TYASr5UV6HEcXatwdFQfmLVUqQQQMUxHL0")); // 包含0（非Base58）
     }
+
+    #[test]
+    fn test_is_valid_evm_address() {
+        assert!(is_valid_evm_address(b"0xdAC17F958D2ee523a2206206994597C13D831ec7"));
+        assert!(!is_valid_evm_address(b"dAC17F958D2ee523a2206206994597C13D831ec7")); // 缺少 0x
+        assert!(!is_valid_evm_address(b"0xdAC17F958D2ee523a2206206994597C13D831ec")); // 长度不对
+        assert!(!is_valid_evm_address(b"0xgAC17F958D2ee523a2206206994597C13D831ec7")); // 非十六进制
+    }
 }
@@ -114,6 +114,9 @@ pub mod pallet {
         pub appeal_result: Option<bool>,
     }
 
+    /// 惩罚记录归档承诺的数据类型标识（叶子为 `(penalty_id, PenaltyRecord).encode()`）
+    pub const PENALTY_ARCHIVE_DATA_TYPE: &[u8] = b"maker_penalty";
+
     /// 🆕 归档惩罚记录（L2精简版，~24字节）
     /// 用于长期存储历史惩罚记录，减少链上存储占用
     #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen, Default)]
@@ -357,6 +360,9 @@ pub mod pallet {
         /// 
         /// 当做市商押金扣除但无指定受益人时，扣除的金额将转入国库账户
         type TreasuryAccount: Get<Self::AccountId>;
+
+        /// 归档承诺接口：惩罚记录转为 L2 摘要前，完整记录整批上传 IPFS 并在链上保留 Merkle 根
+        type ArchiveCommitter: pallet_storage_lifecycle::ArchiveCommitter;
     }
     
     // ===== 存储 =====
@@ -1760,6 +1766,7 @@ pub mod pallet {
 
             let mut cursor = PenaltyArchiveCursor::<T>::get();
             let mut archived_count = 0u32;
+            let mut committed = sp_std::vec::Vec::new();
             let threshold_block = now.saturating_sub(age_threshold.into());
 
             // 从游标位置开始检查
@@ -1776,6 +1783,7 @@ pub mod pallet {
                     if record.deducted_at < threshold_block {
                         // 创建归档版本
                         let archived = ArchivedPenaltyL2::from_full::<T>(cursor, &record);
+                        committed.push((cursor, record.encode()));
                         
                         // 计算年月（简化：使用区块号除以每月区块数）
                         let block_num: u32 = record.deducted_at.saturated_into();
@@ -1811,11 +1819,25 @@ pub mod pallet {
             // 更新游标
             PenaltyArchiveCursor::<T>::put(cursor);
 
-            // 返回消耗的权重
+            // 完整记录整批提交归档承诺（失败不影响 L2 归档）
+            let mut commit_weight = Weight::zero();
+            if !committed.is_empty() {
+                let bytes: usize = committed.iter().map(|(_, record)| record.len()).sum();
+                commit_weight = <T::ArchiveCommitter as pallet_storage_lifecycle::ArchiveCommitter>::commit_weight(
+                    committed.len() as u32,
+                    bytes.saturated_into(),
+                );
+                let _ = <T::ArchiveCommitter as pallet_storage_lifecycle::ArchiveCommitter>::commit_archive(
+                    PENALTY_ARCHIVE_DATA_TYPE,
+                    committed,
+                );
+            }
+
+            // 返回消耗的权重（含归档承诺）
             Weight::from_parts(
                 (archived_count as u64) * 80_000 + 10_000,
                 0
-            )
+            ).saturating_add(commit_weight)
         }
     }
 
@@ -14,11 +14,18 @@
 //! ## Sell 流程（原 Swap）
 //! 1. 用户 create_sell_order → 用户 NXS 锁定到托管
 //! 2. 做市商链下转 USDT → mark_sell_complete（提交 TRC20 tx hash）
-//! 3. OCW 验证 TRC20 → confirm_verification → NXS 释放给做市商
+//! 3. OCW 验证 TRC20 → 签名载荷无签名交易提交结果 → claim_sell_verification_reward → NXS 释放给做市商
+//!
+//! ## 支付通道
+//! Sell 订单可选择收款通道（`create_sell_order_on_rail`），通道注册表 `PaymentRails`
+//! 由委员会维护（USDC-TRC20、USDT-ERC20/BEP20 等），`rail_id = 0` 为内置 USDT-TRC20。
+//! 本模块同时实现 `PaymentRailProvider`，供 `pallet-entity-market` 共享通道定义。
 //!
 //! ## 版本历史
 //! - v0.1.0 (2026-02-08): 初始骨架
 //! - v1.0.0 (2026-02-08): Phase 1 完整实现
+//! - v1.1.0 (2026-10-17): Sell 侧 OCW 自动验证（签名载荷提交、去重、退避重试）
+//! - v1.2.0 (2026-10-17): 支付通道注册表（多代币 / 多链 Sell 订单）
 
 extern crate alloc;
 
@@ -26,6 +33,7 @@ pub use pallet::*;
 
 pub mod ocw;
 pub mod kyc;
+pub mod migrations;
 pub mod types;
 
 #[cfg(test)]
@@ -44,15 +52,20 @@ pub mod pallet {
     use super::*;
     use frame_support::pallet_prelude::*;
     use frame_system::pallet_prelude::*;
+    use frame_system::offchain::{AppCrypto, CreateBare, SignedPayload, SigningTypes};
     use frame_support::{
         traits::{Currency, Get, UnixTime},
         BoundedVec,
         PalletId,
-        sp_runtime::{SaturatedConversion, traits::{Saturating, AccountIdConversion}},
+        sp_runtime::{SaturatedConversion, traits::{Saturating, AccountIdConversion, IdentifyAccount}},
     };
     use sp_core::H256;
     use pallet_trading_common::{
         TronAddress,
+        PayoutAddress,
+        PaymentRail,
+        RailId,
+        DEFAULT_RAIL_ID,
         MomentOf,
         Cid,
         PricingProvider,
@@ -66,6 +79,15 @@ pub mod pallet {
     /// Pallet ID（用于生成内部账户）
     const PALLET_ID: PalletId = PalletId(*b"p2p/trad");
 
+    /// Buy 订单归档承诺的数据类型标识（叶子为 `(order_id, BuyOrder).encode()`）
+    pub const BUY_ORDER_ARCHIVE_DATA_TYPE: &[u8] = b"p2p_buy_order";
+
+    /// Sell 订单归档承诺的数据类型标识（叶子为 `(sell_id, SellOrder).encode()`）
+    pub const SELL_ORDER_ARCHIVE_DATA_TYPE: &[u8] = b"p2p_sell_order";
+
+    /// OCW Sell 验证结果载荷有效期（区块数）：`block_number` 早于当前区块超过该值的签名结果视为过期
+    pub const SELL_PAYLOAD_MAX_AGE: u32 = 5;
+
     /// Balance 类型别名
     pub type BalanceOf<T> = <<T as Config>::Currency as Currency<
         <T as frame_system::Config>::AccountId,
@@ -76,7 +98,11 @@ pub mod pallet {
     // ========================================================================
 
     #[pallet::config]
-    pub trait Config: frame_system::Config<RuntimeEvent: From<Event<Self>>> {
+    pub trait Config:
+        frame_system::Config<RuntimeEvent: From<Event<Self>>>
+        + SigningTypes
+        + CreateBare<Call<Self>>
+    {
 
         /// 货币操作
         type Currency: Currency<Self::AccountId>;
@@ -115,6 +141,9 @@ pub mod pallet {
         /// CID 锁定管理器（争议期间锁定证据）
         type CidLockManager: pallet_storage_service::CidLockManager<Self::Hash, BlockNumberFor<Self>>;
 
+        /// 归档承诺接口（完整订单移出活跃存储前整批上传 IPFS，链上保留 Merkle 根）
+        type ArchiveCommitter: pallet_storage_lifecycle::ArchiveCommitter;
+
         /// 权重信息
         type WeightInfo: WeightInfo;
 
@@ -213,6 +242,11 @@ pub mod pallet {
         /// 最低 Sell 手续费
         #[pallet::constant]
         type MinSellFee: Get<BalanceOf<Self>>;
+
+        // ===== OCW =====
+
+        /// OCW 签名密钥类型（Sell 验证结果签名载荷）
+        type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
     }
 
     /// Identity 验证 Provider trait（从 OTC 迁移）
@@ -232,7 +266,11 @@ pub mod pallet {
         fn has_problematic_judgement(_who: &AccountId) -> bool { false }
     }
 
+    /// v1: Sell 订单与验证请求新增 `rail_id`（见 `migrations::v1`）
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
     #[pallet::pallet]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
     // ========================================================================
@@ -265,6 +303,11 @@ pub mod pallet {
             let w3 = Self::cleanup_expired_buy_tx_hashes(now, 10);
             w1.saturating_add(w2).saturating_add(w3)
         }
+
+        /// OCW：扫描 AwaitingVerification 的 Sell 订单并提交 TRC20 验证结果
+        fn offchain_worker(block_number: BlockNumberFor<T>) {
+            Self::offchain_verify_sell_orders(block_number);
+        }
     }
 
     // ========================================================================
@@ -431,6 +474,20 @@ pub mod pallet {
         (bool, Option<BoundedVec<u8, ConstU32<128>>>), OptionQuery,
     >;
 
+    /// 允许提交 Sell 验证结果的 OCW 签名账户
+    #[pallet::storage]
+    #[pallet::getter(fn sell_ocw_authorities)]
+    pub type SellOcwAuthorities<T: Config> = StorageValue<
+        _, BoundedVec<T::AccountId, ConstU32<32>>, ValueQuery,
+    >;
+
+    /// 支付通道注册表（`DEFAULT_RAIL_ID` 未注册时使用内置 USDT-TRC20）
+    #[pallet::storage]
+    #[pallet::getter(fn payment_rails)]
+    pub type PaymentRails<T: Config> = StorageMap<
+        _, Blake2_128Concat, RailId, PaymentRail, OptionQuery,
+    >;
+
     /// Sell 少付证据
     #[pallet::storage]
     #[pallet::getter(fn sell_underpaid_evidences)]
@@ -570,8 +627,10 @@ pub mod pallet {
         pub nxs_amount: BalanceOf<T>,
         /// USDT 金额（精度 10^6）
         pub usdt_amount: u64,
-        /// USDT 接收地址
-        pub usdt_address: TronAddress,
+        /// USDT 接收地址（格式由支付通道所在链决定）
+        pub usdt_address: PayoutAddress,
+        /// 支付通道
+        pub rail_id: RailId,
         /// 创建区块
         pub created_at: BlockNumberFor<T>,
         /// 超时区块
@@ -596,13 +655,32 @@ pub mod pallet {
     pub struct SellVerificationRequest<T: Config> {
         pub sell_id: u64,
         pub tx_hash: BoundedVec<u8, ConstU32<128>>,
-        pub expected_to: TronAddress,
+        pub expected_to: PayoutAddress,
         pub expected_amount: u64,
+        pub rail_id: RailId,
         pub submitted_at: BlockNumberFor<T>,
         pub verification_timeout_at: BlockNumberFor<T>,
         pub retry_count: u8,
     }
 
+    /// OCW Sell 验证结果签名载荷
+    #[derive(Encode, Decode, codec::DecodeWithMemTracking, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+    pub struct SellVerificationPayload<Public, BlockNumber> {
+        pub sell_id: u64,
+        pub verified: bool,
+        pub reason: Option<sp_std::vec::Vec<u8>>,
+        /// 生成载荷时的区块号
+        pub block_number: BlockNumber,
+        /// OCW 签名公钥
+        pub public: Public,
+    }
+
+    impl<T: SigningTypes> SignedPayload<T> for SellVerificationPayload<T::Public, BlockNumberFor<T>> {
+        fn public(&self) -> T::Public {
+            self.public.clone()
+        }
+    }
+
     /// Sell 少付证据
     #[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
     #[scale_info(skip_type_params(T))]
@@ -721,6 +799,18 @@ pub mod pallet {
             sell_id: u64,
             reason: BoundedVec<u8, ConstU32<128>>,
         },
+        /// OCW 验证结果已上链（等待 claim_sell_verification_reward 确认）
+        SellOcwResultSubmitted {
+            sell_id: u64,
+            verified: bool,
+            submitter: T::AccountId,
+        },
+        /// OCW 签名账户列表已更新
+        SellOcwAuthoritiesUpdated { count: u32 },
+        /// 支付通道已注册
+        PaymentRailRegistered { rail_id: RailId, rail: PaymentRail },
+        /// 支付通道启用状态变更
+        PaymentRailStatusChanged { rail_id: RailId, enabled: bool },
         /// Sell 超时退款
         SellTimeout {
             sell_id: u64,
@@ -869,6 +959,20 @@ pub mod pallet {
         InsufficientEscrowForDeposit,
         /// 少付证据不存在
         EvidenceNotFound,
+        /// OCW 验证结果已提交
+        OcwResultAlreadySubmitted,
+        /// OCW 签名账户过多
+        TooManyOcwAuthorities,
+        /// 支付通道不存在
+        PaymentRailNotFound,
+        /// 支付通道已停用
+        PaymentRailDisabled,
+        /// 支付通道已存在（含内置通道 ID）
+        PaymentRailExists,
+        /// 支付通道参数无效
+        InvalidPaymentRail,
+        /// 收款地址与支付通道所在链不匹配
+        InvalidPayoutAddress,
 
         // ===== KYC 错误 =====
 
@@ -884,6 +988,8 @@ pub mod pallet {
         AccountAlreadyExempted,
         /// 账户不在豁免列表中
         AccountNotExempted,
+        /// OCW 验证结果载荷已过期或早于当前验证请求
+        StaleVerificationPayload,
     }
 
     // ========================================================================
@@ -985,7 +1091,7 @@ pub mod pallet {
         ) -> DispatchResult {
             let user = ensure_signed(origin)?;
             let _sell_id = Self::do_create_sell_order(
-                &user, maker_id, nxs_amount, usdt_address,
+                &user, maker_id, nxs_amount, usdt_address, DEFAULT_RAIL_ID,
             )?;
             Ok(())
         }
@@ -1037,19 +1143,45 @@ pub mod pallet {
             Self::do_handle_sell_verification_timeout(sell_id)
         }
 
-        /// OCW 提交 Sell 验证结果（无签名交易）
+        /// OCW 提交 Sell 验证结果（签名载荷 + 无签名交易）
+        ///
+        /// 签名与签名账户白名单在 `ValidateUnsigned` 中校验；
+        /// 载荷须在当前验证请求提交之后、`SELL_PAYLOAD_MAX_AGE` 个区块内生成，旧结果不可重放。
         #[pallet::call_index(15)]
         #[pallet::weight(T::WeightInfo::mark_sell_complete())]
         pub fn ocw_submit_sell_verification(
             origin: OriginFor<T>,
-            sell_id: u64,
-            verified: bool,
-            reason: Option<sp_std::vec::Vec<u8>>,
+            payload: SellVerificationPayload<T::Public, BlockNumberFor<T>>,
+            _signature: T::Signature,
         ) -> DispatchResult {
             ensure_none(origin)?;
-            let reason_bounded: Option<BoundedVec<u8, ConstU32<128>>> = reason
+            let sell_id = payload.sell_id;
+            let record = SellOrders::<T>::get(sell_id)
+                .ok_or(Error::<T>::SellOrderNotFound)?;
+            ensure!(
+                record.status == SellOrderStatus::AwaitingVerification,
+                Error::<T>::InvalidSellStatus
+            );
+            ensure!(
+                !SellOcwVerificationResults::<T>::contains_key(sell_id),
+                Error::<T>::OcwResultAlreadySubmitted
+            );
+            let request = SellPendingVerifications::<T>::get(sell_id)
+                .ok_or(Error::<T>::VerificationNotFound)?;
+            ensure!(
+                Self::sell_payload_is_fresh(&request, payload.block_number),
+                Error::<T>::StaleVerificationPayload
+            );
+
+            let reason_bounded: Option<BoundedVec<u8, ConstU32<128>>> = payload.reason
                 .map(|r| r.try_into().unwrap_or_default());
-            SellOcwVerificationResults::<T>::insert(sell_id, (verified, reason_bounded));
+            SellOcwVerificationResults::<T>::insert(sell_id, (payload.verified, reason_bounded));
+
+            Self::deposit_event(Event::SellOcwResultSubmitted {
+                sell_id,
+                verified: payload.verified,
+                submitter: payload.public.into_account(),
+            });
             Ok(())
         }
 
@@ -1110,6 +1242,78 @@ pub mod pallet {
             Self::do_maker_confirm_usdt_refund(&maker, sell_id, refund_tx_hash)
         }
 
+        /// 设置允许提交 Sell 验证结果的 OCW 签名账户（CommitteeOrigin）
+        #[pallet::call_index(21)]
+        #[pallet::weight(T::WeightInfo::set_sell_ocw_authorities())]
+        pub fn set_sell_ocw_authorities(
+            origin: OriginFor<T>,
+            authorities: sp_std::vec::Vec<T::AccountId>,
+        ) -> DispatchResult {
+            T::CommitteeOrigin::ensure_origin(origin)?;
+            let bounded: BoundedVec<T::AccountId, ConstU32<32>> = authorities
+                .try_into().map_err(|_| Error::<T>::TooManyOcwAuthorities)?;
+            let count = bounded.len() as u32;
+            SellOcwAuthorities::<T>::put(bounded);
+            Self::deposit_event(Event::SellOcwAuthoritiesUpdated { count });
+            Ok(())
+        }
+
+        /// 注册支付通道（CommitteeOrigin）
+        ///
+        /// 通道注册后合约与精度不可修改，只能启用/停用。
+        #[pallet::call_index(22)]
+        #[pallet::weight(T::WeightInfo::register_payment_rail())]
+        pub fn register_payment_rail(
+            origin: OriginFor<T>,
+            rail_id: RailId,
+            rail: PaymentRail,
+        ) -> DispatchResult {
+            T::CommitteeOrigin::ensure_origin(origin)?;
+            ensure!(
+                rail_id != DEFAULT_RAIL_ID && !PaymentRails::<T>::contains_key(rail_id),
+                Error::<T>::PaymentRailExists
+            );
+            ensure!(rail.is_valid(), Error::<T>::InvalidPaymentRail);
+            PaymentRails::<T>::insert(rail_id, rail.clone());
+            Self::deposit_event(Event::PaymentRailRegistered { rail_id, rail });
+            Ok(())
+        }
+
+        /// 启用/停用支付通道（CommitteeOrigin）
+        ///
+        /// 停用只影响新订单，进行中的订单仍按原通道验证。
+        #[pallet::call_index(23)]
+        #[pallet::weight(T::WeightInfo::set_payment_rail_enabled())]
+        pub fn set_payment_rail_enabled(
+            origin: OriginFor<T>,
+            rail_id: RailId,
+            enabled: bool,
+        ) -> DispatchResult {
+            T::CommitteeOrigin::ensure_origin(origin)?;
+            let mut rail = Self::payment_rail_of(rail_id).ok_or(Error::<T>::PaymentRailNotFound)?;
+            rail.enabled = enabled;
+            PaymentRails::<T>::insert(rail_id, rail);
+            Self::deposit_event(Event::PaymentRailStatusChanged { rail_id, enabled });
+            Ok(())
+        }
+
+        /// 创建指定支付通道的 Sell 订单
+        #[pallet::call_index(24)]
+        #[pallet::weight(T::WeightInfo::create_sell_order())]
+        pub fn create_sell_order_on_rail(
+            origin: OriginFor<T>,
+            maker_id: u64,
+            nxs_amount: BalanceOf<T>,
+            usdt_address: sp_std::vec::Vec<u8>,
+            rail_id: RailId,
+        ) -> DispatchResult {
+            let user = ensure_signed(origin)?;
+            let _sell_id = Self::do_create_sell_order(
+                &user, maker_id, nxs_amount, usdt_address, rail_id,
+            )?;
+            Ok(())
+        }
+
         // ===== KYC 管理 Extrinsics =====
 
         /// 启用 KYC 要求
@@ -1210,7 +1414,8 @@ pub mod pallet {
 
         fn validate_unsigned(source: TransactionSource, call: &Self::Call) -> TransactionValidity {
             match call {
-                Call::ocw_submit_sell_verification { sell_id, verified: _, reason } => {
+                Call::ocw_submit_sell_verification { payload, signature } => {
+                    let sell_id = &payload.sell_id;
                     match source {
                         TransactionSource::Local | TransactionSource::InBlock => {},
                         TransactionSource::External => {
@@ -1225,14 +1430,32 @@ pub mod pallet {
                     if record.status != SellOrderStatus::AwaitingVerification {
                         return InvalidTransaction::Custom(2).into();
                     }
-                    if !SellPendingVerifications::<T>::contains_key(sell_id) {
-                        return InvalidTransaction::Custom(3).into();
+                    let request = match SellPendingVerifications::<T>::get(sell_id) {
+                        Some(r) => r,
+                        None => return InvalidTransaction::Custom(3).into(),
+                    };
+                    // 载荷须属于当前验证请求且未过期，防止旧签名结果重放
+                    if !Self::sell_payload_is_fresh(&request, payload.block_number) {
+                        return InvalidTransaction::Stale.into();
                     }
-                    if let Some(ref r) = reason {
+                    if let Some(ref r) = payload.reason {
                         if r.len() > 256 {
                             return InvalidTransaction::Custom(4).into();
                         }
                     }
+                    // 签名校验：载荷必须由其声明的公钥签名
+                    if !SignedPayload::<T>::verify::<T::AuthorityId>(payload, signature.clone()) {
+                        return InvalidTransaction::BadProof.into();
+                    }
+                    // 签名账户必须在白名单内
+                    let signer = payload.public.clone().into_account();
+                    if !SellOcwAuthorities::<T>::get().contains(&signer) {
+                        return InvalidTransaction::Custom(5).into();
+                    }
+                    // 去重：同一订单只接受一次结果
+                    if SellOcwVerificationResults::<T>::contains_key(sell_id) {
+                        return InvalidTransaction::Custom(6).into();
+                    }
 
                     let priority = match source {
                         TransactionSource::Local => 100,
@@ -1242,7 +1465,7 @@ pub mod pallet {
 
                     ValidTransaction::with_tag_prefix("P2pTRC20Verify")
                         .priority(priority)
-                        .longevity(10)
+                        .longevity(SELL_PAYLOAD_MAX_AGE as u64)
                         .and_provides([&(b"p2p_verify", sell_id)])
                         .propagate(true)
                         .build()
@@ -1262,6 +1485,41 @@ pub mod pallet {
             PALLET_ID.into_account_truncating()
         }
 
+        /// OCW 验证结果载荷是否有效：生成区块不早于验证请求提交区块、不晚于当前区块，
+        /// 且距当前区块不超过 `SELL_PAYLOAD_MAX_AGE`
+        fn sell_payload_is_fresh(
+            request: &SellVerificationRequest<T>,
+            block_number: BlockNumberFor<T>,
+        ) -> bool {
+            let now = frame_system::Pallet::<T>::block_number();
+            block_number >= request.submitted_at
+                && block_number <= now
+                && now.saturating_sub(block_number) <= SELL_PAYLOAD_MAX_AGE.into()
+        }
+
+        /// 查询支付通道（`DEFAULT_RAIL_ID` 未注册时返回内置 USDT-TRC20）
+        pub fn payment_rail_of(rail_id: RailId) -> Option<PaymentRail> {
+            PaymentRails::<T>::get(rail_id).or_else(|| {
+                (rail_id == DEFAULT_RAIL_ID).then(PaymentRail::tron_usdt)
+            })
+        }
+
+        /// 按支付通道校验收款地址
+        ///
+        /// TRON 通道沿用长度校验（兼容既有订单），EVM 通道要求 `0x` + 40 位十六进制。
+        pub fn validate_payout_address(
+            rail: &PaymentRail,
+            address: sp_std::vec::Vec<u8>,
+        ) -> Result<PayoutAddress, DispatchError> {
+            if rail.chain.evm_chain_id().is_some() {
+                ensure!(rail.chain.is_valid_address(&address), Error::<T>::InvalidPayoutAddress);
+            } else {
+                let _: TronAddress = address.clone()
+                    .try_into().map_err(|_| Error::<T>::InvalidTronAddress)?;
+            }
+            address.try_into().map_err(|_| Error::<T>::InvalidPayoutAddress.into())
+        }
+
         /// KYC 验证
         pub fn enforce_kyc_requirement(who: &T::AccountId) -> DispatchResult {
             let config = KycConfigStore::<T>::get();
@@ -1868,6 +2126,7 @@ pub mod pallet {
             maker_id: u64,
             nxs_amount: BalanceOf<T>,
             usdt_address: sp_std::vec::Vec<u8>,
+            rail_id: RailId,
         ) -> Result<u64, DispatchError> {
             // 验证最小金额
             ensure!(nxs_amount >= T::MinSellAmount::get(), Error::<T>::SellAmountTooLow);
@@ -1879,9 +2138,10 @@ pub mod pallet {
                     MakerValidationError::NotActive => Error::<T>::MakerNotActive,
                 })?;
 
-            // 验证 USDT 地址
-            let usdt_addr: TronAddress = usdt_address
-                .try_into().map_err(|_| Error::<T>::InvalidTronAddress)?;
+            // 验证支付通道与收款地址
+            let rail = Self::payment_rail_of(rail_id).ok_or(Error::<T>::PaymentRailNotFound)?;
+            ensure!(rail.enabled, Error::<T>::PaymentRailDisabled);
+            let usdt_addr = Self::validate_payout_address(&rail, usdt_address)?;
 
             // 获取价格并计算 USDT 金额
             let price_balance = T::Pricing::get_cos_to_usd_rate()
@@ -1913,6 +2173,7 @@ pub mod pallet {
                 nxs_amount,
                 usdt_amount,
                 usdt_address: usdt_addr,
+                rail_id,
                 created_at: current_block,
                 timeout_at,
                 trc20_tx_hash: None,
@@ -1973,6 +2234,7 @@ pub mod pallet {
                 tx_hash: tx_hash.clone(),
                 expected_to: record.usdt_address.clone(),
                 expected_amount: record.usdt_amount,
+                rail_id: record.rail_id,
                 submitted_at: current_block,
                 verification_timeout_at,
                 retry_count: 0,
@@ -2507,6 +2769,7 @@ pub mod pallet {
         /// Buy 归档（L1）
         pub fn archive_completed_buy_orders(max: u32) -> Weight {
             let mut archived = 0u32;
+            let mut committed = sp_std::vec::Vec::new();
             let mut cursor = BuyArchiveCursor::<T>::get();
             let next_id = NextBuyOrderId::<T>::get();
 
@@ -2519,6 +2782,7 @@ pub mod pallet {
                             | BuyOrderState::Closed | BuyOrderState::Expired
                         );
                         if is_terminal {
+                            committed.push((cursor, order.encode()));
                             let archived_order = ArchivedBuyOrder {
                                 maker_id: order.maker_id,
                                 taker: order.taker,
@@ -2543,13 +2807,15 @@ pub mod pallet {
                 }
             }
             BuyArchiveCursor::<T>::put(cursor);
+            let commit_weight = Self::commit_archived_orders(BUY_ORDER_ARCHIVE_DATA_TYPE, committed);
 
-            Weight::from_parts((archived as u64) * 50_000, 0)
+            Weight::from_parts((archived as u64) * 50_000, 0).saturating_add(commit_weight)
         }
 
         /// Sell 归档（L1）
         pub fn archive_completed_sell_orders(max: u32) -> Weight {
             let mut archived = 0u32;
+            let mut committed = sp_std::vec::Vec::new();
             let mut cursor = SellArchiveCursor::<T>::get();
             let next_id = NextSellOrderId::<T>::get();
 
@@ -2562,6 +2828,7 @@ pub mod pallet {
                             | SellOrderStatus::ArbitrationApproved | SellOrderStatus::ArbitrationRejected
                         );
                         if is_terminal {
+                            committed.push((cursor, record.encode()));
                             let archived_sell = ArchivedSellOrder {
                                 sell_id: cursor,
                                 maker_id: record.maker_id,
@@ -2588,8 +2855,28 @@ pub mod pallet {
                 }
             }
             SellArchiveCursor::<T>::put(cursor);
+            let commit_weight = Self::commit_archived_orders(SELL_ORDER_ARCHIVE_DATA_TYPE, committed);
+
+            Weight::from_parts((archived as u64) * 50_000, 0).saturating_add(commit_weight)
+        }
 
-            Weight::from_parts((archived as u64) * 50_000, 0)
+        /// 完整订单整批提交归档承诺（失败不影响 L1 归档），返回承诺权重
+        fn commit_archived_orders(data_type: &[u8], committed: sp_std::vec::Vec<(u64, sp_std::vec::Vec<u8>)>) -> Weight {
+            if committed.is_empty() {
+                return Weight::zero();
+            }
+            let bytes: usize = committed.iter().map(|(_, record)| record.len()).sum();
+            let weight = <T::ArchiveCommitter as pallet_storage_lifecycle::ArchiveCommitter>::commit_weight(
+                committed.len() as u32,
+                bytes.saturated_into(),
+            );
+            if let Err(e) = <T::ArchiveCommitter as pallet_storage_lifecycle::ArchiveCommitter>::commit_archive(
+                data_type,
+                committed,
+            ) {
+                log::warn!(target: "p2p", "Archive commitment failed: {:?}", e);
+            }
+            weight
         }
 
         /// Buy 侧 TxHash TTL 清理（复用 Sell 侧的 TxHashTtlBlocks）
@@ -2614,3 +2901,11 @@ pub mod pallet {
         }
     }
 }
+
+// ==================== 支付通道查询 ====================
+
+impl<T: Config> pallet_trading_common::PaymentRailProvider for Pallet<T> {
+    fn payment_rail(rail_id: pallet_trading_common::RailId) -> Option<pallet_trading_common::PaymentRail> {
+        Self::payment_rail_of(rail_id)
+    }
+}
@@ -0,0 +1,153 @@
+//! P2P Trading 存储迁移
+
+use super::*;
+use crate::types::SellOrderStatus;
+use frame_support::{
+    migrations::VersionedMigration,
+    pallet_prelude::*,
+    traits::UncheckedOnRuntimeUpgrade,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use pallet_trading_common::{PayoutAddress, DEFAULT_RAIL_ID};
+use sp_std::marker::PhantomData;
+
+/// v0 → v1：Sell 订单与验证请求新增 `rail_id`
+///
+/// v0 的 `SellOrder` / `SellVerificationRequest` 没有 `rail_id` 字段，收款地址为 `TronAddress`
+///（与 `PayoutAddress` 编码相同，上限更小）。v0 只支持内置 USDT-TRC20，
+/// 迁移为所有存量记录补上 `DEFAULT_RAIL_ID`。
+pub mod v1 {
+    use super::*;
+
+    pub(crate) mod v0 {
+        use super::*;
+
+        /// v0 Sell 订单（无 `rail_id`）
+        #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+        pub struct OldSellOrder<AccountId, Balance, BlockNumber> {
+            pub sell_id: u64,
+            pub maker_id: u64,
+            pub maker: AccountId,
+            pub user: AccountId,
+            pub nxs_amount: Balance,
+            pub usdt_amount: u64,
+            pub usdt_address: PayoutAddress,
+            pub created_at: BlockNumber,
+            pub timeout_at: BlockNumber,
+            pub trc20_tx_hash: Option<BoundedVec<u8, ConstU32<128>>>,
+            pub completed_at: Option<BlockNumber>,
+            pub evidence_cid: Option<BoundedVec<u8, ConstU32<256>>>,
+            pub status: SellOrderStatus,
+            pub price_usdt: u64,
+            pub dispute_deposit: Option<Balance>,
+        }
+
+        /// v0 Sell 验证请求（无 `rail_id`）
+        #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+        pub struct OldSellVerificationRequest<BlockNumber> {
+            pub sell_id: u64,
+            pub tx_hash: BoundedVec<u8, ConstU32<128>>,
+            pub expected_to: PayoutAddress,
+            pub expected_amount: u64,
+            pub submitted_at: BlockNumber,
+            pub verification_timeout_at: BlockNumber,
+            pub retry_count: u8,
+        }
+    }
+
+    /// 未做版本检查的迁移逻辑，通过 [`MigrateV0ToV1`] 使用
+    pub struct InnerMigrateV0ToV1<T>(PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for InnerMigrateV0ToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut orders = 0u64;
+            SellOrders::<T>::translate::<
+                v0::OldSellOrder<T::AccountId, BalanceOf<T>, BlockNumberFor<T>>,
+                _,
+            >(|_, old| {
+                orders += 1;
+                Some(SellOrder {
+                    sell_id: old.sell_id,
+                    maker_id: old.maker_id,
+                    maker: old.maker,
+                    user: old.user,
+                    nxs_amount: old.nxs_amount,
+                    usdt_amount: old.usdt_amount,
+                    usdt_address: old.usdt_address,
+                    rail_id: DEFAULT_RAIL_ID,
+                    created_at: old.created_at,
+                    timeout_at: old.timeout_at,
+                    trc20_tx_hash: old.trc20_tx_hash,
+                    completed_at: old.completed_at,
+                    evidence_cid: old.evidence_cid,
+                    status: old.status,
+                    price_usdt: old.price_usdt,
+                    dispute_deposit: old.dispute_deposit,
+                })
+            });
+
+            let mut requests = 0u64;
+            SellPendingVerifications::<T>::translate::<
+                v0::OldSellVerificationRequest<BlockNumberFor<T>>,
+                _,
+            >(|_, old| {
+                requests += 1;
+                Some(SellVerificationRequest {
+                    sell_id: old.sell_id,
+                    tx_hash: old.tx_hash,
+                    expected_to: old.expected_to,
+                    expected_amount: old.expected_amount,
+                    rail_id: DEFAULT_RAIL_ID,
+                    submitted_at: old.submitted_at,
+                    verification_timeout_at: old.verification_timeout_at,
+                    retry_count: old.retry_count,
+                })
+            });
+
+            log::info!(
+                target: "runtime::trading-p2p",
+                "migrated {} sell orders and {} pending verifications to v1 (rail_id = {})",
+                orders,
+                requests,
+                DEFAULT_RAIL_ID,
+            );
+            let total = orders.saturating_add(requests);
+            T::DbWeight::get().reads_writes(total, total)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<alloc::vec::Vec<u8>, sp_runtime::TryRuntimeError> {
+            let orders = SellOrders::<T>::iter_keys().count() as u32;
+            let requests = SellPendingVerifications::<T>::iter_keys().count() as u32;
+            Ok((orders, requests).encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: alloc::vec::Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+            let (orders, requests) = <(u32, u32)>::decode(&mut &state[..])
+                .map_err(|_| sp_runtime::TryRuntimeError::Other("invalid pre_upgrade state"))?;
+            let mut migrated_orders = 0u32;
+            for (_, order) in SellOrders::<T>::iter() {
+                ensure!(order.rail_id == DEFAULT_RAIL_ID, "sell order rail_id not migrated");
+                migrated_orders += 1;
+            }
+            let mut migrated_requests = 0u32;
+            for (_, request) in SellPendingVerifications::<T>::iter() {
+                ensure!(request.rail_id == DEFAULT_RAIL_ID, "verification rail_id not migrated");
+                migrated_requests += 1;
+            }
+            ensure!(orders == migrated_orders, "sell orders lost during migration");
+            ensure!(requests == migrated_requests, "pending verifications lost during migration");
+            Ok(())
+        }
+    }
+
+    /// 带版本检查的 v0 → v1 迁移（仅在链上版本为 0 时执行，完成后写入版本 1）
+    pub type MigrateV0ToV1<T> = VersionedMigration<
+        0,
+        1,
+        InnerMigrateV0ToV1<T>,
+        Pallet<T>,
+        <T as frame_system::Config>::DbWeight,
+    >;
+}
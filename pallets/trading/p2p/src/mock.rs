@@ -8,7 +8,12 @@ use frame_support::{
     traits::{ConstU16, ConstU32, ConstU64},
 };
 use sp_core::H256;
+use sp_core::offchain::{
+    testing::{TestOffchainExt, TestTransactionPoolExt},
+    OffchainDbExt, OffchainWorkerExt, TransactionPoolExt,
+};
 use sp_runtime::{
+    testing::{TestSignature, TestXt, UintAuthorityId},
     traits::{BlakeTwo256, IdentityLookup},
     BuildStorage,
 };
@@ -82,6 +87,40 @@ impl pallet_balances::Config for Test {
     type DoneSlashHandler = ();
 }
 
+// ==================== OCW 签名 ====================
+
+pub type Extrinsic = TestXt<RuntimeCall, ()>;
+
+impl frame_system::offchain::SigningTypes for Test {
+    type Public = UintAuthorityId;
+    type Signature = TestSignature;
+}
+
+impl<LocalCall> frame_system::offchain::CreateTransactionBase<LocalCall> for Test
+where
+    RuntimeCall: From<LocalCall>,
+{
+    type RuntimeCall = RuntimeCall;
+    type Extrinsic = Extrinsic;
+}
+
+impl<LocalCall> frame_system::offchain::CreateBare<LocalCall> for Test
+where
+    RuntimeCall: From<LocalCall>,
+{
+    fn create_bare(call: RuntimeCall) -> Extrinsic {
+        Extrinsic::new_bare(call)
+    }
+}
+
+/// 测试签名身份：UintAuthorityId(n) 对应账户 n
+pub struct TestAuthId;
+impl frame_system::offchain::AppCrypto<UintAuthorityId, TestSignature> for TestAuthId {
+    type RuntimeAppPublic = UintAuthorityId;
+    type GenericPublic = UintAuthorityId;
+    type GenericSignature = TestSignature;
+}
+
 // ==================== Stub Implementations ====================
 
 /// Mock Escrow: 所有操作成功
@@ -223,6 +262,7 @@ impl pallet_trading_p2p::Config for Test {
     type VerificationOrigin = frame_system::EnsureRoot<u64>;
     type ArbitratorOrigin = frame_system::EnsureRoot<u64>;
     type CidLockManager = MockCidLockManager;
+    type ArchiveCommitter = ();
     type WeightInfo = ();
 
     // Buy-side 常量
@@ -251,6 +291,8 @@ impl pallet_trading_p2p::Config for Test {
     type VerificationReward = VerificationReward;
     type SellFeeRateBps = SellFeeRateBps;
     type MinSellFee = MinSellFee;
+
+    type AuthorityId = TestAuthId;
 }
 
 // ==================== Helper ====================
@@ -262,6 +304,7 @@ pub const MAKER_ACCOUNT: u64 = 10;
 pub const MAKER_ID: u64 = 1;
 pub const ESCROW_ACCOUNT: u64 = 999;
 pub const PALLET_ACCOUNT: u64 = 3524478956; // PalletId(*b"p2p/trad").into_account_truncating()
+pub const OCW_AUTHORITY: u64 = 77;
 
 /// 创建测试环境，预设余额
 pub fn new_test_ext() -> sp_io::TestExternalities {
@@ -283,3 +326,18 @@ pub fn new_test_ext() -> sp_io::TestExternalities {
 
     t.into()
 }
+
+/// 创建带 OCW 扩展（HTTP / 本地存储 / 交易池）的测试环境
+///
+/// 本地 keystore 中仅有 `OCW_AUTHORITY` 一个签名密钥。
+pub fn new_test_ext_with_ocw(
+    offchain: TestOffchainExt,
+    pool: TestTransactionPoolExt,
+) -> sp_io::TestExternalities {
+    let mut t = new_test_ext();
+    t.register_extension(OffchainDbExt::new(offchain.clone()));
+    t.register_extension(OffchainWorkerExt::new(offchain));
+    t.register_extension(TransactionPoolExt::new(pool));
+    UintAuthorityId::set_all_keys(vec![OCW_AUTHORITY]);
+    t
+}
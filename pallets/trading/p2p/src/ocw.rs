@@ -6,10 +6,303 @@
 //! ## 设计
 //! - TRC20 验证逻辑委托给独立 crate `pallet-trading-trc20-verifier`
 //! - 本模块负责：何时触发验证、如何将结果写回链上
+//!
+//! ## 流程
+//! 1. `offchain_worker` 扫描 `SellPendingVerifications`（每区块最多 `MAX_VERIFICATIONS_PER_BLOCK` 笔）
+//! 2. 按订单支付通道（TRON 数据源 / EVM RPC）验证代币合约、收款地址与金额
+//! 3. 得出明确结论 → 签名载荷 + 无签名交易 `ocw_submit_sell_verification`
+//! 4. 网络错误 / 交易尚未索引 → 本地记录重试次数，指数退避后再试
+//!
+//! ## 去重
+//! - 链上：`SellOcwVerificationResults` 已有结果则不再提交（ValidateUnsigned 同样拒绝）
+//! - 链下：本地存储记录每笔订单的尝试状态，`compare_and_set` 防止并发 OCW 重复处理
+//!
+//! ## 密钥
+//! 节点需在 keystore 中插入 `p2pv` 类型的 sr25519 密钥，且对应账户
+//! 已通过 `set_sell_ocw_authorities` 加入白名单。
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use codec::{Decode, Encode};
+use frame_system::offchain::{SendUnsignedTransaction, Signer};
+use frame_system::pallet_prelude::BlockNumberFor;
+use sp_core::crypto::KeyTypeId;
+use sp_core::offchain::StorageKind;
+use sp_runtime::traits::SaturatedConversion;
+
+use crate::pallet::{
+    Call, Config, Pallet, SellOcwVerificationResults, SellOrders, SellPendingVerifications,
+    SellVerificationPayload, SellVerificationRequest,
+};
+use crate::types::SellOrderStatus;
 
 pub use pallet_trading_trc20_verifier::{
+    verify_payment,
     verify_trc20_transaction,
     verify_trc20_transaction_simple,
+    ExpectedTransfer,
+    PaymentNetwork,
     TronTxVerification,
     AmountStatus,
 };
+use pallet_trading_common::PaymentRail;
+
+// ==================== 签名密钥 ====================
+
+/// P2P OCW 专用签名 KeyType。注意：需要在节点端注册对应密钥。
+pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"p2pv");
+
+/// OCW 签名算法（sr25519）
+pub mod crypto {
+    use super::KEY_TYPE;
+    use sp_application_crypto::{app_crypto, sr25519};
+    use sp_runtime::{MultiSignature, MultiSigner};
+
+    app_crypto!(sr25519, KEY_TYPE);
+
+    /// 运行时使用的签名身份
+    pub struct P2pAuthId;
+
+    impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for P2pAuthId {
+        type RuntimeAppPublic = Public;
+        type GenericSignature = sp_core::sr25519::Signature;
+        type GenericPublic = sp_core::sr25519::Public;
+    }
+}
+
+// ==================== 常量配置 ====================
+
+/// 每个区块最多发起的验证数（限制 HTTP 请求量）
+pub const MAX_VERIFICATIONS_PER_BLOCK: u32 = 5;
+
+/// 重试退避基数（区块）
+pub const RETRY_BASE_BLOCKS: u32 = 5;
+
+/// 重试退避上限（区块）
+pub const RETRY_MAX_BACKOFF_BLOCKS: u32 = 300;
+
+/// 结果提交后的重发冷却期（区块）
+///
+/// 交易可能被交易池丢弃；冷却期过后链上仍无结果则重新验证并提交。
+pub const RESUBMIT_COOLDOWN_BLOCKS: u32 = 20;
+
+/// 订单 OCW 状态存储键前缀
+const SELL_OCW_STATE_PREFIX: &[u8] = b"p2p_ocw::sell_state::";
+
+// ==================== 本地状态 ====================
+
+/// 单笔 Sell 订单的 OCW 处理状态（offchain 本地存储）
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, Debug)]
+pub struct SellOcwState {
+    /// 连续失败（需重试）次数
+    pub attempts: u32,
+    /// 下次允许处理的区块
+    pub next_attempt_at: u32,
+    /// 是否已提交过结果
+    pub submitted: bool,
+}
+
+/// 验证结论
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum VerificationOutcome {
+    /// 明确结论，可提交上链
+    Conclusive {
+        verified: bool,
+        reason: Option<Vec<u8>>,
+    },
+    /// 暂时无法判断（网络错误、交易尚未索引），稍后重试
+    Retry(&'static str),
+}
+
+/// 将 TRC20 验证结果归类为明确结论或重试
+pub fn classify_verification(
+    result: Result<TronTxVerification, &'static str>,
+) -> VerificationOutcome {
+    match result {
+        Ok(v) if v.is_valid => VerificationOutcome::Conclusive { verified: true, reason: None },
+        Ok(v) => VerificationOutcome::Conclusive {
+            verified: false,
+            reason: Some(v.error.unwrap_or_else(|| b"Verification failed".to_vec())),
+        },
+        Err(e) => VerificationOutcome::Retry(e),
+    }
+}
+
+/// 第 `attempts` 次失败后的退避区块数：`RETRY_BASE_BLOCKS * 2^attempts`，封顶 `RETRY_MAX_BACKOFF_BLOCKS`
+pub fn retry_backoff(attempts: u32) -> u32 {
+    RETRY_BASE_BLOCKS
+        .saturating_mul(1u32 << attempts.min(16))
+        .min(RETRY_MAX_BACKOFF_BLOCKS)
+}
+
+/// 支付通道对应的验证网络
+pub fn payment_network(rail: &PaymentRail) -> PaymentNetwork {
+    match rail.chain.evm_chain_id() {
+        Some(chain_id) => PaymentNetwork::Evm { chain_id },
+        None => PaymentNetwork::Tron,
+    }
+}
+
+fn sell_state_key(sell_id: u64) -> Vec<u8> {
+    let mut key = SELL_OCW_STATE_PREFIX.to_vec();
+    key.extend_from_slice(&sell_id.to_le_bytes());
+    key
+}
+
+/// 读取订单 OCW 状态
+pub fn get_sell_ocw_state(sell_id: u64) -> SellOcwState {
+    sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, &sell_state_key(sell_id))
+        .and_then(|data| SellOcwState::decode(&mut &data[..]).ok())
+        .unwrap_or_default()
+}
+
+fn save_sell_ocw_state(sell_id: u64, state: &SellOcwState) {
+    sp_io::offchain::local_storage_set(
+        StorageKind::PERSISTENT,
+        &sell_state_key(sell_id),
+        &state.encode(),
+    );
+}
+
+// ==================== OCW 主流程 ====================
+
+impl<T: Config> Pallet<T> {
+    /// OCW 入口：处理待验证的 Sell 订单
+    pub fn offchain_verify_sell_orders(block_number: BlockNumberFor<T>) {
+        if SellPendingVerifications::<T>::iter_keys().next().is_none() {
+            return;
+        }
+
+        let signer = Signer::<T, T::AuthorityId>::any_account();
+        if !signer.can_sign() {
+            log::debug!(target: "p2p-ocw", "No local p2pv key, skip sell verification");
+            return;
+        }
+
+        let now: u32 = block_number.saturated_into();
+        let mut processed = 0u32;
+
+        for (sell_id, request) in SellPendingVerifications::<T>::iter() {
+            if processed >= MAX_VERIFICATIONS_PER_BLOCK {
+                break;
+            }
+            if !Self::should_verify_sell(sell_id, &request, now) {
+                continue;
+            }
+            // 抢占处理权：失败说明其他 OCW 实例正在处理
+            let Some(state) = Self::claim_sell_attempt(sell_id, now) else {
+                continue;
+            };
+            processed += 1;
+            Self::process_sell_verification(&signer, sell_id, &request, state, block_number);
+        }
+
+        if processed > 0 {
+            log::info!(target: "p2p-ocw", "Processed {} sell verifications at block {}", processed, now);
+        }
+    }
+
+    /// 判断订单是否需要本轮处理
+    fn should_verify_sell(sell_id: u64, request: &SellVerificationRequest<T>, now: u32) -> bool {
+        match SellOrders::<T>::get(sell_id) {
+            Some(record) if record.status == SellOrderStatus::AwaitingVerification => {},
+            _ => return false,
+        }
+        // 链上已有结果，等待 claim
+        if SellOcwVerificationResults::<T>::contains_key(sell_id) {
+            return false;
+        }
+        // 已超时，交给 handle_sell_verification_timeout
+        let timeout_at: u32 = request.verification_timeout_at.saturated_into();
+        if now >= timeout_at {
+            return false;
+        }
+        get_sell_ocw_state(sell_id).next_attempt_at <= now
+    }
+
+    /// 通过 compare-and-set 占用本次尝试，返回占用前的状态
+    fn claim_sell_attempt(sell_id: u64, now: u32) -> Option<SellOcwState> {
+        let key = sell_state_key(sell_id);
+        let old_raw = sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, &key);
+        let old = old_raw
+            .as_ref()
+            .and_then(|data| SellOcwState::decode(&mut &data[..]).ok())
+            .unwrap_or_default();
+
+        let mut claimed = old.clone();
+        claimed.next_attempt_at = now.saturating_add(retry_backoff(old.attempts));
+
+        if sp_io::offchain::local_storage_compare_and_set(
+            StorageKind::PERSISTENT,
+            &key,
+            old_raw,
+            &claimed.encode(),
+        ) {
+            Some(old)
+        } else {
+            None
+        }
+    }
+
+    /// 验证单笔订单并提交结果
+    fn process_sell_verification(
+        signer: &Signer<T, T::AuthorityId, frame_system::offchain::ForAny>,
+        sell_id: u64,
+        request: &SellVerificationRequest<T>,
+        mut state: SellOcwState,
+        block_number: BlockNumberFor<T>,
+    ) {
+        let now: u32 = block_number.saturated_into();
+        let Some(rail) = Pallet::<T>::payment_rail_of(request.rail_id) else {
+            log::error!(target: "p2p-ocw", "Sell {} references unknown payment rail {}", sell_id, request.rail_id);
+            return;
+        };
+        let expected = ExpectedTransfer {
+            token_contract: rail.token_contract.as_slice(),
+            decimals: rail.decimals,
+            min_confirmations: rail.min_confirmations,
+            to: request.expected_to.as_slice(),
+            amount: request.expected_amount,
+        };
+        let result = verify_payment(payment_network(&rail), request.tx_hash.as_slice(), &expected);
+
+        let (verified, reason) = match classify_verification(result) {
+            VerificationOutcome::Conclusive { verified, reason } => (verified, reason),
+            VerificationOutcome::Retry(e) => {
+                state.attempts = state.attempts.saturating_add(1);
+                state.next_attempt_at = now.saturating_add(retry_backoff(state.attempts));
+                save_sell_ocw_state(sell_id, &state);
+                log::warn!(target: "p2p-ocw", "Sell {} verification deferred ({}), retry at block {}",
+                    sell_id, e, state.next_attempt_at);
+                return;
+            },
+        };
+
+        let sent = signer.send_unsigned_transaction(
+            |account| SellVerificationPayload {
+                sell_id,
+                verified,
+                reason: reason.clone(),
+                block_number,
+                public: account.public.clone(),
+            },
+            |payload, signature| Call::ocw_submit_sell_verification { payload, signature },
+        );
+
+        match sent {
+            Some((_, Ok(()))) => {
+                state.attempts = 0;
+                state.submitted = true;
+                state.next_attempt_at = now.saturating_add(RESUBMIT_COOLDOWN_BLOCKS);
+                log::info!(target: "p2p-ocw", "Submitted sell {} verification: verified={}", sell_id, verified);
+            },
+            _ => {
+                state.attempts = state.attempts.saturating_add(1);
+                state.next_attempt_at = now.saturating_add(retry_backoff(state.attempts));
+                log::error!(target: "p2p-ocw", "Failed to submit sell {} verification", sell_id);
+            },
+        }
+        save_sell_ocw_state(sell_id, &state);
+    }
+}
@@ -1,13 +1,28 @@
 //! # P2P Pallet 单元测试
 //!
-//! 覆盖 Buy-side、Sell-side、KYC、Hooks 核心流程。
+//! 覆盖 Buy-side、Sell-side、KYC、Hooks、OCW、支付通道核心流程。
 
 use crate::mock::*;
 use crate::pallet::*;
 use crate::types::*;
-use frame_support::{assert_ok, assert_noop};
+use frame_support::{assert_ok, assert_noop, BoundedVec};
+use pallet_trading_common::{PaymentChain, PaymentRail, PaymentRailProvider};
 use sp_core::H256;
 
+/// USDT-BEP20 支付通道
+fn bep20_rail() -> PaymentRail {
+    PaymentRail {
+        chain: PaymentChain::Bsc,
+        token_contract: BoundedVec::truncate_from(b"0x55d398326f99059fF775485246999027B3197955".to_vec()),
+        decimals: 18,
+        min_confirmations: 15,
+        enabled: true,
+    }
+}
+
+const BSC_RAIL_ID: u32 = 2;
+const EVM_PAYOUT_ADDRESS: &[u8] = b"0x1111111111111111111111111111111111111111";
+
 // ==================== 1. Mock 环境验证 ====================
 
 #[test]
@@ -713,3 +728,482 @@ fn archived_order_l2_default() {
     assert_eq!(l2.amount_tier, 0);
     assert_eq!(l2.flags, 0);
 }
+
+// ==================== 10. OCW Sell 验证测试 ====================
+
+mod ocw {
+    use super::*;
+    use codec::{Decode, Encode};
+    use frame_support::{pallet_prelude::TransactionSource, traits::Hooks, unsigned::ValidateUnsigned};
+    use pallet_trading_trc20_verifier::{replay, TronSourceConfig, TRONGRID_MAINNET};
+    use sp_core::offchain::testing::{OffchainState, TestOffchainExt, TestTransactionPoolExt};
+    use sp_runtime::testing::{TestSignature, UintAuthorityId};
+
+    const SELL_TX_HASH: [u8; 32] = [0xABu8; 32];
+    /// 合法 Base58Check 收款地址，数据源需要从中解出 20 字节地址与调用数据比对
+    const VALID_TRON_ADDRESS: &[u8] = b"TA4Y62o6YC2Zsck9rZVGTvqW1AQ7X9zTnj";
+
+    /// 辅助：以合法 TRON 地址创建 Sell 订单、进入 AwaitingVerification 并授权 OCW 账户
+    fn setup_awaiting_sell() -> u64 {
+        System::set_block_number(1);
+        assert_ok!(P2pTrading::create_sell_order(
+            RuntimeOrigin::signed(BUYER),
+            MAKER_ID,
+            10_000_000_000_000,
+            VALID_TRON_ADDRESS.to_vec(),
+        ));
+        await_verification(0)
+    }
+
+    fn await_verification(sell_id: u64) -> u64 {
+        assert_ok!(P2pTrading::mark_sell_complete(
+            RuntimeOrigin::signed(MAKER_ACCOUNT),
+            sell_id,
+            SELL_TX_HASH.to_vec(),
+        ));
+        assert_ok!(P2pTrading::set_sell_ocw_authorities(RuntimeOrigin::root(), vec![OCW_AUTHORITY]));
+        sell_id
+    }
+
+    fn trongrid() -> TronSourceConfig {
+        TronSourceConfig::trongrid(TRONGRID_MAINNET)
+    }
+
+    /// 辅助：单数据源串行模式，便于预设 HTTP 响应
+    fn use_single_trongrid_endpoint() {
+        replay::use_sources(vec![trongrid()]);
+    }
+
+    /// 预设 TronGrid 响应
+    fn expect_trongrid(state: &mut OffchainState, body: &str) {
+        replay::expect_lookup(state, &trongrid(), &SELL_TX_HASH, body);
+    }
+
+    /// 录制格式的 TronGrid 成功转账响应（收款地址为 Sell 订单的收款地址）
+    fn trongrid_transfer(amount: u64) -> String {
+        replay::trongrid_transfer(VALID_TRON_ADDRESS, amount)
+    }
+
+    fn take_submitted_call(pool_state: &mut sp_core::offchain::testing::PoolState) -> Call<Test> {
+        assert_eq!(pool_state.transactions.len(), 1);
+        let raw = pool_state.transactions.pop().unwrap();
+        let tx = Extrinsic::decode(&mut &*raw).unwrap();
+        match tx.function {
+            RuntimeCall::P2pTrading(call) => call,
+            other => panic!("unexpected call: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn ocw_submits_verified_result_and_claim_completes() {
+        let (offchain, offchain_state) = TestOffchainExt::new();
+        let (pool, pool_state) = TestTransactionPoolExt::new();
+        new_test_ext_with_ocw(offchain, pool).execute_with(|| {
+            let sell_id = setup_awaiting_sell();
+            use_single_trongrid_endpoint();
+            expect_trongrid(&mut offchain_state.write(), &trongrid_transfer(1_000_000));
+
+            P2pTrading::offchain_worker(1);
+
+            let call = take_submitted_call(&mut pool_state.write());
+            assert!(P2pTrading::validate_unsigned(TransactionSource::Local, &call).is_ok());
+            let Call::ocw_submit_sell_verification { payload, signature } = call else {
+                panic!("unexpected call");
+            };
+            assert_eq!(payload.sell_id, sell_id);
+            assert!(payload.verified);
+            assert_eq!(payload.public, UintAuthorityId(OCW_AUTHORITY));
+
+            assert_ok!(P2pTrading::ocw_submit_sell_verification(RuntimeOrigin::none(), payload, signature));
+            assert_eq!(P2pTrading::sell_ocw_verification_results(sell_id), Some((true, None)));
+
+            assert_ok!(P2pTrading::claim_sell_verification_reward(RuntimeOrigin::signed(BUYER2), sell_id));
+            assert_eq!(P2pTrading::sell_orders(sell_id).unwrap().status, SellOrderStatus::Completed);
+        });
+    }
+
+    #[test]
+    fn ocw_submits_failure_for_underpaid_transfer() {
+        let (offchain, offchain_state) = TestOffchainExt::new();
+        let (pool, pool_state) = TestTransactionPoolExt::new();
+        new_test_ext_with_ocw(offchain, pool).execute_with(|| {
+            let sell_id = setup_awaiting_sell();
+            use_single_trongrid_endpoint();
+            expect_trongrid(&mut offchain_state.write(), &trongrid_transfer(600_000));
+
+            P2pTrading::offchain_worker(1);
+
+            let Call::ocw_submit_sell_verification { payload, .. } = take_submitted_call(&mut pool_state.write()) else {
+                panic!("unexpected call");
+            };
+            assert_eq!(payload.sell_id, sell_id);
+            assert!(!payload.verified);
+            assert!(payload.reason.is_some());
+        });
+    }
+
+    #[test]
+    fn ocw_rejects_trx_transfer_as_usdt_payment() {
+        let (offchain, offchain_state) = TestOffchainExt::new();
+        let (pool, pool_state) = TestTransactionPoolExt::new();
+        new_test_ext_with_ocw(offchain, pool).execute_with(|| {
+            let sell_id = setup_awaiting_sell();
+            use_single_trongrid_endpoint();
+            // 收款地址与金额匹配，但转的是 TRX 而非 USDT
+            expect_trongrid(
+                &mut offchain_state.write(),
+                &replay::trongrid_trx_transfer(VALID_TRON_ADDRESS, 1_000_000),
+            );
+
+            P2pTrading::offchain_worker(1);
+
+            let Call::ocw_submit_sell_verification { payload, .. } = take_submitted_call(&mut pool_state.write()) else {
+                panic!("unexpected call");
+            };
+            assert_eq!(payload.sell_id, sell_id);
+            assert!(!payload.verified);
+            assert!(payload.reason.is_some());
+        });
+    }
+
+    #[test]
+    fn ocw_backs_off_when_tx_not_indexed_yet() {
+        let (offchain, offchain_state) = TestOffchainExt::new();
+        let (pool, pool_state) = TestTransactionPoolExt::new();
+        new_test_ext_with_ocw(offchain, pool).execute_with(|| {
+            let sell_id = setup_awaiting_sell();
+            use_single_trongrid_endpoint();
+            expect_trongrid(&mut offchain_state.write(), replay::TRONGRID_NOT_FOUND);
+
+            P2pTrading::offchain_worker(1);
+            assert!(pool_state.read().transactions.is_empty());
+
+            let state = crate::ocw::get_sell_ocw_state(sell_id);
+            assert_eq!(state.attempts, 1);
+            assert_eq!(state.next_attempt_at, 1 + crate::ocw::retry_backoff(1));
+
+            // 退避期内不再发起请求
+            P2pTrading::offchain_worker(2);
+            assert!(pool_state.read().transactions.is_empty());
+
+            // 退避结束后重试成功
+            expect_trongrid(&mut offchain_state.write(), &trongrid_transfer(1_000_000));
+            P2pTrading::offchain_worker(state.next_attempt_at.into());
+            let Call::ocw_submit_sell_verification { payload, .. } = take_submitted_call(&mut pool_state.write()) else {
+                panic!("unexpected call");
+            };
+            assert!(payload.verified);
+
+            // 已提交：冷却期内不重复提交
+            P2pTrading::offchain_worker((state.next_attempt_at + 1).into());
+            assert!(pool_state.read().transactions.is_empty());
+        });
+    }
+
+    #[test]
+    fn ocw_verifies_through_own_full_node() {
+        let (offchain, offchain_state) = TestOffchainExt::new();
+        let (pool, pool_state) = TestTransactionPoolExt::new();
+        new_test_ext_with_ocw(offchain, pool).execute_with(|| {
+            let sell_id = setup_awaiting_sell();
+            let node = TronSourceConfig::full_node("http://127.0.0.1:8090");
+            replay::use_sources(vec![trongrid(), node.clone()]);
+            // 公共 API 尚未索引，回退到自建全节点
+            expect_trongrid(&mut offchain_state.write(), replay::TRONGRID_NOT_FOUND);
+            replay::expect_lookup(
+                &mut offchain_state.write(),
+                &node,
+                &SELL_TX_HASH,
+                &replay::full_node_transfer(VALID_TRON_ADDRESS, 1_000_000),
+            );
+
+            P2pTrading::offchain_worker(1);
+
+            let Call::ocw_submit_sell_verification { payload, .. } = take_submitted_call(&mut pool_state.write()) else {
+                panic!("unexpected call");
+            };
+            assert_eq!(payload.sell_id, sell_id);
+            assert!(payload.verified);
+        });
+    }
+
+    #[test]
+    fn ocw_verifies_bep20_sell_through_evm_receipt() {
+        let (offchain, offchain_state) = TestOffchainExt::new();
+        let (pool, pool_state) = TestTransactionPoolExt::new();
+        new_test_ext_with_ocw(offchain, pool).execute_with(|| {
+            System::set_block_number(1);
+            assert_ok!(P2pTrading::register_payment_rail(RuntimeOrigin::root(), BSC_RAIL_ID, bep20_rail()));
+            assert_ok!(P2pTrading::create_sell_order_on_rail(
+                RuntimeOrigin::signed(BUYER),
+                MAKER_ID,
+                10_000_000_000_000,
+                EVM_PAYOUT_ADDRESS.to_vec(),
+                BSC_RAIL_ID,
+            ));
+            let sell_id = await_verification(0);
+            assert_eq!(P2pTrading::sell_pending_verifications(sell_id).unwrap().rail_id, BSC_RAIL_ID);
+
+            let endpoint = "http://127.0.0.1:8545";
+            pallet_trading_trc20_verifier::set_evm_endpoints(56, vec![endpoint.into()]);
+            // 1 USDT = 10^18 原始单位（18 位精度），区块 100，链头 120
+            replay::expect_evm_lookup(
+                &mut offchain_state.write(),
+                endpoint,
+                &SELL_TX_HASH,
+                &replay::evm_receipt(bep20_rail().token_contract.as_slice(), EVM_PAYOUT_ADDRESS, 10u128.pow(18), 100),
+                Some(120),
+            );
+
+            P2pTrading::offchain_worker(1);
+
+            let Call::ocw_submit_sell_verification { payload, .. } = take_submitted_call(&mut pool_state.write()) else {
+                panic!("unexpected call");
+            };
+            assert_eq!(payload.sell_id, sell_id);
+            assert!(payload.verified);
+        });
+    }
+
+    #[test]
+    fn ocw_skips_without_local_key() {
+        let (offchain, _offchain_state) = TestOffchainExt::new();
+        let (pool, pool_state) = TestTransactionPoolExt::new();
+        new_test_ext_with_ocw(offchain, pool).execute_with(|| {
+            setup_awaiting_sell();
+            UintAuthorityId::set_all_keys(Vec::<u64>::new());
+
+            P2pTrading::offchain_worker(1);
+            assert!(pool_state.read().transactions.is_empty());
+        });
+    }
+
+    #[test]
+    fn validate_unsigned_rejects_bad_or_unauthorized_signatures() {
+        new_test_ext().execute_with(|| {
+            let sell_id = setup_awaiting_sell();
+            let payload = SellVerificationPayload {
+                sell_id,
+                verified: true,
+                reason: None,
+                block_number: 1u64,
+                public: UintAuthorityId(OCW_AUTHORITY),
+            };
+
+            // 签名与载荷不匹配
+            let bad_sig = TestSignature(OCW_AUTHORITY, b"other payload".to_vec());
+            let call = Call::ocw_submit_sell_verification { payload: payload.clone(), signature: bad_sig };
+            assert!(P2pTrading::validate_unsigned(TransactionSource::External, &call).is_err());
+
+            // 签名正确但账户不在白名单
+            let outsider = SellVerificationPayload { public: UintAuthorityId(5), ..payload.clone() };
+            let sig = TestSignature(5, outsider.encode());
+            let call = Call::ocw_submit_sell_verification { payload: outsider, signature: sig };
+            assert!(P2pTrading::validate_unsigned(TransactionSource::External, &call).is_err());
+
+            // 白名单账户正确签名
+            let sig = TestSignature(OCW_AUTHORITY, payload.encode());
+            let call = Call::ocw_submit_sell_verification { payload: payload.clone(), signature: sig.clone() };
+            assert!(P2pTrading::validate_unsigned(TransactionSource::External, &call).is_ok());
+
+            // 结果上链后重复提交被拒绝
+            assert_ok!(P2pTrading::ocw_submit_sell_verification(RuntimeOrigin::none(), payload.clone(), sig.clone()));
+            assert!(P2pTrading::validate_unsigned(TransactionSource::External, &call).is_err());
+            assert_noop!(
+                P2pTrading::ocw_submit_sell_verification(RuntimeOrigin::none(), payload, sig),
+                Error::<Test>::OcwResultAlreadySubmitted
+            );
+        });
+    }
+
+    #[test]
+    fn validate_unsigned_rejects_stale_or_replayed_payloads() {
+        new_test_ext().execute_with(|| {
+            let sell_id = setup_awaiting_sell();
+            let signed = |block_number: u64| {
+                let payload = SellVerificationPayload {
+                    sell_id,
+                    verified: false,
+                    reason: None,
+                    block_number,
+                    public: UintAuthorityId(OCW_AUTHORITY),
+                };
+                let sig = TestSignature(OCW_AUTHORITY, payload.encode());
+                (payload, sig)
+            };
+
+            // 早于验证请求提交区块的载荷（旧请求的签名结果）被拒绝
+            let (old, old_sig) = signed(0);
+            let call = Call::ocw_submit_sell_verification { payload: old.clone(), signature: old_sig.clone() };
+            assert!(P2pTrading::validate_unsigned(TransactionSource::External, &call).is_err());
+            assert_noop!(
+                P2pTrading::ocw_submit_sell_verification(RuntimeOrigin::none(), old, old_sig),
+                Error::<Test>::StaleVerificationPayload
+            );
+
+            // 超过有效期后，曾经有效的载荷不可再提交
+            let (payload, sig) = signed(1);
+            let call = Call::ocw_submit_sell_verification { payload: payload.clone(), signature: sig.clone() };
+            System::set_block_number(1 + SELL_PAYLOAD_MAX_AGE as u64);
+            assert!(P2pTrading::validate_unsigned(TransactionSource::External, &call).is_ok());
+            System::set_block_number(2 + SELL_PAYLOAD_MAX_AGE as u64);
+            assert!(P2pTrading::validate_unsigned(TransactionSource::External, &call).is_err());
+            assert_noop!(
+                P2pTrading::ocw_submit_sell_verification(RuntimeOrigin::none(), payload, sig),
+                Error::<Test>::StaleVerificationPayload
+            );
+
+            // 未来区块的载荷同样被拒绝
+            let (future, future_sig) = signed(10 + SELL_PAYLOAD_MAX_AGE as u64);
+            let call = Call::ocw_submit_sell_verification { payload: future, signature: future_sig };
+            assert!(P2pTrading::validate_unsigned(TransactionSource::External, &call).is_err());
+        });
+    }
+
+    #[test]
+    fn set_sell_ocw_authorities_requires_committee() {
+        new_test_ext().execute_with(|| {
+            assert_noop!(
+                P2pTrading::set_sell_ocw_authorities(RuntimeOrigin::signed(BUYER), vec![OCW_AUTHORITY]),
+                sp_runtime::DispatchError::BadOrigin
+            );
+            assert_ok!(P2pTrading::set_sell_ocw_authorities(RuntimeOrigin::root(), vec![OCW_AUTHORITY]));
+            assert_eq!(P2pTrading::sell_ocw_authorities().to_vec(), vec![OCW_AUTHORITY]);
+        });
+    }
+}
+
+// ==================== 11. 支付通道测试 ====================
+
+#[test]
+fn register_payment_rail_works() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_noop!(
+            P2pTrading::register_payment_rail(RuntimeOrigin::signed(BUYER), BSC_RAIL_ID, bep20_rail()),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(P2pTrading::register_payment_rail(RuntimeOrigin::root(), BSC_RAIL_ID, bep20_rail()));
+        assert_eq!(P2pTrading::payment_rails(BSC_RAIL_ID), Some(bep20_rail()));
+        assert_eq!(<P2pTrading as PaymentRailProvider>::payment_rail(BSC_RAIL_ID), Some(bep20_rail()));
+        System::assert_last_event(
+            Event::<Test>::PaymentRailRegistered { rail_id: BSC_RAIL_ID, rail: bep20_rail() }.into(),
+        );
+
+        // 通道不可覆盖，内置通道 ID 不可注册
+        for rail_id in [BSC_RAIL_ID, 0] {
+            assert_noop!(
+                P2pTrading::register_payment_rail(RuntimeOrigin::root(), rail_id, bep20_rail()),
+                Error::<Test>::PaymentRailExists
+            );
+        }
+        let invalid = PaymentRail { chain: PaymentChain::Tron, ..bep20_rail() };
+        assert_noop!(
+            P2pTrading::register_payment_rail(RuntimeOrigin::root(), 3, invalid),
+            Error::<Test>::InvalidPaymentRail
+        );
+    });
+}
+
+#[test]
+fn builtin_rail_available_without_registration() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(P2pTrading::payment_rail_of(0), Some(PaymentRail::tron_usdt()));
+        assert_eq!(P2pTrading::payment_rail_of(BSC_RAIL_ID), None);
+
+        let sell_id = create_sell_order();
+        assert_eq!(P2pTrading::sell_orders(sell_id).unwrap().rail_id, 0);
+    });
+}
+
+#[test]
+fn create_sell_order_on_rail_validates_rail_and_address() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        let sell = |address: &[u8], rail_id| P2pTrading::create_sell_order_on_rail(
+            RuntimeOrigin::signed(BUYER), MAKER_ID, 10_000_000_000_000, address.to_vec(), rail_id,
+        );
+
+        assert_noop!(sell(EVM_PAYOUT_ADDRESS, BSC_RAIL_ID), Error::<Test>::PaymentRailNotFound);
+        assert_ok!(P2pTrading::register_payment_rail(RuntimeOrigin::root(), BSC_RAIL_ID, bep20_rail()));
+
+        // EVM 通道拒绝 TRON 地址
+        assert_noop!(sell(b"TA4Y62o6YC2Zsck9rZVGTvqW1AQ7X9zTnj", BSC_RAIL_ID), Error::<Test>::InvalidPayoutAddress);
+        assert_ok!(sell(EVM_PAYOUT_ADDRESS, BSC_RAIL_ID));
+        let record = P2pTrading::sell_orders(0).unwrap();
+        assert_eq!(record.rail_id, BSC_RAIL_ID);
+        assert_eq!(record.usdt_address.to_vec(), EVM_PAYOUT_ADDRESS.to_vec());
+
+        // 停用后不接受新订单，进行中的订单不受影响
+        assert_ok!(P2pTrading::set_payment_rail_enabled(RuntimeOrigin::root(), BSC_RAIL_ID, false));
+        System::assert_last_event(
+            Event::<Test>::PaymentRailStatusChanged { rail_id: BSC_RAIL_ID, enabled: false }.into(),
+        );
+        assert_noop!(sell(EVM_PAYOUT_ADDRESS, BSC_RAIL_ID), Error::<Test>::PaymentRailDisabled);
+        assert_eq!(P2pTrading::sell_orders(0).unwrap().rail_id, BSC_RAIL_ID);
+    });
+}
+
+// ============================================================================
+// 存储迁移
+// ============================================================================
+
+#[test]
+fn migration_v1_adds_default_rail_to_sell_records() {
+    use crate::migrations::{self, v1::v0::{OldSellOrder, OldSellVerificationRequest}};
+    use codec::Encode;
+    use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+    use pallet_trading_common::DEFAULT_RAIL_ID;
+
+    new_test_ext().execute_with(|| {
+        StorageVersion::new(0).put::<P2pTrading>();
+
+        let address: BoundedVec<u8, _> = b"TA4Y62o6YC2Zsck9rZVGTvqW1AQ7X9zTnj".to_vec().try_into().unwrap();
+        let tx_hash: BoundedVec<u8, _> = b"legacy_tx".to_vec().try_into().unwrap();
+        let old_order = OldSellOrder::<u64, BalanceOf<Test>, u64> {
+            sell_id: 7,
+            maker_id: MAKER_ID,
+            maker: MAKER_ACCOUNT,
+            user: BUYER,
+            nxs_amount: 1_000,
+            usdt_amount: 5_000_000,
+            usdt_address: address.clone(),
+            created_at: 1,
+            timeout_at: 100,
+            trc20_tx_hash: Some(tx_hash.clone()),
+            completed_at: None,
+            evidence_cid: None,
+            status: SellOrderStatus::AwaitingVerification,
+            price_usdt: 500_000,
+            dispute_deposit: None,
+        };
+        let old_request = OldSellVerificationRequest::<u64> {
+            sell_id: 7,
+            tx_hash: tx_hash.clone(),
+            expected_to: address.clone(),
+            expected_amount: 5_000_000,
+            submitted_at: 2,
+            verification_timeout_at: 50,
+            retry_count: 1,
+        };
+        frame_support::storage::unhashed::put_raw(&SellOrders::<Test>::hashed_key_for(7), &old_order.encode());
+        frame_support::storage::unhashed::put_raw(
+            &SellPendingVerifications::<Test>::hashed_key_for(7),
+            &old_request.encode(),
+        );
+
+        migrations::v1::MigrateV0ToV1::<Test>::on_runtime_upgrade();
+
+        assert_eq!(P2pTrading::on_chain_storage_version(), StorageVersion::new(1));
+        let order = SellOrders::<Test>::get(7).expect("sell order migrated");
+        assert_eq!(order.rail_id, DEFAULT_RAIL_ID);
+        assert_eq!(order.usdt_address, address);
+        assert_eq!(order.trc20_tx_hash, Some(tx_hash));
+        assert_eq!(order.status, SellOrderStatus::AwaitingVerification);
+        assert_eq!(order.price_usdt, 500_000);
+        let request = SellPendingVerifications::<Test>::get(7).expect("verification migrated");
+        assert_eq!(request.rail_id, DEFAULT_RAIL_ID);
+        assert_eq!(request.expected_amount, 5_000_000);
+        assert_eq!(request.retry_count, 1);
+    });
+}
@@ -15,6 +15,9 @@ pub trait WeightInfo {
     fn mark_sell_complete() -> Weight;
     fn report_sell() -> Weight;
     fn confirm_sell_verification() -> Weight;
+    fn set_sell_ocw_authorities() -> Weight;
+    fn register_payment_rail() -> Weight;
+    fn set_payment_rail_enabled() -> Weight;
     // KYC
     fn enable_kyc() -> Weight;
     fn disable_kyc() -> Weight;
@@ -31,6 +34,9 @@ impl WeightInfo for () {
     fn mark_sell_complete() -> Weight { Weight::from_parts(40_000, 0) }
     fn report_sell() -> Weight { Weight::from_parts(20_000, 0) }
     fn confirm_sell_verification() -> Weight { Weight::from_parts(40_000, 0) }
+    fn set_sell_ocw_authorities() -> Weight { Weight::from_parts(10_000, 0) }
+    fn register_payment_rail() -> Weight { Weight::from_parts(15_000, 0) }
+    fn set_payment_rail_enabled() -> Weight { Weight::from_parts(10_000, 0) }
     fn enable_kyc() -> Weight { Weight::from_parts(10_000, 0) }
     fn disable_kyc() -> Weight { Weight::from_parts(10_000, 0) }
 }
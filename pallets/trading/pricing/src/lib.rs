@@ -7,14 +7,23 @@
 //! 1. NXS/USDT 市场价格聚合（P2P Buy + Sell 两方向）
 //! 2. CNY/USDT 汇率获取（通过 Offchain Worker）
 //! 3. 价格偏离检查
+//! 4. 喂价预言机：注册喂价人按轮次提交签名报价，链上聚合（中位数 + 异常值剔除）
 //!
 //! ## 版本历史
 //! - v1.4.0 (2026-02-08): 适配 P2P 统一模型，OTC→Buy, Bridge→Sell
+//! - v1.5.0 (2026-10-17): 新增喂价预言机模式（多喂价人签名报价、链上聚合、喂价人准确度、TWAP）
 //!
 //! ## Offchain Worker
 //! - 每24小时自动从 Exchange Rate API 获取 CNY/USD 汇率
 //! - API: https://api.exchangerate-api.com/v4/latest/USD
 //! - 汇率存储在 offchain local storage 中，供链上查询使用
+//!
+//! ## 喂价预言机
+//! - 治理注册喂价人（`add_oracle_feeder`），每 `OracleRoundLength` 个区块为一轮
+//! - 喂价人通过签名交易 `submit_oracle_quote` 提交当前轮报价（NXS/USDT、CNY/USDT）
+//! - 轮次结束时链上聚合：中位数 → 剔除偏离超过 `MaxQuoteDeviation` 的报价 → 再取中位数
+//! - 记录每个喂价人的采纳/剔除次数与平均偏差
+//! - 启用预言机模式后，`get_nxs_usdt_price` / `get_cny_usdt_rate` 返回聚合价格的 TWAP
 
 pub use pallet::*;
 pub use pallet::ExchangeRateData;
@@ -30,12 +39,14 @@ mod tests;
 
 mod ocw;
 
+pub mod oracle;
+
 #[frame_support::pallet]
 pub mod pallet {
     use frame_support::{pallet_prelude::*, traits::Get};
     use frame_system::pallet_prelude::*;
     use sp_runtime::{
-        traits::Saturating,
+        traits::{SaturatedConversion, Saturating, Zero},
         transaction_validity::{
             InvalidTransaction, TransactionSource, TransactionValidity, ValidTransaction,
         },
@@ -58,6 +69,30 @@ pub mod pallet {
         /// 默认 14400 个区块（约24小时，假设6秒出块）
         #[pallet::constant]
         type ExchangeRateUpdateInterval: Get<u32>;
+
+        /// 函数级中文注释：喂价人管理权限（注册/移除喂价人、切换预言机模式）
+        type OracleGovernanceOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// 函数级中文注释：最大喂价人数量
+        #[pallet::constant]
+        type MaxOracleFeeders: Get<u32>;
+
+        /// 函数级中文注释：喂价轮次长度（区块数）
+        /// 轮次编号 = 区块号 / OracleRoundLength
+        #[pallet::constant]
+        type OracleRoundLength: Get<BlockNumberFor<Self>>;
+
+        /// 函数级中文注释：每轮最少有效报价数（剔除异常值后）
+        #[pallet::constant]
+        type MinOracleQuotes: Get<u32>;
+
+        /// 函数级中文注释：报价相对本轮中位数的最大偏离（bps），超过视为异常值
+        #[pallet::constant]
+        type MaxQuoteDeviation: Get<u16>;
+
+        /// 函数级中文注释：默认 TWAP 窗口（区块数），治理可通过 `set_oracle_twap_window` 调整
+        #[pallet::constant]
+        type DefaultTwapWindow: Get<BlockNumberFor<Self>>;
     }
 
     // ===== P3修复：类型安全的循环缓冲区索引 =====
@@ -155,6 +190,84 @@ pub mod pallet {
         pub updated_at: u64,
     }
 
+    // ===== 喂价预言机类型 =====
+
+    /// 每个喂价对保留的聚合价格历史点数（用于 TWAP）
+    pub const ORACLE_HISTORY_SIZE: u32 = 64;
+
+    /// 聚合价格超过该轮数未更新即视为过期，回退到原有价格来源
+    pub const ORACLE_MAX_STALE_ROUNDS: u32 = 3;
+
+    /// 函数级中文注释：喂价对
+    #[derive(Clone, Copy, Encode, Decode, codec::DecodeWithMemTracking, TypeInfo, MaxEncodedLen, RuntimeDebug, PartialEq, Eq)]
+    pub enum OracleFeed {
+        /// NXS/USDT 价格（精度 10^6）
+        NxsUsdt,
+        /// CNY/USDT 汇率（精度 10^6）
+        CnyUsdt,
+    }
+
+    impl OracleFeed {
+        /// 所有喂价对（轮次结算时遍历）
+        pub const ALL: [OracleFeed; 2] = [OracleFeed::NxsUsdt, OracleFeed::CnyUsdt];
+
+        /// 报价是否在该喂价对的合理范围内
+        pub fn is_valid_price(&self, price: u64) -> bool {
+            match self {
+                OracleFeed::NxsUsdt => price > 0,
+                // 与 OCW 汇率提交相同的范围（5.0 ~ 10.0 CNY/USD）
+                OracleFeed::CnyUsdt => (5_000_000..=10_000_000).contains(&price),
+            }
+        }
+    }
+
+    /// 函数级中文注释：当前轮次收集到的报价
+    #[derive(Clone, Encode, Decode, TypeInfo, MaxEncodedLen, RuntimeDebug, PartialEq, Eq)]
+    #[scale_info(skip_type_params(T))]
+    pub struct OracleRoundQuotes<T: Config> {
+        /// 轮次编号
+        pub round: u32,
+        /// (喂价人, 报价)
+        pub quotes: BoundedVec<(T::AccountId, u64), T::MaxOracleFeeders>,
+    }
+
+    /// 函数级中文注释：聚合后的预言机价格
+    #[derive(Clone, Encode, Decode, TypeInfo, MaxEncodedLen, RuntimeDebug, Default, PartialEq, Eq)]
+    pub struct AggregatedPrice<BlockNumber> {
+        /// 聚合价格（精度 10^6）
+        pub price: u64,
+        /// 所属轮次
+        pub round: u32,
+        /// 被采纳的报价数
+        pub quotes: u32,
+        /// 聚合所在区块
+        pub updated_at: BlockNumber,
+    }
+
+    /// 函数级中文注释：喂价人准确度统计
+    #[derive(Clone, Encode, Decode, TypeInfo, MaxEncodedLen, RuntimeDebug, Default, PartialEq, Eq)]
+    pub struct FeederStats {
+        /// 被采纳的报价次数
+        pub accepted: u32,
+        /// 因偏离过大被剔除的次数
+        pub rejected: u32,
+        /// 累计偏差（相对本轮参考中位数，bps）
+        pub total_deviation_bps: u64,
+        /// 最近一次参与的轮次
+        pub last_round: u32,
+    }
+
+    impl FeederStats {
+        /// 平均偏差（bps），未参与任何轮次时为 0
+        pub fn average_deviation_bps(&self) -> u64 {
+            let rounds = self.accepted.saturating_add(self.rejected) as u64;
+            if rounds == 0 {
+                return 0;
+            }
+            self.total_deviation_bps / rounds
+        }
+    }
+
     /// 函数级中文注释：Buy 方向（USDT→NXS）价格聚合数据
     /// 维护最近累计 1,000,000 NXS 的 Buy 方向订单统计
     #[pallet::storage]
@@ -235,6 +348,56 @@ pub mod pallet {
     #[pallet::getter(fn last_rate_update_block)]
     pub type LastRateUpdateBlock<T: Config> = StorageValue<_, BlockNumberFor<T>, ValueQuery>;
 
+    // ===== 喂价预言机存储 =====
+
+    /// 函数级中文注释：已注册的喂价人
+    #[pallet::storage]
+    #[pallet::getter(fn oracle_feeders)]
+    pub type OracleFeeders<T: Config> =
+        StorageValue<_, BoundedVec<T::AccountId, T::MaxOracleFeeders>, ValueQuery>;
+
+    /// 函数级中文注释：各喂价对当前轮次收集中的报价
+    #[pallet::storage]
+    pub type OracleRounds<T: Config> = StorageMap<_, Twox64Concat, OracleFeed, OracleRoundQuotes<T>>;
+
+    /// 函数级中文注释：各喂价对最近一次聚合价格
+    #[pallet::storage]
+    #[pallet::getter(fn oracle_price)]
+    pub type OraclePrices<T: Config> =
+        StorageMap<_, Twox64Concat, OracleFeed, AggregatedPrice<BlockNumberFor<T>>>;
+
+    /// 函数级中文注释：聚合价格历史 (区块, 价格)，最多 ORACLE_HISTORY_SIZE 个点，用于 TWAP
+    #[pallet::storage]
+    pub type OraclePriceHistory<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        OracleFeed,
+        BoundedVec<(BlockNumberFor<T>, u64), ConstU32<ORACLE_HISTORY_SIZE>>,
+        ValueQuery,
+    >;
+
+    /// 函数级中文注释：喂价人准确度统计
+    #[pallet::storage]
+    #[pallet::getter(fn feeder_stats)]
+    pub type OracleFeederStats<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, FeederStats, ValueQuery>;
+
+    /// 函数级中文注释：是否启用预言机模式
+    /// 启用后价格消费方（p2p、maker 押金、entity）使用聚合价格 TWAP
+    #[pallet::storage]
+    #[pallet::getter(fn oracle_enabled)]
+    pub type OracleEnabled<T> = StorageValue<_, bool, ValueQuery>;
+
+    /// 函数级中文注释：消费方使用的 TWAP 窗口（区块数）
+    #[pallet::storage]
+    #[pallet::getter(fn oracle_twap_window)]
+    pub type OracleTwapWindow<T: Config> =
+        StorageValue<_, BlockNumberFor<T>, ValueQuery, DefaultOracleTwapWindow<T>>;
+
+    #[pallet::type_value]
+    pub fn DefaultOracleTwapWindow<T: Config>() -> BlockNumberFor<T> {
+        T::DefaultTwapWindow::get()
+    }
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -278,6 +441,35 @@ pub mod pallet {
             /// 更新时的区块号
             block_number: BlockNumberFor<T>,
         },
+        /// 函数级中文注释：喂价人已注册
+        OracleFeederAdded { feeder: T::AccountId },
+        /// 函数级中文注释：喂价人已移除
+        OracleFeederRemoved { feeder: T::AccountId },
+        /// 函数级中文注释：喂价人提交报价
+        OracleQuoteSubmitted {
+            feeder: T::AccountId,
+            feed: OracleFeed,
+            round: u32,
+            price: u64,
+        },
+        /// 函数级中文注释：轮次聚合完成
+        OraclePriceAggregated {
+            feed: OracleFeed,
+            round: u32,
+            price: u64,
+            accepted: u32,
+            rejected: u32,
+        },
+        /// 函数级中文注释：轮次有效报价不足，本轮作废
+        OracleRoundFailed {
+            feed: OracleFeed,
+            round: u32,
+            quotes: u32,
+        },
+        /// 函数级中文注释：预言机模式切换
+        OracleModeChanged { enabled: bool },
+        /// 函数级中文注释：TWAP 窗口更新
+        OracleTwapWindowUpdated { window: BlockNumberFor<T> },
     }
 
     #[pallet::error]
@@ -301,6 +493,18 @@ pub mod pallet {
         ArithmeticOverflow,
         /// P3修复：单笔订单数量超过上限
         OrderTooLarge,
+        /// 喂价人已注册
+        FeederAlreadyRegistered,
+        /// 不是已注册的喂价人
+        NotOracleFeeder,
+        /// 喂价人数量已达上限
+        TooManyFeeders,
+        /// 报价轮次不是当前轮次
+        WrongOracleRound,
+        /// 本轮已提交过报价
+        DuplicateOracleQuote,
+        /// TWAP 窗口无效（必须 > 0）
+        InvalidTwapWindow,
     }
 
     #[pallet::pallet]
@@ -723,7 +927,7 @@ pub mod pallet {
         /// - `Err(Error::PriceDeviationTooLarge)`: 价格偏离超过限制
         /// 
         /// # 逻辑
-        /// 1. 获取当前市场加权平均价格作为基准价格
+        /// 1. 获取基准价格（`get_nxs_usdt_price`：预言机 TWAP，回退市场加权平均价格）
         /// 2. 验证基准价格有效（> 0）
         /// 3. 计算订单价格与基准价格的偏离率（绝对值，单位：bps）
         /// 4. 检查偏离率是否超过 MaxPriceDeviation 配置的限制
@@ -740,8 +944,8 @@ pub mod pallet {
         /// - P2P Sell 订单创建时的价格合理性检查
         /// - 防止极端价格订单，保护买卖双方
         pub fn check_price_deviation(order_price_usdt: u64) -> DispatchResult {
-            // 1. 获取基准价格（市场加权平均价格）
-            let base_price = Self::get_cos_market_price_weighted();
+            // 1. 获取基准价格（预言机 TWAP 或市场加权平均价格）
+            let base_price = Self::get_nxs_usdt_price();
             
             // 2. 验证基准价格有效
             ensure!(base_price > 0, Error::<T>::InvalidBasePrice);
@@ -917,6 +1121,117 @@ pub mod pallet {
             
             Ok(())
         }
+
+        /// 函数级详细中文注释：注册喂价人
+        ///
+        /// # 权限
+        /// - `OracleGovernanceOrigin`
+        ///
+        /// # 错误
+        /// - `FeederAlreadyRegistered`: 已注册
+        /// - `TooManyFeeders`: 达到 `MaxOracleFeeders`
+        #[pallet::call_index(3)]
+        #[pallet::weight(T::DbWeight::get().reads_writes(1, 1))]
+        pub fn add_oracle_feeder(origin: OriginFor<T>, feeder: T::AccountId) -> DispatchResult {
+            T::OracleGovernanceOrigin::ensure_origin(origin)?;
+
+            OracleFeeders::<T>::try_mutate(|feeders| -> DispatchResult {
+                ensure!(!feeders.contains(&feeder), Error::<T>::FeederAlreadyRegistered);
+                feeders.try_push(feeder.clone()).map_err(|_| Error::<T>::TooManyFeeders)?;
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::OracleFeederAdded { feeder });
+            Ok(())
+        }
+
+        /// 函数级详细中文注释：移除喂价人
+        ///
+        /// 已提交的当前轮报价保留并参与聚合；准确度统计保留用于审计。
+        #[pallet::call_index(4)]
+        #[pallet::weight(T::DbWeight::get().reads_writes(1, 1))]
+        pub fn remove_oracle_feeder(origin: OriginFor<T>, feeder: T::AccountId) -> DispatchResult {
+            T::OracleGovernanceOrigin::ensure_origin(origin)?;
+
+            OracleFeeders::<T>::try_mutate(|feeders| -> DispatchResult {
+                let pos = feeders.iter().position(|f| f == &feeder).ok_or(Error::<T>::NotOracleFeeder)?;
+                feeders.remove(pos);
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::OracleFeederRemoved { feeder });
+            Ok(())
+        }
+
+        /// 函数级详细中文注释：喂价人提交当前轮报价（签名交易）
+        ///
+        /// # 参数
+        /// - `feed`: 喂价对
+        /// - `round`: 轮次编号（必须等于当前轮次，防止延迟报价被计入）
+        /// - `price`: 报价（精度 10^6）
+        ///
+        /// # 错误
+        /// - `NotOracleFeeder`: 调用者未注册
+        /// - `WrongOracleRound`: 非当前轮次
+        /// - `DuplicateOracleQuote`: 本轮已报价
+        /// - `InvalidPrice`: 报价超出喂价对合理范围
+        #[pallet::call_index(5)]
+        #[pallet::weight(T::DbWeight::get().reads_writes(3, 2))]
+        pub fn submit_oracle_quote(
+            origin: OriginFor<T>,
+            feed: OracleFeed,
+            round: u32,
+            price: u64,
+        ) -> DispatchResult {
+            let feeder = ensure_signed(origin)?;
+            ensure!(Self::oracle_feeders().contains(&feeder), Error::<T>::NotOracleFeeder);
+            ensure!(feed.is_valid_price(price), Error::<T>::InvalidPrice);
+
+            let current_round = Self::oracle_round_at(frame_system::Pallet::<T>::block_number());
+            ensure!(round == current_round, Error::<T>::WrongOracleRound);
+
+            // 上一轮若未在轮次边界结算（如轮次长度调整），先行结算
+            Self::settle_stale_round(feed, current_round);
+
+            OracleRounds::<T>::try_mutate(feed, |maybe_round| -> DispatchResult {
+                let entry = maybe_round.get_or_insert_with(|| OracleRoundQuotes {
+                    round: current_round,
+                    quotes: BoundedVec::default(),
+                });
+                ensure!(
+                    !entry.quotes.iter().any(|(who, _)| who == &feeder),
+                    Error::<T>::DuplicateOracleQuote
+                );
+                entry.quotes.try_push((feeder.clone(), price)).map_err(|_| Error::<T>::TooManyFeeders)?;
+                Ok(())
+            })?;
+
+            Self::deposit_event(Event::OracleQuoteSubmitted { feeder, feed, round, price });
+            Ok(())
+        }
+
+        /// 函数级详细中文注释：启用/关闭预言机模式
+        ///
+        /// 关闭时价格消费方回退到 P2P 成交聚合价格与 OCW 汇率。
+        #[pallet::call_index(6)]
+        #[pallet::weight(T::DbWeight::get().writes(1))]
+        pub fn set_oracle_enabled(origin: OriginFor<T>, enabled: bool) -> DispatchResult {
+            T::OracleGovernanceOrigin::ensure_origin(origin)?;
+            OracleEnabled::<T>::put(enabled);
+            Self::deposit_event(Event::OracleModeChanged { enabled });
+            Ok(())
+        }
+
+        /// 函数级详细中文注释：设置消费方使用的 TWAP 窗口（区块数）
+        #[pallet::call_index(7)]
+        #[pallet::weight(T::DbWeight::get().writes(1))]
+        pub fn set_oracle_twap_window(origin: OriginFor<T>, window: BlockNumberFor<T>) -> DispatchResult {
+            T::OracleGovernanceOrigin::ensure_origin(origin)?;
+            ensure!(!window.is_zero(), Error::<T>::InvalidTwapWindow);
+            OracleTwapWindow::<T>::put(window);
+            Self::deposit_event(Event::OracleTwapWindowUpdated { window });
+            Ok(())
+        }
     }
     
     // ===== P0-1修复：OCW 无签名交易验证 =====
@@ -960,6 +1275,20 @@ pub mod pallet {
 
     #[pallet::hooks]
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        /// 轮次边界：结算上一轮报价
+        fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+            let round_length = T::OracleRoundLength::get();
+            if round_length.is_zero() || !(now % round_length).is_zero() {
+                return Weight::zero();
+            }
+            let current_round = Self::oracle_round_at(now);
+            let mut weight = Weight::zero();
+            for feed in OracleFeed::ALL {
+                weight = weight.saturating_add(Self::settle_stale_round(feed, current_round));
+            }
+            weight
+        }
+
         /// Offchain Worker 入口点
         ///
         /// 每个区块执行一次，检查是否需要更新汇率
@@ -976,7 +1305,11 @@ pub mod pallet {
         ///
         /// # 返回
         /// - `u64`: CNY/USD 汇率（精度 10^6），如果未设置则返回默认值 7_200_000（7.2）
+        /// - 预言机模式下优先返回聚合汇率的 TWAP
         pub fn get_cny_usdt_rate() -> u64 {
+            if let Some(rate) = Self::oracle_reference_price(OracleFeed::CnyUsdt) {
+                return rate;
+            }
             let rate_data = CnyUsdtRate::<T>::get();
             if rate_data.cny_rate > 0 {
                 rate_data.cny_rate
@@ -1027,4 +1360,141 @@ pub mod pallet {
                 as u64
         }
     }
+
+    // ===== 喂价预言机 =====
+
+    impl<T: Config> Pallet<T> {
+        /// 函数级中文注释：区块所属的喂价轮次编号
+        pub fn oracle_round_at(block: BlockNumberFor<T>) -> u32 {
+            let round_length = T::OracleRoundLength::get();
+            if round_length.is_zero() {
+                return 0;
+            }
+            (block / round_length).saturated_into()
+        }
+
+        /// 函数级中文注释：若收集中的报价属于已结束的轮次，则结算
+        fn settle_stale_round(feed: OracleFeed, current_round: u32) -> Weight {
+            match OracleRounds::<T>::get(feed) {
+                Some(pending) if pending.round < current_round => {
+                    OracleRounds::<T>::remove(feed);
+                    let count = pending.quotes.len() as u64;
+                    Self::finalize_oracle_round(feed, pending);
+                    T::DbWeight::get().reads_writes(2 + count, 3 + count)
+                },
+                _ => T::DbWeight::get().reads(1),
+            }
+        }
+
+        /// 函数级详细中文注释：聚合一轮报价并更新喂价人统计
+        ///
+        /// # 逻辑
+        /// 1. `oracle::aggregate_quotes`：中位数 → 剔除偏离 > MaxQuoteDeviation 的报价 → 再取中位数
+        /// 2. 每个喂价人记录相对参考中位数的偏差，以及被采纳/剔除
+        /// 3. 有效报价不足 MinOracleQuotes 时本轮作废，保留上一轮价格
+        /// 4. 成功时写入 `OraclePrices` 并追加 TWAP 历史点
+        pub(crate) fn finalize_oracle_round(feed: OracleFeed, pending: OracleRoundQuotes<T>) {
+            let prices: sp_std::vec::Vec<u64> = pending.quotes.iter().map(|(_, p)| *p).collect();
+            let aggregation = crate::oracle::aggregate_quotes(
+                &prices,
+                T::MaxQuoteDeviation::get() as u64,
+                T::MinOracleQuotes::get(),
+            );
+
+            let reference = aggregation
+                .as_ref()
+                .map(|a| a.reference)
+                .unwrap_or_else(|| crate::oracle::median(&mut prices.clone()));
+            for (i, (feeder, price)) in pending.quotes.iter().enumerate() {
+                let accepted = aggregation.as_ref().map(|a| a.accepted[i]).unwrap_or(false);
+                OracleFeederStats::<T>::mutate(feeder, |stats| {
+                    if accepted {
+                        stats.accepted = stats.accepted.saturating_add(1);
+                    } else {
+                        stats.rejected = stats.rejected.saturating_add(1);
+                    }
+                    stats.total_deviation_bps = stats
+                        .total_deviation_bps
+                        .saturating_add(crate::oracle::deviation_bps(*price, reference).min(10_000));
+                    stats.last_round = pending.round;
+                });
+            }
+
+            let Some(aggregation) = aggregation else {
+                Self::deposit_event(Event::OracleRoundFailed {
+                    feed,
+                    round: pending.round,
+                    quotes: prices.len() as u32,
+                });
+                return;
+            };
+
+            let now = frame_system::Pallet::<T>::block_number();
+            let accepted = aggregation.accepted_count();
+            OraclePrices::<T>::insert(feed, AggregatedPrice {
+                price: aggregation.price,
+                round: pending.round,
+                quotes: accepted,
+                updated_at: now,
+            });
+            OraclePriceHistory::<T>::mutate(feed, |history| {
+                if history.is_full() {
+                    history.remove(0);
+                }
+                let _ = history.try_push((now, aggregation.price));
+            });
+
+            Self::deposit_event(Event::OraclePriceAggregated {
+                feed,
+                round: pending.round,
+                price: aggregation.price,
+                accepted,
+                rejected: (prices.len() as u32).saturating_sub(accepted),
+            });
+        }
+
+        /// 函数级详细中文注释：指定窗口的时间加权平均价格（TWAP）
+        ///
+        /// # 参数
+        /// - `feed`: 喂价对
+        /// - `window`: 窗口长度（区块数），可任意指定；历史最多保留 ORACLE_HISTORY_SIZE 轮
+        ///
+        /// # 返回
+        /// - `None`: 尚无聚合价格
+        pub fn get_twap(feed: OracleFeed, window: BlockNumberFor<T>) -> Option<u64> {
+            let points: sp_std::vec::Vec<(u64, u64)> = OraclePriceHistory::<T>::get(feed)
+                .iter()
+                .map(|(block, price)| ((*block).saturated_into(), *price))
+                .collect();
+            let now: u64 = frame_system::Pallet::<T>::block_number().saturated_into();
+            crate::oracle::time_weighted_average(&points, now, window.saturated_into())
+        }
+
+        /// 函数级详细中文注释：消费方使用的预言机价格
+        ///
+        /// 预言机模式启用、且最近一次聚合未超过 ORACLE_MAX_STALE_ROUNDS 轮时，
+        /// 返回 `OracleTwapWindow` 窗口的 TWAP；否则返回 None，由调用方回退。
+        pub fn oracle_reference_price(feed: OracleFeed) -> Option<u64> {
+            if !OracleEnabled::<T>::get() {
+                return None;
+            }
+            let latest = OraclePrices::<T>::get(feed)?;
+            let now = frame_system::Pallet::<T>::block_number();
+            let max_age = T::OracleRoundLength::get().saturating_mul(ORACLE_MAX_STALE_ROUNDS.into());
+            if now.saturating_sub(latest.updated_at) > max_age {
+                return None;
+            }
+            Self::get_twap(feed, OracleTwapWindow::<T>::get()).filter(|p| *p > 0)
+        }
+
+        /// 函数级详细中文注释：获取 NXS/USDT 参考价格（精度 10^6）
+        ///
+        /// 预言机模式下返回聚合价格 TWAP，否则回退到 `get_cos_market_price_weighted`。
+        /// p2p、做市商押金（`DepositCalculatorImpl`）与 entity 定价均经由 runtime 的
+        /// `TradingPricingProvider` 使用此价格。
+        pub fn get_nxs_usdt_price() -> u64 {
+            Self::oracle_reference_price(OracleFeed::NxsUsdt)
+                .unwrap_or_else(Self::get_cos_market_price_weighted)
+        }
+    }
 }
@@ -26,6 +26,11 @@ parameter_types! {
     pub const BlockHashCount: u64 = 250;
     pub const MaxPriceDeviation: u16 = 2000; // 20% (2000 bps)
     pub const ExchangeRateUpdateInterval: u32 = 10; // 测试用较短间隔
+    pub const MaxOracleFeeders: u32 = 5;
+    pub const OracleRoundLength: u64 = 10; // 每 10 个区块一轮
+    pub const MinOracleQuotes: u32 = 2;
+    pub const MaxQuoteDeviation: u16 = 500; // 5%
+    pub const DefaultTwapWindow: u64 = 20;
 }
 
 impl frame_system::Config for Test {
@@ -65,6 +70,12 @@ impl pallet_pricing::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type MaxPriceDeviation = MaxPriceDeviation;
     type ExchangeRateUpdateInterval = ExchangeRateUpdateInterval;
+    type OracleGovernanceOrigin = frame_system::EnsureRoot<u64>;
+    type MaxOracleFeeders = MaxOracleFeeders;
+    type OracleRoundLength = OracleRoundLength;
+    type MinOracleQuotes = MinOracleQuotes;
+    type MaxQuoteDeviation = MaxQuoteDeviation;
+    type DefaultTwapWindow = DefaultTwapWindow;
 }
 
 /// 函数级中文注释：创建测试环境
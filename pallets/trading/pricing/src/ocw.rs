@@ -274,31 +274,16 @@ impl<T: Config> Pallet<T> {
     }
     
     /// 验证数据源间偏差是否在允许范围内
+    ///
+    /// 偏差计算与链上喂价聚合共用 `oracle::max_spread_bps`
     fn validate_rate_deviation(rates: &[u64]) -> Result<(), &'static str> {
-        if rates.is_empty() {
-            return Ok(());
-        }
-        
-        let min_rate = *rates.iter().min().unwrap_or(&0);
-        let max_rate = *rates.iter().max().unwrap_or(&0);
-        
-        if min_rate == 0 {
-            return Err("存在无效汇率");
-        }
-        
-        // 计算偏差（基点）
-        let deviation_bps = ((max_rate - min_rate) as u128)
-            .saturating_mul(10000)
-            .checked_div(min_rate as u128)
-            .unwrap_or(0) as u64;
+        let deviation_bps = crate::oracle::max_spread_bps(rates)?;
         
         if deviation_bps > MAX_SOURCE_DEVIATION_BPS {
             log::error!(
-                "❌ 数据源偏差过大: {} bps > {} bps (min={}, max={})",
+                "❌ 数据源偏差过大: {} bps > {} bps",
                 deviation_bps,
                 MAX_SOURCE_DEVIATION_BPS,
-                min_rate,
-                max_rate
             );
             return Err("数据源偏差过大");
         }
@@ -307,22 +292,9 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
     
-    /// 计算中位数
+    /// 计算中位数（与链上喂价聚合共用 `oracle::median`）
     fn calculate_median(rates: &mut Vec<u64>) -> u64 {
-        if rates.is_empty() {
-            return 0;
-        }
-        
-        rates.sort();
-        let len = rates.len();
-        
-        if len % 2 == 0 {
-            // 偶数个，取中间两个的平均值
-            (rates[len / 2 - 1] + rates[len / 2]) / 2
-        } else {
-            // 奇数个，取中间值
-            rates[len / 2]
-        }
+        crate::oracle::median(rates)
     }
     
     /// 使用指定模式解析 CNY 汇率
@@ -0,0 +1,209 @@
+//! # 喂价预言机聚合算法
+//!
+//! 本模块只包含纯函数，供链上喂价轮次聚合与 OCW 多数据源聚合共用：
+//! - 中位数计算
+//! - 偏差计算（基点）
+//! - 异常报价剔除
+//! - 时间加权平均价格（TWAP）
+//!
+//! ## 版本历史
+//! - v0.1.0 (2026-10-17): 从 OCW 的 `calculate_median` / `validate_rate_deviation` 提取
+
+extern crate alloc;
+use alloc::vec::Vec;
+
+/// 函数级中文注释：计算中位数
+///
+/// 偶数个元素时取中间两个的平均值；空输入返回 0。
+pub fn median(values: &mut [u64]) -> u64 {
+    if values.is_empty() {
+        return 0;
+    }
+
+    values.sort_unstable();
+    let len = values.len();
+
+    if len % 2 == 0 {
+        // 偶数个，取中间两个的平均值（u128 防溢出）
+        ((values[len / 2 - 1] as u128 + values[len / 2] as u128) / 2) as u64
+    } else {
+        // 奇数个，取中间值
+        values[len / 2]
+    }
+}
+
+/// 函数级中文注释：计算 `value` 相对 `base` 的偏差（基点）
+///
+/// `base` 为 0 时返回 `u64::MAX`（视为无限偏差）。
+pub fn deviation_bps(value: u64, base: u64) -> u64 {
+    if base == 0 {
+        return u64::MAX;
+    }
+    let diff = value.abs_diff(base) as u128;
+    let bps = diff.saturating_mul(10_000) / base as u128;
+    bps.min(u64::MAX as u128) as u64
+}
+
+/// 函数级中文注释：计算一组报价的最大价差（基点，相对最小值）
+///
+/// ## 返回
+/// - `Ok(bps)`: 最大价差；空输入为 0
+/// - `Err`: 存在为 0 的无效报价
+pub fn max_spread_bps(values: &[u64]) -> Result<u64, &'static str> {
+    let (Some(min), Some(max)) = (values.iter().min(), values.iter().max()) else {
+        return Ok(0);
+    };
+    if *min == 0 {
+        return Err("存在无效汇率");
+    }
+    Ok(deviation_bps(*max, *min))
+}
+
+/// 函数级中文注释：单轮报价聚合结果
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct QuoteAggregation {
+    /// 剔除异常值后的中位数价格
+    pub price: u64,
+    /// 初始中位数（用于计算喂价人偏差）
+    pub reference: u64,
+    /// 每个报价是否被采纳（与输入顺序一致）
+    pub accepted: Vec<bool>,
+}
+
+impl QuoteAggregation {
+    /// 被采纳的报价数量
+    pub fn accepted_count(&self) -> u32 {
+        self.accepted.iter().filter(|a| **a).count() as u32
+    }
+}
+
+/// 函数级详细中文注释：聚合一轮报价（中位数 + 异常值剔除）
+///
+/// # 算法
+/// 1. 以全部报价的中位数作为参考价
+/// 2. 偏离参考价超过 `max_deviation_bps` 的报价视为异常，予以剔除
+/// 3. 对剩余报价重新取中位数作为本轮价格
+///
+/// # 返回
+/// - `Some(aggregation)`: 采纳的报价数 ≥ `min_quotes`
+/// - `None`: 报价不足，本轮作废
+pub fn aggregate_quotes(
+    quotes: &[u64],
+    max_deviation_bps: u64,
+    min_quotes: u32,
+) -> Option<QuoteAggregation> {
+    if quotes.is_empty() {
+        return None;
+    }
+
+    let reference = median(&mut quotes.to_vec());
+    let accepted: Vec<bool> = quotes
+        .iter()
+        .map(|q| *q > 0 && deviation_bps(*q, reference) <= max_deviation_bps)
+        .collect();
+
+    let mut kept: Vec<u64> = quotes
+        .iter()
+        .zip(accepted.iter())
+        .filter(|(_, ok)| **ok)
+        .map(|(q, _)| *q)
+        .collect();
+
+    if (kept.len() as u32) < min_quotes.max(1) {
+        return None;
+    }
+
+    Some(QuoteAggregation {
+        price: median(&mut kept),
+        reference,
+        accepted,
+    })
+}
+
+/// 函数级详细中文注释：计算时间加权平均价格（TWAP）
+///
+/// # 参数
+/// - `points`: `(区块, 价格)`，按区块升序排列；每个价格一直有效到下一个点
+/// - `now`: 当前区块
+/// - `window`: 窗口长度（区块数）
+///
+/// # 算法
+/// 窗口为 `[now - window, now]`。窗口开始前最后一个点从窗口起点开始计权，
+/// 之后每个点的权重为其有效区块数。窗口长度为 0（或所有点都在 `now`）时
+/// 返回最新价格。
+///
+/// # 返回
+/// - `None`: 没有任何价格点
+pub fn time_weighted_average(points: &[(u64, u64)], now: u64, window: u64) -> Option<u64> {
+    let (_, last_price) = *points.last()?;
+    let start = now.saturating_sub(window);
+
+    let mut weighted: u128 = 0;
+    let mut total_weight: u128 = 0;
+
+    for (i, (block, price)) in points.iter().enumerate() {
+        let end = points.get(i + 1).map(|(b, _)| *b).unwrap_or(now).min(now);
+        let seg_start = (*block).max(start);
+        if end <= seg_start {
+            continue;
+        }
+        let weight = (end - seg_start) as u128;
+        weighted = weighted.saturating_add((*price as u128).saturating_mul(weight));
+        total_weight = total_weight.saturating_add(weight);
+    }
+
+    if total_weight == 0 {
+        return Some(last_price);
+    }
+    Some((weighted / total_weight).min(u64::MAX as u128) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn median_handles_odd_and_even() {
+        assert_eq!(median(&mut []), 0);
+        assert_eq!(median(&mut [3, 1, 2]), 2);
+        assert_eq!(median(&mut [4, 1, 3, 2]), 2);
+        assert_eq!(median(&mut [u64::MAX, u64::MAX]), u64::MAX);
+    }
+
+    #[test]
+    fn deviation_and_spread() {
+        assert_eq!(deviation_bps(105, 100), 500);
+        assert_eq!(deviation_bps(95, 100), 500);
+        assert_eq!(deviation_bps(1, 0), u64::MAX);
+        assert_eq!(max_spread_bps(&[]), Ok(0));
+        assert_eq!(max_spread_bps(&[100, 104, 102]), Ok(400));
+        assert!(max_spread_bps(&[0, 100]).is_err());
+    }
+
+    #[test]
+    fn aggregate_rejects_outliers() {
+        // 参考中位数 101，200 偏离过大被剔除
+        let agg = aggregate_quotes(&[100, 101, 102, 200], 500, 3).unwrap();
+        assert_eq!(agg.accepted, vec![true, true, true, false]);
+        assert_eq!(agg.accepted_count(), 3);
+        assert_eq!(agg.price, 101);
+
+        // 剔除后不足最少报价数
+        assert!(aggregate_quotes(&[100, 200, 400], 500, 2).is_none());
+        assert!(aggregate_quotes(&[], 500, 1).is_none());
+    }
+
+    #[test]
+    fn twap_weights_by_duration() {
+        let points = [(10, 100), (20, 200)];
+        // 窗口 [10, 30]: 100 × 10 + 200 × 10
+        assert_eq!(time_weighted_average(&points, 30, 20), Some(150));
+        // 窗口 [25, 30]: 仅 200 有效
+        assert_eq!(time_weighted_average(&points, 30, 5), Some(200));
+        // 窗口 [0, 30]: 窗口起点早于首个点，只按实际覆盖区块计权
+        assert_eq!(time_weighted_average(&points, 30, 30), Some(150));
+        // 零窗口返回最新价格
+        assert_eq!(time_weighted_average(&points, 20, 0), Some(200));
+        assert_eq!(time_weighted_average(&[], 20, 10), None);
+    }
+}
@@ -282,3 +282,196 @@ fn check_price_deviation_no_base_price() {
     });
 }
 
+
+// ==================== 喂价预言机测试 (5个) ====================
+
+use crate::{OracleFeed, OraclePrices, OracleRounds};
+use frame_support::traits::Hooks;
+
+/// 函数级中文注释：推进到指定区块并执行 on_initialize（轮次结算）
+fn run_to_block(n: u64) {
+    while System::block_number() < n {
+        let next = System::block_number() + 1;
+        System::set_block_number(next);
+        Pricing::on_initialize(next);
+    }
+}
+
+/// 函数级中文注释：注册喂价人 1..=n
+fn register_feeders(n: u64) {
+    for feeder in 1..=n {
+        assert_ok!(Pricing::add_oracle_feeder(RuntimeOrigin::root(), feeder));
+    }
+}
+
+/// Test 11: 喂价人管理
+#[test]
+fn oracle_feeder_management_works() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        assert_noop!(
+            Pricing::add_oracle_feeder(RuntimeOrigin::signed(1), 1),
+            sp_runtime::DispatchError::BadOrigin
+        );
+
+        register_feeders(5);
+        assert_noop!(
+            Pricing::add_oracle_feeder(RuntimeOrigin::root(), 1),
+            Error::<Test>::FeederAlreadyRegistered
+        );
+        assert_noop!(
+            Pricing::add_oracle_feeder(RuntimeOrigin::root(), 6),
+            Error::<Test>::TooManyFeeders
+        );
+
+        assert_ok!(Pricing::remove_oracle_feeder(RuntimeOrigin::root(), 5));
+        assert_eq!(Pricing::oracle_feeders().to_vec(), vec![1, 2, 3, 4]);
+        assert_noop!(
+            Pricing::remove_oracle_feeder(RuntimeOrigin::root(), 5),
+            Error::<Test>::NotOracleFeeder
+        );
+        System::assert_last_event(Event::OracleFeederRemoved { feeder: 5 }.into());
+    });
+}
+
+/// Test 12: 报价校验（非喂价人、错误轮次、重复报价、超范围汇率）
+#[test]
+fn submit_oracle_quote_validates() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        register_feeders(2);
+
+        assert_noop!(
+            Pricing::submit_oracle_quote(RuntimeOrigin::signed(9), OracleFeed::NxsUsdt, 0, USDT),
+            Error::<Test>::NotOracleFeeder
+        );
+        assert_noop!(
+            Pricing::submit_oracle_quote(RuntimeOrigin::signed(1), OracleFeed::NxsUsdt, 1, USDT),
+            Error::<Test>::WrongOracleRound
+        );
+        assert_noop!(
+            Pricing::submit_oracle_quote(RuntimeOrigin::signed(1), OracleFeed::CnyUsdt, 0, 12 * USDT),
+            Error::<Test>::InvalidPrice
+        );
+
+        assert_ok!(Pricing::submit_oracle_quote(RuntimeOrigin::signed(1), OracleFeed::NxsUsdt, 0, USDT));
+        assert_noop!(
+            Pricing::submit_oracle_quote(RuntimeOrigin::signed(1), OracleFeed::NxsUsdt, 0, USDT),
+            Error::<Test>::DuplicateOracleQuote
+        );
+        // 不同喂价对互不影响
+        assert_ok!(Pricing::submit_oracle_quote(RuntimeOrigin::signed(1), OracleFeed::CnyUsdt, 0, 7_200_000));
+        assert_eq!(OracleRounds::<Test>::get(OracleFeed::NxsUsdt).unwrap().quotes.len(), 1);
+    });
+}
+
+/// Test 13: 轮次结算 - 中位数聚合、异常值剔除与喂价人准确度
+#[test]
+fn oracle_round_aggregates_and_rejects_outliers() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        register_feeders(4);
+
+        let quotes = [100_000u64, 101_000, 102_000, 150_000];
+        for (i, price) in quotes.iter().enumerate() {
+            assert_ok!(Pricing::submit_oracle_quote(
+                RuntimeOrigin::signed(i as u64 + 1),
+                OracleFeed::NxsUsdt,
+                0,
+                *price
+            ));
+        }
+
+        // 区块 10 进入第 1 轮，结算第 0 轮
+        run_to_block(10);
+        assert!(OracleRounds::<Test>::get(OracleFeed::NxsUsdt).is_none());
+
+        let aggregated = OraclePrices::<Test>::get(OracleFeed::NxsUsdt).unwrap();
+        assert_eq!(aggregated.price, 101_000);
+        assert_eq!(aggregated.round, 0);
+        assert_eq!(aggregated.quotes, 3);
+        System::assert_has_event(
+            Event::OraclePriceAggregated {
+                feed: OracleFeed::NxsUsdt,
+                round: 0,
+                price: 101_000,
+                accepted: 3,
+                rejected: 1,
+            }
+            .into(),
+        );
+
+        // 参考中位数 101_500：喂价人 4 偏离约 47.8%，被剔除
+        let outlier = Pricing::feeder_stats(4);
+        assert_eq!((outlier.accepted, outlier.rejected), (0, 1));
+        assert!(outlier.average_deviation_bps() > 4_000);
+        let honest = Pricing::feeder_stats(2);
+        assert_eq!((honest.accepted, honest.rejected, honest.last_round), (1, 0, 0));
+        assert!(honest.average_deviation_bps() < 100);
+    });
+}
+
+/// Test 14: 有效报价不足时本轮作废，保留上一轮价格
+#[test]
+fn oracle_round_fails_without_quorum() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        register_feeders(2);
+
+        assert_ok!(Pricing::submit_oracle_quote(RuntimeOrigin::signed(1), OracleFeed::CnyUsdt, 0, 7_200_000));
+        run_to_block(10);
+
+        assert!(OraclePrices::<Test>::get(OracleFeed::CnyUsdt).is_none());
+        System::assert_has_event(
+            Event::OracleRoundFailed { feed: OracleFeed::CnyUsdt, round: 0, quotes: 1 }.into(),
+        );
+        assert_eq!(Pricing::feeder_stats(1).rejected, 1);
+        // 未聚合时仍使用默认汇率
+        assert_ok!(Pricing::set_oracle_enabled(RuntimeOrigin::root(), true));
+        assert_eq!(Pricing::get_cny_usdt_rate(), 7_200_000);
+    });
+}
+
+/// Test 15: 预言机模式下消费方使用 TWAP，过期后回退到市场价格
+#[test]
+fn oracle_mode_serves_twap_and_falls_back_when_stale() {
+    new_test_ext().execute_with(|| {
+        System::set_block_number(1);
+        crate::ColdStartExited::<Test>::put(true);
+        register_feeders(2);
+        assert_ok!(Pricing::add_buy_trade(1000, 50 * USDT, 100 * NXS));
+
+        // 第 0 轮 1 USDT，第 1 轮 2 USDT
+        for feeder in 1..=2 {
+            assert_ok!(Pricing::submit_oracle_quote(RuntimeOrigin::signed(feeder), OracleFeed::NxsUsdt, 0, USDT));
+        }
+        run_to_block(10);
+        for feeder in 1..=2 {
+            assert_ok!(Pricing::submit_oracle_quote(RuntimeOrigin::signed(feeder), OracleFeed::NxsUsdt, 1, 2 * USDT));
+        }
+        run_to_block(20);
+
+        // 未启用预言机模式：仍为 P2P 成交加权均价
+        assert_eq!(Pricing::get_nxs_usdt_price(), 50 * USDT);
+
+        assert_ok!(Pricing::set_oracle_enabled(RuntimeOrigin::root(), true));
+        run_to_block(30);
+        // 窗口 [10, 30]：1 USDT × 10 + 2 USDT × 10
+        assert_eq!(Pricing::get_twap(OracleFeed::NxsUsdt, 20), Some(1_500_000));
+        assert_eq!(Pricing::get_nxs_usdt_price(), 1_500_000);
+        assert_eq!(Pricing::get_twap(OracleFeed::NxsUsdt, 5), Some(2 * USDT));
+
+        assert_noop!(
+            Pricing::set_oracle_twap_window(RuntimeOrigin::root(), 0),
+            Error::<Test>::InvalidTwapWindow
+        );
+        assert_ok!(Pricing::set_oracle_twap_window(RuntimeOrigin::root(), 5));
+        assert_eq!(Pricing::get_nxs_usdt_price(), 2 * USDT);
+        // 基准价格随之切换为预言机价格
+        assert_ok!(Pricing::check_price_deviation(2 * USDT));
+
+        // 超过 ORACLE_MAX_STALE_ROUNDS 轮未更新，回退到市场价格
+        run_to_block(51);
+        assert_eq!(Pricing::get_nxs_usdt_price(), 50 * USDT);
+    });
+}
@@ -9,6 +9,11 @@ pub trait WeightInfo {
     fn set_cold_start_params() -> Weight;
     fn reset_cold_start() -> Weight;
     fn ocw_submit_exchange_rate() -> Weight;
+    fn add_oracle_feeder() -> Weight;
+    fn remove_oracle_feeder() -> Weight;
+    fn submit_oracle_quote() -> Weight;
+    fn set_oracle_enabled() -> Weight;
+    fn set_oracle_twap_window() -> Weight;
 }
 
 /// Substrate 权重实现
@@ -29,6 +34,29 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(2))
             .saturating_add(T::DbWeight::get().writes(2))
     }
+    fn add_oracle_feeder() -> Weight {
+        Weight::from_parts(20_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn remove_oracle_feeder() -> Weight {
+        Weight::from_parts(20_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(1))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn submit_oracle_quote() -> Weight {
+        Weight::from_parts(35_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().writes(2))
+    }
+    fn set_oracle_enabled() -> Weight {
+        Weight::from_parts(10_000_000, 0)
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    fn set_oracle_twap_window() -> Weight {
+        Weight::from_parts(10_000_000, 0)
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
 }
 
 /// 默认权重实现（用于测试）
@@ -36,4 +64,9 @@ impl WeightInfo for () {
     fn set_cold_start_params() -> Weight { Weight::from_parts(25_000_000, 0) }
     fn reset_cold_start() -> Weight { Weight::from_parts(20_000_000, 0) }
     fn ocw_submit_exchange_rate() -> Weight { Weight::from_parts(30_000_000, 0) }
+    fn add_oracle_feeder() -> Weight { Weight::from_parts(20_000_000, 0) }
+    fn remove_oracle_feeder() -> Weight { Weight::from_parts(20_000_000, 0) }
+    fn submit_oracle_quote() -> Weight { Weight::from_parts(35_000_000, 0) }
+    fn set_oracle_enabled() -> Weight { Weight::from_parts(10_000_000, 0) }
+    fn set_oracle_twap_window() -> Weight { Weight::from_parts(10_000_000, 0) }
 }
@@ -0,0 +1,181 @@
+//! # TRON 地址工具
+//!
+//! TRON 地址有三种常见表示：
+//! - Base58Check 字符串（`T...`，34 字符），链上 `TronAddress` 存储的就是它
+//! - 21 字节十六进制（`41` 前缀 + 20 字节），TronGrid / 全节点 API 使用
+//! - 20 字节十六进制（EVM 风格），出现在 TRC20 `Transfer` 事件 topics 中
+//!
+//! EVM 链地址（`0x` + 40 位十六进制）同样解析为 20 字节形式参与比对。
+//!
+//! 各数据源解析器通过 [`AddressForms`] 统一比对，避免逐个解析器处理格式差异。
+
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::bytes_to_hex;
+
+/// Base58 字母表（Bitcoin 风格）
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// TRON 主网地址前缀字节
+pub const TRON_ADDRESS_PREFIX: u8 = 0x41;
+
+/// Base58 解码
+pub fn base58_decode(input: &[u8]) -> Option<Vec<u8>> {
+    let mut bytes: Vec<u8> = Vec::new();
+
+    for &c in input {
+        let mut carry = BASE58_ALPHABET.iter().position(|&a| a == c)? as u32;
+        for b in bytes.iter_mut().rev() {
+            carry += (*b as u32) * 58;
+            *b = (carry & 0xff) as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.insert(0, (carry & 0xff) as u8);
+            carry >>= 8;
+        }
+    }
+
+    let leading_zeros = input.iter().take_while(|&&c| c == b'1').count();
+    let mut out = alloc::vec![0u8; leading_zeros];
+    out.extend(bytes);
+    Some(out)
+}
+
+/// 解码 TRON Base58Check 地址为 21 字节（`0x41` + 20 字节）
+///
+/// 校验和不匹配或前缀错误时返回 `None`。
+pub fn decode_tron_address(address: &[u8]) -> Option<[u8; 21]> {
+    let raw = base58_decode(address)?;
+    if raw.len() != 25 || raw[0] != TRON_ADDRESS_PREFIX {
+        return None;
+    }
+
+    let (payload, checksum) = raw.split_at(21);
+    let digest = sp_io::hashing::sha2_256(&sp_io::hashing::sha2_256(payload));
+    if digest[..4] != checksum[..] {
+        return None;
+    }
+
+    let mut out = [0u8; 21];
+    out.copy_from_slice(payload);
+    Some(out)
+}
+
+/// 解析 EVM 地址（`0x` + 40 位十六进制），返回小写 20 字节十六进制
+pub fn evm_hex20(address: &[u8]) -> Option<String> {
+    let hex = address.strip_prefix(b"0x")?;
+    if hex.len() != 40 || !hex.iter().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    core::str::from_utf8(hex).ok().map(|h| h.to_ascii_lowercase())
+}
+
+/// 同一地址的各种表示（全部小写十六进制）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AddressForms {
+    /// Base58Check 原文（若输入是合法 UTF-8）
+    pub base58: Option<String>,
+    /// 21 字节十六进制（`41...`）
+    pub hex21: Option<String>,
+    /// 20 字节十六进制
+    pub hex20: Option<String>,
+    /// 原始字节的十六进制（兼容直接以字节存储的地址）
+    pub raw_hex: String,
+}
+
+impl AddressForms {
+    /// 从链上存储的地址字节构建
+    pub fn new(address: &[u8]) -> Self {
+        let decoded = decode_tron_address(address);
+        Self {
+            base58: core::str::from_utf8(address).ok().map(String::from),
+            hex21: decoded.map(|d| bytes_to_hex(&d)),
+            hex20: decoded
+                .map(|d| bytes_to_hex(&d[1..]))
+                .or_else(|| evm_hex20(address)),
+            raw_hex: bytes_to_hex(address),
+        }
+    }
+
+    /// 判断 API 返回的地址字符串是否为同一地址
+    ///
+    /// 支持 Base58、`41` 前缀十六进制、`0x` 前缀 20 字节十六进制、
+    /// 以及 32 字节左补零的事件 topic。
+    pub fn matches(&self, candidate: &str) -> bool {
+        if self.base58.as_deref() == Some(candidate) {
+            return true;
+        }
+
+        let lower = candidate.trim_start_matches("0x").to_ascii_lowercase();
+        if lower == self.raw_hex {
+            return true;
+        }
+        if let Some(ref hex21) = self.hex21 {
+            if &lower == hex21 {
+                return true;
+            }
+        }
+        if let Some(ref hex20) = self.hex20 {
+            // 20 字节，或 32 字节 topic 的末尾 20 字节
+            if lower.len() >= 40 && lower.ends_with(hex20.as_str())
+                && lower[..lower.len() - 40].bytes().all(|c| c == b'0')
+            {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// 判断原始响应文本中是否出现该地址的任一表示
+    pub fn appears_in(&self, text: &str) -> bool {
+        let lower = text.to_ascii_lowercase();
+        self.base58.as_deref().map_or(false, |b| text.contains(b))
+            || lower.contains(&self.raw_hex)
+            || self.hex21.as_deref().map_or(false, |h| lower.contains(h))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 官方 USDT 合约
+    const USDT: &[u8] = b"TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t";
+
+    #[test]
+    fn decode_usdt_contract_address() {
+        let decoded = decode_tron_address(USDT).unwrap();
+        assert_eq!(bytes_to_hex(&decoded), "41a614f803b6fd780986a42c78ec9c7f77e6ded13c");
+    }
+
+    #[test]
+    fn decode_rejects_bad_checksum_and_alphabet() {
+        assert!(decode_tron_address(b"TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6u").is_none());
+        assert!(decode_tron_address(b"T0OIl").is_none());
+        assert!(decode_tron_address(&[0x41u8; 34]).is_none());
+    }
+
+    #[test]
+    fn address_forms_match_all_representations() {
+        let forms = AddressForms::new(USDT);
+        assert!(forms.matches("TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t"));
+        assert!(forms.matches("41A614F803B6FD780986A42C78EC9C7F77E6DED13C"));
+        assert!(forms.matches("0xa614f803b6fd780986a42c78ec9c7f77e6ded13c"));
+        assert!(forms.matches("000000000000000000000000a614f803b6fd780986a42c78ec9c7f77e6ded13c"));
+        assert!(!forms.matches("ffffffffffffffffffffffffa614f803b6fd780986a42c78ec9c7f77e6ded13c"));
+        assert!(!forms.matches("TA4Y62o6YC2Zsck9rZVGTvqW1AQ7X9zTnj"));
+    }
+
+    #[test]
+    fn evm_address_matches_topic() {
+        let forms = AddressForms::new(b"0xdAC17F958D2ee523a2206206994597C13D831ec7");
+        assert_eq!(forms.hex20.as_deref(), Some("dac17f958d2ee523a2206206994597c13d831ec7"));
+        assert!(forms.matches("0x000000000000000000000000dac17f958d2ee523a2206206994597c13d831ec7"));
+        assert!(forms.matches("0xDAC17F958D2EE523A2206206994597C13D831EC7"));
+        assert!(evm_hex20(b"0x1234").is_none());
+    }
+}
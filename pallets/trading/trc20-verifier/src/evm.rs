@@ -0,0 +1,287 @@
+//! # EVM 链稳定币转账验证
+//!
+//! 通过 JSON-RPC `eth_getTransactionReceipt` 读取交易回执，解码代币合约的
+//! `Transfer(address,address,uint256)` 事件，并用 `eth_blockNumber` 计算确认数。
+//! 适用于 Ethereum（ERC20）、BNB Smart Chain（BEP20）等 EVM 兼容链。
+//!
+//! ## 结论约定（与 TRON 数据源一致）
+//! - 回执为 `null`（未打包）或确认数不足 → `Err`，调用方稍后重试
+//! - `status = 0x0`、无匹配 Transfer 事件、金额不足 → `Ok` 且 `is_valid = false`
+//!
+//! ## 端点
+//! 每条链的 RPC 端点列表独立存储在 OCW 本地存储中，按健康评分串行故障转移。
+
+extern crate alloc;
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use codec::{Decode, Encode};
+use lite_json::json::JsonValue;
+use sp_core::offchain::StorageKind;
+
+use crate::address::{evm_hex20, AddressForms};
+use crate::source::{
+    conclude_amount, field, find_transfer_log, hex_to_u128, new_result, parse_json, string_field,
+    ExpectedTransfer, HttpMethod, SourceRequest,
+};
+use crate::{bytes_to_hex, fetch, get_endpoint_health, record_endpoint_failure, record_endpoint_success,
+    current_timestamp_ms, TronTxVerification};
+
+/// 默认 EVM RPC 端点（链 ID, URL）
+pub const DEFAULT_EVM_ENDPOINTS: &[(u64, &str)] = &[
+    (1, "https://ethereum-rpc.publicnode.com"),
+    (1, "https://eth.llamarpc.com"),
+    (56, "https://bsc-dataseed.bnbchain.org"),
+    (56, "https://bsc-rpc.publicnode.com"),
+];
+
+/// EVM 端点配置存储键前缀（后接链 ID 小端字节）
+const EVM_ENDPOINTS_PREFIX: &[u8] = b"ocw_evm_endpoints::";
+
+// ==================== 端点配置 ====================
+
+fn evm_endpoints_key(chain_id: u64) -> Vec<u8> {
+    let mut key = EVM_ENDPOINTS_PREFIX.to_vec();
+    key.extend_from_slice(&chain_id.to_le_bytes());
+    key
+}
+
+/// 获取指定链的 RPC 端点（未配置时使用默认列表）
+pub fn get_evm_endpoints(chain_id: u64) -> Vec<String> {
+    sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, &evm_endpoints_key(chain_id))
+        .and_then(|data| Vec::<String>::decode(&mut &data[..]).ok())
+        .unwrap_or_else(|| {
+            DEFAULT_EVM_ENDPOINTS
+                .iter()
+                .filter(|(id, _)| *id == chain_id)
+                .map(|(_, url)| String::from(*url))
+                .collect()
+        })
+}
+
+/// 设置指定链的 RPC 端点（例如指向自建节点）
+pub fn set_evm_endpoints(chain_id: u64, endpoints: Vec<String>) {
+    sp_io::offchain::local_storage_set(
+        StorageKind::PERSISTENT,
+        &evm_endpoints_key(chain_id),
+        &endpoints.encode(),
+    );
+    log::info!(target: "trc20-verifier", "Set {} EVM endpoints for chain {}", endpoints.len(), chain_id);
+}
+
+/// 按健康评分排序的 RPC 端点
+fn get_sorted_evm_endpoints(chain_id: u64) -> Vec<String> {
+    let mut endpoints: Vec<(String, u32)> = get_evm_endpoints(chain_id)
+        .into_iter()
+        .map(|e| {
+            let score = get_endpoint_health(&e).score;
+            (e, score)
+        })
+        .collect();
+    endpoints.sort_by(|a, b| b.1.cmp(&a.1));
+    endpoints.into_iter().map(|(e, _)| e).collect()
+}
+
+// ==================== 请求构建 ====================
+
+fn json_rpc_request(endpoint: &str, method: &str, params: &str) -> SourceRequest {
+    SourceRequest {
+        method: HttpMethod::Post,
+        url: String::from(endpoint),
+        body: format!(r#"{{"jsonrpc":"2.0","id":1,"method":"{}","params":[{}]}}"#, method, params)
+            .into_bytes(),
+        headers: alloc::vec![("Content-Type".to_string(), "application/json".to_string())],
+    }
+}
+
+/// `eth_getTransactionReceipt` 请求
+pub fn receipt_request(endpoint: &str, tx_hash_hex: &str) -> SourceRequest {
+    json_rpc_request(endpoint, "eth_getTransactionReceipt", &format!(r#""0x{}""#, tx_hash_hex))
+}
+
+/// `eth_blockNumber` 请求
+pub fn block_number_request(endpoint: &str) -> SourceRequest {
+    json_rpc_request(endpoint, "eth_blockNumber", "")
+}
+
+// ==================== 响应解析 ====================
+
+/// 读取 JSON-RPC `result` 字段，RPC 错误返回 Err
+fn rpc_result(json: &JsonValue) -> Result<&JsonValue, &'static str> {
+    if field(json, "error").is_some() {
+        return Err("JSON-RPC error");
+    }
+    field(json, "result").ok_or("Unrecognized JSON-RPC response")
+}
+
+/// 解析 `eth_blockNumber` 响应
+pub fn parse_block_number(response: &[u8]) -> Result<u64, &'static str> {
+    let json = parse_json(response)?;
+    match rpc_result(&json)? {
+        JsonValue::String(chars) => {
+            let hex: String = chars.iter().collect();
+            hex_to_u128(&hex)
+                .and_then(|n| u64::try_from(n).ok())
+                .ok_or("Invalid block number")
+        },
+        _ => Err("Invalid block number"),
+    }
+}
+
+/// 解析 `eth_getTransactionReceipt` 响应，返回验证结果与交易所在区块
+pub fn parse_receipt(
+    response: &[u8],
+    expected: &ExpectedTransfer,
+) -> Result<(TronTxVerification, u64), &'static str> {
+    let json = parse_json(response)?;
+    let receipt = match rpc_result(&json)? {
+        JsonValue::Null => return Err("Transaction not found"),
+        receipt @ JsonValue::Object(_) => receipt,
+        _ => return Err("Unrecognized JSON-RPC response"),
+    };
+
+    let block = string_field(receipt, "blockNumber")
+        .and_then(|b| hex_to_u128(&b))
+        .and_then(|b| u64::try_from(b).ok())
+        .ok_or("Receipt missing block number")?;
+
+    let mut result = new_result(expected.amount);
+    if string_field(receipt, "status").as_deref() != Some("0x1") {
+        result.error = Some(b"Transaction not successful".to_vec());
+        return Ok((result, block));
+    }
+
+    let token_hex20 = evm_hex20(expected.token_contract).ok_or("Invalid token contract")?;
+    let logs = match field(receipt, "logs") {
+        Some(JsonValue::Array(items)) => items.as_slice(),
+        _ => &[][..],
+    };
+    let Some(transfer) = find_transfer_log(logs, &token_hex20, &AddressForms::new(expected.to)) else {
+        result.error = Some(b"Recipient address mismatch".to_vec());
+        return Ok((result, block));
+    };
+
+    result.from_address = Some(transfer.from.into_bytes());
+    result.to_address = Some(transfer.to.into_bytes());
+    let actual_amount = transfer.raw_amount.and_then(|raw| expected.normalize_amount(raw));
+    Ok((conclude_amount(result, expected.amount, actual_amount), block))
+}
+
+// ==================== 验证入口 ====================
+
+/// 验证 EVM 链上的代币转账
+///
+/// 依次尝试该链的 RPC 端点；回执存在后再查询最新区块，
+/// 确认数达到 `expected.min_confirmations` 才给出结论。
+pub fn verify_evm_transfer(
+    chain_id: u64,
+    tx_hash: &[u8],
+    expected: &ExpectedTransfer,
+) -> Result<TronTxVerification, &'static str> {
+    let tx_hash_hex = bytes_to_hex(tx_hash);
+    let endpoints = get_sorted_evm_endpoints(chain_id);
+    let mut last_error = "No endpoints available";
+
+    for endpoint in endpoints.iter() {
+        let start_time = current_timestamp_ms();
+        let verdict = fetch(&receipt_request(endpoint, &tx_hash_hex))
+            .and_then(|body| parse_receipt(&body, expected))
+            .and_then(|(result, block)| {
+                let head = parse_block_number(&fetch(&block_number_request(endpoint))?)?;
+                let confirmations = head.saturating_sub(block).saturating_add(1);
+                if confirmations < expected.min_confirmations as u64 {
+                    log::debug!(target: "trc20-verifier", "Chain {} tx has {} confirmations, need {}",
+                        chain_id, confirmations, expected.min_confirmations);
+                    return Err("Insufficient confirmations");
+                }
+                Ok(TronTxVerification {
+                    confirmations: confirmations.min(u32::MAX as u64) as u32,
+                    ..result
+                })
+            });
+
+        match verdict {
+            Ok(mut result) => {
+                record_endpoint_success(endpoint, (current_timestamp_ms() - start_time) as u32);
+                result.tx_hash = tx_hash.to_vec();
+                return Ok(result);
+            },
+            // 端点可用但暂无结论：记为成功，继续尝试其他端点（可能索引更快）
+            Err(e @ ("Transaction not found" | "Insufficient confirmations")) => {
+                record_endpoint_success(endpoint, (current_timestamp_ms() - start_time) as u32);
+                last_error = e;
+            },
+            Err(e) => {
+                record_endpoint_failure(endpoint);
+                log::warn!(target: "trc20-verifier", "EVM endpoint {} failed: {}", endpoint, e);
+                last_error = e;
+            },
+        }
+    }
+
+    Err(last_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::replay;
+
+    const TOKEN: &[u8] = crate::USDT_BEP20_CONTRACT.as_bytes();
+    const TO: &[u8] = b"0x1111111111111111111111111111111111111111";
+
+    fn bep20(amount: u64) -> ExpectedTransfer<'static> {
+        ExpectedTransfer { token_contract: TOKEN, decimals: 18, min_confirmations: 15, to: TO, amount }
+    }
+
+    #[test]
+    fn parse_receipt_decodes_18_decimal_transfer() {
+        let receipt = replay::evm_receipt(TOKEN, TO, 25 * 10u128.pow(18), 100);
+        let (result, block) = parse_receipt(receipt.as_bytes(), &bep20(25_000_000)).unwrap();
+        assert_eq!(block, 100);
+        assert!(result.is_valid);
+        assert_eq!(result.actual_amount, Some(25_000_000));
+    }
+
+    #[test]
+    fn parse_receipt_outcomes() {
+        assert_eq!(
+            parse_receipt(replay::EVM_RECEIPT_NOT_FOUND.as_bytes(), &bep20(1)).map(|(r, _)| r),
+            Err("Transaction not found")
+        );
+
+        let reverted = replay::evm_receipt(TOKEN, TO, 10u128.pow(18), 100).replace(r#""status":"0x1""#, r#""status":"0x0""#);
+        let (result, _) = parse_receipt(reverted.as_bytes(), &bep20(1_000_000)).unwrap();
+        assert_eq!(result.error, Some(b"Transaction not successful".to_vec()));
+
+        let other = replay::evm_receipt(TOKEN, b"0x2222222222222222222222222222222222222222", 10u128.pow(18), 100);
+        let (result, _) = parse_receipt(other.as_bytes(), &bep20(1_000_000)).unwrap();
+        assert_eq!(result.error, Some(b"Recipient address mismatch".to_vec()));
+
+        assert_eq!(parse_block_number(replay::evm_block_number(0x1234).as_bytes()), Ok(0x1234));
+    }
+
+    #[test]
+    fn verify_evm_transfer_requires_confirmations() {
+        let (offchain, state) = sp_core::offchain::testing::TestOffchainExt::new();
+        let mut ext = sp_io::TestExternalities::default();
+        ext.register_extension(sp_core::offchain::OffchainDbExt::new(offchain.clone()));
+        ext.register_extension(sp_core::offchain::OffchainWorkerExt::new(offchain));
+
+        ext.execute_with(|| {
+            let endpoint = "http://127.0.0.1:8545";
+            set_evm_endpoints(56, alloc::vec![endpoint.into()]);
+            let receipt = replay::evm_receipt(TOKEN, TO, 10u128.pow(18), 100);
+
+            // 仅 5 个确认
+            replay::expect_evm_lookup(&mut state.write(), endpoint, &[0xCD; 32], &receipt, Some(104));
+            assert_eq!(verify_evm_transfer(56, &[0xCD; 32], &bep20(1_000_000)), Err("Insufficient confirmations"));
+
+            replay::expect_evm_lookup(&mut state.write(), endpoint, &[0xCD; 32], &receipt, Some(114));
+            let result = verify_evm_transfer(56, &[0xCD; 32], &bep20(1_000_000)).unwrap();
+            assert!(result.is_valid);
+            assert_eq!(result.confirmations, 15);
+        });
+    }
+}
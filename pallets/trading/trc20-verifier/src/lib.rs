@@ -3,44 +3,69 @@
 //! # TRC20 交易验证共享库
 //!
 //! ## 概述
-//! 共享 TRC20 验证逻辑。
+//! 共享稳定币转账验证逻辑（TRON TRC20 与 EVM ERC20/BEP20）。
 //! 可被 `pallet-trading-p2p`、`pallet-entity-market` 等模块复用。
 //!
 //! ## 功能
-//! - TronGrid API 调用验证 TRC20 交易
+//! - 可插拔数据源（TronGrid / TronScan / 自建全节点），各自独立解析
 //! - 端点健康评分与动态排序
 //! - 并行请求竞速模式
 //! - 金额匹配状态判定
+//! - 多代币（`ExpectedTransfer` 指定合约与精度）与 EVM 链（`eth_getTransactionReceipt`）
 //!
 //! ## 版本历史
 //! - v0.1.0 (2026-02-08): 提取为共享库
+//! - v0.2.0 (2026-10-17): `TronDataSource` 数据源抽象、全节点支持、录制响应回放测试工具
+//! - v0.3.0 (2026-10-17): 多代币 / 多链验证（USDC-TRC20、USDT-ERC20/BEP20），EVM 回执验证
 
 extern crate alloc;
 
 use alloc::vec::Vec;
-use alloc::string::{String, ToString};
+use alloc::string::String;
 use alloc::format;
 use sp_runtime::offchain::{http, Duration};
 use sp_core::offchain::StorageKind;
 use codec::{Encode, Decode};
 
+pub mod address;
+pub mod evm;
+pub mod source;
+#[cfg(feature = "std")]
+pub mod replay;
+
+pub use address::{decode_tron_address, evm_hex20, AddressForms};
+pub use evm::{get_evm_endpoints, set_evm_endpoints, verify_evm_transfer, DEFAULT_EVM_ENDPOINTS};
+pub use source::{
+    apply_confirmations, judge_amount, normalize_amount, parse_now_block, ExpectedTransfer,
+    HttpMethod, SourceRequest, TronDataSource, TronSourceConfig, TronSourceKind, AMOUNT_DECIMALS,
+};
+
 // ==================== 常量配置 ====================
 
-/// 默认 TRON API 端点列表（按优先级排序）
+/// 默认 TRON 数据源列表（按优先级排序）
 ///
 /// ⚠️ 注意：所有端点必须是主网端点，不能使用测试网！
-pub const DEFAULT_ENDPOINTS: &[&str] = &[
-    "https://api.trongrid.io",         // TronGrid 官方
-    "https://api.tronstack.io",        // TronStack 第三方
-    "https://apilist.tronscanapi.com", // TronScan
+pub const DEFAULT_ENDPOINTS: &[(TronSourceKind, &str)] = &[
+    (TronSourceKind::TronGrid, "https://api.trongrid.io"),         // TronGrid 官方
+    (TronSourceKind::TronGrid, "https://api.tronstack.io"),        // TronStack 第三方（TronGrid 兼容）
+    (TronSourceKind::TronScan, "https://apilist.tronscanapi.com"), // TronScan
 ];
 
-/// 主端点（用于 URL 构建）
+/// TronGrid 官方主网端点
 pub const TRONGRID_MAINNET: &str = "https://api.trongrid.io";
 
 /// 官方 USDT TRC20 合约地址 (Mainnet)
 pub const USDT_CONTRACT: &str = "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t";
 
+/// 官方 USDC TRC20 合约地址 (Mainnet)
+pub const USDC_TRC20_CONTRACT: &str = "TEkxiTehnzSmSe2XqrBj4w32RUN966rdz8";
+
+/// 官方 USDT ERC20 合约地址（Ethereum 主网，6 位精度）
+pub const USDT_ERC20_CONTRACT: &str = "0xdAC17F958D2ee523a2206206994597C13D831ec7";
+
+/// 官方 USDT BEP20 合约地址（BNB Smart Chain，18 位精度）
+pub const USDT_BEP20_CONTRACT: &str = "0x55d398326f99059fF775485246999027B3197955";
+
 /// HTTP 请求超时（毫秒）- 串行模式
 pub const HTTP_TIMEOUT_MS: u64 = 10_000;
 
@@ -157,8 +182,8 @@ fn save_endpoint_health(endpoint: &str, health: &EndpointHealth) {
 /// 端点配置
 #[derive(Debug, Clone, Encode, Decode)]
 pub struct EndpointConfig {
-    /// 端点 URL 列表
-    pub endpoints: Vec<String>,
+    /// 数据源列表
+    pub sources: Vec<TronSourceConfig>,
     /// 是否启用并行竞速模式
     pub parallel_mode: bool,
     /// 最后更新时间
@@ -168,7 +193,10 @@ pub struct EndpointConfig {
 impl Default for EndpointConfig {
     fn default() -> Self {
         Self {
-            endpoints: DEFAULT_ENDPOINTS.iter().map(|s| String::from(*s)).collect(),
+            sources: DEFAULT_ENDPOINTS
+                .iter()
+                .map(|(kind, url)| TronSourceConfig::new(*kind, url))
+                .collect(),
             parallel_mode: true,
             updated_at: 0,
         }
@@ -191,47 +219,51 @@ pub fn save_endpoint_config(config: &EndpointConfig) {
     );
 }
 
-/// 添加自定义端点
+/// 添加自定义 TronGrid 兼容端点
 pub fn add_endpoint(endpoint: &str) {
+    add_source(TronSourceConfig::trongrid(endpoint));
+}
+
+/// 添加数据源（例如自建全节点）
+pub fn add_source(source: TronSourceConfig) {
     let mut config = get_endpoint_config();
-    let endpoint_str = String::from(endpoint);
 
-    if !config.endpoints.contains(&endpoint_str) {
-        config.endpoints.push(endpoint_str);
+    if !config.sources.iter().any(|s| s.base_url == source.base_url) {
+        log::info!(target: "trc20-verifier", "Added {:?} source: {}", source.kind, source.base_url);
+        config.sources.push(source);
         config.updated_at = current_timestamp_ms();
         save_endpoint_config(&config);
-        log::info!(target: "trc20-verifier", "Added endpoint: {}", endpoint);
     }
 }
 
 /// 移除端点
 pub fn remove_endpoint(endpoint: &str) {
     let mut config = get_endpoint_config();
-    let endpoint_str = String::from(endpoint);
+    let endpoint = endpoint.trim_end_matches('/');
 
-    if let Some(pos) = config.endpoints.iter().position(|e| e == &endpoint_str) {
-        config.endpoints.remove(pos);
+    if let Some(pos) = config.sources.iter().position(|s| s.base_url == endpoint) {
+        config.sources.remove(pos);
         config.updated_at = current_timestamp_ms();
         save_endpoint_config(&config);
         log::info!(target: "trc20-verifier", "Removed endpoint: {}", endpoint);
     }
 }
 
-/// 获取按健康评分排序的端点列表
-pub fn get_sorted_endpoints() -> Vec<String> {
+/// 获取按健康评分排序的数据源列表
+pub fn get_sorted_sources() -> Vec<TronSourceConfig> {
     let config = get_endpoint_config();
-    let mut endpoints_with_scores: Vec<(String, u32)> = config.endpoints
-        .iter()
-        .map(|e| {
-            let health = get_endpoint_health(e);
-            (e.clone(), health.score)
+    let mut sources_with_scores: Vec<(TronSourceConfig, u32)> = config.sources
+        .into_iter()
+        .map(|s| {
+            let health = get_endpoint_health(&s.base_url);
+            (s, health.score)
         })
         .collect();
 
-    // 按评分降序排序
-    endpoints_with_scores.sort_by(|a, b| b.1.cmp(&a.1));
+    // 按评分降序排序（稳定排序，同分保持配置顺序）
+    sources_with_scores.sort_by(|a, b| b.1.cmp(&a.1));
 
-    endpoints_with_scores.into_iter().map(|(e, _)| e).collect()
+    sources_with_scores.into_iter().map(|(s, _)| s).collect()
 }
 
 // ==================== TRC20 验证结果 ====================
@@ -248,6 +280,8 @@ pub struct TronTxVerification {
     /// 预期金额
     pub expected_amount: Option<u64>,
     pub confirmations: u32,
+    /// 交易所在区块（数据源报告时）
+    pub block_number: Option<u64>,
     pub error: Option<Vec<u8>>,
     /// 金额匹配状态
     pub amount_status: AmountStatus,
@@ -289,6 +323,7 @@ impl Default for TronTxVerification {
             actual_amount: None,
             expected_amount: None,
             confirmations: 0,
+            block_number: None,
             error: None,
             amount_status: AmountStatus::Unknown,
         }
@@ -306,20 +341,53 @@ impl Default for TronTxVerification {
 ///
 /// ## 返回
 /// - `Ok(TronTxVerification)`: 验证结果（含详细状态）
+/// - `Err`: 所有数据源均无法给出结论（网络错误、交易尚未索引），或确认数不足
+///   （`"Insufficient confirmations"`，不再尝试其他数据源，稍后重试）
 pub fn verify_trc20_transaction(
     tx_hash: &[u8],
     expected_to: &[u8],
     expected_amount: u64,
 ) -> Result<TronTxVerification, &'static str> {
-    // 1. 构建 API URL
+    verify_tron_transfer(tx_hash, &ExpectedTransfer::usdt(expected_to, expected_amount))
+}
+
+/// 验证 TRON 链上任意 TRC20 代币转账（合约、精度、确认数由 `expected` 指定）
+pub fn verify_tron_transfer(
+    tx_hash: &[u8],
+    expected: &ExpectedTransfer,
+) -> Result<TronTxVerification, &'static str> {
     let tx_hash_hex = bytes_to_hex(tx_hash);
-    let url = format!("{}/v1/transactions/{}", TRONGRID_MAINNET, tx_hash_hex);
+    let config = get_endpoint_config();
 
-    // 2. 发送 HTTP 请求（带故障转移）
-    let response = fetch_url_with_fallback(&url)?;
+    let mut result = if config.parallel_mode && config.sources.len() > 1 {
+        verify_parallel_race(&config.sources, &tx_hash_hex, expected)
+    } else {
+        verify_sequential(&tx_hash_hex, expected)
+    }?;
+
+    result.tx_hash = tx_hash.to_vec();
+    Ok(result)
+}
 
-    // 3. 解析响应
-    parse_tron_response(&response, expected_to, expected_amount)
+/// 付款所在网络
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaymentNetwork {
+    /// TRON（TronGrid / TronScan / 全节点数据源）
+    Tron,
+    /// EVM 兼容链（JSON-RPC 端点）
+    Evm { chain_id: u64 },
+}
+
+/// 按网络分派的统一验证入口
+pub fn verify_payment(
+    network: PaymentNetwork,
+    tx_hash: &[u8],
+    expected: &ExpectedTransfer,
+) -> Result<TronTxVerification, &'static str> {
+    match network {
+        PaymentNetwork::Tron => verify_tron_transfer(tx_hash, expected),
+        PaymentNetwork::Evm { chain_id } => verify_evm_transfer(chain_id, tx_hash, expected),
+    }
 }
 
 /// 简化验证接口：仅返回 bool
@@ -334,50 +402,71 @@ pub fn verify_trc20_transaction_simple(
 
 // ==================== 并行请求竞速模式 ====================
 
-/// 并行请求结果
-#[allow(dead_code)]
-struct RaceResult {
-    endpoint: String,
-    response: Vec<u8>,
-    response_ms: u32,
+/// 发送数据源请求
+fn send_request(
+    request: &SourceRequest,
+    deadline: sp_runtime::offchain::Timestamp,
+) -> Result<http::PendingRequest, &'static str> {
+    let method = match request.method {
+        HttpMethod::Get => http::Method::Get,
+        HttpMethod::Post => http::Method::Post,
+    };
+    // 空 chunk 表示请求体结束，GET 请求不能写入
+    let body: Vec<&[u8]> = if request.body.is_empty() {
+        Vec::new()
+    } else {
+        alloc::vec![request.body.as_slice()]
+    };
+    let mut http_request = http::Request::post(&request.url, body).method(method);
+    for (name, value) in request.headers.iter() {
+        http_request = http_request.add_header(name, value);
+    }
+
+    http_request
+        .deadline(deadline)
+        .send()
+        .map_err(|_| "Failed to send HTTP request")
 }
 
-/// 发送 HTTP GET 请求（智能模式选择）
-fn fetch_url_with_fallback(url: &str) -> Result<Vec<u8>, &'static str> {
-    let config = get_endpoint_config();
+/// 读取响应体（仅接受非空 200 响应）
+fn read_response(response: http::Response) -> Result<Vec<u8>, &'static str> {
+    if response.code != 200 {
+        log::warn!(target: "trc20-verifier", "HTTP response code: {}", response.code);
+        return Err("Non-200 HTTP response");
+    }
 
-    if config.parallel_mode && config.endpoints.len() > 1 {
-        fetch_url_parallel_race(url, &config.endpoints)
-    } else {
-        fetch_url_sequential(url)
+    let body = response.body().collect::<Vec<u8>>();
+    if body.is_empty() {
+        return Err("Empty response body");
     }
+    Ok(body)
 }
 
-/// 并行竞速模式：同时请求所有端点，使用最快响应
-fn fetch_url_parallel_race(url: &str, endpoints: &[String]) -> Result<Vec<u8>, &'static str> {
-    log::info!(target: "trc20-verifier", "Starting parallel race with {} endpoints", endpoints.len());
+/// 并行竞速模式：同时请求所有数据源，采用第一个能给出结论的响应
+fn verify_parallel_race(
+    sources: &[TronSourceConfig],
+    tx_hash_hex: &str,
+    expected: &ExpectedTransfer,
+) -> Result<TronTxVerification, &'static str> {
+    log::info!(target: "trc20-verifier", "Starting parallel race with {} sources", sources.len());
 
     let start_time = current_timestamp_ms();
 
     // 准备所有请求
-    let mut pending_requests: Vec<(String, http::PendingRequest)> = Vec::new();
+    let mut pending_requests: Vec<(alloc::boxed::Box<dyn TronDataSource>, http::PendingRequest)> = Vec::new();
     let timeout = sp_io::offchain::timestamp()
         .add(Duration::from_millis(HTTP_TIMEOUT_RACE_MS));
 
-    for endpoint in endpoints.iter() {
-        let target_url = url.replace(TRONGRID_MAINNET, endpoint);
-
-        let request = http::Request::get(&target_url);
-        match request.deadline(timeout).send() {
+    for config in sources.iter() {
+        let source = config.data_source();
+        match send_request(&source.build_request(tx_hash_hex), timeout) {
             Ok(pending) => {
-                pending_requests.push((endpoint.clone(), pending));
-                log::debug!(target: "trc20-verifier", "Sent request to {}", endpoint);
+                log::debug!(target: "trc20-verifier", "Sent request to {}", source.base_url());
+                pending_requests.push((source, pending));
             },
             Err(_) => {
-                log::warn!(target: "trc20-verifier", "Failed to send request to {}", endpoint);
-                let mut health = get_endpoint_health(endpoint);
-                health.record_failure();
-                save_endpoint_health(endpoint, &health);
+                log::warn!(target: "trc20-verifier", "Failed to send request to {}", source.base_url());
+                record_endpoint_failure(source.base_url());
             }
         }
     }
@@ -386,242 +475,148 @@ fn fetch_url_parallel_race(url: &str, endpoints: &[String]) -> Result<Vec<u8>, &
         return Err("Failed to send any requests");
     }
 
-    // 轮询等待第一个成功响应
-    let mut winner: Option<RaceResult> = None;
-    let mut failed_endpoints: Vec<String> = Vec::new();
-
-    for (endpoint, pending) in pending_requests {
-        match pending.try_wait(timeout) {
-            Ok(Ok(response)) => {
-                let response_ms = (current_timestamp_ms() - start_time) as u32;
-
-                if response.code == 200 {
-                    let body = response.body().collect::<Vec<u8>>();
-                    if !body.is_empty() {
-                        log::info!(target: "trc20-verifier", "Winner: {} ({}ms)", endpoint, response_ms);
-
-                        let mut health = get_endpoint_health(&endpoint);
-                        health.record_success(response_ms);
-                        save_endpoint_health(&endpoint, &health);
-
-                        winner = Some(RaceResult {
-                            endpoint,
-                            response: body,
-                            response_ms,
-                        });
-                        break;
-                    }
-                }
+    // 依次收取响应，第一个给出结论的数据源胜出
+    let mut last_error = "All parallel requests failed";
 
-                failed_endpoints.push(endpoint);
+    for (source, pending) in pending_requests {
+        let body = match pending.try_wait(timeout) {
+            Ok(Ok(response)) => read_response(response),
+            Ok(Err(_)) => Err("HTTP request failed"),
+            Err(_) => Err("HTTP request timeout"),
+        };
+        let body = match body {
+            Ok(body) => body,
+            Err(e) => {
+                record_endpoint_failure(source.base_url());
+                last_error = e;
+                continue;
             },
-            Ok(Err(_)) | Err(_) => {
-                failed_endpoints.push(endpoint);
-            }
-        }
-    }
+        };
 
-    // 记录失败的端点
-    for endpoint in failed_endpoints {
-        let mut health = get_endpoint_health(&endpoint);
-        health.record_failure();
-        save_endpoint_health(&endpoint, &health);
-    }
+        let response_ms = (current_timestamp_ms() - start_time) as u32;
+        record_endpoint_success(source.base_url(), response_ms);
 
-    match winner {
-        Some(result) => Ok(result.response),
-        None => {
-            log::error!(target: "trc20-verifier", "All parallel requests failed");
-            Err("All parallel requests failed")
+        match source_verdict(source.as_ref(), &body, expected) {
+            Ok(result) => {
+                log::info!(target: "trc20-verifier", "Winner: {} ({}ms)", source.base_url(), response_ms);
+                return Ok(result);
+            },
+            // 交易已上链但确认数不足：结论待定，其他数据源不能代替确认
+            Err(e @ "Insufficient confirmations") => {
+                log::info!(target: "trc20-verifier", "{} reports insufficient confirmations", source.base_url());
+                return Err(e);
+            },
+            Err(e) => {
+                log::debug!(target: "trc20-verifier", "{} inconclusive: {}", source.base_url(), e);
+                last_error = e;
+            },
         }
     }
+
+    log::error!(target: "trc20-verifier", "No source produced a verdict: {}", last_error);
+    Err(last_error)
 }
 
-/// 串行故障转移模式：按健康评分依次尝试端点
-fn fetch_url_sequential(url: &str) -> Result<Vec<u8>, &'static str> {
-    let sorted_endpoints = get_sorted_endpoints();
+/// 串行故障转移模式：按健康评分依次尝试数据源
+fn verify_sequential(
+    tx_hash_hex: &str,
+    expected: &ExpectedTransfer,
+) -> Result<TronTxVerification, &'static str> {
+    let sorted_sources = get_sorted_sources();
     let mut last_error = "No endpoints available";
 
-    log::info!(target: "trc20-verifier", "Sequential mode with {} endpoints (sorted by health)",
-        sorted_endpoints.len());
+    log::info!(target: "trc20-verifier", "Sequential mode with {} sources (sorted by health)",
+        sorted_sources.len());
 
-    for (idx, endpoint) in sorted_endpoints.iter().enumerate() {
-        let target_url = url.replace(TRONGRID_MAINNET, endpoint);
+    for (idx, config) in sorted_sources.iter().enumerate() {
+        let source = config.data_source();
         let start_time = current_timestamp_ms();
 
-        log::debug!(target: "trc20-verifier", "Trying endpoint {} ({}/{})",
-            endpoint, idx + 1, sorted_endpoints.len());
+        log::debug!(target: "trc20-verifier", "Trying {:?} source {} ({}/{})",
+            config.kind, source.base_url(), idx + 1, sorted_sources.len());
 
-        match fetch_url(&target_url) {
-            Ok(response) => {
-                let response_ms = (current_timestamp_ms() - start_time) as u32;
+        let body = match fetch(&source.build_request(tx_hash_hex)) {
+            Ok(body) => body,
+            Err(e) => {
+                record_endpoint_failure(source.base_url());
+                log::warn!(target: "trc20-verifier", "Endpoint {} failed: {}", source.base_url(), e);
+                last_error = e;
+                continue;
+            },
+        };
 
-                let mut health = get_endpoint_health(endpoint);
-                health.record_success(response_ms);
-                save_endpoint_health(endpoint, &health);
+        let response_ms = (current_timestamp_ms() - start_time) as u32;
+        record_endpoint_success(source.base_url(), response_ms);
 
+        // 解析失败（交易未索引、格式不识别）时继续尝试下一个数据源；
+        // 确认数不足是最终结果，不转移到其他数据源
+        match source_verdict(source.as_ref(), &body, expected) {
+            Ok(result) => {
                 if idx > 0 {
-                    log::info!(target: "trc20-verifier", "Fallback endpoint {} succeeded ({}ms)",
-                        endpoint, response_ms);
+                    log::info!(target: "trc20-verifier", "Fallback source {} succeeded ({}ms)",
+                        source.base_url(), response_ms);
                 }
-                return Ok(response);
+                return Ok(result);
+            },
+            Err(e @ "Insufficient confirmations") => {
+                log::info!(target: "trc20-verifier", "{} reports insufficient confirmations", source.base_url());
+                return Err(e);
             },
             Err(e) => {
-                let mut health = get_endpoint_health(endpoint);
-                health.record_failure();
-                save_endpoint_health(endpoint, &health);
-
-                log::warn!(target: "trc20-verifier", "Endpoint {} failed: {}", endpoint, e);
+                log::debug!(target: "trc20-verifier", "{} inconclusive: {}", source.base_url(), e);
                 last_error = e;
-            }
+            },
         }
     }
 
-    log::error!(target: "trc20-verifier", "All {} endpoints failed", sorted_endpoints.len());
+    log::error!(target: "trc20-verifier", "All {} sources failed", sorted_sources.len());
     Err(last_error)
 }
 
-/// 发送 HTTP GET 请求
-fn fetch_url(url: &str) -> Result<Vec<u8>, &'static str> {
-    log::debug!(target: "trc20-verifier", "Fetching URL: {}", url);
+/// 解析数据源响应；响应不含确认数的数据源再查询最新区块计算确认数
+fn source_verdict(
+    source: &dyn TronDataSource,
+    body: &[u8],
+    expected: &ExpectedTransfer,
+) -> Result<TronTxVerification, &'static str> {
+    let result = source.parse_response(body, expected)?;
+    let Some(request) = source.head_request() else {
+        return Ok(result);
+    };
+    if result.block_number.is_none() {
+        return Err("Insufficient confirmations");
+    }
+    let head = parse_now_block(&fetch(&request)?)?;
+    apply_confirmations(result, head, expected.min_confirmations)
+}
 
-    let request = http::Request::get(url);
+/// 发送单个请求并等待响应
+fn fetch(request: &SourceRequest) -> Result<Vec<u8>, &'static str> {
+    log::debug!(target: "trc20-verifier", "Fetching URL: {} {}", request.method.as_str(), request.url);
 
     let timeout = sp_io::offchain::timestamp()
         .add(Duration::from_millis(HTTP_TIMEOUT_MS));
 
-    let pending = request
-        .deadline(timeout)
-        .send()
-        .map_err(|_| "Failed to send HTTP request")?;
-
-    let response = pending
+    let response = send_request(request, timeout)?
         .try_wait(timeout)
         .map_err(|_| "HTTP request timeout")?
         .map_err(|_| "HTTP request failed")?;
 
-    if response.code != 200 {
-        log::warn!(target: "trc20-verifier", "HTTP response code: {}", response.code);
-        return Err("Non-200 HTTP response");
-    }
-
-    let body = response.body().collect::<Vec<u8>>();
-
-    if body.is_empty() {
-        return Err("Empty response body");
-    }
-
+    let body = read_response(response)?;
     log::debug!(target: "trc20-verifier", "Received {} bytes", body.len());
     Ok(body)
 }
 
-// ==================== 响应解析 ====================
-
-/// 解析 TronGrid API 响应
-fn parse_tron_response(
-    response: &[u8],
-    expected_to: &[u8],
-    expected_amount: u64,
-) -> Result<TronTxVerification, &'static str> {
-    let response_str = core::str::from_utf8(response)
-        .map_err(|_| "Invalid UTF-8 response")?;
-
-    let mut result = TronTxVerification::default();
-    result.expected_amount = Some(expected_amount);
-
-    // 检查是否包含成功状态
-    if !response_str.contains("\"contractRet\":\"SUCCESS\"")
-        && !response_str.contains("\"contractRet\": \"SUCCESS\"") {
-        result.error = Some(b"Transaction not successful".to_vec());
-        return Ok(result);
-    }
-
-    // 检查收款地址
-    let expected_to_hex = bytes_to_hex(expected_to);
-    if !response_str.contains(&expected_to_hex) {
-        result.error = Some(b"Recipient address mismatch".to_vec());
-        return Ok(result);
-    }
-
-    // 提取实际金额
-    let actual_amount = extract_amount(response_str);
-    result.actual_amount = actual_amount;
-
-    // 计算金额匹配状态
-    let (amount_status, is_acceptable) = match actual_amount {
-        Some(actual) => {
-            let min_exact = expected_amount * 995 / 1000;  // -0.5%
-            let max_exact = expected_amount * 1005 / 1000; // +0.5%
-            let severe_threshold = expected_amount / 2;    // 50%
-
-            if actual >= min_exact && actual <= max_exact {
-                (AmountStatus::Exact, true)
-            } else if actual > max_exact {
-                let excess = actual.saturating_sub(expected_amount);
-                log::info!(target: "trc20-verifier", "Overpaid: expected={}, actual={}, excess={}",
-                    expected_amount, actual, excess);
-                (AmountStatus::Overpaid { excess }, true)
-            } else if actual >= severe_threshold {
-                let shortage = expected_amount.saturating_sub(actual);
-                log::warn!(target: "trc20-verifier", "Underpaid: expected={}, actual={}, shortage={}",
-                    expected_amount, actual, shortage);
-                (AmountStatus::Underpaid { shortage }, false)
-            } else if actual > 0 {
-                let shortage = expected_amount.saturating_sub(actual);
-                log::error!(target: "trc20-verifier", "Severely underpaid: expected={}, actual={}",
-                    expected_amount, actual);
-                (AmountStatus::SeverelyUnderpaid { shortage }, false)
-            } else {
-                (AmountStatus::Invalid, false)
-            }
-        },
-        None => {
-            log::error!(target: "trc20-verifier", "Failed to extract amount from response");
-            (AmountStatus::Invalid, false)
-        }
-    };
-
-    result.amount_status = amount_status.clone();
-
-    if !is_acceptable {
-        let error_msg = match &amount_status {
-            AmountStatus::Underpaid { shortage } =>
-                format!("Underpaid by {} (expected {}, got {})",
-                    shortage, expected_amount, actual_amount.unwrap_or(0)),
-            AmountStatus::SeverelyUnderpaid { shortage } =>
-                format!("Severely underpaid by {} (possible fraud)", shortage),
-            AmountStatus::Invalid =>
-                "Invalid or zero amount".to_string(),
-            _ => "Amount mismatch".to_string(),
-        };
-        result.error = Some(error_msg.into_bytes());
-        return Ok(result);
-    }
-
-    result.is_valid = true;
-
-    Ok(result)
+fn record_endpoint_success(endpoint: &str, response_ms: u32) {
+    let mut health = get_endpoint_health(endpoint);
+    health.record_success(response_ms);
+    save_endpoint_health(endpoint, &health);
 }
 
-/// 从响应中提取金额
-fn extract_amount(response: &str) -> Option<u64> {
-    let patterns = ["\"amount\":", "\"amount\": "];
-
-    for pattern in patterns {
-        if let Some(start) = response.find(pattern) {
-            let after_key = &response[start + pattern.len()..];
-            let trimmed = after_key.trim_start();
-            let num_str: String = trimmed.chars()
-                .take_while(|c| c.is_numeric())
-                .collect();
-            if !num_str.is_empty() {
-                if let Ok(amount) = num_str.parse::<u64>() {
-                    return Some(amount);
-                }
-            }
-        }
-    }
-    None
+fn record_endpoint_failure(endpoint: &str) {
+    let mut health = get_endpoint_health(endpoint);
+    health.record_failure();
+    save_endpoint_health(endpoint, &health);
 }
 
 // ==================== 工具函数 ====================
@@ -683,11 +678,114 @@ mod tests {
     }
 
     #[test]
-    fn test_extract_amount() {
-        assert_eq!(extract_amount(r#""amount":1000000"#), Some(1000000));
-        assert_eq!(extract_amount(r#""amount": 2500000"#), Some(2500000));
-        assert_eq!(extract_amount(r#"no amount here"#), None);
-        assert_eq!(extract_amount(r#""amount":0"#), Some(0));
+    fn test_trongrid_amount_extraction() {
+        let to = b"TA4Y62o6YC2Zsck9rZVGTvqW1AQ7X9zTnj";
+        let source = TronSourceConfig::trongrid(TRONGRID_MAINNET).data_source();
+        let parse = |body: &str| source.parse_response(body.as_bytes(), &ExpectedTransfer::usdt(to, 1_000_000));
+
+        let ok = parse(&replay::trongrid_transfer(to, 1_000_000)).unwrap();
+        assert_eq!(ok.actual_amount, Some(1_000_000));
+        let spaced = parse(&replay::trongrid_transfer(to, 2_500_000).replace(r#"":"#, r#"": "#)).unwrap();
+        assert_eq!(spaced.actual_amount, Some(2_500_000));
+
+        // 调用数据缺少金额字：不是完整的 transfer 调用
+        let calldata = replay::transfer_calldata(to, 1_000_000);
+        let truncated = replay::trongrid_transfer(to, 1_000_000).replace(&calldata, &calldata[..8 + 64]);
+        let missing = parse(&truncated).unwrap();
+        assert!(!missing.is_valid);
+        assert_eq!(missing.actual_amount, None);
+    }
+
+    #[test]
+    fn test_trongrid_response_not_found() {
+        let source = TronSourceConfig::trongrid(TRONGRID_MAINNET).data_source();
+        assert_eq!(
+            source.parse_response(replay::TRONGRID_NOT_FOUND.as_bytes(), &ExpectedTransfer::usdt(b"TXyz", 1_000_000)),
+            Err("Transaction not found")
+        );
+    }
+
+    #[test]
+    fn test_sequential_falls_back_to_full_node() {
+        let (offchain, state) = sp_core::offchain::testing::TestOffchainExt::new();
+        let mut ext = sp_io::TestExternalities::default();
+        ext.register_extension(sp_core::offchain::OffchainDbExt::new(offchain.clone()));
+        ext.register_extension(sp_core::offchain::OffchainWorkerExt::new(offchain));
+
+        ext.execute_with(|| {
+            let to = b"TA4Y62o6YC2Zsck9rZVGTvqW1AQ7X9zTnj";
+            let grid = TronSourceConfig::trongrid(TRONGRID_MAINNET);
+            let node = TronSourceConfig::full_node("http://127.0.0.1:8090");
+            replay::use_sources(vec![grid.clone(), node.clone()]);
+            replay::expect_lookup(&mut state.write(), &grid, &[0xAB; 32], replay::TRONGRID_NOT_FOUND);
+            replay::expect_lookup(&mut state.write(), &node, &[0xAB; 32], &replay::full_node_transfer(to, 5_000_000));
+
+            let result = verify_trc20_transaction(&[0xAB; 32], to, 5_000_000).unwrap();
+            assert!(result.is_valid);
+            assert_eq!(result.tx_hash, vec![0xAB; 32]);
+            assert_eq!(get_endpoint_health("http://127.0.0.1:8090").success_count, 1);
+        });
+    }
+
+    #[test]
+    fn test_insufficient_confirmations_stops_failover() {
+        let (offchain, state) = sp_core::offchain::testing::TestOffchainExt::new();
+        let mut ext = sp_io::TestExternalities::default();
+        ext.register_extension(sp_core::offchain::OffchainDbExt::new(offchain.clone()));
+        ext.register_extension(sp_core::offchain::OffchainWorkerExt::new(offchain));
+
+        ext.execute_with(|| {
+            let to = b"TA4Y62o6YC2Zsck9rZVGTvqW1AQ7X9zTnj";
+            let grid = TronSourceConfig::trongrid(TRONGRID_MAINNET);
+            let node = TronSourceConfig::full_node("http://127.0.0.1:8090");
+            replay::use_sources(vec![grid.clone(), node.clone()]);
+
+            // TronGrid 查到交易但只有 5 个确认：直接返回，不再请求全节点
+            replay::expect_lookup_at(
+                &mut state.write(),
+                &grid,
+                &[0xAB; 32],
+                &replay::trongrid_transfer(to, 5_000_000),
+                Some(replay::FIXTURE_BLOCK + 4),
+            );
+            assert_eq!(
+                verify_trc20_transaction(&[0xAB; 32], to, 5_000_000),
+                Err("Insufficient confirmations")
+            );
+
+            // 确认数足够后给出结论
+            replay::expect_lookup(&mut state.write(), &grid, &[0xAB; 32], &replay::trongrid_transfer(to, 5_000_000));
+            let result = verify_trc20_transaction(&[0xAB; 32], to, 5_000_000).unwrap();
+            assert!(result.is_valid);
+            assert_eq!(result.confirmations, 30);
+        });
+    }
+
+    #[test]
+    fn test_all_sources_not_found_is_retryable() {
+        let (offchain, state) = sp_core::offchain::testing::TestOffchainExt::new();
+        let mut ext = sp_io::TestExternalities::default();
+        ext.register_extension(sp_core::offchain::OffchainDbExt::new(offchain.clone()));
+        ext.register_extension(sp_core::offchain::OffchainWorkerExt::new(offchain));
+
+        ext.execute_with(|| {
+            let scan = TronSourceConfig::tronscan("https://apilist.tronscanapi.com");
+            replay::use_sources(vec![scan.clone()]);
+            replay::expect_lookup(&mut state.write(), &scan, &[0x01; 32], replay::EMPTY_OBJECT);
+
+            assert_eq!(
+                verify_trc20_transaction(&[0x01; 32], b"TXyz", 1),
+                Err("Transaction not found")
+            );
+        });
+    }
+
+    #[test]
+    fn test_default_sources() {
+        let config = EndpointConfig::default();
+        assert_eq!(config.sources.len(), DEFAULT_ENDPOINTS.len());
+        assert_eq!(config.sources[0], TronSourceConfig::trongrid(TRONGRID_MAINNET));
+        assert_eq!(config.sources[2].kind, TronSourceKind::TronScan);
     }
 
     #[test]
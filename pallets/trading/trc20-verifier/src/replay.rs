@@ -0,0 +1,224 @@
+//! # 录制响应回放（测试工具）
+//!
+//! 为依赖本库的 pallet 提供无网络的端到端测试支持：
+//! - 各数据源的录制格式响应构造函数
+//! - 根据数据源的 `build_request` 预设 `TestOffchainExt` 的 HTTP 期望（含确认数所需的最新区块查询）
+//! - EVM JSON-RPC 回执 / 区块高度响应
+//!
+//! ## 用法
+//! ```ignore
+//! let source = TronSourceConfig::trongrid(TRONGRID_MAINNET);
+//! replay::use_sources(vec![source.clone()]);
+//! replay::expect_lookup(&mut state.write(), &source, &tx_hash, &replay::trongrid_transfer(to, amount));
+//! ```
+
+use sp_core::offchain::testing::{OffchainState, PendingRequest};
+
+use crate::address::{decode_tron_address, evm_hex20, AddressForms};
+use crate::evm::{block_number_request, receipt_request};
+use crate::source::{SourceRequest, TronSourceConfig};
+use crate::{bytes_to_hex, save_endpoint_config, EndpointConfig, USDT_CONTRACT};
+
+/// TronGrid 交易未索引响应
+pub const TRONGRID_NOT_FOUND: &str =
+    r#"{"data":[],"success":true,"meta":{"at":1700000000000,"page_size":0}}"#;
+
+/// TronScan / 全节点交易未找到响应
+pub const EMPTY_OBJECT: &str = "{}";
+
+/// EVM 交易未打包响应
+pub const EVM_RECEIPT_NOT_FOUND: &str = r#"{"jsonrpc":"2.0","id":1,"result":null}"#;
+
+/// 录制响应中的付款方（20 字节十六进制）
+pub const PAYER_HEX20: &str = "ea51342dabbb928ae1e576bd39eff8aaf070a8c6";
+
+/// 录制响应中交易所在区块
+pub const FIXTURE_BLOCK: u64 = 60_000_000;
+
+/// `expect_lookup` 预设的最新区块：录制交易有 30 个确认
+pub const CONFIRMED_HEAD: u64 = FIXTURE_BLOCK + 29;
+
+/// 录制响应中的交易ID
+const FIXTURE_TX_ID: &str = "7c2d4d7c8e2a2b3c5e8f4a1d6b9c0e3f5a7b9d1c3e5f7a9b1d3f5a7c9e1b3d5f";
+
+/// 使用指定数据源（串行模式，请求顺序确定）
+pub fn use_sources(sources: Vec<TronSourceConfig>) {
+    save_endpoint_config(&EndpointConfig { sources, parallel_mode: false, updated_at: 0 });
+}
+
+/// 预设一次交易查询的 HTTP 响应
+///
+/// 响应含交易所在区块（`blockNumber`）且数据源不直接报告确认数时，
+/// 同时预设随后的最新区块查询，返回 [`CONFIRMED_HEAD`]。
+pub fn expect_lookup(
+    state: &mut OffchainState,
+    source: &TronSourceConfig,
+    tx_hash: &[u8],
+    response: &str,
+) {
+    let head = response.contains(r#""blockNumber""#).then_some(CONFIRMED_HEAD);
+    expect_lookup_at(state, source, tx_hash, response, head);
+}
+
+/// 预设一次交易查询，`head` 为随后查询到的最新区块（None 表示不会查询）
+pub fn expect_lookup_at(
+    state: &mut OffchainState,
+    source: &TronSourceConfig,
+    tx_hash: &[u8],
+    response: &str,
+    head: Option<u64>,
+) {
+    let data_source = source.data_source();
+    expect_request(state, data_source.build_request(&bytes_to_hex(tx_hash)), response);
+    if let (Some(head), Some(request)) = (head, data_source.head_request()) {
+        expect_request(state, request, &now_block(head));
+    }
+}
+
+/// 预设一次 EVM 交易查询：回执，以及（给出 `head` 时）随后的区块高度查询
+pub fn expect_evm_lookup(
+    state: &mut OffchainState,
+    endpoint: &str,
+    tx_hash: &[u8],
+    receipt: &str,
+    head: Option<u64>,
+) {
+    expect_request(state, receipt_request(endpoint, &bytes_to_hex(tx_hash)), receipt);
+    if let Some(head) = head {
+        expect_request(state, block_number_request(endpoint), &evm_block_number(head));
+    }
+}
+
+fn expect_request(state: &mut OffchainState, request: SourceRequest, response: &str) {
+    state.expect_request(PendingRequest {
+        method: request.method.as_str().into(),
+        uri: request.url,
+        body: request.body,
+        headers: request.headers,
+        response: Some(response.as_bytes().to_vec()),
+        sent: true,
+        ..Default::default()
+    });
+}
+
+/// TronGrid `/v1/transactions/{id}` 成功 USDT 转账响应
+///
+/// 字段与主网返回一致：`ret`、签名、`raw_data.contract[0]`（TriggerSmartContract）、
+/// 能量 / 带宽统计等；仅替换收款地址与金额。收款地址必须是合法的 Base58Check TRON 地址。
+pub fn trongrid_transfer(to: &[u8], amount: u64) -> String {
+    trongrid_trigger(&usdt_hex21(), &transfer_calldata(to, amount), None)
+}
+
+/// TronGrid 成功 USDT 转账响应，附带付款方填写的备注（`raw_data.data`，十六进制）
+pub fn trongrid_transfer_with_memo(to: &[u8], amount: u64, memo_hex: &str) -> String {
+    trongrid_trigger(&usdt_hex21(), &transfer_calldata(to, amount), Some(memo_hex))
+}
+
+/// `transfer(address,uint256)` 调用数据
+pub fn transfer_calldata(to: &[u8], amount: u64) -> String {
+    let to = decode_tron_address(to).expect("TronGrid fixture requires a valid TRON address");
+    format!(
+        "{}000000000000000000000000{}{:064x}",
+        crate::source::TRANSFER_METHOD_ID,
+        bytes_to_hex(&to[1..]),
+        amount,
+    )
+}
+
+fn trongrid_trigger(contract_hex21: &str, calldata: &str, memo_hex: Option<&str>) -> String {
+    let memo = memo_hex.map(|m| format!(r#""data":"{}","#, m)).unwrap_or_default();
+    format!(
+        r#"{{"data":[{{"ret":[{{"contractRet":"SUCCESS","fee":13844850}}],"signature":["4b5c2a1e0d9f8e7d6c5b4a39281706f5e4d3c2b1a09f8e7d6c5b4a3928170615f4e3d2c1b0a9f8e7d6c5b4a39281706f5e4d3c2b1a09f8e7d6c5b4a392817061b"],"txID":"{}","net_usage":0,"raw_data_hex":"0a02c6f42208...","net_fee":345000,"energy_usage":0,"blockNumber":{},"block_timestamp":1710000000000,"energy_fee":13499850,"energy_usage_total":64285,"raw_data":{{{}"contract":[{{"parameter":{{"value":{{"data":"{}","owner_address":"41{}","contract_address":"{}"}},"type_url":"type.googleapis.com/protocol.TriggerSmartContract"}},"type":"TriggerSmartContract"}}],"ref_block_bytes":"c6f4","ref_block_hash":"8f6a2f1e3b9d0c55","expiration":1710000057000,"fee_limit":100000000,"timestamp":1709999998000}},"internal_transactions":[]}}],"success":true,"meta":{{"at":1710000060000,"page_size":1}}}}"#,
+        FIXTURE_TX_ID,
+        FIXTURE_BLOCK,
+        memo,
+        calldata,
+        PAYER_HEX20,
+        contract_hex21,
+    )
+}
+
+/// TronGrid 成功 TRX 转账响应（`TransferContract`，无合约地址）
+///
+/// 收款地址与金额字段与 TRC20 转账同名，用于验证不会被误判为 USDT 到账。
+pub fn trongrid_trx_transfer(to: &[u8], amount: u64) -> String {
+    let forms = AddressForms::new(to);
+    format!(
+        r#"{{"data":[{{"ret":[{{"contractRet":"SUCCESS"}}],"txID":"{}","blockNumber":{},"raw_data":{{"contract":[{{"parameter":{{"value":{{"amount":{},"owner_address":"41{}","to_address":"{}"}},"type_url":"type.googleapis.com/protocol.TransferContract"}},"type":"TransferContract"}}]}}}}],"success":true}}"#,
+        FIXTURE_TX_ID,
+        FIXTURE_BLOCK,
+        amount,
+        PAYER_HEX20,
+        forms.hex21.unwrap_or(forms.raw_hex),
+    )
+}
+
+fn usdt_hex21() -> String {
+    AddressForms::new(USDT_CONTRACT.as_bytes()).hex21.expect("USDT contract is a valid TRON address")
+}
+
+/// TronScan 成功 USDT 转账响应（含 `trc20TransferInfo`）
+pub fn tronscan_transfer(to: &[u8], amount: u64) -> String {
+    format!(
+        r#"{{"hash":"00","contractRet":"SUCCESS","confirmed":true,"confirmations":30,"trc20TransferInfo":[{{"contract_address":"{}","from_address":"TXLAQ63Xg1NAzckPwKHvzw7CSEmLMEqcdj","to_address":"{}","amount_str":"{}","decimals":6,"symbol":"USDT"}}]}}"#,
+        USDT_CONTRACT,
+        String::from_utf8_lossy(to),
+        amount,
+    )
+}
+
+/// 全节点 `gettransactioninfobyid` 成功 USDT 转账响应（含 Transfer 事件日志）
+///
+/// 收款地址必须是合法的 Base58Check TRON 地址。
+pub fn full_node_transfer(to: &[u8], amount: u64) -> String {
+    full_node_token_transfer(USDT_CONTRACT.as_bytes(), to, amount as u128)
+}
+
+/// 全节点任意 TRC20 代币转账响应，`raw_amount` 为链上原始金额
+pub fn full_node_token_transfer(token: &[u8], to: &[u8], raw_amount: u128) -> String {
+    let to = decode_tron_address(to).expect("full node fixture requires a valid TRON address");
+    let token = decode_tron_address(token).expect("full node fixture requires a valid token contract");
+    format!(
+        r#"{{"id":"00","blockNumber":{},"contract_address":"{}","receipt":{{"energy_usage_total":14650,"result":"SUCCESS"}},"log":[{{"address":"{}","topics":["{}","000000000000000000000000{}","000000000000000000000000{}"],"data":"{:064x}"}}]}}"#,
+        FIXTURE_BLOCK,
+        bytes_to_hex(&token),
+        bytes_to_hex(&token[1..]),
+        crate::source::TRANSFER_EVENT_TOPIC,
+        PAYER_HEX20,
+        bytes_to_hex(&to[1..]),
+        raw_amount,
+    )
+}
+
+/// EVM `eth_getTransactionReceipt` 成功转账响应
+///
+/// `token` / `to` 为 `0x` 十六进制地址，`raw_amount` 为链上原始金额。
+pub fn evm_receipt(token: &[u8], to: &[u8], raw_amount: u128, block: u64) -> String {
+    let token = evm_hex20(token).expect("EVM fixture requires a 0x token address");
+    let to = evm_hex20(to).expect("EVM fixture requires a 0x recipient address");
+    format!(
+        r#"{{"jsonrpc":"2.0","id":1,"result":{{"blockNumber":"0x{:x}","status":"0x1","gasUsed":"0xb41d","logs":[{{"address":"0x{}","topics":["0x{}","0x000000000000000000000000{}","0x000000000000000000000000{}"],"data":"0x{:064x}","logIndex":"0x0"}}]}}}}"#,
+        block,
+        token,
+        crate::source::TRANSFER_EVENT_TOPIC,
+        PAYER_HEX20,
+        to,
+        raw_amount,
+    )
+}
+
+/// EVM `eth_blockNumber` 响应
+pub fn evm_block_number(head: u64) -> String {
+    format!(r#"{{"jsonrpc":"2.0","id":1,"result":"0x{:x}"}}"#, head)
+}
+
+/// 全节点 / TronGrid `/wallet/getnowblock` 响应
+pub fn now_block(head: u64) -> String {
+    format!(
+        r#"{{"blockID":"{:016x}a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718","block_header":{{"raw_data":{{"number":{},"txTrieRoot":"0000000000000000000000000000000000000000000000000000000000000000","witness_address":"41{}","parentHash":"{:016x}0000000000000000000000000000000000000000000000ff","version":30,"timestamp":1710000090000}},"witness_signature":"00"}}}}"#,
+        head,
+        head,
+        PAYER_HEX20,
+        head.saturating_sub(1),
+    )
+}
@@ -0,0 +1,854 @@
+//! # TRON 数据源抽象
+//!
+//! 不同 TRON API 返回的交易结构各不相同，本模块为每种数据源提供独立的
+//! 请求构建与响应解析，统一输出 [`TronTxVerification`]。
+//!
+//! ## 支持的数据源
+//! - `TronGrid`：REST `GET /v1/transactions/{hash}`（TronGrid / TronStack 等兼容服务）
+//! - `TronScan`：REST `GET /api/transaction-info?hash={hash}`
+//! - `FullNode`：自建全节点 HTTP API `POST /wallet/gettransactioninfobyid`
+//!
+//! ## 解析约定
+//! - 交易尚未索引 → `Err("Transaction not found")`，由调用方按重试处理
+//! - 确认数不足 → `Err("Insufficient confirmations")`，本轮验证到此为止，稍后重试；
+//!   TronScan 直接报告确认数，TronGrid / 全节点由交易所在区块与 `/wallet/getnowblock` 计算
+//! - 交易已确定但不满足条件（失败、地址不符、金额不足）→ `Ok` 且 `is_valid = false`
+//! - 响应格式无法识别 → `Err`，换下一个数据源
+//! - 链上原始金额按代币精度换算为 6 位精度后再判定
+
+extern crate alloc;
+
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use codec::{Decode, Encode};
+use lite_json::json::JsonValue;
+
+use crate::address::{decode_tron_address, AddressForms};
+use crate::{bytes_to_hex, AmountStatus, TronTxVerification, MIN_CONFIRMATIONS, USDT_CONTRACT};
+
+/// TRC20 `Transfer(address,address,uint256)` 事件签名
+pub const TRANSFER_EVENT_TOPIC: &str =
+    "ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+
+/// TRC20 `transfer(address,uint256)` 方法选择器
+pub const TRANSFER_METHOD_ID: &str = "a9059cbb";
+
+/// 判定金额使用的精度（10^6，与链上订单金额一致）
+pub const AMOUNT_DECIMALS: u8 = 6;
+
+// ==================== 预期转账 ====================
+
+/// 预期的代币转账
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExpectedTransfer<'a> {
+    /// 代币合约地址（TRON Base58 / EVM `0x` 十六进制）
+    pub token_contract: &'a [u8],
+    /// 代币精度
+    pub decimals: u8,
+    /// 最小确认数
+    pub min_confirmations: u32,
+    /// 收款地址
+    pub to: &'a [u8],
+    /// 预期金额（精度 10^6）
+    pub amount: u64,
+}
+
+impl<'a> ExpectedTransfer<'a> {
+    /// 官方 USDT-TRC20 转账
+    pub fn usdt(to: &'a [u8], amount: u64) -> Self {
+        Self {
+            token_contract: USDT_CONTRACT.as_bytes(),
+            decimals: 6,
+            min_confirmations: MIN_CONFIRMATIONS,
+            to,
+            amount,
+        }
+    }
+
+    /// 将链上原始金额换算为 6 位精度，超出 u64 返回 None
+    pub fn normalize_amount(&self, raw: u128) -> Option<u64> {
+        normalize_amount(raw, self.decimals)
+    }
+}
+
+/// 按代币精度换算到 6 位精度（向下取整）
+pub fn normalize_amount(raw: u128, decimals: u8) -> Option<u64> {
+    let normalized = if decimals >= AMOUNT_DECIMALS {
+        let divisor = 10u128.checked_pow((decimals - AMOUNT_DECIMALS) as u32)?;
+        raw / divisor
+    } else {
+        let factor = 10u128.checked_pow((AMOUNT_DECIMALS - decimals) as u32)?;
+        raw.checked_mul(factor)?
+    };
+    u64::try_from(normalized).ok()
+}
+
+// ==================== 请求描述 ====================
+
+/// HTTP 方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HttpMethod {
+    Get,
+    Post,
+}
+
+impl HttpMethod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HttpMethod::Get => "GET",
+            HttpMethod::Post => "POST",
+        }
+    }
+}
+
+/// 数据源生成的 HTTP 请求描述（与具体 HTTP 客户端解耦，便于回放测试）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceRequest {
+    pub method: HttpMethod,
+    pub url: String,
+    pub body: Vec<u8>,
+    pub headers: Vec<(String, String)>,
+}
+
+// ==================== 数据源 trait ====================
+
+/// TRON 交易数据源
+pub trait TronDataSource {
+    /// 数据源类型
+    fn kind(&self) -> TronSourceKind;
+
+    /// 基础 URL（同时作为健康评分的键）
+    fn base_url(&self) -> &str;
+
+    /// 构建查询交易的请求
+    fn build_request(&self, tx_hash_hex: &str) -> SourceRequest;
+
+    /// 构建查询最新区块的请求
+    ///
+    /// 响应只给出交易所在区块的数据源需要据此计算确认数；直接报告确认数的数据源返回 None。
+    fn head_request(&self) -> Option<SourceRequest>;
+
+    /// 解析响应并判定代币合约、收款地址与金额
+    fn parse_response(
+        &self,
+        response: &[u8],
+        expected: &ExpectedTransfer,
+    ) -> Result<TronTxVerification, &'static str>;
+}
+
+/// 数据源类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+pub enum TronSourceKind {
+    /// TronGrid REST API 及其兼容服务
+    TronGrid,
+    /// TronScan API
+    TronScan,
+    /// 自建全节点 HTTP API
+    FullNode,
+}
+
+/// 数据源配置（持久化在 OCW 本地存储中）
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct TronSourceConfig {
+    pub kind: TronSourceKind,
+    pub base_url: String,
+}
+
+impl TronSourceConfig {
+    pub fn new(kind: TronSourceKind, base_url: &str) -> Self {
+        Self { kind, base_url: String::from(base_url.trim_end_matches('/')) }
+    }
+
+    pub fn trongrid(base_url: &str) -> Self {
+        Self::new(TronSourceKind::TronGrid, base_url)
+    }
+
+    pub fn tronscan(base_url: &str) -> Self {
+        Self::new(TronSourceKind::TronScan, base_url)
+    }
+
+    pub fn full_node(base_url: &str) -> Self {
+        Self::new(TronSourceKind::FullNode, base_url)
+    }
+
+    /// 创建对应的数据源实现
+    pub fn data_source(&self) -> Box<dyn TronDataSource> {
+        let base_url = self.base_url.clone();
+        match self.kind {
+            TronSourceKind::TronGrid => Box::new(TronGridSource { base_url }),
+            TronSourceKind::TronScan => Box::new(TronScanSource { base_url }),
+            TronSourceKind::FullNode => Box::new(FullNodeSource { base_url }),
+        }
+    }
+}
+
+// ==================== TronGrid ====================
+
+/// TronGrid REST 数据源
+pub struct TronGridSource {
+    pub base_url: String,
+}
+
+impl TronDataSource for TronGridSource {
+    fn kind(&self) -> TronSourceKind {
+        TronSourceKind::TronGrid
+    }
+
+    fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    fn build_request(&self, tx_hash_hex: &str) -> SourceRequest {
+        SourceRequest {
+            method: HttpMethod::Get,
+            url: format!("{}/v1/transactions/{}", self.base_url, tx_hash_hex),
+            body: Vec::new(),
+            headers: Vec::new(),
+        }
+    }
+
+    fn head_request(&self) -> Option<SourceRequest> {
+        Some(now_block_request(&self.base_url))
+    }
+
+    fn parse_response(
+        &self,
+        response: &[u8],
+        expected: &ExpectedTransfer,
+    ) -> Result<TronTxVerification, &'static str> {
+        let json = parse_json(response)?;
+        // TronGrid 对尚未索引的交易返回空 data 数组
+        let tx = match field(&json, "data") {
+            Some(JsonValue::Array(items)) => items.first().ok_or("Transaction not found")?,
+            _ => return Err("Unrecognized TronGrid response"),
+        };
+
+        let mut result = new_result(expected.amount);
+        result.block_number = field(tx, "blockNumber").and_then(json_to_u64);
+        let succeeded = match field(tx, "ret") {
+            Some(JsonValue::Array(rets)) => rets
+                .first()
+                .and_then(|r| string_field(r, "contractRet"))
+                .as_deref()
+                == Some("SUCCESS"),
+            _ => false,
+        };
+        if !succeeded {
+            result.error = Some(b"Transaction not successful".to_vec());
+            return Ok(result);
+        }
+
+        // 只看交易唯一的合约调用 raw_data.contract[0]：`raw_data.data` 是付款方可任意填写的备注，
+        // 其中伪造的 transfer 调用数据不能当作到账依据
+        let contract = match field(tx, "raw_data").and_then(|raw| field(raw, "contract")) {
+            Some(JsonValue::Array(items)) => items.first(),
+            _ => None,
+        }
+        .ok_or("Unrecognized TronGrid response")?;
+
+        // 必须是对预期代币合约的 TriggerSmartContract 调用：TRX `TransferContract`
+        // 的 to_address / amount 不代表代币到账
+        let value = field(contract, "parameter").and_then(|p| field(p, "value"));
+        let token = AddressForms::new(expected.token_contract);
+        let is_token_call = string_field(contract, "type").as_deref() == Some("TriggerSmartContract")
+            && value
+                .and_then(|v| string_field(v, "contract_address"))
+                .map_or(false, |c| token.matches(&c));
+        let Some(value) = value.filter(|_| is_token_call) else {
+            result.error = Some(b"Unexpected token contract".to_vec());
+            return Ok(result);
+        };
+
+        // 调用数据：transfer(to, amount)
+        let Some((to, raw_amount)) = string_field(value, "data").and_then(|d| decode_transfer_calldata(&d)) else {
+            result.error = Some(b"Not a token transfer".to_vec());
+            return Ok(result);
+        };
+
+        let forms = AddressForms::new(expected.to);
+        if !forms.matches(&to) {
+            result.error = Some(b"Recipient address mismatch".to_vec());
+            return Ok(result);
+        }
+        result.to_address = Some(to.into_bytes());
+        result.from_address = string_field(value, "owner_address").map(String::into_bytes);
+
+        let actual_amount = raw_amount.and_then(|raw| expected.normalize_amount(raw));
+        Ok(conclude_amount(result, expected.amount, actual_amount))
+    }
+}
+
+// ==================== TronScan ====================
+
+/// TronScan 数据源
+pub struct TronScanSource {
+    pub base_url: String,
+}
+
+impl TronDataSource for TronScanSource {
+    fn kind(&self) -> TronSourceKind {
+        TronSourceKind::TronScan
+    }
+
+    fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    fn build_request(&self, tx_hash_hex: &str) -> SourceRequest {
+        SourceRequest {
+            method: HttpMethod::Get,
+            url: format!("{}/api/transaction-info?hash={}", self.base_url, tx_hash_hex),
+            body: Vec::new(),
+            headers: Vec::new(),
+        }
+    }
+
+    /// 响应自带 `confirmations` / `confirmed`，在 `parse_response` 中检查
+    fn head_request(&self) -> Option<SourceRequest> {
+        None
+    }
+
+    fn parse_response(
+        &self,
+        response: &[u8],
+        expected: &ExpectedTransfer,
+    ) -> Result<TronTxVerification, &'static str> {
+        let json = parse_json(response)?;
+        // TronScan 对未知交易返回空对象
+        match &json {
+            JsonValue::Object(obj) if obj.is_empty() => return Err("Transaction not found"),
+            JsonValue::Object(_) => {},
+            _ => return Err("Unrecognized TronScan response"),
+        }
+
+        let mut result = new_result(expected.amount);
+        result.confirmations = field(&json, "confirmations")
+            .and_then(json_to_u64)
+            .map(|c| c.min(u32::MAX as u64) as u32)
+            .unwrap_or(0);
+
+        // 尚未固化且确认数不足：暂不下结论
+        let confirmed = matches!(field(&json, "confirmed"), Some(JsonValue::Boolean(true)));
+        if !confirmed && result.confirmations < expected.min_confirmations {
+            return Err("Insufficient confirmations");
+        }
+
+        if string_field(&json, "contractRet").as_deref() != Some("SUCCESS") {
+            result.error = Some(b"Transaction not successful".to_vec());
+            return Ok(result);
+        }
+
+        let transfers = match field(&json, "trc20TransferInfo")
+            .or_else(|| field(&json, "tokenTransferInfo"))
+        {
+            Some(JsonValue::Array(items)) => items.iter().collect::<Vec<_>>(),
+            Some(item @ JsonValue::Object(_)) => alloc::vec![item],
+            _ => Vec::new(),
+        };
+
+        let forms = AddressForms::new(expected.to);
+        let token = AddressForms::new(expected.token_contract);
+        let transfer = transfers.into_iter().find(|t| {
+            string_field(t, "contract_address").map_or(false, |c| token.matches(&c))
+                && string_field(t, "to_address").map_or(false, |to| forms.matches(&to))
+        });
+        let Some(transfer) = transfer else {
+            result.error = Some(b"Recipient address mismatch".to_vec());
+            return Ok(result);
+        };
+
+        result.to_address = string_field(transfer, "to_address").map(String::into_bytes);
+        result.from_address = string_field(transfer, "from_address").map(String::into_bytes);
+        let actual_amount = field(transfer, "amount_str")
+            .or_else(|| field(transfer, "amount"))
+            .and_then(json_to_u128)
+            .and_then(|raw| expected.normalize_amount(raw));
+        Ok(conclude_amount(result, expected.amount, actual_amount))
+    }
+}
+
+// ==================== 全节点 ====================
+
+/// 自建全节点 HTTP API 数据源
+///
+/// 运营者可指向自己的 java-tron 节点，避免依赖公共 API 的限流与可用性。
+pub struct FullNodeSource {
+    pub base_url: String,
+}
+
+impl TronDataSource for FullNodeSource {
+    fn kind(&self) -> TronSourceKind {
+        TronSourceKind::FullNode
+    }
+
+    fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    fn build_request(&self, tx_hash_hex: &str) -> SourceRequest {
+        SourceRequest {
+            method: HttpMethod::Post,
+            url: format!("{}/wallet/gettransactioninfobyid", self.base_url),
+            body: format!(r#"{{"value":"{}"}}"#, tx_hash_hex).into_bytes(),
+            headers: alloc::vec![("Content-Type".to_string(), "application/json".to_string())],
+        }
+    }
+
+    fn head_request(&self) -> Option<SourceRequest> {
+        Some(now_block_request(&self.base_url))
+    }
+
+    fn parse_response(
+        &self,
+        response: &[u8],
+        expected: &ExpectedTransfer,
+    ) -> Result<TronTxVerification, &'static str> {
+        let json = parse_json(response)?;
+        // 全节点对未知（或尚未打包）的交易返回空对象
+        match &json {
+            JsonValue::Object(obj) if obj.is_empty() => return Err("Transaction not found"),
+            JsonValue::Object(_) => {},
+            _ => return Err("Unrecognized full node response"),
+        }
+
+        let mut result = new_result(expected.amount);
+        result.block_number = field(&json, "blockNumber").and_then(json_to_u64);
+        let receipt_ok = field(&json, "receipt")
+            .and_then(|r| string_field(r, "result"))
+            .as_deref()
+            == Some("SUCCESS");
+        if !receipt_ok {
+            result.error = Some(b"Transaction not successful".to_vec());
+            return Ok(result);
+        }
+
+        let token_hex20 = decode_tron_address(expected.token_contract)
+            .map(|d| bytes_to_hex(&d[1..]))
+            .ok_or("Invalid token contract")?;
+        let forms = AddressForms::new(expected.to);
+
+        let logs = match field(&json, "log") {
+            Some(JsonValue::Array(items)) => items.as_slice(),
+            _ => &[][..],
+        };
+        // 在代币合约的 Transfer 事件中寻找收款方为 expected.to 的一条
+        let Some(transfer) = find_transfer_log(logs, &token_hex20, &forms) else {
+            result.error = Some(b"Recipient address mismatch".to_vec());
+            return Ok(result);
+        };
+
+        result.from_address = Some(transfer.from.into_bytes());
+        result.to_address = Some(transfer.to.into_bytes());
+        let actual_amount = transfer.raw_amount.and_then(|raw| expected.normalize_amount(raw));
+        Ok(conclude_amount(result, expected.amount, actual_amount))
+    }
+}
+
+// ==================== 确认数 ====================
+
+/// 全节点 HTTP API `POST /wallet/getnowblock`（TronGrid 同样提供）
+fn now_block_request(base_url: &str) -> SourceRequest {
+    SourceRequest {
+        method: HttpMethod::Post,
+        url: format!("{}/wallet/getnowblock", base_url),
+        body: Vec::new(),
+        headers: Vec::new(),
+    }
+}
+
+/// 解析 `getnowblock` 响应中的最新区块号（`block_header.raw_data.number`）
+pub fn parse_now_block(response: &[u8]) -> Result<u64, &'static str> {
+    let json = parse_json(response)?;
+    field(&json, "block_header")
+        .and_then(|header| field(header, "raw_data"))
+        .and_then(|raw| field(raw, "number"))
+        .and_then(json_to_u64)
+        .ok_or("Unrecognized block response")
+}
+
+/// 按最新区块计算确认数，不足 `min_confirmations` 时暂不下结论
+///
+/// 交易所在区块未知（尚未打包）同样视为确认数不足。
+pub fn apply_confirmations(
+    mut result: TronTxVerification,
+    head: u64,
+    min_confirmations: u32,
+) -> Result<TronTxVerification, &'static str> {
+    let block = result.block_number.ok_or("Insufficient confirmations")?;
+    let confirmations = head.saturating_sub(block).saturating_add(1);
+    if confirmations < min_confirmations as u64 {
+        return Err("Insufficient confirmations");
+    }
+    result.confirmations = confirmations.min(u32::MAX as u64) as u32;
+    Ok(result)
+}
+
+// ==================== Transfer 事件 ====================
+
+/// 解码后的 `Transfer` 事件
+pub(crate) struct TransferLog {
+    pub from: String,
+    pub to: String,
+    pub raw_amount: Option<u128>,
+}
+
+/// 在事件日志中查找指定合约、收款方为 `to` 的 `Transfer` 事件
+///
+/// TRON 全节点与 EVM `eth_getTransactionReceipt` 的日志结构相同
+/// （`address` / `topics` / `data`），仅地址是否带 `0x` 前缀不同。
+pub(crate) fn find_transfer_log(
+    logs: &[JsonValue],
+    token_hex20: &str,
+    to: &AddressForms,
+) -> Option<TransferLog> {
+    logs.iter().find_map(|log| {
+        let address = string_field(log, "address")?;
+        if !address.trim_start_matches("0x").eq_ignore_ascii_case(token_hex20) {
+            return None;
+        }
+        let topics: Vec<String> = match field(log, "topics") {
+            Some(JsonValue::Array(items)) => items.iter().filter_map(json_to_string).collect(),
+            _ => return None,
+        };
+        if topics.len() < 3
+            || !topics[0].trim_start_matches("0x").eq_ignore_ascii_case(TRANSFER_EVENT_TOPIC)
+        {
+            return None;
+        }
+        if !to.matches(&topics[2]) {
+            return None;
+        }
+        Some(TransferLog {
+            from: topics[1].clone(),
+            to: topics[2].clone(),
+            raw_amount: string_field(log, "data").and_then(|d| hex_to_u128(&d)),
+        })
+    })
+}
+
+// ==================== 金额判定 ====================
+
+/// 根据预期金额判定实际金额的匹配状态
+///
+/// 返回 `(状态, 是否可接受)`，±0.5% 视为精确匹配，多付可接受。
+pub fn judge_amount(expected_amount: u64, actual_amount: Option<u64>) -> (AmountStatus, bool) {
+    let Some(actual) = actual_amount else {
+        log::error!(target: "trc20-verifier", "Failed to extract amount from response");
+        return (AmountStatus::Invalid, false);
+    };
+
+    let min_exact = expected_amount * 995 / 1000;  // -0.5%
+    let max_exact = expected_amount * 1005 / 1000; // +0.5%
+    let severe_threshold = expected_amount / 2;    // 50%
+
+    if actual >= min_exact && actual <= max_exact {
+        (AmountStatus::Exact, true)
+    } else if actual > max_exact {
+        let excess = actual.saturating_sub(expected_amount);
+        log::info!(target: "trc20-verifier", "Overpaid: expected={}, actual={}, excess={}",
+            expected_amount, actual, excess);
+        (AmountStatus::Overpaid { excess }, true)
+    } else if actual >= severe_threshold {
+        let shortage = expected_amount.saturating_sub(actual);
+        log::warn!(target: "trc20-verifier", "Underpaid: expected={}, actual={}, shortage={}",
+            expected_amount, actual, shortage);
+        (AmountStatus::Underpaid { shortage }, false)
+    } else if actual > 0 {
+        let shortage = expected_amount.saturating_sub(actual);
+        log::error!(target: "trc20-verifier", "Severely underpaid: expected={}, actual={}",
+            expected_amount, actual);
+        (AmountStatus::SeverelyUnderpaid { shortage }, false)
+    } else {
+        (AmountStatus::Invalid, false)
+    }
+}
+
+/// 写入金额判定结果
+pub(crate) fn conclude_amount(
+    mut result: TronTxVerification,
+    expected_amount: u64,
+    actual_amount: Option<u64>,
+) -> TronTxVerification {
+    let (amount_status, is_acceptable) = judge_amount(expected_amount, actual_amount);
+    result.actual_amount = actual_amount;
+    result.amount_status = amount_status.clone();
+
+    if !is_acceptable {
+        let error_msg = match &amount_status {
+            AmountStatus::Underpaid { shortage } =>
+                format!("Underpaid by {} (expected {}, got {})",
+                    shortage, expected_amount, actual_amount.unwrap_or(0)),
+            AmountStatus::SeverelyUnderpaid { shortage } =>
+                format!("Severely underpaid by {} (possible fraud)", shortage),
+            AmountStatus::Invalid =>
+                "Invalid or zero amount".to_string(),
+            _ => "Amount mismatch".to_string(),
+        };
+        result.error = Some(error_msg.into_bytes());
+        return result;
+    }
+
+    result.is_valid = true;
+    result
+}
+
+pub(crate) fn new_result(expected_amount: u64) -> TronTxVerification {
+    TronTxVerification { expected_amount: Some(expected_amount), ..Default::default() }
+}
+
+// ==================== JSON 工具 ====================
+
+pub(crate) fn parse_json(response: &[u8]) -> Result<JsonValue, &'static str> {
+    let text = core::str::from_utf8(response).map_err(|_| "Invalid UTF-8 response")?;
+    lite_json::parse_json(text).map_err(|_| "Invalid JSON response")
+}
+
+/// 读取对象的直接字段
+pub(crate) fn field<'a>(value: &'a JsonValue, key: &str) -> Option<&'a JsonValue> {
+    match value {
+        JsonValue::Object(obj) => obj
+            .iter()
+            .find(|(k, _)| k.iter().copied().eq(key.chars()))
+            .map(|(_, v)| v),
+        _ => None,
+    }
+}
+
+pub(crate) fn string_field(value: &JsonValue, key: &str) -> Option<String> {
+    field(value, key).and_then(json_to_string)
+}
+
+fn json_to_string(value: &JsonValue) -> Option<String> {
+    match value {
+        JsonValue::String(chars) => Some(chars.iter().collect()),
+        _ => None,
+    }
+}
+
+/// 数字或十进制字符串转 u64（不接受小数）
+fn json_to_u64(value: &JsonValue) -> Option<u64> {
+    match value {
+        JsonValue::Number(n) if n.fraction_length == 0 => Some(n.integer),
+        JsonValue::String(chars) => chars.iter().collect::<String>().parse::<u64>().ok(),
+        _ => None,
+    }
+}
+
+/// 数字或十进制字符串转 u128（18 位精度代币的原始金额可能超出 u64）
+fn json_to_u128(value: &JsonValue) -> Option<u128> {
+    match value {
+        JsonValue::String(chars) => chars.iter().collect::<String>().parse::<u128>().ok(),
+        other => json_to_u64(other).map(u128::from),
+    }
+}
+
+/// 十六进制（可含 `0x` 前缀与前导零）转 u128，超出范围返回 None
+pub(crate) fn hex_to_u128(hex: &str) -> Option<u128> {
+    let digits = hex.trim_start_matches("0x").trim_start_matches('0');
+    if digits.is_empty() {
+        return Some(0);
+    }
+    if digits.len() > 32 {
+        return None;
+    }
+    u128::from_str_radix(digits, 16).ok()
+}
+
+/// 解码 `transfer(address,uint256)` 调用数据，返回 (收款地址 hex20, 原始金额)
+fn decode_transfer_calldata(data: &str) -> Option<(String, Option<u128>)> {
+    let data = data.trim_start_matches("0x").to_ascii_lowercase();
+    if data.len() < 8 + 128 || !data.starts_with(TRANSFER_METHOD_ID) {
+        return None;
+    }
+    let to = String::from(&data[8 + 24..8 + 64]);
+    let amount = hex_to_u128(&data[8 + 64..8 + 128]);
+    Some((to, amount))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::replay;
+
+    /// 有效 Base58 收款地址，对应 hex21 `410102...14`
+    const RECIPIENT: &[u8] = b"TA4Y62o6YC2Zsck9rZVGTvqW1AQ7X9zTnj";
+    const TX: &str = "abababababababababababababababababababababababababababababababab";
+
+    fn parse(source: TronSourceConfig, response: &str, amount: u64) -> Result<TronTxVerification, &'static str> {
+        source.data_source().parse_response(response.as_bytes(), &ExpectedTransfer::usdt(RECIPIENT, amount))
+    }
+
+    #[test]
+    fn build_requests_per_source() {
+        let grid = TronSourceConfig::trongrid("https://api.trongrid.io/").data_source();
+        assert_eq!(grid.build_request(TX).url, format!("https://api.trongrid.io/v1/transactions/{}", TX));
+
+        let scan = TronSourceConfig::tronscan("https://apilist.tronscanapi.com").data_source();
+        let req = scan.build_request(TX);
+        assert_eq!(req.method, HttpMethod::Get);
+        assert_eq!(req.url, format!("https://apilist.tronscanapi.com/api/transaction-info?hash={}", TX));
+
+        let node = TronSourceConfig::full_node("http://127.0.0.1:8090").data_source();
+        let req = node.build_request(TX);
+        assert_eq!(req.method, HttpMethod::Post);
+        assert_eq!(req.url, "http://127.0.0.1:8090/wallet/gettransactioninfobyid");
+        assert_eq!(req.body, format!(r#"{{"value":"{}"}}"#, TX).into_bytes());
+    }
+
+    #[test]
+    fn trongrid_parses_trigger_smart_contract() {
+        let ok = parse(TronSourceConfig::trongrid("g"), &replay::trongrid_transfer(RECIPIENT, 1_000_000), 1_000_000).unwrap();
+        assert!(ok.is_valid);
+        assert_eq!(ok.amount_status, AmountStatus::Exact);
+        assert_eq!(ok.from_address, Some(format!("41{}", replay::PAYER_HEX20).into_bytes()));
+
+        let under = parse(TronSourceConfig::trongrid("g"), &replay::trongrid_transfer(RECIPIENT, 900_000), 1_000_000).unwrap();
+        assert!(!under.is_valid);
+        assert_eq!(under.amount_status, AmountStatus::Underpaid { shortage: 100_000 });
+
+        let reverted = replay::trongrid_transfer(RECIPIENT, 1_000_000).replace(r#""contractRet":"SUCCESS""#, r#""contractRet":"REVERT""#);
+        assert_eq!(
+            parse(TronSourceConfig::trongrid("g"), &reverted, 1_000_000).unwrap().error,
+            Some(b"Transaction not successful".to_vec())
+        );
+
+        assert_eq!(parse(TronSourceConfig::trongrid("g"), replay::TRONGRID_NOT_FOUND, 1), Err("Transaction not found"));
+    }
+
+    #[test]
+    fn trongrid_ignores_forged_memo() {
+        // 付款方向自己的另一个地址转 0.01 USDT，备注里伪造付给 RECIPIENT 的完整 transfer 调用数据
+        let forged = replay::transfer_calldata(RECIPIENT, 1_000_000);
+        let tx = replay::trongrid_transfer_with_memo(b"T9yD14Nj9j7xAB4dbGeiX9h8unkKHxuWwb", 10_000, &forged);
+
+        let result = parse(TronSourceConfig::trongrid("g"), &tx, 1_000_000).unwrap();
+        assert!(!result.is_valid);
+        assert_eq!(result.error, Some(b"Recipient address mismatch".to_vec()));
+
+        // 备注不影响真实转账的判定
+        let honest = replay::trongrid_transfer_with_memo(RECIPIENT, 1_000_000, "6f72646572");
+        assert!(parse(TronSourceConfig::trongrid("g"), &honest, 1_000_000).unwrap().is_valid);
+    }
+
+    #[test]
+    fn trongrid_rejects_trx_transfer() {
+        // 收款地址与金额都匹配的 TRX 转账不能当作 USDT 到账
+        let trx = parse(TronSourceConfig::trongrid("g"), &replay::trongrid_trx_transfer(RECIPIENT, 1_000_000), 1_000_000).unwrap();
+        assert!(!trx.is_valid);
+        assert_eq!(trx.error, Some(b"Unexpected token contract".to_vec()));
+
+        // 其他 TRC20 合约同样拒绝
+        let usdc = replay::trongrid_transfer(RECIPIENT, 1_000_000).replace(
+            &AddressForms::new(USDT_CONTRACT.as_bytes()).hex21.unwrap(),
+            &AddressForms::new(crate::USDC_TRC20_CONTRACT.as_bytes()).hex21.unwrap(),
+        );
+        assert_eq!(
+            parse(TronSourceConfig::trongrid("g"), &usdc, 1_000_000).unwrap().error,
+            Some(b"Unexpected token contract".to_vec())
+        );
+    }
+
+    #[test]
+    fn tronscan_checks_contract_and_recipient() {
+        let ok = parse(TronSourceConfig::tronscan("s"), &replay::tronscan_transfer(RECIPIENT, 2_000_000), 2_000_000).unwrap();
+        assert!(ok.is_valid);
+        assert_eq!(ok.confirmations, 30);
+
+        let other = parse(TronSourceConfig::tronscan("s"), &replay::tronscan_transfer(b"TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t", 2_000_000), 2_000_000).unwrap();
+        assert_eq!(other.error, Some(b"Recipient address mismatch".to_vec()));
+
+        assert_eq!(parse(TronSourceConfig::tronscan("s"), "{}", 1), Err("Transaction not found"));
+    }
+
+    #[test]
+    fn full_node_reads_transfer_log() {
+        let ok = parse(TronSourceConfig::full_node("n"), &replay::full_node_transfer(RECIPIENT, 3_000_000), 3_000_000).unwrap();
+        assert!(ok.is_valid);
+        assert_eq!(ok.actual_amount, Some(3_000_000));
+
+        let failed = r#"{"id":"ab","receipt":{"result":"REVERT"},"log":[]}"#;
+        assert_eq!(
+            parse(TronSourceConfig::full_node("n"), failed, 1).unwrap().error,
+            Some(b"Transaction not successful".to_vec())
+        );
+        assert_eq!(parse(TronSourceConfig::full_node("n"), "{}", 1), Err("Transaction not found"));
+        assert_eq!(parse(TronSourceConfig::full_node("n"), "<html>", 1), Err("Invalid JSON response"));
+    }
+
+    #[test]
+    fn parsers_check_expected_token_contract() {
+        let usdc = ExpectedTransfer {
+            token_contract: crate::USDC_TRC20_CONTRACT.as_bytes(),
+            ..ExpectedTransfer::usdt(RECIPIENT, 1_000_000)
+        };
+        let node = TronSourceConfig::full_node("n").data_source();
+
+        // USDT 转账不满足 USDC 预期
+        let usdt_transfer = replay::full_node_transfer(RECIPIENT, 1_000_000);
+        let wrong_token = node.parse_response(usdt_transfer.as_bytes(), &usdc).unwrap();
+        assert_eq!(wrong_token.error, Some(b"Recipient address mismatch".to_vec()));
+
+        let usdc_transfer = replay::full_node_token_transfer(crate::USDC_TRC20_CONTRACT.as_bytes(), RECIPIENT, 1_000_000);
+        assert!(node.parse_response(usdc_transfer.as_bytes(), &usdc).unwrap().is_valid);
+
+        let scan = TronSourceConfig::tronscan("s").data_source();
+        let scan_usdt = replay::tronscan_transfer(RECIPIENT, 1_000_000);
+        assert!(!scan.parse_response(scan_usdt.as_bytes(), &usdc).unwrap().is_valid);
+    }
+
+    #[test]
+    fn tronscan_waits_for_confirmations() {
+        let pending = replay::tronscan_transfer(RECIPIENT, 1_000_000)
+            .replace(r#""confirmed":true,"confirmations":30"#, r#""confirmed":false,"confirmations":3"#);
+        assert_eq!(parse(TronSourceConfig::tronscan("s"), &pending, 1_000_000), Err("Insufficient confirmations"));
+    }
+
+    #[test]
+    fn block_sources_compute_confirmations_from_head() {
+        let grid = TronSourceConfig::trongrid("https://api.trongrid.io").data_source();
+        let node = TronSourceConfig::full_node("http://127.0.0.1:8090").data_source();
+        assert_eq!(grid.head_request().unwrap().url, "https://api.trongrid.io/wallet/getnowblock");
+        assert_eq!(node.head_request().unwrap().url, "http://127.0.0.1:8090/wallet/getnowblock");
+        assert!(TronSourceConfig::tronscan("s").data_source().head_request().is_none());
+
+        let head = parse_now_block(replay::now_block(replay::FIXTURE_BLOCK + 18).as_bytes()).unwrap();
+        assert_eq!(head, replay::FIXTURE_BLOCK + 18);
+        assert_eq!(parse_now_block(b"{}"), Err("Unrecognized block response"));
+
+        let result = parse(TronSourceConfig::full_node("n"), &replay::full_node_transfer(RECIPIENT, 1_000_000), 1_000_000).unwrap();
+        assert_eq!(result.block_number, Some(replay::FIXTURE_BLOCK));
+        // 交易所在区块计 1 个确认
+        assert_eq!(apply_confirmations(result.clone(), head, 20), Err("Insufficient confirmations"));
+        assert_eq!(apply_confirmations(result.clone(), head, 19).unwrap().confirmations, 19);
+
+        let grid_result = parse(TronSourceConfig::trongrid("g"), &replay::trongrid_transfer(RECIPIENT, 1_000_000), 1_000_000).unwrap();
+        assert_eq!(grid_result.block_number, Some(replay::FIXTURE_BLOCK));
+
+        // 未打包（无区块号）视为确认数不足
+        let unpacked = TronTxVerification { block_number: None, ..result };
+        assert_eq!(apply_confirmations(unpacked, head, 1), Err("Insufficient confirmations"));
+    }
+
+    #[test]
+    fn normalize_amount_by_decimals() {
+        assert_eq!(normalize_amount(1_500_000, 6), Some(1_500_000));
+        // 18 位精度：1.5 代币
+        assert_eq!(normalize_amount(1_500_000_000_000_000_000, 18), Some(1_500_000));
+        // 超出 u64 的 18 位精度原始金额仍可换算
+        assert_eq!(normalize_amount(100_000 * 10u128.pow(18), 18), Some(100_000_000_000));
+        assert_eq!(normalize_amount(15, 1), Some(1_500_000));
+        assert_eq!(normalize_amount(u128::MAX, 6), None);
+        assert_eq!(hex_to_u128("0x0de0b6b3a7640000"), Some(10u128.pow(18)));
+    }
+
+    #[test]
+    fn judge_amount_thresholds() {
+        assert_eq!(judge_amount(1_000_000, Some(1_004_000)), (AmountStatus::Exact, true));
+        assert_eq!(judge_amount(1_000_000, Some(1_010_000)), (AmountStatus::Overpaid { excess: 10_000 }, true));
+        assert_eq!(judge_amount(1_000_000, Some(400_000)), (AmountStatus::SeverelyUnderpaid { shortage: 600_000 }, false));
+        assert_eq!(judge_amount(1_000_000, Some(0)), (AmountStatus::Invalid, false));
+        assert_eq!(judge_amount(1_000_000, None), (AmountStatus::Invalid, false));
+    }
+}
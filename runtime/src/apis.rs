@@ -42,8 +42,9 @@ use sp_version::RuntimeVersion;
 
 // Local module imports
 use super::{
-	AccountId, Aura, Balance, Block, Executive, Grandpa, InherentDataExt, Nonce, Runtime,
-	RuntimeCall, RuntimeGenesisConfig, SessionKeys, StorageService, System, TransactionPayment, VERSION,
+	AccountId, Aura, Balance, Block, EntityMarket, Executive, Grandpa, Hash, InherentDataExt, Nonce, Runtime,
+	RuntimeCall, RuntimeGenesisConfig, SessionKeys, StorageLifecycle, StorageService, System, TransactionPayment,
+	VERSION,
 };
 
 impl_runtime_apis! {
@@ -329,4 +330,42 @@ impl_runtime_apis! {
 				.collect()
 		}
 	}
+
+	impl pallet_storage_lifecycle::runtime_api::StorageLifecycleApi<Block, Hash> for Runtime {
+		fn archive_commitment(
+			data_type: Vec<u8>,
+			batch_id: u64,
+		) -> Option<pallet_storage_lifecycle::ArchiveCommitment<Hash>> {
+			let data_type = pallet_storage_lifecycle::DataType::try_from(data_type).ok()?;
+			StorageLifecycle::archive_commitment(data_type, batch_id)
+		}
+
+		fn verify_archived_record(
+			data_type: Vec<u8>,
+			batch_id: u64,
+			index: u32,
+			leaf: Vec<u8>,
+			proof: Vec<Hash>,
+		) -> bool {
+			StorageLifecycle::verify_record(&data_type, batch_id, index, &leaf, &proof)
+		}
+	}
+
+	impl pallet_entity_market::runtime_api::EntityMarketApi<Block, Balance, Balance> for Runtime {
+		fn candles(
+			shop_id: u64,
+			interval: pallet_entity_market::CandleInterval,
+			limit: u32,
+		) -> Vec<pallet_entity_market::Candle<Balance, Balance>> {
+			EntityMarket::get_candles(shop_id, interval, limit)
+		}
+
+		fn order_book_depth(shop_id: u64, depth: u32) -> pallet_entity_market::OrderBookDepth<Balance, Balance> {
+			EntityMarket::get_order_book_depth(shop_id, depth)
+		}
+
+		fn market_summary(shop_id: u64) -> pallet_entity_market::MarketSummary<Balance, Balance> {
+			EntityMarket::get_market_summary(shop_id)
+		}
+	}
 }
@@ -42,9 +42,9 @@ use sp_version::RuntimeVersion;
 // Local module imports
 use super::{
 	AccountId, Aura, Balance, Balances, Block, BlockNumber, Hash, Nonce, PalletInfo, Runtime,
-	RuntimeCall, RuntimeEvent, RuntimeFreezeReason, RuntimeHoldReason, RuntimeOrigin, RuntimeTask,
+	RuntimeCall, RuntimeEvent, Signature, UncheckedExtrinsic, RuntimeFreezeReason, RuntimeHoldReason, RuntimeOrigin, RuntimeTask,
 	System, Timestamp, EXISTENTIAL_DEPOSIT, SLOT_DURATION, VERSION, UNIT, MINUTES, HOURS, DAYS,
-	TechnicalCommittee, ArbitrationCommittee, TreasuryCouncil, ContentCommittee,
+	TechnicalCommittee, ArbitrationCommittee, TreasuryCouncil, ContentCommittee, StorageLifecycle,
 	// Entity types (原 ShareMall)
 	Assets, Escrow, EntityRegistry, EntityShop, EntityService, EntityTransaction, EntityToken, EntityKyc,
 };
@@ -240,6 +240,12 @@ impl pallet_trading_pricing::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type MaxPriceDeviation = ConstU16<2000>; // 20% 最大价格偏离
 	type ExchangeRateUpdateInterval = ConstU32<{ 24 * HOURS }>; // 24小时更新汇率
+	type OracleGovernanceOrigin = frame_system::EnsureRoot<AccountId>;
+	type MaxOracleFeeders = ConstU32<21>;
+	type OracleRoundLength = ConstU32<{ 10 * MINUTES }>; // 每10分钟一轮喂价
+	type MinOracleQuotes = ConstU32<3>;
+	type MaxQuoteDeviation = ConstU16<300>; // 偏离中位数 3% 视为异常报价
+	type DefaultTwapWindow = ConstU32<{ HOURS }>; // 1小时 TWAP
 }
 
 // -------------------- Credit (信用风控) --------------------
@@ -269,7 +275,8 @@ pub struct TradingPricingProvider;
 
 impl pallet_trading_common::PricingProvider<Balance> for TradingPricingProvider {
 	fn get_cos_to_usd_rate() -> Option<Balance> {
-		let price = pallet_trading_pricing::Pallet::<Runtime>::get_cos_market_price_weighted();
+		// 预言机模式下为喂价聚合 TWAP，否则为 P2P 成交加权均价
+		let price = pallet_trading_pricing::Pallet::<Runtime>::get_nxs_usdt_price();
 		if price > 0 {
 			Some(price as Balance)
 		} else {
@@ -299,6 +306,7 @@ impl pallet_trading_maker::Config for Runtime {
 	type ContentRegistry = pallet_storage_service::Pallet<Runtime>;
 	type WeightInfo = ();
 	type TreasuryAccount = TreasuryAccountId; // 国库账户
+	type ArchiveCommitter = StorageLifecycle; // 惩罚记录归档承诺
 }
 
 // -------------------- P2P Trading (统一 Buy + Sell，替代 OTC + Swap) --------------------
@@ -376,6 +384,30 @@ impl pallet_trading_p2p::pallet::IdentityVerificationProvider<AccountId> for P2p
 	}
 }
 
+// -------------------- OCW 交易构造（签名载荷 + 无签名交易） --------------------
+
+impl frame_system::offchain::SigningTypes for Runtime {
+	type Public = <Signature as sp_runtime::traits::Verify>::Signer;
+	type Signature = Signature;
+}
+
+impl<LocalCall> frame_system::offchain::CreateTransactionBase<LocalCall> for Runtime
+where
+	RuntimeCall: From<LocalCall>,
+{
+	type RuntimeCall = RuntimeCall;
+	type Extrinsic = UncheckedExtrinsic;
+}
+
+impl<LocalCall> frame_system::offchain::CreateBare<LocalCall> for Runtime
+where
+	RuntimeCall: From<LocalCall>,
+{
+	fn create_bare(call: RuntimeCall) -> UncheckedExtrinsic {
+		UncheckedExtrinsic::new_bare(call)
+	}
+}
+
 impl pallet_trading_p2p::Config for Runtime {
 	type Currency = Balances;
 	type Timestamp = TimestampProvider;
@@ -392,6 +424,7 @@ impl pallet_trading_p2p::Config for Runtime {
 	>;
 	type ArbitratorOrigin = frame_system::EnsureRoot<AccountId>;
 	type CidLockManager = pallet_storage_service::Pallet<Runtime>;
+	type ArchiveCommitter = StorageLifecycle; // 订单归档承诺
 	type WeightInfo = ();
 	// Buy-side 常量
 	type BuyOrderTimeout = ConstU64<3600000>; // 1小时（毫秒）
@@ -418,6 +451,8 @@ impl pallet_trading_p2p::Config for Runtime {
 	type VerificationReward = ConstU128<{ UNIT / 10 }>; // 0.1 NXS
 	type SellFeeRateBps = ConstU32<10>; // 0.1%
 	type MinSellFee = ConstU128<{ UNIT / 10 }>; // 0.1 NXS
+	// OCW 签名密钥（keystore 类型 p2pv）
+	type AuthorityId = pallet_trading_p2p::ocw::crypto::P2pAuthId;
 }
 
 // ============================================================================
@@ -489,6 +524,17 @@ impl pallet_storage_service::Config for Runtime {
 	type MonthlyPublicFeeQuota = ConstU128<{ 10 * UNIT }>;
 	type QuotaResetPeriod = ConstU32<{ 30 * DAYS }>;
 	type DefaultBillingPeriod = ConstU32<{ 30 * DAYS }>;
+	// 存储挑战：每小时一轮，每轮 8 个，10 分钟内响应，失败基础罚没 1 UNIT
+	type Randomness = CollectiveFlipRandomness;
+	type ChallengeChunkSize = ConstU32<1024>;
+	type ChallengeInterval = ConstU32<HOURS>;
+	type ChallengeResponseWindow = ConstU32<{ 10 * MINUTES }>;
+	type ChallengesPerRound = ConstU32<8>;
+	type ChallengeSlashAmount = ConstU128<UNIT>;
+	// 提交 Merkle 根后指派运营者须在 1 天内背书或拒绝
+	type AttestationWindow = ConstU32<DAYS>;
+	// OCW Pin 状态上报：运营者在 keystore 中插入 `ipfs` 类型 sr25519 密钥
+	type AuthorityId = pallet_storage_service::sr25519_app::IpfsAuthId;
 }
 
 // -------------------- Evidence (证据存证) --------------------
@@ -644,6 +690,28 @@ impl pallet_arbitration::pallet::ArbitrationRouter<AccountId, Balance> for Unifi
 			_ => None,
 		}
 	}
+
+	/// 获取纠纷双方 (Release 受益方, Refund 受益方)
+	fn get_parties(domain: [u8; 8], id: u64) -> Result<(AccountId, AccountId), sp_runtime::DispatchError> {
+		use pallet_arbitration::pallet::domains;
+		use sp_runtime::DispatchError;
+
+		match domain {
+			// Buy：托管做市商 NXS，Release → 买家(taker)，Refund → 做市商(maker)
+			d if d == domains::OTC_ORDER => {
+				let order = pallet_trading_p2p::BuyOrders::<Runtime>::get(id)
+					.ok_or(DispatchError::Other("OrderNotFound"))?;
+				Ok((order.taker, order.maker))
+			},
+			// Sell：托管用户 NXS，Release → 做市商，Refund → 用户
+			d if d == domains::SWAP => {
+				let sell = pallet_trading_p2p::SellOrders::<Runtime>::get(id)
+					.ok_or(DispatchError::Other("SellOrderNotFound"))?;
+				Ok((sell.maker, sell.user))
+			},
+			_ => Err(DispatchError::Other("PartiesUnknown")),
+		}
+	}
 }
 
 /// 信用分更新器实现
@@ -678,6 +746,19 @@ impl pallet_arbitration::pallet::Config for Runtime {
 	type CidLockManager = pallet_storage_service::Pallet<Runtime>;
 	// 🆕 信用分更新器
 	type CreditUpdater = TradingCreditUpdater;
+	type ArchiveCommitter = StorageLifecycle; // 投诉归档承诺
+	// 🆕 陪审团（去中心化仲裁）
+	type JurySeedDelay = ConstU32<{ 10 * MINUTES }>; // 锁定未来种子区块，防止申请方预知抽取结果
+	type MinJurorStake = ConstU128<{ 100 * UNIT }>; // 最低质押 100 NXS
+	type MaxJurorsPerDomain = ConstU32<500>;
+	type InitialPanelSize = ConstU32<3>; // 3 → 7 → 15 人，之后交由仲裁委员会
+	type MaxPanelSize = ConstU32<15>;
+	type CommitPeriod = ConstU32<{ 2 * DAYS }>;
+	type RevealPeriod = ConstU32<{ 1 * DAYS }>;
+	type AppealPeriod = ConstU32<{ 2 * DAYS }>;
+	type AppealBaseFee = ConstU128<{ 10 * UNIT }>; // 首次上诉 10 NXS，之后每轮翻倍
+	type JurorSlashBps = ConstU16<1000>; // 不一致/未揭示罚没 10%
+	type JurorUnbondingPeriod = ConstU32<{ 7 * DAYS }>;
 }
 
 // ============================================================================
@@ -873,6 +954,7 @@ impl pallet_storage_lifecycle::Config for Runtime {
 	type PurgeDelay = ConstU32<{ 180 * DAYS }>;     // L2后180天可清除
 	type EnablePurge = ConstBool<false>;             // 默认不启用清除
 	type MaxBatchSize = ConstU32<100>;               // 每次最多处理100条
+	type ContentRegistry = pallet_storage_service::Pallet<Runtime>; // 归档内容 Pin 登记
 }
 
 // ============================================================================
@@ -1041,6 +1123,8 @@ parameter_types! {
 	pub const GovernanceQuorumThreshold: u8 = 10;
 	/// 创建提案所需最低代币持有比例: 1%
 	pub const GovernanceMinProposalThreshold: u16 = 100;
+	/// 单个链上调用提案的权重上限: 区块的 10%
+	pub GovernanceMaxProposalCallWeight: Weight = Perbill::from_percent(10) * RuntimeBlockWeights::get().max_block;
 }
 
 /// 平台账户
@@ -1067,6 +1151,7 @@ impl pallet_entity_registry::Config for Runtime {
 	type MaxEntitiesPerUser = ConstU32<3>;
 	type ShopProvider = EntityShop;
 	type PlatformAccount = EntityPlatformAccount;
+	type EntityGovernanceOrigin = pallet_entity_common::EnsureEntityGovernance;
 }
 
 impl pallet_entity_shop::Config for Runtime {
@@ -1081,6 +1166,7 @@ impl pallet_entity_shop::Config for Runtime {
 	type MinOperatingBalance = ConstU128<{ UNIT / 10 }>;
 	type WarningThreshold = ConstU128<{ UNIT }>;
 	type CommissionFundGuard = crate::CommissionCore;
+	type EntityGovernanceOrigin = pallet_entity_common::EnsureEntityGovernance;
 }
 
 impl pallet_entity_service::Config for Runtime {
@@ -1094,6 +1180,7 @@ impl pallet_entity_service::Config for Runtime {
 	type ProductDepositUsdt = ConstU64<1_000_000>;  // 1 USDT
 	type MinProductDepositCos = ConstU128<{ UNIT / 100 }>;
 	type MaxProductDepositCos = ConstU128<{ 10 * UNIT }>;
+	type EntityGovernanceOrigin = pallet_entity_common::EnsureEntityGovernance;
 }
 
 impl pallet_entity_transaction::Config for Runtime {
@@ -1110,6 +1197,7 @@ impl pallet_entity_transaction::Config for Runtime {
 	type ConfirmTimeout = EntityConfirmTimeout;
 	type ServiceConfirmTimeout = ConstU32<{ 7 * 24 * 600 }>;  // 7 天
 	type CommissionHandler = OrderCommissionBridge;
+	type Promotions = EntityGovernance;
 	type MaxCidLength = ConstU32<64>;
 }
 
@@ -1137,6 +1225,9 @@ impl pallet_entity_token::Config for Runtime {
 	type MaxDividendRecipients = ConstU32<500>;
 	type KycProvider = TokenKycProvider;
 	type MemberProvider = TokenMemberProvider;
+	type EntityGovernanceOrigin = pallet_entity_common::EnsureEntityGovernance;
+	type MilestoneProvider = EntityTokenSale;
+	type MaxVestingTranches = ConstU32<20>;
 }
 
 // EntityTokenProvider 使用 EntityToken pallet 直接实现
@@ -1233,6 +1324,10 @@ impl pallet_entity_commission::MemberProvider<AccountId> for MemberProviderBridg
 		pallet_entity_member::Pallet::<Runtime>::custom_level_count(shop_id)
 	}
 
+	fn remove_upgrade_rule(shop_id: u64, rule_id: u32) -> sp_runtime::DispatchResult {
+		pallet_entity_member::Pallet::<Runtime>::governance_remove_upgrade_rule(shop_id, rule_id)
+	}
+
 	fn auto_register(shop_id: u64, account: &AccountId, referrer: Option<AccountId>) -> sp_runtime::DispatchResult {
 		pallet_entity_member::Pallet::<Runtime>::auto_register(shop_id, account, referrer)
 	}
@@ -1246,10 +1341,11 @@ impl pallet_entity_common::OrderCommissionHandler<AccountId, Balance> for OrderC
 		order_id: u64,
 		buyer: &AccountId,
 		order_amount: Balance,
+		platform_fee: Balance,
 	) -> Result<(), sp_runtime::DispatchError> {
-		// available_pool = order_amount，commission 内部会按 max_commission_rate 和 shop 余额封顶
+		// available_pool = order_amount，commission 内部会按 max_commission_rate 和来源可出资额度封顶
 		<pallet_commission_core::Pallet<Runtime> as pallet_commission_common::CommissionProvider<AccountId, Balance>>::process_commission(
-			shop_id, order_id, buyer, order_amount, order_amount,
+			shop_id, order_id, buyer, order_amount, order_amount, platform_fee,
 		)
 	}
 	fn on_order_cancelled(order_id: u64) -> Result<(), sp_runtime::DispatchError> {
@@ -1266,8 +1362,12 @@ impl pallet_entity_governance::Config for Runtime {
 	type EntityProvider = EntityRegistry;
 	type ShopProvider = EntityShop;
 	type TokenProvider = EntityTokenProvider;
+	type ProductProvider = EntityService;
 	type CommissionProvider = EntityCommissionProvider;
 	type MemberProvider = EntityMemberProvider;
+	type ReferralWriter = crate::CommissionReferral;
+	type LevelDiffWriter = crate::CommissionLevelDiff;
+	type SingleLineWriter = crate::CommissionSingleLine;
 	type VotingPeriod = GovernanceVotingPeriod;
 	type ExecutionDelay = GovernanceExecutionDelay;
 	type PassThreshold = GovernancePassThreshold;
@@ -1277,6 +1377,10 @@ impl pallet_entity_governance::Config for Runtime {
 	type MaxCidLength = ConstU32<64>;
 	type MaxActiveProposals = ConstU32<10>;
 	type MaxCommitteeSize = ConstU32<20>;
+	type MaxDelegators = ConstU32<100>;
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type MaxProposalCallWeight = GovernanceMaxProposalCallWeight;
 }
 
 impl pallet_entity_member::Config for Runtime {
@@ -1299,13 +1403,16 @@ impl pallet_commission_core::Config for Runtime {
 	type Currency = Balances;
 	type ShopProvider = EntityShop;
 	type EntityProvider = EntityRegistry;
+	type EntityGovernanceOrigin = pallet_entity_common::EnsureEntityGovernance;
 	type MemberProvider = EntityMemberProvider;
+	type PlatformAccount = EntityPlatformAccount;
 	type ReferralPlugin = crate::CommissionReferral;
 	type LevelDiffPlugin = crate::CommissionLevelDiff;
 	type SingleLinePlugin = crate::CommissionSingleLine;
-	type TeamPlugin = ();
+	type TeamPlugin = crate::CommissionTeam;
 	type ReferralWriter = crate::CommissionReferral;
 	type LevelDiffWriter = crate::CommissionLevelDiff;
+	type TeamWriter = crate::CommissionTeam;
 	type MaxCommissionRecordsPerOrder = ConstU32<20>;
 	type MaxCustomLevels = ConstU32<10>;
 }
@@ -1342,6 +1449,18 @@ impl pallet_commission_single_line::Config for Runtime {
 	type MaxSingleLineLength = ConstU32<50>;
 }
 
+impl pallet_commission_team::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type MemberProvider = EntityMemberProvider;
+	type EntityProvider = EntityRegistry;
+	type EntityGovernanceOrigin = pallet_entity_common::EnsureEntityGovernance;
+	type BonusPayer = crate::CommissionCore;
+	type MaxTeamTiers = ConstU32<10>;
+	type MaxTeamDepth = ConstU32<30>;
+	type WeightInfo = pallet_commission_team::weights::SubstrateWeight<Runtime>;
+}
+
 parameter_types! {
 	pub MarketTreasuryAccount: AccountId = AccountId::from([0u8; 32]);
 }
@@ -1369,6 +1488,8 @@ impl pallet_entity_market::Config for Runtime {
 	type DepositForfeitRate = ConstU16<5000>;  // 50%
 	type UsdtToNxsRate = ConstU64<100_000>;  // 1 USDT = 0.1 NXS
 	type TreasuryAccount = MarketTreasuryAccount;
+	type PaymentRails = TradingP2p;  // 共享 P2P 支付通道注册表
+	type EntityGovernanceOrigin = pallet_entity_common::EnsureEntityGovernance;
 }
 
 // ============================================================================
@@ -1396,6 +1517,7 @@ impl pallet_entity_disclosure::Config for Runtime {
 	type StandardDisclosureInterval = StandardDisclosureInterval;
 	type EnhancedDisclosureInterval = EnhancedDisclosureInterval;
 	type MajorHolderThreshold = ConstU16<500>; // 5%
+	type MilestoneProvider = EntityTokenSale;
 }
 
 impl pallet_entity_kyc::Config for Runtime {
@@ -1435,6 +1557,10 @@ impl pallet_entity_tokensale::Config for Runtime {
 	type MaxWhitelistSize = ConstU32<1000>;
 	type MaxRoundsHistory = ConstU32<50>;
 	type MaxSubscriptionsPerRound = ConstU32<10000>;
+	type MaxLotteryDrawsPerCall = ConstU32<200>;
+	type LotterySeedDelay = ConstU32<{ 10 * MINUTES }>;
+	type MaxVestingTranches = ConstU32<20>;
+	type EntityGovernanceOrigin = pallet_entity_common::EnsureEntityGovernance;
 }
 
 // ============================================================================
@@ -1446,6 +1572,8 @@ parameter_types! {
 	pub const BotConsensusMinStake: Balance = 100 * UNIT;
 	/// 节点退出冷却期: ~1 天 (14400 blocks @ 6s/block)
 	pub const BotConsensusExitCooldown: BlockNumber = DAYS;
+	/// Equivocation 举报押金: 10 COS（证据无效时罚没）
+	pub const BotEquivocationReportDeposit: Balance = 10 * UNIT;
 	/// Era 长度: 1 天
 	pub const BotEraLength: BlockNumber = DAYS;
 	/// 每 Era 通胀铸币: 100 NXS (Phase 0)
@@ -1475,6 +1603,18 @@ impl pallet_bot_consensus::BotRegistryProvider<AccountId> for BotRegistryBridge
 			.map(|owner| &owner == who)
 			.unwrap_or(false)
 	}
+
+	fn bot_owner(bot_id_hash: &[u8; 32]) -> Option<AccountId> {
+		pallet_bot_registry::Pallet::<Runtime>::get_bot_owner(bot_id_hash)
+	}
+
+	fn bot_public_key(bot_id_hash: &[u8; 32]) -> Option<[u8; 32]> {
+		pallet_bot_registry::Pallet::<Runtime>::get_bot_public_key(bot_id_hash)
+	}
+
+	fn suspend_bot(bot_id_hash: &[u8; 32]) {
+		pallet_bot_registry::Pallet::<Runtime>::suspend_for_misbehavior(bot_id_hash)
+	}
 }
 
 impl pallet_bot_consensus::Config for Runtime {
@@ -1484,6 +1624,7 @@ impl pallet_bot_consensus::Config for Runtime {
 	type MaxNodes = ConstU32<100>;
 	type SlashPercentage = ConstU32<10>;
 	type ReporterRewardPercentage = ConstU32<50>;
+	type EquivocationReportDeposit = BotEquivocationReportDeposit;
 	type SuspendThreshold = ConstU16<2000>;
 	type ForceExitThreshold = ConstU16<1000>;
 	type EraLength = BotEraLength;
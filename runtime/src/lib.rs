@@ -172,7 +172,13 @@ pub type SignedPayload = generic::SignedPayload<RuntimeCall, TxExtension>;
 ///
 /// This can be a tuple of types, each implementing `OnRuntimeUpgrade`.
 #[allow(unused_parens)]
-pub type Migrations = ();
+pub type Migrations = (
+	pallet_commission_core::migrations::v1::MigrateV0ToV1<Runtime>,
+	pallet_bot_consensus::migrations::v1::MigrateV0ToV1<Runtime>,
+	pallet_trading_p2p::migrations::v1::MigrateV0ToV1<Runtime>,
+	pallet_entity_market::migrations::v1::MigrateV0ToV1<Runtime>,
+	pallet_entity_governance::migrations::v1::MigrateV0ToV1<Runtime>,
+);
 
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
@@ -181,6 +187,7 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -343,6 +350,9 @@ mod runtime {
 	#[runtime::pallet_index(135)]
 	pub type CommissionSingleLine = pallet_commission_single_line;
 
+	#[runtime::pallet_index(136)]
+	pub type CommissionTeam = pallet_commission_team;
+
 	#[runtime::pallet_index(128)]
 	pub type EntityMarket = pallet_entity_market;
 